@@ -34,7 +34,7 @@ export default function DataSettingsPage() {
 
   const [restoreLoading, setRestoreLoading] = useState(false);
   const [restoreResult, setRestoreResult] = useState<
-    { ok: true } | { ok: false; message: string } | null
+    { ok: true; keyringRestored: boolean } | { ok: false; message: string } | null
   >(null);
 
   // Pending restore — path chosen in dialog, waiting for confirmation.
@@ -103,8 +103,13 @@ export default function DataSettingsPage() {
     setRestoreResult(null);
 
     try {
-      await settingsIpc.restoreDataBackup(pendingRestorePath);
-      setRestoreResult({ ok: true });
+      const result = await settingsIpc.restoreDataBackup(pendingRestorePath);
+      const keyringRestored =
+        typeof result === 'object' &&
+        result !== null &&
+        !Array.isArray(result) &&
+        result.keyring_restored === true;
+      setRestoreResult({ ok: true, keyringRestored });
     } catch (err) {
       const msg = err instanceof Error ? err.message : 'Erreur lors de la restauration.';
       setRestoreResult({ ok: false, message: msg });
@@ -140,7 +145,10 @@ export default function DataSettingsPage() {
           </CardTitle>
           <CardDescription>
             Crée une copie complète de la base de données locale. Utilisez cette sauvegarde
-            pour archiver vos données ou les transférer vers un autre appareil.
+            pour archiver vos données ou les transférer vers un autre appareil. La clé des
+            identifiants chiffrés est enregistrée à côté, dans un fichier{' '}
+            <span className="font-mono text-xs">.keyring</span> : conservez les deux fichiers
+            ensemble.
           </CardDescription>
         </CardHeader>
         <CardContent className="space-y-3">
@@ -202,6 +210,13 @@ export default function DataSettingsPage() {
                 <strong>Sauvegarde préparée.</strong> Quittez et redémarrez l&apos;application
                 pour finaliser la restauration. Les données actuelles seront remplacées au
                 prochain démarrage.
+                {!restoreResult.keyringRestored && (
+                  <>
+                    {' '}Aucun fichier <span className="font-mono text-xs">.keyring</span>{' '}
+                    n&apos;accompagne cette sauvegarde : les identifiants enregistrés (SMTP,
+                    SMS, stockage cloud, intégrations) devront peut-être être saisis à nouveau.
+                  </>
+                )}
               </AlertDescription>
             </Alert>
           )}
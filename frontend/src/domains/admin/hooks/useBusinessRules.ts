@@ -163,6 +163,26 @@ function backendToActions(actions: BackendRuleAction[]): RuleAction[] {
       };
     }
 
+    if (action.type === "set_task_tag") {
+      return { type: "set_field", target: "tags", value: action.tag };
+    }
+
+    if (action.type === "send_message") {
+      return {
+        type: "send_notification",
+        target: action.channel,
+        value: action.body,
+      };
+    }
+
+    if (action.type === "create_notification") {
+      return {
+        type: "send_notification",
+        target: "in_app",
+        value: action.message,
+      };
+    }
+
     return {
       type: "send_notification",
       target: action.event_name,
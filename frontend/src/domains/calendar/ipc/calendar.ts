@@ -6,6 +6,16 @@ import type {
   CalendarFilter,
   ConflictDetection,
   CalendarDateRange,
+  CapacityHeatmap,
+  CreateAbsenceInput,
+  IcsImportSummary,
+  PublicHoliday,
+  RecurrenceScope,
+  ScheduleProposal,
+  SlotSearch,
+  SlotSuggestions,
+  TechnicianAbsence,
+  TechnicianAvailability,
 } from "@/lib/backend";
 import type { CreateEventInput, UpdateEventInput } from "@/lib/ipc/types/index";
 import type { JsonObject, JsonValue } from "@/types/json";
@@ -167,18 +177,31 @@ export const calendarEvents = {
   updateEvent: async (
     id: string,
     eventData: UpdateEventInput,
+    occurrence?: { recurrenceId: string; scope: RecurrenceScope },
   ): Promise<JsonValue> => {
     const result = await safeInvoke<JsonValue>(IPC_COMMANDS.UPDATE_EVENT, {
-      request: { id, event_data: eventData },
+      request: {
+        id,
+        event_data: eventData,
+        recurrence_id: occurrence?.recurrenceId,
+        scope: occurrence?.scope,
+      },
     });
     invalidatePattern("calendar:");
     signalMutation("calendar");
     return result;
   },
 
-  deleteEvent: async (id: string): Promise<JsonValue> => {
+  deleteEvent: async (
+    id: string,
+    occurrence?: { recurrenceId: string; scope: RecurrenceScope },
+  ): Promise<JsonValue> => {
     const result = await safeInvoke<JsonValue>(IPC_COMMANDS.DELETE_EVENT, {
-      request: { id },
+      request: {
+        id,
+        recurrence_id: occurrence?.recurrenceId,
+        scope: occurrence?.scope,
+      },
     });
     invalidatePattern("calendar:");
     signalMutation("calendar");
@@ -196,4 +219,182 @@ export const calendarEvents = {
       request: { task_id: taskId },
     });
   },
+
+  /** iCalendar file of a date range, optionally for one technician. */
+  exportIcs: async (
+    startDate: string,
+    endDate: string,
+    technicianId?: string,
+  ): Promise<string> => {
+    return safeInvoke<string>(IPC_COMMANDS.CALENDAR_EXPORT_ICS, {
+      request: compactJsonObject({
+        start_date: startDate,
+        end_date: endDate,
+        technician_id: technicianId,
+      }),
+    });
+  },
+
+  /** iCalendar invitation for one appointment, with its reminders. */
+  exportEventInvite: async (id: string): Promise<string> => {
+    return safeInvoke<string>(IPC_COMMANDS.CALENDAR_EXPORT_EVENT_INVITE, {
+      request: { id },
+    });
+  },
+
+  importIcs: async (
+    icsData: string,
+    options?: { updateExisting?: boolean; technicianId?: string },
+  ): Promise<IcsImportSummary> => {
+    const result = await safeInvoke<IcsImportSummary>(
+      IPC_COMMANDS.CALENDAR_IMPORT_ICS,
+      {
+        request: compactJsonObject({
+          ics_data: icsData,
+          update_existing: options?.updateExisting ?? false,
+          technician_id: options?.technicianId,
+        }),
+      },
+    );
+    invalidatePattern("calendar:");
+    signalMutation("calendar");
+    return result;
+  },
+
+  /** Path of the read-only `.ics` feed kept in the app data directory. */
+  getFeedPath: async (): Promise<string> => {
+    return safeInvoke<string>(IPC_COMMANDS.CALENDAR_GET_FEED_PATH, {});
+  },
+
+  getTechnicianAvailability: async (
+    technicianId: string,
+  ): Promise<TechnicianAvailability> => {
+    return safeInvoke<TechnicianAvailability>(
+      IPC_COMMANDS.CALENDAR_GET_TECHNICIAN_AVAILABILITY,
+      { request: { technician_id: technicianId } },
+    );
+  },
+
+  /** Working time, daily capacity and skills; unset ones follow the shop. */
+  setTechnicianAvailability: async (
+    availability: TechnicianAvailability,
+  ): Promise<TechnicianAvailability> => {
+    const result = await safeInvoke<TechnicianAvailability>(
+      IPC_COMMANDS.CALENDAR_SET_TECHNICIAN_AVAILABILITY,
+      { request: { availability } },
+    );
+    invalidatePattern("calendar:");
+    signalMutation("calendar");
+    return result;
+  },
+
+  listAbsences: async (
+    startDate: string,
+    endDate: string,
+    technicianId?: string,
+  ): Promise<TechnicianAbsence[]> => {
+    return safeInvoke<TechnicianAbsence[]>(
+      IPC_COMMANDS.CALENDAR_LIST_ABSENCES,
+      {
+        request: compactJsonObject({
+          start_date: startDate,
+          end_date: endDate,
+          technician_id: technicianId,
+        }),
+      },
+    );
+  },
+
+  addAbsence: async (
+    absence: CreateAbsenceInput,
+  ): Promise<TechnicianAbsence> => {
+    const result = await safeInvoke<TechnicianAbsence>(
+      IPC_COMMANDS.CALENDAR_ADD_ABSENCE,
+      { request: { absence } },
+    );
+    invalidatePattern("calendar:");
+    signalMutation("calendar");
+    return result;
+  },
+
+  deleteAbsence: async (id: string): Promise<boolean> => {
+    const result = await safeInvoke<boolean>(
+      IPC_COMMANDS.CALENDAR_DELETE_ABSENCE,
+      { request: { id } },
+    );
+    invalidatePattern("calendar:");
+    signalMutation("calendar");
+    return result;
+  },
+
+  listPublicHolidays: async (
+    startDate: string,
+    endDate: string,
+  ): Promise<PublicHoliday[]> => {
+    return safeInvoke<PublicHoliday[]>(
+      IPC_COMMANDS.CALENDAR_LIST_PUBLIC_HOLIDAYS,
+      { request: { start_date: startDate, end_date: endDate } },
+    );
+  },
+
+  /** Adds the holiday, or renames it when the date already is one. */
+  setPublicHoliday: async (holiday: PublicHoliday): Promise<PublicHoliday> => {
+    const result = await safeInvoke<PublicHoliday>(
+      IPC_COMMANDS.CALENDAR_SET_PUBLIC_HOLIDAY,
+      { request: { holiday } },
+    );
+    invalidatePattern("calendar:");
+    signalMutation("calendar");
+    return result;
+  },
+
+  deletePublicHoliday: async (date: string): Promise<boolean> => {
+    const result = await safeInvoke<boolean>(
+      IPC_COMMANDS.CALENDAR_DELETE_PUBLIC_HOLIDAY,
+      { request: { date } },
+    );
+    invalidatePattern("calendar:");
+    signalMutation("calendar");
+    return result;
+  },
+
+  /** Booked time against capacity, Monday to Sunday of `weekStart`'s week. */
+  getCapacityHeatmap: async (
+    weekStart: string,
+    technicianIds?: string[],
+  ): Promise<CapacityHeatmap> => {
+    return safeInvoke<CapacityHeatmap>(
+      IPC_COMMANDS.CALENDAR_GET_CAPACITY_HEATMAP,
+      {
+        request: compactJsonObject({
+          week_start: weekStart,
+          technician_ids: technicianIds,
+        }),
+      },
+    );
+  },
+
+  suggestSlots: async (search: SlotSearch): Promise<SlotSuggestions> => {
+    return safeInvoke<SlotSuggestions>(IPC_COMMANDS.CALENDAR_SUGGEST_SLOTS, {
+      request: { search },
+    });
+  },
+
+  // Read-only: proposals are booked through scheduleTask.
+  proposeSchedule: async (
+    startDate: string,
+    endDate: string,
+    technicianIds?: string[],
+  ): Promise<ScheduleProposal> => {
+    return safeInvoke<ScheduleProposal>(
+      IPC_COMMANDS.CALENDAR_PROPOSE_SCHEDULE,
+      {
+        request: compactJsonObject({
+          start_date: startDate,
+          end_date: endDate,
+          technician_ids: technicianIds,
+        }),
+      },
+    );
+  },
 };
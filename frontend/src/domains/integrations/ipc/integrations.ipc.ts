@@ -2,8 +2,18 @@ import { safeInvoke } from "@/lib/ipc/core";
 import { IPC_COMMANDS } from "@/lib/ipc/commands";
 import type { JsonObject } from "@/types/json";
 import type {
+  BackendDeliveryAttempt,
+  BackendDeliveryLogEntry,
+  BackendDeliveryLogPage,
+  BackendInboundRequestLogPage,
   BackendIntegrationConfig,
+  BackendPreviewPayloadResponse,
+  BackendSigningSecretResponse,
   CreateBackendIntegrationRequest,
+  ListBackendDeliveriesRequest,
+  ListBackendInboundRequestsRequest,
+  PreviewBackendPayloadRequest,
+  RotateBackendSigningSecretRequest,
   TestBackendIntegrationResponse,
   UpdateBackendIntegrationRequest,
 } from "@/shared/types";
@@ -36,4 +46,49 @@ export const integrationsIpc = {
 
   retryDeadLetters: (id: string) =>
     safeInvoke<number>(IPC_COMMANDS.INTEGRATION_RETRY_DEAD_LETTER, { id }),
+
+  revealSigningSecret: (id: string) =>
+    safeInvoke<BackendSigningSecretResponse>(
+      IPC_COMMANDS.INTEGRATION_REVEAL_SIGNING_SECRET,
+      { id },
+    ),
+
+  rotateSigningSecret: (id: string, request: RotateBackendSigningSecretRequest) =>
+    safeInvoke<BackendSigningSecretResponse>(
+      IPC_COMMANDS.INTEGRATION_ROTATE_SIGNING_SECRET,
+      { id, request: request as unknown as JsonObject },
+    ),
+
+  listDeliveries: (id: string, request: ListBackendDeliveriesRequest = {}) =>
+    safeInvoke<BackendDeliveryLogPage>(IPC_COMMANDS.INTEGRATION_LIST_DELIVERIES, {
+      id,
+      request: request as unknown as JsonObject,
+    }),
+
+  listDeliveryAttempts: (deliveryId: string) =>
+    safeInvoke<BackendDeliveryAttempt[]>(
+      IPC_COMMANDS.INTEGRATION_LIST_DELIVERY_ATTEMPTS,
+      { delivery_id: deliveryId },
+    ),
+
+  replayDelivery: (deliveryId: string) =>
+    safeInvoke<BackendDeliveryLogEntry>(
+      IPC_COMMANDS.INTEGRATION_REPLAY_DELIVERY,
+      { delivery_id: deliveryId },
+    ),
+
+  previewPayload: (request: PreviewBackendPayloadRequest) =>
+    safeInvoke<BackendPreviewPayloadResponse>(
+      IPC_COMMANDS.INTEGRATION_PREVIEW_PAYLOAD,
+      { request: request as unknown as JsonObject },
+    ),
+
+  listInboundRequests: (
+    id: string,
+    request: ListBackendInboundRequestsRequest = {},
+  ) =>
+    safeInvoke<BackendInboundRequestLogPage>(
+      IPC_COMMANDS.INTEGRATION_LIST_INBOUND_REQUESTS,
+      { id, request: request as unknown as JsonObject },
+    ),
 } as const;
@@ -2,8 +2,11 @@ import { safeInvoke } from "@/lib/ipc/core";
 import { IPC_COMMANDS } from "@/lib/ipc/commands";
 import type { JsonObject } from "@/types/json";
 import type {
+  BacktestBackendRuleRequest,
+  BackendRuleBacktestReport,
   BackendRuleDefinition,
   BackendRuleEvaluationResult,
+  BackendRuleTriggerSchema,
   CreateBackendRuleRequest,
   TestBackendRuleRequest,
   UpdateBackendRuleRequest,
@@ -40,4 +43,15 @@ export const rulesIpc = {
     safeInvoke<BackendRuleEvaluationResult>(IPC_COMMANDS.RULE_TEST, {
       request: request as unknown as JsonObject,
     }),
+
+  backtest: (request: BacktestBackendRuleRequest) =>
+    safeInvoke<BackendRuleBacktestReport>(IPC_COMMANDS.RULE_BACKTEST, {
+      request: request as unknown as JsonObject,
+    }),
+
+  triggerSchemas: () =>
+    safeInvoke<BackendRuleTriggerSchema[]>(
+      IPC_COMMANDS.RULE_TRIGGER_SCHEMAS,
+      {},
+    ),
 } as const;
@@ -162,4 +162,7 @@ export const settingsIpc = {
     safeInvoke<JsonValue>(IPC_COMMANDS.RESTORE_DATA_BACKUP, {
       source_path: sourcePath,
     }),
+
+  rotateSecretsKey: () =>
+    safeInvoke<JsonValue>(IPC_COMMANDS.ROTATE_SECRETS_KEY, {}),
 };
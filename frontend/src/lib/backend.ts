@@ -143,17 +143,24 @@ export type EventStatus = "confirmed" | "tentative" | "cancelled";
 
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 
+/**
+ * Which part of a recurring series an edit or delete applies to.
+ */
+export type RecurrenceScope = "this" | "this_and_following" | "all";
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
 /**
  * Input for creating a calendar event.
  */
-export type CreateEventInput = { title: string, description: string | null, startDatetime: string, endDatetime: string, allDay: boolean | null, timezone: string | null, eventType: EventType | null, category: string | null, taskId: string | null, clientId: string | null, technicianId: string | null, location: string | null, meetingLink: string | null, isVirtual: boolean | null, participants: Array<EventParticipant> | null, reminders: Array<number> | null, color: string | null, tags: Array<string> | null, notes: string | null, };
+export type CreateEventInput = { title: string, description: string | null, startDatetime: string, endDatetime: string, allDay: boolean | null, timezone: string | null, eventType: EventType | null, category: string | null, taskId: string | null, clientId: string | null, technicianId: string | null, location: string | null, meetingLink: string | null, isVirtual: boolean | null, participants: Array<EventParticipant> | null, reminders: Array<number> | null, color: string | null, tags: Array<string> | null, notes: string | null, recurrenceRule: string | null, };
 
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 
 /**
  * Input for updating a calendar event.
  */
-export type UpdateEventInput = { title: string | null, description: string | null, startDatetime: string | null, endDatetime: string | null, allDay: boolean | null, timezone: string | null, eventType: EventType | null, category: string | null, taskId: string | null, clientId: string | null, location: string | null, meetingLink: string | null, isVirtual: boolean | null, participants: Array<EventParticipant> | null, status: EventStatus | null, reminders: Array<number> | null, color: string | null, tags: Array<string> | null, notes: string | null, };
+export type UpdateEventInput = { title: string | null, description: string | null, startDatetime: string | null, endDatetime: string | null, allDay: boolean | null, timezone: string | null, eventType: EventType | null, category: string | null, taskId: string | null, clientId: string | null, location: string | null, meetingLink: string | null, isVirtual: boolean | null, participants: Array<EventParticipant> | null, status: EventStatus | null, reminders: Array<number> | null, color: string | null, tags: Array<string> | null, notes: string | null, recurrenceRule: string | null, };
 
 
 
@@ -163,7 +170,7 @@ export type UpdateEventInput = { title: string | null, description: string | nul
 /**
  * Calendar event entity.
  */
-export type CalendarEvent = { id: string, title: string, description: string | null, startDatetime: string, endDatetime: string, allDay: boolean, timezone: string, eventType: EventType, category: string | null, taskId: string | null, clientId: string | null, technicianId: string | null, location: string | null, meetingLink: string | null, isVirtual: boolean, participants: Array<EventParticipant>, isRecurring: boolean, recurrenceRule: string | null, parentEventId: string | null, reminders: Array<number>, status: EventStatus, color: string | null, tags: Array<string>, notes: string | null, synced: boolean, lastSyncedAt: bigint | null, createdAt: bigint, updatedAt: bigint, createdBy: string | null, updatedBy: string | null, deletedAt: bigint | null, deletedBy: string | null, };
+export type CalendarEvent = { id: string, title: string, description: string | null, startDatetime: string, endDatetime: string, allDay: boolean, timezone: string, eventType: EventType, category: string | null, taskId: string | null, clientId: string | null, technicianId: string | null, location: string | null, meetingLink: string | null, isVirtual: boolean, participants: Array<EventParticipant>, isRecurring: boolean, recurrenceRule: string | null, parentEventId: string | null, reminders: Array<number>, status: EventStatus, color: string | null, tags: Array<string>, notes: string | null, synced: boolean, lastSyncedAt: bigint | null, createdAt: bigint, updatedAt: bigint, createdBy: string | null, updatedBy: string | null, deletedAt: bigint | null, deletedBy: string | null, icalUid: string | null, recurrenceId?: string, };
 
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 
@@ -201,7 +208,12 @@ export type CalendarFilter = { date_range: CalendarDateRange, technician_ids: Ar
 /**
  * Conflict detection result.
  */
-export type ConflictDetection = { has_conflict: boolean, conflict_type: string | null, conflicting_tasks: Array<CalendarTask>, message: string | null, };
+export type ConflictDetection = { has_conflict: boolean, conflict_type: string | null, conflicting_tasks: Array<CalendarTask>, message: string | null, 
+/**
+ * Availability problems that do not block the booking: outside working
+ * time or over capacity, when the organization only warns about them.
+ */
+warnings: Array<string>, };
 
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 
@@ -210,6 +222,165 @@ export type ConflictDetection = { has_conflict: boolean, conflict_type: string |
  */
 export type CalendarDateRange = { start_date: string, end_date: string, };
 
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Outcome of an iCalendar import.
+ */
+export type IcsImportSummary = { total_processed: number, created: number, updated: number, 
+/**
+ * Events already imported, left as they were.
+ */
+duplicates_skipped: number, failed: number, errors: Array<string>, };
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A technician's own working time, overriding the organization's business
+ * hours and days. Unset fields follow the organization.
+ */
+export type TechnicianAvailability = { technician_id: string, 
+/**
+ * `HH:MM`.
+ */
+work_start: string | null, work_end: string | null, 
+/**
+ * ISO weekdays, `"1"` (Monday) to `"7"` (Sunday).
+ */
+working_days: Array<string> | null, 
+/**
+ * Bookable minutes per working day; the whole working day when unset.
+ */
+daily_capacity_minutes: number | null, 
+/**
+ * Intervention types the technician carries out (`ppf`, `ceramic`, …).
+ * Empty means any.
+ */
+skills: Array<string>, };
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Days a technician is away, `start_date` to `end_date` inclusive.
+ */
+export type TechnicianAbsence = { id: string, technician_id: string, start_date: string, end_date: string, 
+/**
+ * `leave`, `sick`, `training` or `other`.
+ */
+kind: string, note: string | null, created_at: number, created_by: string | null, };
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type CreateAbsenceInput = { technician_id: string, start_date: string, end_date: string, kind: string | null, note: string | null, };
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A day nobody works.
+ */
+export type PublicHoliday = { date: string, name: string, };
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Whether a technician can be booked on a day.
+ */
+export type DayStatus = "working" | "day_off" | "holiday" | "absent";
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * One cell of the capacity heat map.
+ */
+export type DayCapacity = { date: string, status: DayStatus, capacity_minutes: number, booked_minutes: number, 
+/**
+ * Booked share of the capacity; `None` when there is no capacity.
+ */
+load_percent: number | null, };
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type TechnicianWeekCapacity = { technician_id: string, technician_name: string, days: Array<DayCapacity>, capacity_minutes: number, booked_minutes: number, };
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Booked time against capacity, per technician and day, Monday to Sunday.
+ */
+export type CapacityHeatmap = { week_start: string, week_end: string, technicians: Array<TechnicianWeekCapacity>, };
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * What to find slots for: a task, or a duration and intervention type.
+ */
+export type SlotSearch = { 
+/**
+ * Task to book; its duration and intervention type are used.
+ */
+task_id: string | null, 
+/**
+ * Minutes, when not searching for a task.
+ */
+duration_minutes: number | null, 
+/**
+ * `ppf`, `ceramic`, `detailing` or `other`; any technician when unset.
+ */
+intervention_type: string | null, start_date: string, end_date: string, 
+/**
+ * Technicians to search; all active technicians when unset.
+ */
+technician_ids: Array<string> | null, 
+/**
+ * Slots per technician, 5 by default.
+ */
+limit: number | null, };
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A free slot, scored 0–100.
+ */
+export type SlotCandidate = { date: string, start_time: string, end_time: string, score: number, 
+/**
+ * Minutes booked that day once the slot is taken.
+ */
+booked_minutes: number, capacity_minutes: number, };
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type TechnicianSlots = { technician_id: string, technician_name: string, 
+/**
+ * Best first.
+ */
+slots: Array<SlotCandidate>, };
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Free slots per technician, best technician first.
+ */
+export type SlotSuggestions = { duration_minutes: number, intervention_type: string | null, technicians: Array<TechnicianSlots>, };
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A slot proposed for an unscheduled task.
+ */
+export type ProposedBooking = { task_id: string, task_number: string, title: string, technician_id: string, technician_name: string, date: string, start_time: string, end_time: string, duration_minutes: number, score: number, };
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type UnplacedTask = { task_id: string, task_number: string, title: string, reason: string, };
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Slots proposed for every unscheduled task, most urgent placed first.
+ * Nothing is booked until the proposals are scheduled.
+ */
+export type ScheduleProposal = { start_date: string, end_date: string, proposals: Array<ProposedBooking>, unplaced: Array<UnplacedTask>, };
+
 
 // @domain:clients
 // Client types
@@ -857,6 +1028,353 @@ export type UpdateQuoteAttachmentRequest = { description: string | null, attachm
 export type ConvertQuoteToTaskResponse = { quote: Quote, task_id: string, task_number: string, };
 
 
+// Quote revision types
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Frozen snapshot of a sent quote.
+ */
+export type QuoteRevision = { id: string, quote_id: string, 
+/**
+ * 1-based, assigned in send order.
+ */
+revision: number, valid_until: string | null, description: string | null, terms: string | null, discount_type: string | null, discount_value: bigint | null, discount_amount: bigint | null, subtotal: bigint, tax_total: bigint, total: bigint, created_at: string, created_by: string | null, 
+/**
+ * Lines as sent. `id` is the id of the originating quote item.
+ */
+items: Array<QuoteItem>, };
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A line present in both revisions whose content changed.
+ */
+export type QuoteItemChange = { item_id: string, before: QuoteItem, after: QuoteItem, 
+/**
+ * Names of the fields that differ (e.g. `"qty"`, `"unit_price"`).
+ */
+fields: Array<string>, };
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Differences between two revisions of the same quote.
+ */
+export type QuoteRevisionDiff = { quote_id: string, from_revision: number, to_revision: number, added: Array<QuoteItem>, removed: Array<QuoteItem>, changed: Array<QuoteItemChange>, 
+/**
+ * Header fields that differ (`"terms"`, `"valid_until"`, `"discount"`, ...).
+ */
+changed_fields: Array<string>, subtotal_delta: bigint, tax_total_delta: bigint, total_delta: bigint, discount_amount_delta: bigint, };
+
+
+// Quote acceptance types
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Recorded acceptance. Immutable once written.
+ */
+export type QuoteAcceptance = { id: string, quote_id: string, revision_id: string, revision: number, signer_name: string, 
+/**
+ * Base64-encoded PNG or JPEG, without data URL prefix.
+ */
+signature: string, 
+/**
+ * SHA-256 (hex) of the accepted revision's canonical payload.
+ */
+payload_hash: string, signed_at: string, 
+/**
+ * Staff member whose session captured the signature.
+ */
+recorded_by: string | null, };
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Customer signature captured for a sent quote.
+ */
+export type SignQuoteAcceptanceRequest = { 
+/**
+ * Revision number shown to the customer when signing. Must be the
+ * latest sent revision.
+ */
+revision: number, signer_name: string, 
+/**
+ * Base64-encoded PNG or JPEG, without data URL prefix.
+ */
+signature: string, };
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Stored acceptance plus a fresh check of its hash against the revision.
+ */
+export type QuoteAcceptanceVerification = { acceptance: QuoteAcceptance, 
+/**
+ * Hash recomputed from the stored revision now.
+ */
+current_hash: string, 
+/**
+ * `false` means the stored revision no longer matches what was signed.
+ */
+hash_matches: boolean, };
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Response for a signed acceptance.
+ */
+export type QuoteSignedAcceptResponse = { quote: Quote, acceptance: QuoteAcceptance, };
+
+
+// @domain:invoices
+// Invoice types
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type InvoiceKind = "invoice" | "credit_note";
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type InvoiceStatus = "draft" | "issued" | "partially_paid" | "paid" | "cancelled";
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type InvoiceLineKind = "labor" | "material" | "service" | "discount";
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type InvoiceLine = { id: string, invoice_id: string, kind: InvoiceLineKind, label: string, description: string | null, qty: number, unit_price: bigint, 
+/**
+ * VAT rate in percent; `0.0` for exempt lines.
+ */
+tax_rate: number, line_total: bigint, material_id: string | null, position: number, created_at: bigint, };
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * One row of the per-rate VAT summary printed at the bottom of an invoice.
+ */
+export type VatBreakdownLine = { tax_rate: number, 
+/**
+ * Net amount at this rate, after its share of the document discount.
+ */
+taxable_amount: bigint, tax_amount: bigint, };
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type Invoice = { id: string, 
+/**
+ * Assigned when the invoice is issued; `None` while in draft.
+ */
+invoice_number: string | null, kind: InvoiceKind, status: InvoiceStatus, client_id: string, client_name: string | null, client_address: string | null, 
+/**
+ * Buyer VAT number and structured address, frozen with the name for
+ * e-invoice exports.
+ */
+client_tax_id: string | null, client_street: string | null, client_postcode: string | null, client_city: string | null, client_country: string | null, task_id: string | null, quote_id: string | null, intervention_id: string | null, 
+/**
+ * For credit notes: the invoice being credited.
+ */
+credited_invoice_id: string | null, issue_date: bigint | null, due_date: bigint | null, currency: string, notes: string | null, terms: string | null, 
+/**
+ * Net total, after `discount_amount` (same convention as quotes).
+ */
+subtotal: bigint, discount_amount: bigint, tax_total: bigint, total: bigint, amount_paid: bigint, created_by: string | null, issued_at: bigint | null, created_at: bigint, updated_at: bigint, lines: Array<InvoiceLine>, vat_breakdown: Array<VatBreakdownLine>, };
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Invoice defaults stored as JSON in `organizations.invoice_settings`.
+ * Numbering prefix and payment terms come from the organization key-value
+ * settings (`invoice_prefix`, `payment_terms`).
+ */
+export type InvoiceBillingSettings = { credit_note_prefix: string, 
+/**
+ * VAT rate applied to generated lines and to lines entered without one.
+ */
+default_tax_rate: number, 
+/**
+ * Hourly labour rate in cents, used when invoicing an intervention.
+ */
+labor_hourly_rate: bigint, 
+/**
+ * Markup applied to consumed material cost, in percent.
+ */
+material_markup_percent: number, 
+/**
+ * Printed under the totals (late-payment penalties, recovery fee…).
+ */
+legal_mentions: string | null, 
+/**
+ * Share of an accepted quote, in percent, to collect as a deposit before
+ * it can be converted into a task. `0` disables the check.
+ */
+deposit_percent: number, 
+/**
+ * Legal reason printed in e-invoices for 0 % VAT lines, e.g.
+ * `TVA non applicable, art. 293 B du CGI`. Required to issue such lines.
+ */
+vat_exemption_reason: string | null, };
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type InvoiceLineInput = { kind: InvoiceLineKind, label: string, description: string | null, qty: number, unit_price: bigint, 
+/**
+ * Falls back to `InvoiceBillingSettings::default_tax_rate` when absent.
+ */
+tax_rate: number | null, material_id: string | null, };
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type CreateInvoiceRequest = { client_id: string, task_id: string | null, quote_id: string | null, intervention_id: string | null, due_date: bigint | null, notes: string | null, terms: string | null, discount_amount: bigint | null, lines: Array<InvoiceLineInput>, };
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Edits to a draft. `lines`, when present, replaces all lines.
+ */
+export type UpdateInvoiceRequest = { due_date: bigint | null, notes: string | null, terms: string | null, discount_amount: bigint | null, lines: Array<InvoiceLineInput> | null, };
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type CreateCreditNoteRequest = { invoice_id: string, reason: string | null, 
+/**
+ * Lines to credit. When absent the whole remaining amount is credited and
+ * the original invoice is cancelled.
+ */
+lines: Array<InvoiceLineInput> | null, };
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type InvoiceQuery = { client_id: string | null, status: InvoiceStatus | null, kind: InvoiceKind | null, limit: bigint | null, offset: bigint | null, };
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type InvoiceListResponse = { data: Array<Invoice>, total: bigint, };
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type InvoiceExportResponse = { file_path: string, };
+
+
+// Payment types
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type PaymentKind = "deposit" | "settlement";
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type PaymentMethod = "cash" | "card" | "transfer" | "cheque";
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type Payment = { id: string, client_id: string, kind: PaymentKind, method: PaymentMethod, amount: bigint, 
+/**
+ * Set for deposits.
+ */
+quote_id: string | null, 
+/**
+ * Set for settlements.
+ */
+invoice_id: string | null, paid_at: bigint, reference: string | null, notes: string | null, recorded_by: string | null, created_at: bigint, };
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type RecordPaymentRequest = { method: PaymentMethod, amount: bigint, 
+/**
+ * Defaults to now.
+ */
+paid_at: bigint | null, reference: string | null, notes: string | null, };
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type PaymentQuery = { client_id: string | null, quote_id: string | null, invoice_id: string | null, limit: bigint | null, offset: bigint | null, };
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type PaymentListResponse = { data: Array<Payment>, total: bigint, };
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type QuoteBalance = { quote_id: string, total: bigint, 
+/**
+ * Deposit the organization asks for before work starts; `0` when
+ * `deposit_percent` is not configured.
+ */
+deposit_required: bigint, deposits_paid: bigint, 
+/**
+ * Part of `deposit_required` still to collect.
+ */
+deposit_outstanding: bigint, 
+/**
+ * `total - deposits_paid`.
+ */
+balance: bigint, };
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type InvoiceBalance = { invoice_id: string, total: bigint, 
+/**
+ * Issued credit notes against the invoice.
+ */
+credited: bigint, 
+/**
+ * Settlements on the invoice plus deposits on its quote.
+ */
+paid: bigint, outstanding: bigint, };
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Open invoice with money still owed, as read for the receivables report.
+ */
+export type ReceivableInvoice = { invoice_id: string, invoice_number: string | null, client_id: string, client_name: string | null, due_date: bigint | null, outstanding: bigint, 
+/**
+ * Whole days past `due_date` at the report date; `0` when not yet due.
+ */
+days_overdue: bigint, };
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Outstanding amounts split by how long they are overdue.
+ */
+export type AgingBuckets = { 
+/**
+ * Not yet due.
+ */
+current: bigint, days_1_30: bigint, days_31_60: bigint, days_61_90: bigint, days_over_90: bigint, total: bigint, };
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type AgedReceivablesRow = { client_id: string, client_name: string | null, buckets: AgingBuckets, invoices: Array<ReceivableInvoice>, };
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type AgedReceivablesQuery = { client_id: string | null, 
+/**
+ * Report date in epoch milliseconds; defaults to now.
+ */
+as_of: bigint | null, };
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type AgedReceivablesReport = { as_of: bigint, 
+/**
+ * One row per client, largest balance first.
+ */
+rows: Array<AgedReceivablesRow>, totals: AgingBuckets, };
+
+
+// E-invoice types
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type EInvoiceFormat = "factur_x" | "cii" | "ubl";
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type EInvoiceViolation = { rule: string, message: string, };
+
+
 // @domain:interventions
 // Intervention types
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
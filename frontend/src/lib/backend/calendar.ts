@@ -33,17 +33,24 @@ export type EventStatus = "confirmed" | "tentative" | "cancelled";
 
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 
+/**
+ * Which part of a recurring series an edit or delete applies to.
+ */
+export type RecurrenceScope = "this" | "this_and_following" | "all";
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
 /**
  * TODO: document
  */
-export type CreateEventInput = { title: string, description: string | null, startDatetime: string, endDatetime: string, allDay: boolean | null, timezone: string | null, eventType: EventType | null, category: string | null, taskId: string | null, clientId: string | null, technicianId: string | null, location: string | null, meetingLink: string | null, isVirtual: boolean | null, participants: Array<EventParticipant> | null, reminders: Array<number> | null, color: string | null, tags: Array<string> | null, notes: string | null, };
+export type CreateEventInput = { title: string, description: string | null, startDatetime: string, endDatetime: string, allDay: boolean | null, timezone: string | null, eventType: EventType | null, category: string | null, taskId: string | null, clientId: string | null, technicianId: string | null, location: string | null, meetingLink: string | null, isVirtual: boolean | null, participants: Array<EventParticipant> | null, reminders: Array<number> | null, color: string | null, tags: Array<string> | null, notes: string | null, recurrenceRule: string | null, };
 
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 
 /**
  * TODO: document
  */
-export type UpdateEventInput = { title: string | null, description: string | null, startDatetime: string | null, endDatetime: string | null, allDay: boolean | null, timezone: string | null, eventType: EventType | null, category: string | null, taskId: string | null, clientId: string | null, location: string | null, meetingLink: string | null, isVirtual: boolean | null, participants: Array<EventParticipant> | null, status: EventStatus | null, reminders: Array<number> | null, color: string | null, tags: Array<string> | null, notes: string | null, };
+export type UpdateEventInput = { title: string | null, description: string | null, startDatetime: string | null, endDatetime: string | null, allDay: boolean | null, timezone: string | null, eventType: EventType | null, category: string | null, taskId: string | null, clientId: string | null, location: string | null, meetingLink: string | null, isVirtual: boolean | null, participants: Array<EventParticipant> | null, status: EventStatus | null, reminders: Array<number> | null, color: string | null, tags: Array<string> | null, notes: string | null, recurrenceRule: string | null, };
 
 
 
@@ -53,7 +60,7 @@ export type UpdateEventInput = { title: string | null, description: string | nul
 /**
  * TODO: document
  */
-export type CalendarEvent = { id: string, title: string, description: string | null, startDatetime: string, endDatetime: string, allDay: boolean, timezone: string, eventType: EventType, category: string | null, taskId: string | null, clientId: string | null, technicianId: string | null, location: string | null, meetingLink: string | null, isVirtual: boolean, participants: Array<EventParticipant>, isRecurring: boolean, recurrenceRule: string | null, parentEventId: string | null, reminders: Array<number>, status: EventStatus, color: string | null, tags: Array<string>, notes: string | null, synced: boolean, lastSyncedAt: bigint | null, createdAt: bigint, updatedAt: bigint, createdBy: string | null, updatedBy: string | null, deletedAt: bigint | null, deletedBy: string | null, };
+export type CalendarEvent = { id: string, title: string, description: string | null, startDatetime: string, endDatetime: string, allDay: boolean, timezone: string, eventType: EventType, category: string | null, taskId: string | null, clientId: string | null, technicianId: string | null, location: string | null, meetingLink: string | null, isVirtual: boolean, participants: Array<EventParticipant>, isRecurring: boolean, recurrenceRule: string | null, parentEventId: string | null, reminders: Array<number>, status: EventStatus, color: string | null, tags: Array<string>, notes: string | null, synced: boolean, lastSyncedAt: bigint | null, createdAt: bigint, updatedAt: bigint, createdBy: string | null, updatedBy: string | null, deletedAt: bigint | null, deletedBy: string | null, icalUid: string | null, recurrenceId?: string, };
 
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 
@@ -94,7 +101,12 @@ export type CalendarFilter = { date_range: CalendarDateRange, technician_ids: Ar
 /**
  * Conflict detection result
  */
-export type ConflictDetection = { has_conflict: boolean, conflict_type: string | null, conflicting_tasks: Array<CalendarTask>, message: string | null, };
+export type ConflictDetection = { has_conflict: boolean, conflict_type: string | null, conflicting_tasks: Array<CalendarTask>, message: string | null, 
+/**
+ * Availability problems that do not block the booking: outside working
+ * time or over capacity, when the organization only warns about them.
+ */
+warnings: Array<string>, };
 
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 
@@ -102,3 +114,162 @@ export type ConflictDetection = { has_conflict: boolean, conflict_type: string |
  * Date range filter for calendar queries
  */
 export type CalendarDateRange = { start_date: string, end_date: string, };
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Outcome of an iCalendar import.
+ */
+export type IcsImportSummary = { total_processed: number, created: number, updated: number, 
+/**
+ * Events already imported, left as they were.
+ */
+duplicates_skipped: number, failed: number, errors: Array<string>, };
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A technician's own working time, overriding the organization's business
+ * hours and days. Unset fields follow the organization.
+ */
+export type TechnicianAvailability = { technician_id: string, 
+/**
+ * `HH:MM`.
+ */
+work_start: string | null, work_end: string | null, 
+/**
+ * ISO weekdays, `"1"` (Monday) to `"7"` (Sunday).
+ */
+working_days: Array<string> | null, 
+/**
+ * Bookable minutes per working day; the whole working day when unset.
+ */
+daily_capacity_minutes: number | null, 
+/**
+ * Intervention types the technician carries out (`ppf`, `ceramic`, …).
+ * Empty means any.
+ */
+skills: Array<string>, };
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Days a technician is away, `start_date` to `end_date` inclusive.
+ */
+export type TechnicianAbsence = { id: string, technician_id: string, start_date: string, end_date: string, 
+/**
+ * `leave`, `sick`, `training` or `other`.
+ */
+kind: string, note: string | null, created_at: number, created_by: string | null, };
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type CreateAbsenceInput = { technician_id: string, start_date: string, end_date: string, kind: string | null, note: string | null, };
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A day nobody works.
+ */
+export type PublicHoliday = { date: string, name: string, };
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Whether a technician can be booked on a day.
+ */
+export type DayStatus = "working" | "day_off" | "holiday" | "absent";
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * One cell of the capacity heat map.
+ */
+export type DayCapacity = { date: string, status: DayStatus, capacity_minutes: number, booked_minutes: number, 
+/**
+ * Booked share of the capacity; `None` when there is no capacity.
+ */
+load_percent: number | null, };
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type TechnicianWeekCapacity = { technician_id: string, technician_name: string, days: Array<DayCapacity>, capacity_minutes: number, booked_minutes: number, };
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Booked time against capacity, per technician and day, Monday to Sunday.
+ */
+export type CapacityHeatmap = { week_start: string, week_end: string, technicians: Array<TechnicianWeekCapacity>, };
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * What to find slots for: a task, or a duration and intervention type.
+ */
+export type SlotSearch = { 
+/**
+ * Task to book; its duration and intervention type are used.
+ */
+task_id: string | null, 
+/**
+ * Minutes, when not searching for a task.
+ */
+duration_minutes: number | null, 
+/**
+ * `ppf`, `ceramic`, `detailing` or `other`; any technician when unset.
+ */
+intervention_type: string | null, start_date: string, end_date: string, 
+/**
+ * Technicians to search; all active technicians when unset.
+ */
+technician_ids: Array<string> | null, 
+/**
+ * Slots per technician, 5 by default.
+ */
+limit: number | null, };
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A free slot, scored 0–100.
+ */
+export type SlotCandidate = { date: string, start_time: string, end_time: string, score: number, 
+/**
+ * Minutes booked that day once the slot is taken.
+ */
+booked_minutes: number, capacity_minutes: number, };
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type TechnicianSlots = { technician_id: string, technician_name: string, 
+/**
+ * Best first.
+ */
+slots: Array<SlotCandidate>, };
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Free slots per technician, best technician first.
+ */
+export type SlotSuggestions = { duration_minutes: number, intervention_type: string | null, technicians: Array<TechnicianSlots>, };
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A slot proposed for an unscheduled task.
+ */
+export type ProposedBooking = { task_id: string, task_number: string, title: string, technician_id: string, technician_name: string, date: string, start_time: string, end_time: string, duration_minutes: number, score: number, };
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type UnplacedTask = { task_id: string, task_number: string, title: string, reason: string, };
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Slots proposed for every unscheduled task, most urgent placed first.
+ * Nothing is booked until the proposals are scheduled.
+ */
+export type ScheduleProposal = { start_date: string, end_date: string, proposals: Array<ProposedBooking>, unplaced: Array<UnplacedTask>, };
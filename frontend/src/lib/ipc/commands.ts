@@ -183,6 +183,9 @@ export const IPC_COMMANDS = {
   RULE_DISABLE: "disable_rule",
   RULE_DELETE: "delete_rule",
   RULE_TEST: "test_rule",
+  RULE_BACKTEST: "backtest_rule",
+  RULE_EXECUTIONS: "list_rule_executions",
+  RULE_TRIGGER_SCHEMAS: "list_rule_trigger_schemas",
 
   // Integrations commands
   INTEGRATION_LIST: "list_integrations",
@@ -192,6 +195,13 @@ export const IPC_COMMANDS = {
   INTEGRATION_TEST: "test_integration",
   INTEGRATION_DELETE: "delete_integration",
   INTEGRATION_RETRY_DEAD_LETTER: "retry_dead_letter_integrations",
+  INTEGRATION_REVEAL_SIGNING_SECRET: "reveal_integration_signing_secret",
+  INTEGRATION_ROTATE_SIGNING_SECRET: "rotate_integration_signing_secret",
+  INTEGRATION_LIST_DELIVERIES: "list_integration_deliveries",
+  INTEGRATION_LIST_DELIVERY_ATTEMPTS: "list_integration_delivery_attempts",
+  INTEGRATION_REPLAY_DELIVERY: "replay_integration_delivery",
+  INTEGRATION_PREVIEW_PAYLOAD: "preview_integration_payload",
+  INTEGRATION_LIST_INBOUND_REQUESTS: "list_integration_inbound_requests",
 
   // Bootstrap commands
   BOOTSTRAP_FIRST_ADMIN: "bootstrap_first_admin",
@@ -243,6 +253,21 @@ export const IPC_COMMANDS = {
   CALENDAR_GET_TASKS: "calendar_get_tasks",
   CALENDAR_CHECK_CONFLICTS: "calendar_check_conflicts",
   CALENDAR_SCHEDULE_TASK: "calendar_schedule_task",
+  CALENDAR_EXPORT_ICS: "calendar_export_ics",
+  CALENDAR_EXPORT_EVENT_INVITE: "calendar_export_event_invite",
+  CALENDAR_IMPORT_ICS: "calendar_import_ics",
+  CALENDAR_GET_FEED_PATH: "calendar_get_feed_path",
+  CALENDAR_GET_TECHNICIAN_AVAILABILITY: "calendar_get_technician_availability",
+  CALENDAR_SET_TECHNICIAN_AVAILABILITY: "calendar_set_technician_availability",
+  CALENDAR_LIST_ABSENCES: "calendar_list_absences",
+  CALENDAR_ADD_ABSENCE: "calendar_add_absence",
+  CALENDAR_DELETE_ABSENCE: "calendar_delete_absence",
+  CALENDAR_LIST_PUBLIC_HOLIDAYS: "calendar_list_public_holidays",
+  CALENDAR_SET_PUBLIC_HOLIDAY: "calendar_set_public_holiday",
+  CALENDAR_DELETE_PUBLIC_HOLIDAY: "calendar_delete_public_holiday",
+  CALENDAR_GET_CAPACITY_HEATMAP: "calendar_get_capacity_heatmap",
+  CALENDAR_SUGGEST_SLOTS: "calendar_suggest_slots",
+  CALENDAR_PROPOSE_SCHEDULE: "calendar_propose_schedule",
 
   // Quote commands
   QUOTE_GET_STATS: "quote_get_stats",
@@ -267,6 +292,35 @@ export const IPC_COMMANDS = {
   QUOTE_ATTACHMENT_UPDATE: "quote_attachment_update",
   QUOTE_ATTACHMENT_DELETE: "quote_attachment_delete",
   QUOTE_CONVERT_TO_TASK: "quote_convert_to_task",
+  QUOTE_EXPORT_REVISION_PDF: "quote_export_revision_pdf",
+  QUOTE_GET_REVISIONS: "quote_get_revisions",
+  QUOTE_DIFF_REVISIONS: "quote_diff_revisions",
+  QUOTE_ACCEPT_WITH_SIGNATURE: "quote_accept_with_signature",
+  QUOTE_GET_ACCEPTANCE: "quote_get_acceptance",
+
+  // Invoice commands
+  INVOICE_LIST: "invoice_list",
+  INVOICE_GET: "invoice_get",
+  INVOICE_CREATE: "invoice_create",
+  INVOICE_CREATE_FROM_QUOTE: "invoice_create_from_quote",
+  INVOICE_CREATE_FROM_INTERVENTION: "invoice_create_from_intervention",
+  INVOICE_UPDATE_DRAFT: "invoice_update_draft",
+  INVOICE_DELETE_DRAFT: "invoice_delete_draft",
+  INVOICE_ISSUE: "invoice_issue",
+  INVOICE_CREATE_CREDIT_NOTE: "invoice_create_credit_note",
+  INVOICE_EXPORT_PDF: "invoice_export_pdf",
+  INVOICE_BALANCE: "invoice_balance",
+  INVOICE_EINVOICE_CHECK: "invoice_einvoice_check",
+  INVOICE_EXPORT_EINVOICE: "invoice_export_einvoice",
+  QUOTE_EXPORT_EINVOICE: "quote_export_einvoice",
+
+  // Payment commands
+  PAYMENT_LIST: "payment_list",
+  PAYMENT_RECORD_DEPOSIT: "payment_record_deposit",
+  PAYMENT_RECORD_SETTLEMENT: "payment_record_settlement",
+  PAYMENT_DELETE: "payment_delete",
+  PAYMENT_QUOTE_BALANCE: "payment_quote_balance",
+  PAYMENT_AGED_RECEIVABLES: "payment_aged_receivables",
 
   // Organization commands
   GET_ONBOARDING_STATUS: "get_onboarding_status",
@@ -287,6 +341,9 @@ export const IPC_COMMANDS = {
   EXPORT_DATA_BACKUP: "export_data_backup",
   RESTORE_DATA_BACKUP: "restore_data_backup",
 
+  // Secrets vault commands
+  ROTATE_SECRETS_KEY: "rotate_secrets_key",
+
   // Audit/Security commands
   GET_SECURITY_METRICS: "get_security_metrics",
   GET_SECURITY_EVENTS: "get_security_events",
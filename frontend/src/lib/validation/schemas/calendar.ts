@@ -94,6 +94,12 @@ export const CalendarEventSchema = z.object({
   updatedBy: z.string().nullable(),
   deletedAt: z.number().nullable(),
   deletedBy: z.string().nullable(),
+
+  // iCalendar UID the event was imported with
+  icalUid: z.string().nullable(),
+
+  // Original start of an expanded occurrence of a recurring event
+  recurrenceId: z.string().optional(),
 });
 
 export const CreateEventInputSchema = z.object({
@@ -116,6 +122,7 @@ export const CreateEventInputSchema = z.object({
   color: z.string().nullable(),
   tags: z.array(z.string()).nullable(),
   notes: z.string().nullable(),
+  recurrenceRule: z.string().nullable(),
 });
 
 export const UpdateEventInputSchema = z.object({
@@ -138,6 +145,7 @@ export const UpdateEventInputSchema = z.object({
   color: z.string().nullable(),
   tags: z.array(z.string()).nullable(),
   notes: z.string().nullable(),
+  recurrenceRule: z.string().nullable(),
 });
 
 /**
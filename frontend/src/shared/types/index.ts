@@ -65,19 +65,39 @@ export type {
   BackendRuleStatus,
   BackendRuleMode,
   BackendRuleTrigger,
+  BackendRuleTriggerSchema,
   BackendRuleAction,
   BackendRuleDefinition,
   CreateBackendRuleRequest,
   UpdateBackendRuleRequest,
   BackendRuleEvaluationResult,
   TestBackendRuleRequest,
+  BacktestBackendRuleRequest,
+  BackendRuleBacktestMatch,
+  BackendRuleBacktestReport,
   BackendIntegrationKind,
   BackendIntegrationStatus,
   BackendDeliveryStatus,
+  BackendIntegrationRetryPolicy,
+  BackendIntegrationHttpMethod,
+  BackendPayloadFormat,
+  BackendPayloadTemplate,
   BackendIntegrationConfig,
   CreateBackendIntegrationRequest,
   UpdateBackendIntegrationRequest,
   TestBackendIntegrationResponse,
+  BackendOutboundDelivery,
+  BackendDeliveryAttempt,
+  BackendDeliveryLogEntry,
+  ListBackendDeliveriesRequest,
+  BackendDeliveryLogPage,
+  BackendInboundRequestLogEntry,
+  ListBackendInboundRequestsRequest,
+  BackendInboundRequestLogPage,
+  PreviewBackendPayloadRequest,
+  BackendPreviewPayloadResponse,
+  RotateBackendSigningSecretRequest,
+  BackendSigningSecretResponse,
   MonitoringConfig,
   SecurityPolicy,
   SecurityPolicyType,
@@ -4,6 +4,11 @@
  * Type definitions for configuration management
  */
 
+import type {
+  PaginatedResponse,
+  PaginationParams,
+} from "@/shared/types/pagination.types";
+
 export interface ConfigurationItem {
   id: string;
   category: string;
@@ -130,7 +135,21 @@ export type BackendRuleTrigger =
   | "task_created"
   | "task_status_changed"
   | "intervention_started"
-  | "intervention_finalized";
+  | "intervention_finalized"
+  | "quote_sent"
+  | "quote_accepted"
+  | "quote_expired"
+  | "material_low_stock"
+  | "material_consumed"
+  | "client_created"
+  | "step_completed"
+  | "photo_uploaded";
+
+/** Payload keys a rule condition can reference for one trigger. */
+export interface BackendRuleTriggerSchema {
+  trigger: BackendRuleTrigger;
+  fields: string[];
+}
 
 export type BackendRuleAction =
   | { type: "block"; message: string }
@@ -138,7 +157,23 @@ export type BackendRuleAction =
       type: "queue_integration";
       event_name: string;
       integration_ids?: string[] | null;
-    };
+    }
+  | {
+      type: "send_message";
+      channel: "email" | "sms" | "in_app";
+      recipient_id?: string | null;
+      recipient_email?: string | null;
+      recipient_phone?: string | null;
+      subject?: string | null;
+      body: string;
+    }
+  | {
+      type: "create_notification";
+      user_id?: string | null;
+      title: string;
+      message: string;
+    }
+  | { type: "set_task_tag"; tag: string };
 
 export interface BackendRuleDefinition {
   id: string;
@@ -189,7 +224,33 @@ export interface TestBackendRuleRequest {
   payload: unknown;
 }
 
-export type BackendIntegrationKind = "webhook";
+export interface BacktestBackendRuleRequest {
+  rule_id: string;
+  days: number;
+}
+
+export interface BackendRuleBacktestMatch {
+  source_id: string;
+  entity_id?: string | null;
+  occurred_at: number;
+  would_block: boolean;
+  matched_conditions: string[];
+  payload: unknown;
+}
+
+export interface BackendRuleBacktestReport {
+  rule_id: string;
+  trigger: BackendRuleTrigger;
+  mode: BackendRuleMode;
+  since: number;
+  evaluated: number;
+  matched: number;
+  would_block: number;
+  truncated: boolean;
+  matches: BackendRuleBacktestMatch[];
+}
+
+export type BackendIntegrationKind = "webhook" | "inbound_webhook";
 export type BackendIntegrationStatus = "draft" | "active" | "disabled";
 export type BackendDeliveryStatus =
   | "pending"
@@ -198,6 +259,28 @@ export type BackendDeliveryStatus =
   | "delivered"
   | "dead_letter";
 
+export interface BackendIntegrationRetryPolicy {
+  max_attempts: number;
+  backoff_base_seconds: number;
+  backoff_max_seconds: number;
+  timeout_seconds: number;
+  /** 0 never disables the integration. */
+  disable_after_failures: number;
+}
+
+export type BackendIntegrationHttpMethod = "POST" | "PUT" | "PATCH";
+export type BackendPayloadFormat = "json" | "form";
+
+/**
+ * Reshapes the event before it is sent. `{{event}}`, `{{delivery_id}}` and
+ * `{{payload.<path>}}` placeholders are filled into `body`.
+ */
+export interface BackendPayloadTemplate {
+  body?: unknown;
+  include_fields?: string[];
+  static_fields?: Record<string, unknown>;
+}
+
 export interface BackendIntegrationConfig {
   id: string;
   name: string;
@@ -208,6 +291,14 @@ export interface BackendIntegrationConfig {
   headers: Record<string, string>;
   subscribed_events: string[];
   has_secret: boolean;
+  has_signing_secret: boolean;
+  previous_signing_secret_expires_at?: number | null;
+  retry_policy: BackendIntegrationRetryPolicy;
+  http_method: BackendIntegrationHttpMethod;
+  payload_format: BackendPayloadFormat;
+  payload_template?: BackendPayloadTemplate | null;
+  consecutive_failures: number;
+  disabled_reason?: string | null;
   last_tested_at?: number | null;
   created_at: number;
   updated_at: number;
@@ -216,11 +307,17 @@ export interface BackendIntegrationConfig {
 
 export interface CreateBackendIntegrationRequest {
   name: string;
+  /** Defaults to `"webhook"`. */
+  kind?: BackendIntegrationKind;
   description?: string | null;
   endpoint_url: string;
   headers: Record<string, string>;
   subscribed_events: string[];
   secret_token?: string | null;
+  retry_policy?: BackendIntegrationRetryPolicy | null;
+  http_method?: BackendIntegrationHttpMethod;
+  payload_format?: BackendPayloadFormat;
+  payload_template?: BackendPayloadTemplate | null;
 }
 
 export interface UpdateBackendIntegrationRequest {
@@ -231,6 +328,10 @@ export interface UpdateBackendIntegrationRequest {
   subscribed_events?: string[];
   status?: BackendIntegrationStatus;
   secret_token?: string | null;
+  retry_policy?: BackendIntegrationRetryPolicy | null;
+  http_method?: BackendIntegrationHttpMethod;
+  payload_format?: BackendPayloadFormat;
+  payload_template?: BackendPayloadTemplate | null;
 }
 
 export interface TestBackendIntegrationResponse {
@@ -239,6 +340,91 @@ export interface TestBackendIntegrationResponse {
   tested_at: number;
 }
 
+export interface BackendOutboundDelivery {
+  id: string;
+  integration_id: string;
+  event_name: string;
+  payload: unknown;
+  status: BackendDeliveryStatus;
+  idempotency_key?: string | null;
+  attempt_count: number;
+  last_error?: string | null;
+  next_retry_at?: number | null;
+  created_at: number;
+  updated_at: number;
+}
+
+export interface BackendDeliveryAttempt {
+  id: string;
+  delivery_id: string;
+  integration_id: string;
+  attempt_number: number;
+  succeeded: boolean;
+  response_status?: number | null;
+  response_excerpt?: string | null;
+  error?: string | null;
+  latency_ms: number;
+  attempted_at: number;
+}
+
+export interface BackendDeliveryLogEntry {
+  delivery: BackendOutboundDelivery;
+  last_attempt?: BackendDeliveryAttempt | null;
+}
+
+export interface ListBackendDeliveriesRequest {
+  status?: BackendDeliveryStatus | null;
+  pagination?: PaginationParams;
+}
+
+export type BackendDeliveryLogPage = PaginatedResponse<BackendDeliveryLogEntry>;
+
+/** A request received by an inbound integration and the response it got. */
+export interface BackendInboundRequestLogEntry {
+  id: string;
+  integration_id: string;
+  action: string;
+  idempotency_key?: string | null;
+  correlation_id: string;
+  response_status: number;
+  response_body: unknown;
+  resource_type?: string | null;
+  resource_id?: string | null;
+  error?: string | null;
+  received_at: number;
+}
+
+export interface ListBackendInboundRequestsRequest {
+  pagination?: PaginationParams;
+}
+
+export type BackendInboundRequestLogPage =
+  PaginatedResponse<BackendInboundRequestLogEntry>;
+
+export interface PreviewBackendPayloadRequest {
+  event_name: string;
+  payload_template?: BackendPayloadTemplate | null;
+  payload_format?: BackendPayloadFormat;
+  /** Defaults to the latest queued payload for the event. */
+  sample_payload?: unknown;
+}
+
+export interface BackendPreviewPayloadResponse {
+  content_type: string;
+  body: string;
+  sample_payload: unknown;
+}
+
+export interface RotateBackendSigningSecretRequest {
+  grace_period_minutes?: number | null;
+}
+
+export interface BackendSigningSecretResponse {
+  integration_id: string;
+  signing_secret: string;
+  previous_signing_secret_expires_at?: number | null;
+}
+
 export type IntegrationType =
   | "email"
   | "sms"
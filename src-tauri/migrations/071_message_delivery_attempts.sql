@@ -0,0 +1,11 @@
+-- Migration 071: Delivery bookkeeping for outbound messages
+-- The message dispatcher retries failed email/SMS sends with backoff. It needs
+-- to know how many attempts were made and when the next one is due, neither of
+-- which fits in the existing status/error_message pair.
+
+ALTER TABLE messages ADD COLUMN attempts INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE messages ADD COLUMN next_attempt_at INTEGER;
+
+-- Dispatcher poll: WHERE status IN ('pending', 'failed') AND next_attempt_at <= ?
+CREATE INDEX IF NOT EXISTS idx_messages_dispatch
+  ON messages(status, next_attempt_at);
@@ -0,0 +1,6 @@
+-- Migration 072: SMS delivery accounting on outbound messages
+-- SMS gateways bill per segment and hand back their own message id, which is
+-- what support needs when a client says a reminder never arrived.
+
+ALTER TABLE messages ADD COLUMN segment_count INTEGER;
+ALTER TABLE messages ADD COLUMN provider_message_id TEXT;
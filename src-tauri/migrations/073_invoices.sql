@@ -0,0 +1,66 @@
+-- Migration 073: Invoices and credit notes
+-- Invoices are drafted from an accepted quote or a completed intervention and
+-- only receive their legal number when issued. French rules require a
+-- continuous sequence without gaps, so numbers come from invoice_sequences
+-- inside the same transaction that moves the invoice out of draft.
+
+CREATE TABLE IF NOT EXISTS invoices (
+  id TEXT PRIMARY KEY NOT NULL,
+  invoice_number TEXT UNIQUE,
+  kind TEXT NOT NULL DEFAULT 'invoice'
+    CHECK(kind IN ('invoice', 'credit_note')),
+  status TEXT NOT NULL DEFAULT 'draft'
+    CHECK(status IN ('draft', 'issued', 'partially_paid', 'paid', 'cancelled')),
+  client_id TEXT NOT NULL REFERENCES clients(id),
+  task_id TEXT REFERENCES tasks(id) ON DELETE SET NULL,
+  quote_id TEXT REFERENCES quotes(id) ON DELETE SET NULL,
+  intervention_id TEXT REFERENCES interventions(id) ON DELETE SET NULL,
+  credited_invoice_id TEXT REFERENCES invoices(id),
+  -- Buyer identity frozen at issue time; later client edits must not alter
+  -- an issued invoice.
+  client_name TEXT,
+  client_address TEXT,
+  issue_date INTEGER,
+  due_date INTEGER,
+  currency TEXT NOT NULL DEFAULT 'EUR',
+  notes TEXT,
+  terms TEXT,
+  subtotal INTEGER NOT NULL DEFAULT 0,
+  discount_amount INTEGER NOT NULL DEFAULT 0,
+  tax_total INTEGER NOT NULL DEFAULT 0,
+  total INTEGER NOT NULL DEFAULT 0,
+  amount_paid INTEGER NOT NULL DEFAULT 0,
+  created_by TEXT,
+  issued_at INTEGER,
+  created_at INTEGER NOT NULL DEFAULT (unixepoch() * 1000),
+  updated_at INTEGER NOT NULL DEFAULT (unixepoch() * 1000)
+);
+
+CREATE TABLE IF NOT EXISTS invoice_lines (
+  id TEXT PRIMARY KEY NOT NULL,
+  invoice_id TEXT NOT NULL REFERENCES invoices(id) ON DELETE CASCADE,
+  kind TEXT NOT NULL DEFAULT 'service'
+    CHECK(kind IN ('labor', 'material', 'service', 'discount')),
+  label TEXT NOT NULL,
+  description TEXT,
+  qty REAL NOT NULL DEFAULT 1,
+  unit_price INTEGER NOT NULL DEFAULT 0,
+  tax_rate REAL NOT NULL DEFAULT 0,
+  line_total INTEGER NOT NULL DEFAULT 0,
+  material_id TEXT REFERENCES materials(id),
+  position INTEGER NOT NULL DEFAULT 0,
+  created_at INTEGER NOT NULL DEFAULT (unixepoch() * 1000)
+);
+
+-- One row per numbering series and year, e.g. `invoice:2026`.
+CREATE TABLE IF NOT EXISTS invoice_sequences (
+  sequence_key TEXT PRIMARY KEY NOT NULL,
+  next_value INTEGER NOT NULL,
+  updated_at INTEGER NOT NULL DEFAULT (unixepoch() * 1000)
+);
+
+CREATE INDEX IF NOT EXISTS idx_invoices_client ON invoices(client_id, created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_invoices_status ON invoices(status, due_date);
+CREATE INDEX IF NOT EXISTS idx_invoices_quote ON invoices(quote_id) WHERE quote_id IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_invoices_intervention ON invoices(intervention_id) WHERE intervention_id IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_invoice_lines_invoice ON invoice_lines(invoice_id, position);
@@ -0,0 +1,32 @@
+-- Migration 074: Payments ledger
+-- Money received from customers: deposits (acomptes) taken on an accepted
+-- quote and settlements against an issued invoice. invoices.amount_paid is
+-- derived from this ledger; deposits on a quote count towards any invoice
+-- raised from that quote.
+
+CREATE TABLE IF NOT EXISTS payments (
+  id TEXT PRIMARY KEY NOT NULL,
+  client_id TEXT NOT NULL REFERENCES clients(id),
+  kind TEXT NOT NULL
+    CHECK(kind IN ('deposit', 'settlement')),
+  method TEXT NOT NULL
+    CHECK(method IN ('cash', 'card', 'transfer', 'cheque')),
+  -- Integer cents, always positive.
+  amount INTEGER NOT NULL CHECK(amount > 0),
+  quote_id TEXT REFERENCES quotes(id),
+  invoice_id TEXT REFERENCES invoices(id),
+  paid_at INTEGER NOT NULL,
+  -- Cheque number, transfer reference, card receipt…
+  reference TEXT,
+  notes TEXT,
+  recorded_by TEXT,
+  created_at INTEGER NOT NULL DEFAULT (unixepoch() * 1000),
+  CHECK(
+    (kind = 'deposit' AND quote_id IS NOT NULL AND invoice_id IS NULL)
+    OR (kind = 'settlement' AND invoice_id IS NOT NULL)
+  )
+);
+
+CREATE INDEX IF NOT EXISTS idx_payments_quote ON payments(quote_id) WHERE quote_id IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_payments_invoice ON payments(invoice_id) WHERE invoice_id IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_payments_client_paid_at ON payments(client_id, paid_at DESC);
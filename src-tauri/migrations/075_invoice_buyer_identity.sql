@@ -0,0 +1,10 @@
+-- Migration 075: Structured buyer identity on invoices
+-- EN 16931 / Factur-X exports need the buyer's VAT number and a structured
+-- postal address (country code in particular), not just the printed address
+-- block. Like client_name and client_address, these are frozen at issue time.
+
+ALTER TABLE invoices ADD COLUMN client_tax_id TEXT;
+ALTER TABLE invoices ADD COLUMN client_street TEXT;
+ALTER TABLE invoices ADD COLUMN client_postcode TEXT;
+ALTER TABLE invoices ADD COLUMN client_city TEXT;
+ALTER TABLE invoices ADD COLUMN client_country TEXT;
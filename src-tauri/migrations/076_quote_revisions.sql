@@ -0,0 +1,55 @@
+-- Migration 076: Immutable quote revisions
+-- Every Draft → Sent transition freezes what the customer receives (lines,
+-- totals, terms, discount) as a numbered revision, so reopening a quote after
+-- ChangesRequested/Rejected no longer overwrites the version that was sent.
+-- Quotes sent before this migration have no revision until they are re-sent.
+
+CREATE TABLE IF NOT EXISTS quote_revisions (
+    id TEXT PRIMARY KEY NOT NULL,
+    quote_id TEXT NOT NULL REFERENCES quotes(id) ON DELETE CASCADE,
+    revision INTEGER NOT NULL CHECK(revision >= 1),
+    valid_until INTEGER,
+    description TEXT,
+    terms TEXT,
+    discount_type TEXT,
+    discount_value INTEGER,
+    discount_amount INTEGER,
+    subtotal INTEGER NOT NULL DEFAULT 0,
+    tax_total INTEGER NOT NULL DEFAULT 0,
+    total INTEGER NOT NULL DEFAULT 0,
+    created_at INTEGER NOT NULL DEFAULT (unixepoch() * 1000),
+    created_by TEXT,
+    UNIQUE(quote_id, revision)
+);
+
+-- item_id keeps the id of the originating quote_items row so lines can be
+-- matched across revisions when diffing.
+CREATE TABLE IF NOT EXISTS quote_revision_items (
+    revision_id TEXT NOT NULL REFERENCES quote_revisions(id) ON DELETE CASCADE,
+    item_id TEXT NOT NULL,
+    kind TEXT NOT NULL DEFAULT 'service' CHECK(kind IN ('labor', 'material', 'service', 'discount')),
+    label TEXT NOT NULL,
+    description TEXT,
+    qty REAL NOT NULL DEFAULT 1,
+    unit_price INTEGER NOT NULL DEFAULT 0,
+    tax_rate REAL,
+    material_id TEXT,
+    position INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (revision_id, item_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_quote_revisions_quote_id ON quote_revisions(quote_id, revision);
+
+-- A sent document must never change afterwards. Deletes are still allowed so
+-- purging a quote cascades to its revisions.
+CREATE TRIGGER IF NOT EXISTS quote_revisions_no_update
+BEFORE UPDATE ON quote_revisions
+BEGIN
+    SELECT RAISE(ABORT, 'quote revisions are immutable');
+END;
+
+CREATE TRIGGER IF NOT EXISTS quote_revision_items_no_update
+BEFORE UPDATE ON quote_revision_items
+BEGIN
+    SELECT RAISE(ABORT, 'quote revisions are immutable');
+END;
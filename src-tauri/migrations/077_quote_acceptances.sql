@@ -0,0 +1,22 @@
+-- Migration 077: Signed customer acceptance of quotes
+-- Records who signed which sent revision, the captured signature image
+-- (base64, like interventions.customer_signature) and a SHA-256 hash of the
+-- revision payload, so the exact document that was agreed can be proven later.
+
+CREATE TABLE IF NOT EXISTS quote_acceptances (
+    id TEXT PRIMARY KEY NOT NULL,
+    quote_id TEXT NOT NULL UNIQUE REFERENCES quotes(id) ON DELETE CASCADE,
+    revision_id TEXT NOT NULL REFERENCES quote_revisions(id),
+    revision INTEGER NOT NULL,
+    signer_name TEXT NOT NULL,
+    signature TEXT NOT NULL,
+    payload_hash TEXT NOT NULL,
+    signed_at INTEGER NOT NULL,
+    recorded_by TEXT
+);
+
+CREATE TRIGGER IF NOT EXISTS quote_acceptances_no_update
+BEFORE UPDATE ON quote_acceptances
+BEGIN
+    SELECT RAISE(ABORT, 'quote acceptances are immutable');
+END;
@@ -0,0 +1,10 @@
+-- Migration 078: Reactive rule runs in rule_execution_logs
+-- Reactive rules run from domain events and execute side-effect actions; each
+-- run records its mode, the triggering event and the per-action outcome.
+
+ALTER TABLE rule_execution_logs ADD COLUMN mode TEXT NOT NULL DEFAULT 'blocking';
+ALTER TABLE rule_execution_logs ADD COLUMN event_id TEXT;
+ALTER TABLE rule_execution_logs ADD COLUMN action_results_json TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_rule_execution_logs_correlation_id
+    ON rule_execution_logs(correlation_id);
@@ -0,0 +1,14 @@
+-- Migration 079: Signed webhook deliveries
+-- Each integration signs deliveries with its own secret. A rotated-out secret
+-- keeps signing until its grace period ends. Outbox rows carry an idempotency
+-- key so the same event is queued (and received) once per integration.
+
+ALTER TABLE integration_configs ADD COLUMN encrypted_signing_secret TEXT;
+ALTER TABLE integration_configs ADD COLUMN encrypted_previous_signing_secret TEXT;
+ALTER TABLE integration_configs ADD COLUMN previous_signing_secret_expires_at INTEGER;
+
+ALTER TABLE integration_outbox ADD COLUMN idempotency_key TEXT;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_integration_outbox_idempotency_key
+    ON integration_outbox(idempotency_key)
+    WHERE idempotency_key IS NOT NULL;
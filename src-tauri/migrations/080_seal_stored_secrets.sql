@@ -0,0 +1,3 @@
+-- Migration 080: Seal stored credentials with the secrets vault
+-- Handled in Rust: integration secrets in the old XOR format and plain-text
+-- settings credentials are re-encrypted with the per-installation vault key.
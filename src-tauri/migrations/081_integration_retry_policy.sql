@@ -0,0 +1,33 @@
+-- Migration 081: Per-integration retry policy and delivery log
+-- Each integration has its own retry policy. Every delivery attempt is logged
+-- with its response status, a body excerpt and its latency. An integration
+-- whose deliveries keep failing is disabled automatically.
+
+ALTER TABLE integration_configs ADD COLUMN max_attempts INTEGER NOT NULL DEFAULT 5;
+ALTER TABLE integration_configs ADD COLUMN backoff_base_seconds INTEGER NOT NULL DEFAULT 60;
+ALTER TABLE integration_configs ADD COLUMN backoff_max_seconds INTEGER NOT NULL DEFAULT 3600;
+ALTER TABLE integration_configs ADD COLUMN timeout_seconds INTEGER NOT NULL DEFAULT 10;
+-- 0 never disables.
+ALTER TABLE integration_configs ADD COLUMN disable_after_failures INTEGER NOT NULL DEFAULT 20;
+ALTER TABLE integration_configs ADD COLUMN consecutive_failures INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE integration_configs ADD COLUMN disabled_reason TEXT;
+
+CREATE TABLE IF NOT EXISTS integration_delivery_attempts (
+    id TEXT PRIMARY KEY,
+    delivery_id TEXT NOT NULL,
+    integration_id TEXT NOT NULL,
+    attempt_number INTEGER NOT NULL,
+    succeeded INTEGER NOT NULL,
+    response_status INTEGER,
+    response_excerpt TEXT,
+    error TEXT,
+    latency_ms INTEGER NOT NULL,
+    attempted_at INTEGER NOT NULL,
+    FOREIGN KEY (delivery_id) REFERENCES integration_outbox(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_integration_delivery_attempts_delivery
+    ON integration_delivery_attempts(delivery_id, attempt_number);
+
+CREATE INDEX IF NOT EXISTS idx_integration_outbox_integration_created
+    ON integration_outbox(integration_id, created_at DESC);
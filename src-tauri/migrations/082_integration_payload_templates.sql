@@ -0,0 +1,7 @@
+-- Migration 082: Payload templates for outbound integrations
+-- An integration can reshape the event payload with a JSON template and send
+-- it with another HTTP method or as a form-encoded body.
+
+ALTER TABLE integration_configs ADD COLUMN http_method TEXT NOT NULL DEFAULT 'POST';
+ALTER TABLE integration_configs ADD COLUMN payload_format TEXT NOT NULL DEFAULT 'json';
+ALTER TABLE integration_configs ADD COLUMN payload_template_json TEXT;
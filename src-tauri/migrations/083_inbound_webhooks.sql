@@ -0,0 +1,29 @@
+-- Migration 083: Inbound webhooks
+-- An integration can also receive signed requests from an external system
+-- (e.g. the booking site) to create tasks, upsert clients and answer quotes.
+-- Every request is logged; a retried request with the same Idempotency-Key
+-- gets the logged response back instead of acting twice.
+
+ALTER TABLE integration_configs ADD COLUMN kind TEXT NOT NULL DEFAULT 'webhook';
+
+CREATE TABLE IF NOT EXISTS integration_inbound_requests (
+    id TEXT PRIMARY KEY,
+    integration_id TEXT NOT NULL,
+    action TEXT NOT NULL,
+    idempotency_key TEXT,
+    correlation_id TEXT NOT NULL,
+    response_status INTEGER NOT NULL,
+    response_body TEXT NOT NULL,
+    resource_type TEXT,
+    resource_id TEXT,
+    error TEXT,
+    received_at INTEGER NOT NULL,
+    FOREIGN KEY (integration_id) REFERENCES integration_configs(id) ON DELETE CASCADE
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_integration_inbound_requests_idempotency
+    ON integration_inbound_requests(integration_id, idempotency_key)
+    WHERE idempotency_key IS NOT NULL;
+
+CREATE INDEX IF NOT EXISTS idx_integration_inbound_requests_integration_received
+    ON integration_inbound_requests(integration_id, received_at DESC);
@@ -0,0 +1,31 @@
+-- Migration 084: Durable domain event store
+-- Every event published on the event bus is appended here so it survives a
+-- restart and can be queried or replayed to a handler later. `version`
+-- counts events per aggregate, starting at 1.
+
+CREATE TABLE IF NOT EXISTS domain_events (
+    id TEXT PRIMARY KEY,
+    event_type TEXT NOT NULL,
+    aggregate_type TEXT NOT NULL,
+    aggregate_id TEXT NOT NULL,
+    version INTEGER NOT NULL,
+    correlation_id TEXT NOT NULL,
+    user_id TEXT,
+    source TEXT NOT NULL,
+    payload TEXT NOT NULL,
+    metadata TEXT NOT NULL,
+    occurred_at INTEGER NOT NULL,
+    stored_at INTEGER NOT NULL
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_domain_events_aggregate_version
+    ON domain_events(aggregate_id, version);
+
+CREATE INDEX IF NOT EXISTS idx_domain_events_type_occurred
+    ON domain_events(event_type, occurred_at);
+
+CREATE INDEX IF NOT EXISTS idx_domain_events_user_occurred
+    ON domain_events(user_id, occurred_at);
+
+CREATE INDEX IF NOT EXISTS idx_domain_events_occurred
+    ON domain_events(occurred_at);
@@ -0,0 +1,10 @@
+-- Migration 085: iCalendar UIDs on calendar events
+-- Events imported from an .ics file keep the UID they were published with,
+-- so importing the same file again updates them instead of adding copies.
+-- Events created in the app have no stored UID; they export as `<id>@<host>`.
+
+ALTER TABLE calendar_events ADD COLUMN ical_uid TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_events_ical_uid
+    ON calendar_events(ical_uid)
+    WHERE ical_uid IS NOT NULL;
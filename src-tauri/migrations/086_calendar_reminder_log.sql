@@ -0,0 +1,22 @@
+-- Migration 086: Fired calendar reminders
+-- One row per reminder sent (or deliberately skipped) for an occurrence of an
+-- event or scheduled task, per recipient. The reminder scheduler claims a row
+-- before sending, so a reminder is never fired twice, even across restarts.
+-- `recipient` is `user:<id>`, `client:<id>:email` or `client:<id>:sms`;
+-- `occurrence_start` is the UTC start of the occurrence the reminder is for.
+
+CREATE TABLE IF NOT EXISTS calendar_reminder_log (
+    id TEXT PRIMARY KEY,
+    source_type TEXT NOT NULL CHECK(source_type IN ('event', 'task')),
+    source_id TEXT NOT NULL,
+    occurrence_start TEXT NOT NULL,
+    minutes_before INTEGER NOT NULL,
+    recipient TEXT NOT NULL,
+    status TEXT NOT NULL CHECK(status IN ('sent', 'failed', 'skipped')),
+    error TEXT,
+    fired_at INTEGER NOT NULL,
+    UNIQUE(source_type, source_id, occurrence_start, minutes_before, recipient)
+);
+
+CREATE INDEX IF NOT EXISTS idx_calendar_reminder_log_fired
+    ON calendar_reminder_log(fired_at);
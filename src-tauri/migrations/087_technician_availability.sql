@@ -0,0 +1,44 @@
+-- Migration 087: Technician availability
+-- Per-technician working time and daily capacity, absences and public
+-- holidays, used by scheduling to keep bookings within working time and
+-- capacity. Working time left unset on a technician follows the
+-- organization's business hours and days.
+
+CREATE TABLE IF NOT EXISTS technician_availability (
+    technician_id TEXT PRIMARY KEY NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    work_start TEXT,
+    work_end TEXT,
+    -- JSON array of ISO weekdays ("1" = Monday … "7" = Sunday)
+    working_days TEXT,
+    daily_capacity_minutes INTEGER CHECK(daily_capacity_minutes IS NULL OR daily_capacity_minutes >= 0),
+    -- JSON array of intervention types the technician carries out
+    skills TEXT NOT NULL DEFAULT '[]',
+    updated_at INTEGER NOT NULL DEFAULT (unixepoch() * 1000),
+    updated_by TEXT
+);
+
+CREATE TABLE IF NOT EXISTS technician_absences (
+    id TEXT PRIMARY KEY NOT NULL,
+    technician_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    start_date TEXT NOT NULL,
+    end_date TEXT NOT NULL,
+    kind TEXT NOT NULL DEFAULT 'leave' CHECK(kind IN ('leave', 'sick', 'training', 'other')),
+    note TEXT,
+    created_at INTEGER NOT NULL DEFAULT (unixepoch() * 1000),
+    created_by TEXT,
+    CHECK(end_date >= start_date)
+);
+
+CREATE INDEX IF NOT EXISTS idx_technician_absences_period
+    ON technician_absences(technician_id, start_date, end_date);
+
+CREATE TABLE IF NOT EXISTS public_holidays (
+    date TEXT PRIMARY KEY NOT NULL,
+    name TEXT NOT NULL,
+    created_at INTEGER NOT NULL DEFAULT (unixepoch() * 1000)
+);
+
+-- 'warn' schedules bookings outside working time or over capacity with a
+-- warning; 'reject' refuses them unless forced.
+INSERT OR IGNORE INTO organization_settings (key, value, category) VALUES
+  ('business_capacity_policy', 'warn', 'business');
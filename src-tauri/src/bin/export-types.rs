@@ -17,8 +17,11 @@ use rpma_ppf_intervention::domains::auth::{
 };
 use rpma_ppf_intervention::domains::calendar::models::{
     CalendarDateRange, CalendarEvent, CalendarFilter, CalendarTask, CalendarTaskPriority,
-    CalendarTaskStatus, ConflictDetection, CreateEventInput, EventParticipant, EventStatus,
-    EventType, ParticipantStatus, UpdateEventInput,
+    CalendarTaskStatus, CapacityHeatmap, ConflictDetection, CreateAbsenceInput, CreateEventInput,
+    DayCapacity, DayStatus, EventParticipant, EventStatus, EventType, IcsImportSummary,
+    ParticipantStatus, ProposedBooking, PublicHoliday, RecurrenceScope, ScheduleProposal,
+    SlotCandidate, SlotSearch, SlotSuggestions, TechnicianAbsence, TechnicianAvailability,
+    TechnicianSlots, TechnicianWeekCapacity, UnplacedTask, UpdateEventInput,
 };
 use rpma_ppf_intervention::domains::clients::application::client_service::{
     ClientStat, ClientStats,
@@ -48,11 +51,25 @@ use rpma_ppf_intervention::domains::inventory::domain::models::material::{
 use rpma_ppf_intervention::domains::inventory::domain::models::material_ts::{
     InventoryTransactionTS, MaterialConsumptionTS, MaterialTS,
 };
+use rpma_ppf_intervention::domains::invoices::domain::models::einvoice::{
+    EInvoiceFormat, EInvoiceViolation,
+};
+use rpma_ppf_intervention::domains::invoices::domain::models::invoices::{
+    CreateCreditNoteRequest, CreateInvoiceRequest, Invoice, InvoiceBillingSettings,
+    InvoiceExportResponse, InvoiceKind, InvoiceLine, InvoiceLineInput, InvoiceLineKind,
+    InvoiceListResponse, InvoiceQuery, InvoiceStatus, UpdateInvoiceRequest, VatBreakdownLine,
+};
+use rpma_ppf_intervention::domains::invoices::domain::models::payments::{
+    AgedReceivablesQuery, AgedReceivablesReport, AgedReceivablesRow, AgingBuckets, InvoiceBalance,
+    Payment, PaymentKind, PaymentListResponse, PaymentMethod, PaymentQuery, QuoteBalance,
+    ReceivableInvoice, RecordPaymentRequest,
+};
 use rpma_ppf_intervention::domains::notifications::models::{
     Message, MessageListResponse, MessagePriority, MessageQuery, MessageStatus, MessageTemplate,
-    MessageTemplateRequest, MessageType, Notification, NotificationChannel, NotificationConfig,
-    NotificationMessage, NotificationPreferences, NotificationPriority, NotificationStatus,
-    NotificationTemplate, NotificationType, SendMessageRequest, TemplateVariables,
+    MessageTemplatePreview, MessageTemplateRequest, MessageType, Notification,
+    NotificationChannel, NotificationConfig, NotificationMessage, NotificationPreferences,
+    NotificationPriority, NotificationStatus, NotificationTemplate, NotificationType,
+    PreviewMessageTemplateRequest, SendMessageRequest, TemplateVariables,
     UpdateNotificationPreferencesRequest,
 };
 use rpma_ppf_intervention::domains::quotes::domain::models::quote::{
@@ -62,6 +79,20 @@ use rpma_ppf_intervention::domains::quotes::domain::models::quote::{
     QuoteQuery, QuoteStats, QuoteStatus, TaskCreatedInfo, UpdateQuoteAttachmentRequest,
     UpdateQuoteItemRequest, UpdateQuoteRequest,
 };
+use rpma_ppf_intervention::domains::quotes::domain::models::quote_acceptance::{
+    QuoteAcceptance, QuoteAcceptanceVerification, QuoteSignedAcceptResponse,
+    SignQuoteAcceptanceRequest,
+};
+use rpma_ppf_intervention::domains::quotes::domain::models::quote_revision::{
+    QuoteItemChange, QuoteRevision, QuoteRevisionDiff,
+};
+use rpma_ppf_intervention::domains::rules::domain::models::rules::RuleTrigger;
+use rpma_ppf_intervention::domains::rules::domain::models::trigger_payloads::{
+    ClientCreatedPayload, InterventionFinalizedPayload, InterventionStartedPayload,
+    MaterialConsumedPayload, MaterialLowStockPayload, PhotoUploadedPayload, QuoteAcceptedPayload,
+    QuoteExpiredPayload, QuoteSentPayload, RuleTriggerPayload, RuleTriggerSchema,
+    StepCompletedPayload, TaskCreatedPayload, TaskStatusChangedPayload,
+};
 use rpma_ppf_intervention::domains::settings::{
     AppSettings, AppearanceSettings, BackupSettings, CreateOrganizationRequest,
     DataManagementSettings, DatabaseSettings, DiagnosticSettings, GeneralSettings,
@@ -241,6 +272,10 @@ fn main() {
     type_definitions
         .push_str(&EventStatus::export_to_string().expect("Failed to export EventStatus type"));
     type_definitions.push_str("\n");
+    type_definitions.push_str(
+        &RecurrenceScope::export_to_string().expect("Failed to export RecurrenceScope type"),
+    );
+    type_definitions.push_str("\n");
     type_definitions.push_str(
         &CreateEventInput::export_to_string().expect("Failed to export CreateEventInput type"),
     );
@@ -279,6 +314,66 @@ fn main() {
     type_definitions.push_str(
         &CalendarDateRange::export_to_string().expect("Failed to export CalendarDateRange type"),
     );
+    type_definitions.push_str("\n");
+    type_definitions.push_str(
+        &IcsImportSummary::export_to_string().expect("Failed to export IcsImportSummary type"),
+    );
+    type_definitions.push_str("\n");
+    type_definitions.push_str(
+        &TechnicianAvailability::export_to_string()
+            .expect("Failed to export TechnicianAvailability type"),
+    );
+    type_definitions.push_str("\n");
+    type_definitions.push_str(
+        &TechnicianAbsence::export_to_string().expect("Failed to export TechnicianAbsence type"),
+    );
+    type_definitions.push_str("\n");
+    type_definitions.push_str(
+        &CreateAbsenceInput::export_to_string().expect("Failed to export CreateAbsenceInput type"),
+    );
+    type_definitions.push_str("\n");
+    type_definitions
+        .push_str(&PublicHoliday::export_to_string().expect("Failed to export PublicHoliday type"));
+    type_definitions.push_str("\n");
+    type_definitions
+        .push_str(&DayStatus::export_to_string().expect("Failed to export DayStatus type"));
+    type_definitions.push_str("\n");
+    type_definitions
+        .push_str(&DayCapacity::export_to_string().expect("Failed to export DayCapacity type"));
+    type_definitions.push_str("\n");
+    type_definitions.push_str(
+        &TechnicianWeekCapacity::export_to_string()
+            .expect("Failed to export TechnicianWeekCapacity type"),
+    );
+    type_definitions.push_str("\n");
+    type_definitions.push_str(
+        &CapacityHeatmap::export_to_string().expect("Failed to export CapacityHeatmap type"),
+    );
+    type_definitions.push_str("\n");
+    type_definitions
+        .push_str(&SlotSearch::export_to_string().expect("Failed to export SlotSearch type"));
+    type_definitions.push_str("\n");
+    type_definitions
+        .push_str(&SlotCandidate::export_to_string().expect("Failed to export SlotCandidate type"));
+    type_definitions.push_str("\n");
+    type_definitions.push_str(
+        &TechnicianSlots::export_to_string().expect("Failed to export TechnicianSlots type"),
+    );
+    type_definitions.push_str("\n");
+    type_definitions.push_str(
+        &SlotSuggestions::export_to_string().expect("Failed to export SlotSuggestions type"),
+    );
+    type_definitions.push_str("\n");
+    type_definitions.push_str(
+        &ProposedBooking::export_to_string().expect("Failed to export ProposedBooking type"),
+    );
+    type_definitions.push_str("\n");
+    type_definitions
+        .push_str(&UnplacedTask::export_to_string().expect("Failed to export UnplacedTask type"));
+    type_definitions.push_str("\n");
+    type_definitions.push_str(
+        &ScheduleProposal::export_to_string().expect("Failed to export ScheduleProposal type"),
+    );
     type_definitions.push_str("\n\n");
 
     // Domain: clients
@@ -642,6 +737,240 @@ fn main() {
     );
     type_definitions.push_str("\n\n");
 
+    // Quote revision types
+    type_definitions.push_str("// Quote revision types\n");
+    type_definitions
+        .push_str(&QuoteRevision::export_to_string().expect("Failed to export QuoteRevision type"));
+    type_definitions.push_str("\n");
+    type_definitions.push_str(
+        &QuoteItemChange::export_to_string().expect("Failed to export QuoteItemChange type"),
+    );
+    type_definitions.push_str("\n");
+    type_definitions.push_str(
+        &QuoteRevisionDiff::export_to_string().expect("Failed to export QuoteRevisionDiff type"),
+    );
+    type_definitions.push_str("\n\n");
+
+    // Quote acceptance types
+    type_definitions.push_str("// Quote acceptance types\n");
+    type_definitions.push_str(
+        &QuoteAcceptance::export_to_string().expect("Failed to export QuoteAcceptance type"),
+    );
+    type_definitions.push_str("\n");
+    type_definitions.push_str(
+        &SignQuoteAcceptanceRequest::export_to_string()
+            .expect("Failed to export SignQuoteAcceptanceRequest type"),
+    );
+    type_definitions.push_str("\n");
+    type_definitions.push_str(
+        &QuoteAcceptanceVerification::export_to_string()
+            .expect("Failed to export QuoteAcceptanceVerification type"),
+    );
+    type_definitions.push_str("\n");
+    type_definitions.push_str(
+        &QuoteSignedAcceptResponse::export_to_string()
+            .expect("Failed to export QuoteSignedAcceptResponse type"),
+    );
+    type_definitions.push_str("\n\n");
+
+    // Domain: invoices
+    type_definitions.push_str("// @domain:invoices\n");
+    // Invoice types
+    type_definitions.push_str("// Invoice types\n");
+    type_definitions
+        .push_str(&InvoiceKind::export_to_string().expect("Failed to export InvoiceKind type"));
+    type_definitions.push_str("\n");
+    type_definitions
+        .push_str(&InvoiceStatus::export_to_string().expect("Failed to export InvoiceStatus type"));
+    type_definitions.push_str("\n");
+    type_definitions.push_str(
+        &InvoiceLineKind::export_to_string().expect("Failed to export InvoiceLineKind type"),
+    );
+    type_definitions.push_str("\n");
+    type_definitions
+        .push_str(&InvoiceLine::export_to_string().expect("Failed to export InvoiceLine type"));
+    type_definitions.push_str("\n");
+    type_definitions.push_str(
+        &VatBreakdownLine::export_to_string().expect("Failed to export VatBreakdownLine type"),
+    );
+    type_definitions.push_str("\n");
+    type_definitions.push_str(&Invoice::export_to_string().expect("Failed to export Invoice type"));
+    type_definitions.push_str("\n");
+    type_definitions.push_str(
+        &InvoiceBillingSettings::export_to_string()
+            .expect("Failed to export InvoiceBillingSettings type"),
+    );
+    type_definitions.push_str("\n");
+    type_definitions.push_str(
+        &InvoiceLineInput::export_to_string().expect("Failed to export InvoiceLineInput type"),
+    );
+    type_definitions.push_str("\n");
+    type_definitions.push_str(
+        &CreateInvoiceRequest::export_to_string()
+            .expect("Failed to export CreateInvoiceRequest type"),
+    );
+    type_definitions.push_str("\n");
+    type_definitions.push_str(
+        &UpdateInvoiceRequest::export_to_string()
+            .expect("Failed to export UpdateInvoiceRequest type"),
+    );
+    type_definitions.push_str("\n");
+    type_definitions.push_str(
+        &CreateCreditNoteRequest::export_to_string()
+            .expect("Failed to export CreateCreditNoteRequest type"),
+    );
+    type_definitions.push_str("\n");
+    type_definitions
+        .push_str(&InvoiceQuery::export_to_string().expect("Failed to export InvoiceQuery type"));
+    type_definitions.push_str("\n");
+    type_definitions.push_str(
+        &InvoiceListResponse::export_to_string()
+            .expect("Failed to export InvoiceListResponse type"),
+    );
+    type_definitions.push_str("\n");
+    type_definitions.push_str(
+        &InvoiceExportResponse::export_to_string()
+            .expect("Failed to export InvoiceExportResponse type"),
+    );
+    type_definitions.push_str("\n\n");
+
+    // Payment types
+    type_definitions.push_str("// Payment types\n");
+    type_definitions
+        .push_str(&PaymentKind::export_to_string().expect("Failed to export PaymentKind type"));
+    type_definitions.push_str("\n");
+    type_definitions
+        .push_str(&PaymentMethod::export_to_string().expect("Failed to export PaymentMethod type"));
+    type_definitions.push_str("\n");
+    type_definitions.push_str(&Payment::export_to_string().expect("Failed to export Payment type"));
+    type_definitions.push_str("\n");
+    type_definitions.push_str(
+        &RecordPaymentRequest::export_to_string()
+            .expect("Failed to export RecordPaymentRequest type"),
+    );
+    type_definitions.push_str("\n");
+    type_definitions
+        .push_str(&PaymentQuery::export_to_string().expect("Failed to export PaymentQuery type"));
+    type_definitions.push_str("\n");
+    type_definitions.push_str(
+        &PaymentListResponse::export_to_string()
+            .expect("Failed to export PaymentListResponse type"),
+    );
+    type_definitions.push_str("\n");
+    type_definitions
+        .push_str(&QuoteBalance::export_to_string().expect("Failed to export QuoteBalance type"));
+    type_definitions.push_str("\n");
+    type_definitions.push_str(
+        &InvoiceBalance::export_to_string().expect("Failed to export InvoiceBalance type"),
+    );
+    type_definitions.push_str("\n");
+    type_definitions.push_str(
+        &ReceivableInvoice::export_to_string().expect("Failed to export ReceivableInvoice type"),
+    );
+    type_definitions.push_str("\n");
+    type_definitions
+        .push_str(&AgingBuckets::export_to_string().expect("Failed to export AgingBuckets type"));
+    type_definitions.push_str("\n");
+    type_definitions.push_str(
+        &AgedReceivablesRow::export_to_string().expect("Failed to export AgedReceivablesRow type"),
+    );
+    type_definitions.push_str("\n");
+    type_definitions.push_str(
+        &AgedReceivablesQuery::export_to_string()
+            .expect("Failed to export AgedReceivablesQuery type"),
+    );
+    type_definitions.push_str("\n");
+    type_definitions.push_str(
+        &AgedReceivablesReport::export_to_string()
+            .expect("Failed to export AgedReceivablesReport type"),
+    );
+    type_definitions.push_str("\n\n");
+
+    // E-invoice types
+    type_definitions.push_str("// E-invoice types\n");
+    type_definitions.push_str(
+        &EInvoiceFormat::export_to_string().expect("Failed to export EInvoiceFormat type"),
+    );
+    type_definitions.push_str("\n");
+    type_definitions.push_str(
+        &EInvoiceViolation::export_to_string().expect("Failed to export EInvoiceViolation type"),
+    );
+    type_definitions.push_str("\n\n");
+
+    // Domain: rules
+    type_definitions.push_str("// @domain:rules\n");
+    // Rule trigger payload types (condition autocomplete)
+    type_definitions.push_str("// Rule trigger payload types\n");
+    type_definitions
+        .push_str(&RuleTrigger::export_to_string().expect("Failed to export RuleTrigger type"));
+    type_definitions.push_str("\n");
+    type_definitions.push_str(
+        &TaskCreatedPayload::export_to_string().expect("Failed to export TaskCreatedPayload type"),
+    );
+    type_definitions.push_str("\n");
+    type_definitions.push_str(
+        &TaskStatusChangedPayload::export_to_string()
+            .expect("Failed to export TaskStatusChangedPayload type"),
+    );
+    type_definitions.push_str("\n");
+    type_definitions.push_str(
+        &InterventionStartedPayload::export_to_string()
+            .expect("Failed to export InterventionStartedPayload type"),
+    );
+    type_definitions.push_str("\n");
+    type_definitions.push_str(
+        &InterventionFinalizedPayload::export_to_string()
+            .expect("Failed to export InterventionFinalizedPayload type"),
+    );
+    type_definitions.push_str("\n");
+    type_definitions.push_str(
+        &QuoteSentPayload::export_to_string().expect("Failed to export QuoteSentPayload type"),
+    );
+    type_definitions.push_str("\n");
+    type_definitions.push_str(
+        &QuoteAcceptedPayload::export_to_string()
+            .expect("Failed to export QuoteAcceptedPayload type"),
+    );
+    type_definitions.push_str("\n");
+    type_definitions.push_str(
+        &QuoteExpiredPayload::export_to_string()
+            .expect("Failed to export QuoteExpiredPayload type"),
+    );
+    type_definitions.push_str("\n");
+    type_definitions.push_str(
+        &MaterialLowStockPayload::export_to_string()
+            .expect("Failed to export MaterialLowStockPayload type"),
+    );
+    type_definitions.push_str("\n");
+    type_definitions.push_str(
+        &MaterialConsumedPayload::export_to_string()
+            .expect("Failed to export MaterialConsumedPayload type"),
+    );
+    type_definitions.push_str("\n");
+    type_definitions.push_str(
+        &ClientCreatedPayload::export_to_string()
+            .expect("Failed to export ClientCreatedPayload type"),
+    );
+    type_definitions.push_str("\n");
+    type_definitions.push_str(
+        &StepCompletedPayload::export_to_string()
+            .expect("Failed to export StepCompletedPayload type"),
+    );
+    type_definitions.push_str("\n");
+    type_definitions.push_str(
+        &PhotoUploadedPayload::export_to_string()
+            .expect("Failed to export PhotoUploadedPayload type"),
+    );
+    type_definitions.push_str("\n");
+    type_definitions.push_str(
+        &RuleTriggerPayload::export_to_string().expect("Failed to export RuleTriggerPayload type"),
+    );
+    type_definitions.push_str("\n");
+    type_definitions.push_str(
+        &RuleTriggerSchema::export_to_string().expect("Failed to export RuleTriggerSchema type"),
+    );
+    type_definitions.push_str("\n\n");
+
     // Domain: interventions
     type_definitions.push_str("// @domain:interventions\n");
     // Intervention types
@@ -907,6 +1236,16 @@ fn main() {
             .expect("Failed to export MessageTemplateRequest type"),
     );
     type_definitions.push_str("\n");
+    type_definitions.push_str(
+        &PreviewMessageTemplateRequest::export_to_string()
+            .expect("Failed to export PreviewMessageTemplateRequest type"),
+    );
+    type_definitions.push_str("\n");
+    type_definitions.push_str(
+        &MessageTemplatePreview::export_to_string()
+            .expect("Failed to export MessageTemplatePreview type"),
+    );
+    type_definitions.push_str("\n");
     type_definitions.push_str(
         &SendMessageRequest::export_to_string().expect("Failed to export SendMessageRequest type"),
     );
@@ -1167,9 +1506,26 @@ fn main() {
         "CalendarFilter",
         "ConflictDetection",
         "CalendarDateRange",
+        "IcsImportSummary",
+        "TechnicianAvailability",
+        "TechnicianAbsence",
+        "CreateAbsenceInput",
+        "PublicHoliday",
+        "DayStatus",
+        "DayCapacity",
+        "TechnicianWeekCapacity",
+        "CapacityHeatmap",
+        "SlotSearch",
+        "SlotCandidate",
+        "TechnicianSlots",
+        "SlotSuggestions",
+        "ProposedBooking",
+        "UnplacedTask",
+        "ScheduleProposal",
         "CreateEventInput",
         "UpdateEventInput",
         "ParticipantStatus",
+        "RecurrenceScope",
         "Client",
         "ClientWithTasks",
         "ClientQuery",
@@ -1211,6 +1567,13 @@ fn main() {
         "CreateQuoteAttachmentRequest",
         "UpdateQuoteAttachmentRequest",
         "ConvertQuoteToTaskResponse",
+        "QuoteRevision",
+        "QuoteItemChange",
+        "QuoteRevisionDiff",
+        "QuoteAcceptance",
+        "SignQuoteAcceptanceRequest",
+        "QuoteAcceptanceVerification",
+        "QuoteSignedAcceptResponse",
         "Intervention",
         "InterventionStatus",
         "InterventionType",
@@ -1258,6 +1621,8 @@ fn main() {
         "MessageListResponse",
         "MessageTemplate",
         "MessageTemplateRequest",
+        "PreviewMessageTemplateRequest",
+        "MessageTemplatePreview",
         "SendMessageRequest",
         "NotificationPreferences",
         "UpdateNotificationPreferencesRequest",
@@ -1329,6 +1694,54 @@ fn main() {
         "InterventionStats",
         "InterventionManagementResponse",
         "InterventionProgressResponse",
+        // Rule trigger payload types
+        "RuleTrigger",
+        "TaskCreatedPayload",
+        "TaskStatusChangedPayload",
+        "InterventionStartedPayload",
+        "InterventionFinalizedPayload",
+        "QuoteSentPayload",
+        "QuoteAcceptedPayload",
+        "QuoteExpiredPayload",
+        "MaterialLowStockPayload",
+        "MaterialConsumedPayload",
+        "ClientCreatedPayload",
+        "StepCompletedPayload",
+        "PhotoUploadedPayload",
+        "RuleTriggerPayload",
+        "RuleTriggerSchema",
+        // Invoice types
+        "InvoiceKind",
+        "InvoiceStatus",
+        "InvoiceLineKind",
+        "InvoiceLine",
+        "VatBreakdownLine",
+        "Invoice",
+        "InvoiceBillingSettings",
+        "InvoiceLineInput",
+        "CreateInvoiceRequest",
+        "UpdateInvoiceRequest",
+        "CreateCreditNoteRequest",
+        "InvoiceQuery",
+        "InvoiceListResponse",
+        "InvoiceExportResponse",
+        // Payment types
+        "PaymentKind",
+        "PaymentMethod",
+        "Payment",
+        "RecordPaymentRequest",
+        "PaymentQuery",
+        "PaymentListResponse",
+        "QuoteBalance",
+        "InvoiceBalance",
+        "ReceivableInvoice",
+        "AgingBuckets",
+        "AgedReceivablesRow",
+        "AgedReceivablesQuery",
+        "AgedReceivablesReport",
+        // E-invoice types
+        "EInvoiceFormat",
+        "EInvoiceViolation",
         // Shared IPC envelope
         "ApiResponse",
     ];
@@ -4,6 +4,7 @@ use crate::commands::{AppState, UserRole};
 use crate::resolve_context;
 use crate::shared::constants::{APP_BRAND, APP_COPYRIGHT_NOTICE, APP_LICENSE_NAME, APP_WATERMARK};
 use crate::shared::ipc::AppError;
+use crate::shared::services::secrets_vault::{KEYRING_FILE_NAME, RESTORE_KEYRING_FILE_NAME};
 use r2d2::Pool;
 use r2d2_sqlite::SqliteConnectionManager;
 use serde::Serialize;
@@ -360,7 +361,9 @@ pub async fn get_app_info(correlation_id: Option<String>) -> Result<serde_json::
 ///
 /// Performs a FULL WAL checkpoint before copying so the snapshot is
 /// consistent.  The destination path is supplied by the frontend (after the
-/// user picks it via the native save-file dialog).
+/// user picks it via the native save-file dialog).  The secrets keyring is
+/// copied to `{dest_path}.keyring` so the backup's credentials can be opened
+/// after a key rotation or on another machine.
 ///
 /// Requires Admin role (data export is a privileged operation).
 #[tracing::instrument(skip_all)]
@@ -373,10 +376,14 @@ pub async fn export_data_backup(
     let _ctx = resolve_context!(&state, &correlation_id, UserRole::Admin);
     let pool = state.db.pool().clone();
     let db_path = state.app_config.app_data_dir.join("rpma.db");
+    let keyring_path = state.app_config.app_data_dir.join(KEYRING_FILE_NAME);
 
     tokio::task::spawn_blocking(move || {
         crate::shared::services::system::SystemService::export_data_backup(
-            &pool, &db_path, &dest_path,
+            &pool,
+            &db_path,
+            &keyring_path,
+            &dest_path,
         )
     })
     .await
@@ -389,7 +396,9 @@ pub async fn export_data_backup(
 /// The backup is validated (SQLite magic header) and copied to
 /// `{app_data_dir}/rpma.restore.db`.  On the next application startup the
 /// staged file is automatically renamed to `rpma.db` before the connection
-/// pool is opened.
+/// pool is opened.  The keyring saved next to the backup is staged to
+/// `{app_data_dir}/secrets.restore.keyring` and replaces `secrets.keyring` at
+/// the same time.
 ///
 /// Requires Admin role (restore overwrites all local data).
 #[tracing::instrument(skip_all)]
@@ -401,24 +410,57 @@ pub async fn restore_data_backup(
 ) -> Result<serde_json::Value, String> {
     let _ctx = resolve_context!(&state, &correlation_id, UserRole::Admin);
     let staged_path = state.app_config.app_data_dir.join("rpma.restore.db");
+    let staged_keyring_path = state
+        .app_config
+        .app_data_dir
+        .join(RESTORE_KEYRING_FILE_NAME);
 
-    tokio::task::spawn_blocking(move || {
+    let keyring_restored = tokio::task::spawn_blocking(move || {
         crate::shared::services::system::SystemService::stage_restore_backup(
             &source_path,
             &staged_path,
+            &staged_keyring_path,
         )
     })
     .await
     .map_err(|e| String::from(AppError::Internal(format!("Task join error: {}", e))))?
     .map_err(|e| String::from(AppError::Internal(e)))?;
 
+    let message = if keyring_restored {
+        "Backup staged successfully. Quit and restart the application to complete the restore."
+    } else {
+        "Backup staged successfully. Quit and restart the application to complete the restore. \
+         The backup has no secrets keyring: stored credentials may need to be entered again."
+    };
     Ok(serde_json::json!({
         "success": true,
         "staged": true,
-        "message": "Backup staged successfully. Quit and restart the application to complete the restore."
+        "keyring_restored": keyring_restored,
+        "message": message
     }))
 }
 
+/// Rotate the key that encrypts stored credentials (integration secrets,
+/// cloud storage, SMTP and SMS gateway credentials) and re-encrypt them.
+///
+/// Requires Admin role.
+#[tracing::instrument(skip_all)]
+#[tauri::command]
+pub async fn rotate_secrets_key(
+    state: AppState<'_>,
+    correlation_id: Option<String>,
+) -> Result<serde_json::Value, String> {
+    let _ctx = resolve_context!(&state, &correlation_id, UserRole::Admin);
+    let pool = state.db.pool().clone();
+
+    tokio::task::spawn_blocking(move || {
+        crate::shared::services::system::SystemService::rotate_secrets_key(&pool)
+    })
+    .await
+    .map_err(|e| String::from(AppError::Internal(format!("Task join error: {}", e))))?
+    .map_err(|e| String::from(AppError::Internal(e)))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -290,6 +290,7 @@ impl Database {
             34 => self.apply_migration_34(),
             40 => self.apply_migration_40(),
             65 => self.apply_migration_65(),
+            80 => self.apply_migration_80(),
             _ => {
                 // Try to apply generic SQL migration for all other versions.
                 self.apply_sql_migration(version)
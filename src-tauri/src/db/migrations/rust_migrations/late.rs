@@ -1,9 +1,11 @@
 //! Rust-implemented migrations 040+.
 //!
 //! Late-stage migrations that add activity and reference lookup indexes,
-//! and schema constraint updates (065+).
+//! schema constraint updates (065+), and the secrets vault upgrade (080).
 
 use crate::db::{Database, DbResult};
+use crate::shared::db::secrets_repository::SecretsRepository;
+use crate::shared::services::secrets_vault::secrets_vault;
 use rusqlite::params;
 
 impl Database {
@@ -256,4 +258,36 @@ impl Database {
         );
         Ok(())
     }
+
+    /// Migration 080: Seal stored credentials with the secrets vault.
+    ///
+    /// Needs the vault key, which is not in the database, so it cannot be a
+    /// SQL file: startup installs the vault before migrations run. Integration
+    /// secrets move from the XOR scheme to AEAD; settings credentials that
+    /// were kept in plain text are sealed. Idempotent: sealed values are left
+    /// alone. A secret that cannot be decoded does not block startup: it is
+    /// logged and left as stored, and the integration needs its secret
+    /// entered again.
+    pub(in crate::db::migrations) fn apply_migration_80(&self) -> DbResult<()> {
+        let conn = self.get_connection()?;
+        tracing::info!("Migration 080: Sealing stored credentials");
+
+        let report = SecretsRepository::reseal_all(&conn, &secrets_vault())
+            .map_err(|e| format!("Migration 080: {}", e))?;
+        for secret in &report.undecodable {
+            tracing::warn!(
+                secret = %secret,
+                "Migration 080: integration secret cannot be decoded; re-enter it"
+            );
+        }
+
+        conn.execute(
+            "INSERT INTO schema_version (version) VALUES (?1)",
+            params![80],
+        )
+        .map_err(|e| e.to_string())?;
+
+        tracing::info!("Migration 080: sealed {} credential(s)", report.resealed);
+        Ok(())
+    }
 }
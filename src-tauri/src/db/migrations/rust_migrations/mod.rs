@@ -10,7 +10,7 @@
 //! - `mid`              — migrations 016–018 (indexes, cache, audit log)
 //! - `inventory_audit`  — migrations 024–027 (inventory system, audit, perf indexes, constraints)
 //! - `user_integrity`   — migrations 028–034 (user columns, sessions, FKs, CHECK constraints)
-//! - `late`             — migration 040+ (activity indexes)
+//! - `late`             — migration 040+ (activity indexes, secrets vault upgrade)
 
 mod early;
 mod inventory_audit;
@@ -650,3 +650,119 @@ fn test_no_user_sessions_on_fresh_db() {
         "sessions table must exist on a fresh database"
     );
 }
+
+/// Migration 080 must seal integration secrets still in the pre-vault XOR
+/// format and plain-text settings credentials, leaving both readable.
+#[test]
+fn test_migration_080_seals_legacy_secrets() {
+    use crate::shared::db::secrets_repository::SecretsRepository;
+    use crate::shared::services::secrets_vault::{secrets_vault, SecretsVault};
+    use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+    use sha2::{Digest, Sha256};
+
+    let temp_file = NamedTempFile::new().expect("Failed to create temp file");
+    let db = Database::new(temp_file.path(), "").expect("Failed to create database");
+    db.init().expect("Failed to init schema");
+    db.migrate(79).expect("Failed to migrate to 079");
+
+    // The scheme integrations used before the vault.
+    let key = std::env::var("RPMA_DB_KEY").unwrap_or_else(|_| "rpma-dev-key".to_string());
+    let digest = Sha256::digest(key.as_bytes());
+    let legacy: Vec<u8> = b"legacy-token"
+        .iter()
+        .enumerate()
+        .map(|(index, byte)| byte ^ digest[index % digest.len()])
+        .collect();
+    {
+        let conn = db.get_connection().expect("conn");
+        conn.execute(
+            "INSERT INTO integration_configs
+                 (id, name, endpoint_url, encrypted_secret, status, created_at, updated_at)
+             VALUES ('int-legacy', 'Legacy', 'https://example.com/hook', ?1, 'active', 0, 0)",
+            params![BASE64.encode(legacy)],
+        )
+        .expect("insert legacy integration");
+        conn.execute(
+            "UPDATE app_settings SET storage_settings = ?1 WHERE id = 'global'",
+            params![r#"{"photo_storage_type":"cloud","cloud_secret_key":"s3-secret"}"#],
+        )
+        .expect("store plain-text credential");
+    }
+
+    db.migrate(80).expect("Migration 080 must succeed");
+
+    let conn = db.get_connection().expect("conn");
+    let sealed_secret: String = conn
+        .query_row(
+            "SELECT encrypted_secret FROM integration_configs WHERE id = 'int-legacy'",
+            [],
+            |row| row.get(0),
+        )
+        .expect("read integration secret");
+    assert!(SecretsVault::is_sealed(&sealed_secret));
+    assert_eq!(
+        secrets_vault().open(&sealed_secret).expect("open secret"),
+        "legacy-token"
+    );
+
+    let storage: String = conn
+        .query_row(
+            "SELECT storage_settings FROM app_settings WHERE id = 'global'",
+            [],
+            |row| row.get(0),
+        )
+        .expect("read storage settings");
+    let storage: serde_json::Value = serde_json::from_str(&storage).expect("storage json");
+    let sealed_key = storage["cloud_secret_key"].as_str().expect("sealed key");
+    assert_eq!(storage["photo_storage_type"], "cloud");
+    assert_eq!(
+        secrets_vault().open(sealed_key).expect("open credential"),
+        "s3-secret"
+    );
+
+    // Sealed values are left alone on a second pass.
+    assert_eq!(
+        SecretsRepository::reseal_all(&conn, &secrets_vault())
+            .expect("reseal")
+            .resealed,
+        0
+    );
+}
+
+/// A legacy secret that cannot be decoded does not fail migration 080: it is
+/// reported and kept as stored for re-entry.
+#[test]
+fn test_migration_080_skips_undecodable_secrets() {
+    use crate::shared::db::secrets_repository::SecretsRepository;
+    use crate::shared::services::secrets_vault::secrets_vault;
+
+    let temp_file = NamedTempFile::new().expect("Failed to create temp file");
+    let db = Database::new(temp_file.path(), "").expect("Failed to create database");
+    db.init().expect("Failed to init schema");
+    db.migrate(79).expect("Failed to migrate to 079");
+    db.get_connection()
+        .expect("conn")
+        .execute(
+            "INSERT INTO integration_configs
+                 (id, name, endpoint_url, encrypted_secret, status, created_at, updated_at)
+             VALUES ('int-garbled', 'Garbled', 'https://example.com/hook', 'not base64!', 'active', 0, 0)",
+            [],
+        )
+        .expect("insert garbled integration");
+
+    db.migrate(80).expect("An undecodable secret must not block migration 080");
+
+    let conn = db.get_connection().expect("conn");
+    let stored: Option<String> = conn
+        .query_row(
+            "SELECT encrypted_secret FROM integration_configs WHERE id = 'int-garbled'",
+            [],
+            |row| row.get(0),
+        )
+        .expect("read integration secret");
+    assert_eq!(stored.as_deref(), Some("not base64!"));
+
+    let report = SecretsRepository::reseal_all(&conn, &secrets_vault()).expect("reseal");
+    assert_eq!(report.resealed, 0);
+    assert_eq!(report.undecodable, vec!["int-garbled.encrypted_secret"]);
+}
@@ -259,6 +259,8 @@ impl Database {
     }
 
     pub async fn new_in_memory() -> DbResult<Self> {
+        // In-memory databases are throwaway; migration 080 still needs a vault.
+        crate::shared::services::secrets_vault::install_ephemeral_secrets_vault();
         // Use a shared in-memory database so multiple pooled connections see the same schema/data.
         let db_name = format!(
             "file:rpma_test_{}?mode=memory&cache=shared",
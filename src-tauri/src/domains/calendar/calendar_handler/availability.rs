@@ -0,0 +1,465 @@
+//! Technician availability: working time, absences, public holidays and
+//! daily capacity (see [`crate::domains::calendar::domain::availability`]).
+//!
+//! Scheduling checks bookings against it. Depending on the organization's
+//! `business_capacity_policy`, a booking outside working time or over
+//! capacity is scheduled with a warning (`warn`, the default) or refused
+//! like an overlap (`reject`); forcing the booking skips the check.
+
+use super::*;
+use crate::commands::AppError;
+use crate::domains::calendar::domain::availability::{
+    event_span, load_percent, minutes_on, occupies_capacity, parse_clock, parse_weekday,
+    planned_minutes, task_minutes, task_span, AvailabilityIssue, TechnicianSchedule, WorkingWeek,
+    ABSENCE_KINDS,
+};
+use crate::domains::calendar::domain::recurrence::parse_timezone;
+use crate::shared::services::cross_domain::InterventionType;
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime};
+use chrono_tz::Tz;
+use std::collections::HashMap;
+
+pub const CAPACITY_POLICY_REJECT: &str = "reject";
+
+/// The organization's working week and scheduling settings.
+pub struct OrganizationCalendar {
+    pub week: WorkingWeek,
+    pub timezone: Tz,
+    pub reject_over_capacity: bool,
+}
+
+/// Time technicians have booked.
+#[derive(Default)]
+pub struct Bookings {
+    /// Minutes per technician and day.
+    pub minutes: HashMap<(String, NaiveDate), i32>,
+    /// Timed bookings per technician, in shop time.
+    pub busy: HashMap<String, Vec<(NaiveDateTime, NaiveDateTime)>>,
+}
+
+/// Availability problems of one booking.
+pub struct BookingCheck {
+    pub issues: Vec<AvailabilityIssue>,
+    pub blocking: bool,
+}
+
+pub(super) fn parse_date(value: &str, field: &str) -> Result<NaiveDate, AppError> {
+    NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d")
+        .map_err(|_| AppError::Validation(format!("{} must be in YYYY-MM-DD format", field)))
+}
+
+pub(super) fn format_date(date: NaiveDate) -> String {
+    date.format("%Y-%m-%d").to_string()
+}
+
+impl CalendarService {
+    pub fn organization_calendar(&self) -> Result<OrganizationCalendar, AppError> {
+        let settings = self.organization.get_all_settings()?;
+        let business = settings.business;
+        let week = WorkingWeek::parse(
+            &business.business_hours_start,
+            &business.business_hours_end,
+            &business.business_days,
+        )
+        .or_else(|e| {
+            tracing::warn!("Invalid business hours, using defaults: {}", e);
+            let defaults = crate::shared::services::cross_domain::BusinessSettings::default();
+            WorkingWeek::parse(
+                &defaults.business_hours_start,
+                &defaults.business_hours_end,
+                &defaults.business_days,
+            )
+        })
+        .map_err(AppError::Internal)?;
+        Ok(OrganizationCalendar {
+            week,
+            timezone: parse_timezone(&settings.regional.timezone)
+                .unwrap_or(chrono_tz::Europe::Paris),
+            reject_over_capacity: business.business_capacity_policy == CAPACITY_POLICY_REJECT,
+        })
+    }
+
+    /// Working time, absences and holidays of each technician in `ids` from
+    /// `start` to `end`.
+    pub fn technician_schedules(
+        &self,
+        organization: &OrganizationCalendar,
+        ids: &[String],
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<HashMap<String, TechnicianSchedule>, AppError> {
+        let (start, end) = (format_date(start), format_date(end));
+        let holidays = self.availability.list_holidays(&start, &end)?;
+        let mut absences = self.availability.list_absences(None, &start, &end)?;
+        let overrides: HashMap<String, TechnicianAvailability> = self
+            .availability
+            .list_availability()?
+            .into_iter()
+            .map(|availability| (availability.technician_id.clone(), availability))
+            .collect();
+
+        let mut schedules = HashMap::new();
+        for id in ids {
+            let own = overrides.get(id);
+            let week = match own {
+                Some(own) => organization.week.with_overrides(own).unwrap_or_else(|e| {
+                    tracing::warn!(technician_id = %id, "Invalid working time ignored: {}", e);
+                    organization.week.clone()
+                }),
+                None => organization.week.clone(),
+            };
+            let (own_absences, rest): (Vec<_>, Vec<_>) = absences
+                .into_iter()
+                .partition(|absence| &absence.technician_id == id);
+            absences = rest;
+            schedules.insert(
+                id.clone(),
+                TechnicianSchedule {
+                    week,
+                    daily_capacity_minutes: own.and_then(|own| own.daily_capacity_minutes),
+                    absences: own_absences,
+                    holidays: holidays.clone(),
+                },
+            );
+        }
+        Ok(schedules)
+    }
+
+    /// What technicians in `ids` have booked from `start` to `end`: their
+    /// tasks, and their own timed events. Tasks in `exclude` are left out.
+    pub async fn bookings(
+        &self,
+        organization: &OrganizationCalendar,
+        ids: &[String],
+        start: NaiveDate,
+        end: NaiveDate,
+        exclude: &[String],
+    ) -> Result<Bookings, AppError> {
+        let mut bookings = Bookings::default();
+        if ids.is_empty() {
+            return Ok(bookings);
+        }
+        let tasks = self.repo.get_tasks(
+            &CalendarDateRange {
+                start_date: format_date(start),
+                end_date: format_date(end),
+            },
+            Some(ids),
+            None,
+        )?;
+        for task in tasks {
+            if exclude.contains(&task.id) || !occupies_capacity(&task.status) {
+                continue;
+            }
+            let (Some(technician_id), Ok(date)) = (
+                task.technician_id.clone(),
+                NaiveDate::parse_from_str(&task.scheduled_date, "%Y-%m-%d"),
+            ) else {
+                continue;
+            };
+            *bookings
+                .minutes
+                .entry((technician_id.clone(), date))
+                .or_default() += task_minutes(&task);
+            if let Some(span) = task_span(&task) {
+                bookings.busy.entry(technician_id).or_default().push(span);
+            }
+        }
+
+        // Events are matched on their stored times; a day's margin on each
+        // side covers times stored with an offset.
+        let events = self
+            .events
+            .find_by_date_range(
+                &format_date(start - Duration::days(1)),
+                &format!("{}T23:59:59", format_date(end + Duration::days(1))),
+                None,
+            )
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        for event in events {
+            let Some(technician_id) = event.technician_id.clone() else {
+                continue;
+            };
+            if !ids.contains(&technician_id) {
+                continue;
+            }
+            let Some(span) = event_span(&event, organization.timezone) else {
+                continue;
+            };
+            let mut date = span.0.date().max(start);
+            while date <= span.1.date().min(end) {
+                let minutes = minutes_on(span, date);
+                if minutes > 0 {
+                    *bookings
+                        .minutes
+                        .entry((technician_id.clone(), date))
+                        .or_default() += minutes;
+                }
+                date += Duration::days(1);
+            }
+            bookings.busy.entry(technician_id).or_default().push(span);
+        }
+        Ok(bookings)
+    }
+
+    /// Whether booking `task_id` for `technician_id` on `date` fits their
+    /// working time and capacity.
+    pub async fn check_availability(
+        &self,
+        task_id: &str,
+        technician_id: &str,
+        date: &str,
+        new_start: Option<&str>,
+        new_end: Option<&str>,
+    ) -> Result<BookingCheck, AppError> {
+        let Ok(day) = NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d") else {
+            return Ok(BookingCheck {
+                issues: vec![],
+                blocking: false,
+            });
+        };
+        let organization = self.organization_calendar()?;
+        let ids = [technician_id.to_string()];
+        let schedule = self
+            .technician_schedules(&organization, &ids, day, day)?
+            .remove(technician_id)
+            .ok_or_else(|| AppError::Internal("Technician schedule missing".to_string()))?;
+        let booked = self
+            .bookings(&organization, &ids, day, day, &[task_id.to_string()])
+            .await?
+            .minutes
+            .get(&(technician_id.to_string(), day))
+            .copied()
+            .unwrap_or(0);
+
+        let times = match (
+            new_start.and_then(parse_clock),
+            new_end.and_then(parse_clock),
+        ) {
+            (Some(start), Some(end)) if end > start => Some((start, end)),
+            _ => None,
+        };
+        let estimated = self.availability.task_estimated_duration(task_id)?;
+        let minutes = planned_minutes(new_start, new_end, estimated);
+        Ok(BookingCheck {
+            issues: schedule.check_booking(day, times, minutes, booked),
+            blocking: organization.reject_over_capacity,
+        })
+    }
+
+    // ── Availability management ──────────────────────────────────────────────
+
+    /// A technician's own working time; unset fields follow the organization.
+    pub fn get_technician_availability(
+        &self,
+        technician_id: &str,
+    ) -> Result<TechnicianAvailability, AppError> {
+        Ok(self
+            .availability
+            .get_availability(technician_id)?
+            .unwrap_or_else(|| TechnicianAvailability {
+                technician_id: technician_id.to_string(),
+                ..Default::default()
+            }))
+    }
+
+    pub fn set_technician_availability(
+        &self,
+        mut availability: TechnicianAvailability,
+        user_id: &str,
+    ) -> Result<TechnicianAvailability, AppError> {
+        if !self.availability.user_exists(&availability.technician_id)? {
+            return Err(AppError::NotFound(format!(
+                "Technician {} not found",
+                availability.technician_id
+            )));
+        }
+        if let Some(days) = availability.working_days.take() {
+            let mut numbers = days
+                .iter()
+                .map(|day| {
+                    parse_weekday(day)
+                        .map(|weekday| weekday.number_from_monday())
+                        .ok_or_else(|| AppError::Validation(format!("Invalid weekday: {}", day)))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            numbers.sort_unstable();
+            numbers.dedup();
+            availability.working_days =
+                Some(numbers.into_iter().map(|day| day.to_string()).collect());
+        }
+        if let Some(minutes) = availability.daily_capacity_minutes {
+            if !(0..=24 * 60).contains(&minutes) {
+                return Err(AppError::Validation(
+                    "daily_capacity_minutes must be between 0 and 1440".to_string(),
+                ));
+            }
+        }
+        let mut skills = Vec::new();
+        for skill in &availability.skills {
+            let skill = skill.trim().to_lowercase();
+            skill
+                .parse::<InterventionType>()
+                .map_err(AppError::Validation)?;
+            if !skills.contains(&skill) {
+                skills.push(skill);
+            }
+        }
+        availability.skills = skills;
+        self.organization_calendar()?
+            .week
+            .with_overrides(&availability)
+            .map_err(AppError::Validation)?;
+
+        self.availability
+            .save_availability(&availability, user_id)?;
+        Ok(availability)
+    }
+
+    pub fn list_absences(
+        &self,
+        technician_id: Option<&str>,
+        start_date: &str,
+        end_date: &str,
+    ) -> Result<Vec<TechnicianAbsence>, AppError> {
+        parse_date(start_date, "start_date")?;
+        parse_date(end_date, "end_date")?;
+        self.availability
+            .list_absences(technician_id, start_date.trim(), end_date.trim())
+    }
+
+    pub fn add_absence(
+        &self,
+        input: CreateAbsenceInput,
+        user_id: &str,
+    ) -> Result<TechnicianAbsence, AppError> {
+        let start = parse_date(&input.start_date, "start_date")?;
+        let end = parse_date(&input.end_date, "end_date")?;
+        if end < start {
+            return Err(AppError::Validation(
+                "end_date must not be before start_date".to_string(),
+            ));
+        }
+        let kind = input
+            .kind
+            .as_deref()
+            .unwrap_or("leave")
+            .trim()
+            .to_lowercase();
+        if !ABSENCE_KINDS.contains(&kind.as_str()) {
+            return Err(AppError::Validation(format!(
+                "Invalid absence kind: {} (expected one of {})",
+                kind,
+                ABSENCE_KINDS.join(", ")
+            )));
+        }
+        if !self.availability.user_exists(&input.technician_id)? {
+            return Err(AppError::NotFound(format!(
+                "Technician {} not found",
+                input.technician_id
+            )));
+        }
+        let input = CreateAbsenceInput {
+            start_date: format_date(start),
+            end_date: format_date(end),
+            ..input
+        };
+        self.availability.create_absence(&input, &kind, user_id)
+    }
+
+    pub fn delete_absence(&self, id: &str) -> Result<bool, AppError> {
+        self.availability.delete_absence(id)
+    }
+
+    pub fn list_public_holidays(
+        &self,
+        start_date: &str,
+        end_date: &str,
+    ) -> Result<Vec<PublicHoliday>, AppError> {
+        parse_date(start_date, "start_date")?;
+        parse_date(end_date, "end_date")?;
+        self.availability
+            .list_holidays(start_date.trim(), end_date.trim())
+    }
+
+    pub fn set_public_holiday(&self, holiday: PublicHoliday) -> Result<PublicHoliday, AppError> {
+        let date = parse_date(&holiday.date, "date")?;
+        let name = holiday.name.trim();
+        if name.is_empty() {
+            return Err(AppError::Validation(
+                "Holiday name cannot be empty".to_string(),
+            ));
+        }
+        let holiday = PublicHoliday {
+            date: format_date(date),
+            name: name.to_string(),
+        };
+        self.availability.save_holiday(&holiday)?;
+        Ok(holiday)
+    }
+
+    pub fn delete_public_holiday(&self, date: &str) -> Result<bool, AppError> {
+        self.availability.delete_holiday(date.trim())
+    }
+
+    // ── Capacity heat map ────────────────────────────────────────────────────
+
+    /// Capacity and booked time per technician for the Monday-to-Sunday week
+    /// containing `week_start`. All active technicians unless `technician_ids`
+    /// is given.
+    pub async fn capacity_heatmap(
+        &self,
+        week_start: &str,
+        technician_ids: Option<Vec<String>>,
+    ) -> Result<CapacityHeatmap, AppError> {
+        let day = parse_date(week_start, "week_start")?;
+        let monday = day - Duration::days(day.weekday().num_days_from_monday().into());
+        let sunday = monday + Duration::days(6);
+
+        let technicians = self
+            .availability
+            .list_technicians(technician_ids.as_deref())?;
+        let ids: Vec<String> = technicians.iter().map(|(id, _)| id.clone()).collect();
+        let organization = self.organization_calendar()?;
+        let schedules = self.technician_schedules(&organization, &ids, monday, sunday)?;
+        let booked = self
+            .bookings(&organization, &ids, monday, sunday, &[])
+            .await?
+            .minutes;
+
+        let technicians = technicians
+            .into_iter()
+            .filter_map(|(id, name)| {
+                let schedule = schedules.get(&id)?;
+                let days: Vec<DayCapacity> = (0..7)
+                    .map(|offset| {
+                        let date = monday + Duration::days(offset);
+                        let (status, capacity_minutes) = schedule.day(date);
+                        let booked_minutes = booked.get(&(id.clone(), date)).copied().unwrap_or(0);
+                        DayCapacity {
+                            date: format_date(date),
+                            status,
+                            capacity_minutes,
+                            booked_minutes,
+                            load_percent: load_percent(booked_minutes, capacity_minutes),
+                        }
+                    })
+                    .collect();
+                Some(TechnicianWeekCapacity {
+                    technician_id: id,
+                    technician_name: name,
+                    capacity_minutes: days.iter().map(|day| day.capacity_minutes).sum(),
+                    booked_minutes: days.iter().map(|day| day.booked_minutes).sum(),
+                    days,
+                })
+            })
+            .collect();
+
+        Ok(CapacityHeatmap {
+            week_start: format_date(monday),
+            week_end: format_date(sunday),
+            technicians,
+        })
+    }
+}
@@ -0,0 +1,382 @@
+//! AvailabilityRepository — technician working time, absences and public holidays.
+
+use super::*;
+use crate::commands::AppError;
+use crate::db::Database;
+use rusqlite::{params, OptionalExtension};
+use std::sync::Arc;
+
+/// Repository for technician availability.
+pub struct AvailabilityRepository {
+    db: Arc<Database>,
+}
+
+fn db_error(e: impl std::fmt::Display) -> AppError {
+    AppError::Database(e.to_string())
+}
+
+fn availability_from_row(row: &rusqlite::Row) -> rusqlite::Result<TechnicianAvailability> {
+    let working_days: Option<String> = row.get(3)?;
+    let skills: String = row.get(5)?;
+    Ok(TechnicianAvailability {
+        technician_id: row.get(0)?,
+        work_start: row.get(1)?,
+        work_end: row.get(2)?,
+        working_days: working_days.and_then(|days| serde_json::from_str(&days).ok()),
+        daily_capacity_minutes: row.get(4)?,
+        skills: serde_json::from_str(&skills).unwrap_or_default(),
+    })
+}
+
+fn absence_from_row(row: &rusqlite::Row) -> rusqlite::Result<TechnicianAbsence> {
+    Ok(TechnicianAbsence {
+        id: row.get(0)?,
+        technician_id: row.get(1)?,
+        start_date: row.get(2)?,
+        end_date: row.get(3)?,
+        kind: row.get(4)?,
+        note: row.get(5)?,
+        created_at: row.get(6)?,
+        created_by: row.get(7)?,
+    })
+}
+
+/// A task as the slot finder sees it.
+#[derive(Debug, Clone)]
+pub struct PlanningTask {
+    pub id: String,
+    pub task_number: String,
+    pub title: String,
+    pub technician_id: Option<String>,
+    /// Minutes.
+    pub estimated_duration: Option<i32>,
+    pub ppf_zones: Vec<String>,
+    pub vehicle_plate: String,
+    /// Type of the task's intervention, when it has one.
+    pub intervention_type: Option<String>,
+}
+
+fn planning_task_from_row(row: &rusqlite::Row) -> rusqlite::Result<PlanningTask> {
+    let ppf_zones: Option<String> = row.get(5)?;
+    Ok(PlanningTask {
+        id: row.get(0)?,
+        task_number: row.get(1)?,
+        title: row.get(2)?,
+        technician_id: row.get(3)?,
+        estimated_duration: row.get(4)?,
+        ppf_zones: ppf_zones
+            .and_then(|zones| serde_json::from_str(&zones).ok())
+            .unwrap_or_default(),
+        vehicle_plate: row.get::<_, Option<String>>(6)?.unwrap_or_default(),
+        intervention_type: row.get(7)?,
+    })
+}
+
+const PLANNING_TASK_COLUMNS: &str =
+    "t.id, t.task_number, t.title, t.technician_id, t.estimated_duration, t.ppf_zones,
+     t.vehicle_plate,
+     (SELECT i.intervention_type FROM interventions i WHERE i.task_id = t.id
+      ORDER BY i.created_at DESC LIMIT 1)";
+
+const AVAILABILITY_COLUMNS: &str =
+    "technician_id, work_start, work_end, working_days, daily_capacity_minutes, skills";
+const ABSENCE_COLUMNS: &str =
+    "id, technician_id, start_date, end_date, kind, note, created_at, created_by";
+
+impl AvailabilityRepository {
+    pub fn new(db: Arc<Database>) -> Self {
+        Self { db }
+    }
+
+    pub fn get_availability(
+        &self,
+        technician_id: &str,
+    ) -> Result<Option<TechnicianAvailability>, AppError> {
+        let conn = self.db.get_connection().map_err(db_error)?;
+        conn.query_row(
+            &format!(
+                "SELECT {} FROM technician_availability WHERE technician_id = ?1",
+                AVAILABILITY_COLUMNS
+            ),
+            params![technician_id],
+            availability_from_row,
+        )
+        .optional()
+        .map_err(db_error)
+    }
+
+    /// Overrides of every technician that has any.
+    pub fn list_availability(&self) -> Result<Vec<TechnicianAvailability>, AppError> {
+        let conn = self.db.get_connection().map_err(db_error)?;
+        let mut stmt = conn
+            .prepare(&format!(
+                "SELECT {} FROM technician_availability",
+                AVAILABILITY_COLUMNS
+            ))
+            .map_err(db_error)?;
+        let rows = stmt
+            .query_map([], availability_from_row)
+            .map_err(db_error)?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(db_error)?;
+        Ok(rows)
+    }
+
+    pub fn save_availability(
+        &self,
+        availability: &TechnicianAvailability,
+        user_id: &str,
+    ) -> Result<(), AppError> {
+        let working_days = availability
+            .working_days
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()
+            .map_err(|e| AppError::Internal(e.to_string()))?;
+        let skills = serde_json::to_string(&availability.skills)
+            .map_err(|e| AppError::Internal(e.to_string()))?;
+        self.db
+            .execute(
+                "INSERT INTO technician_availability
+                 (technician_id, work_start, work_end, working_days, daily_capacity_minutes,
+                  skills, updated_at, updated_by)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+                 ON CONFLICT(technician_id) DO UPDATE SET
+                    work_start = excluded.work_start,
+                    work_end = excluded.work_end,
+                    working_days = excluded.working_days,
+                    daily_capacity_minutes = excluded.daily_capacity_minutes,
+                    skills = excluded.skills,
+                    updated_at = excluded.updated_at,
+                    updated_by = excluded.updated_by",
+                params![
+                    availability.technician_id,
+                    availability.work_start,
+                    availability.work_end,
+                    working_days,
+                    availability.daily_capacity_minutes,
+                    skills,
+                    chrono::Utc::now().timestamp_millis(),
+                    user_id
+                ],
+            )
+            .map(|_| ())
+            .map_err(AppError::Database)
+    }
+
+    /// Absences overlapping `start_date`..=`end_date`, of one technician or all.
+    pub fn list_absences(
+        &self,
+        technician_id: Option<&str>,
+        start_date: &str,
+        end_date: &str,
+    ) -> Result<Vec<TechnicianAbsence>, AppError> {
+        let conn = self.db.get_connection().map_err(db_error)?;
+        let mut stmt = conn
+            .prepare(&format!(
+                "SELECT {} FROM technician_absences
+                 WHERE start_date <= ?2 AND end_date >= ?1
+                   AND (?3 IS NULL OR technician_id = ?3)
+                 ORDER BY start_date, technician_id",
+                ABSENCE_COLUMNS
+            ))
+            .map_err(db_error)?;
+        let rows = stmt
+            .query_map(
+                params![start_date, end_date, technician_id],
+                absence_from_row,
+            )
+            .map_err(db_error)?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(db_error)?;
+        Ok(rows)
+    }
+
+    pub fn create_absence(
+        &self,
+        input: &CreateAbsenceInput,
+        kind: &str,
+        user_id: &str,
+    ) -> Result<TechnicianAbsence, AppError> {
+        let absence = TechnicianAbsence {
+            id: crate::shared::utils::uuid::generate_uuid_string(),
+            technician_id: input.technician_id.clone(),
+            start_date: input.start_date.clone(),
+            end_date: input.end_date.clone(),
+            kind: kind.to_string(),
+            note: input.note.clone(),
+            created_at: chrono::Utc::now().timestamp_millis(),
+            created_by: Some(user_id.to_string()),
+        };
+        self.db
+            .execute(
+                &format!(
+                    "INSERT INTO technician_absences ({}) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                    ABSENCE_COLUMNS
+                ),
+                params![
+                    absence.id,
+                    absence.technician_id,
+                    absence.start_date,
+                    absence.end_date,
+                    absence.kind,
+                    absence.note,
+                    absence.created_at,
+                    absence.created_by
+                ],
+            )
+            .map_err(AppError::Database)?;
+        Ok(absence)
+    }
+
+    pub fn delete_absence(&self, id: &str) -> Result<bool, AppError> {
+        self.db
+            .execute("DELETE FROM technician_absences WHERE id = ?1", params![id])
+            .map(|deleted| deleted > 0)
+            .map_err(AppError::Database)
+    }
+
+    pub fn list_holidays(
+        &self,
+        start_date: &str,
+        end_date: &str,
+    ) -> Result<Vec<PublicHoliday>, AppError> {
+        let conn = self.db.get_connection().map_err(db_error)?;
+        let mut stmt = conn
+            .prepare(
+                "SELECT date, name FROM public_holidays
+                 WHERE date BETWEEN ?1 AND ?2 ORDER BY date",
+            )
+            .map_err(db_error)?;
+        let rows = stmt
+            .query_map(params![start_date, end_date], |row| {
+                Ok(PublicHoliday {
+                    date: row.get(0)?,
+                    name: row.get(1)?,
+                })
+            })
+            .map_err(db_error)?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(db_error)?;
+        Ok(rows)
+    }
+
+    pub fn save_holiday(&self, holiday: &PublicHoliday) -> Result<(), AppError> {
+        self.db
+            .execute(
+                "INSERT INTO public_holidays (date, name) VALUES (?1, ?2)
+                 ON CONFLICT(date) DO UPDATE SET name = excluded.name",
+                params![holiday.date, holiday.name],
+            )
+            .map(|_| ())
+            .map_err(AppError::Database)
+    }
+
+    pub fn delete_holiday(&self, date: &str) -> Result<bool, AppError> {
+        self.db
+            .execute("DELETE FROM public_holidays WHERE date = ?1", params![date])
+            .map(|deleted| deleted > 0)
+            .map_err(AppError::Database)
+    }
+
+    /// Active technicians as `(id, full name)`, all of them or those in `ids`.
+    pub fn list_technicians(
+        &self,
+        ids: Option<&[String]>,
+    ) -> Result<Vec<(String, String)>, AppError> {
+        let conn = self.db.get_connection().map_err(db_error)?;
+        let mut sql = String::from(
+            "SELECT id, COALESCE(full_name, username, id) FROM users
+             WHERE role = 'technician' AND is_active = 1 AND deleted_at IS NULL",
+        );
+        let ids = ids.unwrap_or_default();
+        if !ids.is_empty() {
+            sql.push_str(&format!(
+                " AND id IN ({})",
+                crate::shared::utils::sql::in_clause_placeholders(ids)
+            ));
+        }
+        sql.push_str(" ORDER BY full_name");
+        let mut stmt = conn.prepare(&sql).map_err(db_error)?;
+        let rows = stmt
+            .query_map(rusqlite::params_from_iter(ids), |row| {
+                Ok((row.get(0)?, row.get(1)?))
+            })
+            .map_err(db_error)?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(db_error)?;
+        Ok(rows)
+    }
+
+    pub fn user_exists(&self, id: &str) -> Result<bool, AppError> {
+        let conn = self.db.get_connection().map_err(db_error)?;
+        conn.query_row(
+            "SELECT COUNT(*) FROM users WHERE id = ?1 AND deleted_at IS NULL",
+            params![id],
+            |row| row.get::<_, i64>(0),
+        )
+        .map(|count| count > 0)
+        .map_err(db_error)
+    }
+
+    /// Estimated duration of a task, in minutes.
+    pub fn task_estimated_duration(&self, task_id: &str) -> Result<Option<i32>, AppError> {
+        let conn = self.db.get_connection().map_err(db_error)?;
+        conn.query_row(
+            "SELECT estimated_duration FROM tasks WHERE id = ?1 AND deleted_at IS NULL",
+            params![task_id],
+            |row| row.get(0),
+        )
+        .optional()
+        .map(Option::flatten)
+        .map_err(db_error)
+    }
+
+    pub fn get_planning_task(&self, task_id: &str) -> Result<Option<PlanningTask>, AppError> {
+        let conn = self.db.get_connection().map_err(db_error)?;
+        conn.query_row(
+            &format!(
+                "SELECT {} FROM tasks t WHERE t.id = ?1 AND t.deleted_at IS NULL",
+                PLANNING_TASK_COLUMNS
+            ),
+            params![task_id],
+            planning_task_from_row,
+        )
+        .optional()
+        .map_err(db_error)
+    }
+
+    /// Open tasks missing a date, start time or technician, most urgent first.
+    pub fn list_unscheduled_tasks(&self) -> Result<Vec<PlanningTask>, AppError> {
+        use crate::shared::contracts::task_status::TaskStatus;
+        let open = [
+            TaskStatus::Draft,
+            TaskStatus::Pending,
+            TaskStatus::Assigned,
+            TaskStatus::Scheduled,
+        ]
+        .map(|status| status.to_string());
+        let conn = self.db.get_connection().map_err(db_error)?;
+        let mut stmt = conn
+            .prepare(&format!(
+                "SELECT {} FROM tasks t
+                 WHERE t.deleted_at IS NULL
+                   AND t.status IN (?1, ?2, ?3, ?4)
+                   AND (t.scheduled_date IS NULL OR t.scheduled_date = ''
+                        OR t.start_time IS NULL OR t.start_time = ''
+                        OR t.technician_id IS NULL OR t.technician_id = '')
+                 ORDER BY CASE t.priority
+                            WHEN 'urgent' THEN 0 WHEN 'high' THEN 1
+                            WHEN 'medium' THEN 2 ELSE 3 END,
+                          t.scheduled_date IS NULL, t.scheduled_date, t.created_at",
+                PLANNING_TASK_COLUMNS
+            ))
+            .map_err(db_error)?;
+        let rows = stmt
+            .query_map(rusqlite::params_from_iter(&open), planning_task_from_row)
+            .map_err(db_error)?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(db_error)?;
+        Ok(rows)
+    }
+}
@@ -2,6 +2,7 @@
 
 use super::*;
 use crate::db::Database;
+use crate::domains::calendar::domain::recurrence::Series;
 use crate::shared::repositories::base::{RepoError, RepoResult, Repository};
 use async_trait::async_trait;
 use rusqlite::params;
@@ -19,6 +20,9 @@ impl CalendarEventRepository {
     }
 
     /// Find events within a date range.
+    ///
+    /// Recurring series are expanded into their occurrences in the range
+    /// (see [`Series::expand`]) instead of being returned as stored.
     pub async fn find_by_date_range(
         &self,
         start_date: &str,
@@ -31,16 +35,21 @@ impl CalendarEventRepository {
                 event_type, category, task_id, client_id, technician_id, location, meeting_link,
                 is_virtual, participants, is_recurring, recurrence_rule, parent_event_id,
                 reminders, status, color, tags, notes, synced, last_synced_at,
-                created_at, updated_at, created_by, updated_by, deleted_at, deleted_by
+                created_at, updated_at, created_by, updated_by, deleted_at, deleted_by, ical_uid
             FROM calendar_events
             WHERE deleted_at IS NULL
-            AND start_datetime >= ?
-            AND end_datetime <= ?
+            AND (
+                (COALESCE(is_recurring, 0) = 0 AND start_datetime >= ? AND end_datetime <= ?)
+                OR (is_recurring = 1 AND start_datetime <= ?)
+            )
         "#
         .to_string();
 
-        let mut params_vec: Vec<rusqlite::types::Value> =
-            vec![start_date.to_string().into(), end_date.to_string().into()];
+        let mut params_vec: Vec<rusqlite::types::Value> = vec![
+            start_date.to_string().into(),
+            end_date.to_string().into(),
+            end_date.to_string().into(),
+        ];
 
         if let Some(tech_id) = technician_id {
             sql.push_str(" AND technician_id = ?");
@@ -49,10 +58,65 @@ impl CalendarEventRepository {
 
         sql.push_str(" ORDER BY start_datetime ASC");
 
-        self.db
+        let rows: Vec<CalendarEvent> = self
+            .db
             .query_as(&sql, rusqlite::params_from_iter(params_vec.iter()))
             .map_err(|e| {
                 RepoError::Database(format!("Failed to query events by date range: {}", e))
+            })?;
+
+        let mut events = Vec::with_capacity(rows.len());
+        for event in rows {
+            if !event.is_recurring {
+                events.push(event);
+                continue;
+            }
+            match Series::of(&event) {
+                Ok(series) => events.extend(series.expand(&event, start_date, end_date)),
+                Err(e) => {
+                    tracing::warn!(event_id = %event.id, "Recurring event not expanded: {}", e);
+                    if event.start_datetime.as_str() >= start_date
+                        && event.end_datetime.as_str() <= end_date
+                    {
+                        events.push(event);
+                    }
+                }
+            }
+        }
+        events.sort_by(|a, b| a.start_datetime.cmp(&b.start_datetime));
+        Ok(events)
+    }
+
+    /// Find exceptions and split-off series created from `parent_id`.
+    pub async fn find_by_parent(&self, parent_id: &str) -> RepoResult<Vec<CalendarEvent>> {
+        self.db
+            .query_as(
+                r#"SELECT id, title, description, start_datetime, end_datetime, all_day, timezone,
+                event_type, category, task_id, client_id, technician_id, location, meeting_link,
+                is_virtual, participants, is_recurring, recurrence_rule, parent_event_id,
+                reminders, status, color, tags, notes, synced, last_synced_at,
+                created_at, updated_at, created_by, updated_by, deleted_at, deleted_by, ical_uid
+            FROM calendar_events WHERE deleted_at IS NULL AND parent_event_id = ?
+            ORDER BY start_datetime ASC"#,
+                params![parent_id],
+            )
+            .map_err(|e| RepoError::Database(format!("Failed to query events by parent: {}", e)))
+    }
+
+    /// Find the event imported with iCalendar UID `uid`.
+    pub async fn find_by_ical_uid(&self, uid: &str) -> RepoResult<Option<CalendarEvent>> {
+        self.db
+            .query_single_as(
+                r#"SELECT id, title, description, start_datetime, end_datetime, all_day, timezone,
+                event_type, category, task_id, client_id, technician_id, location, meeting_link,
+                is_virtual, participants, is_recurring, recurrence_rule, parent_event_id,
+                reminders, status, color, tags, notes, synced, last_synced_at,
+                created_at, updated_at, created_by, updated_by, deleted_at, deleted_by, ical_uid
+            FROM calendar_events WHERE deleted_at IS NULL AND ical_uid = ?"#,
+                params![uid],
+            )
+            .map_err(|e| {
+                RepoError::Database(format!("Failed to query event by iCalendar UID: {}", e))
             })
     }
 
@@ -64,7 +128,7 @@ impl CalendarEventRepository {
                 event_type, category, task_id, client_id, technician_id, location, meeting_link,
                 is_virtual, participants, is_recurring, recurrence_rule, parent_event_id,
                 reminders, status, color, tags, notes, synced, last_synced_at,
-                created_at, updated_at, created_by, updated_by, deleted_at, deleted_by
+                created_at, updated_at, created_by, updated_by, deleted_at, deleted_by, ical_uid
             FROM calendar_events WHERE deleted_at IS NULL AND technician_id = ?
             ORDER BY start_datetime ASC"#,
                 params![technician_id],
@@ -82,7 +146,7 @@ impl CalendarEventRepository {
                 event_type, category, task_id, client_id, technician_id, location, meeting_link,
                 is_virtual, participants, is_recurring, recurrence_rule, parent_event_id,
                 reminders, status, color, tags, notes, synced, last_synced_at,
-                created_at, updated_at, created_by, updated_by, deleted_at, deleted_by
+                created_at, updated_at, created_by, updated_by, deleted_at, deleted_by, ical_uid
             FROM calendar_events WHERE deleted_at IS NULL AND task_id = ?
             ORDER BY start_datetime ASC"#,
                 params![task_id],
@@ -107,6 +171,7 @@ impl CalendarEventRepository {
             .map_err(|e| RepoError::Database(format!("Failed to serialize tags: {}", e)))?;
 
         let event_type_str = input.event_type.unwrap_or(EventType::Meeting).to_string();
+        let recurrence_rule = input.recurrence_rule.filter(|rule| !rule.trim().is_empty());
 
         let params_vec: Vec<rusqlite::types::Value> = vec![
             id.clone().into(),
@@ -128,9 +193,9 @@ impl CalendarEventRepository {
             input.meeting_link.into(),
             (input.is_virtual.unwrap_or(false) as i32).into(),
             participants_json.into(),
-            0i32.into(),
-            None::<String>.into(),
-            None::<String>.into(),
+            (recurrence_rule.is_some() as i32).into(),
+            recurrence_rule.into(),
+            input.parent_event_id.into(),
             reminders_json.into(),
             EventStatus::Confirmed.to_string().into(),
             input.color.into(),
@@ -141,6 +206,7 @@ impl CalendarEventRepository {
             now.into(),
             created_by.clone().into(),
             created_by.into(),
+            input.ical_uid.into(),
         ];
 
         self.db.execute(
@@ -148,8 +214,9 @@ impl CalendarEventRepository {
                 id, title, description, start_datetime, end_datetime, all_day, timezone,
                 event_type, category, task_id, client_id, technician_id, location, meeting_link,
                 is_virtual, participants, is_recurring, recurrence_rule, parent_event_id,
-                reminders, status, color, tags, notes, synced, created_at, updated_at, created_by, updated_by
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"#,
+                reminders, status, color, tags, notes, synced, created_at, updated_at, created_by, updated_by,
+                ical_uid
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"#,
             rusqlite::params_from_iter(params_vec)
         ).map_err(|e| RepoError::Database(format!("Failed to create calendar event: {}", e)))?;
 
@@ -253,6 +320,12 @@ impl CalendarEventRepository {
             sql.push_str(", notes = ?");
             params_vec.push(notes.clone().into());
         }
+        if let Some(rule) = &input.recurrence_rule {
+            let rule = Some(rule.trim()).filter(|rule| !rule.is_empty());
+            sql.push_str(", is_recurring = ?, recurrence_rule = ?");
+            params_vec.push((rule.is_some() as i32).into());
+            params_vec.push(rule.map(str::to_string).into());
+        }
 
         sql.push_str(" WHERE id = ? AND deleted_at IS NULL");
         params_vec.push(id.to_string().into());
@@ -279,7 +352,7 @@ impl Repository<CalendarEvent, String> for CalendarEventRepository {
                 event_type, category, task_id, client_id, technician_id, location, meeting_link,
                 is_virtual, participants, is_recurring, recurrence_rule, parent_event_id,
                 reminders, status, color, tags, notes, synced, last_synced_at,
-                created_at, updated_at, created_by, updated_by, deleted_at, deleted_by
+                created_at, updated_at, created_by, updated_by, deleted_at, deleted_by, ical_uid
             FROM calendar_events WHERE id = ? AND deleted_at IS NULL"#,
                 params![id],
             )
@@ -293,7 +366,7 @@ impl Repository<CalendarEvent, String> for CalendarEventRepository {
                 event_type, category, task_id, client_id, technician_id, location, meeting_link,
                 is_virtual, participants, is_recurring, recurrence_rule, parent_event_id,
                 reminders, status, color, tags, notes, synced, last_synced_at,
-                created_at, updated_at, created_by, updated_by, deleted_at, deleted_by
+                created_at, updated_at, created_by, updated_by, deleted_at, deleted_by, ical_uid
             FROM calendar_events WHERE deleted_at IS NULL ORDER BY start_datetime ASC"#,
                 [],
             )
@@ -323,6 +396,7 @@ impl Repository<CalendarEvent, String> for CalendarEventRepository {
                 color: entity.color,
                 tags: Some(entity.tags),
                 notes: entity.notes,
+                recurrence_rule: Some(entity.recurrence_rule.unwrap_or_default()),
             };
             self.update(&entity.id, update_input, entity.updated_by)
                 .await?
@@ -348,6 +422,9 @@ impl Repository<CalendarEvent, String> for CalendarEventRepository {
                 color: entity.color,
                 tags: Some(entity.tags),
                 notes: entity.notes,
+                recurrence_rule: entity.recurrence_rule,
+                parent_event_id: entity.parent_event_id,
+                ical_uid: entity.ical_uid,
             };
             self.create(create_input, entity.created_by).await
         }
@@ -2,6 +2,7 @@
 
 use super::*;
 use crate::shared::context::RequestContext;
+use crate::shared::contracts::auth::UserRole;
 use crate::shared::contracts::rate_limiter::RateLimiterPort;
 use crate::shared::ipc::errors::AppError as IpcAppError;
 use crate::shared::repositories::base::Repository;
@@ -24,9 +25,14 @@ pub enum CalendarCommand {
     UpdateEvent {
         id: String,
         event_data: UpdateEventInput,
+        /// Targets one occurrence of a recurring event.
+        recurrence_id: Option<String>,
+        scope: Option<RecurrenceScope>,
     },
     DeleteEvent {
         id: String,
+        recurrence_id: Option<String>,
+        scope: Option<RecurrenceScope>,
     },
     GetEventsForTechnician {
         technician_id: String,
@@ -52,6 +58,59 @@ pub enum CalendarCommand {
         new_end: Option<String>,
         force: bool,
     },
+    ExportIcs {
+        start_date: String,
+        end_date: String,
+        technician_id: Option<String>,
+    },
+    ExportEventInvite {
+        id: String,
+    },
+    ImportIcs {
+        ics_data: String,
+        update_existing: bool,
+        technician_id: Option<String>,
+    },
+    GetFeedPath,
+    GetTechnicianAvailability {
+        technician_id: String,
+    },
+    SetTechnicianAvailability {
+        availability: TechnicianAvailability,
+    },
+    ListAbsences {
+        technician_id: Option<String>,
+        start_date: String,
+        end_date: String,
+    },
+    AddAbsence {
+        absence: CreateAbsenceInput,
+    },
+    DeleteAbsence {
+        id: String,
+    },
+    ListPublicHolidays {
+        start_date: String,
+        end_date: String,
+    },
+    SetPublicHoliday {
+        holiday: PublicHoliday,
+    },
+    DeletePublicHoliday {
+        date: String,
+    },
+    GetCapacityHeatmap {
+        week_start: String,
+        technician_ids: Option<Vec<String>>,
+    },
+    SuggestSlots {
+        search: SlotSearch,
+    },
+    ProposeSchedule {
+        start_date: String,
+        end_date: String,
+        technician_ids: Option<Vec<String>>,
+    },
 }
 
 /// Response enum for the Calendar bounded context.
@@ -62,6 +121,17 @@ pub enum CalendarResponse {
     Deleted(bool),
     Events(Vec<CalendarEvent>),
     Conflict(ConflictDetection),
+    Ics(String),
+    IcsImport(IcsImportSummary),
+    FeedPath(String),
+    Availability(TechnicianAvailability),
+    Absences(Vec<TechnicianAbsence>),
+    Absence(TechnicianAbsence),
+    Holidays(Vec<PublicHoliday>),
+    Holiday(PublicHoliday),
+    CapacityHeatmap(CapacityHeatmap),
+    SlotSuggestions(SlotSuggestions),
+    ScheduleProposal(ScheduleProposal),
 }
 
 // ── Private helpers ───────────────────────────────────────────────────────────
@@ -101,11 +171,41 @@ mod helpers {
     }
 }
 
+/// Organizer of invitations sent by the current user.
+fn ics_organizer(ctx: &RequestContext) -> crate::domains::calendar::domain::ical::Organizer {
+    crate::domains::calendar::domain::ical::Organizer {
+        name: ctx.auth.username.clone(),
+        email: ctx.auth.email.clone(),
+    }
+}
+
+/// Availability is managed by admins and supervisors.
+fn ensure_availability_management(ctx: &RequestContext) -> Result<(), IpcAppError> {
+    if !matches!(ctx.auth.role, UserRole::Admin | UserRole::Supervisor) {
+        return Err(IpcAppError::Authorization(
+            "Not authorized to manage technician availability".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Proposing a schedule for every unscheduled task is for admins and
+/// supervisors.
+fn ensure_schedule_planning(ctx: &RequestContext) -> Result<(), IpcAppError> {
+    if !matches!(ctx.auth.role, UserRole::Admin | UserRole::Supervisor) {
+        return Err(IpcAppError::Authorization(
+            "Not authorized to plan the schedule".to_string(),
+        ));
+    }
+    Ok(())
+}
+
 /// Facade for the Calendar bounded context.
 pub struct CalendarFacade {
     pub(super) calendar_service: Arc<CalendarService>,
     pub(super) calendar_event_repository: Arc<dyn CalendarEventRepositoryContract>,
     rate_limiter: Option<Arc<dyn RateLimiterPort>>,
+    feed: Option<Arc<CalendarFeed>>,
 }
 
 impl std::fmt::Debug for CalendarFacade {
@@ -124,6 +224,7 @@ impl CalendarFacade {
             calendar_service,
             calendar_event_repository,
             rate_limiter: Some(rate_limiter),
+            feed: None,
         }
     }
 
@@ -137,6 +238,19 @@ impl CalendarFacade {
             calendar_service,
             calendar_event_repository,
             rate_limiter: None,
+            feed: None,
+        }
+    }
+
+    /// Keep the local ICS feed in line with changes made through the facade.
+    pub fn with_feed(mut self, feed: Arc<CalendarFeed>) -> Self {
+        self.feed = Some(feed);
+        self
+    }
+
+    fn feed_changed(&self) {
+        if let Some(feed) = &self.feed {
+            feed.mark_stale();
         }
     }
 
@@ -212,14 +326,23 @@ impl CalendarFacade {
                         "Event start time must be before end time".to_string(),
                     ));
                 }
+                if let Some(rule) = &event_data.recurrence_rule {
+                    occurrences::validate_recurrence(rule, event_data.timezone.as_deref())?;
+                }
                 let event = self
                     .calendar_event_repository
                     .create(event_data, Some(ctx.auth.user_id.clone()))
                     .await
                     .map_err(|e| IpcAppError::internal_sanitized("create_calendar_event", &e))?;
+                self.feed_changed();
                 Ok(CalendarResponse::Event(event))
             }
-            CalendarCommand::UpdateEvent { id, event_data } => {
+            CalendarCommand::UpdateEvent {
+                id,
+                event_data,
+                recurrence_id,
+                scope,
+            } => {
                 // TODO(ADR-001): extract business logic to application/
                 if let (Some(start), Some(end)) =
                     (&event_data.start_datetime, &event_data.end_datetime)
@@ -230,19 +353,55 @@ impl CalendarFacade {
                         ));
                     }
                 }
+                if let Some(rule) = event_data.recurrence_rule.as_deref() {
+                    if !rule.is_empty() {
+                        occurrences::validate_recurrence(rule, event_data.timezone.as_deref())?;
+                    }
+                }
+                if let Some(recurrence_id) = recurrence_id {
+                    let event = occurrences::update_occurrence(
+                        self.calendar_event_repository.as_ref(),
+                        &id,
+                        &recurrence_id,
+                        scope.unwrap_or(RecurrenceScope::This),
+                        event_data,
+                        Some(ctx.auth.user_id.clone()),
+                    )
+                    .await?;
+                    self.feed_changed();
+                    return Ok(CalendarResponse::OptionalEvent(event));
+                }
                 let event = self
                     .calendar_event_repository
                     .update(&id, event_data, Some(ctx.auth.user_id.clone()))
                     .await
                     .map_err(|e| IpcAppError::internal_sanitized("update_calendar_event", &e))?;
+                self.feed_changed();
                 Ok(CalendarResponse::OptionalEvent(event))
             }
-            CalendarCommand::DeleteEvent { id } => {
+            CalendarCommand::DeleteEvent {
+                id,
+                recurrence_id,
+                scope,
+            } => {
+                if let Some(recurrence_id) = recurrence_id {
+                    let deleted = occurrences::delete_occurrence(
+                        self.calendar_event_repository.as_ref(),
+                        &id,
+                        &recurrence_id,
+                        scope.unwrap_or(RecurrenceScope::This),
+                        Some(ctx.auth.user_id.clone()),
+                    )
+                    .await?;
+                    self.feed_changed();
+                    return Ok(CalendarResponse::Deleted(deleted));
+                }
                 let deleted = self
                     .calendar_event_repository
                     .delete_by_id(id)
                     .await
                     .map_err(|e| IpcAppError::internal_sanitized("delete_calendar_event", &e))?;
+                self.feed_changed();
                 Ok(CalendarResponse::Deleted(deleted))
             }
             CalendarCommand::GetEventsForTechnician { technician_id } => {
@@ -320,8 +479,149 @@ impl CalendarFacade {
                     });
                     return Err(IpcAppError::Validation(msg));
                 }
+                self.feed_changed();
                 Ok(CalendarResponse::Conflict(result))
             }
+            CalendarCommand::ExportIcs {
+                start_date,
+                end_date,
+                technician_id,
+            } => {
+                self.validate_date_range(&start_date, &end_date)?;
+                let from = helpers::date_str_to_epoch_ms(&start_date, false);
+                let to = helpers::date_str_to_epoch_ms(&end_date, true);
+                let ics = ics::export_range(
+                    self.calendar_event_repository.as_ref(),
+                    &self.calendar_service,
+                    (from, to),
+                    (&start_date, &end_date),
+                    technician_id.as_deref(),
+                )
+                .await?;
+                Ok(CalendarResponse::Ics(ics))
+            }
+            CalendarCommand::ExportEventInvite { id } => {
+                let organizer = ics_organizer(ctx);
+                let ics =
+                    ics::export_invite(self.calendar_event_repository.as_ref(), &id, organizer)
+                        .await?;
+                Ok(CalendarResponse::Ics(ics))
+            }
+            CalendarCommand::ImportIcs {
+                ics_data,
+                update_existing,
+                technician_id,
+            } => {
+                if ics_data.trim().is_empty() {
+                    return Err(IpcAppError::Validation(
+                        "ICS data cannot be empty".to_string(),
+                    ));
+                }
+                let summary = ics::import(
+                    self.calendar_event_repository.as_ref(),
+                    &ics_data,
+                    update_existing,
+                    technician_id,
+                    &ctx.auth.user_id,
+                )
+                .await?;
+                if summary.created > 0 || summary.updated > 0 {
+                    self.feed_changed();
+                }
+                Ok(CalendarResponse::IcsImport(summary))
+            }
+            CalendarCommand::GetFeedPath => {
+                let feed = self.feed.as_ref().ok_or_else(|| {
+                    IpcAppError::Internal("Calendar feed is not available".to_string())
+                })?;
+                Ok(CalendarResponse::FeedPath(
+                    feed.path().to_string_lossy().into_owned(),
+                ))
+            }
+            CalendarCommand::GetTechnicianAvailability { technician_id } => {
+                let availability = self
+                    .calendar_service
+                    .get_technician_availability(&technician_id)?;
+                Ok(CalendarResponse::Availability(availability))
+            }
+            CalendarCommand::SetTechnicianAvailability { availability } => {
+                ensure_availability_management(ctx)?;
+                let availability = self
+                    .calendar_service
+                    .set_technician_availability(availability, &ctx.auth.user_id)?;
+                Ok(CalendarResponse::Availability(availability))
+            }
+            CalendarCommand::ListAbsences {
+                technician_id,
+                start_date,
+                end_date,
+            } => {
+                self.validate_date_range(&start_date, &end_date)?;
+                let absences = self.calendar_service.list_absences(
+                    technician_id.as_deref(),
+                    &start_date,
+                    &end_date,
+                )?;
+                Ok(CalendarResponse::Absences(absences))
+            }
+            CalendarCommand::AddAbsence { absence } => {
+                ensure_availability_management(ctx)?;
+                let absence = self
+                    .calendar_service
+                    .add_absence(absence, &ctx.auth.user_id)?;
+                Ok(CalendarResponse::Absence(absence))
+            }
+            CalendarCommand::DeleteAbsence { id } => {
+                ensure_availability_management(ctx)?;
+                let deleted = self.calendar_service.delete_absence(&id)?;
+                Ok(CalendarResponse::Deleted(deleted))
+            }
+            CalendarCommand::ListPublicHolidays {
+                start_date,
+                end_date,
+            } => {
+                self.validate_date_range(&start_date, &end_date)?;
+                let holidays = self
+                    .calendar_service
+                    .list_public_holidays(&start_date, &end_date)?;
+                Ok(CalendarResponse::Holidays(holidays))
+            }
+            CalendarCommand::SetPublicHoliday { holiday } => {
+                ensure_availability_management(ctx)?;
+                let holiday = self.calendar_service.set_public_holiday(holiday)?;
+                Ok(CalendarResponse::Holiday(holiday))
+            }
+            CalendarCommand::DeletePublicHoliday { date } => {
+                ensure_availability_management(ctx)?;
+                let deleted = self.calendar_service.delete_public_holiday(&date)?;
+                Ok(CalendarResponse::Deleted(deleted))
+            }
+            CalendarCommand::GetCapacityHeatmap {
+                week_start,
+                technician_ids,
+            } => {
+                let heatmap = self
+                    .calendar_service
+                    .capacity_heatmap(&week_start, technician_ids)
+                    .await?;
+                Ok(CalendarResponse::CapacityHeatmap(heatmap))
+            }
+            CalendarCommand::SuggestSlots { search } => {
+                let suggestions = self.calendar_service.suggest_slots(search).await?;
+                Ok(CalendarResponse::SlotSuggestions(suggestions))
+            }
+            CalendarCommand::ProposeSchedule {
+                start_date,
+                end_date,
+                technician_ids,
+            } => {
+                ensure_schedule_planning(ctx)?;
+                let proposal = self
+                    .calendar_service
+                    .propose_schedule(&start_date, &end_date, technician_ids)
+                    .await?;
+                Ok(CalendarResponse::ScheduleProposal(proposal))
+            }
         }
     }
 }
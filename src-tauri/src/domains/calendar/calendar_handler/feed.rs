@@ -0,0 +1,145 @@
+//! Local ICS feed of the workshop calendar.
+//!
+//! `calendar/calendar.ics` in the app data directory holds the events and
+//! scheduled tasks from 90 days back to a year ahead. Calendar apps on the
+//! same machine, or a folder synced to phones, can subscribe to it. The file
+//! is read-only and is regenerated by [`CalendarFeed::run`] after every
+//! calendar change.
+
+use super::*;
+use crate::shared::event_bus::{DomainEvent, DomainEventHandler};
+use crate::shared::repositories::CalendarEventRepositoryContract;
+use async_trait::async_trait;
+use chrono::{Duration, NaiveTime, Utc};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::Notify;
+
+pub const FEED_DIR: &str = "calendar";
+pub const FEED_FILE: &str = "calendar.ics";
+const PAST_DAYS: i64 = 90;
+const FUTURE_DAYS: i64 = 365;
+/// Wait after a change so that a burst of changes rewrites the file once.
+const DEBOUNCE: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// Keeps the feed file in line with the calendar.
+pub struct CalendarFeed {
+    events: Arc<dyn CalendarEventRepositoryContract>,
+    calendar_service: Arc<CalendarService>,
+    path: PathBuf,
+    stale: Notify,
+}
+
+impl CalendarFeed {
+    pub fn new(
+        events: Arc<dyn CalendarEventRepositoryContract>,
+        calendar_service: Arc<CalendarService>,
+        app_data_dir: &Path,
+    ) -> Self {
+        Self {
+            events,
+            calendar_service,
+            path: app_data_dir.join(FEED_DIR).join(FEED_FILE),
+            stale: Notify::new(),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Ask for the file to be regenerated.
+    pub fn mark_stale(&self) {
+        self.stale.notify_one();
+    }
+
+    /// Rewrite the file now.
+    pub async fn regenerate(&self) -> Result<(), String> {
+        let today = Utc::now().date_naive();
+        let start = today - Duration::days(PAST_DAYS);
+        let end = today + Duration::days(FUTURE_DAYS);
+        let from_ms = start.and_time(NaiveTime::MIN).and_utc().timestamp_millis();
+        let to_ms = (end + Duration::days(1))
+            .and_time(NaiveTime::MIN)
+            .and_utc()
+            .timestamp_millis()
+            - 1000;
+        let document = ics::export_range(
+            self.events.as_ref(),
+            &self.calendar_service,
+            (from_ms, to_ms),
+            (
+                &start.format("%Y-%m-%d").to_string(),
+                &end.format("%Y-%m-%d").to_string(),
+            ),
+            None,
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+        write_read_only(&self.path, &document)
+            .map_err(|e| format!("Failed to write {}: {}", self.path.display(), e))
+    }
+
+    /// Regenerate the file at start-up and after each change. Runs forever.
+    pub async fn run(self: Arc<Self>) {
+        loop {
+            if let Err(error) = self.regenerate().await {
+                tracing::warn!("Calendar feed regeneration failed: {}", error);
+            }
+            self.stale.notified().await;
+            tokio::time::sleep(DEBOUNCE).await;
+        }
+    }
+}
+
+/// Replace `path` with `contents` and mark it read-only. The new contents
+/// are written next to it first so readers never see a partial file.
+fn write_read_only(path: &Path, contents: &str) -> std::io::Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let staging = path.with_extension("ics.tmp");
+    std::fs::write(&staging, contents)?;
+    if let Ok(metadata) = std::fs::metadata(path) {
+        // Windows refuses to replace a read-only file.
+        let mut permissions = metadata.permissions();
+        #[allow(clippy::permissions_set_readonly_false)]
+        permissions.set_readonly(false);
+        std::fs::set_permissions(path, permissions)?;
+    }
+    std::fs::rename(&staging, path)?;
+    let mut permissions = std::fs::metadata(path)?.permissions();
+    permissions.set_readonly(true);
+    std::fs::set_permissions(path, permissions)
+}
+
+/// Marks the feed stale when tasks are scheduled, moved or removed outside
+/// the calendar commands.
+pub struct CalendarFeedHandler {
+    feed: Arc<CalendarFeed>,
+}
+
+impl CalendarFeedHandler {
+    pub fn new(feed: Arc<CalendarFeed>) -> Self {
+        Self { feed }
+    }
+}
+
+#[async_trait]
+impl DomainEventHandler for CalendarFeedHandler {
+    async fn handle(&self, _event: &DomainEvent) -> Result<(), String> {
+        self.feed.mark_stale();
+        Ok(())
+    }
+
+    fn interested_events(&self) -> Vec<&'static str> {
+        vec![
+            DomainEvent::TASK_CREATED,
+            DomainEvent::TASK_UPDATED,
+            DomainEvent::TASK_ASSIGNED,
+            DomainEvent::TASK_STATUS_CHANGED,
+            DomainEvent::TASK_COMPLETED,
+            DomainEvent::TASK_DELETED,
+        ]
+    }
+}
@@ -0,0 +1,276 @@
+//! iCalendar export and import.
+//!
+//! Exports hold calendar events plus scheduled tasks that have no event of
+//! their own. Recurring series are written once with their RRULE; exceptions
+//! are separate events, excluded from the series by EXDATE.
+//!
+//! Imports are de-duplicated by UID: an event remembers the UID it was
+//! imported under in `ical_uid`, and UIDs from our own exports point back at
+//! the event they were written from.
+
+use super::*;
+use crate::domains::calendar::domain::ical::{
+    event_uid, own_event_id, parse_calendar, IcsEvent, IcsWriter, Organizer,
+};
+use crate::domains::calendar::domain::recurrence::Series;
+use crate::shared::ipc::errors::AppError as IpcAppError;
+use crate::shared::repositories::base::RepoError;
+use crate::shared::repositories::CalendarEventRepositoryContract;
+use chrono::Utc;
+use std::collections::HashSet;
+
+pub const CALENDAR_NAME: &str = "Workshop calendar";
+
+/// Events and scheduled tasks between `from_ms` and `to_ms` (dates
+/// `start_date` to `end_date` for tasks), as a VCALENDAR to publish.
+pub async fn export_range(
+    repo: &dyn CalendarEventRepositoryContract,
+    service: &CalendarService,
+    (from_ms, to_ms): (i64, i64),
+    (start_date, end_date): (&str, &str),
+    technician_id: Option<&str>,
+) -> Result<String, IpcAppError> {
+    let map_err = |e| IpcAppError::internal_sanitized("export_calendar_ics", &e);
+    let events = repo
+        .find_events_in_range(from_ms, to_ms, technician_id)
+        .await
+        .map_err(map_err)?;
+    let tasks = service
+        .get_tasks(
+            CalendarDateRange {
+                start_date: start_date.to_string(),
+                end_date: end_date.to_string(),
+            },
+            technician_id.map(|id| vec![id.to_string()]),
+            None,
+        )
+        .await
+        .map_err(|e| IpcAppError::internal_sanitized("export_calendar_ics", &e))?;
+
+    let mut writer = IcsWriter::publish(CALENDAR_NAME, Utc::now());
+    let mut written = HashSet::new();
+    let mut covered_tasks = HashSet::new();
+    for event in events {
+        if !written.insert(event.id.clone()) {
+            continue;
+        }
+        if let Some(task_id) = &event.task_id {
+            covered_tasks.insert(task_id.clone());
+        }
+        // Expanded occurrences carry the series id; write the series itself.
+        let event = match event.recurrence_id {
+            Some(_) => match repo.find_by_id(event.id.clone()).await.map_err(map_err)? {
+                Some(series) => series,
+                None => continue,
+            },
+            None => event,
+        };
+        if let Err(reason) = writer.add_event(&event) {
+            tracing::warn!(event_id = %event.id, "Skipping event in ICS export: {}", reason);
+        }
+    }
+    for task in tasks
+        .iter()
+        .filter(|task| !covered_tasks.contains(&task.id))
+    {
+        if let Err(reason) = writer.add_task(task) {
+            tracing::warn!(task_id = %task.id, "Skipping task in ICS export: {}", reason);
+        }
+    }
+    Ok(writer.finish())
+}
+
+/// An invitation to event `id` from the current user, with one VALARM per
+/// reminder.
+pub async fn export_invite(
+    repo: &dyn CalendarEventRepositoryContract,
+    id: &str,
+    organizer: Organizer,
+) -> Result<String, IpcAppError> {
+    let event = repo
+        .find_by_id(id.to_string())
+        .await
+        .map_err(|e| IpcAppError::internal_sanitized("export_event_invite", &e))?
+        .ok_or_else(|| IpcAppError::NotFound(format!("Calendar event {} not found", id)))?;
+    let mut writer = IcsWriter::invite(organizer, Utc::now());
+    writer
+        .add_event(&event)
+        .map_err(|reason| IpcAppError::Validation(format!("Cannot export event: {}", reason)))?;
+    Ok(writer.finish())
+}
+
+/// The event previously imported under `uid`, or the one our own export
+/// gave that UID.
+async fn find_imported(
+    repo: &dyn CalendarEventRepositoryContract,
+    uid: &str,
+) -> Result<Option<CalendarEvent>, IpcAppError> {
+    let map_err = |e| IpcAppError::internal_sanitized("import_calendar_ics", &e);
+    if let Some(event) = repo.find_by_ical_uid(uid).await.map_err(map_err)? {
+        return Ok(Some(event));
+    }
+    match own_event_id(uid) {
+        Some(id) => repo.find_by_id(id.to_string()).await.map_err(map_err),
+        None => Ok(None),
+    }
+}
+
+enum Imported {
+    Created,
+    Updated,
+    Skipped,
+}
+
+/// Create `input`, or update `existing` from `ics` when `update_existing`
+/// is set.
+async fn save(
+    repo: &dyn CalendarEventRepositoryContract,
+    ics: &IcsEvent,
+    existing: Option<CalendarEvent>,
+    input: CreateEventInput,
+    update_existing: bool,
+    user_id: &str,
+) -> Result<Imported, String> {
+    let map_err = |e: RepoError| e.to_string();
+    if let Some(existing) = existing {
+        if !update_existing {
+            return Ok(Imported::Skipped);
+        }
+        repo.update(
+            &existing.id,
+            ics.to_update_input()?,
+            Some(user_id.to_string()),
+        )
+        .await
+        .map_err(map_err)?;
+        return Ok(Imported::Updated);
+    }
+
+    let event = repo
+        .create(input, Some(user_id.to_string()))
+        .await
+        .map_err(map_err)?;
+    // New events start confirmed.
+    if let Some(status) = ics.status.clone().filter(|status| *status != event.status) {
+        let changes = UpdateEventInput {
+            status: Some(status),
+            ..Default::default()
+        };
+        repo.update(&event.id, changes, Some(user_id.to_string()))
+            .await
+            .map_err(map_err)?;
+    }
+    Ok(Imported::Created)
+}
+
+/// Import the VEVENTs of `ics_data`. Events already imported (same UID) are
+/// skipped, or updated when `update_existing` is set. A VEVENT overriding one
+/// occurrence of a recurring event becomes an exception of that series.
+pub async fn import(
+    repo: &dyn CalendarEventRepositoryContract,
+    ics_data: &str,
+    update_existing: bool,
+    technician_id: Option<String>,
+    user_id: &str,
+) -> Result<IcsImportSummary, IpcAppError> {
+    let parsed = parse_calendar(ics_data).map_err(IpcAppError::Validation)?;
+    let mut summary = IcsImportSummary::default();
+    let mut events = Vec::new();
+    for result in parsed {
+        summary.total_processed += 1;
+        match result {
+            Ok(event) => events.push(event),
+            Err(error) => {
+                summary.failed += 1;
+                summary.errors.push(format!(
+                    "{}: {}",
+                    error.uid.as_deref().unwrap_or("VEVENT"),
+                    error.reason
+                ));
+            }
+        }
+    }
+    // Series must exist before their overridden occurrences.
+    events.sort_by_key(|event| event.recurrence_id.is_some());
+
+    for ics in &events {
+        let outcome = match ics.recurrence_id {
+            None => import_event(repo, ics, update_existing, &technician_id, user_id).await,
+            Some(_) => import_override(repo, ics, update_existing, &technician_id, user_id).await,
+        };
+        match outcome {
+            Ok(Imported::Created) => summary.created += 1,
+            Ok(Imported::Updated) => summary.updated += 1,
+            Ok(Imported::Skipped) => summary.duplicates_skipped += 1,
+            Err(reason) => {
+                summary.failed += 1;
+                summary.errors.push(format!("{}: {}", ics.uid, reason));
+            }
+        }
+    }
+    Ok(summary)
+}
+
+async fn import_event(
+    repo: &dyn CalendarEventRepositoryContract,
+    ics: &IcsEvent,
+    update_existing: bool,
+    technician_id: &Option<String>,
+    user_id: &str,
+) -> Result<Imported, String> {
+    let existing = find_imported(repo, &ics.uid)
+        .await
+        .map_err(|e| e.to_string())?;
+    let input = ics.to_create_input(ics.uid.clone(), technician_id.clone())?;
+    save(repo, ics, existing, input, update_existing, user_id).await
+}
+
+/// Import a VEVENT with RECURRENCE-ID as a child of its series, removing the
+/// occurrence it replaces from the series.
+async fn import_override(
+    repo: &dyn CalendarEventRepositoryContract,
+    ics: &IcsEvent,
+    update_existing: bool,
+    technician_id: &Option<String>,
+    user_id: &str,
+) -> Result<Imported, String> {
+    let Some(recurrence_id) = ics.recurrence_id else {
+        return Err("missing RECURRENCE-ID".to_string());
+    };
+    let series_event = find_imported(repo, &ics.uid)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "the recurring event it belongs to was not imported".to_string())?;
+    let series = Series::of(&series_event)?;
+    let occurrence = recurrence_id.local_in(series.tz);
+    let key = format!(
+        "{}#{}",
+        event_uid(&series_event),
+        occurrence.format("%Y%m%dT%H%M%S")
+    );
+    let existing = find_imported(repo, &key).await.map_err(|e| e.to_string())?;
+    let mut input = ics.to_create_input(key, technician_id.clone())?;
+    input.parent_event_id = Some(series_event.id.clone());
+    input.technician_id = input
+        .technician_id
+        .or_else(|| series_event.technician_id.clone());
+    let imported = save(repo, ics, existing, input, update_existing, user_id).await?;
+
+    // Still generated by the series (first import, or the series was just
+    // replaced by an update): exclude it.
+    if series.occurrence(&series.format(occurrence)).is_ok() {
+        let changes = UpdateEventInput {
+            recurrence_rule: Some(
+                series
+                    .rule
+                    .excluding(occurrence, series.form, series.tz)
+                    .to_string(),
+            ),
+            ..Default::default()
+        };
+        repo.update(&series_event.id, changes, Some(user_id.to_string()))
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+    Ok(imported)
+}
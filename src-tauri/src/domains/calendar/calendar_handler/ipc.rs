@@ -23,6 +23,7 @@ fn facade(state: &AppState<'_>) -> CalendarFacade {
         state.calendar_event_repository.clone(),
         rl,
     )
+    .with_feed(state.calendar_feed.clone())
 }
 
 // ── IPC commands ──────────────────────────────────────────────────────────────
@@ -116,6 +117,8 @@ pub async fn update_event(
             CalendarCommand::UpdateEvent {
                 id: request.id,
                 event_data: request.event_data,
+                recurrence_id: request.recurrence_id,
+                scope: request.scope,
             },
             &ctx,
         )
@@ -139,7 +142,14 @@ pub async fn delete_event(
     let ctx = calendar_context(&state, &request.correlation_id)?;
     info!("delete_event command received");
     match facade(&state)
-        .execute(CalendarCommand::DeleteEvent { id: request.id }, &ctx)
+        .execute(
+            CalendarCommand::DeleteEvent {
+                id: request.id,
+                recurrence_id: request.recurrence_id,
+                scope: request.scope,
+            },
+            &ctx,
+        )
         .await?
     {
         CalendarResponse::Deleted(deleted) => {
@@ -292,3 +302,386 @@ pub async fn calendar_schedule_task(
         )),
     }
 }
+
+#[tauri::command]
+#[instrument(skip(state))]
+pub async fn calendar_export_ics(
+    request: ExportIcsRequest,
+    state: AppState<'_>,
+) -> Result<ApiResponse<String>, AppError> {
+    let ctx = calendar_context(&state, &request.correlation_id)?;
+    info!("calendar_export_ics command received");
+    match facade(&state)
+        .execute(
+            CalendarCommand::ExportIcs {
+                start_date: request.start_date,
+                end_date: request.end_date,
+                technician_id: request.technician_id,
+            },
+            &ctx,
+        )
+        .await?
+    {
+        CalendarResponse::Ics(ics) => {
+            Ok(ApiResponse::success(ics).with_correlation_id(Some(ctx.correlation_id)))
+        }
+        _ => Err(AppError::Internal(
+            "Unexpected calendar facade response".to_string(),
+        )),
+    }
+}
+
+#[tauri::command]
+#[instrument(skip(state))]
+pub async fn calendar_export_event_invite(
+    request: ExportEventInviteRequest,
+    state: AppState<'_>,
+) -> Result<ApiResponse<String>, AppError> {
+    let ctx = calendar_context(&state, &request.correlation_id)?;
+    info!("calendar_export_event_invite command received");
+    match facade(&state)
+        .execute(CalendarCommand::ExportEventInvite { id: request.id }, &ctx)
+        .await?
+    {
+        CalendarResponse::Ics(ics) => {
+            Ok(ApiResponse::success(ics).with_correlation_id(Some(ctx.correlation_id)))
+        }
+        _ => Err(AppError::Internal(
+            "Unexpected calendar facade response".to_string(),
+        )),
+    }
+}
+
+#[tauri::command]
+#[instrument(skip(state, request))]
+pub async fn calendar_import_ics(
+    request: ImportIcsRequest,
+    state: AppState<'_>,
+) -> Result<ApiResponse<IcsImportSummary>, AppError> {
+    let ctx = calendar_context(&state, &request.correlation_id)?;
+    info!("calendar_import_ics command received");
+    match facade(&state)
+        .execute(
+            CalendarCommand::ImportIcs {
+                ics_data: request.ics_data,
+                update_existing: request.update_existing,
+                technician_id: request.technician_id,
+            },
+            &ctx,
+        )
+        .await?
+    {
+        CalendarResponse::IcsImport(summary) => {
+            Ok(ApiResponse::success(summary).with_correlation_id(Some(ctx.correlation_id)))
+        }
+        _ => Err(AppError::Internal(
+            "Unexpected calendar facade response".to_string(),
+        )),
+    }
+}
+
+#[tauri::command]
+#[instrument(skip(state))]
+pub async fn calendar_get_feed_path(
+    correlation_id: Option<String>,
+    state: AppState<'_>,
+) -> Result<ApiResponse<String>, AppError> {
+    let ctx = calendar_context(&state, &correlation_id)?;
+    info!("calendar_get_feed_path command received");
+    match facade(&state)
+        .execute(CalendarCommand::GetFeedPath, &ctx)
+        .await?
+    {
+        CalendarResponse::FeedPath(path) => {
+            Ok(ApiResponse::success(path).with_correlation_id(Some(ctx.correlation_id)))
+        }
+        _ => Err(AppError::Internal(
+            "Unexpected calendar facade response".to_string(),
+        )),
+    }
+}
+
+#[tauri::command]
+#[instrument(skip(state))]
+pub async fn calendar_get_technician_availability(
+    request: GetTechnicianAvailabilityRequest,
+    state: AppState<'_>,
+) -> Result<ApiResponse<TechnicianAvailability>, AppError> {
+    let ctx = calendar_context(&state, &request.correlation_id)?;
+    info!("calendar_get_technician_availability command received");
+    match facade(&state)
+        .execute(
+            CalendarCommand::GetTechnicianAvailability {
+                technician_id: request.technician_id,
+            },
+            &ctx,
+        )
+        .await?
+    {
+        CalendarResponse::Availability(availability) => {
+            Ok(ApiResponse::success(availability).with_correlation_id(Some(ctx.correlation_id)))
+        }
+        _ => Err(AppError::Internal(
+            "Unexpected calendar facade response".to_string(),
+        )),
+    }
+}
+
+#[tauri::command]
+#[instrument(skip(state, request))]
+pub async fn calendar_set_technician_availability(
+    request: SetTechnicianAvailabilityRequest,
+    state: AppState<'_>,
+) -> Result<ApiResponse<TechnicianAvailability>, AppError> {
+    let ctx = calendar_context(&state, &request.correlation_id)?;
+    info!("calendar_set_technician_availability command received");
+    match facade(&state)
+        .execute(
+            CalendarCommand::SetTechnicianAvailability {
+                availability: request.availability,
+            },
+            &ctx,
+        )
+        .await?
+    {
+        CalendarResponse::Availability(availability) => {
+            Ok(ApiResponse::success(availability).with_correlation_id(Some(ctx.correlation_id)))
+        }
+        _ => Err(AppError::Internal(
+            "Unexpected calendar facade response".to_string(),
+        )),
+    }
+}
+
+#[tauri::command]
+#[instrument(skip(state))]
+pub async fn calendar_list_absences(
+    request: ListAbsencesRequest,
+    state: AppState<'_>,
+) -> Result<ApiResponse<Vec<TechnicianAbsence>>, AppError> {
+    let ctx = calendar_context(&state, &request.correlation_id)?;
+    info!("calendar_list_absences command received");
+    match facade(&state)
+        .execute(
+            CalendarCommand::ListAbsences {
+                technician_id: request.technician_id,
+                start_date: request.start_date,
+                end_date: request.end_date,
+            },
+            &ctx,
+        )
+        .await?
+    {
+        CalendarResponse::Absences(absences) => {
+            Ok(ApiResponse::success(absences).with_correlation_id(Some(ctx.correlation_id)))
+        }
+        _ => Err(AppError::Internal(
+            "Unexpected calendar facade response".to_string(),
+        )),
+    }
+}
+
+#[tauri::command]
+#[instrument(skip(state, request))]
+pub async fn calendar_add_absence(
+    request: AddAbsenceRequest,
+    state: AppState<'_>,
+) -> Result<ApiResponse<TechnicianAbsence>, AppError> {
+    let ctx = calendar_context(&state, &request.correlation_id)?;
+    info!("calendar_add_absence command received");
+    match facade(&state)
+        .execute(
+            CalendarCommand::AddAbsence {
+                absence: request.absence,
+            },
+            &ctx,
+        )
+        .await?
+    {
+        CalendarResponse::Absence(absence) => {
+            Ok(ApiResponse::success(absence).with_correlation_id(Some(ctx.correlation_id)))
+        }
+        _ => Err(AppError::Internal(
+            "Unexpected calendar facade response".to_string(),
+        )),
+    }
+}
+
+#[tauri::command]
+#[instrument(skip(state))]
+pub async fn calendar_delete_absence(
+    request: DeleteAbsenceRequest,
+    state: AppState<'_>,
+) -> Result<ApiResponse<bool>, AppError> {
+    let ctx = calendar_context(&state, &request.correlation_id)?;
+    info!("calendar_delete_absence command received");
+    match facade(&state)
+        .execute(CalendarCommand::DeleteAbsence { id: request.id }, &ctx)
+        .await?
+    {
+        CalendarResponse::Deleted(deleted) => {
+            Ok(ApiResponse::success(deleted).with_correlation_id(Some(ctx.correlation_id)))
+        }
+        _ => Err(AppError::Internal(
+            "Unexpected calendar facade response".to_string(),
+        )),
+    }
+}
+
+#[tauri::command]
+#[instrument(skip(state))]
+pub async fn calendar_list_public_holidays(
+    request: ListPublicHolidaysRequest,
+    state: AppState<'_>,
+) -> Result<ApiResponse<Vec<PublicHoliday>>, AppError> {
+    let ctx = calendar_context(&state, &request.correlation_id)?;
+    info!("calendar_list_public_holidays command received");
+    match facade(&state)
+        .execute(
+            CalendarCommand::ListPublicHolidays {
+                start_date: request.start_date,
+                end_date: request.end_date,
+            },
+            &ctx,
+        )
+        .await?
+    {
+        CalendarResponse::Holidays(holidays) => {
+            Ok(ApiResponse::success(holidays).with_correlation_id(Some(ctx.correlation_id)))
+        }
+        _ => Err(AppError::Internal(
+            "Unexpected calendar facade response".to_string(),
+        )),
+    }
+}
+
+#[tauri::command]
+#[instrument(skip(state, request))]
+pub async fn calendar_set_public_holiday(
+    request: SetPublicHolidayRequest,
+    state: AppState<'_>,
+) -> Result<ApiResponse<PublicHoliday>, AppError> {
+    let ctx = calendar_context(&state, &request.correlation_id)?;
+    info!("calendar_set_public_holiday command received");
+    match facade(&state)
+        .execute(
+            CalendarCommand::SetPublicHoliday {
+                holiday: request.holiday,
+            },
+            &ctx,
+        )
+        .await?
+    {
+        CalendarResponse::Holiday(holiday) => {
+            Ok(ApiResponse::success(holiday).with_correlation_id(Some(ctx.correlation_id)))
+        }
+        _ => Err(AppError::Internal(
+            "Unexpected calendar facade response".to_string(),
+        )),
+    }
+}
+
+#[tauri::command]
+#[instrument(skip(state))]
+pub async fn calendar_delete_public_holiday(
+    request: DeletePublicHolidayRequest,
+    state: AppState<'_>,
+) -> Result<ApiResponse<bool>, AppError> {
+    let ctx = calendar_context(&state, &request.correlation_id)?;
+    info!("calendar_delete_public_holiday command received");
+    match facade(&state)
+        .execute(
+            CalendarCommand::DeletePublicHoliday { date: request.date },
+            &ctx,
+        )
+        .await?
+    {
+        CalendarResponse::Deleted(deleted) => {
+            Ok(ApiResponse::success(deleted).with_correlation_id(Some(ctx.correlation_id)))
+        }
+        _ => Err(AppError::Internal(
+            "Unexpected calendar facade response".to_string(),
+        )),
+    }
+}
+
+#[tauri::command]
+#[instrument(skip(state))]
+pub async fn calendar_get_capacity_heatmap(
+    request: GetCapacityHeatmapRequest,
+    state: AppState<'_>,
+) -> Result<ApiResponse<CapacityHeatmap>, AppError> {
+    let ctx = calendar_context(&state, &request.correlation_id)?;
+    info!("calendar_get_capacity_heatmap command received");
+    match facade(&state)
+        .execute(
+            CalendarCommand::GetCapacityHeatmap {
+                week_start: request.week_start,
+                technician_ids: request.technician_ids,
+            },
+            &ctx,
+        )
+        .await?
+    {
+        CalendarResponse::CapacityHeatmap(heatmap) => {
+            Ok(ApiResponse::success(heatmap).with_correlation_id(Some(ctx.correlation_id)))
+        }
+        _ => Err(AppError::Internal(
+            "Unexpected calendar facade response".to_string(),
+        )),
+    }
+}
+
+#[tauri::command]
+#[instrument(skip(state))]
+pub async fn calendar_suggest_slots(
+    request: SuggestSlotsRequest,
+    state: AppState<'_>,
+) -> Result<ApiResponse<SlotSuggestions>, AppError> {
+    let ctx = calendar_context(&state, &request.correlation_id)?;
+    info!("calendar_suggest_slots command received");
+    match facade(&state)
+        .execute(
+            CalendarCommand::SuggestSlots {
+                search: request.search,
+            },
+            &ctx,
+        )
+        .await?
+    {
+        CalendarResponse::SlotSuggestions(suggestions) => {
+            Ok(ApiResponse::success(suggestions).with_correlation_id(Some(ctx.correlation_id)))
+        }
+        _ => Err(AppError::Internal(
+            "Unexpected calendar facade response".to_string(),
+        )),
+    }
+}
+
+#[tauri::command]
+#[instrument(skip(state))]
+pub async fn calendar_propose_schedule(
+    request: ProposeScheduleRequest,
+    state: AppState<'_>,
+) -> Result<ApiResponse<ScheduleProposal>, AppError> {
+    let ctx = calendar_context(&state, &request.correlation_id)?;
+    info!("calendar_propose_schedule command received");
+    match facade(&state)
+        .execute(
+            CalendarCommand::ProposeSchedule {
+                start_date: request.start_date,
+                end_date: request.end_date,
+                technician_ids: request.technician_ids,
+            },
+            &ctx,
+        )
+        .await?
+    {
+        CalendarResponse::ScheduleProposal(proposal) => {
+            Ok(ApiResponse::success(proposal).with_correlation_id(Some(ctx.correlation_id)))
+        }
+        _ => Err(AppError::Internal(
+            "Unexpected calendar facade response".to_string(),
+        )),
+    }
+}
@@ -3,15 +3,26 @@
 use crate::domains::calendar::models::*;
 use serde::Deserialize;
 
+pub mod availability;
+pub mod availability_repository;
 pub mod event_repository;
 pub mod facade;
+pub mod feed;
+pub mod ics;
 pub mod ipc;
+pub mod occurrences;
+pub mod reminder_repository;
+pub mod reminders;
 pub mod repository;
 pub mod service;
+pub mod slots;
 
+pub use availability_repository::AvailabilityRepository;
 pub use event_repository::*;
 pub use facade::*;
+pub use feed::{CalendarFeed, CalendarFeedHandler};
 pub use ipc::*;
+pub use reminders::ReminderScheduler;
 pub use repository::*;
 pub use service::*;
 
@@ -71,6 +82,11 @@ pub struct CreateEventRequest {
 pub struct UpdateEventRequest {
     pub id: String,
     pub event_data: UpdateEventInput,
+    /// Original start of the occurrence to edit, for recurring events.
+    #[serde(default)]
+    pub recurrence_id: Option<String>,
+    #[serde(default)]
+    pub scope: Option<RecurrenceScope>,
     #[serde(default)]
     pub correlation_id: Option<String>,
 }
@@ -79,6 +95,11 @@ pub struct UpdateEventRequest {
 #[derive(Deserialize, Debug)]
 pub struct DeleteEventRequest {
     pub id: String,
+    /// Original start of the occurrence to delete, for recurring events.
+    #[serde(default)]
+    pub recurrence_id: Option<String>,
+    #[serde(default)]
+    pub scope: Option<RecurrenceScope>,
     #[serde(default)]
     pub correlation_id: Option<String>,
 }
@@ -99,6 +120,133 @@ pub struct GetEventsForTaskRequest {
     pub correlation_id: Option<String>,
 }
 
+/// Export events and scheduled tasks as an iCalendar file.
+#[derive(Deserialize, Debug)]
+pub struct ExportIcsRequest {
+    pub start_date: String,
+    pub end_date: String,
+    /// Only this technician's schedule.
+    pub technician_id: Option<String>,
+    #[serde(default)]
+    pub correlation_id: Option<String>,
+}
+
+/// Export one appointment as an invitation.
+#[derive(Deserialize, Debug)]
+pub struct ExportEventInviteRequest {
+    pub id: String,
+    #[serde(default)]
+    pub correlation_id: Option<String>,
+}
+
+/// Import the events of an iCalendar file.
+#[derive(Deserialize, Debug)]
+pub struct ImportIcsRequest {
+    pub ics_data: String,
+    /// Update events imported before instead of skipping them.
+    #[serde(default)]
+    pub update_existing: bool,
+    /// Technician to assign new events to.
+    pub technician_id: Option<String>,
+    #[serde(default)]
+    pub correlation_id: Option<String>,
+}
+
+/// Get a technician's working time request.
+#[derive(Deserialize, Debug)]
+pub struct GetTechnicianAvailabilityRequest {
+    pub technician_id: String,
+    #[serde(default)]
+    pub correlation_id: Option<String>,
+}
+
+/// Set a technician's working time, capacity and skills request.
+#[derive(Deserialize, Debug)]
+pub struct SetTechnicianAvailabilityRequest {
+    pub availability: TechnicianAvailability,
+    #[serde(default)]
+    pub correlation_id: Option<String>,
+}
+
+/// List absences overlapping a date range request.
+#[derive(Deserialize, Debug)]
+pub struct ListAbsencesRequest {
+    pub technician_id: Option<String>,
+    pub start_date: String,
+    pub end_date: String,
+    #[serde(default)]
+    pub correlation_id: Option<String>,
+}
+
+/// Add absence request.
+#[derive(Deserialize, Debug)]
+pub struct AddAbsenceRequest {
+    pub absence: CreateAbsenceInput,
+    #[serde(default)]
+    pub correlation_id: Option<String>,
+}
+
+/// Delete absence request.
+#[derive(Deserialize, Debug)]
+pub struct DeleteAbsenceRequest {
+    pub id: String,
+    #[serde(default)]
+    pub correlation_id: Option<String>,
+}
+
+/// List public holidays request.
+#[derive(Deserialize, Debug)]
+pub struct ListPublicHolidaysRequest {
+    pub start_date: String,
+    pub end_date: String,
+    #[serde(default)]
+    pub correlation_id: Option<String>,
+}
+
+/// Add or rename a public holiday request.
+#[derive(Deserialize, Debug)]
+pub struct SetPublicHolidayRequest {
+    pub holiday: PublicHoliday,
+    #[serde(default)]
+    pub correlation_id: Option<String>,
+}
+
+/// Delete public holiday request.
+#[derive(Deserialize, Debug)]
+pub struct DeletePublicHolidayRequest {
+    pub date: String,
+    #[serde(default)]
+    pub correlation_id: Option<String>,
+}
+
+/// Weekly capacity heat map request.
+#[derive(Deserialize, Debug)]
+pub struct GetCapacityHeatmapRequest {
+    /// Any day of the week; the week runs Monday to Sunday.
+    pub week_start: String,
+    pub technician_ids: Option<Vec<String>>,
+    #[serde(default)]
+    pub correlation_id: Option<String>,
+}
+
+/// Slot suggestions request.
+#[derive(Deserialize, Debug)]
+pub struct SuggestSlotsRequest {
+    pub search: SlotSearch,
+    #[serde(default)]
+    pub correlation_id: Option<String>,
+}
+
+/// Schedule proposal request, for every unscheduled task.
+#[derive(Deserialize, Debug)]
+pub struct ProposeScheduleRequest {
+    pub start_date: String,
+    pub end_date: String,
+    pub technician_ids: Option<Vec<String>>,
+    #[serde(default)]
+    pub correlation_id: Option<String>,
+}
+
 // ── Inline tests (migrated from infrastructure/calendar.rs) ──────────────────
 
 #[cfg(test)]
@@ -651,4 +799,424 @@ mod tests {
             .expect("failed");
         assert!(result.has_conflict, "Non-force mode should detect conflict");
     }
+
+    fn weekly_series() -> CreateEventInput {
+        CreateEventInput {
+            title: "Weekly check".to_string(),
+            description: None,
+            start_datetime: "2025-06-02T09:00:00".to_string(),
+            end_datetime: "2025-06-02T10:00:00".to_string(),
+            all_day: None,
+            timezone: Some("Europe/Paris".to_string()),
+            event_type: None,
+            category: None,
+            task_id: None,
+            client_id: None,
+            technician_id: None,
+            location: None,
+            meeting_link: None,
+            is_virtual: None,
+            participants: None,
+            reminders: None,
+            color: None,
+            tags: None,
+            notes: None,
+            recurrence_rule: Some("FREQ=WEEKLY;COUNT=4".to_string()),
+            parent_event_id: None,
+            ical_uid: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_date_range_expands_recurring_events() {
+        let (db, _test_db) = setup_test_db();
+        let repo = CalendarEventRepository::new(db);
+        let series = repo.create(weekly_series(), None).await.unwrap();
+
+        let events = repo
+            .find_by_date_range("2025-06-05", "2025-06-30", None)
+            .await
+            .unwrap();
+        let starts: Vec<_> = events.iter().map(|e| e.start_datetime.as_str()).collect();
+        assert_eq!(
+            starts,
+            vec![
+                "2025-06-09T09:00:00",
+                "2025-06-16T09:00:00",
+                "2025-06-23T09:00:00"
+            ]
+        );
+        assert!(events.iter().all(|e| e.id == series.id));
+        assert_eq!(
+            events[0].recurrence_id.as_deref(),
+            Some("2025-06-09T09:00:00")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_editing_one_occurrence_materialises_exception() {
+        let (db, _test_db) = setup_test_db();
+        let repo = CalendarEventRepository::new(db);
+        let series = repo.create(weekly_series(), None).await.unwrap();
+
+        let changes = UpdateEventInput {
+            title: Some("Moved check".to_string()),
+            start_datetime: Some("2025-06-10T14:00:00".to_string()),
+            end_datetime: Some("2025-06-10T15:00:00".to_string()),
+            ..Default::default()
+        };
+        let exception = occurrences::update_occurrence(
+            &repo,
+            &series.id,
+            "2025-06-09T09:00:00",
+            RecurrenceScope::This,
+            changes,
+            None,
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        assert_eq!(
+            exception.parent_event_id.as_deref(),
+            Some(series.id.as_str())
+        );
+        assert!(!exception.is_recurring);
+
+        let events = repo
+            .find_by_date_range("2025-06-01", "2025-06-30", None)
+            .await
+            .unwrap();
+        let summary: Vec<_> = events
+            .iter()
+            .map(|e| (e.start_datetime.as_str(), e.title.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("2025-06-02T09:00:00", "Weekly check"),
+                ("2025-06-10T14:00:00", "Moved check"),
+                ("2025-06-16T09:00:00", "Weekly check"),
+                ("2025-06-23T09:00:00", "Weekly check"),
+            ]
+        );
+
+        assert!(occurrences::delete_occurrence(
+            &repo,
+            &series.id,
+            "2025-06-16T09:00:00",
+            RecurrenceScope::ThisAndFollowing,
+            None,
+        )
+        .await
+        .unwrap());
+        let events = repo
+            .find_by_date_range("2025-06-01", "2025-06-30", None)
+            .await
+            .unwrap();
+        assert_eq!(events.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_ics_import_deduplicates_by_uid() {
+        let (db, _test_db) = setup_test_db();
+        let repo = CalendarEventRepository::new(db);
+        let ics_data = "BEGIN:VCALENDAR\r\nVERSION:2.0\r\n\
+            BEGIN:VEVENT\r\nUID:weekly@example.com\r\n\
+            DTSTART;TZID=Europe/Paris:20250602T090000\r\nDURATION:PT1H\r\n\
+            RRULE:FREQ=WEEKLY;COUNT=3\r\nSUMMARY:Weekly check\r\nEND:VEVENT\r\n\
+            BEGIN:VEVENT\r\nUID:weekly@example.com\r\n\
+            RECURRENCE-ID;TZID=Europe/Paris:20250609T090000\r\n\
+            DTSTART;TZID=Europe/Paris:20250610T140000\r\nDURATION:PT1H\r\n\
+            SUMMARY:Moved check\r\nEND:VEVENT\r\n\
+            END:VCALENDAR\r\n";
+
+        let first = ics::import(&repo, ics_data, false, None, "user-1")
+            .await
+            .unwrap();
+        assert_eq!((first.created, first.duplicates_skipped), (2, 0));
+        let again = ics::import(&repo, ics_data, false, None, "user-1")
+            .await
+            .unwrap();
+        assert_eq!((again.created, again.duplicates_skipped), (0, 2));
+        let updated = ics::import(&repo, ics_data, true, None, "user-1")
+            .await
+            .unwrap();
+        assert_eq!((updated.created, updated.updated), (0, 2));
+
+        let events = repo
+            .find_by_date_range("2025-06-01", "2025-06-30", None)
+            .await
+            .unwrap();
+        let summary: Vec<_> = events
+            .iter()
+            .map(|e| (e.start_datetime.as_str(), e.title.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("2025-06-02T09:00:00", "Weekly check"),
+                ("2025-06-10T14:00:00", "Moved check"),
+                ("2025-06-16T09:00:00", "Weekly check"),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_reminder_log_fires_each_reminder_once() {
+        use crate::domains::calendar::domain::reminders::{Reminder, ReminderSource};
+        use reminder_repository::{ReminderLogRepository, ReminderOutcome};
+
+        let (db, _test_db) = setup_test_db();
+        let log = ReminderLogRepository::new(db);
+        let reminder = Reminder {
+            source: ReminderSource::Task,
+            source_id: "task-1".to_string(),
+            title: "T-001 — Capot".to_string(),
+            starts_at: chrono::DateTime::from_timestamp(1_781_078_400, 0).unwrap(),
+            minutes_before: 60,
+            earliest: true,
+            user_id: Some("tech1".to_string()),
+            client_id: None,
+            task_id: Some("task-1".to_string()),
+        };
+
+        let claimed = log
+            .claim(&reminder, "user:tech1", ReminderOutcome::Sent, 1_000)
+            .unwrap();
+        assert!(claimed.is_some());
+        let again = log
+            .claim(&reminder, "user:tech1", ReminderOutcome::Sent, 2_000)
+            .unwrap();
+        assert!(again.is_none(), "A reminder must not fire twice");
+        let other_recipient = log
+            .claim(&reminder, "client:c1:sms", ReminderOutcome::Sent, 2_000)
+            .unwrap();
+        assert!(other_recipient.is_some());
+
+        let moved = Reminder {
+            starts_at: reminder.starts_at + chrono::Duration::hours(2),
+            ..reminder
+        };
+        assert!(log
+            .claim(&moved, "user:tech1", ReminderOutcome::Sent, 3_000)
+            .unwrap()
+            .is_some());
+        assert_eq!(log.prune_before(2_500).unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_capacity_and_working_time_follow_the_capacity_policy() {
+        let (db, _test_db) = setup_test_db();
+        let service = CalendarService::new(db.clone());
+        // 2025-06-16 is a Monday.
+        insert_test_task(
+            &db,
+            "task-morning",
+            "tech1",
+            "2025-06-16",
+            Some("09:00"),
+            Some("11:00"),
+            "pending",
+        );
+        insert_test_task(
+            &db,
+            "task-new",
+            "tech1",
+            "2025-06-16",
+            None,
+            None,
+            "pending",
+        );
+        service
+            .set_technician_availability(
+                TechnicianAvailability {
+                    technician_id: "tech1".to_string(),
+                    daily_capacity_minutes: Some(180),
+                    ..Default::default()
+                },
+                "admin",
+            )
+            .unwrap();
+
+        let check = |date: &'static str, start: &'static str, end: &'static str| {
+            service.check_conflicts(
+                "task-new".to_string(),
+                date.to_string(),
+                Some(start.to_string()),
+                Some(end.to_string()),
+            )
+        };
+
+        let warned = check("2025-06-16", "13:00", "15:00").await.unwrap();
+        assert!(!warned.has_conflict, "The default policy only warns");
+        assert_eq!(warned.warnings.len(), 1);
+        assert!(warned.warnings[0].contains("240 of 180 minutes"));
+
+        db.execute(
+            "INSERT OR REPLACE INTO organization_settings (key, value, category)
+             VALUES ('business_capacity_policy', 'reject', 'business')",
+            [],
+        )
+        .unwrap();
+        let rejected = check("2025-06-16", "13:00", "15:00").await.unwrap();
+        assert!(rejected.has_conflict);
+        assert_eq!(rejected.conflict_type.as_deref(), Some("over_capacity"));
+
+        let evening = check("2025-06-16", "17:30", "18:30").await.unwrap();
+        assert_eq!(
+            evening.conflict_type.as_deref(),
+            Some("outside_working_time")
+        );
+
+        service
+            .set_public_holiday(PublicHoliday {
+                date: "2025-06-17".to_string(),
+                name: "Jour férié".to_string(),
+            })
+            .unwrap();
+        let holiday = check("2025-06-17", "09:00", "10:00").await.unwrap();
+        assert_eq!(
+            holiday.conflict_type.as_deref(),
+            Some("technician_unavailable")
+        );
+
+        let heatmap = service
+            .capacity_heatmap("2025-06-18", Some(vec!["tech1".to_string()]))
+            .await
+            .unwrap();
+        assert_eq!(heatmap.week_start, "2025-06-16");
+        let days = &heatmap.technicians[0].days;
+        assert_eq!(days[0].booked_minutes, 120);
+        assert_eq!(days[0].load_percent, Some(67));
+        assert_eq!(days[1].status, DayStatus::Holiday);
+        assert_eq!(days[6].status, DayStatus::DayOff);
+        assert_eq!(heatmap.technicians[0].capacity_minutes, 4 * 180);
+    }
+
+    #[tokio::test]
+    async fn test_slots_avoid_bookings_and_follow_skills() {
+        use chrono::Datelike;
+        let (db, _test_db) = setup_test_db();
+        let service = CalendarService::new(db.clone());
+        let today = chrono::Utc::now().date_naive();
+        let monday =
+            today + chrono::Duration::days(14 - i64::from(today.weekday().num_days_from_monday()));
+        let tuesday = (monday + chrono::Duration::days(1)).to_string();
+        let friday = (monday + chrono::Duration::days(4)).to_string();
+        let monday = monday.to_string();
+
+        insert_test_task(
+            &db,
+            "task-booked",
+            "tech1",
+            &monday,
+            Some("08:00"),
+            Some("12:00"),
+            "pending",
+        );
+        insert_test_task(&db, "task-other", "tech2", &monday, None, None, "cancelled");
+        service
+            .set_technician_availability(
+                TechnicianAvailability {
+                    technician_id: "tech2".to_string(),
+                    skills: vec!["ceramic".to_string()],
+                    ..Default::default()
+                },
+                "admin",
+            )
+            .unwrap();
+        // Unscheduled: no estimate, so the express workflow's 40 minutes.
+        for (id, technician_id, priority) in [
+            ("task-new", None, "medium"),
+            ("task-assigned", Some("tech1"), "urgent"),
+        ] {
+            db.execute(
+                r#"INSERT INTO tasks (id, task_number, title, vehicle_plate, ppf_zones,
+                    technician_id, status, priority, created_at, updated_at)
+                   VALUES (?1, ?1, ?1, 'ABC123', '["hood"]', ?2, 'pending', ?3, 0, 0)"#,
+                params![id, technician_id, priority],
+            )
+            .unwrap();
+        }
+        let technicians = Some(vec!["tech1".to_string(), "tech2".to_string()]);
+
+        let suggestions = service
+            .suggest_slots(SlotSearch {
+                task_id: Some("task-new".to_string()),
+                start_date: monday.clone(),
+                end_date: friday.clone(),
+                technician_ids: technicians.clone(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(suggestions.duration_minutes, 40);
+        let best = &suggestions.technicians[0];
+        assert_eq!(
+            best.technician_id, "tech2",
+            "The free technician ranks first"
+        );
+        assert_eq!(best.slots[0].date, monday);
+        assert_eq!(
+            (
+                best.slots[0].start_time.as_str(),
+                best.slots[0].end_time.as_str()
+            ),
+            ("08:00", "08:40")
+        );
+        // tech1 is free after 12:00 on Monday, but an empty Tuesday ranks higher.
+        let tech1 = &suggestions.technicians[1].slots;
+        assert_eq!(
+            (tech1[0].date.as_str(), tech1[0].start_time.as_str()),
+            (tuesday.as_str(), "08:00")
+        );
+        let monday_slot = tech1.iter().find(|slot| slot.date == monday).unwrap();
+        assert_eq!(monday_slot.start_time, "12:00");
+        assert_eq!(monday_slot.booked_minutes, 280);
+
+        let ppf = service
+            .suggest_slots(SlotSearch {
+                duration_minutes: Some(60),
+                intervention_type: Some("PPF".to_string()),
+                start_date: monday.clone(),
+                end_date: friday.clone(),
+                technician_ids: technicians.clone(),
+                limit: Some(2),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(ppf.technicians.len(), 1, "tech2 only does ceramic");
+        assert_eq!(ppf.technicians[0].slots.len(), 2);
+
+        let proposal = service
+            .propose_schedule(&monday, &friday, technicians)
+            .await
+            .unwrap();
+        assert!(proposal.unplaced.is_empty());
+        let placed: Vec<_> = proposal
+            .proposals
+            .iter()
+            .map(|booking| {
+                (
+                    booking.task_id.as_str(),
+                    booking.technician_id.as_str(),
+                    booking.date.as_str(),
+                    booking.start_time.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            placed,
+            [
+                ("task-assigned", "tech1", tuesday.as_str(), "08:00"),
+                ("task-new", "tech2", monday.as_str(), "08:00"),
+            ]
+        );
+
+        assert!(matches!(
+            service.propose_schedule(&friday, &monday, None).await,
+            Err(crate::commands::AppError::Validation(_))
+        ));
+    }
 }
@@ -0,0 +1,200 @@
+//! Edits and deletes aimed at one occurrence of a recurring series.
+//!
+//! * `This` excludes the occurrence from the series (EXDATE) and, for an
+//!   edit, materialises it as a child event with the changes applied.
+//! * `ThisAndFollowing` ends the series just before the occurrence (UNTIL)
+//!   and, for an edit, continues it as a new child series from there.
+//! * `All` applies to the series; a new time given for the occurrence moves
+//!   every occurrence by the same amount.
+
+use super::*;
+use crate::domains::calendar::domain::recurrence::{
+    parse_event_time, parse_timezone, RecurrenceRule, Series,
+};
+use crate::shared::ipc::errors::AppError as IpcAppError;
+use crate::shared::repositories::CalendarEventRepositoryContract;
+use chrono::NaiveDateTime;
+
+/// Check a recurrence rule and, when given, the timezone its occurrences are
+/// computed in.
+pub fn validate_recurrence(rule: &str, timezone: Option<&str>) -> Result<(), IpcAppError> {
+    rule.parse::<RecurrenceRule>()
+        .and_then(|_| timezone.map_or(Ok(()), |tz| parse_timezone(tz).map(|_| ())))
+        .map_err(|e| IpcAppError::Validation(format!("Invalid recurrence: {}", e)))
+}
+
+async fn load_series(
+    repo: &dyn CalendarEventRepositoryContract,
+    series_id: &str,
+) -> Result<(CalendarEvent, Series), IpcAppError> {
+    let event = repo
+        .find_by_id(series_id.to_string())
+        .await
+        .map_err(|e| IpcAppError::internal_sanitized("get_calendar_event", &e))?
+        .ok_or_else(|| IpcAppError::NotFound(format!("Calendar event {} not found", series_id)))?;
+    let series = Series::of(&event).map_err(IpcAppError::Validation)?;
+    Ok((event, series))
+}
+
+fn rule_change(rule: &RecurrenceRule) -> UpdateEventInput {
+    UpdateEventInput {
+        recurrence_rule: Some(rule.to_string()),
+        ..Default::default()
+    }
+}
+
+/// A copy of `event` at `occurrence`, linked to it as parent.
+fn copy_at(
+    event: &CalendarEvent,
+    series: &Series,
+    occurrence: NaiveDateTime,
+    recurrence_rule: Option<String>,
+) -> CreateEventInput {
+    CreateEventInput {
+        title: event.title.clone(),
+        description: event.description.clone(),
+        start_datetime: series.format(occurrence),
+        end_datetime: series.format(occurrence + series.duration),
+        all_day: Some(event.all_day),
+        timezone: Some(event.timezone.clone()),
+        event_type: Some(event.event_type.clone()),
+        category: event.category.clone(),
+        task_id: event.task_id.clone(),
+        client_id: event.client_id.clone(),
+        technician_id: event.technician_id.clone(),
+        location: event.location.clone(),
+        meeting_link: event.meeting_link.clone(),
+        is_virtual: Some(event.is_virtual),
+        participants: Some(event.participants.clone()),
+        reminders: Some(event.reminders.clone()),
+        color: event.color.clone(),
+        tags: Some(event.tags.clone()),
+        notes: event.notes.clone(),
+        recurrence_rule,
+        parent_event_id: Some(event.id.clone()),
+        ical_uid: None,
+    }
+}
+
+/// Turn times given for `occurrence` into the same shift of the series.
+fn shift_series_times(
+    mut changes: UpdateEventInput,
+    series: &Series,
+    occurrence: NaiveDateTime,
+) -> Result<UpdateEventInput, IpcAppError> {
+    let parse = |value: &str| {
+        parse_event_time(value, series.tz)
+            .map(|(local, _)| local)
+            .ok_or_else(|| IpcAppError::Validation(format!("Invalid event time: {}", value)))
+    };
+    if let Some(start) = changes.start_datetime.take() {
+        let shift = parse(&start)? - occurrence;
+        changes.start_datetime = Some(series.format(series.start + shift));
+    }
+    if let Some(end) = changes.end_datetime.take() {
+        let shift = parse(&end)? - (occurrence + series.duration);
+        changes.end_datetime = Some(series.format(series.start + series.duration + shift));
+    }
+    Ok(changes)
+}
+
+/// Apply `changes` to the occurrence of `series_id` that originally started
+/// at `recurrence_id`. Returns the event that now holds the changes.
+pub async fn update_occurrence(
+    repo: &dyn CalendarEventRepositoryContract,
+    series_id: &str,
+    recurrence_id: &str,
+    scope: RecurrenceScope,
+    mut changes: UpdateEventInput,
+    updated_by: Option<String>,
+) -> Result<Option<CalendarEvent>, IpcAppError> {
+    let (event, series) = load_series(repo, series_id).await?;
+    let (occurrence, preceding) = series
+        .occurrence(recurrence_id)
+        .map_err(IpcAppError::Validation)?;
+    let map_err = |e| IpcAppError::internal_sanitized("update_calendar_event", &e);
+
+    let (trimmed_rule, continuation) = match scope {
+        RecurrenceScope::This => {
+            // A single occurrence does not recur.
+            changes.recurrence_rule = None;
+            (
+                series.rule.excluding(occurrence, series.form, series.tz),
+                None,
+            )
+        }
+        RecurrenceScope::ThisAndFollowing if preceding > 0 => (
+            series
+                .rule
+                .ending_before(occurrence, series.form, series.tz),
+            Some(
+                series
+                    .rule
+                    .starting_at(occurrence, preceding, series.tz)
+                    .to_string(),
+            ),
+        ),
+        RecurrenceScope::ThisAndFollowing | RecurrenceScope::All => {
+            let changes = shift_series_times(changes, &series, occurrence)?;
+            return repo
+                .update(series_id, changes, updated_by)
+                .await
+                .map_err(map_err);
+        }
+    };
+
+    changes.status = changes.status.or(Some(event.status.clone()));
+    let child = repo
+        .create(
+            copy_at(&event, &series, occurrence, continuation),
+            updated_by.clone(),
+        )
+        .await
+        .map_err(map_err)?;
+    repo.update(series_id, rule_change(&trimmed_rule), updated_by.clone())
+        .await
+        .map_err(map_err)?;
+    repo.update(&child.id, changes, updated_by)
+        .await
+        .map_err(map_err)
+}
+
+/// Delete the occurrence of `series_id` that originally started at
+/// `recurrence_id`, with the following ones or the whole series per `scope`.
+pub async fn delete_occurrence(
+    repo: &dyn CalendarEventRepositoryContract,
+    series_id: &str,
+    recurrence_id: &str,
+    scope: RecurrenceScope,
+    deleted_by: Option<String>,
+) -> Result<bool, IpcAppError> {
+    let (_, series) = load_series(repo, series_id).await?;
+    let (occurrence, preceding) = series
+        .occurrence(recurrence_id)
+        .map_err(IpcAppError::Validation)?;
+    let map_err = |e| IpcAppError::internal_sanitized("delete_calendar_event", &e);
+
+    let trimmed_rule = match scope {
+        RecurrenceScope::This => series.rule.excluding(occurrence, series.form, series.tz),
+        RecurrenceScope::ThisAndFollowing if preceding > 0 => {
+            series
+                .rule
+                .ending_before(occurrence, series.form, series.tz)
+        }
+        RecurrenceScope::ThisAndFollowing | RecurrenceScope::All => {
+            // Exceptions and split-off series go with the series.
+            let mut pending = vec![series_id.to_string()];
+            while let Some(id) = pending.pop() {
+                let children = repo.find_by_parent(&id).await.map_err(map_err)?;
+                pending.extend(children.into_iter().map(|child| child.id));
+                repo.delete_by_id(id).await.map_err(map_err)?;
+            }
+            return Ok(true);
+        }
+    };
+    Ok(repo
+        .update(series_id, rule_change(&trimmed_rule), deleted_by)
+        .await
+        .map_err(map_err)?
+        .is_some())
+}
@@ -0,0 +1,117 @@
+//! ReminderLogRepository — the log of fired calendar reminders.
+
+use crate::commands::AppError;
+use crate::db::Database;
+use crate::domains::calendar::domain::reminders::Reminder;
+use rusqlite::{params, OptionalExtension};
+use std::sync::Arc;
+
+/// How a claimed reminder was handled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReminderOutcome {
+    Sent,
+    Failed,
+    /// The recipient turned this kind of reminder off.
+    Skipped,
+}
+
+impl ReminderOutcome {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Sent => "sent",
+            Self::Failed => "failed",
+            Self::Skipped => "skipped",
+        }
+    }
+}
+
+/// Contact details used for customer reminders.
+pub struct ClientContact {
+    pub name: String,
+    pub email: Option<String>,
+    pub phone: Option<String>,
+}
+
+/// Records which reminders have fired so none fires twice.
+pub struct ReminderLogRepository {
+    db: Arc<Database>,
+}
+
+impl ReminderLogRepository {
+    pub fn new(db: Arc<Database>) -> Self {
+        Self { db }
+    }
+
+    /// Record `reminder` for `recipient` unless it was already recorded.
+    /// Returns the log entry id, or `None` when it had already fired.
+    pub fn claim(
+        &self,
+        reminder: &Reminder,
+        recipient: &str,
+        outcome: ReminderOutcome,
+        fired_at: i64,
+    ) -> Result<Option<String>, AppError> {
+        let id = crate::shared::utils::uuid::generate_uuid_string();
+        let inserted = self
+            .db
+            .execute(
+                "INSERT OR IGNORE INTO calendar_reminder_log
+                 (id, source_type, source_id, occurrence_start, minutes_before, recipient, status, fired_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                params![
+                    id,
+                    reminder.source.as_str(),
+                    reminder.source_id,
+                    reminder.occurrence_key(),
+                    reminder.minutes_before,
+                    recipient,
+                    outcome.as_str(),
+                    fired_at
+                ],
+            )
+            .map_err(AppError::Database)?;
+        Ok((inserted > 0).then_some(id))
+    }
+
+    /// Note that sending a claimed reminder failed. It is not retried.
+    pub fn mark_failed(&self, id: &str, error: &str) -> Result<(), AppError> {
+        self.db
+            .execute(
+                "UPDATE calendar_reminder_log SET status = ?2, error = ?3 WHERE id = ?1",
+                params![id, ReminderOutcome::Failed.as_str(), error],
+            )
+            .map(|_| ())
+            .map_err(AppError::Database)
+    }
+
+    pub fn client_contact(&self, client_id: &str) -> Result<Option<ClientContact>, AppError> {
+        let conn = self
+            .db
+            .get_connection()
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        conn.query_row(
+            "SELECT name, email, phone FROM clients WHERE id = ?1 AND deleted_at IS NULL",
+            params![client_id],
+            |row| {
+                Ok(ClientContact {
+                    name: row.get(0)?,
+                    email: row.get(1)?,
+                    phone: row.get(2)?,
+                })
+            },
+        )
+        .optional()
+        .map_err(|e| AppError::Database(e.to_string()))
+    }
+
+    /// Forget reminders fired before `cutoff` (epoch ms). Returns the number
+    /// removed.
+    pub fn prune_before(&self, cutoff: i64) -> Result<usize, AppError> {
+        self.db
+            .execute(
+                "DELETE FROM calendar_reminder_log WHERE fired_at < ?1",
+                params![cutoff],
+            )
+            .map_err(AppError::Database)
+    }
+}
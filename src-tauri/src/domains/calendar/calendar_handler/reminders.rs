@@ -0,0 +1,340 @@
+//! Reminder scheduler for calendar events and scheduled tasks.
+//!
+//! [`ReminderScheduler::run_due`] runs every minute. Each due reminder (see
+//! [`crate::domains::calendar::domain::reminders`]) becomes an in-app
+//! notification for the technician, and — when the shop turned customer
+//! reminders on — one email and/or SMS per appointment to the client.
+//!
+//! Every reminder is claimed in `calendar_reminder_log` before it is sent, so
+//! nothing fires twice, even after a restart. A reminder falling in the
+//! user's quiet hours is not claimed; it is retried on the next run and fires
+//! once quiet hours end, unless the occurrence has started by then. Customer
+//! messages are queued; the message dispatcher holds them during the shop's
+//! quiet hours.
+
+use super::reminder_repository::{ClientContact, ReminderLogRepository, ReminderOutcome};
+use super::*;
+use crate::db::Database;
+use crate::domains::calendar::domain::recurrence::parse_timezone;
+use crate::domains::calendar::domain::reminders::{
+    event_reminders, task_reminders, Reminder, ReminderSource,
+};
+use crate::shared::contracts::notification::NotificationSender;
+use crate::shared::ipc::errors::AppError as IpcAppError;
+use crate::shared::repositories::CalendarEventRepositoryContract;
+use crate::shared::services::cross_domain::{
+    is_quiet_hours_at, NotificationsFacade, SettingsRepository, UserSettingsRepository,
+};
+use crate::shared::services::domain_event::DomainEvent;
+use chrono::{DateTime, Duration, Utc};
+use chrono_tz::Tz;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// Comma-separated minutes before a scheduled task to remind its technician.
+pub const TASK_REMINDER_MINUTES_ENV: &str = "RPMA_TASK_REMINDER_MINUTES";
+pub const DEFAULT_TASK_REMINDER_MINUTES: &[i32] = &[60];
+/// How far ahead occurrences are checked; bounds the longest usable lead time.
+const LOOKAHEAD_DAYS: i64 = 14;
+/// How long fired reminders are remembered.
+const LOG_RETENTION_DAYS: i64 = 90;
+
+/// What one run did.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ReminderRunSummary {
+    pub notified: usize,
+    pub messages_queued: usize,
+    pub skipped: usize,
+    /// Held back by quiet hours, to be retried.
+    pub deferred: usize,
+    pub failed: usize,
+}
+
+pub struct ReminderScheduler {
+    events: Arc<dyn CalendarEventRepositoryContract>,
+    calendar_service: Arc<CalendarService>,
+    log: ReminderLogRepository,
+    notifications: Arc<NotificationsFacade>,
+    messages: Arc<dyn NotificationSender>,
+    db: Arc<Database>,
+}
+
+impl ReminderScheduler {
+    pub fn new(
+        events: Arc<dyn CalendarEventRepositoryContract>,
+        calendar_service: Arc<CalendarService>,
+        notifications: Arc<NotificationsFacade>,
+        messages: Arc<dyn NotificationSender>,
+        db: Arc<Database>,
+    ) -> Self {
+        Self {
+            events,
+            calendar_service,
+            log: ReminderLogRepository::new(db.clone()),
+            notifications,
+            messages,
+            db,
+        }
+    }
+
+    /// Task lead times from `RPMA_TASK_REMINDER_MINUTES`.
+    pub fn task_reminder_minutes() -> Vec<i32> {
+        std::env::var(TASK_REMINDER_MINUTES_ENV)
+            .ok()
+            .map(|value| {
+                value
+                    .split(',')
+                    .filter_map(|minutes| minutes.trim().parse::<i32>().ok())
+                    .filter(|minutes| *minutes >= 0)
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_else(|| DEFAULT_TASK_REMINDER_MINUTES.to_vec())
+    }
+
+    pub async fn run_due(&self) -> Result<ReminderRunSummary, IpcAppError> {
+        self.run_due_at(Utc::now()).await
+    }
+
+    /// Fire the reminders due at `now`.
+    pub async fn run_due_at(&self, now: DateTime<Utc>) -> Result<ReminderRunSummary, IpcAppError> {
+        let app_settings = SettingsRepository::new(self.db.clone()).get_app_settings_db()?;
+        let timezone = app_settings.general.timezone.clone();
+        let shop_tz = parse_timezone(&timezone).unwrap_or(chrono_tz::Europe::Paris);
+
+        let mut summary = ReminderRunSummary::default();
+        for reminder in self.due_reminders(now, shop_tz).await? {
+            if let Some(user_id) = reminder.user_id.as_deref() {
+                self.notify_user(&reminder, user_id, &timezone, shop_tz, now, &mut summary)
+                    .await?;
+            }
+            let settings = &app_settings.notifications;
+            if reminder.earliest && settings.customer_appointment_reminders {
+                if let Some(client_id) = reminder.client_id.as_deref() {
+                    let channels = [
+                        ("email", settings.email_notifications),
+                        ("sms", settings.sms_notifications),
+                    ];
+                    for (channel, enabled) in channels {
+                        if enabled {
+                            self.message_client(
+                                &reminder,
+                                client_id,
+                                channel,
+                                shop_tz,
+                                now,
+                                &mut summary,
+                            )
+                            .await?;
+                        }
+                    }
+                }
+            }
+        }
+
+        let cutoff = now - Duration::days(LOG_RETENTION_DAYS);
+        self.log.prune_before(cutoff.timestamp_millis())?;
+        Ok(summary)
+    }
+
+    /// Reminders of events and scheduled tasks due at `now`.
+    async fn due_reminders(
+        &self,
+        now: DateTime<Utc>,
+        shop_tz: Tz,
+    ) -> Result<Vec<Reminder>, IpcAppError> {
+        // Stored times are compared as text; a day's margin covers floating
+        // times in any offset.
+        let from = now - Duration::days(1);
+        let to = now + Duration::days(LOOKAHEAD_DAYS);
+        let events = self
+            .events
+            .find_events_in_range(from.timestamp_millis(), to.timestamp_millis(), None)
+            .await
+            .map_err(|e| IpcAppError::internal_sanitized("find_due_reminders", &e))?;
+        let tasks = self
+            .calendar_service
+            .get_tasks(
+                CalendarDateRange {
+                    start_date: from.format("%Y-%m-%d").to_string(),
+                    end_date: to.format("%Y-%m-%d").to_string(),
+                },
+                None,
+                None,
+            )
+            .await?;
+
+        // A task whose event has reminders of its own is reminded through it.
+        let covered_tasks: HashSet<&str> = events
+            .iter()
+            .filter(|event| !event.reminders.is_empty())
+            .filter_map(|event| event.task_id.as_deref())
+            .collect();
+        let task_minutes = Self::task_reminder_minutes();
+        let mut due: Vec<Reminder> = events
+            .iter()
+            .flat_map(|event| event_reminders(event, shop_tz, now))
+            .collect();
+        due.extend(
+            tasks
+                .iter()
+                .filter(|task| !covered_tasks.contains(task.id.as_str()))
+                .flat_map(|task| task_reminders(task, &task_minutes, shop_tz, now)),
+        );
+        Ok(due)
+    }
+
+    async fn notify_user(
+        &self,
+        reminder: &Reminder,
+        user_id: &str,
+        timezone: &str,
+        shop_tz: Tz,
+        now: DateTime<Utc>,
+        summary: &mut ReminderRunSummary,
+    ) -> Result<(), IpcAppError> {
+        let recipient = format!("user:{}", user_id);
+        let settings = UserSettingsRepository::new(self.db.clone())
+            .get_user_settings(user_id)?
+            .notifications;
+        if !settings.in_app_enabled {
+            if self
+                .log
+                .claim(
+                    reminder,
+                    &recipient,
+                    ReminderOutcome::Skipped,
+                    now.timestamp_millis(),
+                )?
+                .is_some()
+            {
+                summary.skipped += 1;
+            }
+            return Ok(());
+        }
+        if is_quiet_hours_at(&settings, Some(timezone), now.timestamp_millis()) {
+            summary.deferred += 1;
+            return Ok(());
+        }
+        let Some(log_id) = self.log.claim(
+            reminder,
+            &recipient,
+            ReminderOutcome::Sent,
+            now.timestamp_millis(),
+        )?
+        else {
+            return Ok(());
+        };
+
+        let (entity_type, entity_id, entity_url) = match reminder.source {
+            ReminderSource::Event => (
+                "calendar_event".to_string(),
+                reminder.source_id.clone(),
+                "/schedule".to_string(),
+            ),
+            ReminderSource::Task => (
+                "task".to_string(),
+                reminder.source_id.clone(),
+                format!("/tasks/{}", reminder.source_id),
+            ),
+        };
+        let result = self
+            .notifications
+            .create_notification_from_parts(
+                user_id.to_string(),
+                DomainEvent::DEADLINE_REMINDER_NOTIF.to_string(),
+                format!("Rappel : {}", reminder.title),
+                format!(
+                    "{} commence le {}.",
+                    reminder.title,
+                    local_start(reminder, shop_tz)
+                ),
+                entity_type,
+                entity_id,
+                entity_url,
+            )
+            .await;
+        match result {
+            Ok(_) => summary.notified += 1,
+            Err(error) => {
+                tracing::warn!(reminder = %log_id, "Reminder notification failed: {}", error);
+                self.log.mark_failed(&log_id, &error.to_string())?;
+                summary.failed += 1;
+            }
+        }
+        Ok(())
+    }
+
+    async fn message_client(
+        &self,
+        reminder: &Reminder,
+        client_id: &str,
+        channel: &str,
+        shop_tz: Tz,
+        now: DateTime<Utc>,
+        summary: &mut ReminderRunSummary,
+    ) -> Result<(), IpcAppError> {
+        let Some(contact) = self.log.client_contact(client_id)? else {
+            return Ok(());
+        };
+        let (email, phone) = match channel {
+            "email" => (contact.email.clone().filter(|e| !e.trim().is_empty()), None),
+            _ => (None, contact.phone.clone().filter(|p| !p.trim().is_empty())),
+        };
+        if email.is_none() && phone.is_none() {
+            return Ok(());
+        }
+        let recipient = format!("client:{}:{}", client_id, channel);
+        let Some(log_id) = self.log.claim(
+            reminder,
+            &recipient,
+            ReminderOutcome::Sent,
+            now.timestamp_millis(),
+        )?
+        else {
+            return Ok(());
+        };
+
+        let result = self
+            .messages
+            .send_message_raw(
+                channel.to_string(),
+                Some("appointment_reminder".to_string()),
+                None,
+                email,
+                phone,
+                Some("Rappel de rendez-vous".to_string()),
+                customer_message(&contact, reminder, shop_tz),
+                reminder.task_id.clone(),
+                Some(client_id.to_string()),
+                None,
+                None,
+                Some(log_id.clone()),
+            )
+            .await;
+        match result {
+            Ok(_) => summary.messages_queued += 1,
+            Err(error) => {
+                tracing::warn!(reminder = %log_id, "Customer reminder failed: {}", error);
+                self.log.mark_failed(&log_id, &error.to_string())?;
+                summary.failed += 1;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn local_start(reminder: &Reminder, shop_tz: Tz) -> String {
+    reminder
+        .starts_at
+        .with_timezone(&shop_tz)
+        .format("%d/%m/%Y à %H:%M")
+        .to_string()
+}
+
+fn customer_message(contact: &ClientContact, reminder: &Reminder, shop_tz: Tz) -> String {
+    format!(
+        "Bonjour {}, nous vous rappelons votre rendez-vous le {}. À bientôt !",
+        contact.name,
+        local_start(reminder, shop_tz)
+    )
+}
@@ -4,18 +4,25 @@
 use super::*;
 use crate::commands::AppError;
 use crate::db::Database;
+use crate::shared::services::cross_domain::OrganizationRepository;
 use crate::shared::services::validation::ValidationService;
 use std::sync::Arc;
 
 /// Business logic for calendar scheduling and conflict detection.
 pub struct CalendarService {
     pub(super) repo: CalendarRepository,
+    pub(super) availability: AvailabilityRepository,
+    pub(super) events: CalendarEventRepository,
+    pub(super) organization: OrganizationRepository,
 }
 
 impl CalendarService {
     pub fn new(db: Arc<Database>) -> Self {
         Self {
-            repo: CalendarRepository::new(db),
+            repo: CalendarRepository::new(db.clone()),
+            availability: AvailabilityRepository::new(db.clone()),
+            events: CalendarEventRepository::new(db.clone()),
+            organization: OrganizationRepository::new(db),
         }
     }
 
@@ -54,6 +61,7 @@ impl CalendarService {
                     conflict_type: None,
                     conflicting_tasks: vec![],
                     message: None,
+                    warnings: vec![],
                 });
             }
         };
@@ -65,8 +73,9 @@ impl CalendarService {
             new_end.as_deref(),
         )?;
 
-        let has_conflict = !conflicts.is_empty();
-        let message = if has_conflict {
+        let mut problems = Vec::new();
+        let mut conflict_type = None;
+        if !conflicts.is_empty() {
             let details: Vec<String> = conflicts
                 .iter()
                 .map(|c| {
@@ -78,25 +87,40 @@ impl CalendarService {
                     format!("'{}' ({})", c.title, time_range)
                 })
                 .collect();
-            Some(format!(
+            problems.push(format!(
                 "Technician has {} conflicting task(s) on {}: {}",
                 conflicts.len(),
                 new_date,
                 details.join(", ")
-            ))
-        } else {
-            None
-        };
+            ));
+            conflict_type = Some("time_overlap".to_string());
+        }
 
-        Ok(ConflictDetection {
-            has_conflict,
-            conflict_type: if has_conflict {
-                Some("time_overlap".to_string())
+        let availability = self
+            .check_availability(
+                &task_id,
+                &tech_id,
+                &new_date,
+                new_start.as_deref(),
+                new_end.as_deref(),
+            )
+            .await?;
+        let mut warnings = Vec::new();
+        for issue in availability.issues {
+            if availability.blocking {
+                conflict_type.get_or_insert_with(|| issue.kind().to_string());
+                problems.push(issue.message(&new_date));
             } else {
-                None
-            },
+                warnings.push(issue.message(&new_date));
+            }
+        }
+
+        Ok(ConflictDetection {
+            has_conflict: !problems.is_empty(),
+            conflict_type,
             conflicting_tasks: conflicts,
-            message,
+            message: (!problems.is_empty()).then(|| problems.join("; ")),
+            warnings,
         })
     }
 
@@ -157,6 +181,7 @@ impl CalendarService {
             conflict_type: None,
             conflicting_tasks: vec![],
             message: None,
+            warnings: conflicts.warnings,
         })
     }
 
@@ -177,6 +202,7 @@ impl CalendarService {
                 conflict_type: None,
                 conflicting_tasks: vec![],
                 message: None,
+                warnings: vec![],
             })
         } else {
             self.schedule_task_with_conflict_check(task_id, new_date, new_start, new_end, user_id)
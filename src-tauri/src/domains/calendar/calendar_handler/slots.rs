@@ -0,0 +1,286 @@
+//! Slot finder: free slots for a task or a duration, and a proposed schedule
+//! for every unscheduled task (see
+//! [`crate::domains::calendar::domain::slots`]).
+//!
+//! Slots keep to each technician's working time and capacity and avoid their
+//! tasks and events. Nothing is booked here; a chosen slot is booked through
+//! the usual scheduling, with its conflict and availability checks.
+
+use super::availability::{format_date, parse_date, OrganizationCalendar};
+use super::availability_repository::PlanningTask;
+use super::*;
+use crate::commands::AppError;
+use crate::domains::calendar::domain::slots::{
+    place_all, Slot, SlotNeed, SlotWindow, TechnicianPlan,
+};
+use crate::shared::services::cross_domain::{
+    Intervention, InterventionType, WorkflowStrategyFactory,
+};
+use chrono::NaiveDate;
+use std::collections::HashMap;
+
+/// Longest window searched, in days.
+const MAX_WINDOW_DAYS: i64 = 62;
+const DEFAULT_SLOT_LIMIT: usize = 5;
+
+fn slot_window(
+    organization: &OrganizationCalendar,
+    start_date: &str,
+    end_date: &str,
+) -> Result<SlotWindow, AppError> {
+    let from = parse_date(start_date, "start_date")?;
+    let to = parse_date(end_date, "end_date")?;
+    if to < from {
+        return Err(AppError::Validation(
+            "end_date must not be before start_date".to_string(),
+        ));
+    }
+    if (to - from).num_days() >= MAX_WINDOW_DAYS {
+        return Err(AppError::Validation(format!(
+            "The window cannot exceed {} days",
+            MAX_WINDOW_DAYS
+        )));
+    }
+    Ok(SlotWindow {
+        from,
+        to,
+        not_before: chrono::Utc::now()
+            .with_timezone(&organization.timezone)
+            .naive_local(),
+    })
+}
+
+fn parse_intervention_type(value: Option<String>) -> Result<Option<String>, AppError> {
+    let Some(value) = value.map(|value| value.trim().to_lowercase()) else {
+        return Ok(None);
+    };
+    if value.is_empty() {
+        return Ok(None);
+    }
+    value
+        .parse::<InterventionType>()
+        .map_err(AppError::Validation)?;
+    Ok(Some(value))
+}
+
+/// Minutes a task takes: its estimate, else the steps of the workflow its
+/// intervention would follow.
+fn task_duration(task: &PlanningTask) -> i32 {
+    if let Some(minutes) = task.estimated_duration.filter(|minutes| *minutes > 0) {
+        return minutes;
+    }
+    let mut intervention = Intervention::new(
+        task.id.clone(),
+        task.task_number.clone(),
+        task.vehicle_plate.clone(),
+    );
+    if let Some(kind) = task
+        .intervention_type
+        .as_deref()
+        .and_then(|kind| kind.parse().ok())
+    {
+        intervention.intervention_type = kind;
+    }
+    if !task.ppf_zones.is_empty() {
+        intervention.ppf_zones_config = Some(task.ppf_zones.clone());
+    }
+    WorkflowStrategyFactory::estimated_minutes(&intervention)
+}
+
+fn slot_candidate(slot: &Slot) -> SlotCandidate {
+    SlotCandidate {
+        date: format_date(slot.date),
+        start_time: slot.start.format("%H:%M").to_string(),
+        end_time: slot.end.format("%H:%M").to_string(),
+        score: slot.score,
+        booked_minutes: slot.booked_minutes,
+        capacity_minutes: slot.capacity_minutes,
+    }
+}
+
+impl CalendarService {
+    /// Working time, skills and bookings of the technicians in `ids` (all
+    /// active technicians when unset) over `window`. Tasks in `exclude` are
+    /// not counted as booked.
+    async fn technician_plans(
+        &self,
+        organization: &OrganizationCalendar,
+        ids: Option<&[String]>,
+        window: SlotWindow,
+        exclude: &[String],
+    ) -> Result<Vec<TechnicianPlan>, AppError> {
+        let technicians = self.availability.list_technicians(ids)?;
+        let ids: Vec<String> = technicians.iter().map(|(id, _)| id.clone()).collect();
+        let mut schedules =
+            self.technician_schedules(organization, &ids, window.from, window.to)?;
+        let mut skills: HashMap<String, Vec<String>> = self
+            .availability
+            .list_availability()?
+            .into_iter()
+            .map(|availability| (availability.technician_id, availability.skills))
+            .collect();
+        let mut bookings = self
+            .bookings(organization, &ids, window.from, window.to, exclude)
+            .await?;
+        let mut booked: HashMap<String, HashMap<NaiveDate, i32>> = HashMap::new();
+        for ((technician_id, date), minutes) in bookings.minutes {
+            booked
+                .entry(technician_id)
+                .or_default()
+                .insert(date, minutes);
+        }
+
+        Ok(technicians
+            .into_iter()
+            .filter_map(|(id, name)| {
+                let schedule = schedules.remove(&id)?;
+                Some(TechnicianPlan {
+                    skills: skills.remove(&id).unwrap_or_default(),
+                    busy: bookings.busy.remove(&id).unwrap_or_default(),
+                    booked: booked.remove(&id).unwrap_or_default(),
+                    schedule,
+                    technician_id: id,
+                    technician_name: name,
+                })
+            })
+            .collect())
+    }
+
+    /// Free slots per technician for a task, or for a duration and
+    /// intervention type, best technician first.
+    pub async fn suggest_slots(&self, search: SlotSearch) -> Result<SlotSuggestions, AppError> {
+        let organization = self.organization_calendar()?;
+        let window = slot_window(&organization, &search.start_date, &search.end_date)?;
+        let requested_type = parse_intervention_type(search.intervention_type)?;
+
+        let (duration_minutes, intervention_type, exclude) = match search.task_id {
+            Some(task_id) => {
+                let task = self
+                    .availability
+                    .get_planning_task(&task_id)?
+                    .ok_or_else(|| AppError::NotFound(format!("Task {} not found", task_id)))?;
+                let minutes = search
+                    .duration_minutes
+                    .filter(|minutes| *minutes > 0)
+                    .unwrap_or_else(|| task_duration(&task));
+                let kind = requested_type.or(task.intervention_type);
+                (minutes, kind, vec![task_id])
+            }
+            None => {
+                let minutes = search.duration_minutes.ok_or_else(|| {
+                    AppError::Validation("task_id or duration_minutes is required".to_string())
+                })?;
+                (minutes, requested_type, vec![])
+            }
+        };
+        if !(1..=24 * 60).contains(&duration_minutes) {
+            return Err(AppError::Validation(
+                "duration_minutes must be between 1 and 1440".to_string(),
+            ));
+        }
+
+        let limit = search
+            .limit
+            .filter(|limit| *limit > 0)
+            .map_or(DEFAULT_SLOT_LIMIT, |limit| limit as usize);
+        let need = SlotNeed {
+            minutes: duration_minutes,
+            intervention_type: intervention_type.clone(),
+            technician_id: None,
+        };
+        let plans = self
+            .technician_plans(
+                &organization,
+                search.technician_ids.as_deref(),
+                window,
+                &exclude,
+            )
+            .await?;
+
+        let mut technicians: Vec<(i32, TechnicianSlots)> = plans
+            .iter()
+            .filter_map(|plan| {
+                let slots = plan.slots(&need, window);
+                let best = slots.first()?.score;
+                Some((
+                    best,
+                    TechnicianSlots {
+                        technician_id: plan.technician_id.clone(),
+                        technician_name: plan.technician_name.clone(),
+                        slots: slots.iter().take(limit).map(slot_candidate).collect(),
+                    },
+                ))
+            })
+            .collect();
+        technicians.sort_by(|a, b| b.0.cmp(&a.0));
+
+        Ok(SlotSuggestions {
+            duration_minutes,
+            intervention_type,
+            technicians: technicians.into_iter().map(|(_, slots)| slots).collect(),
+        })
+    }
+
+    /// A slot for every unscheduled task, placed most urgent first so each
+    /// sees the slots taken before it. A task already assigned keeps its
+    /// technician.
+    pub async fn propose_schedule(
+        &self,
+        start_date: &str,
+        end_date: &str,
+        technician_ids: Option<Vec<String>>,
+    ) -> Result<ScheduleProposal, AppError> {
+        let organization = self.organization_calendar()?;
+        let window = slot_window(&organization, start_date, end_date)?;
+        let tasks = self.availability.list_unscheduled_tasks()?;
+        let task_ids: Vec<String> = tasks.iter().map(|task| task.id.clone()).collect();
+        let mut plans = self
+            .technician_plans(&organization, technician_ids.as_deref(), window, &task_ids)
+            .await?;
+
+        let needs: Vec<SlotNeed> = tasks
+            .iter()
+            .map(|task| SlotNeed {
+                minutes: task_duration(task),
+                intervention_type: task.intervention_type.clone(),
+                technician_id: task.technician_id.clone().filter(|id| !id.is_empty()),
+            })
+            .collect();
+        let placements = place_all(&mut plans, &needs, window);
+
+        let mut proposals = Vec::new();
+        let mut unplaced = Vec::new();
+        for ((task, need), placement) in tasks.into_iter().zip(&needs).zip(placements) {
+            match placement {
+                Ok((index, slot)) => {
+                    let candidate = slot_candidate(&slot);
+                    proposals.push(ProposedBooking {
+                        task_id: task.id,
+                        task_number: task.task_number,
+                        title: task.title,
+                        technician_id: plans[index].technician_id.clone(),
+                        technician_name: plans[index].technician_name.clone(),
+                        date: candidate.date,
+                        start_time: candidate.start_time,
+                        end_time: candidate.end_time,
+                        duration_minutes: need.minutes,
+                        score: slot.score,
+                    });
+                }
+                Err(error) => unplaced.push(UnplacedTask {
+                    task_id: task.id,
+                    task_number: task.task_number,
+                    title: task.title,
+                    reason: error.message().to_string(),
+                }),
+            }
+        }
+
+        Ok(ScheduleProposal {
+            start_date: format_date(window.from),
+            end_date: format_date(window.to),
+            proposals,
+            unplaced,
+        })
+    }
+}
@@ -0,0 +1,427 @@
+//! Technician working time and daily capacity.
+//!
+//! A technician works the organization's business hours on its business days
+//! unless their [`TechnicianAvailability`] says otherwise, and is off on
+//! public holidays and during absences. Their capacity on a working day is
+//! the length of the working day, or their own daily capacity when set.
+//!
+//! Booked time is the span of a timed task, else its estimated duration,
+//! plus timed events of the technician that are not tied to a task.
+
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime, Weekday};
+use chrono_tz::Tz;
+
+use super::recurrence::{local_to_utc, parse_event_time, parse_timezone, TimeForm};
+use super::{
+    CalendarEvent, CalendarTask, CalendarTaskStatus, DayStatus, EventStatus, PublicHoliday,
+    TechnicianAbsence, TechnicianAvailability,
+};
+
+pub const ABSENCE_KINDS: &[&str] = &["leave", "sick", "training", "other"];
+
+/// Parse `HH:MM` (or `HH:MM:SS`).
+pub fn parse_clock(value: &str) -> Option<NaiveTime> {
+    ["%H:%M", "%H:%M:%S"]
+        .iter()
+        .find_map(|format| NaiveTime::parse_from_str(value.trim(), format).ok())
+}
+
+/// Parse an ISO weekday number (`"1"` = Monday; `"0"` and `"7"` are Sunday)
+/// or an English day name.
+pub fn parse_weekday(value: &str) -> Option<Weekday> {
+    let value = value.trim();
+    match value.parse::<u32>() {
+        Ok(0) | Ok(7) => Some(Weekday::Sun),
+        Ok(number @ 1..=6) => Weekday::try_from((number - 1) as u8).ok(),
+        Ok(_) => None,
+        Err(_) => value.parse::<Weekday>().ok(),
+    }
+}
+
+/// Working hours and days, the same every week.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorkingWeek {
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+    days: [bool; 7],
+}
+
+impl WorkingWeek {
+    pub fn parse(start: &str, end: &str, days: &[String]) -> Result<Self, String> {
+        let start = parse_clock(start).ok_or_else(|| format!("Invalid start time: {}", start))?;
+        let end = parse_clock(end).ok_or_else(|| format!("Invalid end time: {}", end))?;
+        if start >= end {
+            return Err("Working time must end after it starts".to_string());
+        }
+        let mut working = [false; 7];
+        for day in days {
+            let weekday = parse_weekday(day).ok_or_else(|| format!("Invalid weekday: {}", day))?;
+            working[weekday.num_days_from_monday() as usize] = true;
+        }
+        Ok(Self {
+            start,
+            end,
+            days: working,
+        })
+    }
+
+    /// The organization's week with a technician's overrides applied.
+    pub fn with_overrides(&self, overrides: &TechnicianAvailability) -> Result<Self, String> {
+        let start = match overrides.work_start.as_deref() {
+            Some(value) => {
+                parse_clock(value).ok_or_else(|| format!("Invalid start time: {}", value))?
+            }
+            None => self.start,
+        };
+        let end = match overrides.work_end.as_deref() {
+            Some(value) => {
+                parse_clock(value).ok_or_else(|| format!("Invalid end time: {}", value))?
+            }
+            None => self.end,
+        };
+        let mut week = match &overrides.working_days {
+            Some(days) => Self::parse("00:00", "23:59", days)?,
+            None => self.clone(),
+        };
+        if start >= end {
+            return Err("Working time must end after it starts".to_string());
+        }
+        week.start = start;
+        week.end = end;
+        Ok(week)
+    }
+
+    pub fn works_on(&self, date: NaiveDate) -> bool {
+        self.days[date.weekday().num_days_from_monday() as usize]
+    }
+
+    pub fn day_minutes(&self) -> i32 {
+        (self.end - self.start).num_minutes() as i32
+    }
+
+    pub fn covers(&self, start: NaiveTime, end: NaiveTime) -> bool {
+        start >= self.start && end <= self.end && start < end
+    }
+}
+
+/// Everything that decides when one technician can be booked.
+#[derive(Debug, Clone)]
+pub struct TechnicianSchedule {
+    pub week: WorkingWeek,
+    pub daily_capacity_minutes: Option<i32>,
+    pub absences: Vec<TechnicianAbsence>,
+    pub holidays: Vec<PublicHoliday>,
+}
+
+impl TechnicianSchedule {
+    /// Whether the technician works on `date`, and how many minutes they
+    /// can be booked for.
+    pub fn day(&self, date: NaiveDate) -> (DayStatus, i32) {
+        let day = date.format("%Y-%m-%d").to_string();
+        let status = if self.holidays.iter().any(|holiday| holiday.date == day) {
+            DayStatus::Holiday
+        } else if self
+            .absences
+            .iter()
+            .any(|absence| absence.start_date <= day && day <= absence.end_date)
+        {
+            DayStatus::Absent
+        } else if !self.week.works_on(date) {
+            DayStatus::DayOff
+        } else {
+            DayStatus::Working
+        };
+        let capacity = match status {
+            DayStatus::Working => self
+                .daily_capacity_minutes
+                .map_or(self.week.day_minutes(), |minutes| {
+                    minutes.min(self.week.day_minutes())
+                })
+                .max(0),
+            _ => 0,
+        };
+        (status, capacity)
+    }
+
+    /// Why booking `minutes` on `date` (from `start` to `end` when timed)
+    /// would be a problem, given `booked` minutes already taken that day.
+    pub fn check_booking(
+        &self,
+        date: NaiveDate,
+        times: Option<(NaiveTime, NaiveTime)>,
+        minutes: i32,
+        booked: i32,
+    ) -> Vec<AvailabilityIssue> {
+        let (status, capacity) = self.day(date);
+        let mut issues = Vec::new();
+        match status {
+            DayStatus::Holiday => {
+                let day = date.format("%Y-%m-%d").to_string();
+                let name = self
+                    .holidays
+                    .iter()
+                    .find(|holiday| holiday.date == day)
+                    .map(|holiday| holiday.name.clone())
+                    .unwrap_or_default();
+                issues.push(AvailabilityIssue::Holiday(name));
+            }
+            DayStatus::Absent => issues.push(AvailabilityIssue::Absent),
+            DayStatus::DayOff => issues.push(AvailabilityIssue::DayOff),
+            DayStatus::Working => {
+                if let Some((start, end)) = times {
+                    if !self.week.covers(start, end) {
+                        issues.push(AvailabilityIssue::OutsideHours {
+                            start: self.week.start,
+                            end: self.week.end,
+                        });
+                    }
+                }
+                if minutes > 0 && booked + minutes > capacity {
+                    issues.push(AvailabilityIssue::OverCapacity {
+                        booked: booked + minutes,
+                        capacity,
+                    });
+                }
+            }
+        }
+        issues
+    }
+}
+
+/// A reason a booking falls outside a technician's availability.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AvailabilityIssue {
+    Holiday(String),
+    Absent,
+    DayOff,
+    OutsideHours { start: NaiveTime, end: NaiveTime },
+    OverCapacity { booked: i32, capacity: i32 },
+}
+
+impl AvailabilityIssue {
+    /// `conflict_type` reported for the issue.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Holiday(_) | Self::Absent => "technician_unavailable",
+            Self::DayOff | Self::OutsideHours { .. } => "outside_working_time",
+            Self::OverCapacity { .. } => "over_capacity",
+        }
+    }
+
+    pub fn message(&self, date: &str) -> String {
+        match self {
+            Self::Holiday(name) if name.is_empty() => format!("{} is a public holiday", date),
+            Self::Holiday(name) => format!("{} is a public holiday ({})", date, name),
+            Self::Absent => format!("Technician is absent on {}", date),
+            Self::DayOff => format!("{} is not a working day for the technician", date),
+            Self::OutsideHours { start, end } => format!(
+                "Booking falls outside working hours ({}-{})",
+                start.format("%H:%M"),
+                end.format("%H:%M")
+            ),
+            Self::OverCapacity { booked, capacity } => format!(
+                "Booking brings the technician to {} of {} minutes on {}",
+                booked, capacity, date
+            ),
+        }
+    }
+}
+
+/// Whether a task takes up its technician's time.
+pub fn occupies_capacity(status: &CalendarTaskStatus) -> bool {
+    !matches!(
+        status,
+        CalendarTaskStatus::Cancelled | CalendarTaskStatus::Archived | CalendarTaskStatus::Invalid
+    )
+}
+
+/// Minutes a task takes: its time span when timed, else its estimate.
+pub fn planned_minutes(
+    start_time: Option<&str>,
+    end_time: Option<&str>,
+    estimated_duration: Option<i32>,
+) -> i32 {
+    match (
+        start_time.and_then(parse_clock),
+        end_time.and_then(parse_clock),
+    ) {
+        (Some(start), Some(end)) if end > start => (end - start).num_minutes() as i32,
+        _ => estimated_duration.unwrap_or(0).max(0),
+    }
+}
+
+pub fn task_minutes(task: &CalendarTask) -> i32 {
+    planned_minutes(
+        task.start_time.as_deref(),
+        task.end_time.as_deref(),
+        task.estimated_duration,
+    )
+}
+
+/// Wall-clock span of a task with a start time. A task without an end time
+/// lasts its estimated duration.
+pub fn task_span(task: &CalendarTask) -> Option<(NaiveDateTime, NaiveDateTime)> {
+    let date = NaiveDate::parse_from_str(task.scheduled_date.trim(), "%Y-%m-%d").ok()?;
+    let start = date.and_time(parse_clock(task.start_time.as_deref()?)?);
+    let minutes = task_minutes(task);
+    (minutes > 0).then(|| (start, start + chrono::Duration::minutes(minutes.into())))
+}
+
+/// Wall-clock span of a timed event at the shop, for events that take up a
+/// technician's time on their own (not the events mirroring tasks).
+pub fn event_span(event: &CalendarEvent, shop_tz: Tz) -> Option<(NaiveDateTime, NaiveDateTime)> {
+    if event.all_day || event.task_id.is_some() || event.status == EventStatus::Cancelled {
+        return None;
+    }
+    let tz = parse_timezone(&event.timezone).unwrap_or(shop_tz);
+    let at_shop = |value: &str| {
+        let (local, form) = parse_event_time(value, tz)?;
+        Some(match form {
+            // Floating times are already shop times.
+            TimeForm::Floating | TimeForm::Date if tz == chrono_tz::UTC => local,
+            _ => local_to_utc(local, tz)
+                .with_timezone(&shop_tz)
+                .naive_local(),
+        })
+    };
+    let start = at_shop(&event.start_datetime)?;
+    let end = at_shop(&event.end_datetime)?;
+    (end > start).then_some((start, end))
+}
+
+/// Minutes of `span` falling on `date`.
+pub fn minutes_on(span: (NaiveDateTime, NaiveDateTime), date: NaiveDate) -> i32 {
+    let day_start = date.and_time(NaiveTime::MIN);
+    let day_end = day_start + chrono::Duration::days(1);
+    let start = span.0.max(day_start);
+    let end = span.1.min(day_end);
+    if end > start {
+        (end - start).num_minutes() as i32
+    } else {
+        0
+    }
+}
+
+/// Booked share of `capacity`, rounded.
+pub fn load_percent(booked: i32, capacity: i32) -> Option<i32> {
+    (capacity > 0).then(|| ((booked as f64 / capacity as f64) * 100.0).round() as i32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    fn clock(value: &str) -> NaiveTime {
+        parse_clock(value).unwrap()
+    }
+
+    fn weekdays() -> Vec<String> {
+        ["1", "2", "3", "4", "5"].map(str::to_string).to_vec()
+    }
+
+    fn schedule() -> TechnicianSchedule {
+        TechnicianSchedule {
+            week: WorkingWeek::parse("08:00", "18:00", &weekdays()).unwrap(),
+            daily_capacity_minutes: Some(420),
+            absences: vec![TechnicianAbsence {
+                id: "abs-1".to_string(),
+                technician_id: "tech-1".to_string(),
+                start_date: "2026-06-15".to_string(),
+                end_date: "2026-06-16".to_string(),
+                kind: "leave".to_string(),
+                note: None,
+                created_at: 0,
+                created_by: None,
+            }],
+            holidays: vec![PublicHoliday {
+                date: "2026-07-14".to_string(),
+                name: "Fête nationale".to_string(),
+            }],
+        }
+    }
+
+    #[test]
+    fn parses_weekday_numbers_and_names() {
+        assert_eq!(parse_weekday("1"), Some(Weekday::Mon));
+        assert_eq!(parse_weekday("6"), Some(Weekday::Sat));
+        assert_eq!(parse_weekday("0"), Some(Weekday::Sun));
+        assert_eq!(parse_weekday("7"), Some(Weekday::Sun));
+        assert_eq!(parse_weekday("friday"), Some(Weekday::Fri));
+        assert_eq!(parse_weekday("8"), None);
+    }
+
+    #[test]
+    fn technician_overrides_replace_organization_hours() {
+        let organization = WorkingWeek::parse("08:00", "18:00", &weekdays()).unwrap();
+        let own = organization
+            .with_overrides(&TechnicianAvailability {
+                technician_id: "tech-1".to_string(),
+                work_start: Some("09:30".to_string()),
+                working_days: Some(vec!["2".to_string(), "6".to_string()]),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!((own.start, own.end), (clock("09:30"), clock("18:00")));
+        assert!(own.works_on(date("2026-06-13")));
+        assert!(!own.works_on(date("2026-06-08")));
+        assert_eq!(own.day_minutes(), 510);
+    }
+
+    #[test]
+    fn day_status_and_capacity() {
+        let schedule = schedule();
+        assert_eq!(schedule.day(date("2026-06-10")), (DayStatus::Working, 420));
+        assert_eq!(schedule.day(date("2026-06-13")), (DayStatus::DayOff, 0));
+        assert_eq!(schedule.day(date("2026-06-16")), (DayStatus::Absent, 0));
+        assert_eq!(schedule.day(date("2026-07-14")), (DayStatus::Holiday, 0));
+    }
+
+    #[test]
+    fn booking_checks_working_time_and_capacity() {
+        let schedule = schedule();
+        let wednesday = date("2026-06-10");
+        let morning = Some((clock("09:00"), clock("11:00")));
+        assert!(schedule
+            .check_booking(wednesday, morning, 120, 240)
+            .is_empty());
+
+        let issues = schedule.check_booking(wednesday, morning, 120, 360);
+        assert_eq!(
+            issues,
+            vec![AvailabilityIssue::OverCapacity {
+                booked: 480,
+                capacity: 420
+            }]
+        );
+        assert_eq!(issues[0].kind(), "over_capacity");
+
+        let evening = Some((clock("17:00"), clock("19:00")));
+        let issues = schedule.check_booking(wednesday, evening, 120, 0);
+        assert_eq!(issues[0].kind(), "outside_working_time");
+
+        let issues = schedule.check_booking(date("2026-07-14"), None, 60, 0);
+        assert_eq!(issues[0].kind(), "technician_unavailable");
+        assert!(issues[0].message("2026-07-14").contains("Fête nationale"));
+    }
+
+    #[test]
+    fn planned_minutes_prefers_times_over_estimate() {
+        assert_eq!(planned_minutes(Some("09:00"), Some("10:30"), Some(30)), 90);
+        assert_eq!(planned_minutes(Some("09:00"), None, Some(45)), 45);
+        assert_eq!(planned_minutes(None, None, None), 0);
+    }
+
+    #[test]
+    fn spans_are_split_by_day() {
+        let start = date("2026-06-10").and_time(clock("22:00"));
+        let end = date("2026-06-11").and_time(clock("02:00"));
+        assert_eq!(minutes_on((start, end), date("2026-06-10")), 120);
+        assert_eq!(minutes_on((start, end), date("2026-06-11")), 120);
+        assert_eq!(load_percent(210, 420), Some(50));
+        assert_eq!(load_percent(30, 0), None);
+    }
+}
@@ -0,0 +1,1261 @@
+//! iCalendar (RFC 5545) documents for the workshop calendar.
+//!
+//! [`IcsWriter`] turns calendar events and scheduled tasks into a VCALENDAR
+//! for exports, the local feed and invitations; [`parse_calendar`] reads the
+//! VEVENTs of an imported file.
+//!
+//! Times keep the form they are stored in: a time in an IANA zone is written
+//! with `TZID`, a UTC instant with `Z`, and a time in the `UTC` zone without
+//! either — a floating wall-clock time, which is how scheduled tasks are
+//! stored. No VTIMEZONE is written; `TZID` values are IANA names, which
+//! calendar apps resolve themselves.
+
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use chrono_tz::Tz;
+
+use super::recurrence::{
+    local_to_utc, parse_event_time, parse_timezone, RecurrenceRule, RuleTime, TimeForm,
+};
+use super::{
+    CalendarEvent, CalendarTask, CalendarTaskStatus, CreateEventInput, EventParticipant,
+    EventStatus, ParticipantStatus, UpdateEventInput,
+};
+
+pub const PRODID: &str = "-//RPMA//PPF Intervention//EN";
+/// Right-hand side of the UIDs given to events and tasks created in the app.
+pub const UID_HOST: &str = "rpma-ppf-intervention";
+
+/// Longest content line before folding, in octets.
+const MAX_LINE_OCTETS: usize = 75;
+const UNTITLED: &str = "Untitled event";
+
+/// How an [`IcsTime`] is written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeKind {
+    /// `VALUE=DATE`, for all-day events.
+    Date,
+    /// No zone: the same wall-clock time wherever it is read.
+    Floating,
+    /// With `TZID`.
+    Zoned,
+    /// A UTC instant, with `Z`.
+    Utc,
+}
+
+/// A DATE or DATE-TIME property value: a wall-clock time in `tz`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IcsTime {
+    pub local: NaiveDateTime,
+    pub kind: TimeKind,
+    pub tz: Tz,
+}
+
+impl IcsTime {
+    fn at(&self, local: NaiveDateTime) -> Self {
+        Self { local, ..*self }
+    }
+
+    /// The same moment on the wall clock of `tz`. Dates and floating times
+    /// have no zone and stay as they are.
+    pub fn local_in(&self, tz: Tz) -> NaiveDateTime {
+        match self.kind {
+            TimeKind::Zoned | TimeKind::Utc => local_to_utc(self.local, self.tz)
+                .with_timezone(&tz)
+                .naive_local(),
+            TimeKind::Date | TimeKind::Floating => self.local,
+        }
+    }
+
+    fn params(&self) -> String {
+        match self.kind {
+            TimeKind::Date => ";VALUE=DATE".to_string(),
+            TimeKind::Zoned => format!(";TZID={}", self.tz.name()),
+            TimeKind::Floating | TimeKind::Utc => String::new(),
+        }
+    }
+
+    fn value(&self) -> String {
+        match self.kind {
+            TimeKind::Date => self.local.format("%Y%m%d").to_string(),
+            TimeKind::Floating | TimeKind::Zoned => self.local.format("%Y%m%dT%H%M%S").to_string(),
+            TimeKind::Utc => utc_value(local_to_utc(self.local, self.tz)),
+        }
+    }
+
+    fn property(&self, name: &str) -> String {
+        format!("{}{}:{}", name, self.params(), self.value())
+    }
+}
+
+fn utc_value(instant: DateTime<Utc>) -> String {
+    instant.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+fn millis_value(millis: i64) -> Option<String> {
+    DateTime::from_timestamp_millis(millis).map(utc_value)
+}
+
+/// UID of `event` in exported files.
+pub fn event_uid(event: &CalendarEvent) -> String {
+    event
+        .ical_uid
+        .clone()
+        .unwrap_or_else(|| format!("{}@{}", event.id, UID_HOST))
+}
+
+/// The event id behind a UID given out by [`event_uid`], if it is one.
+pub fn own_event_id(uid: &str) -> Option<&str> {
+    uid.strip_suffix(UID_HOST)
+        .and_then(|rest| rest.strip_suffix('@'))
+        .filter(|id| !id.is_empty() && !id.starts_with("task-"))
+}
+
+fn event_times(event: &CalendarEvent) -> Result<(IcsTime, IcsTime), String> {
+    let tz = parse_timezone(&event.timezone)?;
+    let (start, form) = parse_event_time(&event.start_datetime, tz)
+        .ok_or_else(|| format!("Invalid start time: {}", event.start_datetime))?;
+    let (end, end_form) = parse_event_time(&event.end_datetime, tz)
+        .ok_or_else(|| format!("Invalid end time: {}", event.end_datetime))?;
+
+    if event.all_day || form == TimeForm::Date {
+        // DTEND is exclusive. A stored end date is the last day; a stored
+        // end time such as 23:59:59 falls on it.
+        let last_day = match end_form {
+            TimeForm::Date => end.date(),
+            _ => (end - Duration::seconds(1)).date(),
+        };
+        let first_day = start.date();
+        let date = |day: NaiveDate| IcsTime {
+            local: day.and_time(NaiveTime::MIN),
+            kind: TimeKind::Date,
+            tz,
+        };
+        return Ok((
+            date(first_day),
+            date(last_day.max(first_day) + Duration::days(1)),
+        ));
+    }
+
+    let kind = match form {
+        TimeForm::Utc => TimeKind::Utc,
+        _ if tz == Tz::UTC => TimeKind::Floating,
+        _ => TimeKind::Zoned,
+    };
+    Ok((
+        IcsTime {
+            local: start,
+            kind,
+            tz,
+        },
+        IcsTime {
+            local: end,
+            kind,
+            tz,
+        },
+    ))
+}
+
+/// RRULE and EXDATE lines for a stored rule, with UNTIL and the excluded
+/// dates written the way DTSTART is.
+fn recurrence_lines(rule: &str, start: &IcsTime) -> Result<Vec<String>, String> {
+    let rule: RecurrenceRule = rule.parse()?;
+    let tz = start.tz;
+    let mut rrule = rule.clone();
+    rrule.exdates.clear();
+    rrule.until = rule.until.map(|until| {
+        let local = until.to_local(tz);
+        match start.kind {
+            TimeKind::Date => RuleTime::Date(local.date()),
+            TimeKind::Floating => RuleTime::Floating(local),
+            TimeKind::Zoned | TimeKind::Utc => RuleTime::Utc(local_to_utc(local, tz)),
+        }
+    });
+
+    let mut lines = vec![format!("RRULE:{}", rrule)];
+    if !rule.exdates.is_empty() {
+        let values: Vec<String> = rule
+            .exdates
+            .iter()
+            .map(|exdate| {
+                let local = match exdate {
+                    RuleTime::Date(date) => date.and_time(start.local.time()),
+                    other => other.to_local(tz),
+                };
+                start.at(local).value()
+            })
+            .collect();
+        lines.push(format!("EXDATE{}:{}", start.params(), values.join(",")));
+    }
+    Ok(lines)
+}
+
+fn task_times(task: &CalendarTask) -> Result<(IcsTime, IcsTime), String> {
+    let day = task
+        .scheduled_date
+        .get(..10)
+        .unwrap_or(&task.scheduled_date);
+    let date = NaiveDate::parse_from_str(day, "%Y-%m-%d")
+        .map_err(|_| format!("Invalid scheduled date: {}", task.scheduled_date))?;
+    let parse_time = |value: &str| {
+        NaiveTime::parse_from_str(value, "%H:%M:%S")
+            .or_else(|_| NaiveTime::parse_from_str(value, "%H:%M"))
+            .ok()
+    };
+    let at = |local: NaiveDateTime, kind: TimeKind| IcsTime {
+        local,
+        kind,
+        tz: Tz::UTC,
+    };
+
+    let Some(start_time) = task.start_time.as_deref().and_then(parse_time) else {
+        let start = date.and_time(NaiveTime::MIN);
+        return Ok((
+            at(start, TimeKind::Date),
+            at(start + Duration::days(1), TimeKind::Date),
+        ));
+    };
+    let start = date.and_time(start_time);
+    let end = task
+        .end_time
+        .as_deref()
+        .and_then(parse_time)
+        .map(|end_time| date.and_time(end_time))
+        .filter(|end| *end > start)
+        .unwrap_or_else(|| {
+            let minutes = task.estimated_duration.filter(|minutes| *minutes > 0);
+            start + Duration::minutes(i64::from(minutes.unwrap_or(60)))
+        });
+    Ok((at(start, TimeKind::Floating), at(end, TimeKind::Floating)))
+}
+
+fn escape_text(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for ch in value.chars() {
+        match ch {
+            '\\' => escaped.push_str("\\\\"),
+            ';' => escaped.push_str("\\;"),
+            ',' => escaped.push_str("\\,"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+fn param_value(value: &str) -> String {
+    let value: String = value
+        .chars()
+        .filter(|ch| *ch != '"' && !ch.is_control())
+        .collect();
+    if value.contains([':', ';', ',']) {
+        format!("\"{}\"", value)
+    } else {
+        value
+    }
+}
+
+/// Split a line longer than 75 octets into continuation lines.
+fn fold(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len() + line.len() / MAX_LINE_OCTETS * 3);
+    let mut width = 0;
+    for ch in line.chars() {
+        if width + ch.len_utf8() > MAX_LINE_OCTETS {
+            folded.push_str("\r\n ");
+            width = 1;
+        }
+        folded.push(ch);
+        width += ch.len_utf8();
+    }
+    folded
+}
+
+fn status_value(status: &EventStatus) -> &'static str {
+    match status {
+        EventStatus::Confirmed => "CONFIRMED",
+        EventStatus::Tentative => "TENTATIVE",
+        EventStatus::Cancelled => "CANCELLED",
+    }
+}
+
+fn partstat_value(status: &ParticipantStatus) -> &'static str {
+    match status {
+        ParticipantStatus::Accepted => "ACCEPTED",
+        ParticipantStatus::Declined => "DECLINED",
+        ParticipantStatus::Tentative => "TENTATIVE",
+        ParticipantStatus::NeedsAction => "NEEDS-ACTION",
+    }
+}
+
+/// TRIGGER for a reminder `minutes` before the start.
+fn alarm_trigger(minutes: i32) -> String {
+    if minutes > 0 && minutes % (24 * 60) == 0 {
+        format!("-P{}D", minutes / (24 * 60))
+    } else if minutes > 0 && minutes % 60 == 0 {
+        format!("-PT{}H", minutes / 60)
+    } else {
+        format!("-PT{}M", minutes)
+    }
+}
+
+fn non_blank(value: Option<&str>) -> Option<&str> {
+    value.map(str::trim).filter(|value| !value.is_empty())
+}
+
+/// Sender of an invitation.
+#[derive(Debug, Clone)]
+pub struct Organizer {
+    pub name: String,
+    pub email: String,
+}
+
+/// Builds a VCALENDAR document.
+pub struct IcsWriter {
+    lines: Vec<String>,
+    stamp: DateTime<Utc>,
+    organizer: Option<Organizer>,
+}
+
+impl IcsWriter {
+    /// A calendar to publish: exports and the local feed.
+    pub fn publish(name: &str, stamp: DateTime<Utc>) -> Self {
+        let mut writer = Self::begin("PUBLISH", stamp, None);
+        writer.line(format!("X-WR-CALNAME:{}", escape_text(name)));
+        writer
+    }
+
+    /// An invitation from `organizer` to each event's participants.
+    pub fn invite(organizer: Organizer, stamp: DateTime<Utc>) -> Self {
+        Self::begin("REQUEST", stamp, Some(organizer))
+    }
+
+    fn begin(method: &str, stamp: DateTime<Utc>, organizer: Option<Organizer>) -> Self {
+        let mut writer = Self {
+            lines: Vec::new(),
+            stamp,
+            organizer,
+        };
+        writer.line("BEGIN:VCALENDAR");
+        writer.line("VERSION:2.0");
+        writer.line(format!("PRODID:{}", PRODID));
+        writer.line("CALSCALE:GREGORIAN");
+        writer.line(format!("METHOD:{}", method));
+        writer
+    }
+
+    fn line(&mut self, line: impl Into<String>) {
+        self.lines.push(line.into());
+    }
+
+    fn text(&mut self, name: &str, value: Option<&str>) {
+        if let Some(value) = non_blank(value) {
+            self.line(format!("{}:{}", name, escape_text(value)));
+        }
+    }
+
+    /// Add `event` as a VEVENT, with its rule, participants and one VALARM
+    /// per reminder. Fails if its times, timezone or rule do not parse.
+    pub fn add_event(&mut self, event: &CalendarEvent) -> Result<(), String> {
+        let (start, end) = event_times(event)?;
+        let recurrence = match event
+            .recurrence_rule
+            .as_deref()
+            .filter(|rule| event.is_recurring && !rule.trim().is_empty())
+        {
+            Some(rule) => recurrence_lines(rule, &start)?,
+            None => Vec::new(),
+        };
+
+        self.line("BEGIN:VEVENT");
+        self.line(format!("UID:{}", event_uid(event)));
+        self.line(format!("DTSTAMP:{}", utc_value(self.stamp)));
+        if let Some(created) = millis_value(event.created_at) {
+            self.line(format!("CREATED:{}", created));
+        }
+        if let Some(modified) = millis_value(event.updated_at) {
+            self.line(format!("LAST-MODIFIED:{}", modified));
+        }
+        self.line(start.property("DTSTART"));
+        self.line(end.property("DTEND"));
+        for line in recurrence {
+            self.line(line);
+        }
+        self.line(format!("SUMMARY:{}", escape_text(&event.title)));
+        self.text("DESCRIPTION", event.description.as_deref());
+        self.text("LOCATION", event.location.as_deref());
+        if let Some(link) = non_blank(event.meeting_link.as_deref()) {
+            self.line(format!("URL:{}", link));
+        }
+        self.line(format!("STATUS:{}", status_value(&event.status)));
+        let categories: Vec<String> = event
+            .tags
+            .iter()
+            .filter(|tag| !tag.trim().is_empty())
+            .map(|tag| escape_text(tag.trim()))
+            .collect();
+        if !categories.is_empty() {
+            self.line(format!("CATEGORIES:{}", categories.join(",")));
+        }
+        if let Some(organizer) = &self.organizer {
+            self.line(format!(
+                "ORGANIZER;CN={}:mailto:{}",
+                param_value(&organizer.name),
+                organizer.email
+            ));
+        }
+        let rsvp = if self.organizer.is_some() {
+            ";RSVP=TRUE"
+        } else {
+            ""
+        };
+        for participant in &event.participants {
+            if let Some(email) = non_blank(participant.email.as_deref()) {
+                self.line(format!(
+                    "ATTENDEE;CN={};ROLE=REQ-PARTICIPANT;PARTSTAT={}{}:mailto:{}",
+                    param_value(&participant.name),
+                    partstat_value(&participant.status),
+                    rsvp,
+                    email
+                ));
+            }
+        }
+        for minutes in event.reminders.iter().filter(|minutes| **minutes >= 0) {
+            self.line("BEGIN:VALARM");
+            self.line("ACTION:DISPLAY");
+            self.line(format!("DESCRIPTION:{}", escape_text(&event.title)));
+            self.line(format!("TRIGGER:{}", alarm_trigger(*minutes)));
+            self.line("END:VALARM");
+        }
+        self.line("END:VEVENT");
+        Ok(())
+    }
+
+    /// Add a scheduled task as a VEVENT. A task without a start time is an
+    /// all-day event; one without an end lasts its estimated duration.
+    pub fn add_task(&mut self, task: &CalendarTask) -> Result<(), String> {
+        let (start, end) = task_times(task)?;
+        let mut details = Vec::new();
+        let vehicle = [task.vehicle_plate.as_deref(), task.vehicle_model.as_deref()]
+            .into_iter()
+            .filter_map(non_blank)
+            .collect::<Vec<_>>()
+            .join(" ");
+        if !vehicle.is_empty() {
+            details.push(format!("Vehicle: {}", vehicle));
+        }
+        if let Some(client) = non_blank(task.client_name.as_deref()) {
+            details.push(format!("Client: {}", client));
+        }
+        if let Some(technician) = non_blank(task.technician_name.as_deref()) {
+            details.push(format!("Technician: {}", technician));
+        }
+        let status = match task.status {
+            CalendarTaskStatus::Cancelled => EventStatus::Cancelled,
+            _ => EventStatus::Confirmed,
+        };
+
+        self.line("BEGIN:VEVENT");
+        self.line(format!("UID:task-{}@{}", task.id, UID_HOST));
+        self.line(format!("DTSTAMP:{}", utc_value(self.stamp)));
+        self.line(start.property("DTSTART"));
+        self.line(end.property("DTEND"));
+        self.line(format!(
+            "SUMMARY:{}",
+            escape_text(&format!("{} ({})", task.title, task.task_number))
+        ));
+        self.text("DESCRIPTION", Some(&details.join("\n")));
+        self.line(format!("STATUS:{}", status_value(&status)));
+        self.line("END:VEVENT");
+        Ok(())
+    }
+
+    /// The document, folded and CRLF-terminated.
+    pub fn finish(mut self) -> String {
+        self.line("END:VCALENDAR");
+        let mut document = String::new();
+        for line in &self.lines {
+            document.push_str(&fold(line));
+            document.push_str("\r\n");
+        }
+        document
+    }
+}
+
+// ── Import ────────────────────────────────────────────────────────────────────
+
+/// One unfolded content line: `NAME;PARAM=value:value`.
+#[derive(Debug, Clone)]
+struct ContentLine {
+    name: String,
+    params: Vec<(String, String)>,
+    value: String,
+}
+
+impl ContentLine {
+    fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(param, _)| param == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+fn unfold(text: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for raw in text.split('\n') {
+        let raw = raw.strip_suffix('\r').unwrap_or(raw);
+        if let Some(continuation) = raw.strip_prefix([' ', '\t']) {
+            if let Some(last) = lines.last_mut() {
+                last.push_str(continuation);
+                continue;
+            }
+        }
+        if !raw.trim().is_empty() {
+            lines.push(raw.to_string());
+        }
+    }
+    lines
+}
+
+/// Split on `separator` outside double quotes.
+fn split_unquoted(value: &str, separator: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut in_quotes = false;
+    let mut start = 0;
+    for (index, ch) in value.char_indices() {
+        if ch == '"' {
+            in_quotes = !in_quotes;
+        } else if ch == separator && !in_quotes {
+            parts.push(&value[start..index]);
+            start = index + ch.len_utf8();
+        }
+    }
+    parts.push(&value[start..]);
+    parts
+}
+
+fn parse_line(line: &str) -> Option<ContentLine> {
+    let mut in_quotes = false;
+    let colon = line.char_indices().find_map(|(index, ch)| match ch {
+        '"' => {
+            in_quotes = !in_quotes;
+            None
+        }
+        ':' if !in_quotes => Some(index),
+        _ => None,
+    })?;
+    let mut head = split_unquoted(&line[..colon], ';').into_iter();
+    let name = head.next()?.trim().to_ascii_uppercase();
+    let params = head
+        .filter_map(|param| param.split_once('='))
+        .map(|(name, value)| {
+            (
+                name.trim().to_ascii_uppercase(),
+                value.trim().trim_matches('"').to_string(),
+            )
+        })
+        .collect();
+    Some(ContentLine {
+        name,
+        params,
+        value: line[colon + 1..].to_string(),
+    })
+}
+
+fn unescape_text(value: &str) -> String {
+    let mut text = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(ch) = chars.next() {
+        if ch != '\\' {
+            text.push(ch);
+            continue;
+        }
+        match chars.next() {
+            Some('n') | Some('N') => text.push('\n'),
+            Some(other) => text.push(other),
+            None => {}
+        }
+    }
+    text
+}
+
+/// Split a TEXT list on commas that are not escaped.
+fn split_text_list(value: &str) -> Vec<String> {
+    let mut items = Vec::new();
+    let mut current = String::new();
+    let mut chars = value.chars();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\\' => {
+                current.push(ch);
+                if let Some(next) = chars.next() {
+                    current.push(next);
+                }
+            }
+            ',' => items.push(unescape_text(&std::mem::take(&mut current))),
+            _ => current.push(ch),
+        }
+    }
+    items.push(unescape_text(&current));
+    items
+}
+
+/// Resolve a TZID. Some producers prefix the IANA name, as in
+/// `/mozilla.org/20070129_1/Europe/Paris`.
+fn resolve_tzid(tzid: &str) -> Result<Tz, String> {
+    let segments: Vec<&str> = tzid.trim().split('/').collect();
+    (0..segments.len())
+        .find_map(|skip| parse_timezone(&segments[skip..].join("/")).ok())
+        .ok_or_else(|| format!("Unknown timezone: {}", tzid))
+}
+
+fn parse_time_value(line: &ContentLine, value: &str) -> Result<IcsTime, String> {
+    let value = value.trim();
+    let invalid = || format!("Invalid {} value: {}", line.name, value);
+    let is_date = line
+        .param("VALUE")
+        .is_some_and(|kind| kind.eq_ignore_ascii_case("DATE"))
+        || value.len() == 8;
+    if is_date {
+        let date = NaiveDate::parse_from_str(value, "%Y%m%d").map_err(|_| invalid())?;
+        return Ok(IcsTime {
+            local: date.and_time(NaiveTime::MIN),
+            kind: TimeKind::Date,
+            tz: Tz::UTC,
+        });
+    }
+    let (value, utc) = match value.strip_suffix('Z') {
+        Some(value) => (value, true),
+        None => (value, false),
+    };
+    let local = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S").map_err(|_| invalid())?;
+    let (kind, tz) = match line.param("TZID") {
+        _ if utc => (TimeKind::Utc, Tz::UTC),
+        Some(tzid) => (TimeKind::Zoned, resolve_tzid(tzid)?),
+        None => (TimeKind::Floating, Tz::UTC),
+    };
+    Ok(IcsTime { local, kind, tz })
+}
+
+/// Parse an RFC 5545 duration such as `-PT15M`, `P1D` or `-P1DT2H`. `None`
+/// when malformed or beyond what a `Duration` can hold.
+fn parse_duration(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    let (negative, rest) = match value.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, value.strip_prefix('+').unwrap_or(value)),
+    };
+    let rest = rest.strip_prefix('P')?;
+    let mut total = Duration::zero();
+    let mut number = String::new();
+    let mut in_time = false;
+    for ch in rest.chars() {
+        match ch {
+            'T' => in_time = true,
+            '0'..='9' => number.push(ch),
+            unit => {
+                let amount: i64 = number.parse().ok()?;
+                number.clear();
+                let part = match (unit, in_time) {
+                    ('W', false) => Duration::try_weeks(amount),
+                    ('D', false) => Duration::try_days(amount),
+                    ('H', true) => Duration::try_hours(amount),
+                    ('M', true) => Duration::try_minutes(amount),
+                    ('S', true) => Duration::try_seconds(amount),
+                    _ => None,
+                }?;
+                total = total.checked_add(&part)?;
+            }
+        }
+    }
+    if !number.is_empty() {
+        return None;
+    }
+    Some(if negative { -total } else { total })
+}
+
+/// A VEVENT read from an imported file.
+#[derive(Debug, Clone)]
+pub struct IcsEvent {
+    pub uid: String,
+    /// Set when the VEVENT overrides one occurrence of a recurring event.
+    pub recurrence_id: Option<IcsTime>,
+    pub summary: Option<String>,
+    pub description: Option<String>,
+    pub location: Option<String>,
+    pub url: Option<String>,
+    pub start: IcsTime,
+    pub end: Option<IcsTime>,
+    pub duration: Option<Duration>,
+    pub rrule: Option<String>,
+    pub exdates: Vec<IcsTime>,
+    pub status: Option<EventStatus>,
+    pub categories: Vec<String>,
+    pub attendees: Vec<EventParticipant>,
+    /// Minutes before the start, from VALARM triggers.
+    pub reminders: Vec<i32>,
+}
+
+/// A VEVENT that could not be read, with its UID when it had one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IcsEventError {
+    pub uid: Option<String>,
+    pub reason: String,
+}
+
+#[derive(Default)]
+struct RawEvent {
+    lines: Vec<ContentLine>,
+    alarms: Vec<Vec<ContentLine>>,
+}
+
+impl RawEvent {
+    fn first(&self, name: &str) -> Option<&ContentLine> {
+        self.lines.iter().find(|line| line.name == name)
+    }
+
+    fn all<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a ContentLine> + 'a {
+        self.lines.iter().filter(move |line| line.name == name)
+    }
+
+    fn text(&self, name: &str) -> Option<String> {
+        self.first(name)
+            .map(|line| unescape_text(&line.value))
+            .filter(|value| !value.trim().is_empty())
+    }
+
+    fn read(self) -> Result<IcsEvent, IcsEventError> {
+        let uid = self
+            .first("UID")
+            .map(|line| line.value.trim().to_string())
+            .filter(|uid| !uid.is_empty());
+        let fail = |reason: String| IcsEventError {
+            uid: uid.clone(),
+            reason,
+        };
+        let time = |name: &str| -> Result<Option<IcsTime>, IcsEventError> {
+            self.first(name)
+                .map(|line| parse_time_value(line, &line.value))
+                .transpose()
+                .map_err(fail)
+        };
+
+        let start = time("DTSTART")?.ok_or_else(|| fail("VEVENT has no DTSTART".to_string()))?;
+        let end = time("DTEND")?;
+        let recurrence_id = time("RECURRENCE-ID")?;
+        let duration = match self.first("DURATION") {
+            Some(line) => Some(
+                parse_duration(&line.value)
+                    .filter(|duration| start.local.checked_add_signed(*duration).is_some())
+                    .ok_or_else(|| fail(format!("Invalid DURATION: {}", line.value)))?,
+            ),
+            None => None,
+        };
+        let mut exdates = Vec::new();
+        for line in self.all("EXDATE") {
+            for value in line
+                .value
+                .split(',')
+                .filter(|value| !value.trim().is_empty())
+            {
+                exdates.push(parse_time_value(line, value).map_err(fail)?);
+            }
+        }
+        let status = self.first("STATUS").and_then(|line| {
+            match line.value.trim().to_ascii_uppercase().as_str() {
+                "CONFIRMED" => Some(EventStatus::Confirmed),
+                "TENTATIVE" => Some(EventStatus::Tentative),
+                "CANCELLED" => Some(EventStatus::Cancelled),
+                _ => None,
+            }
+        });
+        let categories = self
+            .all("CATEGORIES")
+            .flat_map(|line| split_text_list(&line.value))
+            .map(|category| category.trim().to_string())
+            .filter(|category| !category.is_empty())
+            .collect();
+        let attendees = self.all("ATTENDEE").filter_map(read_attendee).collect();
+        let mut reminders: Vec<i32> = self
+            .alarms
+            .iter()
+            .filter_map(|alarm| alarm.iter().find(|line| line.name == "TRIGGER"))
+            .filter(|trigger| {
+                trigger.param("RELATED").unwrap_or("START") == "START"
+                    && trigger.param("VALUE").unwrap_or("DURATION") == "DURATION"
+            })
+            .filter_map(|trigger| parse_duration(&trigger.value))
+            .filter(|offset| *offset <= Duration::zero())
+            .filter_map(|offset| i32::try_from(-offset.num_minutes()).ok())
+            .collect();
+        reminders.sort_unstable();
+        reminders.dedup();
+
+        Ok(IcsEvent {
+            uid: uid
+                .clone()
+                .ok_or_else(|| fail("VEVENT has no UID".to_string()))?,
+            recurrence_id,
+            summary: self.text("SUMMARY"),
+            description: self.text("DESCRIPTION"),
+            location: self.text("LOCATION"),
+            url: self
+                .first("URL")
+                .map(|line| line.value.trim().to_string())
+                .filter(|url| !url.is_empty()),
+            start,
+            end,
+            duration,
+            rrule: self
+                .first("RRULE")
+                .map(|line| line.value.trim().to_string()),
+            exdates,
+            status,
+            categories,
+            attendees,
+            reminders,
+        })
+    }
+}
+
+fn read_attendee(line: &ContentLine) -> Option<EventParticipant> {
+    let value = line.value.trim();
+    let email = match value.get(..7) {
+        Some(scheme) if scheme.eq_ignore_ascii_case("mailto:") => &value[7..],
+        _ => value,
+    }
+    .trim();
+    if email.is_empty() {
+        return None;
+    }
+    let status = match line
+        .param("PARTSTAT")
+        .unwrap_or("NEEDS-ACTION")
+        .to_ascii_uppercase()
+        .as_str()
+    {
+        "ACCEPTED" => ParticipantStatus::Accepted,
+        "DECLINED" => ParticipantStatus::Declined,
+        "TENTATIVE" => ParticipantStatus::Tentative,
+        _ => ParticipantStatus::NeedsAction,
+    };
+    Some(EventParticipant {
+        id: email.to_ascii_lowercase(),
+        name: non_blank(line.param("CN")).unwrap_or(email).to_string(),
+        email: Some(email.to_string()),
+        status,
+    })
+}
+
+/// Read the VEVENTs of an iCalendar document. Each VEVENT is read on its
+/// own, so one malformed event does not reject the file.
+pub fn parse_calendar(text: &str) -> Result<Vec<Result<IcsEvent, IcsEventError>>, String> {
+    let mut saw_calendar = false;
+    let mut stack: Vec<String> = Vec::new();
+    let mut current: Option<RawEvent> = None;
+    let mut events = Vec::new();
+
+    for line in unfold(text).iter().filter_map(|line| parse_line(line)) {
+        match line.name.as_str() {
+            "BEGIN" => {
+                let component = line.value.trim().to_ascii_uppercase();
+                let parent = stack.last().map(String::as_str);
+                match (component.as_str(), parent) {
+                    ("VCALENDAR", None) => saw_calendar = true,
+                    ("VEVENT", Some("VCALENDAR")) => current = Some(RawEvent::default()),
+                    ("VALARM", Some("VEVENT")) => {
+                        if let Some(event) = current.as_mut() {
+                            event.alarms.push(Vec::new());
+                        }
+                    }
+                    _ => {}
+                }
+                stack.push(component);
+            }
+            "END" => {
+                let component = stack.pop();
+                if component.as_deref() == Some("VEVENT") {
+                    if let Some(event) = current.take() {
+                        events.push(event.read());
+                    }
+                }
+            }
+            _ => match (stack.last().map(String::as_str), current.as_mut()) {
+                (Some("VEVENT"), Some(event)) => event.lines.push(line),
+                (Some("VALARM"), Some(event)) => {
+                    if let Some(alarm) = event.alarms.last_mut() {
+                        alarm.push(line);
+                    }
+                }
+                _ => {}
+            },
+        }
+    }
+
+    if !saw_calendar {
+        return Err("Not an iCalendar file: no VCALENDAR found".to_string());
+    }
+    Ok(events)
+}
+
+/// Times of an imported event as they are stored.
+struct StoredTimes {
+    start: String,
+    end: String,
+    all_day: bool,
+    timezone: Option<String>,
+}
+
+impl IcsEvent {
+    fn stored_times(&self) -> StoredTimes {
+        let start = self.start;
+        let end = match (self.end, self.duration) {
+            (Some(end), _) => end.local_in(start.tz),
+            // Checked on read.
+            (None, Some(duration)) => start
+                .local
+                .checked_add_signed(duration)
+                .unwrap_or(start.local),
+            (None, None) if start.kind == TimeKind::Date => start.local + Duration::days(1),
+            (None, None) => start.local,
+        };
+        let end = end.max(start.local);
+        let format = |local: NaiveDateTime| local.format("%Y-%m-%dT%H:%M:%S").to_string();
+        match start.kind {
+            // Stored like a scheduled task: from midnight to 23:59:59 on the
+            // last day, since DTEND is exclusive.
+            TimeKind::Date => StoredTimes {
+                start: format(start.local),
+                end: format(end.max(start.local + Duration::days(1)) - Duration::seconds(1)),
+                all_day: true,
+                timezone: None,
+            },
+            TimeKind::Floating => StoredTimes {
+                start: format(start.local),
+                end: format(end),
+                all_day: false,
+                timezone: None,
+            },
+            TimeKind::Zoned => StoredTimes {
+                start: format(start.local),
+                end: format(end),
+                all_day: false,
+                timezone: Some(start.tz.name().to_string()),
+            },
+            TimeKind::Utc => StoredTimes {
+                start: format!("{}Z", format(start.local)),
+                end: format!("{}Z", format(end)),
+                all_day: false,
+                timezone: None,
+            },
+        }
+    }
+
+    /// The RRULE with this event's EXDATEs, as `recurrence_rule` stores it.
+    fn stored_rule(&self) -> Result<Option<String>, String> {
+        let Some(rrule) = self.rrule.as_deref() else {
+            return Ok(None);
+        };
+        let mut rule: RecurrenceRule = rrule.parse()?;
+        rule.exdates = self
+            .exdates
+            .iter()
+            .map(|exdate| match self.start.kind {
+                TimeKind::Date => RuleTime::Date(exdate.local.date()),
+                TimeKind::Floating | TimeKind::Zoned => {
+                    RuleTime::Floating(exdate.local_in(self.start.tz))
+                }
+                TimeKind::Utc => RuleTime::Utc(local_to_utc(exdate.local, exdate.tz)),
+            })
+            .collect();
+        Ok(Some(rule.to_string()))
+    }
+
+    fn title(&self) -> String {
+        non_blank(self.summary.as_deref())
+            .unwrap_or(UNTITLED)
+            .to_string()
+    }
+
+    /// A new event from this VEVENT, remembering `ical_uid`.
+    pub fn to_create_input(
+        &self,
+        ical_uid: String,
+        technician_id: Option<String>,
+    ) -> Result<CreateEventInput, String> {
+        let times = self.stored_times();
+        Ok(CreateEventInput {
+            title: self.title(),
+            description: self.description.clone(),
+            start_datetime: times.start,
+            end_datetime: times.end,
+            all_day: Some(times.all_day),
+            timezone: times.timezone,
+            event_type: None,
+            category: None,
+            task_id: None,
+            client_id: None,
+            technician_id,
+            location: self.location.clone(),
+            meeting_link: self.url.clone(),
+            is_virtual: None,
+            participants: Some(self.attendees.clone()),
+            reminders: Some(self.reminders.clone()),
+            color: None,
+            tags: Some(self.categories.clone()),
+            notes: None,
+            recurrence_rule: self.stored_rule()?,
+            parent_event_id: None,
+            ical_uid: Some(ical_uid),
+        })
+    }
+
+    /// Changes bringing an existing event in line with this VEVENT.
+    pub fn to_update_input(&self) -> Result<UpdateEventInput, String> {
+        let times = self.stored_times();
+        Ok(UpdateEventInput {
+            title: Some(self.title()),
+            description: self.description.clone(),
+            start_datetime: Some(times.start),
+            end_datetime: Some(times.end),
+            all_day: Some(times.all_day),
+            timezone: Some(
+                times
+                    .timezone
+                    .unwrap_or_else(|| crate::shared::constants::DEFAULT_TIMEZONE.to_string()),
+            ),
+            location: self.location.clone(),
+            meeting_link: self.url.clone(),
+            participants: Some(self.attendees.clone()),
+            status: self.status.clone(),
+            reminders: Some(self.reminders.clone()),
+            tags: Some(self.categories.clone()),
+            recurrence_rule: Some(self.stored_rule()?.unwrap_or_default()),
+            ..Default::default()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{CalendarTaskPriority, EventType};
+    use super::*;
+
+    fn event() -> CalendarEvent {
+        CalendarEvent {
+            id: "evt-1".to_string(),
+            title: "Full front PPF, Smith".to_string(),
+            description: Some("Bring the car clean;\nkeys at desk".to_string()),
+            start_datetime: "2025-06-02T09:00:00".to_string(),
+            end_datetime: "2025-06-02T11:30:00".to_string(),
+            all_day: false,
+            timezone: "Europe/Paris".to_string(),
+            event_type: EventType::Appointment,
+            category: None,
+            task_id: None,
+            client_id: None,
+            technician_id: None,
+            location: Some("Bay 2".to_string()),
+            meeting_link: None,
+            is_virtual: false,
+            participants: vec![EventParticipant {
+                id: "c-1".to_string(),
+                name: "Smith, John".to_string(),
+                email: Some("john@example.com".to_string()),
+                status: ParticipantStatus::NeedsAction,
+            }],
+            is_recurring: true,
+            recurrence_rule: Some(
+                "FREQ=WEEKLY;UNTIL=20250630T235959\nEXDATE:20250609T090000".to_string(),
+            ),
+            parent_event_id: None,
+            reminders: vec![15, 1440],
+            status: EventStatus::Confirmed,
+            color: None,
+            tags: vec!["ppf".to_string()],
+            notes: None,
+            synced: false,
+            last_synced_at: None,
+            created_at: 1_748_000_000_000,
+            updated_at: 1_748_000_000_000,
+            created_by: None,
+            updated_by: None,
+            deleted_at: None,
+            deleted_by: None,
+            ical_uid: None,
+            recurrence_id: None,
+        }
+    }
+
+    fn stamp() -> DateTime<Utc> {
+        DateTime::from_timestamp(1_748_000_000, 0).unwrap()
+    }
+
+    #[test]
+    fn invite_writes_zoned_times_rule_attendees_and_alarms() {
+        let organizer = Organizer {
+            name: "Workshop".to_string(),
+            email: "desk@example.com".to_string(),
+        };
+        let mut writer = IcsWriter::invite(organizer, stamp());
+        writer.add_event(&event()).unwrap();
+        let document = writer.finish();
+        assert!(document
+            .lines()
+            .all(|line| line.len() <= MAX_LINE_OCTETS + 1));
+
+        let lines = unfold(&document);
+        for expected in [
+            "METHOD:REQUEST",
+            "UID:evt-1@rpma-ppf-intervention",
+            "DTSTART;TZID=Europe/Paris:20250602T090000",
+            "DTEND;TZID=Europe/Paris:20250602T113000",
+            "RRULE:FREQ=WEEKLY;UNTIL=20250630T215959Z",
+            "EXDATE;TZID=Europe/Paris:20250609T090000",
+            "SUMMARY:Full front PPF\\, Smith",
+            "DESCRIPTION:Bring the car clean\\;\\nkeys at desk",
+            "ORGANIZER;CN=Workshop:mailto:desk@example.com",
+            "ATTENDEE;CN=\"Smith, John\";ROLE=REQ-PARTICIPANT;PARTSTAT=NEEDS-ACTION;RSVP=TRUE:mailto:john@example.com",
+            "TRIGGER:-PT15M",
+            "TRIGGER:-P1D",
+        ] {
+            assert!(lines.iter().any(|line| line == expected), "missing {}", expected);
+        }
+    }
+
+    #[test]
+    fn exported_event_imports_back_unchanged() {
+        let mut writer = IcsWriter::publish("Workshop", stamp());
+        writer.add_event(&event()).unwrap();
+        let parsed = parse_calendar(&writer.finish()).unwrap();
+        let imported = parsed[0].as_ref().unwrap();
+        assert_eq!(own_event_id(&imported.uid), Some("evt-1"));
+
+        let input = imported
+            .to_create_input(imported.uid.clone(), None)
+            .unwrap();
+        assert_eq!(input.title, "Full front PPF, Smith");
+        assert_eq!(input.start_datetime, "2025-06-02T09:00:00");
+        assert_eq!(input.end_datetime, "2025-06-02T11:30:00");
+        assert_eq!(input.timezone.as_deref(), Some("Europe/Paris"));
+        assert_eq!(input.reminders, Some(vec![15, 1440]));
+        assert_eq!(input.participants.unwrap()[0].name, "Smith, John");
+        let rule: RecurrenceRule = input.recurrence_rule.unwrap().parse().unwrap();
+        let original: RecurrenceRule = event().recurrence_rule.unwrap().parse().unwrap();
+        let tz: Tz = "Europe/Paris".parse().unwrap();
+        let start = NaiveDateTime::parse_from_str("2025-06-02T09:00", "%Y-%m-%dT%H:%M").unwrap();
+        assert_eq!(
+            rule.occurrences(start, tz).collect::<Vec<_>>(),
+            original.occurrences(start, tz).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn parses_third_party_events() {
+        let document = "BEGIN:VCALENDAR\r\nVERSION:2.0\r\n\
+            BEGIN:VTIMEZONE\r\nTZID:/mozilla.org/20070129_1/Europe/Paris\r\nEND:VTIMEZONE\r\n\
+            BEGIN:VEVENT\r\nUID:abc@example.com\r\n\
+            DTSTART;TZID=/mozilla.org/20070129_1/Europe/Paris:20250610T140000\r\n\
+            DURATION:PT1H30M\r\nSUMMARY:Ceramic coating\\, \r\n  follow-up\r\n\
+            CATEGORIES:coating,follow\\,up\r\nSTATUS:TENTATIVE\r\n\
+            BEGIN:VALARM\r\nTRIGGER;RELATED=START:-PT1H\r\nACTION:DISPLAY\r\nEND:VALARM\r\n\
+            END:VEVENT\r\n\
+            BEGIN:VEVENT\r\nUID:day@example.com\r\nDTSTART;VALUE=DATE:20250612\r\n\
+            DTEND;VALUE=DATE:20250614\r\nSUMMARY:Closed\r\nEND:VEVENT\r\n\
+            BEGIN:VEVENT\r\nSUMMARY:No uid\r\nDTSTART:20250612T080000Z\r\nEND:VEVENT\r\n\
+            END:VCALENDAR\r\n";
+        let parsed = parse_calendar(document).unwrap();
+        assert_eq!(parsed.len(), 3);
+
+        let coating = parsed[0].as_ref().unwrap();
+        assert_eq!(
+            coating.summary.as_deref(),
+            Some("Ceramic coating,  follow-up")
+        );
+        assert_eq!(coating.categories, vec!["coating", "follow,up"]);
+        assert_eq!(coating.status, Some(EventStatus::Tentative));
+        assert_eq!(coating.reminders, vec![60]);
+        let input = coating.to_create_input(coating.uid.clone(), None).unwrap();
+        assert_eq!(input.start_datetime, "2025-06-10T14:00:00");
+        assert_eq!(input.end_datetime, "2025-06-10T15:30:00");
+        assert_eq!(input.timezone.as_deref(), Some("Europe/Paris"));
+
+        let closed = parsed[1].as_ref().unwrap();
+        let input = closed.to_create_input(closed.uid.clone(), None).unwrap();
+        assert_eq!(input.all_day, Some(true));
+        assert_eq!(input.start_datetime, "2025-06-12T00:00:00");
+        assert_eq!(input.end_datetime, "2025-06-13T23:59:59");
+
+        assert_eq!(
+            parsed[2].as_ref().unwrap_err().reason,
+            "VEVENT has no UID".to_string()
+        );
+        assert!(parse_calendar("hello").is_err());
+    }
+
+    #[test]
+    fn out_of_range_durations_fail_the_event_only() {
+        let document = "BEGIN:VCALENDAR\r\n\
+            BEGIN:VEVENT\r\nUID:long@example.com\r\nDTSTART:20250610T140000Z\r\n\
+            DURATION:P99999999999999D\r\nEND:VEVENT\r\n\
+            BEGIN:VEVENT\r\nUID:far@example.com\r\nDTSTART:20250610T140000Z\r\n\
+            DURATION:P99999999D\r\nEND:VEVENT\r\n\
+            BEGIN:VEVENT\r\nUID:alarm@example.com\r\nDTSTART:20250610T140000Z\r\n\
+            DURATION:PT1H\r\n\
+            BEGIN:VALARM\r\nTRIGGER:-P99999999999999W\r\nACTION:DISPLAY\r\nEND:VALARM\r\n\
+            END:VEVENT\r\n\
+            END:VCALENDAR\r\n";
+        let parsed = parse_calendar(document).unwrap();
+        assert_eq!(
+            parsed[0].as_ref().unwrap_err().reason,
+            "Invalid DURATION: P99999999999999D"
+        );
+        // Fits a Duration, but ends past the last representable date.
+        assert!(parsed[1].is_err());
+        // An unreadable alarm is skipped like any other.
+        assert!(parsed[2].as_ref().unwrap().reminders.is_empty());
+    }
+
+    #[test]
+    fn tasks_export_as_floating_or_all_day_events() {
+        let mut task = CalendarTask {
+            id: "t-1".to_string(),
+            task_number: "T-0042".to_string(),
+            title: "Full wrap".to_string(),
+            status: CalendarTaskStatus::Scheduled,
+            priority: CalendarTaskPriority::Medium,
+            scheduled_date: "2025-06-03".to_string(),
+            start_time: Some("08:30".to_string()),
+            end_time: None,
+            vehicle_plate: Some("AB-123-CD".to_string()),
+            vehicle_model: Some("Model 3".to_string()),
+            technician_id: None,
+            technician_name: Some("Sam".to_string()),
+            client_id: None,
+            client_name: None,
+            estimated_duration: Some(240),
+            actual_duration: None,
+        };
+        let mut writer = IcsWriter::publish("Workshop", stamp());
+        writer.add_task(&task).unwrap();
+        task.start_time = None;
+        writer.add_task(&task).unwrap();
+        let lines = unfold(&writer.finish());
+        for expected in [
+            "UID:task-t-1@rpma-ppf-intervention",
+            "DTSTART:20250603T083000",
+            "DTEND:20250603T123000",
+            "DTSTART;VALUE=DATE:20250603",
+            "DTEND;VALUE=DATE:20250604",
+            "SUMMARY:Full wrap (T-0042)",
+            "DESCRIPTION:Vehicle: AB-123-CD Model 3\\nTechnician: Sam",
+        ] {
+            assert!(
+                lines.iter().any(|line| line == expected),
+                "missing {}",
+                expected
+            );
+        }
+        assert_eq!(own_event_id("task-t-1@rpma-ppf-intervention"), None);
+    }
+}
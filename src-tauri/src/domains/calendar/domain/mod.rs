@@ -1,6 +1,11 @@
 //! Domain layer for the calendar domain (ADR-001).
 
+pub mod availability;
+pub mod ical;
+pub mod recurrence;
+pub mod reminders;
 pub mod repositories;
+pub mod slots;
 
 pub use crate::domains::calendar::models::*;
 pub use repositories::*;
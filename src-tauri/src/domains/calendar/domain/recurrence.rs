@@ -0,0 +1,796 @@
+//! RFC 5545 recurrence rules for calendar events.
+//!
+//! A recurring event stores its rule in `recurrence_rule` as RRULE content,
+//! optionally followed by an `EXDATE` line for cancelled occurrences:
+//!
+//! ```text
+//! FREQ=WEEKLY;INTERVAL=2;BYDAY=TU,TH;UNTIL=20251231T235959Z
+//! EXDATE:20250610T090000Z,20250624T090000Z
+//! ```
+//!
+//! Occurrences are generated on the event's wall clock in its `timezone`, so
+//! a 09:00 appointment stays at 09:00 across daylight-saving changes.
+//! Supported parts are FREQ (DAILY, WEEKLY, MONTHLY, YEARLY), INTERVAL, BYDAY
+//! (ordinals such as `-1FR` with MONTHLY only), COUNT, UNTIL and WKST.
+
+use chrono::{
+    DateTime, Datelike, Duration, LocalResult, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc,
+    Weekday,
+};
+use chrono_tz::Tz;
+use std::collections::VecDeque;
+use std::fmt;
+use std::str::FromStr;
+
+use super::CalendarEvent;
+
+/// Upper bound on generated periods, so an open-ended rule cannot loop forever.
+const MAX_PERIODS: u32 = 50_000;
+/// Largest accepted INTERVAL.
+const MAX_INTERVAL: u32 = 1_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+/// One BYDAY entry, e.g. `TU` or `-1FR` (last Friday of the month).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ByDay {
+    pub ordinal: Option<i32>,
+    pub weekday: Weekday,
+}
+
+/// An UNTIL or EXDATE value as written in the rule: a date, a floating
+/// local time, or a UTC instant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuleTime {
+    Date(NaiveDate),
+    Floating(NaiveDateTime),
+    Utc(DateTime<Utc>),
+}
+
+impl RuleTime {
+    fn parse(value: &str) -> Result<Self, String> {
+        let value = value.trim();
+        if let Some(utc) = value.strip_suffix('Z') {
+            return NaiveDateTime::parse_from_str(utc, "%Y%m%dT%H%M%S")
+                .map(|time| RuleTime::Utc(time.and_utc()))
+                .map_err(|_| format!("Invalid recurrence date-time: {}", value));
+        }
+        if value.contains('T') {
+            return NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S")
+                .map(RuleTime::Floating)
+                .map_err(|_| format!("Invalid recurrence date-time: {}", value));
+        }
+        NaiveDate::parse_from_str(value, "%Y%m%d")
+            .map(RuleTime::Date)
+            .map_err(|_| format!("Invalid recurrence date: {}", value))
+    }
+
+    /// The value on the event's wall clock. A date means its first moment.
+    pub fn to_local(self, tz: Tz) -> NaiveDateTime {
+        match self {
+            RuleTime::Date(date) => date.and_time(NaiveTime::MIN),
+            RuleTime::Floating(local) => local,
+            RuleTime::Utc(instant) => instant.with_timezone(&tz).naive_local(),
+        }
+    }
+
+    fn matches(self, local: NaiveDateTime, tz: Tz) -> bool {
+        match self {
+            RuleTime::Date(date) => local.date() == date,
+            RuleTime::Floating(time) => local == time,
+            RuleTime::Utc(instant) => local_to_utc(local, tz) == instant,
+        }
+    }
+}
+
+impl fmt::Display for RuleTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RuleTime::Date(date) => write!(f, "{}", date.format("%Y%m%d")),
+            RuleTime::Floating(time) => write!(f, "{}", time.format("%Y%m%dT%H%M%S")),
+            RuleTime::Utc(instant) => write!(f, "{}", instant.format("%Y%m%dT%H%M%SZ")),
+        }
+    }
+}
+
+/// A parsed `recurrence_rule`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecurrenceRule {
+    pub frequency: Frequency,
+    pub interval: u32,
+    pub by_day: Vec<ByDay>,
+    pub week_start: Weekday,
+    pub count: Option<u32>,
+    pub until: Option<RuleTime>,
+    pub exdates: Vec<RuleTime>,
+}
+
+fn parse_weekday(code: &str) -> Option<Weekday> {
+    match code {
+        "MO" => Some(Weekday::Mon),
+        "TU" => Some(Weekday::Tue),
+        "WE" => Some(Weekday::Wed),
+        "TH" => Some(Weekday::Thu),
+        "FR" => Some(Weekday::Fri),
+        "SA" => Some(Weekday::Sat),
+        "SU" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+fn weekday_code(weekday: Weekday) -> &'static str {
+    match weekday {
+        Weekday::Mon => "MO",
+        Weekday::Tue => "TU",
+        Weekday::Wed => "WE",
+        Weekday::Thu => "TH",
+        Weekday::Fri => "FR",
+        Weekday::Sat => "SA",
+        Weekday::Sun => "SU",
+    }
+}
+
+fn parse_by_day(value: &str) -> Result<ByDay, String> {
+    let value = value.trim();
+    let invalid = || format!("Invalid BYDAY value: {}", value);
+    // Also keeps the split below on a char boundary.
+    if value.len() < 2 || !value.is_ascii() {
+        return Err(invalid());
+    }
+    let (ordinal, code) = value.split_at(value.len() - 2);
+    let weekday = parse_weekday(code).ok_or_else(invalid)?;
+    let ordinal = if ordinal.is_empty() {
+        None
+    } else {
+        let ordinal: i32 = ordinal.parse().map_err(|_| invalid())?;
+        if ordinal == 0 || ordinal.abs() > 5 {
+            return Err(invalid());
+        }
+        Some(ordinal)
+    };
+    Ok(ByDay { ordinal, weekday })
+}
+
+fn parse_positive(name: &str, value: &str) -> Result<u32, String> {
+    match value.trim().parse::<u32>() {
+        Ok(number) if number > 0 => Ok(number),
+        _ => Err(format!("{} must be a positive number", name)),
+    }
+}
+
+impl RecurrenceRule {
+    fn parse_rrule(value: &str) -> Result<Self, String> {
+        let mut frequency = None;
+        let mut rule = RecurrenceRule {
+            frequency: Frequency::Daily,
+            interval: 1,
+            by_day: Vec::new(),
+            week_start: Weekday::Mon,
+            count: None,
+            until: None,
+            exdates: Vec::new(),
+        };
+        for part in value.split(';').filter(|part| !part.trim().is_empty()) {
+            let (name, value) = part
+                .split_once('=')
+                .ok_or_else(|| format!("Invalid recurrence rule part: {}", part))?;
+            match name.trim().to_ascii_uppercase().as_str() {
+                "FREQ" => {
+                    frequency = Some(match value.trim().to_ascii_uppercase().as_str() {
+                        "DAILY" => Frequency::Daily,
+                        "WEEKLY" => Frequency::Weekly,
+                        "MONTHLY" => Frequency::Monthly,
+                        "YEARLY" => Frequency::Yearly,
+                        other => return Err(format!("Unsupported FREQ: {}", other)),
+                    })
+                }
+                "INTERVAL" => {
+                    rule.interval = parse_positive("INTERVAL", value)?;
+                    if rule.interval > MAX_INTERVAL {
+                        return Err(format!("INTERVAL must be at most {}", MAX_INTERVAL));
+                    }
+                }
+                "COUNT" => rule.count = Some(parse_positive("COUNT", value)?),
+                "UNTIL" => rule.until = Some(RuleTime::parse(value)?),
+                "BYDAY" => {
+                    rule.by_day = value
+                        .to_ascii_uppercase()
+                        .split(',')
+                        .map(parse_by_day)
+                        .collect::<Result<_, _>>()?
+                }
+                "WKST" => {
+                    rule.week_start = parse_weekday(value.trim().to_ascii_uppercase().as_str())
+                        .ok_or_else(|| format!("Invalid WKST: {}", value))?
+                }
+                other => return Err(format!("Unsupported recurrence rule part: {}", other)),
+            }
+        }
+        rule.frequency = frequency.ok_or_else(|| "Recurrence rule needs FREQ".to_string())?;
+
+        if rule.count.is_some() && rule.until.is_some() {
+            return Err("Recurrence rule cannot have both COUNT and UNTIL".to_string());
+        }
+        if !rule.by_day.is_empty() {
+            match rule.frequency {
+                Frequency::Yearly => {
+                    return Err("BYDAY is not supported with FREQ=YEARLY".to_string())
+                }
+                Frequency::Daily | Frequency::Weekly
+                    if rule.by_day.iter().any(|day| day.ordinal.is_some()) =>
+                {
+                    return Err("BYDAY ordinals are only supported with FREQ=MONTHLY".to_string())
+                }
+                _ => {}
+            }
+        }
+        Ok(rule)
+    }
+
+    /// All generated instances from `dtstart` on the wall clock of `tz`, in
+    /// order, including excluded ones (flagged). Stops at UNTIL or COUNT.
+    pub fn instances(&self, dtstart: NaiveDateTime, tz: Tz) -> Instances<'_> {
+        Instances {
+            rule: self,
+            tz,
+            dtstart,
+            until: self.until.map(|until| match until {
+                RuleTime::Date(date) => date.and_hms_opt(23, 59, 59).unwrap_or(dtstart),
+                other => other.to_local(tz),
+            }),
+            period: 0,
+            generated: 0,
+            buffer: VecDeque::new(),
+            done: false,
+        }
+    }
+
+    /// Occurrence starts from `dtstart`, in order, without excluded dates.
+    pub fn occurrences(
+        &self,
+        dtstart: NaiveDateTime,
+        tz: Tz,
+    ) -> impl Iterator<Item = NaiveDateTime> + '_ {
+        self.instances(dtstart, tz)
+            .filter(|instance| !instance.excluded)
+            .map(|instance| instance.start)
+    }
+
+    /// Dates of the `period`-th period, or `None` once it lies beyond the
+    /// dates chrono can represent.
+    fn candidates(&self, dtstart: NaiveDateTime, period: u32) -> Option<Vec<NaiveDate>> {
+        let step = i64::from(period) * i64::from(self.interval);
+        let start = dtstart.date();
+        let mut dates = match self.frequency {
+            Frequency::Daily => {
+                let date = start.checked_add_signed(Duration::try_days(step)?)?;
+                let wanted = self.by_day.is_empty()
+                    || self.by_day.iter().any(|day| day.weekday == date.weekday());
+                if wanted {
+                    vec![date]
+                } else {
+                    Vec::new()
+                }
+            }
+            Frequency::Weekly => {
+                let offset = |weekday: Weekday| {
+                    Duration::days(i64::from(
+                        (weekday.num_days_from_monday() + 7
+                            - self.week_start.num_days_from_monday())
+                            % 7,
+                    ))
+                };
+                let week = start
+                    .checked_sub_signed(offset(start.weekday()))?
+                    .checked_add_signed(Duration::try_weeks(step)?)?;
+                if self.by_day.is_empty() {
+                    vec![week.checked_add_signed(offset(start.weekday()))?]
+                } else {
+                    self.by_day
+                        .iter()
+                        .map(|day| week.checked_add_signed(offset(day.weekday)))
+                        .collect::<Option<_>>()?
+                }
+            }
+            Frequency::Monthly => {
+                let month_index = i64::from(start.year()) * 12 + i64::from(start.month0()) + step;
+                let year = i32::try_from(month_index.div_euclid(12)).ok()?;
+                let month = month_index.rem_euclid(12) as u32 + 1;
+                // Out of range years end the rule; missing days only skip.
+                NaiveDate::from_ymd_opt(year, 1, 1)?;
+                if self.by_day.is_empty() {
+                    NaiveDate::from_ymd_opt(year, month, start.day())
+                        .into_iter()
+                        .collect()
+                } else {
+                    self.by_day
+                        .iter()
+                        .flat_map(|day| weekdays_in_month(year, month, *day))
+                        .collect()
+                }
+            }
+            Frequency::Yearly => {
+                let year = i32::try_from(i64::from(start.year()) + step).ok()?;
+                NaiveDate::from_ymd_opt(year, 1, 1)?;
+                NaiveDate::from_ymd_opt(year, start.month(), start.day())
+                    .into_iter()
+                    .collect()
+            }
+        };
+        dates.sort();
+        dates.dedup();
+        Some(dates)
+    }
+
+    /// This rule ended just before `occurrence` (for "this and following"
+    /// edits), with the dates excluded before it.
+    pub fn ending_before(&self, occurrence: NaiveDateTime, form: TimeForm, tz: Tz) -> Self {
+        let last = occurrence - Duration::seconds(1);
+        let mut rule = self.clone();
+        rule.count = None;
+        rule.until = Some(match form {
+            TimeForm::Utc => RuleTime::Utc(local_to_utc(last, tz)),
+            TimeForm::Floating => RuleTime::Floating(last),
+            TimeForm::Date => RuleTime::Date(last.date()),
+        });
+        rule.exdates
+            .retain(|exdate| exdate.to_local(tz) < occurrence);
+        rule
+    }
+
+    /// The rest of this rule from `occurrence`, which is preceded by
+    /// `preceding` generated instances.
+    pub fn starting_at(&self, occurrence: NaiveDateTime, preceding: u32, tz: Tz) -> Self {
+        let mut rule = self.clone();
+        rule.count = self
+            .count
+            .map(|count| count.saturating_sub(preceding).max(1));
+        rule.exdates
+            .retain(|exdate| exdate.to_local(tz) >= occurrence);
+        rule
+    }
+
+    /// This rule with `occurrence` excluded.
+    pub fn excluding(&self, occurrence: NaiveDateTime, form: TimeForm, tz: Tz) -> Self {
+        let mut rule = self.clone();
+        rule.exdates.push(match form {
+            TimeForm::Utc => RuleTime::Utc(local_to_utc(occurrence, tz)),
+            TimeForm::Floating => RuleTime::Floating(occurrence),
+            TimeForm::Date => RuleTime::Date(occurrence.date()),
+        });
+        rule
+    }
+}
+
+fn weekdays_in_month(year: i32, month: u32, day: ByDay) -> Vec<NaiveDate> {
+    let all: Vec<NaiveDate> = (1..=31)
+        .filter_map(|number| NaiveDate::from_ymd_opt(year, month, number))
+        .filter(|date| date.weekday() == day.weekday)
+        .collect();
+    match day.ordinal {
+        None => all,
+        Some(ordinal) if ordinal > 0 => {
+            all.get(ordinal as usize - 1).copied().into_iter().collect()
+        }
+        Some(ordinal) => all
+            .len()
+            .checked_sub(ordinal.unsigned_abs() as usize)
+            .and_then(|index| all.get(index).copied())
+            .into_iter()
+            .collect(),
+    }
+}
+
+impl FromStr for RecurrenceRule {
+    type Err = String;
+
+    /// Parse RRULE content, with or without the `RRULE:` prefix, plus any
+    /// `EXDATE` lines.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let mut rule = None;
+        let mut exdates = Vec::new();
+        for line in value.lines().map(str::trim).filter(|line| !line.is_empty()) {
+            let (name, content) = line.split_once(':').unwrap_or(("RRULE", line));
+            let name = name.split(';').next().unwrap_or_default();
+            match name.trim().to_ascii_uppercase().as_str() {
+                "RRULE" => rule = Some(RecurrenceRule::parse_rrule(content)?),
+                "EXDATE" => {
+                    for exdate in content
+                        .split(',')
+                        .filter(|exdate| !exdate.trim().is_empty())
+                    {
+                        exdates.push(RuleTime::parse(exdate)?);
+                    }
+                }
+                other => return Err(format!("Unsupported recurrence property: {}", other)),
+            }
+        }
+        let mut rule = rule.ok_or_else(|| "Recurrence rule needs an RRULE".to_string())?;
+        rule.exdates = exdates;
+        Ok(rule)
+    }
+}
+
+impl fmt::Display for RecurrenceRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let frequency = match self.frequency {
+            Frequency::Daily => "DAILY",
+            Frequency::Weekly => "WEEKLY",
+            Frequency::Monthly => "MONTHLY",
+            Frequency::Yearly => "YEARLY",
+        };
+        write!(f, "FREQ={}", frequency)?;
+        if self.interval > 1 {
+            write!(f, ";INTERVAL={}", self.interval)?;
+        }
+        if !self.by_day.is_empty() {
+            let days: Vec<String> = self
+                .by_day
+                .iter()
+                .map(|day| match day.ordinal {
+                    Some(ordinal) => format!("{}{}", ordinal, weekday_code(day.weekday)),
+                    None => weekday_code(day.weekday).to_string(),
+                })
+                .collect();
+            write!(f, ";BYDAY={}", days.join(","))?;
+        }
+        if self.week_start != Weekday::Mon {
+            write!(f, ";WKST={}", weekday_code(self.week_start))?;
+        }
+        if let Some(count) = self.count {
+            write!(f, ";COUNT={}", count)?;
+        }
+        if let Some(until) = self.until {
+            write!(f, ";UNTIL={}", until)?;
+        }
+        if !self.exdates.is_empty() {
+            let exdates: Vec<String> = self.exdates.iter().map(ToString::to_string).collect();
+            write!(f, "\nEXDATE:{}", exdates.join(","))?;
+        }
+        Ok(())
+    }
+}
+
+/// One generated instance of a rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Instance {
+    pub start: NaiveDateTime,
+    pub excluded: bool,
+}
+
+/// Iterator returned by [`RecurrenceRule::instances`].
+pub struct Instances<'a> {
+    rule: &'a RecurrenceRule,
+    tz: Tz,
+    dtstart: NaiveDateTime,
+    until: Option<NaiveDateTime>,
+    period: u32,
+    generated: u32,
+    buffer: VecDeque<NaiveDateTime>,
+    done: bool,
+}
+
+impl Iterator for Instances<'_> {
+    type Item = Instance;
+
+    fn next(&mut self) -> Option<Instance> {
+        loop {
+            if self.done {
+                return None;
+            }
+            let Some(start) = self.buffer.pop_front() else {
+                if self.period >= MAX_PERIODS {
+                    self.done = true;
+                    continue;
+                }
+                let Some(dates) = self.rule.candidates(self.dtstart, self.period) else {
+                    self.done = true;
+                    continue;
+                };
+                let time = self.dtstart.time();
+                self.buffer
+                    .extend(dates.into_iter().map(|date| date.and_time(time)));
+                self.period += 1;
+                continue;
+            };
+            if start < self.dtstart {
+                continue;
+            }
+            let past_until = self.until.is_some_and(|until| start > until);
+            let past_count = self.rule.count.is_some_and(|count| self.generated >= count);
+            if past_until || past_count {
+                self.done = true;
+                continue;
+            }
+            self.generated += 1;
+            return Some(Instance {
+                start,
+                excluded: self
+                    .rule
+                    .exdates
+                    .iter()
+                    .any(|exdate| exdate.matches(start, self.tz)),
+            });
+        }
+    }
+}
+
+/// The UTC instant of a wall-clock time in `tz`. A time skipped by a DST
+/// jump moves forward by an hour; a repeated time takes its first instant.
+pub fn local_to_utc(local: NaiveDateTime, tz: Tz) -> DateTime<Utc> {
+    let resolved = match tz.from_local_datetime(&local) {
+        LocalResult::Single(time) => Some(time),
+        LocalResult::Ambiguous(first, _) => Some(first),
+        LocalResult::None => tz
+            .from_local_datetime(&(local + Duration::hours(1)))
+            .earliest(),
+    };
+    resolved
+        .map(|time| time.with_timezone(&Utc))
+        .unwrap_or_else(|| local.and_utc())
+}
+
+/// Parse an IANA timezone name such as `Europe/Paris`.
+pub fn parse_timezone(name: &str) -> Result<Tz, String> {
+    name.trim()
+        .parse::<Tz>()
+        .map_err(|_| format!("Unknown timezone: {}", name))
+}
+
+/// How a stored `start_datetime` / `end_datetime` is written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeForm {
+    /// An instant: `2025-06-10T07:00:00Z` or with an offset.
+    Utc,
+    /// A wall-clock time in the event's timezone: `2025-06-10T09:00:00`.
+    Floating,
+    /// A date, for all-day events: `2025-06-10`.
+    Date,
+}
+
+/// Parse a stored event time into its wall-clock value in `tz`.
+pub fn parse_event_time(value: &str, tz: Tz) -> Option<(NaiveDateTime, TimeForm)> {
+    let value = value.trim();
+    if let Ok(instant) = DateTime::parse_from_rfc3339(value) {
+        return Some((instant.with_timezone(&tz).naive_local(), TimeForm::Utc));
+    }
+    for format in ["%Y-%m-%dT%H:%M:%S%.f", "%Y-%m-%dT%H:%M"] {
+        if let Ok(local) = NaiveDateTime::parse_from_str(value, format) {
+            return Some((local, TimeForm::Floating));
+        }
+    }
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .ok()
+        .map(|date| (date.and_time(NaiveTime::MIN), TimeForm::Date))
+}
+
+/// Write a wall-clock time in `tz` the way the series stores its times.
+pub fn format_event_time(local: NaiveDateTime, form: TimeForm, tz: Tz) -> String {
+    match form {
+        TimeForm::Utc => local_to_utc(local, tz)
+            .format("%Y-%m-%dT%H:%M:%SZ")
+            .to_string(),
+        TimeForm::Floating => local.format("%Y-%m-%dT%H:%M:%S").to_string(),
+        TimeForm::Date => local.format("%Y-%m-%d").to_string(),
+    }
+}
+
+/// A recurring event with its rule and times parsed.
+pub struct Series {
+    pub rule: RecurrenceRule,
+    pub tz: Tz,
+    pub start: NaiveDateTime,
+    pub form: TimeForm,
+    pub duration: Duration,
+}
+
+impl Series {
+    /// Parse a recurring event. Fails if it has no rule or its rule,
+    /// timezone or times are invalid.
+    pub fn of(event: &CalendarEvent) -> Result<Self, String> {
+        let rule = event
+            .recurrence_rule
+            .as_deref()
+            .filter(|rule| !rule.trim().is_empty())
+            .ok_or_else(|| format!("Event {} is not recurring", event.id))?
+            .parse::<RecurrenceRule>()?;
+        let tz = parse_timezone(&event.timezone)?;
+        let (start, form) = parse_event_time(&event.start_datetime, tz)
+            .ok_or_else(|| format!("Invalid start time: {}", event.start_datetime))?;
+        let (end, _) = parse_event_time(&event.end_datetime, tz)
+            .ok_or_else(|| format!("Invalid end time: {}", event.end_datetime))?;
+        Ok(Self {
+            rule,
+            tz,
+            start,
+            form,
+            duration: end - start,
+        })
+    }
+
+    pub fn format(&self, local: NaiveDateTime) -> String {
+        format_event_time(local, self.form, self.tz)
+    }
+
+    /// The occurrence identified by `recurrence_id` (its original start) and
+    /// the number of instances generated before it.
+    pub fn occurrence(&self, recurrence_id: &str) -> Result<(NaiveDateTime, u32), String> {
+        let not_found = || format!("{} is not an occurrence of this series", recurrence_id);
+        let (target, _) = parse_event_time(recurrence_id, self.tz).ok_or_else(not_found)?;
+        for (preceding, instance) in self.rule.instances(self.start, self.tz).enumerate() {
+            if instance.start > target {
+                break;
+            }
+            if instance.start == target && !instance.excluded {
+                return Ok((target, preceding as u32));
+            }
+        }
+        Err(not_found())
+    }
+
+    /// Occurrences of `series` starting at or after `from` and ending at or
+    /// before `to`, compared the same way stored events are. Each copy keeps
+    /// the series id and carries its original start in `recurrence_id`.
+    pub fn expand(&self, series: &CalendarEvent, from: &str, to: &str) -> Vec<CalendarEvent> {
+        self.rule
+            .occurrences(self.start, self.tz)
+            .map(|start| (self.format(start), self.format(start + self.duration)))
+            .take_while(|(start, _)| start.as_str() <= to)
+            .filter(|(start, end)| start.as_str() >= from && end.as_str() <= to)
+            .map(|(start, end)| {
+                let mut occurrence = series.clone();
+                occurrence.recurrence_id = Some(start.clone());
+                occurrence.start_datetime = start;
+                occurrence.end_datetime = end;
+                occurrence
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(value: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M").unwrap()
+    }
+
+    fn starts(rule: &str, dtstart: &str, limit: usize) -> Vec<String> {
+        rule.parse::<RecurrenceRule>()
+            .unwrap()
+            .occurrences(at(dtstart), chrono_tz::UTC)
+            .take(limit)
+            .map(|start| start.format("%Y-%m-%d %a").to_string())
+            .collect()
+    }
+
+    #[test]
+    fn weekly_byday_with_interval_and_count() {
+        assert_eq!(
+            starts(
+                "FREQ=WEEKLY;INTERVAL=2;BYDAY=TU,TH;COUNT=5",
+                "2025-06-03T09:00",
+                10
+            ),
+            vec![
+                "2025-06-03 Tue",
+                "2025-06-05 Thu",
+                "2025-06-17 Tue",
+                "2025-06-19 Thu",
+                "2025-07-01 Tue"
+            ]
+        );
+    }
+
+    #[test]
+    fn monthly_ordinal_and_missing_days() {
+        assert_eq!(
+            starts(
+                "RRULE:FREQ=MONTHLY;BYDAY=-1FR;COUNT=3",
+                "2025-01-31T08:00",
+                10
+            ),
+            vec!["2025-01-31 Fri", "2025-02-28 Fri", "2025-03-28 Fri"]
+        );
+        // The 31st is skipped in months that do not have one.
+        assert_eq!(
+            starts("FREQ=MONTHLY;COUNT=3", "2025-01-31T08:00", 10),
+            vec!["2025-01-31 Fri", "2025-03-31 Mon", "2025-05-31 Sat"]
+        );
+    }
+
+    #[test]
+    fn until_is_inclusive_and_exdates_still_count() {
+        assert_eq!(
+            starts("FREQ=DAILY;UNTIL=20250604", "2025-06-01T09:00", 10).len(),
+            4
+        );
+        assert_eq!(
+            starts(
+                "FREQ=DAILY;COUNT=3\nEXDATE:20250602T090000",
+                "2025-06-01T09:00",
+                10
+            ),
+            vec!["2025-06-01 Sun", "2025-06-03 Tue"]
+        );
+    }
+
+    #[test]
+    fn rejects_unsupported_rules() {
+        assert!("FREQ=HOURLY".parse::<RecurrenceRule>().is_err());
+        assert!("FREQ=WEEKLY;BYMONTH=3".parse::<RecurrenceRule>().is_err());
+        assert!("FREQ=WEEKLY;BYDAY=2MO".parse::<RecurrenceRule>().is_err());
+        assert!("FREQ=MONTHLY;BYDAY=aÖb".parse::<RecurrenceRule>().is_err());
+        assert!("FREQ=DAILY;COUNT=2;UNTIL=20250101"
+            .parse::<RecurrenceRule>()
+            .is_err());
+        assert!("INTERVAL=2".parse::<RecurrenceRule>().is_err());
+        assert!("FREQ=DAILY;INTERVAL=100000000"
+            .parse::<RecurrenceRule>()
+            .is_err());
+    }
+
+    #[test]
+    fn open_ended_rules_stop_at_the_last_representable_date() {
+        for rule in [
+            "FREQ=YEARLY;INTERVAL=1000",
+            "FREQ=WEEKLY;INTERVAL=1000;BYDAY=MO,FR",
+        ] {
+            let starts = starts(rule, "2025-06-02T09:00", usize::MAX);
+            assert!(!starts.is_empty() && starts.len() < 2 * MAX_PERIODS as usize);
+        }
+    }
+
+    #[test]
+    fn round_trips_through_display() {
+        let text = "FREQ=MONTHLY;INTERVAL=3;BYDAY=1MO,-1FR;COUNT=4\nEXDATE:20250707T090000Z";
+        let rule: RecurrenceRule = text.parse().unwrap();
+        assert_eq!(rule.to_string(), text);
+    }
+
+    #[test]
+    fn utc_series_keeps_local_time_across_dst() {
+        let tz = parse_timezone("Europe/Paris").unwrap();
+        let (start, form) = parse_event_time("2025-03-24T08:00:00Z", tz).unwrap();
+        let rule: RecurrenceRule = "FREQ=WEEKLY;COUNT=2".parse().unwrap();
+        let formatted: Vec<String> = rule
+            .occurrences(start, tz)
+            .map(|local| format_event_time(local, form, tz))
+            .collect();
+        // 09:00 in Paris is 08:00Z in winter and 07:00Z after the switch.
+        assert_eq!(
+            formatted,
+            vec!["2025-03-24T08:00:00Z", "2025-03-31T07:00:00Z"]
+        );
+    }
+
+    #[test]
+    fn split_rules_cover_the_original_series() {
+        let tz = chrono_tz::UTC;
+        let rule: RecurrenceRule = "FREQ=DAILY;COUNT=5\nEXDATE:20250602T090000,20250604T090000"
+            .parse()
+            .unwrap();
+        let split = at("2025-06-03T09:00");
+
+        let before = rule.ending_before(split, TimeForm::Floating, tz);
+        let after = rule.starting_at(split, 2, tz);
+
+        assert_eq!(
+            before.to_string(),
+            "FREQ=DAILY;UNTIL=20250603T085959\nEXDATE:20250602T090000"
+        );
+        assert_eq!(
+            after.to_string(),
+            "FREQ=DAILY;COUNT=3\nEXDATE:20250604T090000"
+        );
+        assert_eq!(before.occurrences(at("2025-06-01T09:00"), tz).count(), 1);
+        assert_eq!(after.occurrences(split, tz).count(), 2);
+    }
+}
@@ -0,0 +1,324 @@
+//! When calendar reminders are due.
+//!
+//! A reminder set `n` minutes before an occurrence is due from `n` minutes
+//! before its start until it starts. Reminders are checked every minute, so
+//! one that came due while the app was closed still fires late, as long as
+//! the occurrence has not started.
+//!
+//! Floating times — stored without an offset in the `UTC` zone, as scheduled
+//! tasks are — are wall-clock times at the shop and are read in its timezone.
+//! All-day events and tasks without a start time are reminded relative to
+//! the start of the working day.
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use chrono_tz::Tz;
+
+use super::recurrence::{local_to_utc, parse_event_time, parse_timezone, TimeForm};
+use super::{CalendarEvent, CalendarTask, CalendarTaskStatus, EventStatus};
+
+/// Start of the working day, for items without a time of day.
+const DAY_START_HOUR: u32 = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReminderSource {
+    Event,
+    Task,
+}
+
+impl ReminderSource {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Event => "event",
+            Self::Task => "task",
+        }
+    }
+}
+
+/// A reminder that is due now.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reminder {
+    pub source: ReminderSource,
+    /// Event id (the series id for a recurring occurrence) or task id.
+    pub source_id: String,
+    pub title: String,
+    pub starts_at: DateTime<Utc>,
+    pub minutes_before: i32,
+    /// The largest lead time set for the occurrence: the reminder customers
+    /// are told about, so they hear once per appointment.
+    pub earliest: bool,
+    /// Who to remind in the app.
+    pub user_id: Option<String>,
+    pub client_id: Option<String>,
+    pub task_id: Option<String>,
+}
+
+impl Reminder {
+    /// Identifies the occurrence in the fire log. A moved occurrence gets a
+    /// new key, so its reminders fire again for the new time.
+    pub fn occurrence_key(&self) -> String {
+        self.starts_at.format("%Y-%m-%dT%H:%M:%SZ").to_string()
+    }
+}
+
+fn day_start(date: NaiveDate) -> NaiveDateTime {
+    date.and_time(NaiveTime::from_hms_opt(DAY_START_HOUR, 0, 0).unwrap_or(NaiveTime::MIN))
+}
+
+/// When an event (or expanded occurrence) starts. `shop_tz` is used for
+/// floating times and for events whose own timezone is invalid.
+pub fn event_start(event: &CalendarEvent, shop_tz: Tz) -> Option<DateTime<Utc>> {
+    let tz = parse_timezone(&event.timezone).unwrap_or(shop_tz);
+    let (local, form) = parse_event_time(&event.start_datetime, tz)?;
+    let tz = match form {
+        TimeForm::Utc => tz,
+        _ if tz == chrono_tz::UTC => shop_tz,
+        _ => tz,
+    };
+    let local = if event.all_day || form == TimeForm::Date {
+        day_start(local.date())
+    } else {
+        local
+    };
+    Some(local_to_utc(local, tz))
+}
+
+/// When a scheduled task starts, in the shop's timezone.
+pub fn task_start(task: &CalendarTask, shop_tz: Tz) -> Option<DateTime<Utc>> {
+    let date = NaiveDate::parse_from_str(task.scheduled_date.trim(), "%Y-%m-%d").ok()?;
+    let local = task
+        .start_time
+        .as_deref()
+        .and_then(|time| {
+            ["%H:%M:%S", "%H:%M"]
+                .iter()
+                .find_map(|format| NaiveTime::parse_from_str(time.trim(), format).ok())
+        })
+        .map(|time| date.and_time(time))
+        .unwrap_or_else(|| day_start(date));
+    Some(local_to_utc(local, shop_tz))
+}
+
+/// The lead times in `minutes` that are due at `now` for an occurrence
+/// starting at `starts_at`, largest first.
+pub fn due_minutes(starts_at: DateTime<Utc>, minutes: &[i32], now: DateTime<Utc>) -> Vec<i32> {
+    if now >= starts_at {
+        return Vec::new();
+    }
+    let mut due: Vec<i32> = minutes
+        .iter()
+        .copied()
+        .filter(|&minutes| minutes >= 0)
+        .filter(|&minutes| starts_at - chrono::Duration::minutes(minutes.into()) <= now)
+        .collect();
+    due.sort_unstable_by(|a, b| b.cmp(a));
+    due.dedup();
+    due
+}
+
+/// Whether a task no longer needs reminding.
+pub fn is_task_closed(status: &CalendarTaskStatus) -> bool {
+    matches!(
+        status,
+        CalendarTaskStatus::Completed
+            | CalendarTaskStatus::Cancelled
+            | CalendarTaskStatus::Archived
+            | CalendarTaskStatus::Failed
+            | CalendarTaskStatus::Invalid
+    )
+}
+
+/// Reminders of `event` due at `now`. The technician is reminded, or the
+/// creator when nobody is assigned.
+pub fn event_reminders(event: &CalendarEvent, shop_tz: Tz, now: DateTime<Utc>) -> Vec<Reminder> {
+    if event.status == EventStatus::Cancelled || event.reminders.is_empty() {
+        return Vec::new();
+    }
+    let Some(starts_at) = event_start(event, shop_tz) else {
+        return Vec::new();
+    };
+    let largest = event.reminders.iter().copied().max();
+    due_minutes(starts_at, &event.reminders, now)
+        .into_iter()
+        .map(|minutes_before| Reminder {
+            source: ReminderSource::Event,
+            source_id: event.id.clone(),
+            title: event.title.clone(),
+            starts_at,
+            minutes_before,
+            earliest: Some(minutes_before) == largest,
+            user_id: event
+                .technician_id
+                .clone()
+                .or_else(|| event.created_by.clone()),
+            client_id: event.client_id.clone(),
+            task_id: event.task_id.clone(),
+        })
+        .collect()
+}
+
+/// Reminders of `task`, `minutes` before it starts, due at `now`.
+pub fn task_reminders(
+    task: &CalendarTask,
+    minutes: &[i32],
+    shop_tz: Tz,
+    now: DateTime<Utc>,
+) -> Vec<Reminder> {
+    if is_task_closed(&task.status) {
+        return Vec::new();
+    }
+    let Some(starts_at) = task_start(task, shop_tz) else {
+        return Vec::new();
+    };
+    let largest = minutes.iter().copied().max();
+    due_minutes(starts_at, minutes, now)
+        .into_iter()
+        .map(|minutes_before| Reminder {
+            source: ReminderSource::Task,
+            source_id: task.id.clone(),
+            title: format!("{} — {}", task.task_number, task.title),
+            starts_at,
+            minutes_before,
+            earliest: Some(minutes_before) == largest,
+            user_id: task.technician_id.clone(),
+            client_id: task.client_id.clone(),
+            task_id: Some(task.id.clone()),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{CalendarTaskPriority, EventType};
+    use super::*;
+    use chrono::TimeZone;
+
+    const PARIS: Tz = chrono_tz::Europe::Paris;
+
+    fn utc(y: i32, m: u32, d: u32, h: u32, min: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, m, d, h, min, 0).unwrap()
+    }
+
+    fn event(start: &str, timezone: &str, reminders: Vec<i32>) -> CalendarEvent {
+        CalendarEvent {
+            id: "evt-1".to_string(),
+            title: "Pose PPF".to_string(),
+            description: None,
+            start_datetime: start.to_string(),
+            end_datetime: start.to_string(),
+            all_day: false,
+            timezone: timezone.to_string(),
+            event_type: EventType::Appointment,
+            category: None,
+            task_id: None,
+            client_id: Some("client-1".to_string()),
+            technician_id: None,
+            location: None,
+            meeting_link: None,
+            is_virtual: false,
+            participants: Vec::new(),
+            is_recurring: false,
+            recurrence_rule: None,
+            parent_event_id: None,
+            reminders,
+            status: EventStatus::Confirmed,
+            color: None,
+            tags: Vec::new(),
+            notes: None,
+            synced: false,
+            last_synced_at: None,
+            created_at: 0,
+            updated_at: 0,
+            created_by: Some("user-1".to_string()),
+            updated_by: None,
+            deleted_at: None,
+            deleted_by: None,
+            ical_uid: None,
+            recurrence_id: None,
+        }
+    }
+
+    fn task(date: &str, start_time: Option<&str>, status: CalendarTaskStatus) -> CalendarTask {
+        CalendarTask {
+            id: "task-1".to_string(),
+            task_number: "T-001".to_string(),
+            title: "Capot".to_string(),
+            status,
+            priority: CalendarTaskPriority::Medium,
+            scheduled_date: date.to_string(),
+            start_time: start_time.map(str::to_string),
+            end_time: None,
+            vehicle_plate: None,
+            vehicle_model: None,
+            technician_id: Some("tech-1".to_string()),
+            technician_name: None,
+            client_id: None,
+            client_name: None,
+            estimated_duration: None,
+            actual_duration: None,
+        }
+    }
+
+    #[test]
+    fn floating_times_are_read_in_the_shop_timezone() {
+        let floating = event("2026-06-10T09:00:00", "UTC", vec![]);
+        assert_eq!(event_start(&floating, PARIS), Some(utc(2026, 6, 10, 7, 0)));
+
+        let zoned = event("2026-06-10T09:00:00", "America/New_York", vec![]);
+        assert_eq!(event_start(&zoned, PARIS), Some(utc(2026, 6, 10, 13, 0)));
+
+        let instant = event("2026-06-10T09:00:00Z", "UTC", vec![]);
+        assert_eq!(event_start(&instant, PARIS), Some(utc(2026, 6, 10, 9, 0)));
+
+        let mut all_day = event("2026-06-10", "UTC", vec![]);
+        all_day.all_day = true;
+        assert_eq!(event_start(&all_day, PARIS), Some(utc(2026, 6, 10, 6, 0)));
+    }
+
+    #[test]
+    fn reminders_are_due_from_their_lead_time_until_the_start() {
+        let start = utc(2026, 6, 10, 9, 0);
+        let minutes = [15, 60, 1440, 60];
+        assert!(due_minutes(start, &minutes, utc(2026, 6, 9, 8, 59)).is_empty());
+        assert_eq!(due_minutes(start, &minutes, utc(2026, 6, 9, 9, 0)), [1440]);
+        assert_eq!(
+            due_minutes(start, &minutes, utc(2026, 6, 10, 8, 50)),
+            [1440, 60, 15]
+        );
+        assert!(due_minutes(start, &minutes, start).is_empty());
+    }
+
+    #[test]
+    fn event_reminders_go_to_the_technician_or_creator() {
+        let mut appointment = event("2026-06-10T09:00:00Z", "UTC", vec![30, 120]);
+        let due = event_reminders(&appointment, PARIS, utc(2026, 6, 10, 8, 45));
+        assert_eq!(due.len(), 2);
+        assert!(due[0].earliest && !due[1].earliest);
+        assert_eq!(due[0].user_id.as_deref(), Some("user-1"));
+        assert_eq!(due[0].occurrence_key(), "2026-06-10T09:00:00Z");
+
+        appointment.technician_id = Some("tech-1".to_string());
+        let due = event_reminders(&appointment, PARIS, utc(2026, 6, 10, 8, 45));
+        assert_eq!(due[0].user_id.as_deref(), Some("tech-1"));
+
+        appointment.status = EventStatus::Cancelled;
+        assert!(event_reminders(&appointment, PARIS, utc(2026, 6, 10, 8, 45)).is_empty());
+    }
+
+    #[test]
+    fn task_reminders_skip_closed_tasks() {
+        let scheduled = task("2026-06-10", Some("14:30"), CalendarTaskStatus::Scheduled);
+        assert_eq!(
+            task_start(&scheduled, PARIS),
+            Some(utc(2026, 6, 10, 12, 30))
+        );
+        let due = task_reminders(&scheduled, &[60], PARIS, utc(2026, 6, 10, 12, 0));
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].task_id.as_deref(), Some("task-1"));
+
+        let untimed = task("2026-06-10", None, CalendarTaskStatus::Assigned);
+        assert_eq!(task_start(&untimed, PARIS), Some(utc(2026, 6, 10, 6, 0)));
+
+        let done = task("2026-06-10", Some("14:30"), CalendarTaskStatus::Completed);
+        assert!(task_reminders(&done, &[60], PARIS, utc(2026, 6, 10, 12, 0)).is_empty());
+    }
+}
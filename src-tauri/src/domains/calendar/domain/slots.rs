@@ -0,0 +1,391 @@
+//! Finding free slots for new bookings.
+//!
+//! A slot is a stretch of a technician's working day long enough for the
+//! booking, overlapping none of their timed bookings, on a day the booking
+//! keeps within their capacity. Each free stretch of a day offers its
+//! earliest start, on a quarter hour.
+//!
+//! Slots are ranked by [`slot_score`]: sooner and on a lighter day is
+//! better, and a technician trained for the intervention type beats one who
+//! does any.
+
+use std::collections::HashMap;
+
+use chrono::{Duration, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+
+use super::availability::TechnicianSchedule;
+use super::DayStatus;
+
+/// Slots start on multiples of this many minutes.
+pub const SLOT_STEP_MINUTES: u32 = 15;
+
+/// A technician's time, as far as planning is concerned.
+#[derive(Debug, Clone)]
+pub struct TechnicianPlan {
+    pub technician_id: String,
+    pub technician_name: String,
+    pub schedule: TechnicianSchedule,
+    /// Intervention types they carry out; empty means any.
+    pub skills: Vec<String>,
+    /// Timed bookings, in shop time.
+    pub busy: Vec<(NaiveDateTime, NaiveDateTime)>,
+    /// Minutes booked per day.
+    pub booked: HashMap<NaiveDate, i32>,
+}
+
+/// A free slot of one technician.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Slot {
+    pub date: NaiveDate,
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+    pub score: i32,
+    /// Minutes booked that day once the slot is taken.
+    pub booked_minutes: i32,
+    pub capacity_minutes: i32,
+}
+
+/// What a booking needs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SlotNeed {
+    pub minutes: i32,
+    /// Any technician can do it when unset.
+    pub intervention_type: Option<String>,
+    /// Only this technician, when the booking is already assigned.
+    pub technician_id: Option<String>,
+}
+
+/// Where slots may be: `from` to `to` inclusive, none starting before
+/// `not_before`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SlotWindow {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub not_before: NaiveDateTime,
+}
+
+/// Why a booking could not be placed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlacementError {
+    NoDuration,
+    NoTechnician,
+    NoSlot,
+}
+
+impl PlacementError {
+    pub fn message(self) -> &'static str {
+        match self {
+            Self::NoDuration => "No estimated duration",
+            Self::NoTechnician => "No technician can take the task",
+            Self::NoSlot => "No free slot in the window",
+        }
+    }
+}
+
+/// 0–100: up to 50 for how early in the window the slot is, up to 40 for
+/// how much of the day's capacity is left once it is taken, and 10 when the
+/// technician is trained for the intervention type.
+pub fn slot_score(
+    day_offset: i64,
+    window_days: i64,
+    booked_after: i32,
+    capacity: i32,
+    specialist: bool,
+) -> i32 {
+    let soon = 1.0 - day_offset as f64 / window_days.max(1) as f64;
+    let free = if capacity > 0 {
+        1.0 - (booked_after as f64 / capacity as f64).min(1.0)
+    } else {
+        0.0
+    };
+    let skill = if specialist { 10.0 } else { 0.0 };
+    (50.0 * soon + 40.0 * free + skill).round() as i32
+}
+
+/// Higher score, then earlier.
+fn best_first(a: &Slot, b: &Slot) -> std::cmp::Ordering {
+    b.score
+        .cmp(&a.score)
+        .then(a.date.cmp(&b.date))
+        .then(a.start.cmp(&b.start))
+}
+
+fn round_up(at: NaiveDateTime) -> NaiveDateTime {
+    let step = SLOT_STEP_MINUTES * 60;
+    let seconds = at.time().num_seconds_from_midnight();
+    let over = seconds % step;
+    if over == 0 && at.nanosecond() == 0 {
+        at
+    } else {
+        at + Duration::seconds((step - over).into()) - Duration::nanoseconds(at.nanosecond().into())
+    }
+}
+
+impl TechnicianPlan {
+    pub fn can_do(&self, intervention_type: Option<&str>) -> bool {
+        intervention_type.is_none()
+            || self.skills.is_empty()
+            || self.is_specialist(intervention_type)
+    }
+
+    fn is_specialist(&self, intervention_type: Option<&str>) -> bool {
+        intervention_type.is_some_and(|wanted| self.skills.iter().any(|skill| skill == wanted))
+    }
+
+    /// Earliest start of each free stretch of `date` that fits `minutes`.
+    fn free_starts(
+        &self,
+        date: NaiveDate,
+        minutes: i32,
+        not_before: NaiveDateTime,
+    ) -> Vec<NaiveDateTime> {
+        let day_start = date.and_time(self.schedule.week.start);
+        let day_end = date.and_time(self.schedule.week.end);
+        let length = Duration::minutes(minutes.into());
+        let mut busy: Vec<_> = self
+            .busy
+            .iter()
+            .copied()
+            .filter(|(start, end)| *start < day_end && *end > day_start)
+            .collect();
+        busy.sort();
+
+        let mut starts = Vec::new();
+        let mut cursor = round_up(day_start.max(not_before));
+        for (start, end) in busy.into_iter().chain([(day_end, day_end)]) {
+            if cursor + length <= start {
+                starts.push(cursor);
+            }
+            if end > cursor {
+                cursor = round_up(end);
+            }
+        }
+        starts
+    }
+
+    /// Free slots for `need` in `window`, best first; none when the
+    /// technician does not carry out its intervention type.
+    pub fn slots(&self, need: &SlotNeed, window: SlotWindow) -> Vec<Slot> {
+        if !self.can_do(need.intervention_type.as_deref()) {
+            return Vec::new();
+        }
+        self.free_slots(need, window)
+    }
+
+    fn free_slots(&self, need: &SlotNeed, window: SlotWindow) -> Vec<Slot> {
+        if need.minutes <= 0 {
+            return Vec::new();
+        }
+        let specialist = self.is_specialist(need.intervention_type.as_deref());
+        let window_days = (window.to - window.from).num_days() + 1;
+        let mut slots = Vec::new();
+        let mut date = window.from;
+        while date <= window.to {
+            let (status, capacity) = self.schedule.day(date);
+            let booked = self.booked.get(&date).copied().unwrap_or(0) + need.minutes;
+            if status == DayStatus::Working && booked <= capacity {
+                let score = slot_score(
+                    (date - window.from).num_days(),
+                    window_days,
+                    booked,
+                    capacity,
+                    specialist,
+                );
+                for start in self.free_starts(date, need.minutes, window.not_before) {
+                    slots.push(Slot {
+                        date,
+                        start: start.time(),
+                        end: (start + Duration::minutes(need.minutes.into())).time(),
+                        score,
+                        booked_minutes: booked,
+                        capacity_minutes: capacity,
+                    });
+                }
+            }
+            date += Duration::days(1);
+        }
+        slots.sort_by(best_first);
+        slots
+    }
+
+    /// Take `slot`, so later searches see the time as booked.
+    pub fn book(&mut self, slot: &Slot) {
+        self.busy
+            .push((slot.date.and_time(slot.start), slot.date.and_time(slot.end)));
+        *self.booked.entry(slot.date).or_default() = slot.booked_minutes;
+    }
+}
+
+/// Place `needs` one after the other, each in the best slot left, and take
+/// that slot. Returns the index of the technician and the slot of each.
+pub fn place_all(
+    plans: &mut [TechnicianPlan],
+    needs: &[SlotNeed],
+    window: SlotWindow,
+) -> Vec<Result<(usize, Slot), PlacementError>> {
+    needs
+        .iter()
+        .map(|need| {
+            if need.minutes <= 0 {
+                return Err(PlacementError::NoDuration);
+            }
+            let candidates: Vec<usize> = plans
+                .iter()
+                .enumerate()
+                .filter(|(_, plan)| match &need.technician_id {
+                    Some(id) => &plan.technician_id == id,
+                    None => plan.can_do(need.intervention_type.as_deref()),
+                })
+                .map(|(index, _)| index)
+                .collect();
+            if candidates.is_empty() {
+                return Err(PlacementError::NoTechnician);
+            }
+            let (index, slot) = candidates
+                .into_iter()
+                // An assigned booking stays with its technician whatever
+                // their skills.
+                .filter_map(|index| {
+                    let slot = plans[index].free_slots(need, window).into_iter().next()?;
+                    Some((index, slot))
+                })
+                .min_by(|(_, a), (_, b)| best_first(a, b))
+                .ok_or(PlacementError::NoSlot)?;
+            plans[index].book(&slot);
+            Ok((index, slot))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::availability::{parse_clock, WorkingWeek};
+    use super::super::PublicHoliday;
+    use super::*;
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    fn at(day: &str, time: &str) -> NaiveDateTime {
+        date(day).and_time(parse_clock(time).unwrap())
+    }
+
+    fn plan(id: &str, skills: &[&str]) -> TechnicianPlan {
+        let weekdays = ["1", "2", "3", "4", "5"].map(str::to_string);
+        TechnicianPlan {
+            technician_id: id.to_string(),
+            technician_name: id.to_uppercase(),
+            schedule: TechnicianSchedule {
+                week: WorkingWeek::parse("08:00", "18:00", &weekdays).unwrap(),
+                daily_capacity_minutes: Some(480),
+                absences: vec![],
+                holidays: vec![PublicHoliday {
+                    date: "2026-06-17".to_string(),
+                    name: "Fermeture".to_string(),
+                }],
+            },
+            skills: skills.iter().map(|skill| skill.to_string()).collect(),
+            busy: vec![],
+            booked: HashMap::new(),
+        }
+    }
+
+    fn need(minutes: i32) -> SlotNeed {
+        SlotNeed {
+            minutes,
+            intervention_type: Some("ppf".to_string()),
+            technician_id: None,
+        }
+    }
+
+    // 2026-06-15 is a Monday.
+    fn window(from: &str, to: &str) -> SlotWindow {
+        SlotWindow {
+            from: date(from),
+            to: date(to),
+            not_before: at("2026-06-01", "00:00"),
+        }
+    }
+
+    #[test]
+    fn free_stretches_skip_busy_time_and_start_on_a_quarter_hour() {
+        let mut tech = plan("tech-1", &[]);
+        tech.busy = vec![
+            (at("2026-06-15", "08:40"), at("2026-06-15", "11:50")),
+            (at("2026-06-15", "13:00"), at("2026-06-15", "15:50")),
+        ];
+        tech.booked.insert(date("2026-06-15"), 340);
+        let slots = tech.slots(&need(60), window("2026-06-15", "2026-06-15"));
+        let starts: Vec<_> = slots.iter().map(|slot| (slot.start, slot.end)).collect();
+        assert_eq!(
+            starts,
+            [
+                (parse_clock("12:00").unwrap(), parse_clock("13:00").unwrap()),
+                (parse_clock("16:00").unwrap(), parse_clock("17:00").unwrap()),
+            ]
+        );
+        assert_eq!(slots[0].booked_minutes, 400);
+    }
+
+    #[test]
+    fn days_off_holidays_full_days_and_the_past_have_no_slots() {
+        let mut tech = plan("tech-1", &[]);
+        tech.booked.insert(date("2026-06-16"), 450);
+        let mut search = window("2026-06-15", "2026-06-21");
+        search.not_before = at("2026-06-15", "17:20");
+        let dates: Vec<_> = tech
+            .slots(&need(60), search)
+            .into_iter()
+            .map(|slot| slot.date)
+            .collect();
+        assert_eq!(dates, [date("2026-06-18"), date("2026-06-19")]);
+    }
+
+    #[test]
+    fn earlier_lighter_days_and_specialists_rank_first() {
+        assert!(slot_score(0, 5, 60, 480, false) > slot_score(1, 5, 60, 480, false));
+        assert!(slot_score(0, 5, 60, 480, false) > slot_score(0, 5, 420, 480, false));
+        assert_eq!(
+            slot_score(0, 5, 60, 480, true),
+            slot_score(0, 5, 60, 480, false) + 10
+        );
+
+        let generalist = plan("tech-1", &[]);
+        let ceramic_only = plan("tech-2", &["ceramic"]);
+        assert!(generalist.can_do(Some("ppf")));
+        assert!(ceramic_only.can_do(None));
+        assert!(ceramic_only
+            .slots(&need(60), window("2026-06-15", "2026-06-15"))
+            .is_empty());
+    }
+
+    #[test]
+    fn placing_books_each_slot_before_the_next() {
+        let mut plans = vec![plan("tech-1", &["ppf"]), plan("tech-2", &["ceramic"])];
+        let needs = vec![
+            need(240),
+            need(240),
+            need(0),
+            SlotNeed {
+                intervention_type: Some("detailing".to_string()),
+                ..need(60)
+            },
+            SlotNeed {
+                technician_id: Some("tech-2".to_string()),
+                ..need(60)
+            },
+        ];
+        let placed = place_all(&mut plans, &needs, window("2026-06-15", "2026-06-15"));
+
+        let (first, second) = (placed[0].clone().unwrap(), placed[1].clone().unwrap());
+        assert_eq!((first.0, second.0), (0, 0));
+        assert_eq!(first.1.start, parse_clock("08:00").unwrap());
+        assert_eq!(second.1.start, parse_clock("12:00").unwrap());
+        assert_eq!(placed[2], Err(PlacementError::NoDuration));
+        assert_eq!(placed[3], Err(PlacementError::NoTechnician));
+        let assigned = placed[4].clone().unwrap();
+        assert_eq!(assigned.0, 1, "An assigned task stays with its technician");
+        assert_eq!(plans[0].booked[&date("2026-06-15")], 480);
+    }
+}
@@ -24,7 +24,7 @@ const EVENT_SELECT: &str = r#"
         event_type, category, task_id, client_id, technician_id, location, meeting_link,
         is_virtual, participants, is_recurring, recurrence_rule, parent_event_id,
         reminders, status, color, tags, notes, synced, last_synced_at,
-        created_at, updated_at, created_by, updated_by, deleted_at, deleted_by
+        created_at, updated_at, created_by, updated_by, deleted_at, deleted_by, ical_uid
     FROM calendar_events
 "#;
 
@@ -149,6 +149,191 @@ pub struct ConflictDetection {
     pub conflict_type: Option<String>,
     pub conflicting_tasks: Vec<CalendarTask>,
     pub message: Option<String>,
+    /// Availability problems that do not block the booking: outside working
+    /// time or over capacity, when the organization only warns about them.
+    #[serde(default)]
+    pub warnings: Vec<String>,
+}
+
+/// Outcome of an iCalendar import.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+pub struct IcsImportSummary {
+    pub total_processed: u32,
+    pub created: u32,
+    pub updated: u32,
+    /// Events already imported, left as they were.
+    pub duplicates_skipped: u32,
+    pub failed: u32,
+    pub errors: Vec<String>,
+}
+
+// ── Technician availability models ───────────────────────────────────────────
+
+/// A technician's own working time, overriding the organization's business
+/// hours and days. Unset fields follow the organization.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+pub struct TechnicianAvailability {
+    pub technician_id: String,
+    /// `HH:MM`.
+    pub work_start: Option<String>,
+    pub work_end: Option<String>,
+    /// ISO weekdays, `"1"` (Monday) to `"7"` (Sunday).
+    pub working_days: Option<Vec<String>>,
+    /// Bookable minutes per working day; the whole working day when unset.
+    pub daily_capacity_minutes: Option<i32>,
+    /// Intervention types the technician carries out (`ppf`, `ceramic`, …).
+    /// Empty means any.
+    #[serde(default)]
+    pub skills: Vec<String>,
+}
+
+/// Days a technician is away, `start_date` to `end_date` inclusive.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct TechnicianAbsence {
+    pub id: String,
+    pub technician_id: String,
+    pub start_date: String,
+    pub end_date: String,
+    /// `leave`, `sick`, `training` or `other`.
+    pub kind: String,
+    pub note: Option<String>,
+    #[ts(type = "number")]
+    pub created_at: i64,
+    pub created_by: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct CreateAbsenceInput {
+    pub technician_id: String,
+    pub start_date: String,
+    pub end_date: String,
+    pub kind: Option<String>,
+    pub note: Option<String>,
+}
+
+/// A day nobody works.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct PublicHoliday {
+    pub date: String,
+    pub name: String,
+}
+
+/// Whether a technician can be booked on a day.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum DayStatus {
+    Working,
+    DayOff,
+    Holiday,
+    Absent,
+}
+
+/// One cell of the capacity heat map.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct DayCapacity {
+    pub date: String,
+    pub status: DayStatus,
+    pub capacity_minutes: i32,
+    pub booked_minutes: i32,
+    /// Booked share of the capacity; `None` when there is no capacity.
+    pub load_percent: Option<i32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct TechnicianWeekCapacity {
+    pub technician_id: String,
+    pub technician_name: String,
+    pub days: Vec<DayCapacity>,
+    pub capacity_minutes: i32,
+    pub booked_minutes: i32,
+}
+
+/// Booked time against capacity, per technician and day, Monday to Sunday.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct CapacityHeatmap {
+    pub week_start: String,
+    pub week_end: String,
+    pub technicians: Vec<TechnicianWeekCapacity>,
+}
+
+// ── Slot finder models ───────────────────────────────────────────────────────
+
+/// What to find slots for: a task, or a duration and intervention type.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+pub struct SlotSearch {
+    /// Task to book; its duration and intervention type are used.
+    pub task_id: Option<String>,
+    /// Minutes, when not searching for a task.
+    pub duration_minutes: Option<i32>,
+    /// `ppf`, `ceramic`, `detailing` or `other`; any technician when unset.
+    pub intervention_type: Option<String>,
+    pub start_date: String,
+    pub end_date: String,
+    /// Technicians to search; all active technicians when unset.
+    pub technician_ids: Option<Vec<String>>,
+    /// Slots per technician, 5 by default.
+    pub limit: Option<i32>,
+}
+
+/// A free slot, scored 0–100.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct SlotCandidate {
+    pub date: String,
+    pub start_time: String,
+    pub end_time: String,
+    pub score: i32,
+    /// Minutes booked that day once the slot is taken.
+    pub booked_minutes: i32,
+    pub capacity_minutes: i32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct TechnicianSlots {
+    pub technician_id: String,
+    pub technician_name: String,
+    /// Best first.
+    pub slots: Vec<SlotCandidate>,
+}
+
+/// Free slots per technician, best technician first.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct SlotSuggestions {
+    pub duration_minutes: i32,
+    pub intervention_type: Option<String>,
+    pub technicians: Vec<TechnicianSlots>,
+}
+
+/// A slot proposed for an unscheduled task.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct ProposedBooking {
+    pub task_id: String,
+    pub task_number: String,
+    pub title: String,
+    pub technician_id: String,
+    pub technician_name: String,
+    pub date: String,
+    pub start_time: String,
+    pub end_time: String,
+    pub duration_minutes: i32,
+    pub score: i32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct UnplacedTask {
+    pub task_id: String,
+    pub task_number: String,
+    pub title: String,
+    pub reason: String,
+}
+
+/// Slots proposed for every unscheduled task, most urgent placed first.
+/// Nothing is booked until the proposals are scheduled.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct ScheduleProposal {
+    pub start_date: String,
+    pub end_date: String,
+    pub proposals: Vec<ProposedBooking>,
+    pub unplaced: Vec<UnplacedTask>,
 }
 
 // ── Calendar event models ─────────────────────────────────────────────────────
@@ -189,6 +374,25 @@ pub struct CalendarEvent {
     pub updated_by: Option<String>,
     pub deleted_at: Option<i64>,
     pub deleted_by: Option<String>,
+    /// UID of the iCalendar event this was imported from.
+    pub ical_uid: Option<String>,
+    /// Set on an occurrence expanded from a recurring series: its original
+    /// start, which identifies it when editing. `id` is the series id.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub recurrence_id: Option<String>,
+}
+
+/// Which part of a recurring series an edit or delete applies to.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum RecurrenceScope {
+    /// Only the chosen occurrence.
+    This,
+    /// The chosen occurrence and every later one.
+    ThisAndFollowing,
+    /// The whole series.
+    All,
 }
 
 /// Calendar event type.
@@ -302,10 +506,22 @@ pub struct CreateEventInput {
     pub color: Option<String>,
     pub tags: Option<Vec<String>>,
     pub notes: Option<String>,
+    /// RFC 5545 RRULE (plus optional EXDATE line) making this a recurring series.
+    #[serde(default)]
+    pub recurrence_rule: Option<String>,
+    /// Series an exception or split-off series was created from. Set by the
+    /// backend only.
+    #[serde(skip)]
+    #[ts(skip)]
+    pub parent_event_id: Option<String>,
+    /// UID of the iCalendar event being imported. Set by the backend only.
+    #[serde(skip)]
+    #[ts(skip)]
+    pub ical_uid: Option<String>,
 }
 
 /// Input for updating a calendar event.
-#[derive(Debug, Clone, Deserialize, TS)]
+#[derive(Debug, Clone, Default, Deserialize, TS)]
 #[serde(rename_all = "camelCase")]
 pub struct UpdateEventInput {
     pub title: Option<String>,
@@ -327,6 +543,9 @@ pub struct UpdateEventInput {
     pub color: Option<String>,
     pub tags: Option<Vec<String>>,
     pub notes: Option<String>,
+    /// New recurrence rule; an empty string stops the event recurring.
+    #[serde(default)]
+    pub recurrence_rule: Option<String>,
 }
 
 impl FromSqlRow for CalendarEvent {
@@ -379,6 +598,8 @@ impl FromSqlRow for CalendarEvent {
             updated_by: row.get(29)?,
             deleted_at: row.get(30)?,
             deleted_by: row.get(31)?,
+            ical_uid: row.get(32)?,
+            recurrence_id: None,
         })
     }
 }
@@ -46,6 +46,20 @@ pub struct ClientStat {
     pub total_tasks: i32,
 }
 
+// ── ClientUpsert ──────────────────────────────────────────────────────────────
+
+/// Result of [`ClientService::upsert_by_email`].
+#[derive(Debug, Clone)]
+pub enum ClientUpsert {
+    Created(Client),
+    Updated(Client),
+    /// The email belongs to a client someone else created. Only its id is
+    /// disclosed to the caller.
+    NotOwned {
+        id: String,
+    },
+}
+
 // ── ClientService ─────────────────────────────────────────────────────────────
 
 /// Application-layer service for client business operations.
@@ -307,6 +321,55 @@ impl ClientService {
         Ok(client)
     }
 
+    /// Create a client, or update the one with the same email.
+    ///
+    /// Used by inbound integrations. An existing client is only updated when
+    /// `user_id` created it; a client someone else manages is left untouched
+    /// and reported by id only.
+    pub async fn upsert_by_email(
+        &self,
+        req: CreateClientRequest,
+        user_id: &str,
+    ) -> Result<ClientUpsert, String> {
+        let existing = match req.email.as_deref() {
+            Some(email) => self
+                .client_repo
+                .find_by_email(email)
+                .await
+                .map_err(|e| format!("Failed to look up client by email: {}", e))?,
+            None => None,
+        };
+        let Some(existing) = existing else {
+            return self
+                .create_client(req, user_id)
+                .await
+                .map(ClientUpsert::Created);
+        };
+        if existing.created_by.as_deref() != Some(user_id) {
+            return Ok(ClientUpsert::NotOwned { id: existing.id });
+        }
+        let update = UpdateClientRequest {
+            id: existing.id.clone(),
+            name: Some(req.name),
+            email: req.email,
+            phone: req.phone,
+            customer_type: Some(req.customer_type),
+            address_street: req.address_street,
+            address_city: req.address_city,
+            address_state: req.address_state,
+            address_zip: req.address_zip,
+            address_country: req.address_country,
+            tax_id: req.tax_id,
+            company_name: req.company_name,
+            contact_person: req.contact_person,
+            notes: req.notes,
+            tags: req.tags,
+        };
+        self.update_client(update, user_id)
+            .await
+            .map(ClientUpsert::Updated)
+    }
+
     /// Soft-delete a client.
     ///
     /// Publishes `DomainEvent::ClientDeactivated` on success (ADR-017).
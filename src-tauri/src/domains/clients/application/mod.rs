@@ -7,6 +7,6 @@ pub mod client_statistics_service;
 pub mod client_validation_service;
 
 pub use client_orchestrator::ClientOrchestrator;
-pub use client_service::ClientService;
+pub use client_service::{ClientService, ClientUpsert};
 pub use client_statistics_service::ClientStatisticsService;
 pub use client_validation_service::ClientValidationService;
@@ -1,7 +1,7 @@
 use crate::db::Database;
 use crate::domains::clients::application::client_input_validator::validate_client_id;
 use crate::domains::clients::application::client_orchestrator::client_into_client_with_tasks;
-use crate::domains::clients::application::ClientService;
+use crate::domains::clients::application::{ClientService, ClientUpsert};
 use crate::domains::clients::domain::models::{
     Client, CreateClientRequest, CustomerType, UpdateClientRequest,
 };
@@ -342,3 +342,67 @@ async fn test_delete_client_rejects_non_owner() {
         "You can only delete clients you created"
     );
 }
+
+#[tokio::test]
+async fn test_upsert_by_email_discloses_only_the_id_of_another_users_client() {
+    let db = Arc::new(Database::new_in_memory().await.expect("in-memory database"));
+    let event_bus = Arc::new(InMemoryEventBus::new());
+    let cache = Arc::new(crate::shared::repositories::cache::Cache::new(256));
+    let repo = Arc::new(
+        crate::domains::clients::infrastructure::client_repository::SqliteClientRepository::new(
+            db, cache,
+        ),
+    );
+    let service = ClientService::new(repo, event_bus);
+    let request = |name: &str| CreateClientRequest {
+        name: name.to_string(),
+        email: Some("shared@example.com".to_string()),
+        phone: None,
+        customer_type: CustomerType::Individual,
+        address_street: None,
+        address_city: None,
+        address_state: None,
+        address_zip: None,
+        address_country: None,
+        tax_id: None,
+        company_name: None,
+        contact_person: None,
+        notes: None,
+        tags: None,
+    };
+
+    let created = match service
+        .upsert_by_email(request("Original"), "integration-a")
+        .await
+        .expect("upsert must succeed")
+    {
+        ClientUpsert::Created(client) => client,
+        other => panic!("expected a created client, got {:?}", other),
+    };
+    let updated = service
+        .upsert_by_email(request("Renamed by owner"), "integration-a")
+        .await
+        .expect("upsert must succeed");
+    let foreign = service
+        .upsert_by_email(request("Renamed by stranger"), "integration-b")
+        .await
+        .expect("upsert must succeed");
+
+    assert!(matches!(
+        updated,
+        ClientUpsert::Updated(ref client) if client.name == "Renamed by owner"
+    ));
+    match foreign {
+        ClientUpsert::NotOwned { id } => assert_eq!(id, created.id),
+        other => panic!(
+            "expected only the id of the existing client, got {:?}",
+            other
+        ),
+    }
+    let stored = service
+        .get_client(&created.id)
+        .await
+        .expect("client lookup must succeed")
+        .expect("client must exist");
+    assert_eq!(stored.name, "Renamed by owner");
+}
@@ -1,7 +1,8 @@
 //! Infrastructure layer for the documents domain (ADR-001, ADR-005).
 //!
-//! SQLite repositories and file-system storage implementations.
+//! SQLite repositories, file-system storage and S3-compatible object storage.
 
+pub mod object_storage;
 pub mod photo_processing;
 pub mod photo_service;
 pub mod report_repository;
@@ -0,0 +1,491 @@
+//! S3-compatible object storage client (AWS Signature Version 4).
+//!
+//! Used by `PhotoService` when the photo storage type is `cloud` or `hybrid`.
+//! Works against AWS S3, Google Cloud Storage (XML interoperability API with
+//! HMAC keys) and any S3-compatible endpoint such as MinIO.
+
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use reqwest::{Client, Method, StatusCode};
+use sha2::{Digest, Sha256};
+
+use crate::domains::documents::{CloudProvider, PhotoError, PhotoResult};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Objects larger than this are uploaded with the multipart API.
+pub const MULTIPART_THRESHOLD: usize = 8 * 1024 * 1024;
+/// Size of each multipart chunk (S3 requires at least 5 MiB except for the last part).
+pub const MULTIPART_PART_SIZE: usize = 5 * 1024 * 1024;
+
+const EMPTY_PAYLOAD_SHA256: &str =
+    "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+
+/// Connection settings for an S3-compatible bucket.
+#[derive(Debug, Clone)]
+pub struct ObjectStorageConfig {
+    pub provider: CloudProvider,
+    pub bucket: String,
+    pub region: String,
+    /// Custom endpoint (e.g. `http://127.0.0.1:9000` for MinIO). When set,
+    /// path-style addressing is used.
+    pub endpoint: Option<String>,
+    pub access_key: String,
+    pub secret_key: String,
+}
+
+/// Minimal S3 client covering the operations needed for photo storage.
+#[derive(Clone)]
+pub struct S3ObjectStore {
+    config: ObjectStorageConfig,
+    http_client: Client,
+}
+
+impl std::fmt::Debug for S3ObjectStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("S3ObjectStore")
+            .field("provider", &self.config.provider)
+            .field("bucket", &self.config.bucket)
+            .field("region", &self.config.region)
+            .field("endpoint", &self.config.endpoint)
+            .finish()
+    }
+}
+
+struct SignedRequest {
+    url: String,
+    headers: Vec<(String, String)>,
+}
+
+impl S3ObjectStore {
+    pub fn new(config: ObjectStorageConfig) -> PhotoResult<Self> {
+        if config.access_key.trim().is_empty() || config.secret_key.trim().is_empty() {
+            return Err(PhotoError::Validation(
+                "Cloud access key and secret key are required".to_string(),
+            ));
+        }
+        if matches!(config.provider, CloudProvider::AzureBlob) && config.endpoint.is_none() {
+            return Err(PhotoError::Validation(
+                "Azure Blob storage requires an S3-compatible gateway endpoint".to_string(),
+            ));
+        }
+        Ok(Self {
+            config,
+            http_client: Client::new(),
+        })
+    }
+
+    /// Canonical `s3://bucket/key` URL stored in `photos.storage_url`.
+    pub fn object_url(&self, key: &str) -> String {
+        format!("s3://{}/{}", self.config.bucket, key)
+    }
+
+    /// Extract the object key from an `s3://bucket/key` URL belonging to this bucket.
+    pub fn key_from_url<'a>(&self, url: &'a str) -> Option<&'a str> {
+        url.strip_prefix("s3://")?
+            .strip_prefix(self.config.bucket.as_str())?
+            .strip_prefix('/')
+    }
+
+    /// Upload an object, switching to multipart upload for large payloads.
+    pub async fn put_object(&self, key: &str, data: &[u8], content_type: &str) -> PhotoResult<()> {
+        if data.len() > MULTIPART_THRESHOLD {
+            return self.put_object_multipart(key, data, content_type).await;
+        }
+        let response = self
+            .send(
+                Method::PUT,
+                key,
+                &[],
+                vec![("content-type".to_string(), content_type.to_string())],
+                data.to_vec(),
+            )
+            .await?;
+        Self::ensure_success(response, "PutObject").await?;
+        Ok(())
+    }
+
+    pub async fn get_object(&self, key: &str) -> PhotoResult<Vec<u8>> {
+        let response = self.send(Method::GET, key, &[], vec![], vec![]).await?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Err(PhotoError::NotFound(format!(
+                "Object {} not found in bucket {}",
+                key, self.config.bucket
+            )));
+        }
+        let response = Self::ensure_success(response, "GetObject").await?;
+        let bytes = response
+            .bytes()
+            .await
+            .map_err(|e| PhotoError::Storage(format!("GetObject body read failed: {}", e)))?;
+        Ok(bytes.to_vec())
+    }
+
+    pub async fn delete_object(&self, key: &str) -> PhotoResult<()> {
+        let response = self.send(Method::DELETE, key, &[], vec![], vec![]).await?;
+        Self::ensure_success(response, "DeleteObject").await?;
+        Ok(())
+    }
+
+    async fn put_object_multipart(
+        &self,
+        key: &str,
+        data: &[u8],
+        content_type: &str,
+    ) -> PhotoResult<()> {
+        let response = self
+            .send(
+                Method::POST,
+                key,
+                &[("uploads", "")],
+                vec![("content-type".to_string(), content_type.to_string())],
+                vec![],
+            )
+            .await?;
+        let body = Self::ensure_success(response, "CreateMultipartUpload")
+            .await?
+            .text()
+            .await
+            .map_err(|e| PhotoError::Storage(e.to_string()))?;
+        let upload_id = extract_xml_tag(&body, "UploadId").ok_or_else(|| {
+            PhotoError::Storage("CreateMultipartUpload response has no UploadId".to_string())
+        })?;
+
+        match self.upload_parts(key, &upload_id, data).await {
+            Ok(etags) => self.complete_multipart(key, &upload_id, &etags).await,
+            Err(error) => {
+                if let Err(abort_error) = self
+                    .send(
+                        Method::DELETE,
+                        key,
+                        &[("uploadId", upload_id.as_str())],
+                        vec![],
+                        vec![],
+                    )
+                    .await
+                {
+                    tracing::warn!("AbortMultipartUpload failed: {}", abort_error);
+                }
+                Err(error)
+            }
+        }
+    }
+
+    async fn upload_parts(
+        &self,
+        key: &str,
+        upload_id: &str,
+        data: &[u8],
+    ) -> PhotoResult<Vec<String>> {
+        let mut etags = Vec::new();
+        for (index, chunk) in data.chunks(MULTIPART_PART_SIZE).enumerate() {
+            let part_number = (index + 1).to_string();
+            let response = self
+                .send(
+                    Method::PUT,
+                    key,
+                    &[
+                        ("partNumber", part_number.as_str()),
+                        ("uploadId", upload_id),
+                    ],
+                    vec![],
+                    chunk.to_vec(),
+                )
+                .await?;
+            let response = Self::ensure_success(response, "UploadPart").await?;
+            let etag = response
+                .headers()
+                .get("etag")
+                .and_then(|value| value.to_str().ok())
+                .ok_or_else(|| PhotoError::Storage("UploadPart response has no ETag".to_string()))?
+                .to_string();
+            etags.push(etag);
+        }
+        Ok(etags)
+    }
+
+    async fn complete_multipart(
+        &self,
+        key: &str,
+        upload_id: &str,
+        etags: &[String],
+    ) -> PhotoResult<()> {
+        let mut xml = String::from("<CompleteMultipartUpload>");
+        for (index, etag) in etags.iter().enumerate() {
+            xml.push_str(&format!(
+                "<Part><PartNumber>{}</PartNumber><ETag>{}</ETag></Part>",
+                index + 1,
+                etag
+            ));
+        }
+        xml.push_str("</CompleteMultipartUpload>");
+
+        let response = self
+            .send(
+                Method::POST,
+                key,
+                &[("uploadId", upload_id)],
+                vec![("content-type".to_string(), "application/xml".to_string())],
+                xml.into_bytes(),
+            )
+            .await?;
+        let body = Self::ensure_success(response, "CompleteMultipartUpload")
+            .await?
+            .text()
+            .await
+            .map_err(|e| PhotoError::Storage(e.to_string()))?;
+        // S3 may answer 200 with an <Error> document when completion fails late.
+        if body.contains("<Error>") {
+            return Err(PhotoError::Storage(format!(
+                "CompleteMultipartUpload failed: {}",
+                extract_xml_tag(&body, "Message").unwrap_or(body)
+            )));
+        }
+        Ok(())
+    }
+
+    async fn send(
+        &self,
+        method: Method,
+        key: &str,
+        query: &[(&str, &str)],
+        headers: Vec<(String, String)>,
+        body: Vec<u8>,
+    ) -> PhotoResult<reqwest::Response> {
+        let signed = self.sign(method.as_str(), key, query, headers, &body, Utc::now());
+        let mut request = self.http_client.request(method, &signed.url);
+        for (name, value) in signed.headers {
+            if name != "host" {
+                request = request.header(name, value);
+            }
+        }
+        request
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| PhotoError::Storage(format!("Object storage request failed: {}", e)))
+    }
+
+    async fn ensure_success(
+        response: reqwest::Response,
+        operation: &str,
+    ) -> PhotoResult<reqwest::Response> {
+        if response.status().is_success() {
+            return Ok(response);
+        }
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        let detail = extract_xml_tag(&body, "Message")
+            .or_else(|| extract_xml_tag(&body, "Code"))
+            .unwrap_or_default();
+        Err(PhotoError::Storage(format!(
+            "{} failed with HTTP {} {}",
+            operation, status, detail
+        )))
+    }
+
+    fn host_and_base_path(&self) -> (String, String, String) {
+        match &self.config.endpoint {
+            Some(endpoint) => {
+                let endpoint = endpoint.trim_end_matches('/');
+                let (scheme, host) = endpoint.split_once("://").unwrap_or(("https", endpoint));
+                (
+                    scheme.to_string(),
+                    signing_host(scheme, host).to_string(),
+                    format!("/{}", self.config.bucket),
+                )
+            }
+            None => match self.config.provider {
+                CloudProvider::GcpStorage => (
+                    "https".to_string(),
+                    "storage.googleapis.com".to_string(),
+                    format!("/{}", self.config.bucket),
+                ),
+                CloudProvider::AwsS3 | CloudProvider::AzureBlob => (
+                    "https".to_string(),
+                    format!(
+                        "{}.s3.{}.amazonaws.com",
+                        self.config.bucket, self.config.region
+                    ),
+                    String::new(),
+                ),
+            },
+        }
+    }
+
+    fn sign(
+        &self,
+        method: &str,
+        key: &str,
+        query: &[(&str, &str)],
+        extra_headers: Vec<(String, String)>,
+        body: &[u8],
+        at: DateTime<Utc>,
+    ) -> SignedRequest {
+        let (scheme, host, base_path) = self.host_and_base_path();
+        let canonical_uri = format!("{}/{}", base_path, uri_encode(key, false));
+        let payload_hash = if body.is_empty() {
+            EMPTY_PAYLOAD_SHA256.to_string()
+        } else {
+            format!("{:x}", Sha256::digest(body))
+        };
+
+        let mut headers = extra_headers;
+        headers.push(("host".to_string(), host.clone()));
+        headers.push(("x-amz-content-sha256".to_string(), payload_hash.clone()));
+        headers.push((
+            "x-amz-date".to_string(),
+            at.format("%Y%m%dT%H%M%SZ").to_string(),
+        ));
+
+        let authorization = sigv4_authorization(
+            &SigningParams {
+                access_key: &self.config.access_key,
+                secret_key: &self.config.secret_key,
+                region: &self.config.region,
+                service: "s3",
+                at,
+            },
+            method,
+            &canonical_uri,
+            query,
+            &headers,
+            &payload_hash,
+        );
+        headers.push(("authorization".to_string(), authorization));
+
+        let query_string = canonical_query_string(query);
+        let url = if query_string.is_empty() {
+            format!("{}://{}{}", scheme, host, canonical_uri)
+        } else {
+            format!("{}://{}{}?{}", scheme, host, canonical_uri, query_string)
+        };
+        SignedRequest { url, headers }
+    }
+}
+
+/// The `host` to sign for an endpoint authority. Clients send the Host
+/// header without the scheme's default port, and the server checks the
+/// signature against what it received, so `:443`/`:80` are dropped.
+pub(crate) fn signing_host<'a>(scheme: &str, authority: &'a str) -> &'a str {
+    match authority.rsplit_once(':') {
+        Some((host, "443")) if scheme.eq_ignore_ascii_case("https") => host,
+        Some((host, "80")) if scheme.eq_ignore_ascii_case("http") => host,
+        _ => authority,
+    }
+}
+
+pub(crate) struct SigningParams<'a> {
+    pub access_key: &'a str,
+    pub secret_key: &'a str,
+    pub region: &'a str,
+    pub service: &'a str,
+    pub at: DateTime<Utc>,
+}
+
+/// Build the SigV4 `Authorization` header value for a request.
+///
+/// `headers` must contain every header to sign (including `host` and
+/// `x-amz-date`); names are lower-cased and sorted here.
+pub(crate) fn sigv4_authorization(
+    params: &SigningParams<'_>,
+    method: &str,
+    canonical_uri: &str,
+    query: &[(&str, &str)],
+    headers: &[(String, String)],
+    payload_hash: &str,
+) -> String {
+    let amz_date = params.at.format("%Y%m%dT%H%M%SZ").to_string();
+    let date = params.at.format("%Y%m%d").to_string();
+
+    let mut sorted_headers: Vec<(String, String)> = headers
+        .iter()
+        .map(|(name, value)| (name.to_lowercase(), value.trim().to_string()))
+        .collect();
+    sorted_headers.sort_by(|a, b| a.0.cmp(&b.0));
+    let canonical_headers: String = sorted_headers
+        .iter()
+        .map(|(name, value)| format!("{}:{}\n", name, value))
+        .collect();
+    let signed_headers = sorted_headers
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .collect::<Vec<_>>()
+        .join(";");
+
+    let canonical_request = format!(
+        "{}\n{}\n{}\n{}\n{}\n{}",
+        method,
+        canonical_uri,
+        canonical_query_string(query),
+        canonical_headers,
+        signed_headers,
+        payload_hash
+    );
+    let scope = format!("{}/{}/{}/aws4_request", date, params.region, params.service);
+    let string_to_sign = format!(
+        "AWS4-HMAC-SHA256\n{}\n{}\n{:x}",
+        amz_date,
+        scope,
+        Sha256::digest(canonical_request.as_bytes())
+    );
+
+    let k_date = hmac_sha256(
+        format!("AWS4{}", params.secret_key).as_bytes(),
+        date.as_bytes(),
+    );
+    let k_region = hmac_sha256(&k_date, params.region.as_bytes());
+    let k_service = hmac_sha256(&k_region, params.service.as_bytes());
+    let k_signing = hmac_sha256(&k_service, b"aws4_request");
+    let signature = hmac_sha256(&k_signing, string_to_sign.as_bytes())
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect::<String>();
+
+    format!(
+        "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+        params.access_key, scope, signed_headers, signature
+    )
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn canonical_query_string(query: &[(&str, &str)]) -> String {
+    let mut pairs: Vec<(String, String)> = query
+        .iter()
+        .map(|(name, value)| (uri_encode(name, true), uri_encode(value, true)))
+        .collect();
+    pairs.sort();
+    pairs
+        .iter()
+        .map(|(name, value)| format!("{}={}", name, value))
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+/// RFC 3986 encoding as required by SigV4. `/` is kept as-is in object keys.
+fn uri_encode(input: &str, encode_slash: bool) -> String {
+    let mut encoded = String::with_capacity(input.len());
+    for byte in input.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            b'/' if !encode_slash => encoded.push('/'),
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+fn extract_xml_tag(xml: &str, tag: &str) -> Option<String> {
+    let open = format!("<{}>", tag);
+    let close = format!("</{}>", tag);
+    let start = xml.find(&open)? + open.len();
+    let end = xml[start..].find(&close)? + start;
+    Some(xml[start..end].to_string())
+}
@@ -4,7 +4,7 @@ use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tokio::fs;
 use tokio::sync::Semaphore;
-use tracing::warn;
+use tracing::{info, warn};
 
 use crate::db::Database;
 use crate::domains::documents::models::Photo;
@@ -15,12 +15,14 @@ use crate::domains::documents::{
 };
 use crate::shared::contracts::common::now;
 
+use super::object_storage::{ObjectStorageConfig, S3ObjectStore};
 use super::photo_processing;
 
 #[derive(Debug)]
 pub struct PhotoService {
     db: Database,
     storage_provider: StorageProvider,
+    object_store: Option<S3ObjectStore>,
     local_storage_path: PathBuf,
     processing_semaphore: Arc<Semaphore>,
     jpeg_quality: u8,
@@ -30,11 +32,13 @@ pub struct PhotoService {
 impl PhotoService {
     pub fn new(db: Database, settings: &PhotoStorageSettings) -> PhotoResult<Self> {
         let storage_provider = Self::create_storage_provider(settings)?;
+        let object_store = Self::create_object_store(&storage_provider, settings)?;
         let local_storage_path = Self::get_local_storage_path(settings)?;
 
         Ok(Self {
             db,
             storage_provider,
+            object_store,
             local_storage_path,
             processing_semaphore: Arc::new(Semaphore::new(4)),
             jpeg_quality: 80,
@@ -82,6 +86,46 @@ impl PhotoService {
         }
     }
 
+    fn create_object_store(
+        storage_provider: &StorageProvider,
+        settings: &PhotoStorageSettings,
+    ) -> PhotoResult<Option<S3ObjectStore>> {
+        let (provider, bucket, region) = match storage_provider {
+            StorageProvider::Local => return Ok(None),
+            StorageProvider::Cloud {
+                provider,
+                bucket,
+                region,
+            } => (provider, bucket, region),
+            StorageProvider::Hybrid {
+                cloud_provider,
+                bucket,
+                region,
+                ..
+            } => (cloud_provider, bucket, region),
+        };
+        let access_key = settings
+            .cloud_access_key
+            .clone()
+            .ok_or_else(|| PhotoError::Validation("Cloud access key not configured".to_string()))?;
+        let secret_key = settings
+            .cloud_secret_key
+            .clone()
+            .ok_or_else(|| PhotoError::Validation("Cloud secret key not configured".to_string()))?;
+        let store = S3ObjectStore::new(ObjectStorageConfig {
+            provider: provider.clone(),
+            bucket: bucket.clone(),
+            region: region.clone(),
+            endpoint: settings
+                .cloud_endpoint
+                .clone()
+                .filter(|endpoint| !endpoint.trim().is_empty()),
+            access_key,
+            secret_key,
+        })?;
+        Ok(Some(store))
+    }
+
     fn parse_cloud_provider(settings: &PhotoStorageSettings) -> PhotoResult<CloudProvider> {
         match settings.cloud_provider.as_deref() {
             Some("aws_s3") => Ok(CloudProvider::AwsS3),
@@ -156,31 +200,15 @@ impl PhotoService {
                     Some(format!("file://{}", file_path.display())),
                 )
             }
-            StorageProvider::Cloud {
-                provider,
-                bucket,
-                region,
-            } => {
-                let storage_url = self
-                    .store_in_cloud(
-                        provider,
-                        bucket,
-                        region,
-                        &request.intervention_id,
-                        &request.file_name,
-                        &compressed_data,
-                    )
-                    .await?;
+            StorageProvider::Cloud { .. } => {
+                // Keep a cached copy so the photo survives an offline upload failure
+                // and can be served without a round-trip.
                 let file_path =
                     self.generate_local_cache_path(&request.intervention_id, &request.file_name);
-                (file_path, Some(storage_url))
+                self.write_file_atomic(&file_path, &compressed_data).await?;
+                (file_path, None)
             }
-            StorageProvider::Hybrid {
-                local_path,
-                cloud_provider,
-                bucket,
-                region,
-            } => {
+            StorageProvider::Hybrid { local_path, .. } => {
                 let file_path = self
                     .store_locally_with_path(
                         local_path,
@@ -189,17 +217,7 @@ impl PhotoService {
                         &compressed_data,
                     )
                     .await?;
-                let storage_url = self
-                    .store_in_cloud(
-                        cloud_provider,
-                        bucket,
-                        region,
-                        &request.intervention_id,
-                        &request.file_name,
-                        &compressed_data,
-                    )
-                    .await?;
-                (file_path, Some(storage_url))
+                (file_path, None)
             }
         };
 
@@ -240,6 +258,30 @@ impl PhotoService {
         photo.is_required = request.is_required;
         photo.storage_url = storage_url;
 
+        if self.object_store.is_some() {
+            let object_key = Self::object_key(&request.intervention_id, &request.file_name);
+            match self
+                .store_in_cloud(&object_key, &compressed_data, &request.mime_type)
+                .await
+            {
+                Ok(url) => {
+                    photo.storage_url = Some(url);
+                    photo.synced = true;
+                    photo.uploaded_at = Some(now());
+                    photo.last_synced_at = photo.uploaded_at;
+                }
+                Err(e) => {
+                    warn!(
+                        "Cloud upload failed for {}, kept local copy for retry: {}",
+                        object_key, e
+                    );
+                    photo.synced = false;
+                    photo.upload_retry_count = 1;
+                    photo.upload_error = Some(e.to_string());
+                }
+            }
+        }
+
         self.save_photo_record(&photo)?;
 
         if let Err(e) = self.generate_thumbnail(&compressed_data, &file_path).await {
@@ -293,21 +335,21 @@ impl PhotoService {
 
     async fn store_in_cloud(
         &self,
-        provider: &CloudProvider,
-        _bucket: &str,
-        _region: &str,
-        intervention_id: &str,
-        file_name: &str,
+        object_key: &str,
         data: &[u8],
+        content_type: &str,
     ) -> PhotoResult<String> {
-        match provider {
-            CloudProvider::AwsS3 | CloudProvider::GcpStorage | CloudProvider::AzureBlob => {
-                let file_path = self.store_locally(intervention_id, file_name, data).await?;
-                let url = format!("file://{}", file_path.display());
-                warn!("Cloud upload not implemented, stored locally: {}", url);
-                Ok(url)
-            }
-        }
+        let store = self
+            .object_store
+            .as_ref()
+            .ok_or_else(|| PhotoError::Storage("Cloud storage is not configured".to_string()))?;
+        store.put_object(object_key, data, content_type).await?;
+        Ok(store.object_url(object_key))
+    }
+
+    /// Object key layout mirrors the local `<intervention>/photos/<file>` tree.
+    fn object_key(intervention_id: &str, file_name: &str) -> String {
+        format!("{}/photos/{}", intervention_id, file_name)
     }
 
     pub fn generate_file_path(&self, intervention_id: &str, file_name: &str) -> PathBuf {
@@ -385,13 +427,20 @@ impl PhotoService {
             .query_single_as::<Photo>("SELECT * FROM photos WHERE id = ?", params![id])?)
     }
 
-    pub fn delete_photo(&self, id: &str) -> PhotoResult<()> {
+    pub async fn delete_photo(&self, id: &str) -> PhotoResult<()> {
         let photo = self
             .get_photo(id)?
             .ok_or_else(|| PhotoError::NotFound(format!("Photo {} not found", id)))?;
         if Path::new(&photo.file_path).exists() {
             let _ = std::fs::remove_file(&photo.file_path);
         }
+        if let (Some(store), Some(url)) = (&self.object_store, photo.storage_url.as_deref()) {
+            if let Some(key) = store.key_from_url(url) {
+                if let Err(e) = store.delete_object(key).await {
+                    warn!("Failed to delete cloud object {}: {}", key, e);
+                }
+            }
+        }
         self.db
             .execute("DELETE FROM photos WHERE id = ?", params![id])?;
         Ok(())
@@ -501,16 +550,40 @@ impl PhotoService {
         })
     }
 
-    pub fn read_photo_data(&self, id: &str) -> PhotoResult<Vec<u8>> {
+    /// Read photo bytes, downloading from cloud storage into the local cache
+    /// when the file is no longer present on disk.
+    pub async fn read_photo_data(&self, id: &str) -> PhotoResult<Vec<u8>> {
         let photo = self
             .get_photo(id)?
             .ok_or_else(|| PhotoError::NotFound(format!("Photo {} not found", id)))?;
-        if !Path::new(&photo.file_path).exists() {
+        if Path::new(&photo.file_path).exists() {
+            return fs::read(&photo.file_path).await.map_err(PhotoError::Io);
+        }
+
+        let remote_key = match (&self.object_store, photo.storage_url.as_deref()) {
+            (Some(store), Some(url)) => store.key_from_url(url).map(|key| (store, key)),
+            _ => None,
+        };
+        let Some((store, key)) = remote_key else {
             return Err(PhotoError::NotFound(
                 "Photo file not found on disk".to_string(),
             ));
+        };
+
+        let data = store.get_object(key).await?;
+        let cache_path = self.generate_local_cache_path(
+            &photo.intervention_id,
+            photo.file_name.as_deref().unwrap_or(&photo.id),
+        );
+        if let Err(e) = self.write_file_atomic(&cache_path, &data).await {
+            warn!("Failed to cache downloaded photo {}: {}", photo.id, e);
+        } else if cache_path != Path::new(&photo.file_path) {
+            self.db.execute(
+                "UPDATE photos SET file_path = ?, updated_at = ? WHERE id = ?",
+                params![cache_path.to_string_lossy().to_string(), now(), photo.id],
+            )?;
         }
-        std::fs::read(&photo.file_path).map_err(PhotoError::Io)
+        Ok(data)
     }
 
     pub async fn upload_photo_with_retry(
@@ -526,20 +599,10 @@ impl PhotoService {
         }
         let mut retry_count = photo.upload_retry_count as u32;
         while retry_count < max_retries {
-            match self.attempt_upload().await {
-                Ok(_) => {
-                    photo.synced = true;
-                    photo.uploaded_at = Some(now());
-                    photo.upload_retry_count = 0;
-                    photo.upload_error = None;
-                    self.save_photo_record(&photo)?;
-                    return Ok(());
-                }
-                Err(e) => {
-                    retry_count += 1;
-                    photo.upload_retry_count = retry_count as i32;
-                    photo.upload_error = Some(e.to_string());
-                    self.save_photo_record(&photo)?;
+            match self.attempt_upload(&mut photo).await {
+                Ok(_) => return Ok(()),
+                Err(_) => {
+                    retry_count = photo.upload_retry_count as u32;
                     let delay = 2u64.pow(retry_count.min(5));
                     tokio::time::sleep(tokio::time::Duration::from_secs(delay)).await;
                 }
@@ -551,12 +614,64 @@ impl PhotoService {
         )))
     }
 
-    async fn attempt_upload(&self) -> PhotoResult<()> {
-        use rand::Rng;
-        if rand::thread_rng().gen::<f64>() < 0.7 {
-            Ok(())
-        } else {
-            Err(PhotoError::Storage("Simulated upload failure".to_string()))
+    /// Make one upload attempt for each unsynced photo still below `max_retries`.
+    ///
+    /// Intended for the periodic background sync; returns the number of photos
+    /// uploaded successfully.
+    pub async fn sync_pending_uploads(&self, limit: usize, max_retries: u32) -> PhotoResult<usize> {
+        if self.object_store.is_none() {
+            return Ok(0);
+        }
+        let pending = self.db.query_as::<Photo>(
+            "SELECT * FROM photos WHERE synced = 0 AND upload_retry_count < ? ORDER BY created_at ASC LIMIT ?",
+            params![max_retries, limit as i64],
+        )?;
+        let mut uploaded = 0usize;
+        for mut photo in pending {
+            if self.attempt_upload(&mut photo).await.is_ok() {
+                uploaded += 1;
+            }
+        }
+        if uploaded > 0 {
+            info!(
+                "Synced {} pending photo upload(s) to cloud storage",
+                uploaded
+            );
+        }
+        Ok(uploaded)
+    }
+
+    /// Upload the local copy of `photo` and persist the outcome on the record.
+    async fn attempt_upload(&self, photo: &mut Photo) -> PhotoResult<()> {
+        let result = match fs::read(&photo.file_path).await {
+            Ok(data) => {
+                let file_name = photo.file_name.clone().unwrap_or_else(|| photo.id.clone());
+                let object_key = Self::object_key(&photo.intervention_id, &file_name);
+                let content_type = photo.mime_type.clone();
+                self.store_in_cloud(&object_key, &data, &content_type).await
+            }
+            Err(e) => Err(PhotoError::Io(e)),
+        };
+        let timestamp = now();
+        match result {
+            Ok(url) => {
+                photo.synced = true;
+                photo.storage_url = Some(url);
+                photo.uploaded_at = Some(timestamp);
+                photo.last_synced_at = Some(timestamp);
+                photo.upload_retry_count = 0;
+                photo.upload_error = None;
+                photo.updated_at = timestamp;
+                self.save_photo_record(photo)?;
+                Ok(())
+            }
+            Err(e) => {
+                photo.upload_retry_count += 1;
+                photo.upload_error = Some(e.to_string());
+                photo.updated_at = timestamp;
+                self.save_photo_record(photo)?;
+                Err(e)
+            }
         }
     }
 }
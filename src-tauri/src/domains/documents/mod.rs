@@ -15,6 +15,7 @@ pub mod photo_types;
 pub mod report_export;
 pub mod report_handler;
 pub mod report_pdf;
+pub mod report_pdf_native;
 pub mod report_template;
 pub mod report_view_model;
 
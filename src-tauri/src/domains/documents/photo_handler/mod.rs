@@ -11,6 +11,8 @@ use crate::domains::documents::models::{InterventionReportResult, Photo};
 pub use crate::domains::documents::photo_types::*;
 use crate::resolve_context;
 use crate::shared::contracts::auth::{UserRole, UserSession};
+use crate::shared::event_bus::publish_event;
+use crate::shared::services::event_bus::event_factory;
 
 #[derive(Debug)]
 pub struct DocumentsFacade {
@@ -126,6 +128,7 @@ impl DocumentsFacade {
             DocumentsCommand::DeletePhoto { photo_id } => {
                 self.photo_service
                     .delete_photo(&photo_id)
+                    .await
                     .map_err(|e| AppError::Internal(e.to_string()))?;
                 Ok(DocumentsResponse::Unit)
             }
@@ -133,6 +136,7 @@ impl DocumentsFacade {
                 let data = self
                     .photo_service
                     .read_photo_data(&photo_id)
+                    .await
                     .map_err(|e| AppError::Internal(e.to_string()))?;
                 Ok(DocumentsResponse::PhotoData(data))
             }
@@ -226,6 +230,14 @@ pub async fn document_store_photo(
     {
         DocumentsResponse::StorePhoto(res) => {
             info!(photo_id = %res.photo.id, "Photo stored");
+            publish_event(event_factory::photo_uploaded_with_ctx(
+                res.photo.id.clone(),
+                res.photo.intervention_id.clone(),
+                res.photo.step_id.clone(),
+                res.photo.step_number,
+                ctx.user_id().to_string(),
+                ctx.correlation_id.clone(),
+            ));
             Ok(ApiResponse::success(res).with_correlation_id(Some(ctx.correlation_id)))
         }
         _ => Err(AppError::Internal("Unexpected response".into())),
@@ -115,6 +115,10 @@ pub struct PhotoStorageSettings {
     pub cloud_provider: Option<String>,
     pub cloud_bucket: Option<String>,
     pub cloud_region: Option<String>,
+    /// Custom S3-compatible endpoint (MinIO, gateways). `None` uses the provider default.
+    pub cloud_endpoint: Option<String>,
+    pub cloud_access_key: Option<String>,
+    pub cloud_secret_key: Option<String>,
 }
 
 impl Default for PhotoStorageSettings {
@@ -125,6 +129,9 @@ impl Default for PhotoStorageSettings {
             cloud_provider: None,
             cloud_bucket: None,
             cloud_region: None,
+            cloud_endpoint: None,
+            cloud_access_key: None,
+            cloud_secret_key: None,
         }
     }
 }
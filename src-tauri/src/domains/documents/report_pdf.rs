@@ -4,17 +4,45 @@
 //! self-contained HTML file, then converted to PDF by a headless Chromium browser
 //! (Edge on Windows, Chrome on Linux/macOS) via [`headless_chrome`].
 //!
+//! When no browser starts, or the browser pipeline fails, the pure-Rust renderer
+//! ([`super::report_pdf_native::render_report_pdf`]) draws the same layout instead.
+//!
 //! The view model is built by [`build_intervention_report_view_model`] before rendering.
 
 use crate::commands::{AppError, AppResult};
 use crate::shared::services::cross_domain::{Client, InterventionStep, Photo};
 
+use super::report_pdf_native::render_report_pdf;
 use super::report_template::render_report_html;
-use super::report_view_model::build_intervention_report_view_model;
+use super::report_view_model::{build_intervention_report_view_model, ReportViewModel};
 
 use headless_chrome::{Browser, LaunchOptions};
 use std::path::Path;
 
+/// Backend used to turn the report view model into a PDF.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportRenderer {
+    /// HTML template printed by headless Chrome/Edge.
+    Chromium,
+    /// Pure-Rust `printpdf` renderer, no browser required.
+    Native,
+}
+
+impl ReportRenderer {
+    /// Chromium when a Chrome/Edge browser actually launches, the native renderer otherwise.
+    ///
+    /// An executable that is found but cannot start (sandbox, missing libraries)
+    /// selects the native renderer too.
+    pub fn detect() -> Self {
+        if InterventionPdfReport::browser_available() {
+            Self::Chromium
+        } else {
+            tracing::info!("No headless browser could be launched, using native PDF renderer");
+            Self::Native
+        }
+    }
+}
+
 /// Comprehensive PDF report generator for PPF interventions.
 pub struct InterventionPdfReport {
     intervention: crate::shared::services::cross_domain::Intervention,
@@ -42,32 +70,74 @@ impl InterventionPdfReport {
         }
     }
 
-    /// Generate the PDF report and write it to `output_path`.
+    /// Generate the PDF report and write it to `output_path`, with the
+    /// renderer picked by [`ReportRenderer::detect`].
+    ///
+    /// A failed Chromium run is retried once with the native renderer.
+    pub async fn generate(&self, output_path: &Path) -> AppResult<()> {
+        let renderer = ReportRenderer::detect();
+        match self.generate_with(renderer, output_path).await {
+            Err(e) if renderer == ReportRenderer::Chromium => {
+                tracing::warn!(
+                    "Headless browser PDF generation failed ({}), retrying with native renderer",
+                    e
+                );
+                self.generate_with(ReportRenderer::Native, output_path)
+                    .await
+            }
+            result => result,
+        }
+    }
+
+    /// Generate the PDF report with an explicit renderer.
     ///
-    /// Pipeline:
+    /// Chromium pipeline:
     /// 1. Build `ReportViewModel` from raw data.
     /// 2. Render HTML template (inline CSS, no external resources).
     /// 3. Write HTML to a temp file.
     /// 4. Launch headless browser (Edge / Chrome), navigate to the temp file.
     /// 5. Print to PDF and write bytes to `output_path`.
     /// 6. Delete the temp HTML file.
-    pub async fn generate(&self, output_path: &Path) -> AppResult<()> {
+    ///
+    /// The native pipeline draws the view model directly and writes the bytes.
+    pub async fn generate_with(
+        &self,
+        renderer: ReportRenderer,
+        output_path: &Path,
+    ) -> AppResult<()> {
         tracing::info!(
-            "Starting PDF generation for intervention: {}",
-            self.intervention.id
+            "Starting PDF generation for intervention: {} ({:?})",
+            self.intervention.id,
+            renderer
         );
 
         // 1. Build view model
-        let vm = build_intervention_report_view_model(
+        let vm = self.view_model();
+
+        match renderer {
+            ReportRenderer::Chromium => self.generate_chromium(&vm, output_path),
+            ReportRenderer::Native => {
+                let pdf_bytes = render_report_pdf(&vm)?;
+                Self::write_pdf(output_path, pdf_bytes)?;
+                tracing::info!("Successfully generated PDF report at {:?}", output_path);
+                Ok(())
+            }
+        }
+    }
+
+    fn view_model(&self) -> ReportViewModel {
+        build_intervention_report_view_model(
             &self.intervention,
             &self.steps,
             &self.photos,
             &self.materials,
             self.client.as_ref(),
-        );
+        )
+    }
 
+    fn generate_chromium(&self, vm: &ReportViewModel, output_path: &Path) -> AppResult<()> {
         // 2. Render HTML
-        let html = render_report_html(&vm);
+        let html = render_report_html(vm);
 
         // 3. Write HTML to temp file — use a unique suffix to avoid collisions in parallel tests
         let unique_id = uuid::Uuid::new_v4().to_string();
@@ -121,22 +191,26 @@ impl InterventionPdfReport {
             .print_to_pdf(None)
             .map_err(|e| AppError::Internal(format!("PDF print failed: {}", e)))?;
 
-        // Write output PDF
+        Self::write_pdf(output_path, pdf_bytes)?;
+
+        tracing::info!("Successfully generated PDF report at {:?}", output_path);
+        Ok(())
+    }
+
+    fn write_pdf(output_path: &Path, pdf_bytes: Vec<u8>) -> AppResult<()> {
         if let Some(parent) = output_path.parent() {
             std::fs::create_dir_all(parent).map_err(|e| {
                 AppError::Internal(format!("Failed to create output directory: {}", e))
             })?;
         }
         std::fs::write(output_path, pdf_bytes)
-            .map_err(|e| AppError::Internal(format!("Failed to write PDF: {}", e)))?;
-
-        tracing::info!("Successfully generated PDF report at {:?}", output_path);
-        Ok(())
+            .map_err(|e| AppError::Internal(format!("Failed to write PDF: {}", e)))
     }
 
-    /// Returns `true` if a headless browser (Chrome or Edge) can be found on this system.
+    /// Returns `true` if a headless browser (Chrome or Edge) can be launched on this system.
     ///
-    /// Used in tests to skip PDF-generation tests in CI environments without a browser.
+    /// Used by [`ReportRenderer::detect`], and in tests to skip PDF-generation
+    /// tests in CI environments without a browser.
     pub fn browser_available() -> bool {
         let opts = LaunchOptions::default_builder().build();
         match opts {
@@ -158,6 +232,7 @@ mod tests {
     use crate::shared::services::cross_domain::{
         Intervention, InterventionStatus, InterventionStep,
     };
+    use base64::{engine::general_purpose, Engine as _};
     use serde_json::json;
 
     fn build_test_intervention() -> Intervention {
@@ -391,6 +466,203 @@ mod tests {
         assert!(data.get("quality_scores").is_some());
     }
 
+    fn test_png(width: u32, height: u32) -> Vec<u8> {
+        let mut bytes = std::io::Cursor::new(Vec::new());
+        image::RgbaImage::from_pixel(width, height, image::Rgba([30, 58, 95, 255]))
+            .write_to(&mut bytes, image::ImageFormat::Png)
+            .unwrap();
+        bytes.into_inner()
+    }
+
+    /// Structural outline of a PDF: per page, every text drawn at 10pt or
+    /// more (titles, section and step headings) and every image, in order.
+    fn pdf_outline(bytes: &[u8]) -> Vec<Vec<String>> {
+        use printpdf::lopdf::{content::Content, Document, Object};
+
+        let doc = Document::load_mem(bytes).expect("native renderer must emit a valid PDF");
+        doc.get_pages()
+            .into_values()
+            .map(|page_id| {
+                let content = Content::decode(&doc.get_page_content(page_id).unwrap()).unwrap();
+                let mut size = 0.0;
+                let mut items = Vec::new();
+                for op in content.operations {
+                    match op.operator.as_str() {
+                        "Tf" => size = op.operands[1].as_float().unwrap(),
+                        "Tj" => {
+                            if let Object::String(text, _) = &op.operands[0] {
+                                let text = Document::decode_text(Some("WinAnsiEncoding"), text);
+                                if size >= 10.0 || text.starts_with("Page ") {
+                                    items.push(text);
+                                }
+                            }
+                        }
+                        "Do" => items.push("[image]".to_string()),
+                        _ => {}
+                    }
+                }
+                items
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_native_renderer_snapshot() {
+        let tmp_dir = std::env::temp_dir().join("rpma_test_pdf");
+        std::fs::create_dir_all(&tmp_dir).unwrap();
+        let photo_path = tmp_dir.join("native_snapshot_photo.png");
+        std::fs::write(&photo_path, test_png(640, 480)).unwrap();
+
+        let mut photo = Photo::new(
+            "test-intervention-001".to_string(),
+            photo_path.to_string_lossy().to_string(),
+        );
+        photo.step_number = Some(1);
+        photo.title = Some("Capot".to_string());
+        let missing = Photo::new(
+            "test-intervention-001".to_string(),
+            tmp_dir.join("missing.png").to_string_lossy().to_string(),
+        );
+
+        let mut intervention = build_test_intervention();
+        intervention.customer_signature = Some(format!(
+            "data:image/png;base64,{}",
+            general_purpose::STANDARD.encode(test_png(300, 100))
+        ));
+
+        let vm = build_intervention_report_view_model(
+            &intervention,
+            &build_test_steps(),
+            &[photo, missing],
+            &[],
+            None,
+        );
+        let bytes = render_report_pdf(&vm).unwrap();
+        let _ = std::fs::remove_file(&photo_path);
+
+        let pages = pdf_outline(&bytes);
+        let total = pages.len();
+        for (index, page) in pages.iter().enumerate() {
+            assert_eq!(
+                page.last(),
+                Some(&format!("Page {} / {}", index + 1, total)),
+                "every page ends with its footer"
+            );
+        }
+
+        let outline: Vec<String> = pages
+            .into_iter()
+            .flatten()
+            .filter(|item| !item.starts_with("Page "))
+            .collect();
+        pretty_assertions::assert_eq!(
+            outline,
+            vec![
+                "RPMA",
+                "RAPPORT D'INTERVENTION PPF",
+                "RÉSUMÉ DE L'INTERVENTION",
+                "CLIENT & VÉHICULE",
+                "CONDITIONS DE TRAVAIL",
+                "MATÉRIAUX",
+                "ÉTAPES DU WORKFLOW",
+                "Étape 1 — Inspection  [Terminé]",
+                "Étape 2 — Preparation  [En attente]",
+                "Étape 3 — Installation  [Terminé]",
+                "Étape 4 — Finalisation  [Terminé]",
+                "CONTRÔLE QUALITÉ",
+                "PHOTOS (2)",
+                "[image]",
+                "VALIDATION CLIENT",
+                "[image]",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_native_renderer_without_photos_or_signature() {
+        let mut intervention = build_test_intervention();
+        intervention.customer_signature = Some("not-an-image".to_string());
+        intervention.weather_condition = None;
+        intervention.lighting_condition = None;
+        intervention.work_location = None;
+        intervention.temperature_celsius = None;
+        intervention.humidity_percentage = None;
+
+        let vm = build_intervention_report_view_model(&intervention, &[], &[], &[], None);
+        let bytes = render_report_pdf(&vm).unwrap();
+
+        let pages = pdf_outline(&bytes);
+        pretty_assertions::assert_eq!(
+            pages,
+            vec![vec![
+                "RPMA",
+                "RAPPORT D'INTERVENTION PPF",
+                "RÉSUMÉ DE L'INTERVENTION",
+                "CLIENT & VÉHICULE",
+                "MATÉRIAUX",
+                "CONTRÔLE QUALITÉ",
+                "VALIDATION CLIENT",
+                "Page 1 / 1",
+            ]]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_generate_with_native_renderer_writes_pdf() {
+        let report = InterventionPdfReport::new(
+            build_test_intervention(),
+            build_test_steps(),
+            Vec::new(),
+            Vec::new(),
+            None,
+        );
+
+        let tmp_dir = std::env::temp_dir().join("rpma_test_pdf");
+        std::fs::create_dir_all(&tmp_dir).unwrap();
+        let output_path = tmp_dir.join("test_native_report.pdf");
+
+        let result = report
+            .generate_with(ReportRenderer::Native, &output_path)
+            .await;
+        assert!(
+            result.is_ok(),
+            "Native PDF generation failed: {:?}",
+            result.err()
+        );
+
+        let bytes = std::fs::read(&output_path).unwrap();
+        assert!(bytes.starts_with(b"%PDF-"));
+
+        let _ = std::fs::remove_file(&output_path);
+    }
+
+    #[tokio::test]
+    async fn test_generate_uses_native_renderer_without_browser() {
+        if InterventionPdfReport::browser_available() {
+            eprintln!("Skipping native fallback test: headless browser available");
+            return;
+        }
+        assert_eq!(ReportRenderer::detect(), ReportRenderer::Native);
+
+        let report = InterventionPdfReport::new(
+            build_test_intervention(),
+            build_test_steps(),
+            Vec::new(),
+            Vec::new(),
+            None,
+        );
+        let tmp_dir = std::env::temp_dir().join("rpma_test_pdf");
+        std::fs::create_dir_all(&tmp_dir).unwrap();
+        let output_path = tmp_dir.join("test_fallback_report.pdf");
+
+        let result = report.generate(&output_path).await;
+        assert!(result.is_ok(), "PDF generation failed: {:?}", result.err());
+        let bytes = std::fs::read(&output_path).unwrap();
+        assert!(bytes.starts_with(b"%PDF-"));
+
+        let _ = std::fs::remove_file(&output_path);
+    }
+
     #[tokio::test]
     async fn test_generate_full_report_no_crash() {
         if !InterventionPdfReport::browser_available() {
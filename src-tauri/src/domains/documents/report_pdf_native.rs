@@ -0,0 +1,825 @@
+//! Pure-Rust PDF renderer for PPF intervention reports.
+//!
+//! Draws the same sections as [`super::report_template::render_report_html`]
+//! directly with [`printpdf`] and the PDF base-14 Helvetica fonts, so a report
+//! can be produced on machines without Chrome/Edge. No font files or external
+//! resources are needed; photos are read from disk and embedded as thumbnails.
+//!
+//! [`super::report_pdf::InterventionPdfReport::generate`] selects this renderer
+//! automatically when no headless browser is installed.
+
+use crate::commands::{AppError, AppResult};
+use base64::{engine::general_purpose, Engine as _};
+use printpdf::path::PaintMode;
+use printpdf::{
+    BuiltinFont, Color, ColorBits, ColorSpace, Image, ImageTransform, ImageXObject,
+    IndirectFontRef, Line, Mm, PdfDocument, PdfDocumentReference, PdfLayerReference, Point, Px,
+    Rect, Rgb,
+};
+
+use super::report_view_model::{severity_label, ReportStep, ReportViewModel};
+
+// ---------------------------------------------------------------------------
+// Page geometry (millimetres, origin bottom-left)
+// ---------------------------------------------------------------------------
+
+const PAGE_WIDTH: f32 = 210.0;
+const PAGE_HEIGHT: f32 = 297.0;
+const MARGIN_X: f32 = 15.0;
+const CONTENT_WIDTH: f32 = PAGE_WIDTH - 2.0 * MARGIN_X;
+const TOP_Y: f32 = PAGE_HEIGHT - 15.0;
+const BOTTOM_Y: f32 = 20.0;
+const KEY_COLUMN_WIDTH: f32 = 60.0;
+
+const BODY_SIZE: f32 = 9.5;
+const SMALL_SIZE: f32 = 8.0;
+const SECTION_SIZE: f32 = 11.0;
+
+/// Photos are laid out three per row, each scaled to fit this box.
+const PHOTOS_PER_ROW: usize = 3;
+const PHOTO_BOX_HEIGHT: f32 = 42.0;
+/// Longest side of an embedded photo thumbnail, in pixels.
+const THUMBNAIL_PX: u32 = 480;
+const SIGNATURE_BOX: (f32, f32) = (70.0, 30.0);
+
+const NAVY: (f32, f32, f32) = (0.118, 0.227, 0.373);
+const LIGHT_BLUE: (f32, f32, f32) = (0.290, 0.620, 1.0);
+const PALE: (f32, f32, f32) = (0.941, 0.957, 0.976);
+const GREY: (f32, f32, f32) = (0.420, 0.447, 0.502);
+const TEXT: (f32, f32, f32) = (0.102, 0.102, 0.102);
+const WHITE: (f32, f32, f32) = (1.0, 1.0, 1.0);
+
+// ---------------------------------------------------------------------------
+// Public entry point
+// ---------------------------------------------------------------------------
+
+/// Render the report view model to PDF bytes without a browser.
+pub fn render_report_pdf(vm: &ReportViewModel) -> AppResult<Vec<u8>> {
+    let mut pdf = PdfCanvas::new(&vm.meta.report_title)?;
+
+    push_header(&mut pdf, vm);
+    push_summary(&mut pdf, vm);
+    push_client_vehicle(&mut pdf, vm);
+    push_work_conditions(&mut pdf, vm);
+    push_materials(&mut pdf, vm);
+    push_workflow_steps(&mut pdf, vm);
+    push_quality(&mut pdf, vm);
+    push_photos(&mut pdf, vm);
+    push_customer_validation(&mut pdf, vm);
+
+    pdf.finish(&format!(
+        "Généré le {} · Intervention {} · RPMA Application",
+        vm.meta.generated_at, vm.meta.intervention_id
+    ))
+}
+
+// ---------------------------------------------------------------------------
+// Section renderers
+// ---------------------------------------------------------------------------
+
+fn push_header(pdf: &mut PdfCanvas, vm: &ReportViewModel) {
+    pdf.fill_rect(0.0, PAGE_HEIGHT - 34.0, PAGE_WIDTH, 34.0, NAVY);
+    pdf.text_at(
+        "RPMA",
+        MARGIN_X,
+        PAGE_HEIGHT - 13.0,
+        20.0,
+        Font::Bold,
+        LIGHT_BLUE,
+    );
+    pdf.text_at(
+        "RAPPORT D'INTERVENTION PPF",
+        MARGIN_X,
+        PAGE_HEIGHT - 21.0,
+        14.0,
+        Font::Bold,
+        WHITE,
+    );
+
+    let mut meta = format!("Intervention : {}", vm.meta.intervention_id);
+    if vm.meta.task_number != vm.display.placeholder_not_specified {
+        meta.push_str(&format!("  |  Tâche : {}", vm.meta.task_number));
+    }
+    meta.push_str(&format!("  |  Généré le {}", vm.meta.generated_at));
+    pdf.text_at(
+        &meta,
+        MARGIN_X,
+        PAGE_HEIGHT - 28.0,
+        SMALL_SIZE,
+        Font::Regular,
+        (0.722, 0.831, 0.941),
+    );
+    pdf.y = PAGE_HEIGHT - 42.0;
+}
+
+fn push_summary(pdf: &mut PdfCanvas, vm: &ReportViewModel) {
+    pdf.section_title("Résumé de l'intervention");
+    pdf.kv("Statut", &vm.summary.status);
+    pdf.kv("Technicien", &vm.summary.technician_name);
+    pdf.kv("Type", &vm.summary.intervention_type);
+    pdf.kv("Durée estimée", &vm.summary.estimated_duration);
+    pdf.kv("Durée réelle", &vm.summary.actual_duration);
+    pdf.kv(
+        "Progression",
+        &format!("{:.1}%", vm.summary.completion_percentage),
+    );
+}
+
+fn push_client_vehicle(pdf: &mut PdfCanvas, vm: &ReportViewModel) {
+    pdf.section_title("Client & Véhicule");
+
+    pdf.sub_title("Client");
+    pdf.kv("Nom", &vm.client.name);
+    pdf.kv("Email", &vm.client.email);
+    pdf.kv("Téléphone", &vm.client.phone);
+
+    // Same rule as the HTML template (BUG-2): only print what is known.
+    let ns = &vm.display.placeholder_not_specified;
+    let known = [&vm.vehicle.make, &vm.vehicle.model]
+        .into_iter()
+        .filter(|v| *v != ns)
+        .map(String::as_str)
+        .collect::<Vec<_>>();
+    let make_model = if known.is_empty() {
+        ns.clone()
+    } else {
+        known.join(" ")
+    };
+
+    pdf.sub_title("Véhicule");
+    pdf.kv("Plaque", &vm.vehicle.plate);
+    pdf.kv("Marque / Modèle", &make_model);
+    pdf.kv("Année", &vm.vehicle.year);
+    pdf.kv("Couleur", &vm.vehicle.color);
+    pdf.kv("VIN", &vm.vehicle.vin);
+}
+
+fn push_work_conditions(pdf: &mut PdfCanvas, vm: &ReportViewModel) {
+    let wc = &vm.work_conditions;
+    if [
+        &wc.weather,
+        &wc.lighting,
+        &wc.location,
+        &wc.temperature,
+        &wc.humidity,
+    ]
+    .iter()
+    .all(|v| *v == &vm.display.placeholder_not_specified)
+    {
+        return;
+    }
+    pdf.section_title("Conditions de travail");
+    pdf.kv("Météo", &wc.weather);
+    pdf.kv("Éclairage", &wc.lighting);
+    pdf.kv("Lieu", &wc.location);
+    pdf.kv("Température", &wc.temperature);
+    pdf.kv("Humidité", &wc.humidity);
+}
+
+fn push_materials(pdf: &mut PdfCanvas, vm: &ReportViewModel) {
+    let m = &vm.materials;
+    pdf.section_title("Matériaux");
+    pdf.kv("Type de film", &m.film_type);
+    pdf.kv("Marque", &m.film_brand);
+    pdf.kv("Modèle", &m.film_model);
+
+    if !m.consumptions.is_empty() {
+        let rows = m
+            .consumptions
+            .iter()
+            .map(|c| {
+                vec![
+                    c.material_id.clone(),
+                    format!("{:.3}", c.quantity_used),
+                    c.total_cost.clone(),
+                    format!("{:.3}", c.waste_quantity),
+                    c.quality_notes.clone(),
+                ]
+            })
+            .collect::<Vec<_>>();
+        pdf.table(
+            &[
+                ("Réf. matériau", 45.0),
+                ("Qté utilisée", 25.0),
+                ("Coût total", 25.0),
+                ("Déchets", 20.0),
+                ("Notes qualité", 65.0),
+            ],
+            &rows,
+        );
+    }
+}
+
+fn push_workflow_steps(pdf: &mut PdfCanvas, vm: &ReportViewModel) {
+    if vm.steps.is_empty() {
+        return;
+    }
+    pdf.section_title("Étapes du workflow");
+    for step in &vm.steps {
+        push_step(pdf, step, &vm.display.placeholder_not_specified);
+    }
+}
+
+fn push_step(pdf: &mut PdfCanvas, step: &ReportStep, placeholder_ns: &str) {
+    pdf.ensure_space(20.0);
+    pdf.fill_rect(MARGIN_X, pdf.y - 7.0, CONTENT_WIDTH, 7.0, PALE);
+    let title = format!("Étape {} — {}  [{}]", step.number, step.title, step.status);
+    pdf.text_at(&title, MARGIN_X + 2.0, pdf.y - 5.0, 10.0, Font::Bold, NAVY);
+    pdf.y -= 10.0;
+
+    if step.started_at != placeholder_ns && !step.started_at.is_empty() {
+        pdf.kv("Début", &step.started_at);
+    }
+    if step.completed_at != placeholder_ns && !step.completed_at.is_empty() {
+        pdf.kv("Fin", &step.completed_at);
+    }
+    if step.duration != placeholder_ns && !step.duration.is_empty() {
+        pdf.kv("Durée", &step.duration);
+    }
+    if step.photo_count > 0 {
+        pdf.kv("Photos", &step.photo_count.to_string());
+    }
+    if step.quality_score != "Non evalue" && !step.quality_score.is_empty() {
+        pdf.kv("Score qualité", &step.quality_score);
+    }
+
+    if !step.notes.is_empty() && step.notes != "Aucune observation" {
+        pdf.paragraph(&step.notes, BODY_SIZE, Font::Italic, GREY);
+    }
+
+    if !step.checklist.is_empty() {
+        pdf.sub_title("Liste de contrôle");
+        let rows = step
+            .checklist
+            .iter()
+            .map(|item| {
+                let mark = if item.checked { "[x]" } else { "[ ]" };
+                vec![mark.to_string(), item.label.clone()]
+            })
+            .collect::<Vec<_>>();
+        pdf.table(&[("", 12.0), ("Élément", 168.0)], &rows);
+    }
+
+    if !step.defects.is_empty() {
+        pdf.sub_title("Défauts détectés");
+        let rows = step
+            .defects
+            .iter()
+            .map(|d| {
+                vec![
+                    d.zone.clone(),
+                    d.defect_type.clone(),
+                    severity_label(&d.severity),
+                    d.notes.clone(),
+                ]
+            })
+            .collect::<Vec<_>>();
+        pdf.table(
+            &[
+                ("Zone", 40.0),
+                ("Type", 40.0),
+                ("Sévérité", 25.0),
+                ("Notes", 75.0),
+            ],
+            &rows,
+        );
+    }
+
+    if !step.zones.is_empty() {
+        pdf.sub_title("Zones PPF");
+        let rows = step
+            .zones
+            .iter()
+            .map(|z| {
+                let name = if z.name.is_empty() { &z.id } else { &z.name };
+                vec![
+                    name.clone(),
+                    z.quality_score
+                        .map(|s| format!("{:.1} / 10", s))
+                        .unwrap_or_default(),
+                    z.status.clone(),
+                ]
+            })
+            .collect::<Vec<_>>();
+        pdf.table(
+            &[("Zone", 80.0), ("Score qualité", 40.0), ("Statut", 60.0)],
+            &rows,
+        );
+    }
+
+    if !step.environment.is_empty() {
+        pdf.sub_title("Environnement");
+        for kv in &step.environment {
+            pdf.kv(&kv.key, &kv.value);
+        }
+    }
+
+    if !step.observations.is_empty() {
+        pdf.sub_title("Observations");
+        for obs in &step.observations {
+            pdf.paragraph(&format!("• {}", obs), BODY_SIZE, Font::Regular, TEXT);
+        }
+    }
+
+    if step.approval_data.approved_by != placeholder_ns
+        && !step.approval_data.approved_by.is_empty()
+    {
+        pdf.paragraph(
+            &format!(
+                "Validé par {} le {}",
+                step.approval_data.approved_by, step.approval_data.approved_at
+            ),
+            SMALL_SIZE,
+            Font::Regular,
+            GREY,
+        );
+    }
+    if !step.approval_data.rejection_reason.is_empty() {
+        pdf.paragraph(
+            &format!("Motif rejet : {}", step.approval_data.rejection_reason),
+            SMALL_SIZE,
+            Font::Regular,
+            (0.863, 0.149, 0.149),
+        );
+    }
+    pdf.y -= 3.0;
+}
+
+fn push_quality(pdf: &mut PdfCanvas, vm: &ReportViewModel) {
+    pdf.section_title("Contrôle qualité");
+    pdf.kv("Score global", &vm.quality.global_quality_score);
+
+    if !vm.quality.checkpoints.is_empty() {
+        let rows = vm
+            .quality
+            .checkpoints
+            .iter()
+            .map(|cp| {
+                vec![
+                    cp.step_name.clone(),
+                    cp.step_status.clone(),
+                    cp.score.clone(),
+                ]
+            })
+            .collect::<Vec<_>>();
+        pdf.table(&[("Étape", 80.0), ("Statut", 60.0), ("Score", 40.0)], &rows);
+    }
+
+    if !vm.quality.final_observations.is_empty() {
+        pdf.sub_title("Observations finales");
+        for obs in &vm.quality.final_observations {
+            pdf.paragraph(&format!("• {}", obs), BODY_SIZE, Font::Regular, TEXT);
+        }
+    }
+}
+
+fn push_photos(pdf: &mut PdfCanvas, vm: &ReportViewModel) {
+    if vm.photos.items.is_empty() {
+        return;
+    }
+    pdf.section_title(&format!("Photos ({})", vm.photos.total_count));
+
+    let cell_width = CONTENT_WIDTH / PHOTOS_PER_ROW as f32;
+    let row_height = PHOTO_BOX_HEIGHT + 10.0;
+    for row in vm.photos.items.chunks(PHOTOS_PER_ROW) {
+        pdf.ensure_space(row_height);
+        let top = pdf.y;
+        for (column, photo) in row.iter().enumerate() {
+            let x = MARGIN_X + column as f32 * cell_width;
+            let box_width = cell_width - 4.0;
+            match load_thumbnail(&photo.file_path) {
+                Some(thumbnail) => pdf.image_in_box(
+                    thumbnail,
+                    x,
+                    top - PHOTO_BOX_HEIGHT,
+                    box_width,
+                    PHOTO_BOX_HEIGHT,
+                ),
+                None => {
+                    tracing::warn!("Report photo not readable: {}", photo.file_path);
+                    pdf.stroke_rect(x, top - PHOTO_BOX_HEIGHT, box_width, PHOTO_BOX_HEIGHT, GREY);
+                    pdf.text_at(
+                        "Photo indisponible",
+                        x + 3.0,
+                        top - PHOTO_BOX_HEIGHT / 2.0,
+                        SMALL_SIZE,
+                        Font::Italic,
+                        GREY,
+                    );
+                }
+            }
+            let caption = truncate(
+                &format!("{} — {}", photo.step_label, photo.caption),
+                chars_per_width(box_width, SMALL_SIZE),
+            );
+            pdf.text_at(
+                &caption,
+                x,
+                top - PHOTO_BOX_HEIGHT - 4.0,
+                SMALL_SIZE,
+                Font::Regular,
+                TEXT,
+            );
+        }
+        pdf.y = top - row_height;
+    }
+}
+
+fn push_customer_validation(pdf: &mut PdfCanvas, vm: &ReportViewModel) {
+    let cv = &vm.customer_validation;
+    pdf.section_title("Validation client");
+    pdf.kv("Satisfaction", &cv.satisfaction);
+    pdf.kv(
+        "Signature",
+        if cv.signature_present {
+            "Signée électroniquement"
+        } else {
+            "Non signée"
+        },
+    );
+    pdf.kv("Commentaires", &cv.comments);
+
+    if let Some(signature) = cv.signature_image.as_deref().and_then(decode_signature) {
+        let (width, height) = SIGNATURE_BOX;
+        pdf.ensure_space(height + 4.0);
+        let x = MARGIN_X + KEY_COLUMN_WIDTH;
+        pdf.image_in_box(signature, x, pdf.y - height, width, height);
+        pdf.stroke_rect(x, pdf.y - height, width, height, GREY);
+        pdf.y -= height + 4.0;
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Images
+// ---------------------------------------------------------------------------
+
+/// Decoded RGB pixels ready to embed.
+struct RgbImage {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl RgbImage {
+    /// Alpha is flattened on white: PDF image XObjects have no alpha channel.
+    fn from_dynamic(image: image::DynamicImage) -> Self {
+        let rgba = image.to_rgba8();
+        let (width, height) = rgba.dimensions();
+        let mut pixels = Vec::with_capacity((width * height * 3) as usize);
+        for px in rgba.pixels() {
+            let alpha = px[3] as u32;
+            for channel in &px.0[..3] {
+                pixels.push(((*channel as u32 * alpha + 255 * (255 - alpha)) / 255) as u8);
+            }
+        }
+        Self {
+            width,
+            height,
+            pixels,
+        }
+    }
+}
+
+fn load_thumbnail(path: &str) -> Option<RgbImage> {
+    let image = image::open(path).ok()?;
+    Some(RgbImage::from_dynamic(
+        image.thumbnail(THUMBNAIL_PX, THUMBNAIL_PX),
+    ))
+}
+
+/// Accepts raw base64 or a `data:image/...;base64,` URL, as stored in
+/// `Intervention.customer_signature`.
+fn decode_signature(signature: &str) -> Option<RgbImage> {
+    let encoded = match signature.split_once(";base64,") {
+        Some((_, data)) => data,
+        None => signature,
+    };
+    let bytes = general_purpose::STANDARD.decode(encoded.trim()).ok()?;
+    let image = image::load_from_memory(&bytes).ok()?;
+    Some(RgbImage::from_dynamic(image))
+}
+
+// ---------------------------------------------------------------------------
+// Drawing helpers
+// ---------------------------------------------------------------------------
+
+#[derive(Clone, Copy)]
+enum Font {
+    Regular,
+    Bold,
+    Italic,
+}
+
+/// A flowing A4 document: `y` is the baseline cursor on the current page.
+struct PdfCanvas {
+    doc: PdfDocumentReference,
+    layer: PdfLayerReference,
+    regular: IndirectFontRef,
+    bold: IndirectFontRef,
+    italic: IndirectFontRef,
+    pages: Vec<PdfLayerReference>,
+    y: f32,
+}
+
+impl PdfCanvas {
+    fn new(title: &str) -> AppResult<Self> {
+        let (doc, page, layer) = PdfDocument::new(title, Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Page 1");
+        let font = |font: BuiltinFont| {
+            doc.add_builtin_font(font)
+                .map_err(|e| AppError::Internal(format!("Failed to load PDF font: {}", e)))
+        };
+        let regular = font(BuiltinFont::Helvetica)?;
+        let bold = font(BuiltinFont::HelveticaBold)?;
+        let italic = font(BuiltinFont::HelveticaOblique)?;
+        let layer = doc.get_page(page).get_layer(layer);
+
+        Ok(Self {
+            pages: vec![layer.clone()],
+            doc,
+            layer,
+            regular,
+            bold,
+            italic,
+            y: TOP_Y,
+        })
+    }
+
+    /// Write page footers and serialize the document.
+    fn finish(self, footer: &str) -> AppResult<Vec<u8>> {
+        let total = self.pages.len();
+        for (index, layer) in self.pages.iter().enumerate() {
+            layer.set_outline_color(rgb(NAVY));
+            layer.set_outline_thickness(0.8);
+            layer.add_line(Line {
+                points: vec![
+                    (Point::new(Mm(MARGIN_X), Mm(14.0)), false),
+                    (Point::new(Mm(PAGE_WIDTH - MARGIN_X), Mm(14.0)), false),
+                ],
+                is_closed: false,
+            });
+            layer.set_fill_color(rgb(GREY));
+            layer.use_text(
+                encode(footer),
+                SMALL_SIZE,
+                Mm(MARGIN_X),
+                Mm(9.0),
+                &self.regular,
+            );
+            layer.use_text(
+                format!("Page {} / {}", index + 1, total),
+                SMALL_SIZE,
+                Mm(PAGE_WIDTH - MARGIN_X - 18.0),
+                Mm(9.0),
+                &self.regular,
+            );
+        }
+        self.doc
+            .save_to_bytes()
+            .map_err(|e| AppError::Internal(format!("Failed to serialize PDF: {}", e)))
+    }
+
+    fn ensure_space(&mut self, height: f32) {
+        if self.y - height < BOTTOM_Y {
+            let (page, layer) = self.doc.add_page(
+                Mm(PAGE_WIDTH),
+                Mm(PAGE_HEIGHT),
+                format!("Page {}", self.pages.len() + 1),
+            );
+            self.layer = self.doc.get_page(page).get_layer(layer);
+            self.pages.push(self.layer.clone());
+            self.y = TOP_Y;
+        }
+    }
+
+    fn font(&self, font: Font) -> &IndirectFontRef {
+        match font {
+            Font::Regular => &self.regular,
+            Font::Bold => &self.bold,
+            Font::Italic => &self.italic,
+        }
+    }
+
+    fn text_at(&self, text: &str, x: f32, y: f32, size: f32, font: Font, color: (f32, f32, f32)) {
+        self.layer.set_fill_color(rgb(color));
+        self.layer
+            .use_text(encode(text), size, Mm(x), Mm(y), self.font(font));
+    }
+
+    fn fill_rect(&self, x: f32, y: f32, width: f32, height: f32, color: (f32, f32, f32)) {
+        self.layer.set_fill_color(rgb(color));
+        self.layer.add_rect(
+            Rect::new(Mm(x), Mm(y), Mm(x + width), Mm(y + height)).with_mode(PaintMode::Fill),
+        );
+    }
+
+    fn stroke_rect(&self, x: f32, y: f32, width: f32, height: f32, color: (f32, f32, f32)) {
+        self.layer.set_outline_color(rgb(color));
+        self.layer.set_outline_thickness(0.5);
+        self.layer.add_rect(
+            Rect::new(Mm(x), Mm(y), Mm(x + width), Mm(y + height)).with_mode(PaintMode::Stroke),
+        );
+    }
+
+    fn section_title(&mut self, title: &str) {
+        self.ensure_space(18.0);
+        self.y -= 4.0;
+        self.text_at(
+            &title.to_uppercase(),
+            MARGIN_X,
+            self.y,
+            SECTION_SIZE,
+            Font::Bold,
+            NAVY,
+        );
+        self.y -= 1.5;
+        self.layer.set_outline_color(rgb(NAVY));
+        self.layer.set_outline_thickness(1.2);
+        self.layer.add_line(Line {
+            points: vec![
+                (Point::new(Mm(MARGIN_X), Mm(self.y)), false),
+                (Point::new(Mm(MARGIN_X + CONTENT_WIDTH), Mm(self.y)), false),
+            ],
+            is_closed: false,
+        });
+        self.y -= 5.5;
+    }
+
+    fn sub_title(&mut self, title: &str) {
+        self.ensure_space(10.0);
+        self.y -= 1.0;
+        self.text_at(title, MARGIN_X, self.y, BODY_SIZE, Font::Bold, TEXT);
+        self.y -= 5.0;
+    }
+
+    fn kv(&mut self, key: &str, value: &str) {
+        let lines = wrap(
+            value,
+            chars_per_width(CONTENT_WIDTH - KEY_COLUMN_WIDTH, BODY_SIZE),
+        );
+        self.ensure_space(line_height(BODY_SIZE) * lines.len() as f32);
+        self.text_at(key, MARGIN_X + 2.0, self.y, BODY_SIZE, Font::Bold, GREY);
+        for line in lines {
+            self.text_at(
+                &line,
+                MARGIN_X + KEY_COLUMN_WIDTH,
+                self.y,
+                BODY_SIZE,
+                Font::Regular,
+                TEXT,
+            );
+            self.y -= line_height(BODY_SIZE);
+        }
+    }
+
+    fn paragraph(&mut self, text: &str, size: f32, font: Font, color: (f32, f32, f32)) {
+        for line in wrap(text, chars_per_width(CONTENT_WIDTH - 4.0, size)) {
+            self.ensure_space(line_height(size));
+            self.text_at(&line, MARGIN_X + 2.0, self.y, size, font, color);
+            self.y -= line_height(size);
+        }
+    }
+
+    /// Header row on navy, then rows whose cells wrap within their column.
+    fn table(&mut self, columns: &[(&str, f32)], rows: &[Vec<String>]) {
+        let row_pad = 1.5;
+        let header_height = line_height(SMALL_SIZE) + row_pad * 2.0;
+        self.ensure_space(header_height * 2.0);
+        self.draw_table_header(columns, header_height);
+
+        for (index, row) in rows.iter().enumerate() {
+            let cells = columns
+                .iter()
+                .zip(row)
+                .map(|((_, width), value)| wrap(value, chars_per_width(width - 3.0, SMALL_SIZE)))
+                .collect::<Vec<_>>();
+            let lines = cells.iter().map(Vec::len).max().unwrap_or(1).max(1);
+            let height = line_height(SMALL_SIZE) * lines as f32 + row_pad * 2.0;
+
+            let page_count = self.pages.len();
+            self.ensure_space(height);
+            if self.pages.len() != page_count {
+                self.draw_table_header(columns, header_height);
+            }
+            if index % 2 == 1 {
+                self.fill_rect(MARGIN_X, self.y - height + 3.0, CONTENT_WIDTH, height, PALE);
+            }
+
+            let mut x = MARGIN_X;
+            for ((_, width), lines) in columns.iter().zip(&cells) {
+                let mut y = self.y;
+                for line in lines {
+                    self.text_at(line, x + 1.5, y, SMALL_SIZE, Font::Regular, TEXT);
+                    y -= line_height(SMALL_SIZE);
+                }
+                x += width;
+            }
+            self.y -= height;
+        }
+        self.y -= 2.0;
+    }
+
+    fn draw_table_header(&mut self, columns: &[(&str, f32)], height: f32) {
+        self.fill_rect(MARGIN_X, self.y - height + 3.0, CONTENT_WIDTH, height, NAVY);
+        let mut x = MARGIN_X;
+        for (title, width) in columns {
+            self.text_at(title, x + 1.5, self.y, SMALL_SIZE, Font::Bold, WHITE);
+            x += width;
+        }
+        self.y -= height;
+    }
+
+    /// Draw `image` scaled to fit (aspect preserved) inside the given box.
+    fn image_in_box(&self, image: RgbImage, x: f32, y: f32, width: f32, height: f32) {
+        // Pick the DPI that makes the image exactly fill the limiting dimension.
+        let dpi = (image.width as f32 * 25.4 / width).max(image.height as f32 * 25.4 / height);
+        let drawn_height = image.height as f32 * 25.4 / dpi;
+
+        Image::from(ImageXObject {
+            width: Px(image.width as usize),
+            height: Px(image.height as usize),
+            color_space: ColorSpace::Rgb,
+            bits_per_component: ColorBits::Bit8,
+            interpolate: true,
+            image_data: image.pixels,
+            image_filter: None,
+            smask: None,
+            clipping_bbox: None,
+        })
+        .add_to_layer(
+            self.layer.clone(),
+            ImageTransform {
+                translate_x: Some(Mm(x)),
+                translate_y: Some(Mm(y + height - drawn_height)),
+                dpi: Some(dpi),
+                ..Default::default()
+            },
+        );
+    }
+}
+
+fn rgb((r, g, b): (f32, f32, f32)) -> Color {
+    Color::Rgb(Rgb::new(r, g, b, None))
+}
+
+/// Base-14 fonts use WinAnsiEncoding: map common characters outside it so
+/// they are not silently dropped.
+fn encode(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            '✓' | '✔' | '✅' => 'x',
+            '\u{2212}' => '-',
+            '\u{00A0}' | '\u{202F}' => ' ',
+            c => c,
+        })
+        .collect()
+}
+
+fn line_height(size: f32) -> f32 {
+    size * 0.3528 * 1.35
+}
+
+/// Approximate Helvetica capacity: average glyph width is about half an em.
+fn chars_per_width(width_mm: f32, size: f32) -> usize {
+    ((width_mm / (size * 0.3528 * 0.52)) as usize).max(1)
+}
+
+fn truncate(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let mut out: String = text.chars().take(max_chars.saturating_sub(1)).collect();
+    out.push('…');
+    out
+}
+
+/// Greedy word wrap; words longer than a line are split.
+fn wrap(text: &str, max_chars: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    for paragraph in text.lines() {
+        let mut line = String::new();
+        for word in paragraph.split_whitespace() {
+            let mut word = word.to_string();
+            while word.chars().count() > max_chars {
+                if !line.is_empty() {
+                    lines.push(std::mem::take(&mut line));
+                }
+                let head: String = word.chars().take(max_chars).collect();
+                word = word.chars().skip(max_chars).collect();
+                lines.push(head);
+            }
+            if line.is_empty() {
+                line = word;
+            } else if line.chars().count() + 1 + word.chars().count() <= max_chars {
+                line.push(' ');
+                line.push_str(&word);
+            } else {
+                lines.push(std::mem::replace(&mut line, word));
+            }
+        }
+        lines.push(line);
+    }
+    if lines.is_empty() {
+        lines.push(String::new());
+    }
+    lines
+}
@@ -602,12 +602,14 @@ mod tests {
             customer_validation: ReportCustomerValidation {
                 satisfaction: "9/10".to_string(),
                 signature_present: true,
+                signature_image: None,
                 comments: "Tres satisfait".to_string(),
             },
             photos: ReportPhotos {
                 total_count: 0,
                 grouped_by_step: vec![],
                 grouped_by_category: vec![],
+                items: vec![],
             },
             display: ReportDisplay {
                 placeholder_not_specified: "Non renseigne".to_string(),
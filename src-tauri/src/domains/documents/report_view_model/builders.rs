@@ -14,8 +14,8 @@ use super::formatters::{
     NOT_EVALUATED, NOT_SPECIFIED, NO_OBSERVATION,
 };
 use super::{
-    ReportApproval, ReportPhotoGroup, ReportPhotos, ReportQuality, ReportQualityCheckpoint,
-    ReportStep,
+    ReportApproval, ReportPhotoGroup, ReportPhotoItem, ReportPhotos, ReportQuality,
+    ReportQualityCheckpoint, ReportStep,
 };
 
 pub(super) fn build_report_step(step: &InterventionStep, photos: &[Photo]) -> ReportStep {
@@ -214,9 +214,36 @@ pub(super) fn build_photos_section(photos: &[Photo], steps: &[InterventionStep])
         .map(|(label, count)| ReportPhotoGroup { label, count })
         .collect();
 
+    // Individual photos, ordered by step number (unassigned photos last)
+    let mut items: Vec<(i32, ReportPhotoItem)> = photos
+        .iter()
+        .enumerate()
+        .map(|(index, photo)| {
+            let step = steps.iter().find(|s| {
+                photo.step_id.as_deref() == Some(&s.id) || photo.step_number == Some(s.step_number)
+            });
+            let caption = photo
+                .title
+                .clone()
+                .or_else(|| photo.zone.clone())
+                .or_else(|| photo.file_name.clone())
+                .unwrap_or_else(|| format!("Photo {}", index + 1));
+            let item = ReportPhotoItem {
+                step_label: step
+                    .map(|s| s.step_name.clone())
+                    .unwrap_or_else(|| NOT_SPECIFIED.to_string()),
+                caption,
+                file_path: photo.file_path.clone(),
+            };
+            (step.map(|s| s.step_number).unwrap_or(i32::MAX), item)
+        })
+        .collect();
+    items.sort_by_key(|(step_number, _)| *step_number);
+
     ReportPhotos {
         total_count: photos.len(),
         grouped_by_step,
         grouped_by_category,
+        items: items.into_iter().map(|(_, item)| item).collect(),
     }
 }
@@ -189,6 +189,7 @@ pub fn build_intervention_report_view_model(
             .map(|s| format!("{}/10", s))
             .unwrap_or_else(|| NOT_EVALUATED.to_string()),
         signature_present: intervention.customer_signature.is_some(),
+        signature_image: intervention.customer_signature.clone(),
         comments: intervention
             .customer_comments
             .clone()
@@ -364,6 +364,47 @@ fn test_vm_customer_validation() {
     assert_eq!(vm.customer_validation.comments, "Tres satisfait");
 }
 
+#[test]
+fn test_vm_photo_items_ordered_by_step() {
+    use crate::shared::services::cross_domain::Photo;
+
+    let intervention = build_test_intervention();
+    let steps = build_test_steps();
+
+    let photo = |path: &str| Photo::new("test-intervention-001".to_string(), path.to_string());
+
+    let unassigned = photo("/photos/a.jpg");
+    let mut installation = photo("/photos/b.jpg");
+    installation.step_number = Some(3);
+    installation.zone = Some("Capot".to_string());
+    let mut inspection = photo("/photos/c.jpg");
+    inspection.step_id = Some(steps[0].id.clone());
+    inspection.title = Some("Vue avant".to_string());
+
+    let vm = build_intervention_report_view_model(
+        &intervention,
+        &steps,
+        &[unassigned, installation, inspection],
+        &[],
+        None,
+    );
+
+    let items: Vec<_> = vm
+        .photos
+        .items
+        .iter()
+        .map(|p| (p.step_label.as_str(), p.caption.as_str(), p.file_path.as_str()))
+        .collect();
+    assert_eq!(
+        items,
+        vec![
+            ("Inspection", "Vue avant", "/photos/c.jpg"),
+            ("Installation", "Capot", "/photos/b.jpg"),
+            ("Non renseigne", "Photo 1", "/photos/a.jpg"),
+        ]
+    );
+}
+
 #[test]
 fn test_vm_vehicle_fields() {
     let intervention = build_test_intervention();
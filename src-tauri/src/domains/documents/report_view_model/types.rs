@@ -155,6 +155,10 @@ pub struct ReportQualityCheckpoint {
 pub struct ReportCustomerValidation {
     pub satisfaction: String,
     pub signature_present: bool,
+    /// Raw `Intervention.customer_signature` (base64 image, optionally a data URL).
+    /// Only the native PDF renderer draws it; skipped when serializing.
+    #[serde(skip)]
+    pub signature_image: Option<String>,
     pub comments: String,
 }
 
@@ -163,6 +167,7 @@ pub struct ReportPhotos {
     pub total_count: usize,
     pub grouped_by_step: Vec<ReportPhotoGroup>,
     pub grouped_by_category: Vec<ReportPhotoGroup>,
+    pub items: Vec<ReportPhotoItem>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -171,6 +176,14 @@ pub struct ReportPhotoGroup {
     pub count: usize,
 }
 
+/// A single photo, in step order, for renderers that embed thumbnails.
+#[derive(Debug, Clone, Serialize)]
+pub struct ReportPhotoItem {
+    pub step_label: String,
+    pub caption: String,
+    pub file_path: String,
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct ReportDisplay {
     pub placeholder_not_specified: String,
@@ -1,6 +1,7 @@
 //! Documents domain test modules.
 
 pub mod integration_documents;
+pub mod object_storage_documents;
 pub mod permission_documents;
 pub mod unit_documents;
 pub mod validation_documents;
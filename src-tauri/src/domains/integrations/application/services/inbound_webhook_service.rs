@@ -0,0 +1,274 @@
+//! Handles requests arriving on the inbound webhook listener.
+//!
+//! Each request is authenticated against its integration's signing secrets,
+//! run as that integration through `InboundCommandHandler`, logged with its
+//! response for idempotent retries, and written to the audit log.
+//!
+//! A signed request stays valid for the signature tolerance, so it must
+//! carry an `Idempotency-Key`, and a signature already answered is refused
+//! under any other key: the key is not part of what is signed.
+
+use chrono::Utc;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+use crate::domains::integrations::application::services::integrations_service::IntegrationsService;
+use crate::domains::integrations::domain::inbound::{
+    InboundAction, InboundHttpRequest, InboundHttpResponse, InboundRoute, CORRELATION_ID_HEADER,
+};
+use crate::domains::integrations::domain::models::integrations::{
+    InboundRequestLogEntry, IntegrationConfig,
+};
+use crate::domains::integrations::domain::webhook_signature::{
+    DEFAULT_TOLERANCE_SECS, IDEMPOTENCY_KEY_HEADER, SIGNATURE_HEADER,
+};
+use crate::shared::context::RequestContext;
+use crate::shared::contracts::inbound_commands::{InboundCommandHandler, InboundCommandOutcome};
+use crate::shared::error::AppError;
+use crate::shared::logging::audit_service::{
+    ActionResult, AuditEvent, AuditEventType, AuditService,
+};
+use crate::shared::logging::correlation::generate_correlation_id;
+
+pub struct InboundWebhookService {
+    integrations: Arc<IntegrationsService>,
+    commands: Arc<dyn InboundCommandHandler>,
+    audit: Arc<AuditService>,
+    /// Requests are handled one at a time, so a retry that arrives while the
+    /// first attempt is still running waits for its stored response instead
+    /// of acting twice. Holds the signatures already answered, with when.
+    processing: Mutex<HashMap<String, i64>>,
+}
+
+impl InboundWebhookService {
+    pub fn new(
+        integrations: Arc<IntegrationsService>,
+        commands: Arc<dyn InboundCommandHandler>,
+        audit: Arc<AuditService>,
+    ) -> Self {
+        Self {
+            integrations,
+            commands,
+            audit,
+            processing: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Answer one request. Failures become error responses.
+    pub async fn handle(&self, request: InboundHttpRequest) -> InboundHttpResponse {
+        let Some(route) = InboundRoute::parse(&request.method, &request.path) else {
+            return InboundHttpResponse::error(&AppError::NotFound(format!(
+                "No inbound route for {} {}",
+                request.method, request.path
+            )));
+        };
+        let mut answered = self.processing.lock().await;
+        let now = Utc::now().timestamp();
+        // A signature is accepted up to the tolerance either side of its
+        // timestamp, so it can stay valid that long after it arrived.
+        answered.retain(|_, received_at| now - *received_at <= 2 * DEFAULT_TOLERANCE_SECS);
+
+        let integration = match self
+            .integrations
+            .authenticate_inbound(
+                &route.integration_id,
+                request.header(SIGNATURE_HEADER),
+                &request.body,
+                now,
+            )
+            .await
+        {
+            Ok(integration) => integration,
+            Err(error) => {
+                self.audit_rejection(&route, &request, &error);
+                return InboundHttpResponse::error(&error);
+            }
+        };
+        let correlation_id = request
+            .header(CORRELATION_ID_HEADER)
+            .filter(|value| !value.trim().is_empty())
+            .map(str::to_string)
+            .unwrap_or_else(generate_correlation_id);
+        let ctx =
+            RequestContext::for_integration(&integration.id, &integration.name, correlation_id);
+        let Some(idempotency_key) = request
+            .header(IDEMPOTENCY_KEY_HEADER)
+            .filter(|value| !value.trim().is_empty())
+            .map(str::to_string)
+        else {
+            return InboundHttpResponse::error(&AppError::Validation(format!(
+                "Missing {} header",
+                IDEMPOTENCY_KEY_HEADER
+            )));
+        };
+
+        match self
+            .integrations
+            .find_inbound_request(&integration.id, &idempotency_key)
+            .await
+        {
+            Ok(Some(previous)) => {
+                return InboundHttpResponse::new(previous.response_status, previous.response_body)
+            }
+            Ok(None) => {}
+            Err(error) => return InboundHttpResponse::error(&error),
+        }
+        // Authentication passed, so the header is there.
+        let signature = request.header(SIGNATURE_HEADER).unwrap_or_default();
+        if answered.contains_key(signature) {
+            let error =
+                AppError::Authentication("Signature already used by another request".to_string());
+            self.audit_rejection(&route, &request, &error);
+            return InboundHttpResponse::error(&error);
+        }
+
+        let result = self.run(&ctx, &integration, &route, &request.body).await;
+        let response = match &result {
+            Ok(outcome) => InboundHttpResponse::new(
+                if outcome.created { 201 } else { 200 },
+                outcome.body.clone(),
+            ),
+            Err(error) => InboundHttpResponse::error(error),
+        };
+        let outcome = result.as_ref().ok();
+        let error = result
+            .as_ref()
+            .err()
+            .map(|error| error.clone().sanitize_for_frontend().to_string());
+
+        let entry = InboundRequestLogEntry {
+            id: Uuid::new_v4().to_string(),
+            integration_id: integration.id.clone(),
+            action: route.action.name().to_string(),
+            // Server errors are not replayed: a retry gets another attempt.
+            idempotency_key: Some(idempotency_key).filter(|_| response.status < 500),
+            correlation_id: ctx.correlation_id.clone(),
+            response_status: response.status,
+            response_body: response.body.clone(),
+            resource_type: outcome.map(|outcome| outcome.resource_type.clone()),
+            resource_id: outcome.map(|outcome| outcome.resource_id.clone()),
+            error: error.clone(),
+            received_at: Utc::now().timestamp_millis(),
+        };
+        if let Err(error) = self.integrations.record_inbound_request(&entry).await {
+            tracing::error!(
+                integration_id = %integration.id,
+                correlation_id = %ctx.correlation_id,
+                "Failed to record inbound request: {}",
+                error
+            );
+        }
+        // Answers a retry could replay are final for this signature too.
+        if entry.idempotency_key.is_some() {
+            answered.insert(signature.to_string(), now);
+        }
+        self.audit_request(&ctx, &request, &entry);
+        response
+    }
+
+    async fn run(
+        &self,
+        ctx: &RequestContext,
+        integration: &IntegrationConfig,
+        route: &InboundRoute,
+        body: &[u8],
+    ) -> Result<InboundCommandOutcome, AppError> {
+        let action = route.action.name();
+        if !integration
+            .subscribed_events
+            .iter()
+            .any(|allowed| allowed == action)
+        {
+            return Err(AppError::Authorization(format!(
+                "Integration {} is not allowed to {}",
+                integration.id, action
+            )));
+        }
+        let payload: serde_json::Value = serde_json::from_slice(body)
+            .map_err(|error| AppError::Validation(format!("Invalid JSON body: {}", error)))?;
+        match &route.action {
+            InboundAction::CreateTask => self.commands.create_task(ctx, payload).await,
+            InboundAction::UpsertClient => self.commands.upsert_client(ctx, payload).await,
+            InboundAction::RespondToQuote { quote_id } => {
+                self.commands.respond_to_quote(ctx, quote_id, payload).await
+            }
+        }
+    }
+
+    fn audit_request(
+        &self,
+        ctx: &RequestContext,
+        request: &InboundHttpRequest,
+        entry: &InboundRequestLogEntry,
+    ) {
+        let succeeded = entry.error.is_none();
+        let event = AuditEvent {
+            id: Uuid::new_v4().to_string(),
+            event_type: AuditEventType::DataImported,
+            user_id: ctx.user_id().to_string(),
+            action: format!("INBOUND_{}", entry.action.to_ascii_uppercase()),
+            resource_id: entry.resource_id.clone(),
+            resource_type: entry.resource_type.clone(),
+            description: match &entry.error {
+                None => format!(
+                    "Integration {} performed {}",
+                    ctx.auth.username, entry.action
+                ),
+                Some(error) => format!(
+                    "Integration {} failed to {}: {}",
+                    ctx.auth.username, entry.action, error
+                ),
+            },
+            ip_address: request.remote_addr.clone(),
+            user_agent: request.header("User-Agent").map(str::to_string),
+            result: if succeeded {
+                ActionResult::Success
+            } else {
+                ActionResult::Failure
+            },
+            previous_state: None,
+            new_state: None,
+            timestamp: Utc::now(),
+            metadata: Some(serde_json::json!({
+                "integration_id": entry.integration_id,
+                "idempotency_key": entry.idempotency_key,
+                "response_status": entry.response_status,
+            })),
+            session_id: None,
+            request_id: Some(ctx.correlation_id.clone()),
+        };
+        if let Err(error) = self.audit.log_event(event) {
+            tracing::error!("Failed to audit inbound request: {}", error);
+        }
+    }
+
+    /// Requests that fail authentication are audited against the integration
+    /// they claim to come from.
+    fn audit_rejection(
+        &self,
+        route: &InboundRoute,
+        request: &InboundHttpRequest,
+        error: &AppError,
+    ) {
+        let event_type = match error {
+            AppError::Authorization(_) => AuditEventType::AuthorizationDenied,
+            _ => AuditEventType::AuthenticationFailure,
+        };
+        if let Err(audit_error) = self.audit.log_security_event(
+            event_type,
+            &format!("integration:{}", route.integration_id),
+            &format!(
+                "Rejected inbound {} request: {}",
+                route.action.name(),
+                error
+            ),
+            request.remote_addr.as_deref(),
+            request.header("User-Agent"),
+            ActionResult::Failure,
+        ) {
+            tracing::error!("Failed to audit rejected inbound request: {}", audit_error);
+        }
+    }
+}
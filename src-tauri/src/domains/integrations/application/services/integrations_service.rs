@@ -1,24 +1,44 @@
 use async_trait::async_trait;
-use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
 use chrono::Utc;
+use rand::{Rng, RngCore};
 use reqwest::Client;
 use sha2::{Digest, Sha256};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use uuid::Uuid;
 
 use crate::db::Database;
+use crate::domains::integrations::domain::inbound::INBOUND_ACTIONS;
 use crate::domains::integrations::domain::models::integrations::{
-    CreateIntegrationRequest, DeliveryStatus, IntegrationConfig, IntegrationKind,
-    IntegrationStatus, TestIntegrationResponse, UpdateIntegrationRequest,
+    CreateIntegrationRequest, DeliveryAttempt, DeliveryLogEntry, DeliveryStatus,
+    InboundRequestLogEntry, IntegrationConfig, IntegrationHttpMethod, IntegrationKind,
+    IntegrationStatus, ListDeliveriesRequest, ListInboundRequestsRequest, OutboundDelivery,
+    PayloadTemplate, PreviewPayloadRequest, PreviewPayloadResponse, RotateSigningSecretRequest,
+    SigningSecretResponse, TestIntegrationResponse, UpdateIntegrationRequest,
+};
+use crate::domains::integrations::domain::webhook_signature::{
+    signature_header, verify_signature, SignatureError, DEFAULT_TOLERANCE_SECS, DELIVERY_ID_HEADER,
+    EVENT_HEADER, IDEMPOTENCY_KEY_HEADER, RESERVED_HEADERS, SIGNATURE_HEADER,
 };
 use crate::domains::integrations::infrastructure::integrations_repository::{
-    DeliveryRecord, IntegrationsRepository, SqliteIntegrationsRepository,
+    DeliveryRecord, IntegrationsRepository, SqliteIntegrationsRepository, StoredIntegrationSecret,
 };
 use crate::shared::context::RequestContext;
 use crate::shared::contracts::integration_sink::{
     IntegrationDispatchRequest, IntegrationEventSink,
 };
 use crate::shared::error::{AppError, AppResult};
+use crate::shared::event_bus::publish_event;
+use crate::shared::repositories::base::{PaginatedResult, PaginationInfo};
+use crate::shared::services::event_bus::event_factory;
+use crate::shared::services::secrets_vault::secrets_vault;
+
+/// How long a rotated-out signing secret keeps signing by default.
+const DEFAULT_SIGNING_GRACE_MINUTES: u32 = 24 * 60;
+/// Longest grace period for a rotated-out signing secret.
+const MAX_SIGNING_GRACE_MINUTES: u32 = 7 * 24 * 60;
+/// Longest response body excerpt kept in the delivery log, in bytes.
+const RESPONSE_EXCERPT_BYTES: usize = 512;
 
 pub struct IntegrationsService {
     repo: Arc<dyn IntegrationsRepository>,
@@ -54,13 +74,19 @@ impl IntegrationsService {
         _ctx: &RequestContext,
         request: CreateIntegrationRequest,
     ) -> AppResult<IntegrationConfig> {
-        self.validate_endpoint(&request.endpoint_url)?;
+        if request.kind == IntegrationKind::Webhook {
+            self.validate_endpoint(&request.endpoint_url)?;
+        }
+        validate_subscribed_events(&request.kind, &request.subscribed_events)?;
+        let retry_policy = request.retry_policy.unwrap_or_default();
+        retry_policy.validate()?;
+        let payload_template = normalize_template(request.payload_template)?;
         let now = Utc::now().timestamp_millis();
         let integration = IntegrationConfig {
             id: Uuid::new_v4().to_string(),
             name: request.name.trim().to_string(),
             description: request.description.map(|value| value.trim().to_string()),
-            kind: IntegrationKind::Webhook,
+            kind: request.kind,
             status: IntegrationStatus::Draft,
             endpoint_url: request.endpoint_url.trim().to_string(),
             headers: request.headers,
@@ -69,6 +95,14 @@ impl IntegrationsService {
                 .secret_token
                 .as_ref()
                 .is_some_and(|value| !value.is_empty()),
+            has_signing_secret: true,
+            previous_signing_secret_expires_at: None,
+            retry_policy,
+            http_method: request.http_method,
+            payload_format: request.payload_format,
+            payload_template,
+            consecutive_failures: 0,
+            disabled_reason: None,
             last_tested_at: None,
             created_at: now,
             updated_at: now,
@@ -77,8 +111,12 @@ impl IntegrationsService {
         let encrypted_secret = request
             .secret_token
             .filter(|value| !value.is_empty())
-            .map(|value| self.encrypt_secret(&value));
-        self.repo.create(&integration, encrypted_secret).await?;
+            .map(|value| self.encrypt_secret(&value))
+            .transpose()?;
+        let signing_secret = self.encrypt_secret(&generate_signing_secret())?;
+        self.repo
+            .create(&integration, encrypted_secret, signing_secret)
+            .await?;
         Ok(integration)
     }
 
@@ -96,22 +134,44 @@ impl IntegrationsService {
             integration.description = Some(description.trim().to_string());
         }
         if let Some(endpoint_url) = request.endpoint_url {
-            self.validate_endpoint(&endpoint_url)?;
+            if integration.kind == IntegrationKind::Webhook {
+                self.validate_endpoint(&endpoint_url)?;
+            }
             integration.endpoint_url = endpoint_url.trim().to_string();
         }
         if let Some(headers) = request.headers {
             integration.headers = headers;
         }
         if let Some(subscribed_events) = request.subscribed_events {
+            validate_subscribed_events(&integration.kind, &subscribed_events)?;
             integration.subscribed_events = subscribed_events;
         }
+        if let Some(retry_policy) = request.retry_policy {
+            retry_policy.validate()?;
+            integration.retry_policy = retry_policy;
+        }
+        if let Some(http_method) = request.http_method {
+            integration.http_method = http_method;
+        }
+        if let Some(payload_format) = request.payload_format {
+            integration.payload_format = payload_format;
+        }
+        if let Some(payload_template) = request.payload_template {
+            integration.payload_template = normalize_template(Some(payload_template))?;
+        }
         if let Some(status) = request.status {
+            // Re-enabling starts a fresh failure streak.
+            if status == IntegrationStatus::Active {
+                integration.consecutive_failures = 0;
+                integration.disabled_reason = None;
+            }
             integration.status = status;
         }
         let encrypted_secret = request
             .secret_token
             .filter(|value| !value.is_empty())
-            .map(|value| self.encrypt_secret(&value));
+            .map(|value| self.encrypt_secret(&value))
+            .transpose()?;
         integration.has_secret = integration.has_secret || encrypted_secret.is_some();
         integration.updated_at = Utc::now().timestamp_millis();
         self.repo.update(&integration, encrypted_secret).await?;
@@ -124,8 +184,18 @@ impl IntegrationsService {
         id: &str,
     ) -> AppResult<TestIntegrationResponse> {
         let integration = self.repo.get_with_secret(id).await?;
+        if integration.config.kind != IntegrationKind::Webhook {
+            return Err(AppError::Validation(
+                "Inbound integrations have no endpoint to test".to_string(),
+            ));
+        }
         let tested_at = Utc::now().timestamp_millis();
-        let mut request = self.http_client.get(&integration.config.endpoint_url);
+        let mut request = self
+            .http_client
+            .get(&integration.config.endpoint_url)
+            .timeout(Duration::from_secs(u64::from(
+                integration.config.retry_policy.timeout_seconds,
+            )));
         for (key, value) in &integration.config.headers {
             request = request.header(key, value);
         }
@@ -156,10 +226,241 @@ impl IntegrationsService {
         })
     }
 
+    /// The current signing secret, for configuring the receiver.
+    pub async fn reveal_signing_secret(
+        &self,
+        _ctx: &RequestContext,
+        id: &str,
+    ) -> AppResult<SigningSecretResponse> {
+        let stored = self.repo.get_with_secret(id).await?;
+        let signing_secret = stored
+            .signing_secret
+            .as_deref()
+            .and_then(|value| self.decrypt_secret(value))
+            .ok_or_else(|| {
+                AppError::NotFound(format!(
+                    "Integration {} has no signing secret; rotate to create one",
+                    id
+                ))
+            })?;
+        Ok(SigningSecretResponse {
+            integration_id: stored.config.id,
+            signing_secret,
+            previous_signing_secret_expires_at: stored.config.previous_signing_secret_expires_at,
+        })
+    }
+
+    /// Replace the signing secret. The old one keeps signing deliveries next
+    /// to the new one for the grace period, so receivers can switch without
+    /// rejecting traffic.
+    pub async fn rotate_signing_secret(
+        &self,
+        _ctx: &RequestContext,
+        id: &str,
+        request: RotateSigningSecretRequest,
+    ) -> AppResult<SigningSecretResponse> {
+        let grace_minutes = request
+            .grace_period_minutes
+            .unwrap_or(DEFAULT_SIGNING_GRACE_MINUTES);
+        if grace_minutes > MAX_SIGNING_GRACE_MINUTES {
+            return Err(AppError::Validation(format!(
+                "Signing secret grace period cannot exceed {} minutes",
+                MAX_SIGNING_GRACE_MINUTES
+            )));
+        }
+        let stored = self.repo.get_with_secret(id).await?;
+        let now = Utc::now().timestamp_millis();
+        let (previous, previous_expires_at) = match stored.signing_secret {
+            Some(current) if grace_minutes > 0 => {
+                (Some(current), Some(now + i64::from(grace_minutes) * 60_000))
+            }
+            _ => (None, None),
+        };
+        let signing_secret = generate_signing_secret();
+        self.repo
+            .update_signing_secrets(
+                id,
+                &self.encrypt_secret(&signing_secret)?,
+                previous,
+                previous_expires_at,
+                now,
+            )
+            .await?;
+        Ok(SigningSecretResponse {
+            integration_id: stored.config.id,
+            signing_secret,
+            previous_signing_secret_expires_at: previous_expires_at,
+        })
+    }
+
     pub async fn retry_dead_letters(&self, _ctx: &RequestContext, id: &str) -> AppResult<usize> {
         self.repo.retry_dead_letters(id).await
     }
 
+    /// The delivery log of an integration, with each delivery's last attempt.
+    pub async fn list_deliveries(
+        &self,
+        _ctx: &RequestContext,
+        id: &str,
+        request: ListDeliveriesRequest,
+    ) -> AppResult<PaginatedResult<DeliveryLogEntry>> {
+        self.repo.get(id).await?;
+        let (entries, total) = self.repo.list_deliveries(id, &request).await?;
+        Ok(PaginatedResult::new(
+            entries,
+            PaginationInfo::new(
+                request.pagination.page(),
+                request.pagination.page_size(),
+                total,
+            ),
+        ))
+    }
+
+    /// Every attempt at sending a delivery, oldest first.
+    pub async fn list_delivery_attempts(
+        &self,
+        _ctx: &RequestContext,
+        delivery_id: &str,
+    ) -> AppResult<Vec<DeliveryAttempt>> {
+        self.repo.get_delivery(delivery_id).await?;
+        self.repo.list_delivery_attempts(delivery_id).await
+    }
+
+    /// Send a delivery again now, whatever its state, with a fresh retry
+    /// budget. It keeps its idempotency key, so receivers that already
+    /// processed it can tell.
+    pub async fn replay_delivery(
+        &self,
+        _ctx: &RequestContext,
+        delivery_id: &str,
+    ) -> AppResult<DeliveryLogEntry> {
+        let delivery = self.repo.get_delivery(delivery_id).await?;
+        let integration = self.repo.get(&delivery.integration_id).await?;
+        if integration.status != IntegrationStatus::Active {
+            return Err(AppError::Validation(format!(
+                "Integration {} is not active; activate it before replaying deliveries",
+                integration.id
+            )));
+        }
+        self.repo
+            .reset_delivery(delivery_id, Utc::now().timestamp_millis())
+            .await?;
+        let delivery = self.repo.get_delivery(delivery_id).await?;
+        let attempt = self.deliver(&delivery).await?;
+        Ok(DeliveryLogEntry {
+            delivery: self.repo.get_delivery(delivery_id).await?,
+            last_attempt: Some(attempt),
+        })
+    }
+
+    /// Render a sample event through a payload template, exactly as it
+    /// would be sent.
+    pub async fn preview_payload(
+        &self,
+        _ctx: &RequestContext,
+        request: PreviewPayloadRequest,
+    ) -> AppResult<PreviewPayloadResponse> {
+        let template = request.payload_template.unwrap_or_default();
+        template.validate()?;
+        let sample_payload = match request.sample_payload {
+            Some(sample_payload) => sample_payload,
+            None => self
+                .repo
+                .latest_payload_for_event(&request.event_name)
+                .await?
+                .ok_or_else(|| {
+                    AppError::Validation(format!(
+                        "No {} event has been queued yet; provide a sample payload",
+                        request.event_name
+                    ))
+                })?,
+        };
+        let rendered = template.render(&request.event_name, "preview", &sample_payload);
+        let body = request.payload_format.encode(&rendered)?;
+        Ok(PreviewPayloadResponse {
+            content_type: request.payload_format.content_type().to_string(),
+            body: String::from_utf8_lossy(&body).into_owned(),
+            sample_payload,
+        })
+    }
+
+    /// Check a request on the inbound listener against the integration's
+    /// live signing secrets. `now` is in Unix seconds.
+    pub async fn authenticate_inbound(
+        &self,
+        integration_id: &str,
+        signature: Option<&str>,
+        body: &[u8],
+        now: i64,
+    ) -> AppResult<IntegrationConfig> {
+        let stored = match self.repo.get_with_secret(integration_id).await {
+            Ok(stored) if stored.config.kind == IntegrationKind::InboundWebhook => stored,
+            Ok(_) | Err(AppError::NotFound(_)) => {
+                return Err(AppError::NotFound(format!(
+                    "Inbound integration not found: {}",
+                    integration_id
+                )))
+            }
+            Err(error) => return Err(error),
+        };
+        let signature = signature.ok_or_else(|| {
+            AppError::Authentication(format!("Missing {} header", SIGNATURE_HEADER))
+        })?;
+        // A request signed with a rotated-out secret passes during its grace
+        // period, as outbound deliveries do.
+        let mut verified = Err(SignatureError::Mismatch);
+        for secret in self.live_signing_secrets(&stored, now * 1000) {
+            verified = verify_signature(&secret, signature, body, now, DEFAULT_TOLERANCE_SECS);
+            if verified.is_ok() {
+                break;
+            }
+        }
+        verified
+            .map_err(|error| AppError::Authentication(format!("Invalid signature: {}", error)))?;
+        if stored.config.status != IntegrationStatus::Active || stored.config.deleted_at.is_some() {
+            return Err(AppError::Authorization(format!(
+                "Integration {} is not active",
+                integration_id
+            )));
+        }
+        Ok(stored.config)
+    }
+
+    /// The stored response to an earlier request with this idempotency key.
+    pub async fn find_inbound_request(
+        &self,
+        integration_id: &str,
+        idempotency_key: &str,
+    ) -> AppResult<Option<InboundRequestLogEntry>> {
+        self.repo
+            .find_inbound_request(integration_id, idempotency_key)
+            .await
+    }
+
+    pub async fn record_inbound_request(&self, entry: &InboundRequestLogEntry) -> AppResult<()> {
+        self.repo.record_inbound_request(entry).await
+    }
+
+    /// The requests an inbound integration has received, with the responses
+    /// they got.
+    pub async fn list_inbound_requests(
+        &self,
+        _ctx: &RequestContext,
+        id: &str,
+        request: ListInboundRequestsRequest,
+    ) -> AppResult<PaginatedResult<InboundRequestLogEntry>> {
+        self.repo.get(id).await?;
+        let (entries, total) = self.repo.list_inbound_requests(id, &request).await?;
+        Ok(PaginatedResult::new(
+            entries,
+            PaginationInfo::new(
+                request.pagination.page(),
+                request.pagination.page_size(),
+                total,
+            ),
+        ))
+    }
+
     pub async fn delete(&self, _ctx: &RequestContext, id: &str) -> AppResult<IntegrationConfig> {
         let mut integration = self.repo.get(id).await?;
         integration.status = IntegrationStatus::Disabled;
@@ -183,31 +484,298 @@ impl IntegrationsService {
         Ok(())
     }
 
-    fn encrypt_secret(&self, secret: &str) -> String {
-        let key = std::env::var("RPMA_DB_KEY").unwrap_or_else(|_| "rpma-dev-key".to_string());
-        let digest = Sha256::digest(key.as_bytes());
-        let encrypted: Vec<u8> = secret
-            .as_bytes()
-            .iter()
-            .enumerate()
-            .map(|(index, byte)| byte ^ digest[index % digest.len()])
-            .collect();
-        BASE64.encode(encrypted)
+    fn encrypt_secret(&self, secret: &str) -> AppResult<String> {
+        secrets_vault().seal(secret)
     }
 
+    /// `None` when the value cannot be opened (retired key, tampering); the
+    /// delivery then goes out without that credential.
     fn decrypt_secret(&self, cipher_text: &str) -> Option<String> {
-        let key = std::env::var("RPMA_DB_KEY").unwrap_or_else(|_| "rpma-dev-key".to_string());
-        let digest = Sha256::digest(key.as_bytes());
-        let decoded = BASE64.decode(cipher_text).ok()?;
-        let decrypted: Vec<u8> = decoded
+        secrets_vault()
+            .open(cipher_text)
+            .map_err(|error| tracing::warn!("Failed to decrypt integration secret: {}", error))
+            .ok()
+    }
+
+    /// Secrets that sign a delivery sent at `now`: the current one, plus the
+    /// previous one during its grace period.
+    fn live_signing_secrets(&self, stored: &StoredIntegrationSecret, now: i64) -> Vec<String> {
+        let previous_live = stored
+            .config
+            .previous_signing_secret_expires_at
+            .is_some_and(|expires_at| expires_at > now);
+        stored
+            .signing_secret
             .iter()
-            .enumerate()
-            .map(|(index, byte)| byte ^ digest[index % digest.len()])
-            .collect();
-        String::from_utf8(decrypted).ok()
+            .chain(
+                stored
+                    .previous_signing_secret
+                    .iter()
+                    .filter(|_| previous_live),
+            )
+            .filter_map(|value| self.decrypt_secret(value))
+            .collect()
+    }
+
+    /// Make one attempt at a delivery: send it, log the attempt, then mark
+    /// the delivery delivered, retrying or dead-lettered per the
+    /// integration's retry policy.
+    async fn deliver(&self, delivery: &OutboundDelivery) -> AppResult<DeliveryAttempt> {
+        let integration = self.repo.get_with_secret(&delivery.integration_id).await?;
+        let policy = &integration.config.retry_policy;
+        let rendered = match &integration.config.payload_template {
+            Some(template) => {
+                template.render(&delivery.event_name, &delivery.id, &delivery.payload)
+            }
+            None => delivery.payload.clone(),
+        };
+        // Sign the exact bytes that are sent.
+        let body = integration.config.payload_format.encode(&rendered)?;
+        let method = match integration.config.http_method {
+            IntegrationHttpMethod::Post => reqwest::Method::POST,
+            IntegrationHttpMethod::Put => reqwest::Method::PUT,
+            IntegrationHttpMethod::Patch => reqwest::Method::PATCH,
+        };
+        let mut request = self
+            .http_client
+            .request(method, &integration.config.endpoint_url)
+            .timeout(Duration::from_secs(u64::from(policy.timeout_seconds)));
+        for (key, value) in &integration.config.headers {
+            if !RESERVED_HEADERS
+                .iter()
+                .any(|reserved| reserved.eq_ignore_ascii_case(key))
+            {
+                request = request.header(key, value);
+            }
+        }
+        if let Some(secret) = integration
+            .secret_token
+            .as_deref()
+            .and_then(|value| self.decrypt_secret(value))
+        {
+            request = request.bearer_auth(secret);
+        }
+
+        let now = Utc::now().timestamp_millis();
+        let signing_secrets = self.live_signing_secrets(&integration, now);
+        if !signing_secrets.is_empty() {
+            let secrets: Vec<&str> = signing_secrets.iter().map(String::as_str).collect();
+            request = request.header(
+                SIGNATURE_HEADER,
+                signature_header(&secrets, now / 1000, &body),
+            );
+        }
+        let started = Instant::now();
+        let result = request
+            .header(
+                reqwest::header::CONTENT_TYPE,
+                integration.config.payload_format.content_type(),
+            )
+            .header(DELIVERY_ID_HEADER, &delivery.id)
+            .header(EVENT_HEADER, &delivery.event_name)
+            .header(
+                IDEMPOTENCY_KEY_HEADER,
+                delivery.idempotency_key.as_deref().unwrap_or(&delivery.id),
+            )
+            .body(body)
+            .send()
+            .await;
+        let (response_status, response_excerpt, error) = match result {
+            Ok(response) => {
+                let status = response.status();
+                let excerpt = match read_excerpt(response).await {
+                    Ok(bytes) => Some(body_excerpt(&bytes)),
+                    Err(error) => {
+                        tracing::debug!("Failed to read webhook response body: {}", error);
+                        None
+                    }
+                };
+                let error = (!status.is_success()).then(|| format!("HTTP {}", status));
+                (Some(status.as_u16()), excerpt, error)
+            }
+            Err(error) => (None, None, Some(error.to_string())),
+        };
+        let latency_ms = started.elapsed().as_millis() as i64;
+
+        let attempt = self
+            .repo
+            .record_attempt(&DeliveryAttempt {
+                id: Uuid::new_v4().to_string(),
+                delivery_id: delivery.id.clone(),
+                integration_id: delivery.integration_id.clone(),
+                attempt_number: delivery.attempt_count + 1,
+                succeeded: error.is_none(),
+                response_status,
+                response_excerpt,
+                error: error.clone(),
+                latency_ms,
+                attempted_at: now,
+            })
+            .await?;
+        let attempts = delivery.attempt_count + 1;
+        match error {
+            None => {
+                self.repo
+                    .mark_delivery_result(
+                        &delivery.id,
+                        DeliveryStatus::Delivered,
+                        attempts,
+                        None,
+                        None,
+                        now,
+                    )
+                    .await?;
+                self.repo
+                    .record_delivery_outcome(&delivery.integration_id, true)
+                    .await?;
+            }
+            Some(error) => {
+                let (status, next_retry_at) = if policy.is_exhausted(attempts) {
+                    (DeliveryStatus::DeadLetter, None)
+                } else {
+                    let jitter = rand::thread_rng().gen::<f64>();
+                    (
+                        DeliveryStatus::Retrying,
+                        Some(now + policy.retry_delay_ms(attempts, jitter)),
+                    )
+                };
+                self.repo
+                    .mark_delivery_result(
+                        &delivery.id,
+                        status,
+                        attempts,
+                        Some(error.clone()),
+                        next_retry_at,
+                        now,
+                    )
+                    .await?;
+                let failures = self
+                    .repo
+                    .record_delivery_outcome(&delivery.integration_id, false)
+                    .await?;
+                if policy.should_disable(failures) {
+                    self.disable_failing(&integration.config, failures, &error, now)
+                        .await?;
+                }
+            }
+        }
+        Ok(attempt)
+    }
+
+    /// Switch off an integration whose deliveries keep failing, and tell
+    /// the admins. Its queued deliveries wait until it is re-enabled.
+    async fn disable_failing(
+        &self,
+        integration: &IntegrationConfig,
+        failures: i64,
+        last_error: &str,
+        now: i64,
+    ) -> AppResult<()> {
+        let reason = format!(
+            "Disabled after {} consecutive failed deliveries; last error: {}",
+            failures, last_error
+        );
+        if self
+            .repo
+            .disable_integration(&integration.id, &reason, now)
+            .await?
+        {
+            tracing::warn!(
+                integration_id = %integration.id,
+                failures,
+                "Integration disabled after sustained delivery failures"
+            );
+            publish_event(event_factory::integration_disabled(
+                integration.id.clone(),
+                integration.name.clone(),
+                failures,
+                reason,
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Read a response body only until it is longer than an excerpt, so a
+/// large or endless body is not buffered.
+async fn read_excerpt(mut response: reqwest::Response) -> reqwest::Result<Vec<u8>> {
+    let mut body = Vec::new();
+    while body.len() <= RESPONSE_EXCERPT_BYTES {
+        match response.chunk().await? {
+            Some(chunk) => body.extend_from_slice(&chunk),
+            None => break,
+        }
+    }
+    Ok(body)
+}
+
+/// The start of a response body, cut on a character boundary.
+fn body_excerpt(body: &[u8]) -> String {
+    let text = String::from_utf8_lossy(&body[..body.len().min(RESPONSE_EXCERPT_BYTES)]);
+    // A multi-byte character split by the cut decodes as U+FFFD; drop it.
+    if body.len() > RESPONSE_EXCERPT_BYTES {
+        text.trim_end_matches('\u{FFFD}').to_string()
+    } else {
+        text.into_owned()
     }
 }
 
+/// Inbound integrations subscribe to the actions they may perform.
+fn validate_subscribed_events(kind: &IntegrationKind, events: &[String]) -> AppResult<()> {
+    if *kind != IntegrationKind::InboundWebhook {
+        return Ok(());
+    }
+    match events
+        .iter()
+        .find(|event| !INBOUND_ACTIONS.contains(&event.as_str()))
+    {
+        Some(event) => Err(AppError::Validation(format!(
+            "Unknown inbound action '{}'; expected one of: {}",
+            event,
+            INBOUND_ACTIONS.join(", ")
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Validate a template; an empty one means no template.
+fn normalize_template(template: Option<PayloadTemplate>) -> AppResult<Option<PayloadTemplate>> {
+    match template {
+        Some(template) if !template.is_empty() => {
+            template.validate()?;
+            Ok(Some(template))
+        }
+        _ => Ok(None),
+    }
+}
+
+fn generate_signing_secret() -> String {
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    let hex: String = bytes.iter().map(|byte| format!("{:02x}", byte)).collect();
+    format!("whsec_{}", hex)
+}
+
+/// Stable per integration and event occurrence, so re-queuing the same event
+/// (e.g. a replayed domain event) does not deliver it twice.
+fn idempotency_key(integration_id: &str, request: &IntegrationDispatchRequest) -> String {
+    let mut hasher = Sha256::new();
+    for part in [
+        integration_id,
+        &request.event_name,
+        &request.correlation_id,
+        &request.payload.to_string(),
+    ] {
+        hasher.update(part.as_bytes());
+        hasher.update([0u8]);
+    }
+    hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
 #[async_trait]
 impl IntegrationEventSink for IntegrationsService {
     async fn enqueue(&self, request: IntegrationDispatchRequest) -> Result<usize, AppError> {
@@ -219,14 +787,17 @@ impl IntegrationEventSink for IntegrationsService {
             )
             .await?;
         let now = Utc::now().timestamp_millis();
+        let mut queued = 0usize;
         for integration in &integrations {
-            self.repo
+            let inserted = self
+                .repo
                 .store_delivery(&DeliveryRecord {
                     id: Uuid::new_v4().to_string(),
                     integration_id: integration.id.clone(),
                     event_name: request.event_name.clone(),
                     payload: request.payload.clone(),
                     correlation_id: request.correlation_id.clone(),
+                    idempotency_key: idempotency_key(&integration.id, &request),
                     status: DeliveryStatus::Pending,
                     attempt_count: 0,
                     last_error: None,
@@ -235,92 +806,18 @@ impl IntegrationEventSink for IntegrationsService {
                     updated_at: now,
                 })
                 .await?;
+            if inserted {
+                queued += 1;
+            }
         }
-        Ok(integrations.len())
+        Ok(queued)
     }
 
     async fn process_pending(&self, limit: usize) -> Result<usize, AppError> {
         let due = self.repo.list_due_deliveries(limit).await?;
         let mut processed = 0usize;
         for delivery in due {
-            let integration = self.repo.get_with_secret(&delivery.integration_id).await?;
-            let mut request = self
-                .http_client
-                .post(&integration.config.endpoint_url)
-                .json(&delivery.payload);
-            for (key, value) in &integration.config.headers {
-                request = request.header(key, value);
-            }
-            if let Some(secret) = integration
-                .secret_token
-                .as_deref()
-                .and_then(|value| self.decrypt_secret(value))
-            {
-                request = request.bearer_auth(secret);
-            }
-
-            let now = Utc::now().timestamp_millis();
-            let result = request.send().await;
-            match result {
-                Ok(response) if response.status().is_success() => {
-                    self.repo
-                        .mark_delivery_result(
-                            &delivery.id,
-                            DeliveryStatus::Delivered,
-                            delivery.attempt_count + 1,
-                            None,
-                            None,
-                            now,
-                        )
-                        .await?;
-                }
-                Ok(response) => {
-                    let attempts = delivery.attempt_count + 1;
-                    let status = if attempts >= 5 {
-                        DeliveryStatus::DeadLetter
-                    } else {
-                        DeliveryStatus::Retrying
-                    };
-                    let next_retry_at = if status == DeliveryStatus::DeadLetter {
-                        None
-                    } else {
-                        Some(now + attempts * 60_000)
-                    };
-                    self.repo
-                        .mark_delivery_result(
-                            &delivery.id,
-                            status,
-                            attempts,
-                            Some(format!("HTTP {}", response.status())),
-                            next_retry_at,
-                            now,
-                        )
-                        .await?;
-                }
-                Err(error) => {
-                    let attempts = delivery.attempt_count + 1;
-                    let status = if attempts >= 5 {
-                        DeliveryStatus::DeadLetter
-                    } else {
-                        DeliveryStatus::Retrying
-                    };
-                    let next_retry_at = if status == DeliveryStatus::DeadLetter {
-                        None
-                    } else {
-                        Some(now + attempts * 60_000)
-                    };
-                    self.repo
-                        .mark_delivery_result(
-                            &delivery.id,
-                            status,
-                            attempts,
-                            Some(error.to_string()),
-                            next_retry_at,
-                            now,
-                        )
-                        .await?;
-                }
-            }
+            self.deliver(&delivery).await?;
             processed += 1;
         }
         Ok(processed)
@@ -1 +1,2 @@
+pub(crate) mod inbound_webhook_service;
 pub(crate) mod integrations_service;
@@ -0,0 +1,183 @@
+//! Inbound webhooks.
+//!
+//! An inbound integration lets an external system, such as the booking site,
+//! act on the workshop through the local listener:
+//!
+//! ```text
+//! POST /integrations/{integration_id}/tasks                       create a task
+//! POST /integrations/{integration_id}/clients                     create or update a client by email
+//! POST /integrations/{integration_id}/quotes/{quote_id}/response  accept, reject or request changes
+//! ```
+//!
+//! Bodies are JSON. Requests are signed like outbound deliveries (see
+//! `webhook_signature`) with the integration's signing secret, and must
+//! carry an `Idempotency-Key`: a retry gets the first response back instead
+//! of acting twice. The integration's `subscribed_events` lists the actions
+//! it may perform.
+
+use serde::Deserialize;
+use std::collections::HashMap;
+
+use crate::shared::error::AppError;
+
+/// Actions an inbound integration can be allowed to perform.
+pub const INBOUND_ACTIONS: [&str; 3] = ["create_task", "upsert_client", "respond_to_quote"];
+
+/// Optional header carrying the sender's correlation id.
+pub const CORRELATION_ID_HEADER: &str = "X-Correlation-Id";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InboundAction {
+    CreateTask,
+    UpsertClient,
+    RespondToQuote { quote_id: String },
+}
+
+impl InboundAction {
+    /// The action's name in `INBOUND_ACTIONS`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            InboundAction::CreateTask => "create_task",
+            InboundAction::UpsertClient => "upsert_client",
+            InboundAction::RespondToQuote { .. } => "respond_to_quote",
+        }
+    }
+}
+
+/// Which integration a request is for and what it asks to do.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InboundRoute {
+    pub integration_id: String,
+    pub action: InboundAction,
+}
+
+impl InboundRoute {
+    /// `None` for any other method or path.
+    pub fn parse(method: &str, path: &str) -> Option<Self> {
+        if method != "POST" {
+            return None;
+        }
+        let path = path.split('?').next().unwrap_or_default();
+        let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+        let (integration_id, action) = match segments.as_slice() {
+            ["integrations", id, "tasks"] => (*id, InboundAction::CreateTask),
+            ["integrations", id, "clients"] => (*id, InboundAction::UpsertClient),
+            ["integrations", id, "quotes", quote_id, "response"] if !quote_id.is_empty() => (
+                *id,
+                InboundAction::RespondToQuote {
+                    quote_id: quote_id.to_string(),
+                },
+            ),
+            _ => return None,
+        };
+        if integration_id.is_empty() {
+            return None;
+        }
+        Some(Self {
+            integration_id: integration_id.to_string(),
+            action,
+        })
+    }
+}
+
+/// A request as read by the listener.
+#[derive(Debug, Clone, Default)]
+pub struct InboundHttpRequest {
+    pub method: String,
+    pub path: String,
+    /// Keyed by lower-cased header name.
+    pub headers: HashMap<String, String>,
+    pub body: Vec<u8>,
+    pub remote_addr: Option<String>,
+}
+
+impl InboundHttpRequest {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .get(&name.to_ascii_lowercase())
+            .map(String::as_str)
+    }
+}
+
+/// The listener's answer, sent as a JSON body.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InboundHttpResponse {
+    pub status: u16,
+    pub body: serde_json::Value,
+}
+
+impl InboundHttpResponse {
+    pub fn new(status: u16, body: serde_json::Value) -> Self {
+        Self { status, body }
+    }
+
+    /// `{"error": {"code", "message"}}` with the error's HTTP status.
+    /// Server-side details are not sent.
+    pub fn error(error: &AppError) -> Self {
+        let sanitized = error.clone().sanitize_for_frontend();
+        Self::new(
+            error.http_status(),
+            serde_json::json!({
+                "error": { "code": error.code(), "message": sanitized.to_string() }
+            }),
+        )
+    }
+}
+
+/// How the customer answered a quote.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QuoteDecision {
+    Accepted,
+    Rejected,
+    ChangesRequested,
+}
+
+/// Body of `POST .../quotes/{quote_id}/response`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct InboundQuoteResponse {
+    pub decision: QuoteDecision,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_routes_are_parsed_from_method_and_path() {
+        assert_eq!(
+            InboundRoute::parse("POST", "/integrations/int-1/tasks"),
+            Some(InboundRoute {
+                integration_id: "int-1".to_string(),
+                action: InboundAction::CreateTask,
+            })
+        );
+        assert_eq!(
+            InboundRoute::parse("POST", "/integrations/int-1/clients/?source=booking")
+                .map(|route| route.action),
+            Some(InboundAction::UpsertClient)
+        );
+        assert_eq!(
+            InboundRoute::parse("POST", "/integrations/int-1/quotes/q-7/response")
+                .map(|route| route.action),
+            Some(InboundAction::RespondToQuote {
+                quote_id: "q-7".to_string()
+            })
+        );
+        for (method, path) in [
+            ("GET", "/integrations/int-1/tasks"),
+            ("POST", "/integrations//tasks"),
+            ("POST", "/integrations/int-1/quotes//response"),
+            ("POST", "/integrations/int-1/invoices"),
+            ("POST", "/"),
+        ] {
+            assert_eq!(
+                InboundRoute::parse(method, path),
+                None,
+                "{} {}",
+                method,
+                path
+            );
+        }
+    }
+}
@@ -1 +1,5 @@
+pub(crate) mod inbound;
 pub(crate) mod models;
+pub(crate) mod payload_template;
+pub(crate) mod retry_policy;
+pub(crate) mod webhook_signature;
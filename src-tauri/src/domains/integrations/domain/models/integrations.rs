@@ -1,10 +1,17 @@
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+use crate::shared::repositories::base::PaginationParams;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
 #[serde(rename_all = "snake_case")]
 pub enum IntegrationKind {
+    /// Sends subscribed events to `endpoint_url`.
+    #[default]
     Webhook,
+    /// Receives signed requests on the local listener; `subscribed_events`
+    /// lists the actions it may perform (see `domain::inbound`).
+    InboundWebhook,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
@@ -25,6 +32,73 @@ pub enum DeliveryStatus {
     DeadLetter,
 }
 
+/// HTTP method of outbound deliveries.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum IntegrationHttpMethod {
+    #[default]
+    Post,
+    Put,
+    Patch,
+}
+
+/// Encoding of the delivery body.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum PayloadFormat {
+    /// `application/json`.
+    #[default]
+    Json,
+    /// `application/x-www-form-urlencoded`; nested values are sent as JSON
+    /// strings.
+    Form,
+}
+
+/// Reshapes the event payload before it is sent. Without a template the
+/// payload goes out as is.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, TS)]
+pub struct PayloadTemplate {
+    /// Body to send, where `{{path}}` placeholders are filled from
+    /// `event`, `delivery_id` and `payload` (e.g. `{{payload.task_id}}`).
+    #[ts(type = "JsonValue | null")]
+    pub body: Option<serde_json::Value>,
+    /// Without `body`: payload paths to keep, all other fields are dropped.
+    #[serde(default)]
+    pub include_fields: Vec<String>,
+    /// Added to the top level of the body, over rendered fields.
+    #[serde(default)]
+    #[ts(type = "Record<string, JsonValue>")]
+    pub static_fields: serde_json::Map<String, serde_json::Value>,
+}
+
+/// How an integration's failed deliveries are retried.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+pub struct IntegrationRetryPolicy {
+    /// Attempts before a delivery goes to the dead letter queue.
+    pub max_attempts: u32,
+    /// Delay before the first retry; doubles with each further attempt.
+    pub backoff_base_seconds: u32,
+    /// Upper bound for the delay between two attempts.
+    pub backoff_max_seconds: u32,
+    /// Per-request timeout.
+    pub timeout_seconds: u32,
+    /// Consecutive failed attempts after which the integration is disabled;
+    /// `0` never disables it.
+    pub disable_after_failures: u32,
+}
+
+impl Default for IntegrationRetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            backoff_base_seconds: 60,
+            backoff_max_seconds: 3600,
+            timeout_seconds: 10,
+            disable_after_failures: 20,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 pub struct IntegrationConfig {
     pub id: String,
@@ -37,21 +111,45 @@ pub struct IntegrationConfig {
     pub headers: std::collections::HashMap<String, String>,
     pub subscribed_events: Vec<String>,
     pub has_secret: bool,
+    /// Deliveries carry an `X-Rpma-Signature` header.
+    pub has_signing_secret: bool,
+    /// End of the grace period during which the previous signing secret
+    /// still signs deliveries.
+    pub previous_signing_secret_expires_at: Option<i64>,
+    pub retry_policy: IntegrationRetryPolicy,
+    pub http_method: IntegrationHttpMethod,
+    pub payload_format: PayloadFormat,
+    pub payload_template: Option<PayloadTemplate>,
+    /// Failed attempts since the last successful delivery.
+    pub consecutive_failures: i64,
+    /// Why the integration was disabled automatically, until it is re-enabled.
+    pub disabled_reason: Option<String>,
     pub last_tested_at: Option<i64>,
     pub created_at: i64,
     pub updated_at: i64,
     pub deleted_at: Option<i64>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
 pub struct CreateIntegrationRequest {
     pub name: String,
+    #[serde(default)]
+    pub kind: IntegrationKind,
     pub description: Option<String>,
     pub endpoint_url: String,
     #[ts(type = "Record<string, string>")]
     pub headers: std::collections::HashMap<String, String>,
     pub subscribed_events: Vec<String>,
     pub secret_token: Option<String>,
+    /// Defaults to `IntegrationRetryPolicy::default()`.
+    #[serde(default)]
+    pub retry_policy: Option<IntegrationRetryPolicy>,
+    #[serde(default)]
+    pub http_method: IntegrationHttpMethod,
+    #[serde(default)]
+    pub payload_format: PayloadFormat,
+    #[serde(default)]
+    pub payload_template: Option<PayloadTemplate>,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
@@ -64,6 +162,12 @@ pub struct UpdateIntegrationRequest {
     pub subscribed_events: Option<Vec<String>>,
     pub status: Option<IntegrationStatus>,
     pub secret_token: Option<String>,
+    #[serde(default)]
+    pub retry_policy: Option<IntegrationRetryPolicy>,
+    pub http_method: Option<IntegrationHttpMethod>,
+    pub payload_format: Option<PayloadFormat>,
+    /// Replaces the template; an empty template removes it.
+    pub payload_template: Option<PayloadTemplate>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
@@ -81,9 +185,107 @@ pub struct OutboundDelivery {
     #[ts(type = "JsonValue")]
     pub payload: serde_json::Value,
     pub status: DeliveryStatus,
+    /// Sent as `Idempotency-Key`; absent on rows queued before signing.
+    pub idempotency_key: Option<String>,
     pub attempt_count: i64,
     pub last_error: Option<String>,
     pub next_retry_at: Option<i64>,
     pub created_at: i64,
     pub updated_at: i64,
 }
+
+/// One attempt at sending a delivery.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct DeliveryAttempt {
+    pub id: String,
+    pub delivery_id: String,
+    pub integration_id: String,
+    pub attempt_number: i64,
+    pub succeeded: bool,
+    /// HTTP status; absent when no response came back.
+    pub response_status: Option<u16>,
+    /// Start of the response body.
+    pub response_excerpt: Option<String>,
+    /// Transport error, e.g. a timeout or a refused connection.
+    pub error: Option<String>,
+    pub latency_ms: i64,
+    pub attempted_at: i64,
+}
+
+/// A delivery with its most recent attempt, for the delivery log.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct DeliveryLogEntry {
+    pub delivery: OutboundDelivery,
+    pub last_attempt: Option<DeliveryAttempt>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+pub struct ListDeliveriesRequest {
+    pub status: Option<DeliveryStatus>,
+    /// Sorted by creation time, newest first unless `sort_order` is `asc`.
+    #[serde(default)]
+    pub pagination: PaginationParams,
+}
+
+/// One request received by an inbound integration.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct InboundRequestLogEntry {
+    pub id: String,
+    pub integration_id: String,
+    /// `create_task`, `upsert_client` or `respond_to_quote`.
+    pub action: String,
+    /// Set when the response is replayed to retries with the same key.
+    pub idempotency_key: Option<String>,
+    pub correlation_id: String,
+    pub response_status: u16,
+    #[ts(type = "JsonValue")]
+    pub response_body: serde_json::Value,
+    /// What the request created or changed, when it succeeded.
+    pub resource_type: Option<String>,
+    pub resource_id: Option<String>,
+    pub error: Option<String>,
+    pub received_at: i64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+pub struct ListInboundRequestsRequest {
+    /// Sorted by reception time, newest first unless `sort_order` is `asc`.
+    #[serde(default)]
+    pub pagination: PaginationParams,
+}
+
+/// Renders a sample event through a template, before it is saved.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct PreviewPayloadRequest {
+    pub event_name: String,
+    pub payload_template: Option<PayloadTemplate>,
+    #[serde(default)]
+    pub payload_format: PayloadFormat,
+    /// Defaults to the payload of the latest queued `event_name` event.
+    #[ts(type = "JsonValue | null")]
+    pub sample_payload: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct PreviewPayloadResponse {
+    pub content_type: String,
+    /// The body exactly as it would be sent.
+    pub body: String,
+    #[ts(type = "JsonValue")]
+    pub sample_payload: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+pub struct RotateSigningSecretRequest {
+    /// How long the current secret keeps signing next to the new one.
+    /// Defaults to 24 hours; `0` retires it immediately.
+    pub grace_period_minutes: Option<u32>,
+}
+
+/// A signing secret, shown to admins so they can configure the receiver.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct SigningSecretResponse {
+    pub integration_id: String,
+    pub signing_secret: String,
+    pub previous_signing_secret_expires_at: Option<i64>,
+}
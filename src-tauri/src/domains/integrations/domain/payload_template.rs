@@ -0,0 +1,275 @@
+//! Payload templates for outbound deliveries.
+//!
+//! A template body is any JSON value. Inside its strings, `{{path}}` is
+//! replaced by the value at a dot-separated path of the render context:
+//!
+//! ```text
+//! { "event": "task_created", "delivery_id": "…", "payload": { …event payload… } }
+//! ```
+//!
+//! A string that is a single placeholder takes the value with its JSON type
+//! (`"{{payload.total}}"` renders `1200`, not `"1200"`); placeholders inside
+//! longer text are rendered as text. Missing paths render as `null`, or as
+//! empty text. Array items are addressed by index (`payload.items.0.name`).
+
+use serde_json::{Map, Value};
+
+use crate::domains::integrations::domain::models::integrations::{PayloadFormat, PayloadTemplate};
+use crate::shared::error::{AppError, AppResult};
+
+impl PayloadTemplate {
+    /// Whether the template leaves the payload unchanged.
+    pub fn is_empty(&self) -> bool {
+        self.body.is_none() && self.include_fields.is_empty() && self.static_fields.is_empty()
+    }
+
+    pub fn validate(&self) -> AppResult<()> {
+        if self.body.is_some() && !self.include_fields.is_empty() {
+            return Err(AppError::Validation(
+                "Payload template include_fields only applies without a body".to_string(),
+            ));
+        }
+        if !self.static_fields.is_empty()
+            && self.body.as_ref().is_some_and(|body| !body.is_object())
+        {
+            return Err(AppError::Validation(
+                "Payload template static_fields need a JSON object body".to_string(),
+            ));
+        }
+        if let Some(field) = self
+            .include_fields
+            .iter()
+            .find(|field| field.split('.').any(str::is_empty))
+        {
+            return Err(AppError::Validation(format!(
+                "Invalid payload template field path: '{}'",
+                field
+            )));
+        }
+        Ok(())
+    }
+
+    /// The body to send for an event.
+    pub fn render(&self, event_name: &str, delivery_id: &str, payload: &Value) -> Value {
+        let mut rendered = match &self.body {
+            Some(body) => {
+                let context = serde_json::json!({
+                    "event": event_name,
+                    "delivery_id": delivery_id,
+                    "payload": payload,
+                });
+                render_value(body, &context)
+            }
+            None if !self.include_fields.is_empty() => {
+                let mut kept = Value::Object(Map::new());
+                for field in &self.include_fields {
+                    if let Some(value) = lookup(payload, field) {
+                        insert_path(&mut kept, field, value.clone());
+                    }
+                }
+                kept
+            }
+            None => payload.clone(),
+        };
+        if let Value::Object(object) = &mut rendered {
+            for (key, value) in &self.static_fields {
+                object.insert(key.clone(), value.clone());
+            }
+        }
+        rendered
+    }
+}
+
+impl PayloadFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            PayloadFormat::Json => "application/json",
+            PayloadFormat::Form => "application/x-www-form-urlencoded",
+        }
+    }
+
+    /// Encode a rendered body. Form encoding sends the top-level fields of
+    /// an object, with nested values as JSON text; any other value is sent
+    /// as a single `payload` field.
+    pub fn encode(&self, body: &Value) -> AppResult<Vec<u8>> {
+        match self {
+            PayloadFormat::Json => {
+                serde_json::to_vec(body).map_err(|error| AppError::Internal(error.to_string()))
+            }
+            PayloadFormat::Form => {
+                let pairs: Vec<(String, String)> = match body {
+                    Value::Object(object) => object
+                        .iter()
+                        .map(|(key, value)| (key.clone(), as_text(value)))
+                        .collect(),
+                    other => vec![("payload".to_string(), other.to_string())],
+                };
+                serde_urlencoded::to_string(&pairs)
+                    .map(String::into_bytes)
+                    .map_err(|error| AppError::Internal(error.to_string()))
+            }
+        }
+    }
+}
+
+fn render_value(template: &Value, context: &Value) -> Value {
+    match template {
+        Value::String(text) => render_string(text, context),
+        Value::Array(items) => Value::Array(
+            items
+                .iter()
+                .map(|item| render_value(item, context))
+                .collect(),
+        ),
+        Value::Object(object) => Value::Object(
+            object
+                .iter()
+                .map(|(key, value)| (key.clone(), render_value(value, context)))
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+fn render_string(text: &str, context: &Value) -> Value {
+    if let Some(path) = sole_placeholder(text) {
+        return lookup(context, path).cloned().unwrap_or(Value::Null);
+    }
+    let mut rendered = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        let Some(length) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        rendered.push_str(&rest[..start]);
+        let path = rest[start + 2..start + 2 + length].trim();
+        if let Some(value) = lookup(context, path) {
+            rendered.push_str(&as_text(value));
+        }
+        rest = &rest[start + 2 + length + 2..];
+    }
+    rendered.push_str(rest);
+    Value::String(rendered)
+}
+
+/// The path of a string made of exactly one placeholder.
+fn sole_placeholder(text: &str) -> Option<&str> {
+    let inner = text.trim().strip_prefix("{{")?.strip_suffix("}}")?;
+    (!inner.contains("{{") && !inner.contains("}}")).then(|| inner.trim())
+}
+
+fn lookup<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.')
+        .try_fold(value, |current, segment| match current {
+            Value::Object(object) => object.get(segment),
+            Value::Array(items) => segment
+                .parse::<usize>()
+                .ok()
+                .and_then(|index| items.get(index)),
+            _ => None,
+        })
+}
+
+fn insert_path(target: &mut Value, path: &str, value: Value) {
+    let mut current = target;
+    let mut segments = path.split('.').peekable();
+    while let Some(segment) = segments.next() {
+        if !current.is_object() {
+            *current = Value::Object(Map::new());
+        }
+        let object = current.as_object_mut().expect("just made an object");
+        if segments.peek().is_none() {
+            object.insert(segment.to_string(), value);
+            return;
+        }
+        current = object
+            .entry(segment.to_string())
+            .or_insert_with(|| Value::Object(Map::new()));
+    }
+}
+
+fn as_text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn payload() -> Value {
+        json!({
+            "task_id": "task-1",
+            "title": "Pose PPF",
+            "total": 1200,
+            "client": { "name": "Garage Martin", "email": "contact@garage.fr" },
+            "items": [{ "name": "Capot" }]
+        })
+    }
+
+    #[test]
+    fn test_body_template_maps_fields_keeping_json_types() {
+        let template = PayloadTemplate {
+            body: Some(json!({
+                "text": "Nouvelle tâche {{payload.title}} pour {{payload.client.name}}",
+                "amount": "{{payload.total}}",
+                "first_item": "{{ payload.items.0.name }}",
+                "missing": "{{payload.nope}}",
+                "kind": "{{event}}",
+                "source": "overridden"
+            })),
+            static_fields: Map::from_iter([("source".to_string(), json!("rpma"))]),
+            ..Default::default()
+        };
+        assert!(template.validate().is_ok());
+        assert_eq!(
+            template.render("task_created", "delivery-1", &payload()),
+            json!({
+                "text": "Nouvelle tâche Pose PPF pour Garage Martin",
+                "amount": 1200,
+                "first_item": "Capot",
+                "missing": null,
+                "kind": "task_created",
+                "source": "rpma"
+            })
+        );
+    }
+
+    #[test]
+    fn test_include_fields_filter_the_payload() {
+        let template = PayloadTemplate {
+            include_fields: vec![
+                "task_id".to_string(),
+                "client.email".to_string(),
+                "absent".to_string(),
+            ],
+            ..Default::default()
+        };
+        assert_eq!(
+            template.render("task_created", "delivery-1", &payload()),
+            json!({ "task_id": "task-1", "client": { "email": "contact@garage.fr" } })
+        );
+        assert!(PayloadTemplate {
+            body: Some(json!({})),
+            include_fields: vec!["task_id".to_string()],
+            ..Default::default()
+        }
+        .validate()
+        .is_err());
+    }
+
+    #[test]
+    fn test_form_encoding_flattens_top_level_fields() {
+        let body =
+            json!({ "text": "a b&c", "total": 1200, "client": { "name": "M" }, "none": null });
+        let encoded = String::from_utf8(PayloadFormat::Form.encode(&body).unwrap()).unwrap();
+        assert_eq!(
+            encoded,
+            "client=%7B%22name%22%3A%22M%22%7D&none=&text=a+b%26c&total=1200"
+        );
+    }
+}
@@ -0,0 +1,121 @@
+//! Retry schedule for outbound deliveries.
+//!
+//! A failed attempt `n` (1-based) is retried after
+//! `min(backoff_base * 2^(n-1), backoff_max)`, of which the upper half is
+//! randomised ("equal jitter"): retries of deliveries that failed together
+//! spread out instead of hitting a recovering receiver at the same moment,
+//! while never coming sooner than half the nominal delay.
+
+use crate::domains::integrations::domain::models::integrations::IntegrationRetryPolicy;
+use crate::shared::error::{AppError, AppResult};
+
+pub const MAX_ATTEMPTS_LIMIT: u32 = 50;
+pub const MAX_BACKOFF_SECONDS: u32 = 7 * 24 * 60 * 60;
+pub const MAX_TIMEOUT_SECONDS: u32 = 120;
+
+impl IntegrationRetryPolicy {
+    pub fn validate(&self) -> AppResult<()> {
+        if !(1..=MAX_ATTEMPTS_LIMIT).contains(&self.max_attempts) {
+            return Err(AppError::Validation(format!(
+                "Retry policy max_attempts must be between 1 and {}",
+                MAX_ATTEMPTS_LIMIT
+            )));
+        }
+        if self.backoff_base_seconds == 0 {
+            return Err(AppError::Validation(
+                "Retry policy backoff_base_seconds must be at least 1".to_string(),
+            ));
+        }
+        if self.backoff_max_seconds < self.backoff_base_seconds
+            || self.backoff_max_seconds > MAX_BACKOFF_SECONDS
+        {
+            return Err(AppError::Validation(format!(
+                "Retry policy backoff_max_seconds must be between backoff_base_seconds and {}",
+                MAX_BACKOFF_SECONDS
+            )));
+        }
+        if !(1..=MAX_TIMEOUT_SECONDS).contains(&self.timeout_seconds) {
+            return Err(AppError::Validation(format!(
+                "Retry policy timeout_seconds must be between 1 and {}",
+                MAX_TIMEOUT_SECONDS
+            )));
+        }
+        Ok(())
+    }
+
+    /// Whether a delivery that has failed `attempts` times is given up on.
+    pub fn is_exhausted(&self, attempts: i64) -> bool {
+        attempts >= i64::from(self.max_attempts)
+    }
+
+    /// Delay before retrying after failed attempt `attempt` (1-based).
+    /// `jitter` is a random value in `[0, 1)`.
+    pub fn retry_delay_ms(&self, attempt: i64, jitter: f64) -> i64 {
+        let doublings = attempt.saturating_sub(1).clamp(0, 32) as u32;
+        let nominal = (u64::from(self.backoff_base_seconds) << doublings)
+            .min(u64::from(self.backoff_max_seconds)) as i64
+            * 1000;
+        let half = nominal / 2;
+        nominal - half + (half as f64 * jitter.clamp(0.0, 1.0)) as i64
+    }
+
+    /// Whether `consecutive_failures` failed attempts in a row should
+    /// disable the integration.
+    pub fn should_disable(&self, consecutive_failures: i64) -> bool {
+        self.disable_after_failures > 0
+            && consecutive_failures >= i64::from(self.disable_after_failures)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_delay_doubles_up_to_the_cap_with_bounded_jitter() {
+        let policy = IntegrationRetryPolicy {
+            backoff_base_seconds: 60,
+            backoff_max_seconds: 600,
+            ..Default::default()
+        };
+        assert_eq!(policy.retry_delay_ms(1, 0.0), 30_000);
+        assert_eq!(policy.retry_delay_ms(1, 0.999_999), 59_999);
+        assert_eq!(policy.retry_delay_ms(2, 0.0), 60_000);
+        assert_eq!(policy.retry_delay_ms(3, 0.5), 180_000);
+        assert_eq!(policy.retry_delay_ms(4, 1.0), 480_000);
+        // 60 * 2^4 = 960 s is over the 600 s cap.
+        assert_eq!(policy.retry_delay_ms(5, 1.0), 600_000);
+        assert_eq!(policy.retry_delay_ms(40, 1.0), 600_000);
+    }
+
+    #[test]
+    fn test_policy_limits() {
+        assert!(IntegrationRetryPolicy::default().validate().is_ok());
+        for invalid in [
+            IntegrationRetryPolicy {
+                max_attempts: 0,
+                ..Default::default()
+            },
+            IntegrationRetryPolicy {
+                backoff_base_seconds: 120,
+                backoff_max_seconds: 60,
+                ..Default::default()
+            },
+            IntegrationRetryPolicy {
+                timeout_seconds: 0,
+                ..Default::default()
+            },
+        ] {
+            assert!(invalid.validate().is_err(), "{:?}", invalid);
+        }
+
+        let policy = IntegrationRetryPolicy {
+            max_attempts: 3,
+            disable_after_failures: 0,
+            ..Default::default()
+        };
+        assert!(!policy.is_exhausted(2));
+        assert!(policy.is_exhausted(3));
+        assert!(!policy.should_disable(1_000));
+    }
+}
@@ -0,0 +1,199 @@
+//! Local HTTP listener for inbound webhooks.
+//!
+//! Off unless `RPMA_INBOUND_WEBHOOK_ADDR` is set (e.g. `127.0.0.1:8787`).
+//! It speaks plain HTTP/1.1, one request per connection, and is meant to sit
+//! behind a tunnel or reverse proxy that terminates TLS.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::Semaphore;
+
+use crate::domains::integrations::domain::inbound::{InboundHttpRequest, InboundHttpResponse};
+use crate::shared::error::AppError;
+
+/// Address to listen on; the listener is not started when unset.
+pub const INBOUND_ADDR_ENV: &str = "RPMA_INBOUND_WEBHOOK_ADDR";
+
+const MAX_HEAD_BYTES: usize = 16 * 1024;
+const MAX_BODY_BYTES: usize = 1024 * 1024;
+const MAX_HEADERS: usize = 64;
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+/// Connections handled at once; further ones wait in the accept backlog.
+const MAX_CONNECTIONS: usize = 32;
+/// Pause after a failed accept (e.g. out of file descriptors) so the loop
+/// does not spin.
+const ACCEPT_RETRY_DELAY: Duration = Duration::from_millis(100);
+
+/// Accept connections, answering each request with `handler`.
+pub async fn serve<H, F>(listener: TcpListener, handler: H)
+where
+    H: Fn(InboundHttpRequest) -> F + Clone + Send + Sync + 'static,
+    F: Future<Output = InboundHttpResponse> + Send,
+{
+    let connections = Arc::new(Semaphore::new(MAX_CONNECTIONS));
+    loop {
+        let Ok(permit) = connections.clone().acquire_owned().await else {
+            return;
+        };
+        let (stream, remote_addr) = match listener.accept().await {
+            Ok(connection) => connection,
+            Err(error) => {
+                tracing::warn!("Inbound webhook listener failed to accept: {}", error);
+                tokio::time::sleep(ACCEPT_RETRY_DELAY).await;
+                continue;
+            }
+        };
+        let handler = handler.clone();
+        tokio::spawn(async move {
+            if let Err(error) =
+                handle_connection(stream, remote_addr.ip().to_string(), handler).await
+            {
+                tracing::debug!("Inbound webhook connection closed: {}", error);
+            }
+            drop(permit);
+        });
+    }
+}
+
+async fn handle_connection<H, F>(
+    mut stream: TcpStream,
+    remote_addr: String,
+    handler: H,
+) -> std::io::Result<()>
+where
+    H: Fn(InboundHttpRequest) -> F,
+    F: Future<Output = InboundHttpResponse>,
+{
+    let response = match tokio::time::timeout(READ_TIMEOUT, read_request(&mut stream)).await {
+        Ok(Ok(mut request)) => {
+            request.remote_addr = Some(remote_addr);
+            handler(request).await
+        }
+        Ok(Err(response)) => response,
+        Err(_) => InboundHttpResponse::new(408, error_body("TIMEOUT", "Request timed out")),
+    };
+    write_response(&mut stream, &response).await
+}
+
+async fn read_request(stream: &mut TcpStream) -> Result<InboundHttpRequest, InboundHttpResponse> {
+    let mut buffer = Vec::with_capacity(4096);
+    let mut chunk = [0u8; 4096];
+    loop {
+        let read = stream
+            .read(&mut chunk)
+            .await
+            .map_err(|_| bad_request("Connection error"))?;
+        if read == 0 {
+            return Err(bad_request("Incomplete request"));
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+
+        let mut headers = [httparse::EMPTY_HEADER; MAX_HEADERS];
+        let mut parsed = httparse::Request::new(&mut headers);
+        let head_len = match parsed.parse(&buffer) {
+            Ok(httparse::Status::Complete(head_len)) => head_len,
+            Ok(httparse::Status::Partial) if buffer.len() < MAX_HEAD_BYTES => continue,
+            Ok(httparse::Status::Partial) => {
+                return Err(InboundHttpResponse::new(
+                    431,
+                    error_body("VALIDATION_ERROR", "Request headers too large"),
+                ))
+            }
+            Err(_) => return Err(bad_request("Malformed HTTP request")),
+        };
+
+        let headers: HashMap<String, String> = parsed
+            .headers
+            .iter()
+            .map(|header| {
+                (
+                    header.name.to_ascii_lowercase(),
+                    String::from_utf8_lossy(header.value).into_owned(),
+                )
+            })
+            .collect();
+        let content_length = match headers.get("content-length") {
+            Some(value) => value
+                .trim()
+                .parse::<usize>()
+                .map_err(|_| bad_request("Invalid Content-Length"))?,
+            None => 0,
+        };
+        if content_length > MAX_BODY_BYTES {
+            return Err(InboundHttpResponse::new(
+                413,
+                error_body("VALIDATION_ERROR", "Request body too large"),
+            ));
+        }
+        let method = parsed.method.unwrap_or_default().to_string();
+        let path = parsed.path.unwrap_or_default().to_string();
+
+        let mut body = buffer.split_off(head_len);
+        while body.len() < content_length {
+            let read = stream
+                .read(&mut chunk)
+                .await
+                .map_err(|_| bad_request("Connection error"))?;
+            if read == 0 {
+                return Err(bad_request("Incomplete request body"));
+            }
+            body.extend_from_slice(&chunk[..read]);
+        }
+        body.truncate(content_length);
+        return Ok(InboundHttpRequest {
+            method,
+            path,
+            headers,
+            body,
+            remote_addr: None,
+        });
+    }
+}
+
+async fn write_response(
+    stream: &mut TcpStream,
+    response: &InboundHttpResponse,
+) -> std::io::Result<()> {
+    let body = response.body.to_string();
+    let head = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        response.status,
+        reason_phrase(response.status),
+        body.len()
+    );
+    stream.write_all(head.as_bytes()).await?;
+    stream.write_all(body.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+fn bad_request(message: &str) -> InboundHttpResponse {
+    InboundHttpResponse::error(&AppError::Validation(message.to_string()))
+}
+
+fn error_body(code: &str, message: &str) -> serde_json::Value {
+    serde_json::json!({ "error": { "code": code, "message": message } })
+}
+
+fn reason_phrase(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        201 => "Created",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        408 => "Request Timeout",
+        409 => "Conflict",
+        413 => "Payload Too Large",
+        422 => "Unprocessable Entity",
+        429 => "Too Many Requests",
+        431 => "Request Header Fields Too Large",
+        500 => "Internal Server Error",
+        503 => "Service Unavailable",
+        _ => "",
+    }
+}
@@ -5,14 +5,27 @@ use std::sync::Arc;
 
 use crate::db::Database;
 use crate::domains::integrations::domain::models::integrations::{
-    DeliveryStatus, IntegrationConfig, IntegrationKind, IntegrationStatus, OutboundDelivery,
+    DeliveryAttempt, DeliveryLogEntry, DeliveryStatus, InboundRequestLogEntry, IntegrationConfig,
+    IntegrationKind, IntegrationRetryPolicy, IntegrationStatus, ListDeliveriesRequest,
+    ListInboundRequestsRequest, OutboundDelivery,
 };
 use crate::shared::error::{AppError, AppResult};
 
+const INTEGRATION_COLUMNS: &str = "id, name, description, kind, endpoint_url, headers_json, subscribed_events_json, encrypted_secret, encrypted_signing_secret, encrypted_previous_signing_secret, previous_signing_secret_expires_at, max_attempts, backoff_base_seconds, backoff_max_seconds, timeout_seconds, disable_after_failures, consecutive_failures, disabled_reason, http_method, payload_format, payload_template_json, status, last_tested_at, created_at, updated_at, deleted_at";
+
+const INBOUND_REQUEST_COLUMNS: &str = "id, integration_id, action, idempotency_key, correlation_id, response_status, response_body, resource_type, resource_id, error, received_at";
+
+const DELIVERY_COLUMNS: &str = "id, integration_id, event_name, payload_json, status, idempotency_key, attempt_count, last_error, next_retry_at, created_at, updated_at";
+
+/// Attempt columns, aliased so they can share a row with a delivery.
+const ATTEMPT_COLUMNS: &str = "a.id AS attempt_id, a.delivery_id AS attempt_delivery_id, a.integration_id AS attempt_integration_id, a.attempt_number, a.succeeded, a.response_status, a.response_excerpt, a.error AS attempt_error, a.latency_ms, a.attempted_at";
+
 #[derive(Debug, Clone)]
 pub struct StoredIntegrationSecret {
     pub config: IntegrationConfig,
     pub secret_token: Option<String>,
+    pub signing_secret: Option<String>,
+    pub previous_signing_secret: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -22,6 +35,7 @@ pub struct DeliveryRecord {
     pub event_name: String,
     pub payload: serde_json::Value,
     pub correlation_id: String,
+    pub idempotency_key: String,
     pub status: DeliveryStatus,
     pub attempt_count: i64,
     pub last_error: Option<String>,
@@ -39,6 +53,7 @@ pub trait IntegrationsRepository: Send + Sync {
         &self,
         integration: &IntegrationConfig,
         encrypted_secret: Option<String>,
+        encrypted_signing_secret: String,
     ) -> AppResult<()>;
     async fn update(
         &self,
@@ -46,13 +61,24 @@ pub trait IntegrationsRepository: Send + Sync {
         encrypted_secret: Option<String>,
     ) -> AppResult<()>;
     async fn update_last_tested_at(&self, id: &str, tested_at: i64) -> AppResult<()>;
+    /// Replace the signing secret; `encrypted_previous` keeps signing until
+    /// `previous_expires_at`.
+    async fn update_signing_secrets(
+        &self,
+        id: &str,
+        encrypted_signing_secret: &str,
+        encrypted_previous: Option<String>,
+        previous_expires_at: Option<i64>,
+        updated_at: i64,
+    ) -> AppResult<()>;
     async fn retry_dead_letters(&self, integration_id: &str) -> AppResult<usize>;
     async fn list_active_for_event(
         &self,
         event_name: &str,
         requested_ids: Option<&Vec<String>>,
     ) -> AppResult<Vec<IntegrationConfig>>;
-    async fn store_delivery(&self, delivery: &DeliveryRecord) -> AppResult<()>;
+    /// Queue a delivery; `false` when one with the same idempotency key exists.
+    async fn store_delivery(&self, delivery: &DeliveryRecord) -> AppResult<bool>;
     async fn list_due_deliveries(&self, limit: usize) -> AppResult<Vec<OutboundDelivery>>;
     async fn mark_delivery_result(
         &self,
@@ -63,6 +89,53 @@ pub trait IntegrationsRepository: Send + Sync {
         next_retry_at: Option<i64>,
         updated_at: i64,
     ) -> AppResult<()>;
+    async fn get_delivery(&self, delivery_id: &str) -> AppResult<OutboundDelivery>;
+    /// A page of an integration's deliveries, newest first by default, and
+    /// the total matching count.
+    async fn list_deliveries(
+        &self,
+        integration_id: &str,
+        request: &ListDeliveriesRequest,
+    ) -> AppResult<(Vec<DeliveryLogEntry>, i64)>;
+    async fn list_delivery_attempts(&self, delivery_id: &str) -> AppResult<Vec<DeliveryAttempt>>;
+    /// Log an attempt; its `attempt_number` is assigned from the delivery's
+    /// history, so numbering continues across replays.
+    async fn record_attempt(&self, attempt: &DeliveryAttempt) -> AppResult<DeliveryAttempt>;
+    /// Reset the failure streak after a success, or extend it after a
+    /// failure. Returns the new streak length.
+    async fn record_delivery_outcome(
+        &self,
+        integration_id: &str,
+        succeeded: bool,
+    ) -> AppResult<i64>;
+    /// Disable an active integration; `false` when it was not active.
+    async fn disable_integration(
+        &self,
+        integration_id: &str,
+        reason: &str,
+        updated_at: i64,
+    ) -> AppResult<bool>;
+    /// Queue a delivery again from scratch, keeping its idempotency key.
+    async fn reset_delivery(&self, delivery_id: &str, now: i64) -> AppResult<()>;
+    /// Payload of the most recently queued `event_name` delivery.
+    async fn latest_payload_for_event(
+        &self,
+        event_name: &str,
+    ) -> AppResult<Option<serde_json::Value>>;
+    /// The stored response to an inbound request with this idempotency key.
+    async fn find_inbound_request(
+        &self,
+        integration_id: &str,
+        idempotency_key: &str,
+    ) -> AppResult<Option<InboundRequestLogEntry>>;
+    async fn record_inbound_request(&self, entry: &InboundRequestLogEntry) -> AppResult<()>;
+    /// A page of an integration's inbound requests, newest first by default,
+    /// and the total count.
+    async fn list_inbound_requests(
+        &self,
+        integration_id: &str,
+        request: &ListInboundRequestsRequest,
+    ) -> AppResult<(Vec<InboundRequestLogEntry>, i64)>;
 }
 
 pub struct SqliteIntegrationsRepository {
@@ -78,11 +151,21 @@ impl SqliteIntegrationsRepository {
         let headers: String = row.get("headers_json")?;
         let events: String = row.get("subscribed_events_json")?;
         let status: String = row.get("status")?;
+        let http_method: String = row.get("http_method")?;
+        let payload_format: String = row.get("payload_format")?;
+        let payload_template: Option<String> = row.get("payload_template_json")?;
+        let kind: String = row.get("kind")?;
         Ok(IntegrationConfig {
             id: row.get("id")?,
             name: row.get("name")?,
             description: row.get("description")?,
-            kind: IntegrationKind::Webhook,
+            kind: serde_json::from_str(&format!("\"{}\"", kind)).map_err(|e| {
+                rusqlite::Error::FromSqlConversionFailure(
+                    0,
+                    rusqlite::types::Type::Text,
+                    Box::new(e),
+                )
+            })?,
             status: serde_json::from_str(&format!("\"{}\"", status)).map_err(|e| {
                 rusqlite::Error::FromSqlConversionFailure(
                     0,
@@ -106,6 +189,45 @@ impl SqliteIntegrationsRepository {
                 )
             })?,
             has_secret: row.get::<_, Option<String>>("encrypted_secret")?.is_some(),
+            has_signing_secret: row
+                .get::<_, Option<String>>("encrypted_signing_secret")?
+                .is_some(),
+            previous_signing_secret_expires_at: row.get("previous_signing_secret_expires_at")?,
+            retry_policy: IntegrationRetryPolicy {
+                max_attempts: row.get("max_attempts")?,
+                backoff_base_seconds: row.get("backoff_base_seconds")?,
+                backoff_max_seconds: row.get("backoff_max_seconds")?,
+                timeout_seconds: row.get("timeout_seconds")?,
+                disable_after_failures: row.get("disable_after_failures")?,
+            },
+            http_method: serde_json::from_str(&format!("\"{}\"", http_method)).map_err(|e| {
+                rusqlite::Error::FromSqlConversionFailure(
+                    0,
+                    rusqlite::types::Type::Text,
+                    Box::new(e),
+                )
+            })?,
+            payload_format: serde_json::from_str(&format!("\"{}\"", payload_format)).map_err(
+                |e| {
+                    rusqlite::Error::FromSqlConversionFailure(
+                        0,
+                        rusqlite::types::Type::Text,
+                        Box::new(e),
+                    )
+                },
+            )?,
+            payload_template: payload_template
+                .map(|value| serde_json::from_str(&value))
+                .transpose()
+                .map_err(|e| {
+                    rusqlite::Error::FromSqlConversionFailure(
+                        0,
+                        rusqlite::types::Type::Text,
+                        Box::new(e),
+                    )
+                })?,
+            consecutive_failures: row.get("consecutive_failures")?,
+            disabled_reason: row.get("disabled_reason")?,
             last_tested_at: row.get("last_tested_at")?,
             created_at: row.get("created_at")?,
             updated_at: row.get("updated_at")?,
@@ -113,6 +235,23 @@ impl SqliteIntegrationsRepository {
         })
     }
 
+    fn map_inbound_request(row: &rusqlite::Row<'_>) -> rusqlite::Result<InboundRequestLogEntry> {
+        let response_body: String = row.get("response_body")?;
+        Ok(InboundRequestLogEntry {
+            id: row.get("id")?,
+            integration_id: row.get("integration_id")?,
+            action: row.get("action")?,
+            idempotency_key: row.get("idempotency_key")?,
+            correlation_id: row.get("correlation_id")?,
+            response_status: row.get("response_status")?,
+            response_body: serde_json::from_str(&response_body).unwrap_or_default(),
+            resource_type: row.get("resource_type")?,
+            resource_id: row.get("resource_id")?,
+            error: row.get("error")?,
+            received_at: row.get("received_at")?,
+        })
+    }
+
     fn map_delivery(row: &rusqlite::Row<'_>) -> rusqlite::Result<OutboundDelivery> {
         let status: String = row.get("status")?;
         let payload: String = row.get("payload_json")?;
@@ -134,6 +273,7 @@ impl SqliteIntegrationsRepository {
                     Box::new(e),
                 )
             })?,
+            idempotency_key: row.get("idempotency_key")?,
             attempt_count: row.get("attempt_count")?,
             last_error: row.get("last_error")?,
             next_retry_at: row.get("next_retry_at")?,
@@ -141,6 +281,26 @@ impl SqliteIntegrationsRepository {
             updated_at: row.get("updated_at")?,
         })
     }
+
+    /// Reads the `ATTEMPT_COLUMNS` aliases; `None` for a delivery with no
+    /// attempt yet.
+    fn map_attempt(row: &rusqlite::Row<'_>) -> rusqlite::Result<Option<DeliveryAttempt>> {
+        let Some(id) = row.get::<_, Option<String>>("attempt_id")? else {
+            return Ok(None);
+        };
+        Ok(Some(DeliveryAttempt {
+            id,
+            delivery_id: row.get("attempt_delivery_id")?,
+            integration_id: row.get("attempt_integration_id")?,
+            attempt_number: row.get("attempt_number")?,
+            succeeded: row.get("succeeded")?,
+            response_status: row.get("response_status")?,
+            response_excerpt: row.get("response_excerpt")?,
+            error: row.get("attempt_error")?,
+            latency_ms: row.get("latency_ms")?,
+            attempted_at: row.get("attempted_at")?,
+        }))
+    }
 }
 
 #[async_trait]
@@ -151,12 +311,12 @@ impl IntegrationsRepository for SqliteIntegrationsRepository {
             .get_connection()
             .map_err(|error| AppError::db_sanitized("integrations.list.get_connection", error))?;
         let mut stmt = conn
-            .prepare(
-                "SELECT id, name, description, endpoint_url, headers_json, subscribed_events_json, encrypted_secret, status, last_tested_at, created_at, updated_at, deleted_at
+            .prepare(&format!(
+                "SELECT {INTEGRATION_COLUMNS}
                  FROM integration_configs
                  WHERE deleted_at IS NULL
-                 ORDER BY updated_at DESC",
-            )
+                 ORDER BY updated_at DESC"
+            ))
             .map_err(|error| AppError::db_sanitized("integrations.list.prepare", error))?;
         let rows = stmt
             .query_map([], Self::map_integration)
@@ -175,9 +335,11 @@ impl IntegrationsRepository for SqliteIntegrationsRepository {
             .get_connection()
             .map_err(|error| AppError::db_sanitized("integrations.get.get_connection", error))?;
         conn.query_row(
-            "SELECT id, name, description, endpoint_url, headers_json, subscribed_events_json, encrypted_secret, status, last_tested_at, created_at, updated_at, deleted_at
-             FROM integration_configs
-             WHERE id = ?1 AND deleted_at IS NULL",
+            &format!(
+                "SELECT {INTEGRATION_COLUMNS}
+                 FROM integration_configs
+                 WHERE id = ?1 AND deleted_at IS NULL"
+            ),
             params![id],
             Self::map_integration,
         )
@@ -192,14 +354,20 @@ impl IntegrationsRepository for SqliteIntegrationsRepository {
         })?;
         let record = conn
             .query_row(
-                "SELECT id, name, description, endpoint_url, headers_json, subscribed_events_json, encrypted_secret, status, last_tested_at, created_at, updated_at, deleted_at
-                 FROM integration_configs
-                 WHERE id = ?1 AND deleted_at IS NULL",
+                &format!(
+                    "SELECT {INTEGRATION_COLUMNS}
+                     FROM integration_configs
+                     WHERE id = ?1 AND deleted_at IS NULL"
+                ),
                 params![id],
                 |row| {
                     let config = Self::map_integration(row)?;
-                    let secret_token: Option<String> = row.get("encrypted_secret")?;
-                    Ok(StoredIntegrationSecret { config, secret_token })
+                    Ok(StoredIntegrationSecret {
+                        config,
+                        secret_token: row.get("encrypted_secret")?,
+                        signing_secret: row.get("encrypted_signing_secret")?,
+                        previous_signing_secret: row.get("encrypted_previous_signing_secret")?,
+                    })
                 },
             )
             .optional()
@@ -211,6 +379,7 @@ impl IntegrationsRepository for SqliteIntegrationsRepository {
         &self,
         integration: &IntegrationConfig,
         encrypted_secret: Option<String>,
+        encrypted_signing_secret: String,
     ) -> AppResult<()> {
         let conn = self
             .db
@@ -218,8 +387,10 @@ impl IntegrationsRepository for SqliteIntegrationsRepository {
             .map_err(|error| AppError::db_sanitized("integrations.create.get_connection", error))?;
         conn.execute(
             "INSERT INTO integration_configs (
-                id, name, description, endpoint_url, headers_json, subscribed_events_json, encrypted_secret, status, last_tested_at, created_at, updated_at, deleted_at
-            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+                id, name, description, endpoint_url, headers_json, subscribed_events_json, encrypted_secret, status, last_tested_at, created_at, updated_at, deleted_at, encrypted_signing_secret,
+                max_attempts, backoff_base_seconds, backoff_max_seconds, timeout_seconds, disable_after_failures,
+                http_method, payload_format, payload_template_json, kind
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22)",
             params![
                 integration.id,
                 integration.name,
@@ -233,6 +404,16 @@ impl IntegrationsRepository for SqliteIntegrationsRepository {
                 integration.created_at,
                 integration.updated_at,
                 integration.deleted_at,
+                encrypted_signing_secret,
+                integration.retry_policy.max_attempts,
+                integration.retry_policy.backoff_base_seconds,
+                integration.retry_policy.backoff_max_seconds,
+                integration.retry_policy.timeout_seconds,
+                integration.retry_policy.disable_after_failures,
+                enum_text(&integration.http_method),
+                enum_text(&integration.payload_format),
+                template_json(integration)?,
+                enum_text(&integration.kind),
             ],
         )
         .map_err(|error| AppError::db_sanitized("integrations.create.execute", error))?;
@@ -259,7 +440,17 @@ impl IntegrationsRepository for SqliteIntegrationsRepository {
                  status = ?8,
                  last_tested_at = ?9,
                  updated_at = ?10,
-                 deleted_at = ?11
+                 deleted_at = ?11,
+                 max_attempts = ?12,
+                 backoff_base_seconds = ?13,
+                 backoff_max_seconds = ?14,
+                 timeout_seconds = ?15,
+                 disable_after_failures = ?16,
+                 consecutive_failures = ?17,
+                 disabled_reason = ?18,
+                 http_method = ?19,
+                 payload_format = ?20,
+                 payload_template_json = ?21
              WHERE id = ?1",
             params![
                 integration.id,
@@ -277,6 +468,16 @@ impl IntegrationsRepository for SqliteIntegrationsRepository {
                 integration.last_tested_at,
                 integration.updated_at,
                 integration.deleted_at,
+                integration.retry_policy.max_attempts,
+                integration.retry_policy.backoff_base_seconds,
+                integration.retry_policy.backoff_max_seconds,
+                integration.retry_policy.timeout_seconds,
+                integration.retry_policy.disable_after_failures,
+                integration.consecutive_failures,
+                integration.disabled_reason,
+                enum_text(&integration.http_method),
+                enum_text(&integration.payload_format),
+                template_json(integration)?,
             ],
         )
         .map_err(|error| AppError::db_sanitized("integrations.update.execute", error))?;
@@ -297,6 +498,38 @@ impl IntegrationsRepository for SqliteIntegrationsRepository {
         Ok(())
     }
 
+    async fn update_signing_secrets(
+        &self,
+        id: &str,
+        encrypted_signing_secret: &str,
+        encrypted_previous: Option<String>,
+        previous_expires_at: Option<i64>,
+        updated_at: i64,
+    ) -> AppResult<()> {
+        let conn = self.db.get_connection().map_err(|error| {
+            AppError::db_sanitized("integrations.update_signing_secrets.get_connection", error)
+        })?;
+        conn.execute(
+            "UPDATE integration_configs
+             SET encrypted_signing_secret = ?2,
+                 encrypted_previous_signing_secret = ?3,
+                 previous_signing_secret_expires_at = ?4,
+                 updated_at = ?5
+             WHERE id = ?1",
+            params![
+                id,
+                encrypted_signing_secret,
+                encrypted_previous,
+                previous_expires_at,
+                updated_at
+            ],
+        )
+        .map_err(|error| {
+            AppError::db_sanitized("integrations.update_signing_secrets.execute", error)
+        })?;
+        Ok(())
+    }
+
     async fn retry_dead_letters(&self, integration_id: &str) -> AppResult<usize> {
         let conn = self.db.get_connection().map_err(|error| {
             AppError::db_sanitized("integrations.retry_dead_letters.get_connection", error)
@@ -321,6 +554,7 @@ impl IntegrationsRepository for SqliteIntegrationsRepository {
             .into_iter()
             .filter(|integration| {
                 integration.status == IntegrationStatus::Active
+                    && integration.kind == IntegrationKind::Webhook
                     && integration
                         .subscribed_events
                         .iter()
@@ -332,14 +566,14 @@ impl IntegrationsRepository for SqliteIntegrationsRepository {
             .collect())
     }
 
-    async fn store_delivery(&self, delivery: &DeliveryRecord) -> AppResult<()> {
+    async fn store_delivery(&self, delivery: &DeliveryRecord) -> AppResult<bool> {
         let conn = self.db.get_connection().map_err(|error| {
             AppError::db_sanitized("integrations.store_delivery.get_connection", error)
         })?;
-        conn.execute(
-            "INSERT INTO integration_outbox (
-                id, integration_id, event_name, payload_json, correlation_id, status, attempt_count, last_error, next_retry_at, created_at, updated_at
-             ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+        let inserted = conn.execute(
+            "INSERT OR IGNORE INTO integration_outbox (
+                id, integration_id, event_name, payload_json, correlation_id, status, attempt_count, last_error, next_retry_at, created_at, updated_at, idempotency_key
+             ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
             params![
                 delivery.id,
                 delivery.integration_id,
@@ -352,10 +586,11 @@ impl IntegrationsRepository for SqliteIntegrationsRepository {
                 delivery.next_retry_at,
                 delivery.created_at,
                 delivery.updated_at,
+                delivery.idempotency_key,
             ],
         )
         .map_err(|error| AppError::db_sanitized("integrations.store_delivery.execute", error))?;
-        Ok(())
+        Ok(inserted > 0)
     }
 
     async fn list_due_deliveries(&self, limit: usize) -> AppResult<Vec<OutboundDelivery>> {
@@ -365,10 +600,12 @@ impl IntegrationsRepository for SqliteIntegrationsRepository {
         let now = chrono::Utc::now().timestamp_millis();
         let mut stmt = conn
             .prepare(
-                "SELECT id, integration_id, event_name, payload_json, status, attempt_count, last_error, next_retry_at, created_at, updated_at
-                 FROM integration_outbox
-                 WHERE status IN ('pending', 'retrying') AND COALESCE(next_retry_at, 0) <= ?1
-                 ORDER BY created_at ASC
+                "SELECT o.id, o.integration_id, o.event_name, o.payload_json, o.status, o.idempotency_key, o.attempt_count, o.last_error, o.next_retry_at, o.created_at, o.updated_at
+                 FROM integration_outbox o
+                 JOIN integration_configs c ON c.id = o.integration_id
+                 WHERE o.status IN ('pending', 'retrying') AND COALESCE(o.next_retry_at, 0) <= ?1
+                   AND c.status = 'active' AND c.deleted_at IS NULL
+                 ORDER BY o.created_at ASC
                  LIMIT ?2",
             )
             .map_err(|error| AppError::db_sanitized("integrations.list_due_deliveries.prepare", error))?;
@@ -423,4 +660,342 @@ impl IntegrationsRepository for SqliteIntegrationsRepository {
         })?;
         Ok(())
     }
+
+    async fn get_delivery(&self, delivery_id: &str) -> AppResult<OutboundDelivery> {
+        let conn = self.db.get_connection().map_err(|error| {
+            AppError::db_sanitized("integrations.get_delivery.get_connection", error)
+        })?;
+        conn.query_row(
+            &format!("SELECT {DELIVERY_COLUMNS} FROM integration_outbox WHERE id = ?1"),
+            params![delivery_id],
+            Self::map_delivery,
+        )
+        .optional()
+        .map_err(|error| AppError::db_sanitized("integrations.get_delivery.query", error))?
+        .ok_or_else(|| AppError::NotFound(format!("Delivery not found: {}", delivery_id)))
+    }
+
+    async fn list_deliveries(
+        &self,
+        integration_id: &str,
+        request: &ListDeliveriesRequest,
+    ) -> AppResult<(Vec<DeliveryLogEntry>, i64)> {
+        let conn = self.db.get_connection().map_err(|error| {
+            AppError::db_sanitized("integrations.list_deliveries.get_connection", error)
+        })?;
+        let status = request.status.as_ref().map(|status| {
+            serde_json::to_string(status)
+                .unwrap_or_default()
+                .trim_matches('"')
+                .to_string()
+        });
+        let total: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM integration_outbox
+                 WHERE integration_id = ?1 AND (?2 IS NULL OR status = ?2)",
+                params![integration_id, status],
+                |row| row.get(0),
+            )
+            .map_err(|error| AppError::db_sanitized("integrations.list_deliveries.count", error))?;
+        let order = request.pagination.sort_order_sql();
+        let mut stmt = conn
+            .prepare(&format!(
+                "SELECT o.id, o.integration_id, o.event_name, o.payload_json, o.status, o.idempotency_key, o.attempt_count, o.last_error, o.next_retry_at, o.created_at, o.updated_at,
+                        {ATTEMPT_COLUMNS}
+                 FROM integration_outbox o
+                 LEFT JOIN integration_delivery_attempts a ON a.id = (
+                     SELECT id FROM integration_delivery_attempts
+                     WHERE delivery_id = o.id
+                     ORDER BY attempt_number DESC
+                     LIMIT 1
+                 )
+                 WHERE o.integration_id = ?1 AND (?2 IS NULL OR o.status = ?2)
+                 ORDER BY o.created_at {order}, o.id {order}
+                 LIMIT ?3 OFFSET ?4"
+            ))
+            .map_err(|error| AppError::db_sanitized("integrations.list_deliveries.prepare", error))?;
+        let rows = stmt
+            .query_map(
+                params![
+                    integration_id,
+                    status,
+                    request.pagination.page_size(),
+                    request.pagination.offset()
+                ],
+                |row| {
+                    Ok(DeliveryLogEntry {
+                        delivery: Self::map_delivery(row)?,
+                        last_attempt: Self::map_attempt(row)?,
+                    })
+                },
+            )
+            .map_err(|error| AppError::db_sanitized("integrations.list_deliveries.query", error))?;
+        let mut entries = Vec::new();
+        for row in rows {
+            entries.push(row.map_err(|error| {
+                AppError::db_sanitized("integrations.list_deliveries.row", error)
+            })?);
+        }
+        Ok((entries, total))
+    }
+
+    async fn list_delivery_attempts(&self, delivery_id: &str) -> AppResult<Vec<DeliveryAttempt>> {
+        let conn = self.db.get_connection().map_err(|error| {
+            AppError::db_sanitized("integrations.list_delivery_attempts.get_connection", error)
+        })?;
+        let mut stmt = conn
+            .prepare(&format!(
+                "SELECT {ATTEMPT_COLUMNS}
+                 FROM integration_delivery_attempts a
+                 WHERE a.delivery_id = ?1
+                 ORDER BY a.attempt_number ASC"
+            ))
+            .map_err(|error| {
+                AppError::db_sanitized("integrations.list_delivery_attempts.prepare", error)
+            })?;
+        let rows = stmt
+            .query_map(params![delivery_id], Self::map_attempt)
+            .map_err(|error| {
+                AppError::db_sanitized("integrations.list_delivery_attempts.query", error)
+            })?;
+        let mut attempts = Vec::new();
+        for row in rows {
+            attempts.extend(row.map_err(|error| {
+                AppError::db_sanitized("integrations.list_delivery_attempts.row", error)
+            })?);
+        }
+        Ok(attempts)
+    }
+
+    async fn record_attempt(&self, attempt: &DeliveryAttempt) -> AppResult<DeliveryAttempt> {
+        let conn = self.db.get_connection().map_err(|error| {
+            AppError::db_sanitized("integrations.record_attempt.get_connection", error)
+        })?;
+        let attempt_number: i64 = conn
+            .query_row(
+                "INSERT INTO integration_delivery_attempts (
+                    id, delivery_id, integration_id, attempt_number, succeeded, response_status, response_excerpt, error, latency_ms, attempted_at
+                 ) VALUES (
+                    ?1, ?2, ?3,
+                    (SELECT COALESCE(MAX(attempt_number), 0) + 1 FROM integration_delivery_attempts WHERE delivery_id = ?2),
+                    ?4, ?5, ?6, ?7, ?8, ?9
+                 )
+                 RETURNING attempt_number",
+                params![
+                    attempt.id,
+                    attempt.delivery_id,
+                    attempt.integration_id,
+                    attempt.succeeded,
+                    attempt.response_status,
+                    attempt.response_excerpt,
+                    attempt.error,
+                    attempt.latency_ms,
+                    attempt.attempted_at,
+                ],
+                |row| row.get(0),
+            )
+            .map_err(|error| AppError::db_sanitized("integrations.record_attempt.execute", error))?;
+        Ok(DeliveryAttempt {
+            attempt_number,
+            ..attempt.clone()
+        })
+    }
+
+    async fn record_delivery_outcome(
+        &self,
+        integration_id: &str,
+        succeeded: bool,
+    ) -> AppResult<i64> {
+        let conn = self.db.get_connection().map_err(|error| {
+            AppError::db_sanitized("integrations.record_delivery_outcome.get_connection", error)
+        })?;
+        conn.query_row(
+            "UPDATE integration_configs
+             SET consecutive_failures = CASE WHEN ?2 THEN 0 ELSE consecutive_failures + 1 END
+             WHERE id = ?1
+             RETURNING consecutive_failures",
+            params![integration_id, succeeded],
+            |row| row.get(0),
+        )
+        .map_err(|error| {
+            AppError::db_sanitized("integrations.record_delivery_outcome.execute", error)
+        })
+    }
+
+    async fn disable_integration(
+        &self,
+        integration_id: &str,
+        reason: &str,
+        updated_at: i64,
+    ) -> AppResult<bool> {
+        let conn = self.db.get_connection().map_err(|error| {
+            AppError::db_sanitized("integrations.disable_integration.get_connection", error)
+        })?;
+        let updated = conn
+            .execute(
+                "UPDATE integration_configs
+                 SET status = 'disabled', disabled_reason = ?2, updated_at = ?3
+                 WHERE id = ?1 AND status = 'active'",
+                params![integration_id, reason, updated_at],
+            )
+            .map_err(|error| {
+                AppError::db_sanitized("integrations.disable_integration.execute", error)
+            })?;
+        Ok(updated > 0)
+    }
+
+    async fn reset_delivery(&self, delivery_id: &str, now: i64) -> AppResult<()> {
+        let conn = self.db.get_connection().map_err(|error| {
+            AppError::db_sanitized("integrations.reset_delivery.get_connection", error)
+        })?;
+        conn.execute(
+            "UPDATE integration_outbox
+             SET status = 'pending', attempt_count = 0, last_error = NULL, next_retry_at = ?2, updated_at = ?2
+             WHERE id = ?1",
+            params![delivery_id, now],
+        )
+        .map_err(|error| AppError::db_sanitized("integrations.reset_delivery.execute", error))?;
+        Ok(())
+    }
+
+    async fn latest_payload_for_event(
+        &self,
+        event_name: &str,
+    ) -> AppResult<Option<serde_json::Value>> {
+        let conn = self.db.get_connection().map_err(|error| {
+            AppError::db_sanitized(
+                "integrations.latest_payload_for_event.get_connection",
+                error,
+            )
+        })?;
+        let payload: Option<String> = conn
+            .query_row(
+                "SELECT payload_json FROM integration_outbox
+                 WHERE event_name = ?1
+                 ORDER BY created_at DESC
+                 LIMIT 1",
+                params![event_name],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(|error| {
+                AppError::db_sanitized("integrations.latest_payload_for_event.query", error)
+            })?;
+        Ok(payload.and_then(|payload| serde_json::from_str(&payload).ok()))
+    }
+
+    async fn find_inbound_request(
+        &self,
+        integration_id: &str,
+        idempotency_key: &str,
+    ) -> AppResult<Option<InboundRequestLogEntry>> {
+        let conn = self.db.get_connection().map_err(|error| {
+            AppError::db_sanitized("integrations.find_inbound_request.get_connection", error)
+        })?;
+        conn.query_row(
+            &format!(
+                "SELECT {INBOUND_REQUEST_COLUMNS} FROM integration_inbound_requests
+                 WHERE integration_id = ?1 AND idempotency_key = ?2"
+            ),
+            params![integration_id, idempotency_key],
+            Self::map_inbound_request,
+        )
+        .optional()
+        .map_err(|error| AppError::db_sanitized("integrations.find_inbound_request.query", error))
+    }
+
+    async fn record_inbound_request(&self, entry: &InboundRequestLogEntry) -> AppResult<()> {
+        let conn = self.db.get_connection().map_err(|error| {
+            AppError::db_sanitized("integrations.record_inbound_request.get_connection", error)
+        })?;
+        conn.execute(
+            &format!(
+                "INSERT INTO integration_inbound_requests ({INBOUND_REQUEST_COLUMNS})
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)"
+            ),
+            params![
+                entry.id,
+                entry.integration_id,
+                entry.action,
+                entry.idempotency_key,
+                entry.correlation_id,
+                entry.response_status,
+                entry.response_body.to_string(),
+                entry.resource_type,
+                entry.resource_id,
+                entry.error,
+                entry.received_at,
+            ],
+        )
+        .map_err(|error| {
+            AppError::db_sanitized("integrations.record_inbound_request.execute", error)
+        })?;
+        Ok(())
+    }
+
+    async fn list_inbound_requests(
+        &self,
+        integration_id: &str,
+        request: &ListInboundRequestsRequest,
+    ) -> AppResult<(Vec<InboundRequestLogEntry>, i64)> {
+        let conn = self.db.get_connection().map_err(|error| {
+            AppError::db_sanitized("integrations.list_inbound_requests.get_connection", error)
+        })?;
+        let total: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM integration_inbound_requests WHERE integration_id = ?1",
+                params![integration_id],
+                |row| row.get(0),
+            )
+            .map_err(|error| {
+                AppError::db_sanitized("integrations.list_inbound_requests.count", error)
+            })?;
+        let order = request.pagination.sort_order_sql();
+        let mut stmt = conn
+            .prepare(&format!(
+                "SELECT {INBOUND_REQUEST_COLUMNS} FROM integration_inbound_requests
+                 WHERE integration_id = ?1
+                 ORDER BY received_at {order}, id {order}
+                 LIMIT ?2 OFFSET ?3"
+            ))
+            .map_err(|error| {
+                AppError::db_sanitized("integrations.list_inbound_requests.prepare", error)
+            })?;
+        let rows = stmt
+            .query_map(
+                params![
+                    integration_id,
+                    request.pagination.page_size(),
+                    request.pagination.offset()
+                ],
+                Self::map_inbound_request,
+            )
+            .map_err(|error| {
+                AppError::db_sanitized("integrations.list_inbound_requests.query", error)
+            })?;
+        let mut entries = Vec::new();
+        for row in rows {
+            entries.push(row.map_err(|error| {
+                AppError::db_sanitized("integrations.list_inbound_requests.row", error)
+            })?);
+        }
+        Ok((entries, total))
+    }
+}
+
+/// Serde name of a unit enum variant, as stored in a TEXT column.
+fn enum_text<T: serde::Serialize>(value: &T) -> String {
+    serde_json::to_string(value)
+        .unwrap_or_default()
+        .trim_matches('"')
+        .to_string()
+}
+
+fn template_json(integration: &IntegrationConfig) -> AppResult<Option<String>> {
+    integration
+        .payload_template
+        .as_ref()
+        .map(serde_json::to_string)
+        .transpose()
+        .map_err(|error| AppError::Internal(error.to_string()))
 }
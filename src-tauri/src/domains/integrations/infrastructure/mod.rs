@@ -1 +1,2 @@
+pub(crate) mod inbound_listener;
 pub(crate) mod integrations_repository;
@@ -0,0 +1,133 @@
+//! Runs inbound webhook commands through the same services as the IPC
+//! handlers, with the integration's `RequestContext` in place of a session.
+
+use std::sync::Arc;
+use tauri::Manager;
+
+use crate::domains::integrations::domain::inbound::{InboundQuoteResponse, QuoteDecision};
+use crate::shared::app_state::AppStateType;
+use crate::shared::context::RequestContext;
+use crate::shared::contracts::inbound_commands::{InboundCommandHandler, InboundCommandOutcome};
+use crate::shared::contracts::integration_sink::IntegrationEventSink;
+use crate::shared::contracts::notification::NotificationSender;
+use crate::shared::contracts::rules_engine::BlockingRuleEngine;
+use crate::shared::contracts::task_scheduler::TaskScheduler;
+use crate::shared::error::AppError;
+use crate::shared::services::cross_domain::{
+    check_client_access, map_client_service_error, sanitize_create_request, ClientUpsert,
+    CreateClientRequest, CreateTaskRequest, QuotesFacade, TaskCommandService,
+};
+
+pub struct AppInboundCommands {
+    app: tauri::AppHandle,
+}
+
+impl AppInboundCommands {
+    pub fn new(app: tauri::AppHandle) -> Self {
+        Self { app }
+    }
+}
+
+fn parse_payload<T: serde::de::DeserializeOwned>(
+    payload: serde_json::Value,
+) -> Result<T, AppError> {
+    serde_json::from_value(payload)
+        .map_err(|error| AppError::Validation(format!("Invalid request body: {}", error)))
+}
+
+fn outcome<T: serde::Serialize>(
+    resource_type: &str,
+    resource_id: String,
+    created: bool,
+    resource: &T,
+) -> Result<InboundCommandOutcome, AppError> {
+    Ok(InboundCommandOutcome {
+        resource_type: resource_type.to_string(),
+        resource_id,
+        created,
+        body: serde_json::to_value(resource)
+            .map_err(|error| AppError::Internal(error.to_string()))?,
+    })
+}
+
+#[async_trait::async_trait]
+impl InboundCommandHandler for AppInboundCommands {
+    async fn create_task(
+        &self,
+        ctx: &RequestContext,
+        payload: serde_json::Value,
+    ) -> Result<InboundCommandOutcome, AppError> {
+        let request: CreateTaskRequest = parse_payload(payload)?;
+        let state = self.app.state::<AppStateType>();
+        let service = TaskCommandService::new(
+            state.task_service.clone(),
+            state.task_import_service.clone(),
+            state.message_service.clone() as Arc<dyn NotificationSender>,
+            state.calendar_service.clone() as Arc<dyn TaskScheduler>,
+            state.event_bus.clone(),
+            state.rules_service.clone() as Arc<dyn BlockingRuleEngine>,
+            state.integrations_service.clone() as Arc<dyn IntegrationEventSink>,
+        );
+        let task = service.create_task(ctx, request).await?;
+        outcome("task", task.id.clone(), true, &task)
+    }
+
+    async fn upsert_client(
+        &self,
+        ctx: &RequestContext,
+        payload: serde_json::Value,
+    ) -> Result<InboundCommandOutcome, AppError> {
+        let request: CreateClientRequest = parse_payload(payload)?;
+        let state = self.app.state::<AppStateType>();
+        check_client_access(
+            &state.auth_service.rate_limiter(),
+            ctx.user_id(),
+            ctx.role(),
+            "create",
+        )?;
+        let request = sanitize_create_request(request)?;
+        let upsert = state
+            .client_service
+            .upsert_by_email(request, ctx.user_id())
+            .await
+            .map_err(|error| map_client_service_error("upsert_client", &error))?;
+        match upsert {
+            ClientUpsert::Created(client) => outcome("client", client.id.clone(), true, &client),
+            ClientUpsert::Updated(client) => outcome("client", client.id.clone(), false, &client),
+            // Another user's client: confirm it exists without disclosing it.
+            ClientUpsert::NotOwned { id } => outcome(
+                "client",
+                id.clone(),
+                false,
+                &serde_json::json!({ "id": id, "created": false }),
+            ),
+        }
+    }
+
+    async fn respond_to_quote(
+        &self,
+        ctx: &RequestContext,
+        quote_id: &str,
+        payload: serde_json::Value,
+    ) -> Result<InboundCommandOutcome, AppError> {
+        let response: InboundQuoteResponse = parse_payload(payload)?;
+        let state = self.app.state::<AppStateType>();
+        let facade = QuotesFacade::new(state.quote_service.clone());
+        let quote = match response.decision {
+            QuoteDecision::Accepted => {
+                let accepted = facade.mark_accepted(
+                    ctx.role(),
+                    quote_id,
+                    ctx.user_id(),
+                    &ctx.correlation_id,
+                )?;
+                return outcome("quote", accepted.quote.id.clone(), false, &accepted);
+            }
+            QuoteDecision::Rejected => facade.mark_rejected(ctx.role(), quote_id, ctx.user_id())?,
+            QuoteDecision::ChangesRequested => {
+                facade.mark_changes_requested(ctx.role(), quote_id)?
+            }
+        };
+        outcome("quote", quote.id.clone(), false, &quote)
+    }
+}
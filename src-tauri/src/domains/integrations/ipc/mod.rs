@@ -6,12 +6,18 @@
 //!   - delegate immediately to the service layer
 //!   - contain no business logic
 
+pub(crate) mod inbound_commands;
+
 use crate::commands::{AppResult, AppState};
 use crate::domains::integrations::domain::models::integrations::{
-    CreateIntegrationRequest, IntegrationConfig, TestIntegrationResponse, UpdateIntegrationRequest,
+    CreateIntegrationRequest, DeliveryAttempt, DeliveryLogEntry, InboundRequestLogEntry,
+    IntegrationConfig, ListDeliveriesRequest, ListInboundRequestsRequest, PreviewPayloadRequest,
+    PreviewPayloadResponse, RotateSigningSecretRequest, SigningSecretResponse,
+    TestIntegrationResponse, UpdateIntegrationRequest,
 };
 use crate::resolve_context;
 use crate::shared::contracts::auth::UserRole;
+use crate::shared::repositories::base::PaginatedResult;
 
 #[tauri::command]
 pub async fn list_integrations(
@@ -63,6 +69,33 @@ pub async fn test_integration(
     state.integrations_service.test_connection(&ctx, &id).await
 }
 
+#[tauri::command]
+pub async fn reveal_integration_signing_secret(
+    id: String,
+    state: AppState<'_>,
+    correlation_id: Option<String>,
+) -> AppResult<SigningSecretResponse> {
+    let ctx = resolve_context!(&state, &correlation_id, UserRole::Admin);
+    state
+        .integrations_service
+        .reveal_signing_secret(&ctx, &id)
+        .await
+}
+
+#[tauri::command]
+pub async fn rotate_integration_signing_secret(
+    id: String,
+    request: RotateSigningSecretRequest,
+    state: AppState<'_>,
+    correlation_id: Option<String>,
+) -> AppResult<SigningSecretResponse> {
+    let ctx = resolve_context!(&state, &correlation_id, UserRole::Admin);
+    state
+        .integrations_service
+        .rotate_signing_secret(&ctx, &id, request)
+        .await
+}
+
 #[tauri::command]
 pub async fn retry_dead_letter_integrations(
     id: String,
@@ -76,6 +109,73 @@ pub async fn retry_dead_letter_integrations(
         .await
 }
 
+#[tauri::command]
+pub async fn list_integration_deliveries(
+    id: String,
+    request: ListDeliveriesRequest,
+    state: AppState<'_>,
+    correlation_id: Option<String>,
+) -> AppResult<PaginatedResult<DeliveryLogEntry>> {
+    let ctx = resolve_context!(&state, &correlation_id, UserRole::Admin);
+    state
+        .integrations_service
+        .list_deliveries(&ctx, &id, request)
+        .await
+}
+
+#[tauri::command]
+pub async fn list_integration_delivery_attempts(
+    delivery_id: String,
+    state: AppState<'_>,
+    correlation_id: Option<String>,
+) -> AppResult<Vec<DeliveryAttempt>> {
+    let ctx = resolve_context!(&state, &correlation_id, UserRole::Admin);
+    state
+        .integrations_service
+        .list_delivery_attempts(&ctx, &delivery_id)
+        .await
+}
+
+#[tauri::command]
+pub async fn replay_integration_delivery(
+    delivery_id: String,
+    state: AppState<'_>,
+    correlation_id: Option<String>,
+) -> AppResult<DeliveryLogEntry> {
+    let ctx = resolve_context!(&state, &correlation_id, UserRole::Admin);
+    state
+        .integrations_service
+        .replay_delivery(&ctx, &delivery_id)
+        .await
+}
+
+#[tauri::command]
+pub async fn preview_integration_payload(
+    request: PreviewPayloadRequest,
+    state: AppState<'_>,
+    correlation_id: Option<String>,
+) -> AppResult<PreviewPayloadResponse> {
+    let ctx = resolve_context!(&state, &correlation_id, UserRole::Admin);
+    state
+        .integrations_service
+        .preview_payload(&ctx, request)
+        .await
+}
+
+#[tauri::command]
+pub async fn list_integration_inbound_requests(
+    id: String,
+    request: ListInboundRequestsRequest,
+    state: AppState<'_>,
+    correlation_id: Option<String>,
+) -> AppResult<PaginatedResult<InboundRequestLogEntry>> {
+    let ctx = resolve_context!(&state, &correlation_id, UserRole::Admin);
+    state
+        .integrations_service
+        .list_inbound_requests(&ctx, &id, request)
+        .await
+}
+
 #[tauri::command]
 pub async fn delete_integration(
     id: String,
@@ -3,17 +3,33 @@
 #[cfg(test)]
 mod tests {
     use crate::db::Database;
+    use crate::domains::integrations::application::services::inbound_webhook_service::InboundWebhookService;
     use crate::domains::integrations::application::services::integrations_service::IntegrationsService;
+    use crate::domains::integrations::domain::inbound::InboundHttpRequest;
     use crate::domains::integrations::domain::models::integrations::{
-        CreateIntegrationRequest, IntegrationStatus, UpdateIntegrationRequest,
+        CreateIntegrationRequest, DeliveryStatus, IntegrationConfig, IntegrationHttpMethod,
+        IntegrationKind, IntegrationRetryPolicy, IntegrationStatus, ListDeliveriesRequest,
+        ListInboundRequestsRequest, PayloadFormat, PayloadTemplate, PreviewPayloadRequest,
+        RotateSigningSecretRequest, UpdateIntegrationRequest,
     };
+    use crate::domains::integrations::domain::webhook_signature::{
+        signature_header, verify_signature, SignatureError, DEFAULT_TOLERANCE_SECS,
+    };
+    use crate::domains::integrations::infrastructure::inbound_listener::serve;
     use crate::shared::context::RequestContext;
+    use crate::shared::contracts::inbound_commands::{
+        InboundCommandHandler, InboundCommandOutcome,
+    };
     use crate::shared::contracts::integration_sink::{
         IntegrationDispatchRequest, IntegrationEventSink,
     };
+    use crate::shared::error::AppError;
+    use crate::shared::logging::audit_service::AuditService;
     use serde_json::json;
     use std::collections::HashMap;
-    use std::sync::Arc;
+    use std::sync::{Arc, Mutex};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
 
     fn ctx() -> RequestContext {
         RequestContext::unauthenticated("integrations-test-correlation".to_string())
@@ -43,6 +59,7 @@ mod tests {
                     headers: HashMap::new(),
                     subscribed_events: vec!["task_created".to_string()],
                     secret_token: Some("super-secret".to_string()),
+                    ..Default::default()
                 },
             )
             .await
@@ -70,6 +87,7 @@ mod tests {
                     headers: HashMap::new(),
                     subscribed_events: vec!["intervention_started".to_string()],
                     secret_token: None,
+                    ..Default::default()
                 },
             )
             .await
@@ -117,6 +135,7 @@ mod tests {
                     headers: HashMap::new(),
                     subscribed_events: vec!["task_created".to_string()],
                     secret_token: None,
+                    ..Default::default()
                 },
             )
             .await
@@ -165,4 +184,949 @@ mod tests {
         assert_eq!(retried, 1);
         assert_eq!(status, "pending");
     }
+
+    /// A request as a webhook receiver sees it: method, lower-cased headers
+    /// and the raw body bytes.
+    struct ReceivedWebhook {
+        method: String,
+        headers: HashMap<String, String>,
+        body: Vec<u8>,
+    }
+
+    /// One-shot webhook receiver: captures the request and answers 200.
+    async fn start_test_receiver() -> (String, Arc<Mutex<Option<ReceivedWebhook>>>) {
+        start_test_receiver_with(
+            b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\nconnection: close\r\n\r\n",
+        )
+        .await
+    }
+
+    /// One-shot webhook receiver: captures the request and writes `response`.
+    async fn start_test_receiver_with(
+        response: &'static [u8],
+    ) -> (String, Arc<Mutex<Option<ReceivedWebhook>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind test receiver");
+        let url = format!("http://{}/hooks", listener.local_addr().expect("addr"));
+        let captured = Arc::new(Mutex::new(None));
+        let received = captured.clone();
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.expect("accept");
+            let mut buffer = vec![0u8; 16 * 1024];
+            let mut raw = Vec::new();
+            let header_end = loop {
+                let read = stream.read(&mut buffer).await.unwrap_or(0);
+                raw.extend_from_slice(&buffer[..read]);
+                let end = raw.windows(4).position(|window| window == b"\r\n\r\n");
+                if read == 0 {
+                    break end.unwrap_or(raw.len());
+                }
+                if let Some(end) = end {
+                    let head = String::from_utf8_lossy(&raw[..end]).to_ascii_lowercase();
+                    let content_length = head
+                        .lines()
+                        .find_map(|line| line.strip_prefix("content-length:"))
+                        .and_then(|value| value.trim().parse::<usize>().ok())
+                        .unwrap_or(0);
+                    if raw.len() >= end + 4 + content_length {
+                        break end;
+                    }
+                }
+            };
+            let head = String::from_utf8_lossy(&raw[..header_end]);
+            let method = head
+                .split_whitespace()
+                .next()
+                .unwrap_or_default()
+                .to_string();
+            let headers = head
+                .lines()
+                .skip(1)
+                .filter_map(|line| line.split_once(':'))
+                .map(|(name, value)| (name.trim().to_ascii_lowercase(), value.trim().to_string()))
+                .collect();
+            let body = raw.get(header_end + 4..).unwrap_or_default().to_vec();
+            *received.lock().unwrap() = Some(ReceivedWebhook {
+                method,
+                headers,
+                body,
+            });
+            let _ = stream.write_all(response).await;
+        });
+        (url, captured)
+    }
+
+    async fn active_webhook(
+        service: &IntegrationsService,
+        endpoint_url: String,
+    ) -> IntegrationConfig {
+        active_webhook_with_policy(service, endpoint_url, None).await
+    }
+
+    async fn active_webhook_with_policy(
+        service: &IntegrationsService,
+        endpoint_url: String,
+        retry_policy: Option<IntegrationRetryPolicy>,
+    ) -> IntegrationConfig {
+        activate(
+            service,
+            CreateIntegrationRequest {
+                name: "Signed webhook".to_string(),
+                description: None,
+                endpoint_url,
+                headers: HashMap::from([("X-Rpma-Event".to_string(), "spoofed".to_string())]),
+                subscribed_events: vec!["task_created".to_string()],
+                secret_token: None,
+                retry_policy,
+                ..Default::default()
+            },
+        )
+        .await
+    }
+
+    async fn activate(
+        service: &IntegrationsService,
+        request: CreateIntegrationRequest,
+    ) -> IntegrationConfig {
+        let integration = service
+            .create(&ctx(), request)
+            .await
+            .expect("create integration");
+        service
+            .update(
+                &ctx(),
+                &integration.id,
+                UpdateIntegrationRequest {
+                    status: Some(IntegrationStatus::Active),
+                    ..Default::default()
+                },
+            )
+            .await
+            .expect("activate integration")
+    }
+
+    fn task_created(correlation_id: &str) -> IntegrationDispatchRequest {
+        IntegrationDispatchRequest {
+            event_name: "task_created".to_string(),
+            payload: json!({ "task_id": "task-42", "title": "Pose PPF" }),
+            correlation_id: correlation_id.to_string(),
+            requested_integration_ids: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_delivery_is_signed_and_verifiable_by_receiver() {
+        let (_db, service) = service_and_db().await;
+        let (url, received) = start_test_receiver().await;
+        let integration = active_webhook(&service, url).await;
+        assert!(integration.has_signing_secret);
+
+        service
+            .enqueue(task_created("corr-signed"))
+            .await
+            .expect("enqueue delivery");
+        assert_eq!(service.process_pending(10).await.expect("deliver"), 1);
+
+        let webhook = received.lock().unwrap().take().expect("webhook received");
+        let secret = service
+            .reveal_signing_secret(&ctx(), &integration.id)
+            .await
+            .expect("reveal signing secret")
+            .signing_secret;
+        let now = chrono::Utc::now().timestamp();
+
+        // What a receiver does: verify the signature over the raw body, then
+        // dedupe on the idempotency key.
+        verify_signature(
+            &secret,
+            &webhook.headers["x-rpma-signature"],
+            &webhook.body,
+            now,
+            DEFAULT_TOLERANCE_SECS,
+        )
+        .expect("signature verifies");
+        assert_eq!(
+            verify_signature(
+                "whsec_wrong",
+                &webhook.headers["x-rpma-signature"],
+                &webhook.body,
+                now,
+                DEFAULT_TOLERANCE_SECS,
+            ),
+            Err(SignatureError::Mismatch)
+        );
+        assert_eq!(webhook.headers["x-rpma-event"], "task_created");
+        assert!(!webhook.headers["x-rpma-delivery-id"].is_empty());
+        assert_eq!(webhook.headers["idempotency-key"].len(), 64);
+        let body: serde_json::Value = serde_json::from_slice(&webhook.body).expect("json body");
+        assert_eq!(body["task_id"], "task-42");
+    }
+
+    #[tokio::test]
+    async fn test_rotated_secret_signs_alongside_new_one_during_grace_period() {
+        let (_db, service) = service_and_db().await;
+        let (url, received) = start_test_receiver().await;
+        let integration = active_webhook(&service, url).await;
+        let old_secret = service
+            .reveal_signing_secret(&ctx(), &integration.id)
+            .await
+            .expect("reveal signing secret")
+            .signing_secret;
+
+        let rotated = service
+            .rotate_signing_secret(
+                &ctx(),
+                &integration.id,
+                RotateSigningSecretRequest {
+                    grace_period_minutes: Some(60),
+                },
+            )
+            .await
+            .expect("rotate signing secret");
+        assert_ne!(rotated.signing_secret, old_secret);
+        assert!(rotated.previous_signing_secret_expires_at.is_some());
+
+        service
+            .enqueue(task_created("corr-rotated"))
+            .await
+            .expect("enqueue delivery");
+        service.process_pending(10).await.expect("deliver");
+
+        let webhook = received.lock().unwrap().take().expect("webhook received");
+        let now = chrono::Utc::now().timestamp();
+        for secret in [&old_secret, &rotated.signing_secret] {
+            verify_signature(
+                secret,
+                &webhook.headers["x-rpma-signature"],
+                &webhook.body,
+                now,
+                DEFAULT_TOLERANCE_SECS,
+            )
+            .expect("both secrets verify during the grace period");
+        }
+
+        let immediate = service
+            .rotate_signing_secret(
+                &ctx(),
+                &integration.id,
+                RotateSigningSecretRequest {
+                    grace_period_minutes: Some(0),
+                },
+            )
+            .await
+            .expect("rotate without grace");
+        assert_eq!(immediate.previous_signing_secret_expires_at, None);
+        assert!(service
+            .rotate_signing_secret(
+                &ctx(),
+                &integration.id,
+                RotateSigningSecretRequest {
+                    grace_period_minutes: Some(8 * 24 * 60),
+                },
+            )
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_same_event_is_queued_once_per_integration() {
+        let (db, service) = service_and_db().await;
+        active_webhook(&service, "https://example.com/hooks".to_string()).await;
+
+        let first = service
+            .enqueue(task_created("corr-dup"))
+            .await
+            .expect("enqueue delivery");
+        let replayed = service
+            .enqueue(task_created("corr-dup"))
+            .await
+            .expect("enqueue replayed delivery");
+        let other = service
+            .enqueue(task_created("corr-other"))
+            .await
+            .expect("enqueue other delivery");
+
+        let outbox_count: i64 = db
+            .get_connection()
+            .expect("outbox connection")
+            .query_row("SELECT COUNT(*) FROM integration_outbox", [], |row| {
+                row.get(0)
+            })
+            .expect("count outbox");
+        assert_eq!((first, replayed, other), (1, 0, 1));
+        assert_eq!(outbox_count, 2);
+    }
+
+    #[tokio::test]
+    async fn test_failed_delivery_is_logged_and_can_be_replayed() {
+        let (_db, service) = service_and_db().await;
+        let (url, _) = start_test_receiver_with(
+            b"HTTP/1.1 503 Service Unavailable\r\ncontent-length: 23\r\nconnection: close\r\n\r\nmaintenance in progress",
+        )
+        .await;
+        let integration = active_webhook_with_policy(
+            &service,
+            url,
+            Some(IntegrationRetryPolicy {
+                max_attempts: 3,
+                backoff_base_seconds: 60,
+                ..Default::default()
+            }),
+        )
+        .await;
+        service
+            .enqueue(task_created("corr-failing"))
+            .await
+            .expect("enqueue delivery");
+        let before = chrono::Utc::now().timestamp_millis();
+        service.process_pending(10).await.expect("attempt delivery");
+
+        let log = service
+            .list_deliveries(&ctx(), &integration.id, ListDeliveriesRequest::default())
+            .await
+            .expect("list deliveries");
+        assert_eq!(log.pagination.total, 1);
+        let entry = &log.data[0];
+        assert_eq!(entry.delivery.status, DeliveryStatus::Retrying);
+        assert_eq!(entry.delivery.attempt_count, 1);
+        // First retry: 60 s, the upper half jittered.
+        let next_retry_at = entry.delivery.next_retry_at.expect("retry scheduled");
+        assert!(next_retry_at >= before + 30_000);
+        assert!(next_retry_at <= chrono::Utc::now().timestamp_millis() + 60_000);
+        let attempt = entry.last_attempt.as_ref().expect("attempt logged");
+        assert!(!attempt.succeeded);
+        assert_eq!(attempt.response_status, Some(503));
+        assert_eq!(
+            attempt.response_excerpt.as_deref(),
+            Some("maintenance in progress")
+        );
+        assert!(attempt.latency_ms >= 0);
+
+        // The receiver recovers; replay sends the delivery right away.
+        let (url, received) = start_test_receiver().await;
+        service
+            .update(
+                &ctx(),
+                &integration.id,
+                UpdateIntegrationRequest {
+                    endpoint_url: Some(url),
+                    ..Default::default()
+                },
+            )
+            .await
+            .expect("point to the recovered receiver");
+        let replayed = service
+            .replay_delivery(&ctx(), &entry.delivery.id)
+            .await
+            .expect("replay delivery");
+        assert_eq!(replayed.delivery.status, DeliveryStatus::Delivered);
+        let webhook = received.lock().unwrap().take().expect("webhook received");
+        assert_eq!(
+            Some(webhook.headers["idempotency-key"].as_str()),
+            entry.delivery.idempotency_key.as_deref()
+        );
+
+        let attempts = service
+            .list_delivery_attempts(&ctx(), &entry.delivery.id)
+            .await
+            .expect("list attempts");
+        let summary: Vec<_> = attempts
+            .iter()
+            .map(|attempt| (attempt.attempt_number, attempt.response_status))
+            .collect();
+        assert_eq!(summary, vec![(1, Some(503)), (2, Some(200))]);
+
+        let delivered = service
+            .list_deliveries(
+                &ctx(),
+                &integration.id,
+                ListDeliveriesRequest {
+                    status: Some(DeliveryStatus::Retrying),
+                    ..Default::default()
+                },
+            )
+            .await
+            .expect("filter deliveries");
+        assert_eq!(delivered.pagination.total, 0);
+    }
+
+    #[tokio::test]
+    async fn test_sustained_failures_disable_integration_until_re_enabled() {
+        let (_db, service) = service_and_db().await;
+        // Nothing listens on a port freed right after binding it.
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind closed port");
+        let url = format!("http://{}/hooks", listener.local_addr().expect("addr"));
+        drop(listener);
+        let integration = active_webhook_with_policy(
+            &service,
+            url,
+            Some(IntegrationRetryPolicy {
+                max_attempts: 1,
+                disable_after_failures: 2,
+                ..Default::default()
+            }),
+        )
+        .await;
+
+        for correlation_id in ["corr-down-1", "corr-down-2"] {
+            service
+                .enqueue(task_created(correlation_id))
+                .await
+                .expect("enqueue delivery");
+        }
+        assert_eq!(service.process_pending(10).await.expect("attempt"), 2);
+
+        let disabled = service
+            .get(&integration.id, &ctx())
+            .await
+            .expect("get integration");
+        assert_eq!(disabled.status, IntegrationStatus::Disabled);
+        assert_eq!(disabled.consecutive_failures, 2);
+        assert!(disabled
+            .disabled_reason
+            .as_deref()
+            .is_some_and(|reason| reason.contains("2 consecutive failed deliveries")));
+        let log = service
+            .list_deliveries(&ctx(), &integration.id, ListDeliveriesRequest::default())
+            .await
+            .expect("list deliveries");
+        assert!(log
+            .data
+            .iter()
+            .all(|entry| entry.delivery.status == DeliveryStatus::DeadLetter));
+        assert!(service
+            .replay_delivery(&ctx(), &log.data[0].delivery.id)
+            .await
+            .is_err());
+
+        let re_enabled = service
+            .update(
+                &ctx(),
+                &integration.id,
+                UpdateIntegrationRequest {
+                    status: Some(IntegrationStatus::Active),
+                    ..Default::default()
+                },
+            )
+            .await
+            .expect("re-enable integration");
+        assert_eq!(re_enabled.consecutive_failures, 0);
+        assert_eq!(re_enabled.disabled_reason, None);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_retry_policy_is_rejected() {
+        let (_db, service) = service_and_db().await;
+        let integration = active_webhook(&service, "https://example.com/hooks".to_string()).await;
+        assert_eq!(integration.retry_policy, IntegrationRetryPolicy::default());
+
+        let result = service
+            .update(
+                &ctx(),
+                &integration.id,
+                UpdateIntegrationRequest {
+                    retry_policy: Some(IntegrationRetryPolicy {
+                        max_attempts: 0,
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                },
+            )
+            .await;
+        assert!(matches!(
+            result,
+            Err(crate::shared::error::AppError::Validation(_))
+        ));
+    }
+
+    fn chat_template() -> PayloadTemplate {
+        PayloadTemplate {
+            body: Some(json!({
+                "text": "Nouvelle tâche {{payload.task_id}}: {{payload.title}}",
+                "event": "{{event}}"
+            })),
+            static_fields: serde_json::Map::from_iter([("channel".to_string(), json!("#atelier"))]),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_templated_delivery_is_sent_with_configured_method_and_format() {
+        let (_db, service) = service_and_db().await;
+        let (url, received) = start_test_receiver().await;
+        let integration = activate(
+            &service,
+            CreateIntegrationRequest {
+                name: "Chat".to_string(),
+                endpoint_url: url,
+                subscribed_events: vec!["task_created".to_string()],
+                http_method: IntegrationHttpMethod::Put,
+                payload_format: PayloadFormat::Form,
+                payload_template: Some(chat_template()),
+                ..Default::default()
+            },
+        )
+        .await;
+        assert_eq!(integration.payload_template, Some(chat_template()));
+
+        service
+            .enqueue(task_created("corr-templated"))
+            .await
+            .expect("enqueue delivery");
+        service.process_pending(10).await.expect("deliver");
+
+        let webhook = received.lock().unwrap().take().expect("webhook received");
+        assert_eq!(webhook.method, "PUT");
+        assert_eq!(
+            webhook.headers["content-type"],
+            "application/x-www-form-urlencoded"
+        );
+        let fields: HashMap<String, String> =
+            serde_urlencoded::from_bytes(&webhook.body).expect("form body");
+        assert_eq!(fields["text"], "Nouvelle tâche task-42: Pose PPF");
+        assert_eq!(fields["event"], "task_created");
+        assert_eq!(fields["channel"], "#atelier");
+        // The signature covers the form body as sent.
+        let secret = service
+            .reveal_signing_secret(&ctx(), &integration.id)
+            .await
+            .expect("reveal signing secret")
+            .signing_secret;
+        verify_signature(
+            &secret,
+            &webhook.headers["x-rpma-signature"],
+            &webhook.body,
+            chrono::Utc::now().timestamp(),
+            DEFAULT_TOLERANCE_SECS,
+        )
+        .expect("signature verifies");
+
+        // An empty template goes back to sending the raw payload.
+        let updated = service
+            .update(
+                &ctx(),
+                &integration.id,
+                UpdateIntegrationRequest {
+                    payload_template: Some(PayloadTemplate::default()),
+                    ..Default::default()
+                },
+            )
+            .await
+            .expect("clear template");
+        assert_eq!(updated.payload_template, None);
+    }
+
+    #[tokio::test]
+    async fn test_preview_renders_sample_or_latest_queued_event() {
+        let (_db, service) = service_and_db().await;
+        let preview = service
+            .preview_payload(
+                &ctx(),
+                PreviewPayloadRequest {
+                    event_name: "task_created".to_string(),
+                    payload_template: Some(chat_template()),
+                    payload_format: PayloadFormat::Json,
+                    sample_payload: Some(json!({ "task_id": "task-7", "title": "Vitres" })),
+                },
+            )
+            .await
+            .expect("preview with sample");
+        assert_eq!(preview.content_type, "application/json");
+        let body: serde_json::Value = serde_json::from_str(&preview.body).expect("json body");
+        assert_eq!(
+            body,
+            json!({
+                "text": "Nouvelle tâche task-7: Vitres",
+                "event": "task_created",
+                "channel": "#atelier"
+            })
+        );
+
+        let without_sample = PreviewPayloadRequest {
+            event_name: "task_created".to_string(),
+            payload_template: None,
+            payload_format: PayloadFormat::Json,
+            sample_payload: None,
+        };
+        assert!(service
+            .preview_payload(&ctx(), without_sample.clone())
+            .await
+            .is_err());
+        active_webhook(&service, "https://example.com/hooks".to_string()).await;
+        service
+            .enqueue(task_created("corr-preview"))
+            .await
+            .expect("enqueue delivery");
+        let preview = service
+            .preview_payload(&ctx(), without_sample)
+            .await
+            .expect("preview latest event");
+        assert_eq!(preview.sample_payload["task_id"], "task-42");
+    }
+
+    /// Records who ran each inbound command and with what payload.
+    #[derive(Default)]
+    struct RecordingCommands {
+        calls: Mutex<Vec<(String, String, serde_json::Value)>>,
+    }
+
+    impl RecordingCommands {
+        fn record(&self, action: &str, ctx: &RequestContext, payload: &serde_json::Value) {
+            self.calls.lock().unwrap().push((
+                action.to_string(),
+                ctx.user_id().to_string(),
+                payload.clone(),
+            ));
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl InboundCommandHandler for RecordingCommands {
+        async fn create_task(
+            &self,
+            ctx: &RequestContext,
+            payload: serde_json::Value,
+        ) -> Result<InboundCommandOutcome, AppError> {
+            self.record("create_task", ctx, &payload);
+            Ok(InboundCommandOutcome {
+                resource_type: "task".to_string(),
+                resource_id: "task-1".to_string(),
+                created: true,
+                body: json!({ "id": "task-1", "title": payload["title"] }),
+            })
+        }
+
+        async fn upsert_client(
+            &self,
+            ctx: &RequestContext,
+            payload: serde_json::Value,
+        ) -> Result<InboundCommandOutcome, AppError> {
+            self.record("upsert_client", ctx, &payload);
+            Err(AppError::Validation("Invalid email format".to_string()))
+        }
+
+        async fn respond_to_quote(
+            &self,
+            ctx: &RequestContext,
+            quote_id: &str,
+            payload: serde_json::Value,
+        ) -> Result<InboundCommandOutcome, AppError> {
+            self.record("respond_to_quote", ctx, &payload);
+            Ok(InboundCommandOutcome {
+                resource_type: "quote".to_string(),
+                resource_id: quote_id.to_string(),
+                created: false,
+                body: json!({ "id": quote_id }),
+            })
+        }
+    }
+
+    struct InboundFixture {
+        db: Arc<Database>,
+        service: Arc<IntegrationsService>,
+        commands: Arc<RecordingCommands>,
+        inbound: Arc<InboundWebhookService>,
+        integration: IntegrationConfig,
+        secret: String,
+    }
+
+    /// An active inbound integration allowed to create tasks and answer quotes.
+    async fn inbound_fixture() -> InboundFixture {
+        let (db, service) = service_and_db().await;
+        let service = Arc::new(service);
+        let audit = Arc::new(AuditService::new(db.clone()));
+        audit.init().expect("init audit log");
+        let integration = activate(
+            &service,
+            CreateIntegrationRequest {
+                name: "Booking site".to_string(),
+                kind: IntegrationKind::InboundWebhook,
+                subscribed_events: vec!["create_task".to_string(), "respond_to_quote".to_string()],
+                ..Default::default()
+            },
+        )
+        .await;
+        let secret = service
+            .reveal_signing_secret(&ctx(), &integration.id)
+            .await
+            .expect("reveal signing secret")
+            .signing_secret;
+        let commands = Arc::new(RecordingCommands::default());
+        let inbound = Arc::new(InboundWebhookService::new(
+            service.clone(),
+            commands.clone(),
+            audit,
+        ));
+        InboundFixture {
+            db,
+            service,
+            commands,
+            inbound,
+            integration,
+            secret,
+        }
+    }
+
+    fn signed_request(
+        path: String,
+        secret: &str,
+        body: &[u8],
+        idempotency_key: &str,
+    ) -> InboundHttpRequest {
+        let headers = HashMap::from([
+            (
+                "x-rpma-signature".to_string(),
+                signature_header(&[secret], chrono::Utc::now().timestamp(), body),
+            ),
+            ("idempotency-key".to_string(), idempotency_key.to_string()),
+        ]);
+        InboundHttpRequest {
+            method: "POST".to_string(),
+            path,
+            headers,
+            body: body.to_vec(),
+            remote_addr: Some("203.0.113.7".to_string()),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_signed_inbound_request_runs_as_integration_and_replays_on_retry() {
+        let fixture = inbound_fixture().await;
+        let path = format!("/integrations/{}/tasks", fixture.integration.id);
+        let body = br#"{"title":"Pose PPF capot"}"#;
+
+        let response = fixture
+            .inbound
+            .handle(signed_request(
+                path.clone(),
+                &fixture.secret,
+                body,
+                "booking-42",
+            ))
+            .await;
+        assert_eq!(response.status, 201);
+        assert_eq!(response.body["title"], "Pose PPF capot");
+
+        // A retry of the same request gets the stored response back.
+        let retry = fixture
+            .inbound
+            .handle(signed_request(path, &fixture.secret, body, "booking-42"))
+            .await;
+        assert_eq!(retry, response);
+
+        let calls = fixture.commands.calls.lock().unwrap().clone();
+        let actor = format!("integration:{}", fixture.integration.id);
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].0, "create_task");
+        assert_eq!(calls[0].1, actor);
+        assert_eq!(calls[0].2["title"], "Pose PPF capot");
+
+        let log = fixture
+            .service
+            .list_inbound_requests(
+                &ctx(),
+                &fixture.integration.id,
+                ListInboundRequestsRequest::default(),
+            )
+            .await
+            .expect("list inbound requests");
+        assert_eq!(log.pagination.total, 1);
+        assert_eq!(log.data[0].response_status, 201);
+        assert_eq!(log.data[0].resource_id.as_deref(), Some("task-1"));
+        assert_eq!(log.data[0].idempotency_key.as_deref(), Some("booking-42"));
+
+        let conn = fixture.db.get_connection().expect("audit connection");
+        let (action, ip_address): (String, String) = conn
+            .query_row(
+                "SELECT action, ip_address FROM audit_events WHERE user_id = ?1",
+                [&actor],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .expect("audit entry for the integration");
+        assert_eq!(action, "INBOUND_CREATE_TASK");
+        assert_eq!(ip_address, "203.0.113.7");
+    }
+
+    #[tokio::test]
+    async fn test_inbound_requests_need_a_key_and_a_fresh_signature() {
+        let fixture = inbound_fixture().await;
+        let path = format!("/integrations/{}/tasks", fixture.integration.id);
+        let body = br#"{"title":"Jantes"}"#;
+
+        let mut keyless = signed_request(path.clone(), &fixture.secret, body, "unused");
+        keyless.headers.remove("idempotency-key");
+        assert_eq!(fixture.inbound.handle(keyless).await.status, 400);
+
+        let first = signed_request(path, &fixture.secret, body, "booking-7");
+        assert_eq!(fixture.inbound.handle(first.clone()).await.status, 201);
+
+        // The key is not signed: a captured request replayed under a new one
+        // is refused.
+        let mut replay = first;
+        replay
+            .headers
+            .insert("idempotency-key".to_string(), "booking-8".to_string());
+        assert_eq!(fixture.inbound.handle(replay).await.status, 401);
+        assert_eq!(fixture.commands.calls.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_inbound_requests_are_authenticated_and_limited_to_allowed_actions() {
+        let fixture = inbound_fixture().await;
+        let id = &fixture.integration.id;
+        let body = br#"{"title":"Vitres"}"#;
+        let tasks_path = format!("/integrations/{}/tasks", id);
+
+        let mut unsigned = signed_request(tasks_path.clone(), &fixture.secret, body, "unsigned");
+        unsigned.headers.remove("x-rpma-signature");
+        let tampered = InboundHttpRequest {
+            body: br#"{"title":"Autre"}"#.to_vec(),
+            ..signed_request(tasks_path.clone(), &fixture.secret, body, "tampered")
+        };
+        let outbound =
+            active_webhook(&fixture.service, "https://example.com/hooks".to_string()).await;
+        let cases = [
+            (unsigned, 401),
+            (tampered, 401),
+            (
+                signed_request(tasks_path.clone(), "whsec_wrong", body, "wrong-secret"),
+                401,
+            ),
+            (
+                signed_request(
+                    "/integrations/unknown/tasks".to_string(),
+                    &fixture.secret,
+                    body,
+                    "unknown",
+                ),
+                404,
+            ),
+            (
+                signed_request(
+                    format!("/integrations/{}/tasks", outbound.id),
+                    &fixture.secret,
+                    body,
+                    "outbound",
+                ),
+                404,
+            ),
+            (
+                signed_request(
+                    format!("/integrations/{}/clients", id),
+                    &fixture.secret,
+                    body,
+                    "clients",
+                ),
+                403,
+            ),
+            (
+                signed_request(tasks_path.clone(), &fixture.secret, b"not json", "not-json"),
+                400,
+            ),
+        ];
+        for (request, status) in cases {
+            let path = request.path.clone();
+            let response = fixture.inbound.handle(request).await;
+            assert_eq!(response.status, status, "{}", path);
+            assert!(response.body["error"]["code"].is_string(), "{}", path);
+        }
+        assert!(fixture.commands.calls.lock().unwrap().is_empty());
+
+        fixture
+            .service
+            .update(
+                &ctx(),
+                id,
+                UpdateIntegrationRequest {
+                    status: Some(IntegrationStatus::Disabled),
+                    ..Default::default()
+                },
+            )
+            .await
+            .expect("disable integration");
+        let response = fixture
+            .inbound
+            .handle(signed_request(
+                tasks_path,
+                &fixture.secret,
+                body,
+                "disabled",
+            ))
+            .await;
+        assert_eq!(response.status, 403);
+
+        // Inbound integrations only list inbound actions, and never receive
+        // outbound deliveries.
+        let invalid = fixture
+            .service
+            .create(
+                &ctx(),
+                CreateIntegrationRequest {
+                    name: "Booking site".to_string(),
+                    kind: IntegrationKind::InboundWebhook,
+                    subscribed_events: vec!["task_created".to_string()],
+                    ..Default::default()
+                },
+            )
+            .await;
+        assert!(matches!(invalid, Err(AppError::Validation(_))));
+        let queued = fixture
+            .service
+            .enqueue(IntegrationDispatchRequest {
+                event_name: "create_task".to_string(),
+                payload: json!({}),
+                correlation_id: "corr-inbound".to_string(),
+                requested_integration_ids: None,
+            })
+            .await
+            .expect("enqueue");
+        assert_eq!(queued, 0);
+    }
+
+    #[tokio::test]
+    async fn test_listener_answers_inbound_requests_over_http() {
+        let fixture = inbound_fixture().await;
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind inbound listener");
+        let address = listener.local_addr().expect("addr");
+        let inbound = fixture.inbound.clone();
+        tokio::spawn(serve(listener, move |request| {
+            let inbound = inbound.clone();
+            async move { inbound.handle(request).await }
+        }));
+
+        let body = br#"{"decision":"accepted"}"#.to_vec();
+        let response = reqwest::Client::new()
+            .post(format!(
+                "http://{}/integrations/{}/quotes/q-7/response",
+                address, fixture.integration.id
+            ))
+            .header(
+                "X-Rpma-Signature",
+                signature_header(&[&fixture.secret], chrono::Utc::now().timestamp(), &body),
+            )
+            .header("Content-Type", "application/json")
+            .header("Idempotency-Key", "quote-7-accepted")
+            .body(body)
+            .send()
+            .await
+            .expect("send inbound request");
+        assert_eq!(response.status().as_u16(), 200);
+        let json: serde_json::Value = response.json().await.expect("json response");
+        assert_eq!(json, json!({ "id": "q-7" }));
+        let calls = fixture.commands.calls.lock().unwrap().clone();
+        assert_eq!(calls[0].0, "respond_to_quote");
+        assert_eq!(calls[0].2["decision"], "accepted");
+
+        let response = reqwest::Client::new()
+            .get(format!("http://{}/integrations", address))
+            .send()
+            .await
+            .expect("send unknown route");
+        assert_eq!(response.status().as_u16(), 404);
+    }
 }
@@ -32,6 +32,7 @@ mod tests {
                     headers: HashMap::new(),
                     subscribed_events: vec!["task_created".to_string()],
                     secret_token: None,
+                    ..Default::default()
                 },
             )
             .await;
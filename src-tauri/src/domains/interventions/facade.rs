@@ -6,7 +6,7 @@ use crate::domains::interventions::application::{
 use crate::domains::interventions::domain::models::intervention::{
     Intervention, InterventionProgress, InterventionWorkflowState,
 };
-use crate::domains::interventions::domain::models::step::InterventionStep;
+use crate::domains::interventions::domain::models::step::{InterventionStep, StepStatus};
 use crate::domains::interventions::domain::services::workflow_state::build_workflow_state;
 use crate::domains::interventions::infrastructure::intervention::InterventionService;
 use crate::domains::interventions::infrastructure::intervention_scoring_service::InterventionScoringService;
@@ -20,9 +20,10 @@ use crate::shared::context::RequestContext;
 use crate::shared::contracts::auth::UserRole;
 use crate::shared::contracts::common::now as now_ms;
 use crate::shared::contracts::events::InterventionFinalized;
-use crate::shared::contracts::rules_engine::{BlockingRuleEngine, RuleCheckRequest};
+use crate::shared::contracts::rules_engine::{payloads, BlockingRuleEngine, RuleCheckRequest};
 use crate::shared::contracts::task_assignment::TaskAssignmentChecker;
 use crate::shared::event_bus::publish_event;
+use crate::shared::services::event_bus::event_factory;
 use crate::shared::ipc::errors::AppError;
 use chrono::Utc;
 
@@ -426,6 +427,17 @@ impl InterventionsFacade {
             )
             .await
             .map_err(AppError::from)?;
+        if response.step.step_status == StepStatus::Completed {
+            publish_event(event_factory::intervention_step_completed_with_ctx(
+                response.step.intervention_id.clone(),
+                response.step.id.clone(),
+                response.step.step_number,
+                ctx.user_id().to_string(),
+                response.step.photo_count,
+                response.step.duration_seconds,
+                ctx.correlation_id.clone(),
+            ));
+        }
         Ok(response)
     }
 
@@ -618,11 +630,11 @@ impl InterventionsFacade {
                 .evaluate(&RuleCheckRequest {
                     trigger: "intervention_started".to_string(),
                     entity_id: Some(request.task_id.clone()),
-                    payload: serde_json::json!({
-                        "task_id": request.task_id.clone(),
-                        "estimated_duration_minutes": request.estimated_duration_minutes,
-                        "priority": request.priority,
-                    }),
+                    payload: payloads::intervention_started(
+                        &request.task_id,
+                        request.estimated_duration_minutes.map(i64::from),
+                        Some(&request.priority),
+                    ),
                     user_id: ctx.auth.user_id.clone(),
                     correlation_id: ctx.correlation_id.clone(),
                 })
@@ -751,11 +763,11 @@ impl InterventionsFacade {
                 .evaluate(&RuleCheckRequest {
                     trigger: "intervention_finalized".to_string(),
                     entity_id: Some(request.intervention_id.clone()),
-                    payload: serde_json::json!({
-                        "intervention_id": request.intervention_id.clone(),
-                        "quality_score": request.quality_score,
-                        "customer_satisfaction": request.customer_satisfaction,
-                    }),
+                    payload: payloads::intervention_finalized(
+                        &request.intervention_id,
+                        request.quality_score,
+                        request.customer_satisfaction,
+                    ),
                     user_id: ctx.auth.user_id.clone(),
                     correlation_id: ctx.correlation_id.clone(),
                 })
@@ -396,6 +396,22 @@ impl WorkflowStrategyFactory {
     pub fn list_available_strategies() -> Vec<&'static str> {
         vec!["express_ppf", "standard_ppf"]
     }
+
+    /// Estimated duration, in whole minutes, of the steps of the strategy
+    /// that would run the intervention.
+    pub fn estimated_minutes(intervention: &Intervention) -> i32 {
+        let context = WorkflowContext {
+            intervention: intervention.clone(),
+            user_id: "system".to_string(),
+            environment_conditions: None,
+        };
+        let seconds: i32 = Self::create_strategy(intervention, &context)
+            .get_workflow_steps(&context)
+            .iter()
+            .filter_map(|step| step.estimated_duration_seconds)
+            .sum();
+        (seconds + 59) / 60
+    }
 }
 
 #[cfg(test)]
@@ -469,4 +485,23 @@ mod tests {
         let strategy = WorkflowStrategyFactory::create_strategy(&intervention, &context);
         assert_eq!(strategy.strategy_name(), "express_ppf"); // Should pick express for small job
     }
+
+    #[test]
+    fn test_estimated_minutes_follow_the_strategy() {
+        let mut intervention = create_test_intervention();
+        assert_eq!(
+            WorkflowStrategyFactory::estimated_minutes(&intervention),
+            40
+        );
+
+        intervention.ppf_zones_config = Some(vec![
+            "hood".to_string(),
+            "fenders".to_string(),
+            "bumper".to_string(),
+        ]);
+        assert_eq!(
+            WorkflowStrategyFactory::estimated_minutes(&intervention),
+            120
+        );
+    }
 }
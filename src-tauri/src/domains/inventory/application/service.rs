@@ -18,6 +18,8 @@ use crate::domains::inventory::infrastructure::MaterialGateway;
 use crate::shared::auth_middleware::AuthMiddleware;
 use crate::shared::contracts::auth::UserRole;
 use crate::shared::contracts::events::InterventionFinalized;
+use crate::shared::event_bus::publish_event;
+use crate::shared::services::event_bus::event_factory;
 
 /// TODO: document
 #[derive(Debug)]
@@ -129,6 +131,7 @@ impl InventoryService {
         &self,
         request: RecordConsumptionRequest,
         role: &UserRole,
+        correlation_id: &str,
     ) -> InventoryResult<MaterialConsumption> {
         if !AuthMiddleware::can_perform_task_operation(role, "update") {
             return Err(InventoryError::Authorization(
@@ -146,9 +149,46 @@ impl InventoryService {
 
         validate_unit_of_measure(&material.unit_of_measure)?;
 
-        self.gateway
-            .record_consumption(request)
-            .map_err(InventoryError::from)
+        let consumption = self
+            .gateway
+            .record_consumption(request, correlation_id)
+            .map_err(InventoryError::from)?;
+        self.publish_consumption_events(&material, &consumption);
+        Ok(consumption)
+    }
+
+    /// Publish `MaterialConsumed`, plus `MaterialLowStock` when this
+    /// consumption took the stock down to its minimum level.
+    fn publish_consumption_events(&self, before: &Material, consumption: &MaterialConsumption) {
+        publish_event(event_factory::material_consumed_by(
+            consumption.material_id.clone(),
+            consumption.intervention_id.clone(),
+            consumption.quantity_used,
+            before.unit_of_measure.to_string(),
+            consumption
+                .recorded_by
+                .clone()
+                .unwrap_or_else(|| "system".to_string()),
+        ));
+
+        if before.is_low_stock() {
+            return;
+        }
+        match self.gateway.get_material(&before.id) {
+            Ok(Some(after)) if after.is_low_stock() => {
+                publish_event(event_factory::material_low_stock(
+                    after.id.clone(),
+                    after.name.clone(),
+                    after.current_stock,
+                    after.minimum_stock.unwrap_or_default(),
+                    after.unit_of_measure.to_string(),
+                ));
+            }
+            Ok(_) => {}
+            Err(e) => {
+                tracing::warn!(material_id = %before.id, error = %e, "Failed to re-read stock after consumption");
+            }
+        }
     }
     // ------------------------------------------------------------------
     // Intervention lifecycle — Saga entry points (ADR-016)
@@ -63,9 +63,10 @@ impl InventoryFacade {
         &self,
         request: RecordConsumptionRequest,
         role: &UserRole,
+        correlation_id: &str,
     ) -> Result<MaterialConsumption, AppError> {
         self.service
-            .record_consumption(request, role)
+            .record_consumption(request, role, correlation_id)
             .map_err(|err| map_inventory_error("record_consumption", err))
     }
 
@@ -8,7 +8,10 @@
 //! - `stats`       — Read-only stats and reporting queries
 //! - `delegation`  — Pass-throughs to sub-repositories
 
+use std::sync::Arc;
+
 use crate::db::Database;
+use crate::shared::contracts::rules_engine::BlockingRuleEngine;
 
 use super::inventory_transaction_service::InventoryTransactionService;
 use super::material_category_repository::MaterialCategoryRepository;
@@ -44,13 +47,27 @@ mod tests;
 ///
 /// Delegates read operations to focused sub-repositories and retains the
 /// atomic write operations that must span multiple tables in one transaction.
-#[derive(Debug)]
 pub struct MaterialService {
     pub(super) db: Database,
     pub(super) categories: MaterialCategoryRepository,
     pub(super) suppliers: SupplierRepository,
     pub(super) consumption: MaterialConsumptionRepository,
     pub(super) transactions: InventoryTransactionService,
+    /// Optional blocking rules engine, checked by `record_consumption`.
+    pub(super) rules_engine: Option<Arc<dyn BlockingRuleEngine>>,
+}
+
+impl std::fmt::Debug for MaterialService {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MaterialService")
+            .field("db", &self.db)
+            .field("categories", &self.categories)
+            .field("suppliers", &self.suppliers)
+            .field("consumption", &self.consumption)
+            .field("transactions", &self.transactions)
+            .field("rules_engine_attached", &self.rules_engine.is_some())
+            .finish()
+    }
 }
 
 impl MaterialService {
@@ -62,6 +79,14 @@ impl MaterialService {
             consumption: MaterialConsumptionRepository::new(db.clone()),
             transactions: InventoryTransactionService::new(db.clone()),
             db,
+            rules_engine: None,
         }
     }
+
+    /// Attach a blocking rules engine so that `record_consumption` enforces
+    /// `material_consumed` rules.
+    pub fn with_rules_engine(mut self, rules_engine: Arc<dyn BlockingRuleEngine>) -> Self {
+        self.rules_engine = Some(rules_engine);
+        self
+    }
 }
@@ -3,6 +3,7 @@
 use crate::domains::inventory::domain::models::material::{
     InventoryTransaction, InventoryTransactionType, Material, MaterialConsumption,
 };
+use crate::shared::contracts::rules_engine::{evaluate_blocking, RuleCheckRequest};
 use rusqlite::params;
 use tracing::{debug, info};
 
@@ -63,9 +64,11 @@ impl super::MaterialService {
     ///
     /// Atomically inserts the consumption record, appends an audit transaction,
     /// and decrements `materials.current_stock` in a single DB transaction.
+    /// `correlation_id` tags the `material_consumed` rule check of the request.
     pub fn record_consumption(
         &self,
         request: RecordConsumptionRequest,
+        correlation_id: &str,
     ) -> MaterialResult<MaterialConsumption> {
         let recorded_by = request
             .recorded_by
@@ -103,6 +106,7 @@ impl super::MaterialService {
                 material.name, material.current_stock, total_needed
             )));
         }
+        self.check_consumption_rules(&request, &recorded_by, &material, correlation_id)?;
 
         let consumption =
             Self::build_consumption_record(&request, &recorded_by, &material, waste_quantity);
@@ -146,6 +150,45 @@ impl super::MaterialService {
         Ok(consumption)
     }
 
+    /// Evaluate `material_consumed` blocking rules, when an engine is attached.
+    fn check_consumption_rules(
+        &self,
+        request: &RecordConsumptionRequest,
+        recorded_by: &str,
+        material: &Material,
+        correlation_id: &str,
+    ) -> MaterialResult<()> {
+        let Some(engine) = &self.rules_engine else {
+            return Ok(());
+        };
+        let outcome = evaluate_blocking(
+            engine.as_ref(),
+            &RuleCheckRequest {
+                trigger: "material_consumed".to_string(),
+                entity_id: Some(material.id.clone()),
+                payload: serde_json::json!({
+                    "material_id": material.id,
+                    "intervention_id": request.intervention_id,
+                    "quantity": request.quantity_used,
+                    "unit": material.unit_of_measure.to_string(),
+                    "step_id": request.step_id,
+                    "waste_quantity": request.waste_quantity,
+                    "current_stock": material.current_stock,
+                }),
+                user_id: recorded_by.to_string(),
+                correlation_id: correlation_id.to_string(),
+            },
+        )
+        .map_err(|e| MaterialError::Database(format!("Rule evaluation failed: {}", e)))?;
+        if outcome.allowed {
+            Ok(())
+        } else {
+            Err(MaterialError::Validation(outcome.message.unwrap_or_else(|| {
+                "Material consumption blocked by active rule".to_string()
+            })))
+        }
+    }
+
     /// Build a `MaterialConsumption` record from the request and fetched material.
     fn build_consumption_record(
         request: &RecordConsumptionRequest,
@@ -54,8 +54,9 @@ impl MaterialGateway {
     pub fn record_consumption(
         &self,
         request: RecordConsumptionRequest,
+        correlation_id: &str,
     ) -> MaterialResult<MaterialConsumption> {
-        self.service.record_consumption(request)
+        self.service.record_consumption(request, correlation_id)
     }
 
     /// TODO: document
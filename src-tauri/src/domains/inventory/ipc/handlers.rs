@@ -33,8 +33,9 @@ pub fn record_consumption(
     service: &InventoryFacade,
     request: RecordConsumptionRequest,
     role: &UserRole,
+    correlation_id: &str,
 ) -> Result<MaterialConsumption, AppError> {
-    service.record_consumption(request, role)
+    service.record_consumption(request, role, correlation_id)
 }
 
 /// TODO: document
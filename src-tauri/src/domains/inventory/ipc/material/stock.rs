@@ -101,7 +101,7 @@ pub async fn material_record_consumption(
         ..request
     };
 
-    match service.record_consumption(adjusted_request, &ctx.auth.role) {
+    match service.record_consumption(adjusted_request, &ctx.auth.role, &ctx.correlation_id) {
         Ok(consumption) => {
             info!(consumption_id = %consumption.id, "Material consumption recorded");
             Ok(ApiResponse::success(consumption)
@@ -0,0 +1,195 @@
+//! Map invoices and pro-forma quotes to the EN 16931 semantic model.
+//!
+//! Everything here is pure: the service loads the invoice, the organization
+//! and, for credit notes, the credited invoice number, then serialises the
+//! resulting [`EInvoiceDocument`] with `einvoice_xml` / `facturx`.
+
+use std::collections::BTreeMap;
+
+use chrono::{NaiveDate, TimeZone};
+use chrono_tz::Europe::Paris;
+
+use crate::domains::invoices::domain::models::einvoice::{
+    country_code, EInvoiceAllowance, EInvoiceDocument, EInvoiceLine, EInvoiceParty,
+    EInvoiceVatBreakdown, VatCategory, TYPE_CODE_CREDIT_NOTE, TYPE_CODE_INVOICE,
+    TYPE_CODE_PROFORMA,
+};
+use crate::domains::invoices::domain::models::invoices::{
+    Invoice, InvoiceBillingSettings, InvoiceKind, InvoiceLineKind,
+};
+use crate::shared::services::cross_domain::Organization;
+
+/// Which document an [`Invoice`] is exported as.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EInvoiceSource {
+    /// An invoice or credit note from the ledger.
+    Ledger,
+    /// A quote rendered as a pro-forma invoice; never issued or numbered.
+    Proforma,
+}
+
+fn non_blank(value: Option<&str>) -> Option<String> {
+    value
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
+fn date_of(timestamp_ms: Option<i64>) -> Option<NaiveDate> {
+    timestamp_ms
+        .and_then(|ms| Paris.timestamp_millis_opt(ms).single())
+        .map(|date| date.date_naive())
+}
+
+pub fn seller_party(organization: Option<&Organization>) -> EInvoiceParty {
+    let Some(organization) = organization else {
+        return EInvoiceParty::default();
+    };
+    EInvoiceParty {
+        name: non_blank(organization.legal_name.as_deref())
+            .unwrap_or_else(|| organization.name.trim().to_string()),
+        siret: non_blank(organization.siret.as_deref()),
+        vat_id: non_blank(organization.tax_id.as_deref()),
+        street: non_blank(organization.address_street.as_deref()),
+        postcode: non_blank(organization.address_zip.as_deref()),
+        city: non_blank(organization.address_city.as_deref()),
+        country_code: organization
+            .address_country
+            .as_deref()
+            .and_then(country_code),
+        email: non_blank(organization.email.as_deref()),
+    }
+}
+
+/// Buyer as frozen on the invoice (see `snapshot_client`).
+pub fn buyer_party(invoice: &Invoice) -> EInvoiceParty {
+    EInvoiceParty {
+        name: non_blank(invoice.client_name.as_deref()).unwrap_or_default(),
+        siret: None,
+        vat_id: non_blank(invoice.client_tax_id.as_deref()),
+        street: non_blank(invoice.client_street.as_deref()),
+        postcode: non_blank(invoice.client_postcode.as_deref()),
+        city: non_blank(invoice.client_city.as_deref()),
+        country_code: invoice.client_country.as_deref().and_then(country_code),
+        email: None,
+    }
+}
+
+/// Build the structured document for `invoice`.
+///
+/// The document discount becomes one allowance per VAT rate, using the
+/// shares already computed in `vat_breakdown`, so the XML totals match the
+/// printed ones to the cent. `preceding_invoice` is the number of the
+/// invoice a credit note corrects.
+pub fn document_from_invoice(
+    invoice: &Invoice,
+    source: EInvoiceSource,
+    seller: EInvoiceParty,
+    settings: &InvoiceBillingSettings,
+    preceding_invoice: Option<String>,
+) -> EInvoiceDocument {
+    let type_code = match (source, invoice.kind) {
+        (EInvoiceSource::Proforma, _) => TYPE_CODE_PROFORMA,
+        (EInvoiceSource::Ledger, InvoiceKind::Invoice) => TYPE_CODE_INVOICE,
+        (EInvoiceSource::Ledger, InvoiceKind::CreditNote) => TYPE_CODE_CREDIT_NOTE,
+    };
+
+    let mut lines_by_rate: BTreeMap<i64, i64> = BTreeMap::new();
+    let lines: Vec<EInvoiceLine> = invoice
+        .lines
+        .iter()
+        .enumerate()
+        .map(|(index, line)| {
+            *lines_by_rate
+                .entry((line.tax_rate * 100.0).round() as i64)
+                .or_default() += line.line_total;
+            // BR-27: the net price may not be negative, so a discount line
+            // carries its sign on the quantity instead.
+            let (quantity, net_price) = if line.unit_price < 0 {
+                (-line.qty, -line.unit_price)
+            } else {
+                (line.qty, line.unit_price)
+            };
+            EInvoiceLine {
+                id: (index + 1).to_string(),
+                name: line.label.clone(),
+                description: non_blank(line.description.as_deref()),
+                quantity,
+                unit_code: match line.kind {
+                    InvoiceLineKind::Labor => "HUR",
+                    _ => "C62",
+                }
+                .to_string(),
+                net_price,
+                net_amount: line.line_total,
+                vat_category: VatCategory::for_rate(line.tax_rate),
+                vat_rate: line.tax_rate,
+            }
+        })
+        .collect();
+
+    let exemption_reason = non_blank(settings.vat_exemption_reason.as_deref());
+    let mut allowances = Vec::new();
+    let mut vat_breakdown = Vec::with_capacity(invoice.vat_breakdown.len());
+    for row in &invoice.vat_breakdown {
+        let vat_category = VatCategory::for_rate(row.tax_rate);
+        let gross = lines_by_rate
+            .get(&((row.tax_rate * 100.0).round() as i64))
+            .copied()
+            .unwrap_or_default();
+        let share = gross - row.taxable_amount;
+        if share != 0 {
+            allowances.push(EInvoiceAllowance {
+                amount: share,
+                reason: "Remise".to_string(),
+                vat_category,
+                vat_rate: row.tax_rate,
+            });
+        }
+        vat_breakdown.push(EInvoiceVatBreakdown {
+            vat_category,
+            vat_rate: row.tax_rate,
+            taxable_amount: row.taxable_amount,
+            tax_amount: row.tax_amount,
+            exemption_reason: match vat_category {
+                VatCategory::Exempt => exemption_reason.clone(),
+                VatCategory::Standard => None,
+            },
+        });
+    }
+
+    let line_total = lines.iter().map(|line| line.net_amount).sum::<i64>();
+    let allowance_total = allowances
+        .iter()
+        .map(|allowance| allowance.amount)
+        .sum::<i64>();
+    let prepaid = match type_code {
+        TYPE_CODE_INVOICE => invoice.amount_paid.clamp(0, invoice.total.max(0)),
+        _ => 0,
+    };
+    EInvoiceDocument {
+        type_code,
+        number: invoice.invoice_number.clone().unwrap_or_default(),
+        issue_date: date_of(invoice.issue_date),
+        due_date: match type_code {
+            TYPE_CODE_INVOICE => date_of(invoice.due_date),
+            _ => None,
+        },
+        currency: invoice.currency.clone(),
+        note: non_blank(invoice.notes.as_deref()),
+        payment_terms: non_blank(invoice.terms.as_deref()),
+        preceding_invoice,
+        seller,
+        buyer: buyer_party(invoice),
+        lines,
+        allowances,
+        vat_breakdown,
+        line_total,
+        allowance_total,
+        tax_basis_total: line_total - allowance_total,
+        tax_total: invoice.tax_total,
+        grand_total: invoice.total,
+        prepaid,
+        due_payable: invoice.total - prepaid,
+    }
+}
@@ -0,0 +1,510 @@
+//! CII (UN/CEFACT D16B, Factur-X EN 16931 profile) and UBL 2.1 writers.
+//!
+//! Hand-written like the PDF renderers: elements are emitted in the order
+//! the XSDs declare them, which `tests/einvoice_schema.rs` checks.
+
+use chrono::NaiveDate;
+
+use crate::domains::invoices::domain::models::einvoice::{
+    EInvoiceDocument, EInvoiceParty, VatCategory, EN16931_SPECIFICATION_ID,
+    TYPE_CODE_CREDIT_NOTE,
+};
+use crate::shared::utils::money::format_decimal;
+
+const CII_NAMESPACES: &str = concat!(
+    r#"xmlns:rsm="urn:un:unece:uncefact:data:standard:CrossIndustryInvoice:100" "#,
+    r#"xmlns:ram="urn:un:unece:uncefact:data:standard:ReusableAggregateBusinessInformationEntity:100" "#,
+    r#"xmlns:qdt="urn:un:unece:uncefact:data:standard:QualifiedDataType:100" "#,
+    r#"xmlns:udt="urn:un:unece:uncefact:data:standard:UnqualifiedDataType:100""#,
+);
+const UBL_CAC: &str = "urn:oasis:names:specification:ubl:schema:xsd:CommonAggregateComponents-2";
+const UBL_CBC: &str = "urn:oasis:names:specification:ubl:schema:xsd:CommonBasicComponents-2";
+
+/// Minimal indenting XML writer.
+struct XmlWriter {
+    out: String,
+    depth: usize,
+}
+
+impl XmlWriter {
+    fn new() -> Self {
+        Self {
+            out: String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n"),
+            depth: 0,
+        }
+    }
+
+    fn indent(&mut self) {
+        for _ in 0..self.depth {
+            self.out.push_str("  ");
+        }
+    }
+
+    fn open(&mut self, tag: &str) {
+        self.open_with(tag, "");
+    }
+
+    /// `attributes` is inserted verbatim; callers pass constants or
+    /// already-escaped values.
+    fn open_with(&mut self, tag: &str, attributes: &str) {
+        self.indent();
+        self.out.push('<');
+        self.out.push_str(tag);
+        if !attributes.is_empty() {
+            self.out.push(' ');
+            self.out.push_str(attributes);
+        }
+        self.out.push_str(">\n");
+        self.depth += 1;
+    }
+
+    fn close(&mut self, tag: &str) {
+        self.depth -= 1;
+        self.indent();
+        self.out.push_str("</");
+        self.out.push_str(tag);
+        self.out.push_str(">\n");
+    }
+
+    fn leaf(&mut self, tag: &str, text: &str) {
+        self.leaf_with(tag, "", text);
+    }
+
+    fn leaf_with(&mut self, tag: &str, attributes: &str, text: &str) {
+        self.indent();
+        self.out.push('<');
+        self.out.push_str(tag);
+        if !attributes.is_empty() {
+            self.out.push(' ');
+            self.out.push_str(attributes);
+        }
+        self.out.push('>');
+        self.out.push_str(&escape(text));
+        self.out.push_str("</");
+        self.out.push_str(tag);
+        self.out.push_str(">\n");
+    }
+
+    fn leaf_opt(&mut self, tag: &str, text: Option<&str>) {
+        if let Some(text) = text.filter(|text| !text.trim().is_empty()) {
+            self.leaf(tag, text);
+        }
+    }
+
+    fn finish(self) -> String {
+        self.out
+    }
+}
+
+pub(crate) fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            // Control characters are not allowed in XML 1.0.
+            c if c.is_control() && !matches!(c, '\n' | '\r' | '\t') => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn decimal(value: f64) -> String {
+    let text = format!("{:.4}", value);
+    let text = text.trim_end_matches('0').trim_end_matches('.');
+    if text.is_empty() || text == "-" {
+        "0".to_string()
+    } else {
+        text.to_string()
+    }
+}
+
+fn cii_date(date: NaiveDate) -> String {
+    date.format("%Y%m%d").to_string()
+}
+
+fn ubl_date(date: NaiveDate) -> String {
+    date.format("%Y-%m-%d").to_string()
+}
+
+fn cii_party(xml: &mut XmlWriter, tag: &str, party: &EInvoiceParty) {
+    xml.open(tag);
+    xml.leaf("ram:Name", &party.name);
+    if let Some(siret) = party.siret.as_deref() {
+        xml.open("ram:SpecifiedLegalOrganization");
+        xml.leaf_with("ram:ID", r#"schemeID="0009""#, siret);
+        xml.close("ram:SpecifiedLegalOrganization");
+    }
+    xml.open("ram:PostalTradeAddress");
+    xml.leaf_opt("ram:PostcodeCode", party.postcode.as_deref());
+    xml.leaf_opt("ram:LineOne", party.street.as_deref());
+    xml.leaf_opt("ram:CityName", party.city.as_deref());
+    xml.leaf("ram:CountryID", party.country_code.as_deref().unwrap_or_default());
+    xml.close("ram:PostalTradeAddress");
+    if let Some(email) = party.email.as_deref() {
+        xml.open("ram:URIUniversalCommunication");
+        xml.leaf_with("ram:URIID", r#"schemeID="EM""#, email);
+        xml.close("ram:URIUniversalCommunication");
+    }
+    if let Some(vat_id) = party.vat_id.as_deref() {
+        xml.open("ram:SpecifiedTaxRegistration");
+        xml.leaf_with("ram:ID", r#"schemeID="VA""#, vat_id);
+        xml.close("ram:SpecifiedTaxRegistration");
+    }
+    xml.close(tag);
+}
+
+fn cii_category(xml: &mut XmlWriter, category: VatCategory, rate: f64) {
+    xml.leaf("ram:CategoryCode", category.code());
+    xml.leaf("ram:RateApplicablePercent", &decimal(rate));
+}
+
+/// Cross Industry Invoice, as embedded in Factur-X (`factur-x.xml`).
+pub fn render_cii(document: &EInvoiceDocument) -> String {
+    let mut xml = XmlWriter::new();
+    xml.open_with("rsm:CrossIndustryInvoice", CII_NAMESPACES);
+
+    xml.open("rsm:ExchangedDocumentContext");
+    xml.open("ram:GuidelineSpecifiedDocumentContextParameter");
+    xml.leaf("ram:ID", EN16931_SPECIFICATION_ID);
+    xml.close("ram:GuidelineSpecifiedDocumentContextParameter");
+    xml.close("rsm:ExchangedDocumentContext");
+
+    xml.open("rsm:ExchangedDocument");
+    xml.leaf("ram:ID", &document.number);
+    xml.leaf("ram:TypeCode", &document.type_code.to_string());
+    xml.open("ram:IssueDateTime");
+    xml.leaf_with(
+        "udt:DateTimeString",
+        r#"format="102""#,
+        &document.issue_date.map(cii_date).unwrap_or_default(),
+    );
+    xml.close("ram:IssueDateTime");
+    if let Some(note) = document.note.as_deref() {
+        xml.open("ram:IncludedNote");
+        xml.leaf("ram:Content", note);
+        xml.close("ram:IncludedNote");
+    }
+    xml.close("rsm:ExchangedDocument");
+
+    xml.open("rsm:SupplyChainTradeTransaction");
+    for line in &document.lines {
+        xml.open("ram:IncludedSupplyChainTradeLineItem");
+        xml.open("ram:AssociatedDocumentLineDocument");
+        xml.leaf("ram:LineID", &line.id);
+        xml.close("ram:AssociatedDocumentLineDocument");
+        xml.open("ram:SpecifiedTradeProduct");
+        xml.leaf("ram:Name", &line.name);
+        xml.leaf_opt("ram:Description", line.description.as_deref());
+        xml.close("ram:SpecifiedTradeProduct");
+        xml.open("ram:SpecifiedLineTradeAgreement");
+        xml.open("ram:NetPriceProductTradePrice");
+        xml.leaf("ram:ChargeAmount", &format_decimal(line.net_price));
+        xml.close("ram:NetPriceProductTradePrice");
+        xml.close("ram:SpecifiedLineTradeAgreement");
+        xml.open("ram:SpecifiedLineTradeDelivery");
+        xml.leaf_with(
+            "ram:BilledQuantity",
+            &format!(r#"unitCode="{}""#, escape(&line.unit_code)),
+            &decimal(line.quantity),
+        );
+        xml.close("ram:SpecifiedLineTradeDelivery");
+        xml.open("ram:SpecifiedLineTradeSettlement");
+        xml.open("ram:ApplicableTradeTax");
+        xml.leaf("ram:TypeCode", "VAT");
+        cii_category(&mut xml, line.vat_category, line.vat_rate);
+        xml.close("ram:ApplicableTradeTax");
+        xml.open("ram:SpecifiedTradeSettlementLineMonetarySummation");
+        xml.leaf("ram:LineTotalAmount", &format_decimal(line.net_amount));
+        xml.close("ram:SpecifiedTradeSettlementLineMonetarySummation");
+        xml.close("ram:SpecifiedLineTradeSettlement");
+        xml.close("ram:IncludedSupplyChainTradeLineItem");
+    }
+
+    xml.open("ram:ApplicableHeaderTradeAgreement");
+    cii_party(&mut xml, "ram:SellerTradeParty", &document.seller);
+    cii_party(&mut xml, "ram:BuyerTradeParty", &document.buyer);
+    xml.close("ram:ApplicableHeaderTradeAgreement");
+
+    xml.open("ram:ApplicableHeaderTradeDelivery");
+    xml.close("ram:ApplicableHeaderTradeDelivery");
+
+    xml.open("ram:ApplicableHeaderTradeSettlement");
+    xml.leaf("ram:InvoiceCurrencyCode", &document.currency);
+    for row in &document.vat_breakdown {
+        xml.open("ram:ApplicableTradeTax");
+        xml.leaf("ram:CalculatedAmount", &format_decimal(row.tax_amount));
+        xml.leaf("ram:TypeCode", "VAT");
+        xml.leaf_opt("ram:ExemptionReason", row.exemption_reason.as_deref());
+        xml.leaf("ram:BasisAmount", &format_decimal(row.taxable_amount));
+        cii_category(&mut xml, row.vat_category, row.vat_rate);
+        xml.close("ram:ApplicableTradeTax");
+    }
+    for allowance in &document.allowances {
+        xml.open("ram:SpecifiedTradeAllowanceCharge");
+        xml.open("ram:ChargeIndicator");
+        xml.leaf("udt:Indicator", "false");
+        xml.close("ram:ChargeIndicator");
+        xml.leaf("ram:ActualAmount", &format_decimal(allowance.amount));
+        xml.leaf("ram:Reason", &allowance.reason);
+        xml.open("ram:CategoryTradeTax");
+        xml.leaf("ram:TypeCode", "VAT");
+        cii_category(&mut xml, allowance.vat_category, allowance.vat_rate);
+        xml.close("ram:CategoryTradeTax");
+        xml.close("ram:SpecifiedTradeAllowanceCharge");
+    }
+    if document.payment_terms.is_some() || document.due_date.is_some() {
+        xml.open("ram:SpecifiedTradePaymentTerms");
+        xml.leaf_opt("ram:Description", document.payment_terms.as_deref());
+        if let Some(due_date) = document.due_date {
+            xml.open("ram:DueDateDateTime");
+            xml.leaf_with("udt:DateTimeString", r#"format="102""#, &cii_date(due_date));
+            xml.close("ram:DueDateDateTime");
+        }
+        xml.close("ram:SpecifiedTradePaymentTerms");
+    }
+    xml.open("ram:SpecifiedTradeSettlementHeaderMonetarySummation");
+    xml.leaf("ram:LineTotalAmount", &format_decimal(document.line_total));
+    xml.leaf(
+        "ram:AllowanceTotalAmount",
+        &format_decimal(document.allowance_total),
+    );
+    xml.leaf(
+        "ram:TaxBasisTotalAmount",
+        &format_decimal(document.tax_basis_total),
+    );
+    xml.leaf_with(
+        "ram:TaxTotalAmount",
+        &format!(r#"currencyID="{}""#, escape(&document.currency)),
+        &format_decimal(document.tax_total),
+    );
+    xml.leaf("ram:GrandTotalAmount", &format_decimal(document.grand_total));
+    xml.leaf("ram:TotalPrepaidAmount", &format_decimal(document.prepaid));
+    xml.leaf("ram:DuePayableAmount", &format_decimal(document.due_payable));
+    xml.close("ram:SpecifiedTradeSettlementHeaderMonetarySummation");
+    if let Some(preceding) = document.preceding_invoice.as_deref() {
+        xml.open("ram:InvoiceReferencedDocument");
+        xml.leaf("ram:IssuerAssignedID", preceding);
+        xml.close("ram:InvoiceReferencedDocument");
+    }
+    xml.close("ram:ApplicableHeaderTradeSettlement");
+    xml.close("rsm:SupplyChainTradeTransaction");
+
+    xml.close("rsm:CrossIndustryInvoice");
+    xml.finish()
+}
+
+fn ubl_party(xml: &mut XmlWriter, tag: &str, party: &EInvoiceParty) {
+    xml.open(tag);
+    xml.open("cac:Party");
+    xml.open("cac:PostalAddress");
+    xml.leaf_opt("cbc:StreetName", party.street.as_deref());
+    xml.leaf_opt("cbc:CityName", party.city.as_deref());
+    xml.leaf_opt("cbc:PostalZone", party.postcode.as_deref());
+    xml.open("cac:Country");
+    xml.leaf(
+        "cbc:IdentificationCode",
+        party.country_code.as_deref().unwrap_or_default(),
+    );
+    xml.close("cac:Country");
+    xml.close("cac:PostalAddress");
+    if let Some(vat_id) = party.vat_id.as_deref() {
+        xml.open("cac:PartyTaxScheme");
+        xml.leaf("cbc:CompanyID", vat_id);
+        xml.open("cac:TaxScheme");
+        xml.leaf("cbc:ID", "VAT");
+        xml.close("cac:TaxScheme");
+        xml.close("cac:PartyTaxScheme");
+    }
+    xml.open("cac:PartyLegalEntity");
+    xml.leaf("cbc:RegistrationName", &party.name);
+    if let Some(siret) = party.siret.as_deref() {
+        xml.leaf_with("cbc:CompanyID", r#"schemeID="0009""#, siret);
+    }
+    xml.close("cac:PartyLegalEntity");
+    if let Some(email) = party.email.as_deref() {
+        xml.open("cac:Contact");
+        xml.leaf("cbc:ElectronicMail", email);
+        xml.close("cac:Contact");
+    }
+    xml.close("cac:Party");
+    xml.close(tag);
+}
+
+fn ubl_tax_category(
+    xml: &mut XmlWriter,
+    tag: &str,
+    category: VatCategory,
+    rate: f64,
+    exemption_reason: Option<&str>,
+) {
+    xml.open(tag);
+    xml.leaf("cbc:ID", category.code());
+    xml.leaf("cbc:Percent", &decimal(rate));
+    xml.leaf_opt("cbc:TaxExemptionReason", exemption_reason);
+    xml.open("cac:TaxScheme");
+    xml.leaf("cbc:ID", "VAT");
+    xml.close("cac:TaxScheme");
+    xml.close(tag);
+}
+
+/// UBL 2.1 `Invoice`, or `CreditNote` for type code 381.
+pub fn render_ubl(document: &EInvoiceDocument) -> String {
+    let credit_note = document.type_code == TYPE_CODE_CREDIT_NOTE;
+    let (root, namespace, line_tag, quantity_tag) = if credit_note {
+        (
+            "CreditNote",
+            "urn:oasis:names:specification:ubl:schema:xsd:CreditNote-2",
+            "cac:CreditNoteLine",
+            "cbc:CreditedQuantity",
+        )
+    } else {
+        (
+            "Invoice",
+            "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2",
+            "cac:InvoiceLine",
+            "cbc:InvoicedQuantity",
+        )
+    };
+    let currency = format!(r#"currencyID="{}""#, escape(&document.currency));
+
+    let mut xml = XmlWriter::new();
+    xml.open_with(
+        root,
+        &format!(
+            r#"xmlns="{}" xmlns:cac="{}" xmlns:cbc="{}""#,
+            namespace, UBL_CAC, UBL_CBC
+        ),
+    );
+    xml.leaf("cbc:CustomizationID", EN16931_SPECIFICATION_ID);
+    xml.leaf("cbc:ID", &document.number);
+    xml.leaf(
+        "cbc:IssueDate",
+        &document.issue_date.map(ubl_date).unwrap_or_default(),
+    );
+    if credit_note {
+        xml.leaf("cbc:CreditNoteTypeCode", &document.type_code.to_string());
+    } else {
+        if let Some(due_date) = document.due_date {
+            xml.leaf("cbc:DueDate", &ubl_date(due_date));
+        }
+        xml.leaf("cbc:InvoiceTypeCode", &document.type_code.to_string());
+    }
+    xml.leaf_opt("cbc:Note", document.note.as_deref());
+    xml.leaf("cbc:DocumentCurrencyCode", &document.currency);
+    if let Some(preceding) = document.preceding_invoice.as_deref() {
+        xml.open("cac:BillingReference");
+        xml.open("cac:InvoiceDocumentReference");
+        xml.leaf("cbc:ID", preceding);
+        xml.close("cac:InvoiceDocumentReference");
+        xml.close("cac:BillingReference");
+    }
+    ubl_party(&mut xml, "cac:AccountingSupplierParty", &document.seller);
+    ubl_party(&mut xml, "cac:AccountingCustomerParty", &document.buyer);
+    if let Some(terms) = document.payment_terms.as_deref() {
+        xml.open("cac:PaymentTerms");
+        xml.leaf("cbc:Note", terms);
+        xml.close("cac:PaymentTerms");
+    }
+    for allowance in &document.allowances {
+        xml.open("cac:AllowanceCharge");
+        xml.leaf("cbc:ChargeIndicator", "false");
+        xml.leaf("cbc:AllowanceChargeReason", &allowance.reason);
+        xml.leaf_with("cbc:Amount", &currency, &format_decimal(allowance.amount));
+        ubl_tax_category(
+            &mut xml,
+            "cac:TaxCategory",
+            allowance.vat_category,
+            allowance.vat_rate,
+            None,
+        );
+        xml.close("cac:AllowanceCharge");
+    }
+    xml.open("cac:TaxTotal");
+    xml.leaf_with("cbc:TaxAmount", &currency, &format_decimal(document.tax_total));
+    for row in &document.vat_breakdown {
+        xml.open("cac:TaxSubtotal");
+        xml.leaf_with(
+            "cbc:TaxableAmount",
+            &currency,
+            &format_decimal(row.taxable_amount),
+        );
+        xml.leaf_with("cbc:TaxAmount", &currency, &format_decimal(row.tax_amount));
+        ubl_tax_category(
+            &mut xml,
+            "cac:TaxCategory",
+            row.vat_category,
+            row.vat_rate,
+            row.exemption_reason.as_deref(),
+        );
+        xml.close("cac:TaxSubtotal");
+    }
+    xml.close("cac:TaxTotal");
+    xml.open("cac:LegalMonetaryTotal");
+    xml.leaf_with(
+        "cbc:LineExtensionAmount",
+        &currency,
+        &format_decimal(document.line_total),
+    );
+    xml.leaf_with(
+        "cbc:TaxExclusiveAmount",
+        &currency,
+        &format_decimal(document.tax_basis_total),
+    );
+    xml.leaf_with(
+        "cbc:TaxInclusiveAmount",
+        &currency,
+        &format_decimal(document.grand_total),
+    );
+    xml.leaf_with(
+        "cbc:AllowanceTotalAmount",
+        &currency,
+        &format_decimal(document.allowance_total),
+    );
+    xml.leaf_with(
+        "cbc:PrepaidAmount",
+        &currency,
+        &format_decimal(document.prepaid),
+    );
+    xml.leaf_with(
+        "cbc:PayableAmount",
+        &currency,
+        &format_decimal(document.due_payable),
+    );
+    xml.close("cac:LegalMonetaryTotal");
+    for line in &document.lines {
+        xml.open(line_tag);
+        xml.leaf("cbc:ID", &line.id);
+        xml.leaf_with(
+            quantity_tag,
+            &format!(r#"unitCode="{}""#, escape(&line.unit_code)),
+            &decimal(line.quantity),
+        );
+        xml.leaf_with(
+            "cbc:LineExtensionAmount",
+            &currency,
+            &format_decimal(line.net_amount),
+        );
+        xml.open("cac:Item");
+        xml.leaf_opt("cbc:Description", line.description.as_deref());
+        xml.leaf("cbc:Name", &line.name);
+        ubl_tax_category(
+            &mut xml,
+            "cac:ClassifiedTaxCategory",
+            line.vat_category,
+            line.vat_rate,
+            None,
+        );
+        xml.close("cac:Item");
+        xml.open("cac:Price");
+        xml.leaf_with("cbc:PriceAmount", &currency, &format_decimal(line.net_price));
+        xml.close("cac:Price");
+        xml.close(line_tag);
+    }
+    xml.close(root);
+    xml.finish()
+}
@@ -0,0 +1,214 @@
+//! Factur-X (EN 16931 profile) writer: the invoice pages as PDF/A-3b with
+//! the CII XML attached as `factur-x.xml`.
+//!
+//! What PDF/A-3b asks of this otherwise hand-written PDF: embedded fonts
+//! (see `pdf_font`), XMP metadata declaring the PDF/A part and the Factur-X
+//! extension schema, a device-independent `DefaultGray` instead of an ICC
+//! output intent (text is the only content and is black), a trailer `/ID`,
+//! and the attachment linked from the catalog's `/AF` array.
+
+use std::sync::OnceLock;
+
+use chrono::{DateTime, Utc};
+use sha2::{Digest, Sha256};
+
+use crate::domains::invoices::application::services::einvoice_xml::escape;
+use crate::domains::invoices::application::services::invoice_pdf::{
+    assemble_pdf, stream_object,
+};
+use crate::domains::invoices::application::services::pdf_font::{
+    embed_win_ansi, EmbeddedFont, FIRST_CHAR, LAST_CHAR,
+};
+use crate::shared::error::{AppError, AppResult};
+
+pub const FACTURX_FILE_NAME: &str = "factur-x.xml";
+
+const REGULAR_TTF: &[u8] = include_bytes!("../../../../../fonts/LiberationSans-Regular.ttf");
+const BOLD_TTF: &[u8] = include_bytes!("../../../../../fonts/LiberationSans-Bold.ttf");
+
+/// CIE 1931 D65 white point for the `CalGray` that stands in for DeviceGray.
+const DEFAULT_GRAY: &str = "/ColorSpace<</DefaultGray[/CalGray<</WhitePoint[0.9505 1 1.089]>>]>>";
+
+pub struct FacturXInput<'a> {
+    /// Page content streams from `invoice_pdf::page_streams`.
+    pub pages: Vec<Vec<u8>>,
+    /// CII XML from `einvoice_xml::render_cii`.
+    pub xml: &'a str,
+    /// `dc:title`, e.g. `Facture F-2026-00001`.
+    pub title: &'a str,
+    /// `dc:creator`: the seller.
+    pub author: &'a str,
+    pub created_at: DateTime<Utc>,
+}
+
+fn fonts() -> AppResult<&'static (EmbeddedFont, EmbeddedFont)> {
+    static FONTS: OnceLock<Option<(EmbeddedFont, EmbeddedFont)>> = OnceLock::new();
+    FONTS
+        .get_or_init(|| Some((embed_win_ansi(REGULAR_TTF)?, embed_win_ansi(BOLD_TTF)?)))
+        .as_ref()
+        .ok_or_else(|| AppError::Internal("Bundled PDF font could not be parsed".to_string()))
+}
+
+fn font_objects(font: &EmbeddedFont, base_font: &str, first_id: usize) -> [Vec<u8>; 3] {
+    let widths = font
+        .widths
+        .iter()
+        .map(i64::to_string)
+        .collect::<Vec<_>>()
+        .join(" ");
+    [
+        format!(
+            "<</Type/Font/Subtype/TrueType/BaseFont/{}/FirstChar {}/LastChar {}/Widths[{}]/Encoding/WinAnsiEncoding/FontDescriptor {} 0 R>>",
+            base_font, FIRST_CHAR, LAST_CHAR, widths, first_id + 1
+        )
+        .into_bytes(),
+        format!(
+            "<</Type/FontDescriptor/FontName/{}/Flags 32/FontBBox[{} {} {} {}]/ItalicAngle 0/Ascent {}/Descent {}/CapHeight {}/StemV 80/FontFile2 {} 0 R>>",
+            base_font,
+            font.bbox[0],
+            font.bbox[1],
+            font.bbox[2],
+            font.bbox[3],
+            font.ascent,
+            font.descent,
+            font.cap_height,
+            first_id + 2
+        )
+        .into_bytes(),
+        stream_object(
+            &format!("/Length1 {}", font.program.len()),
+            &font.program,
+        ),
+    ]
+}
+
+fn xmp_metadata(input: &FacturXInput<'_>) -> String {
+    let date = input.created_at.format("%Y-%m-%dT%H:%M:%SZ").to_string();
+    let property = |name: &str, description: &str| {
+        format!(
+            r#"<rdf:li rdf:parseType="Resource"><pdfaProperty:name>{}</pdfaProperty:name><pdfaProperty:valueType>Text</pdfaProperty:valueType><pdfaProperty:category>external</pdfaProperty:category><pdfaProperty:description>{}</pdfaProperty:description></rdf:li>"#,
+            name, description
+        )
+    };
+    format!(
+        concat!(
+            "<?xpacket begin=\"\u{feff}\" id=\"W5M0MpCehiHzreSzNTczkc9d\"?>\n",
+            "<x:xmpmeta xmlns:x=\"adobe:ns:meta/\">\n",
+            "<rdf:RDF xmlns:rdf=\"http://www.w3.org/1999/02/22-rdf-syntax-ns#\">\n",
+            "<rdf:Description rdf:about=\"\" xmlns:pdfaid=\"http://www.aiim.org/pdfa/ns/id/\">",
+            "<pdfaid:part>3</pdfaid:part><pdfaid:conformance>B</pdfaid:conformance></rdf:Description>\n",
+            "<rdf:Description rdf:about=\"\" xmlns:dc=\"http://purl.org/dc/elements/1.1/\">",
+            "<dc:format>application/pdf</dc:format>",
+            "<dc:title><rdf:Alt><rdf:li xml:lang=\"x-default\">{title}</rdf:li></rdf:Alt></dc:title>",
+            "<dc:creator><rdf:Seq><rdf:li>{author}</rdf:li></rdf:Seq></dc:creator></rdf:Description>\n",
+            "<rdf:Description rdf:about=\"\" xmlns:xmp=\"http://ns.adobe.com/xap/1.0/\">",
+            "<xmp:CreateDate>{date}</xmp:CreateDate><xmp:ModifyDate>{date}</xmp:ModifyDate>",
+            "<xmp:MetadataDate>{date}</xmp:MetadataDate></rdf:Description>\n",
+            "<rdf:Description rdf:about=\"\" xmlns:pdfaExtension=\"http://www.aiim.org/pdfa/ns/extension/\" ",
+            "xmlns:pdfaSchema=\"http://www.aiim.org/pdfa/ns/schema#\" xmlns:pdfaProperty=\"http://www.aiim.org/pdfa/ns/property#\">",
+            "<pdfaExtension:schemas><rdf:Bag><rdf:li rdf:parseType=\"Resource\">",
+            "<pdfaSchema:schema>Factur-X PDFA Extension Schema</pdfaSchema:schema>",
+            "<pdfaSchema:namespaceURI>urn:factur-x:pdfa:CrossIndustryDocument:invoice:1p0#</pdfaSchema:namespaceURI>",
+            "<pdfaSchema:prefix>fx</pdfaSchema:prefix><pdfaSchema:property><rdf:Seq>",
+            "{properties}</rdf:Seq></pdfaSchema:property></rdf:li></rdf:Bag></pdfaExtension:schemas></rdf:Description>\n",
+            "<rdf:Description rdf:about=\"\" xmlns:fx=\"urn:factur-x:pdfa:CrossIndustryDocument:invoice:1p0#\">",
+            "<fx:DocumentType>INVOICE</fx:DocumentType><fx:DocumentFileName>{file_name}</fx:DocumentFileName>",
+            "<fx:Version>1.0</fx:Version><fx:ConformanceLevel>EN 16931</fx:ConformanceLevel></rdf:Description>\n",
+            "</rdf:RDF>\n</x:xmpmeta>\n<?xpacket end=\"w\"?>"
+        ),
+        title = escape(input.title),
+        author = escape(input.author),
+        date = date,
+        file_name = FACTURX_FILE_NAME,
+        properties = [
+            property("DocumentFileName", "The name of the embedded XML document"),
+            property("DocumentType", "The type of the hybrid document in capital letters"),
+            property("Version", "The actual version of the standard applying to the embedded XML document"),
+            property("ConformanceLevel", "The conformance level of the embedded XML document"),
+        ]
+        .concat(),
+    )
+}
+
+/// PDF string literal for ASCII `text`.
+fn pdf_string(text: &str) -> String {
+    let mut out = String::from("(");
+    for c in text.chars() {
+        if matches!(c, '\\' | '(' | ')') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out.push(')');
+    out
+}
+
+pub fn render_facturx_pdf(input: FacturXInput<'_>) -> AppResult<Vec<u8>> {
+    let (regular, bold) = fonts()?;
+    // Objects: 1 catalog, 2 page tree, 3 metadata, 4 XML file, 5 file spec,
+    // 6-8 regular font, 9-11 bold font, then (page, content) pairs.
+    let page_ids: Vec<usize> = (0..input.pages.len())
+        .map(|index| 12 + index * 2)
+        .collect();
+    let pdf_date = input.created_at.format("D:%Y%m%d%H%M%S+00'00'").to_string();
+
+    let mut objects: Vec<Vec<u8>> = vec![
+        format!(
+            "<</Type/Catalog/Pages 2 0 R/Metadata 3 0 R/Names<</EmbeddedFiles<</Names[{} 5 0 R]>>>>/AF[5 0 R]/PageMode/UseAttachments>>",
+            pdf_string(FACTURX_FILE_NAME)
+        )
+        .into_bytes(),
+        format!(
+            "<</Type/Pages/Kids[{}]/Count {}>>",
+            page_ids
+                .iter()
+                .map(|id| format!("{} 0 R", id))
+                .collect::<Vec<_>>()
+                .join(" "),
+            input.pages.len()
+        )
+        .into_bytes(),
+        stream_object("/Type/Metadata/Subtype/XML", xmp_metadata(&input).as_bytes()),
+        stream_object(
+            &format!(
+                "/Type/EmbeddedFile/Subtype/text#2Fxml/Params<</ModDate{}/Size {}>>",
+                pdf_string(&pdf_date),
+                input.xml.len()
+            ),
+            input.xml.as_bytes(),
+        ),
+        format!(
+            "<</Type/Filespec/F{name}/UF{name}/Desc{desc}/AFRelationship/Data/EF<</F 4 0 R/UF 4 0 R>>>>",
+            name = pdf_string(FACTURX_FILE_NAME),
+            desc = pdf_string("Factur-X invoice data")
+        )
+        .into_bytes(),
+    ];
+    objects.extend(font_objects(regular, "LiberationSans", 6));
+    objects.extend(font_objects(bold, "LiberationSans-Bold", 9));
+    for (stream, page_id) in input.pages.iter().zip(&page_ids) {
+        objects.push(
+            format!(
+                "<</Type/Page/Parent 2 0 R/MediaBox[0 0 595 842]/Contents {} 0 R/Resources<</Font<</F1 6 0 R/F2 9 0 R>>{}>>>>",
+                page_id + 1,
+                DEFAULT_GRAY
+            )
+            .into_bytes(),
+        );
+        objects.push(stream_object("", stream));
+    }
+
+    let digest = Sha256::new()
+        .chain_update(input.title.as_bytes())
+        .chain_update(input.xml.as_bytes())
+        .finalize();
+    let id = digest[..16]
+        .iter()
+        .map(|byte| format!("{:02X}", byte))
+        .collect::<String>();
+    Ok(assemble_pdf(
+        "1.7",
+        &objects,
+        &format!("/ID[<{}><{}>]", id, id),
+    ))
+}
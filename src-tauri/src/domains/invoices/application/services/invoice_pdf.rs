@@ -0,0 +1,435 @@
+//! PDF rendering for invoices and credit notes.
+//!
+//! Same hand-written approach as the quote export (standard Helvetica, no
+//! external renderer), with WinAnsi encoding so accents and `€` print
+//! correctly, real xref offsets, and automatic page breaks.
+
+use chrono::TimeZone;
+use chrono_tz::Europe::Paris;
+
+use crate::domains::invoices::domain::models::invoices::{Invoice, InvoiceKind, InvoiceStatus};
+use crate::shared::services::cross_domain::Organization;
+use crate::shared::utils::money::format_eur;
+
+const PAGE_TOP: i32 = 800;
+const PAGE_BOTTOM: i32 = 50;
+const LEFT_MARGIN: i32 = 50;
+const WRAP_COLUMNS: usize = 95;
+
+/// Seller block printed at the top of every invoice.
+#[derive(Debug, Clone, Default)]
+pub struct InvoiceSeller {
+    pub name: String,
+    pub address_lines: Vec<String>,
+    pub siret: Option<String>,
+    pub tax_id: Option<String>,
+    pub email: Option<String>,
+    pub phone: Option<String>,
+}
+
+impl InvoiceSeller {
+    pub fn from_organization(organization: &Organization) -> Self {
+        let city_line = [
+            organization.address_zip.as_deref(),
+            organization.address_city.as_deref(),
+        ]
+        .into_iter()
+        .flatten()
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+        let address_lines = [
+            organization.address_street.clone(),
+            Some(city_line),
+            organization.address_country.clone(),
+        ]
+        .into_iter()
+        .flatten()
+        .filter(|line| !line.trim().is_empty())
+        .collect();
+        Self {
+            name: organization
+                .legal_name
+                .clone()
+                .filter(|name| !name.trim().is_empty())
+                .unwrap_or_else(|| organization.name.clone()),
+            address_lines,
+            siret: organization.siret.clone(),
+            tax_id: organization.tax_id.clone(),
+            email: organization.email.clone(),
+            phone: organization.phone.clone(),
+        }
+    }
+}
+
+struct TextLine {
+    size: u8,
+    bold: bool,
+    text: String,
+}
+
+impl TextLine {
+    fn new(size: u8, bold: bool, text: impl Into<String>) -> Self {
+        Self {
+            size,
+            bold,
+            text: text.into(),
+        }
+    }
+}
+
+fn format_date(timestamp_ms: Option<i64>) -> String {
+    timestamp_ms
+        .and_then(|ms| Paris.timestamp_millis_opt(ms).single())
+        .map(|date| date.format("%d/%m/%Y").to_string())
+        .unwrap_or_else(|| "—".to_string())
+}
+
+fn format_rate(rate: f64) -> String {
+    let text = format!("{:.2}", rate);
+    let text = text.trim_end_matches('0').trim_end_matches('.');
+    text.replace('.', ",")
+}
+
+fn format_qty(qty: f64) -> String {
+    format_rate(qty)
+}
+
+fn wrap(text: &str) -> Vec<String> {
+    let mut lines = Vec::new();
+    for paragraph in text.lines() {
+        let mut current = String::new();
+        for word in paragraph.split_whitespace() {
+            if !current.is_empty()
+                && current.chars().count() + 1 + word.chars().count() > WRAP_COLUMNS
+            {
+                lines.push(std::mem::take(&mut current));
+            }
+            if !current.is_empty() {
+                current.push(' ');
+            }
+            current.push_str(word);
+        }
+        lines.push(current);
+    }
+    lines
+}
+
+fn layout(
+    invoice: &Invoice,
+    seller: &InvoiceSeller,
+    legal_mentions: Option<&str>,
+    title: Option<&str>,
+) -> Vec<TextLine> {
+    let mut out = Vec::new();
+    let document = match invoice.kind {
+        InvoiceKind::Invoice => "FACTURE",
+        InvoiceKind::CreditNote => "AVOIR",
+    };
+    let title = match (title, &invoice.invoice_number) {
+        (Some(title), _) => title.to_string(),
+        (None, Some(number)) => format!("{} N° {}", document, number),
+        (None, None) => format!("{} (brouillon)", document),
+    };
+    out.push(TextLine::new(16, true, title));
+    if invoice.status == InvoiceStatus::Cancelled {
+        out.push(TextLine::new(10, true, "Annulée par avoir"));
+    }
+    out.push(TextLine::new(10, false, ""));
+
+    out.push(TextLine::new(11, true, seller.name.clone()));
+    for line in &seller.address_lines {
+        out.push(TextLine::new(10, false, line.clone()));
+    }
+    if let Some(siret) = seller.siret.as_deref().filter(|value| !value.is_empty()) {
+        out.push(TextLine::new(10, false, format!("SIRET : {}", siret)));
+    }
+    if let Some(tax_id) = seller.tax_id.as_deref().filter(|value| !value.is_empty()) {
+        out.push(TextLine::new(
+            10,
+            false,
+            format!("N° TVA intracommunautaire : {}", tax_id),
+        ));
+    }
+    let contact = [seller.phone.as_deref(), seller.email.as_deref()]
+        .into_iter()
+        .flatten()
+        .filter(|value| !value.is_empty())
+        .collect::<Vec<_>>()
+        .join(" — ");
+    if !contact.is_empty() {
+        out.push(TextLine::new(10, false, contact));
+    }
+    out.push(TextLine::new(10, false, ""));
+
+    out.push(TextLine::new(11, true, "Client"));
+    out.push(TextLine::new(
+        10,
+        false,
+        invoice
+            .client_name
+            .clone()
+            .unwrap_or_else(|| invoice.client_id.clone()),
+    ));
+    if let Some(address) = &invoice.client_address {
+        for line in address.lines().filter(|line| !line.trim().is_empty()) {
+            out.push(TextLine::new(10, false, line.to_string()));
+        }
+    }
+    out.push(TextLine::new(10, false, ""));
+
+    out.push(TextLine::new(
+        10,
+        false,
+        format!("Date d'émission : {}", format_date(invoice.issue_date)),
+    ));
+    if invoice.kind == InvoiceKind::Invoice {
+        out.push(TextLine::new(
+            10,
+            false,
+            format!("Date d'échéance : {}", format_date(invoice.due_date)),
+        ));
+    }
+    if let Some(notes) = invoice.notes.as_deref().filter(|notes| !notes.is_empty()) {
+        for line in wrap(notes) {
+            out.push(TextLine::new(10, false, line));
+        }
+    }
+    out.push(TextLine::new(10, false, ""));
+
+    out.push(TextLine::new(11, true, "Désignation"));
+    for line in &invoice.lines {
+        out.push(TextLine::new(10, false, line.label.clone()));
+        out.push(TextLine::new(
+            9,
+            false,
+            format!(
+                "    {} × {} HT — TVA {} % — {} HT",
+                format_qty(line.qty),
+                format_eur(line.unit_price),
+                format_rate(line.tax_rate),
+                format_eur(line.line_total)
+            ),
+        ));
+    }
+    out.push(TextLine::new(10, false, ""));
+
+    if invoice.discount_amount > 0 {
+        out.push(TextLine::new(
+            10,
+            false,
+            format!("Remise : -{}", format_eur(invoice.discount_amount)),
+        ));
+    }
+    out.push(TextLine::new(
+        10,
+        false,
+        format!("Total HT : {}", format_eur(invoice.subtotal)),
+    ));
+    for vat in &invoice.vat_breakdown {
+        out.push(TextLine::new(
+            10,
+            false,
+            format!(
+                "TVA {} % sur {} : {}",
+                format_rate(vat.tax_rate),
+                format_eur(vat.taxable_amount),
+                format_eur(vat.tax_amount)
+            ),
+        ));
+    }
+    out.push(TextLine::new(
+        12,
+        true,
+        format!("Total TTC : {}", format_eur(invoice.total)),
+    ));
+    if invoice.kind == InvoiceKind::Invoice && invoice.amount_paid > 0 {
+        out.push(TextLine::new(
+            10,
+            false,
+            format!("Déjà réglé : {}", format_eur(invoice.amount_paid)),
+        ));
+        out.push(TextLine::new(
+            10,
+            true,
+            format!(
+                "Reste à payer : {}",
+                format_eur((invoice.total - invoice.amount_paid).max(0))
+            ),
+        ));
+    }
+
+    for block in [invoice.terms.as_deref(), legal_mentions]
+        .into_iter()
+        .flatten()
+        .filter(|block| !block.trim().is_empty())
+    {
+        out.push(TextLine::new(10, false, ""));
+        for line in wrap(block) {
+            out.push(TextLine::new(8, false, line));
+        }
+    }
+    out
+}
+
+/// Encode text for a PDF string literal in WinAnsiEncoding. Characters
+/// outside the code page become `?`.
+fn encode_text(text: &str) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(text.len());
+    for c in text.chars() {
+        let byte = match c {
+            '\\' | '(' | ')' => {
+                bytes.push(b'\\');
+                c as u8
+            }
+            ' '..='~' => c as u8,
+            '€' => 0x80,
+            '‚' => 0x82,
+            '„' => 0x84,
+            '…' => 0x85,
+            '‘' => 0x91,
+            '’' => 0x92,
+            '“' => 0x93,
+            '”' => 0x94,
+            '•' => 0x95,
+            '–' => 0x96,
+            '—' => 0x97,
+            'Œ' => 0x8C,
+            'œ' => 0x9C,
+            'Ÿ' => 0x9F,
+            '\u{a0}'..='\u{ff}' => c as u32 as u8,
+            _ => b'?',
+        };
+        bytes.push(byte);
+    }
+    bytes
+}
+
+fn page_stream(lines: &[TextLine]) -> Vec<u8> {
+    let mut stream = Vec::new();
+    let mut y = PAGE_TOP;
+    for line in lines {
+        y -= i32::from(line.size) + 4;
+        stream.extend_from_slice(
+            format!(
+                "BT /{} {} Tf {} {} Td (",
+                if line.bold { "F2" } else { "F1" },
+                line.size,
+                LEFT_MARGIN,
+                y
+            )
+            .as_bytes(),
+        );
+        stream.extend_from_slice(&encode_text(&line.text));
+        stream.extend_from_slice(b") Tj ET\n");
+    }
+    stream
+}
+
+fn paginate(lines: Vec<TextLine>) -> Vec<Vec<TextLine>> {
+    let mut pages = vec![Vec::new()];
+    let mut y = PAGE_TOP;
+    for line in lines {
+        let height = i32::from(line.size) + 4;
+        if y - height < PAGE_BOTTOM {
+            pages.push(Vec::new());
+            y = PAGE_TOP;
+        }
+        y -= height;
+        pages.last_mut().expect("at least one page").push(line);
+    }
+    pages
+}
+
+/// Content stream of each A4 page, drawing with fonts `/F1` (regular) and
+/// `/F2` (bold) in WinAnsiEncoding. `title` replaces the document heading,
+/// e.g. for a pro-forma.
+pub(crate) fn page_streams(
+    invoice: &Invoice,
+    seller: &InvoiceSeller,
+    legal_mentions: Option<&str>,
+    title: Option<&str>,
+) -> Vec<Vec<u8>> {
+    paginate(layout(invoice, seller, legal_mentions, title))
+        .iter()
+        .map(|page| page_stream(page))
+        .collect()
+}
+
+/// `<</Length n>>stream … endstream` object body.
+pub(crate) fn stream_object(dictionary_entries: &str, data: &[u8]) -> Vec<u8> {
+    let mut object = format!("<<{}/Length {}>>stream\n", dictionary_entries, data.len()).into_bytes();
+    object.extend_from_slice(data);
+    object.extend_from_slice(b"\nendstream");
+    object
+}
+
+/// Serialise numbered objects (object `n` is `objects[n - 1]`, object 1 the
+/// catalog) with a cross-reference table. `trailer_entries` is appended to
+/// the trailer dictionary.
+pub(crate) fn assemble_pdf(version: &str, objects: &[Vec<u8>], trailer_entries: &str) -> Vec<u8> {
+    let mut pdf = format!("%PDF-{}\n", version).into_bytes();
+    pdf.extend_from_slice(b"%\xE2\xE3\xCF\xD3\n");
+    let mut offsets = Vec::with_capacity(objects.len());
+    for (index, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        pdf.extend_from_slice(format!("{} 0 obj\n", index + 1).as_bytes());
+        pdf.extend_from_slice(object);
+        pdf.extend_from_slice(b"\nendobj\n");
+    }
+    let xref_offset = pdf.len();
+    pdf.extend_from_slice(
+        format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1).as_bytes(),
+    );
+    for offset in offsets {
+        pdf.extend_from_slice(format!("{:010} 00000 n \n", offset).as_bytes());
+    }
+    pdf.extend_from_slice(
+        format!(
+            "trailer<</Size {}/Root 1 0 R{}>>\nstartxref\n{}\n%%EOF",
+            objects.len() + 1,
+            trailer_entries,
+            xref_offset
+        )
+        .as_bytes(),
+    );
+    pdf
+}
+
+/// Render an invoice or credit note to PDF bytes (A4).
+pub fn render_invoice_pdf(
+    invoice: &Invoice,
+    seller: &InvoiceSeller,
+    legal_mentions: Option<&str>,
+) -> Vec<u8> {
+    let pages = page_streams(invoice, seller, legal_mentions, None);
+    // Objects: 1 catalog, 2 page tree, 3-4 fonts, then (page, content) pairs.
+    let page_ids: Vec<usize> = (0..pages.len()).map(|index| 5 + index * 2).collect();
+    let mut objects: Vec<Vec<u8>> = vec![
+        b"<</Type/Catalog/Pages 2 0 R>>".to_vec(),
+        format!(
+            "<</Type/Pages/Kids[{}]/Count {}>>",
+            page_ids
+                .iter()
+                .map(|id| format!("{} 0 R", id))
+                .collect::<Vec<_>>()
+                .join(" "),
+            pages.len()
+        )
+        .into_bytes(),
+        b"<</Type/Font/Subtype/Type1/BaseFont/Helvetica/Encoding/WinAnsiEncoding>>".to_vec(),
+        b"<</Type/Font/Subtype/Type1/BaseFont/Helvetica-Bold/Encoding/WinAnsiEncoding>>".to_vec(),
+    ];
+    for (stream, page_id) in pages.iter().zip(&page_ids) {
+        objects.push(
+            format!(
+                "<</Type/Page/Parent 2 0 R/MediaBox[0 0 595 842]/Contents {} 0 R/Resources<</Font<</F1 3 0 R/F2 4 0 R>>>>>>",
+                page_id + 1
+            )
+            .into_bytes(),
+        );
+        objects.push(stream_object("", stream));
+    }
+    assemble_pdf("1.4", &objects, "")
+}
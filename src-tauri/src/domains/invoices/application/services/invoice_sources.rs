@@ -0,0 +1,184 @@
+//! Turn an accepted quote or a completed intervention into a draft invoice.
+//!
+//! The IPC layer loads the source records from their own domains (ADR-003)
+//! and hands them here; nothing in this module touches another domain's
+//! storage.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use crate::domains::invoices::domain::models::invoices::{
+    CreateInvoiceRequest, InvoiceBillingSettings, InvoiceLineInput, InvoiceLineKind,
+};
+use crate::shared::error::{AppError, AppResult};
+use crate::shared::services::cross_domain::{
+    Intervention, InterventionStatus, Material, MaterialConsumption, Quote, QuoteItemKind,
+    QuoteStatus,
+};
+
+/// Quote items as invoice lines, in quote order.
+///
+/// Items without a tax rate were untaxed on the quote and stay at 0 % so the
+/// invoice matches what the customer accepted.
+pub fn lines_from_quote(quote: &Quote) -> Vec<InvoiceLineInput> {
+    let mut items = quote.items.clone();
+    items.sort_by_key(|item| item.position);
+    items
+        .into_iter()
+        .map(|item| InvoiceLineInput {
+            kind: match item.kind {
+                QuoteItemKind::Labor => InvoiceLineKind::Labor,
+                QuoteItemKind::Material => InvoiceLineKind::Material,
+                QuoteItemKind::Service => InvoiceLineKind::Service,
+                QuoteItemKind::Discount => InvoiceLineKind::Discount,
+            },
+            label: item.label,
+            description: item.description,
+            qty: item.qty,
+            unit_price: item.unit_price,
+            tax_rate: Some(item.tax_rate.unwrap_or(0.0)),
+            material_id: item.material_id,
+        })
+        .collect()
+}
+
+/// Build a draft invoice request mirroring an accepted quote line for line.
+pub fn request_from_quote(quote: &Quote) -> AppResult<CreateInvoiceRequest> {
+    if !matches!(quote.status, QuoteStatus::Accepted | QuoteStatus::Converted) {
+        return Err(AppError::Validation(format!(
+            "Only accepted quotes can be invoiced (quote {} is '{}')",
+            quote.quote_number, quote.status
+        )));
+    }
+    if quote.items.is_empty() {
+        return Err(AppError::Validation(format!(
+            "Quote {} has no items to invoice",
+            quote.quote_number
+        )));
+    }
+
+    let lines = lines_from_quote(quote);
+
+    Ok(CreateInvoiceRequest {
+        client_id: quote.client_id.clone(),
+        task_id: quote.task_id.clone(),
+        quote_id: Some(quote.id.clone()),
+        intervention_id: None,
+        due_date: None,
+        notes: Some(match &quote.description {
+            Some(description) if !description.trim().is_empty() => {
+                format!("Devis {} — {}", quote.quote_number, description.trim())
+            }
+            _ => format!("Devis {}", quote.quote_number),
+        }),
+        terms: quote.terms.clone(),
+        discount_amount: quote.discount_amount.filter(|amount| *amount > 0),
+        lines,
+    })
+}
+
+/// The catalogue entries to load for `consumptions`, each once.
+pub fn consumed_material_ids(consumptions: &[MaterialConsumption]) -> Vec<&str> {
+    consumptions
+        .iter()
+        .map(|consumption| consumption.material_id.as_str())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+/// Build a draft invoice request from what was actually done: labour time
+/// at the organization's hourly rate plus consumed materials at cost with the
+/// configured markup.
+///
+/// `materials` carries the catalogue entry for each consumption's
+/// `material_id` when it still exists (for its name and fallback unit cost),
+/// keyed by id.
+pub fn request_from_intervention(
+    intervention: &Intervention,
+    consumptions: &[MaterialConsumption],
+    materials: &HashMap<String, Material>,
+    settings: &InvoiceBillingSettings,
+) -> AppResult<CreateInvoiceRequest> {
+    if intervention.status != InterventionStatus::Completed {
+        return Err(AppError::Validation(format!(
+            "Only completed interventions can be invoiced (status is '{}')",
+            intervention.status
+        )));
+    }
+    let client_id = intervention.client_id.clone().ok_or_else(|| {
+        AppError::Validation("The intervention has no client to invoice".to_string())
+    })?;
+
+    let mut lines = Vec::new();
+    if let Some(minutes) = intervention.actual_duration.filter(|minutes| *minutes > 0) {
+        if settings.labor_hourly_rate > 0 {
+            lines.push(InvoiceLineInput {
+                kind: InvoiceLineKind::Labor,
+                label: "Main d'œuvre — pose de film de protection".to_string(),
+                description: intervention
+                    .task_number
+                    .as_ref()
+                    .map(|number| format!("Intervention {}", number)),
+                qty: (minutes as f64 / 60.0 * 100.0).round() / 100.0,
+                unit_price: settings.labor_hourly_rate,
+                tax_rate: Some(settings.default_tax_rate),
+                material_id: None,
+            });
+        }
+    }
+
+    // One line per material: consumptions recorded step by step are summed.
+    let mut per_material: BTreeMap<&str, (f64, Option<f64>)> = BTreeMap::new();
+    for consumption in consumptions {
+        let entry = per_material
+            .entry(consumption.material_id.as_str())
+            .or_insert((0.0, None));
+        entry.0 += consumption.quantity_used;
+        if entry.1.is_none() {
+            entry.1 = consumption.unit_cost;
+        }
+    }
+    for (material_id, (quantity, unit_cost)) in per_material {
+        if quantity <= 0.0 {
+            continue;
+        }
+        let material = materials.get(material_id);
+        let unit_cost = unit_cost
+            .or_else(|| material.and_then(|material| material.unit_cost))
+            .unwrap_or(0.0);
+        let cost_cents = (unit_cost * 100.0).round();
+        lines.push(InvoiceLineInput {
+            kind: InvoiceLineKind::Material,
+            label: material
+                .map(|material| material.name.clone())
+                .unwrap_or_else(|| material_id.to_string()),
+            description: material.map(|material| material.sku.clone()),
+            qty: quantity,
+            unit_price: (cost_cents * (1.0 + settings.material_markup_percent / 100.0)).round()
+                as i64,
+            tax_rate: Some(settings.default_tax_rate),
+            material_id: Some(material_id.to_string()),
+        });
+    }
+
+    if lines.is_empty() {
+        return Err(AppError::Validation(
+            "Nothing to invoice: no labour time at a configured rate and no consumed materials"
+                .to_string(),
+        ));
+    }
+
+    Ok(CreateInvoiceRequest {
+        client_id,
+        task_id: Some(intervention.task_id.clone()),
+        quote_id: None,
+        intervention_id: Some(intervention.id.clone()),
+        due_date: None,
+        notes: Some(intervention.vehicle_plate.trim())
+            .filter(|plate| !plate.is_empty())
+            .map(|plate| format!("Véhicule {}", plate)),
+        terms: None,
+        discount_amount: None,
+        lines,
+    })
+}
@@ -0,0 +1,878 @@
+use chrono::{Datelike, Utc};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use crate::db::Database;
+use crate::domains::invoices::application::services::einvoice::{
+    document_from_invoice, seller_party, EInvoiceSource,
+};
+use crate::domains::invoices::application::services::einvoice_xml::{render_cii, render_ubl};
+use crate::domains::invoices::application::services::facturx::{render_facturx_pdf, FacturXInput};
+use crate::domains::invoices::application::services::invoice_pdf::{
+    page_streams, render_invoice_pdf, InvoiceSeller,
+};
+use crate::domains::invoices::application::services::invoice_sources::lines_from_quote;
+use crate::domains::invoices::domain::models::einvoice::{
+    validate_en16931, EInvoiceDocument, EInvoiceFormat, EInvoiceViolation,
+};
+use crate::domains::invoices::domain::models::invoices::{
+    compute_invoice_totals, CreateCreditNoteRequest, CreateInvoiceRequest, Invoice,
+    InvoiceBillingSettings, InvoiceExportResponse, InvoiceKind, InvoiceLine, InvoiceLineInput,
+    InvoiceLineKind, InvoiceListResponse, InvoiceQuery, InvoiceStatus, UpdateInvoiceRequest,
+};
+use crate::domains::invoices::domain::models::payments::{outstanding_cents, InvoiceBalance};
+use crate::domains::invoices::infrastructure::invoices_repository::{
+    InvoicesRepository, NumberingRequest, SqliteInvoicesRepository,
+};
+use crate::domains::invoices::infrastructure::payments_repository::{
+    PaymentsRepository, SqlitePaymentsRepository,
+};
+use crate::shared::context::RequestContext;
+use crate::shared::error::{AppError, AppResult};
+use crate::shared::services::cross_domain::{Client, Organization, Quote, SettingsService};
+use crate::shared::utils::money::line_total_cents;
+
+const DAY_MS: i64 = 24 * 60 * 60 * 1000;
+
+pub struct InvoicesService {
+    repo: Arc<dyn InvoicesRepository>,
+    payments: Arc<dyn PaymentsRepository>,
+    settings_service: Arc<SettingsService>,
+}
+
+impl std::fmt::Debug for InvoicesService {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("InvoicesService")
+            .field("repo", &"Arc<dyn InvoicesRepository>")
+            .field("payments", &"Arc<dyn PaymentsRepository>")
+            .finish()
+    }
+}
+
+impl InvoicesService {
+    pub fn new(db: Arc<Database>, settings_service: Arc<SettingsService>) -> Self {
+        Self {
+            repo: Arc::new(SqliteInvoicesRepository::new(db.clone())),
+            payments: Arc::new(SqlitePaymentsRepository::new(db)),
+            settings_service,
+        }
+    }
+
+    pub async fn list(
+        &self,
+        _ctx: &RequestContext,
+        query: InvoiceQuery,
+    ) -> AppResult<InvoiceListResponse> {
+        let (data, total) = self.repo.list(&query).await?;
+        Ok(InvoiceListResponse { data, total })
+    }
+
+    /// Load an invoice with its lines and VAT breakdown.
+    pub async fn get(&self, _ctx: &RequestContext, id: &str) -> AppResult<Invoice> {
+        let mut invoice = self.repo.get(id).await?;
+        invoice.vat_breakdown =
+            compute_invoice_totals(&invoice.lines, invoice.discount_amount).vat_breakdown;
+        Ok(invoice)
+    }
+
+    /// Invoices raised from a quote that have not been cancelled.
+    pub async fn active_for_quote(
+        &self,
+        _ctx: &RequestContext,
+        quote_id: &str,
+    ) -> AppResult<Vec<Invoice>> {
+        self.repo.find_active_for_source(Some(quote_id), None).await
+    }
+
+    /// Create a draft invoice. The number is only assigned by [`Self::issue`].
+    pub async fn create(
+        &self,
+        ctx: &RequestContext,
+        request: CreateInvoiceRequest,
+    ) -> AppResult<Invoice> {
+        if request.client_id.trim().is_empty() {
+            return Err(AppError::Validation("client_id is required".to_string()));
+        }
+        if request.lines.is_empty() {
+            return Err(AppError::Validation(
+                "An invoice needs at least one line".to_string(),
+            ));
+        }
+        for source in [&request.quote_id, &request.intervention_id] {
+            if source.as_deref().is_some_and(|id| id.trim().is_empty()) {
+                return Err(AppError::Validation(
+                    "Source references must not be empty".to_string(),
+                ));
+            }
+        }
+        if request.quote_id.is_some() || request.intervention_id.is_some() {
+            let existing = self
+                .repo
+                .find_active_for_source(
+                    request.quote_id.as_deref(),
+                    request.intervention_id.as_deref(),
+                )
+                .await?;
+            if let Some(existing) = existing.first() {
+                return Err(AppError::Validation(format!(
+                    "This {} is already invoiced ({})",
+                    if request.quote_id.is_some() {
+                        "quote"
+                    } else {
+                        "intervention"
+                    },
+                    existing
+                        .invoice_number
+                        .clone()
+                        .unwrap_or_else(|| "draft".to_string())
+                )));
+            }
+        }
+
+        let settings = self.billing_settings(ctx)?;
+        let now = Utc::now().timestamp_millis();
+        let id = Uuid::new_v4().to_string();
+        let lines = build_lines(&id, &request.lines, settings.default_tax_rate, now)?;
+        let mut invoice = Invoice {
+            id,
+            invoice_number: None,
+            kind: InvoiceKind::Invoice,
+            status: InvoiceStatus::Draft,
+            client_id: request.client_id.trim().to_string(),
+            client_name: None,
+            client_address: None,
+            client_tax_id: None,
+            client_street: None,
+            client_postcode: None,
+            client_city: None,
+            client_country: None,
+            task_id: request.task_id,
+            quote_id: request.quote_id,
+            intervention_id: request.intervention_id,
+            credited_invoice_id: None,
+            issue_date: None,
+            due_date: request.due_date,
+            currency: "EUR".to_string(),
+            notes: request.notes,
+            terms: request.terms,
+            subtotal: 0,
+            discount_amount: 0,
+            tax_total: 0,
+            total: 0,
+            amount_paid: 0,
+            created_by: Some(ctx.user_id().to_string()).filter(|id| !id.is_empty()),
+            issued_at: None,
+            created_at: now,
+            updated_at: now,
+            lines,
+            vat_breakdown: Vec::new(),
+        };
+        let discount = validate_discount(request.discount_amount)?;
+        invoice.apply_totals(compute_invoice_totals(&invoice.lines, discount));
+        self.repo.create(&invoice).await?;
+        info!(invoice_id = %invoice.id, total = invoice.total, "Draft invoice created");
+        Ok(invoice)
+    }
+
+    pub async fn update_draft(
+        &self,
+        ctx: &RequestContext,
+        id: &str,
+        request: UpdateInvoiceRequest,
+    ) -> AppResult<Invoice> {
+        let mut invoice = self.get(ctx, id).await?;
+        if !invoice.status.is_draft() {
+            return Err(AppError::Validation(
+                "Only draft invoices can be edited; issue a credit note instead".to_string(),
+            ));
+        }
+        let now = Utc::now().timestamp_millis();
+        if let Some(due_date) = request.due_date {
+            invoice.due_date = Some(due_date);
+        }
+        if let Some(notes) = request.notes {
+            invoice.notes = Some(notes);
+        }
+        if let Some(terms) = request.terms {
+            invoice.terms = Some(terms);
+        }
+        if let Some(lines) = request.lines {
+            if lines.is_empty() {
+                return Err(AppError::Validation(
+                    "An invoice needs at least one line".to_string(),
+                ));
+            }
+            let settings = self.billing_settings(ctx)?;
+            invoice.lines = build_lines(&invoice.id, &lines, settings.default_tax_rate, now)?;
+        }
+        let discount = match request.discount_amount {
+            Some(amount) => validate_discount(Some(amount))?,
+            None => invoice.discount_amount,
+        };
+        invoice.apply_totals(compute_invoice_totals(&invoice.lines, discount));
+        invoice.updated_at = now;
+        self.repo.update_draft(&invoice).await?;
+        Ok(invoice)
+    }
+
+    pub async fn delete_draft(&self, _ctx: &RequestContext, id: &str) -> AppResult<()> {
+        self.repo.delete_draft(id).await
+    }
+
+    /// Issue a draft: assign the next gapless number, set the issue date and
+    /// the due date (organization payment terms unless one was set), and
+    /// freeze the client's name and address.
+    ///
+    /// The invoice must pass the EN 16931 checks as it will be issued, so a
+    /// missing SIRET or client country is caught before a number is burnt.
+    pub async fn issue(&self, ctx: &RequestContext, id: &str) -> AppResult<Invoice> {
+        let now = Utc::now().timestamp_millis();
+        let (preview, numbering) = self.issue_preview(ctx, id, now).await?;
+        let violations = validate_en16931(&self.einvoice_document(ctx, &preview).await?);
+        if !violations.is_empty() {
+            return Err(AppError::Validation(format!(
+                "Invoice cannot be issued: {}",
+                violations
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join("; ")
+            )));
+        }
+
+        let due_date = preview.due_date.unwrap_or(now);
+        let number = self.repo.issue(id, &numbering, now, due_date).await?;
+        info!(invoice_id = %id, invoice_number = %number, "Invoice issued");
+        // Deposits already taken on the quote count towards the new invoice.
+        self.sync_payment_state(ctx, id).await
+    }
+
+    /// The draft as it would be issued at `now`, with the client's current
+    /// identity and a placeholder number, plus the numbering to issue with.
+    /// Nothing is written.
+    async fn issue_preview(
+        &self,
+        ctx: &RequestContext,
+        id: &str,
+        now: i64,
+    ) -> AppResult<(Invoice, NumberingRequest)> {
+        let invoice = self.get(ctx, id).await?;
+        if !invoice.status.can_transition_to(&InvoiceStatus::Issued) {
+            return Err(AppError::Validation(format!(
+                "Cannot issue an invoice in '{}' status",
+                invoice.status
+            )));
+        }
+        if invoice.lines.is_empty() || invoice.total <= 0 {
+            return Err(AppError::Validation(
+                "Cannot issue an invoice with no amount due".to_string(),
+            ));
+        }
+
+        let invoicing = self
+            .settings_service
+            .get_organization_settings(ctx)?
+            .invoicing;
+        let numbering = NumberingRequest {
+            series: InvoiceKind::Invoice.to_string(),
+            year: current_year(),
+            prefix: invoicing.invoice_prefix,
+            first_value: i64::from(invoicing.invoice_next_number),
+        };
+        let due_date = invoice
+            .due_date
+            .unwrap_or(now + i64::from(invoicing.payment_terms.max(0)) * DAY_MS);
+
+        let mut preview = invoice;
+        self.repo.preview_client_snapshot(&mut preview).await?;
+        preview.invoice_number = Some(format!("{}{}-?", numbering.prefix, numbering.year));
+        preview.issue_date = Some(now);
+        preview.due_date = Some(due_date);
+        Ok((preview, numbering))
+    }
+
+    /// Issue a credit note against an issued invoice.
+    ///
+    /// Without explicit lines the whole invoice is credited and cancelled.
+    /// Partial credit notes leave the invoice open and reduce what is due.
+    pub async fn create_credit_note(
+        &self,
+        ctx: &RequestContext,
+        request: CreateCreditNoteRequest,
+    ) -> AppResult<Invoice> {
+        let original = self.get(ctx, &request.invoice_id).await?;
+        if original.kind != InvoiceKind::Invoice || !original.status.is_open_or_settled() {
+            return Err(AppError::Validation(
+                "Only issued invoices can be credited".to_string(),
+            ));
+        }
+        let already_credited = self.repo.credited_total(&original.id).await?;
+        let remaining = original.total - already_credited;
+        if remaining <= 0 {
+            return Err(AppError::Validation(
+                "This invoice has already been fully credited".to_string(),
+            ));
+        }
+
+        let settings = self.billing_settings(ctx)?;
+        let now = Utc::now().timestamp_millis();
+        let id = Uuid::new_v4().to_string();
+        let (lines, discount) = match &request.lines {
+            Some(inputs) if !inputs.is_empty() => {
+                (build_lines(&id, inputs, settings.default_tax_rate, now)?, 0)
+            }
+            Some(_) => {
+                return Err(AppError::Validation(
+                    "A credit note needs at least one line".to_string(),
+                ))
+            }
+            None if already_credited > 0 => {
+                return Err(AppError::Validation(
+                    "This invoice is partially credited; list the lines to credit".to_string(),
+                ))
+            }
+            None => (
+                original
+                    .lines
+                    .iter()
+                    .map(|line| InvoiceLine {
+                        id: Uuid::new_v4().to_string(),
+                        invoice_id: id.clone(),
+                        created_at: now,
+                        ..line.clone()
+                    })
+                    .collect(),
+                original.discount_amount,
+            ),
+        };
+
+        let mut credit_note = Invoice {
+            id,
+            invoice_number: None,
+            kind: InvoiceKind::CreditNote,
+            status: InvoiceStatus::Issued,
+            client_id: original.client_id.clone(),
+            client_name: None,
+            client_address: None,
+            client_tax_id: None,
+            client_street: None,
+            client_postcode: None,
+            client_city: None,
+            client_country: None,
+            task_id: original.task_id.clone(),
+            quote_id: original.quote_id.clone(),
+            intervention_id: original.intervention_id.clone(),
+            credited_invoice_id: Some(original.id.clone()),
+            issue_date: Some(now),
+            due_date: None,
+            currency: original.currency.clone(),
+            notes: Some(match request.reason.as_deref().map(str::trim) {
+                Some(reason) if !reason.is_empty() => format!(
+                    "Avoir sur facture {} — {}",
+                    original.invoice_number.as_deref().unwrap_or_default(),
+                    reason
+                ),
+                _ => format!(
+                    "Avoir sur facture {}",
+                    original.invoice_number.as_deref().unwrap_or_default()
+                ),
+            }),
+            terms: None,
+            subtotal: 0,
+            discount_amount: 0,
+            tax_total: 0,
+            total: 0,
+            amount_paid: 0,
+            created_by: Some(ctx.user_id().to_string()).filter(|id| !id.is_empty()),
+            issued_at: Some(now),
+            created_at: now,
+            updated_at: now,
+            lines,
+            vat_breakdown: Vec::new(),
+        };
+        credit_note.apply_totals(compute_invoice_totals(&credit_note.lines, discount));
+        if credit_note.total <= 0 {
+            return Err(AppError::Validation(
+                "A credit note must have a positive amount".to_string(),
+            ));
+        }
+        if credit_note.total > remaining {
+            return Err(AppError::Validation(format!(
+                "Credit note total exceeds the amount left on the invoice ({} cents)",
+                remaining
+            )));
+        }
+
+        let cancel_original = credit_note.total == remaining;
+        let numbering = NumberingRequest {
+            series: InvoiceKind::CreditNote.to_string(),
+            year: current_year(),
+            prefix: settings.credit_note_prefix,
+            first_value: 1,
+        };
+        let number = self
+            .repo
+            .create_credit_note(&credit_note, &numbering, cancel_original)
+            .await?;
+        info!(
+            invoice_id = %original.id,
+            credit_note_id = %credit_note.id,
+            credit_note_number = %number,
+            cancel_original,
+            "Credit note issued"
+        );
+        if !cancel_original {
+            // A partial credit lowers what is due and may settle the invoice.
+            self.sync_payment_state(ctx, &original.id).await?;
+        }
+        self.get(ctx, &credit_note.id).await
+    }
+
+    /// Recompute `amount_paid` from the payments ledger and derive
+    /// `issued` / `partially_paid` / `paid` from it, net of credit notes.
+    /// Drafts and cancelled invoices are returned unchanged.
+    pub async fn sync_payment_state(&self, ctx: &RequestContext, id: &str) -> AppResult<Invoice> {
+        let invoice = self.get(ctx, id).await?;
+        if invoice.kind != InvoiceKind::Invoice || !invoice.status.is_open_or_settled() {
+            return Ok(invoice);
+        }
+        let balance = self.balance_of(&invoice).await?;
+        let status = InvoiceStatus::for_amount_paid(balance.paid, balance.total - balance.credited);
+        if status == invoice.status && balance.paid == invoice.amount_paid {
+            return Ok(invoice);
+        }
+        self.repo
+            .update_payment_state(id, balance.paid, status)
+            .await?;
+        info!(
+            invoice_id = %id,
+            amount_paid = balance.paid,
+            %status,
+            "Invoice payment state updated"
+        );
+        self.get(ctx, id).await
+    }
+
+    /// What is invoiced, credited, paid and still owed on an invoice.
+    pub async fn balance(&self, ctx: &RequestContext, id: &str) -> AppResult<InvoiceBalance> {
+        let invoice = self.get(ctx, id).await?;
+        self.balance_of(&invoice).await
+    }
+
+    async fn balance_of(&self, invoice: &Invoice) -> AppResult<InvoiceBalance> {
+        let credited = self.repo.credited_total(&invoice.id).await?;
+        let paid = self
+            .payments
+            .paid_total_for_invoice(&invoice.id, invoice.quote_id.as_deref())
+            .await?;
+        Ok(InvoiceBalance {
+            invoice_id: invoice.id.clone(),
+            total: invoice.total,
+            credited,
+            paid,
+            outstanding: outstanding_cents(invoice.total, credited, paid),
+        })
+    }
+
+    /// Write the invoice PDF to `output_dir/{number}.pdf`.
+    pub async fn export_pdf(
+        &self,
+        ctx: &RequestContext,
+        id: &str,
+        output_dir: PathBuf,
+    ) -> AppResult<InvoiceExportResponse> {
+        let invoice = self.get(ctx, id).await?;
+        let organization = self.organization(ctx)?;
+        let settings = billing_settings_from(organization.as_ref());
+        let seller = organization
+            .as_ref()
+            .map(InvoiceSeller::from_organization)
+            .unwrap_or_default();
+
+        tokio::fs::create_dir_all(&output_dir).await.map_err(|e| {
+            error!("Failed to create invoices directory: {}", e);
+            AppError::Io("Failed to create export directory".to_string())
+        })?;
+        let file_name = format!(
+            "{}.pdf",
+            invoice
+                .invoice_number
+                .clone()
+                .unwrap_or_else(|| format!("brouillon-{}", invoice.id))
+        );
+        let file_path = output_dir.join(file_name);
+
+        let pdf = tokio::task::spawn_blocking(move || {
+            render_invoice_pdf(&invoice, &seller, settings.legal_mentions.as_deref())
+        })
+        .await
+        .map_err(|e| {
+            error!("Failed to join PDF generation task: {}", e);
+            AppError::Internal("PDF generation failed".to_string())
+        })?;
+        tokio::fs::write(&file_path, pdf).await.map_err(|e| {
+            error!("Failed to write invoice PDF: {}", e);
+            AppError::Io("Failed to write invoice PDF".to_string())
+        })?;
+
+        info!(invoice_id = %id, path = %file_path.display(), "Invoice PDF exported");
+        Ok(InvoiceExportResponse {
+            file_path: file_path.to_string_lossy().to_string(),
+        })
+    }
+
+    /// EN 16931 rules the invoice breaks. Drafts are checked as they would
+    /// be issued now, so the list is what [`Self::issue`] would reject.
+    pub async fn einvoice_check(
+        &self,
+        ctx: &RequestContext,
+        id: &str,
+    ) -> AppResult<Vec<EInvoiceViolation>> {
+        let invoice = self.get(ctx, id).await?;
+        let invoice = if invoice.status.is_draft() {
+            self.issue_preview(ctx, id, Utc::now().timestamp_millis())
+                .await?
+                .0
+        } else {
+            invoice
+        };
+        Ok(validate_en16931(
+            &self.einvoice_document(ctx, &invoice).await?,
+        ))
+    }
+
+    /// Write an issued invoice or credit note as Factur-X, CII or UBL to
+    /// `output_dir/{number}{suffix}`.
+    pub async fn export_einvoice(
+        &self,
+        ctx: &RequestContext,
+        id: &str,
+        format: EInvoiceFormat,
+        output_dir: PathBuf,
+    ) -> AppResult<InvoiceExportResponse> {
+        let invoice = self.get(ctx, id).await?;
+        if invoice.status.is_draft() {
+            return Err(AppError::Validation(
+                "Only issued invoices can be exported as e-invoices".to_string(),
+            ));
+        }
+        let document = self.einvoice_document(ctx, &invoice).await?;
+        let title = format!(
+            "{} {}",
+            match invoice.kind {
+                InvoiceKind::Invoice => "Facture",
+                InvoiceKind::CreditNote => "Avoir",
+            },
+            document.number
+        );
+        let file_name = format!("{}{}", document.number, format.file_suffix());
+        let path = self
+            .write_einvoice(
+                ctx,
+                invoice,
+                document,
+                format,
+                None,
+                title,
+                output_dir.join(file_name),
+            )
+            .await?;
+        info!(invoice_id = %id, ?format, path = %path, "E-invoice exported");
+        Ok(InvoiceExportResponse { file_path: path })
+    }
+
+    /// Write a quote as a pro-forma invoice (type code 325) in `format`.
+    ///
+    /// Nothing is stored: the pro-forma is built from the quote items and
+    /// the current client record, dated today.
+    pub async fn export_quote_einvoice(
+        &self,
+        ctx: &RequestContext,
+        quote: &Quote,
+        client: &Client,
+        format: EInvoiceFormat,
+        output_dir: PathBuf,
+    ) -> AppResult<InvoiceExportResponse> {
+        if quote.items.is_empty() {
+            return Err(AppError::Validation(format!(
+                "Quote {} has no items to export",
+                quote.quote_number
+            )));
+        }
+        let invoice = proforma_invoice(quote, client, Utc::now().timestamp_millis())?;
+        let organization = self.organization(ctx)?;
+        let document = document_from_invoice(
+            &invoice,
+            EInvoiceSource::Proforma,
+            seller_party(organization.as_ref()),
+            &billing_settings_from(organization.as_ref()),
+            None,
+        );
+        let title = format!("FACTURE PRO FORMA N° {}", quote.quote_number);
+        let file_name = format!("{}-proforma{}", quote.quote_number, format.file_suffix());
+        let path = self
+            .write_einvoice(
+                ctx,
+                invoice,
+                document,
+                format,
+                Some(title.clone()),
+                title,
+                output_dir.join(file_name),
+            )
+            .await?;
+        info!(quote_id = %quote.id, ?format, path = %path, "Pro-forma e-invoice exported");
+        Ok(InvoiceExportResponse { file_path: path })
+    }
+
+    async fn einvoice_document(
+        &self,
+        ctx: &RequestContext,
+        invoice: &Invoice,
+    ) -> AppResult<EInvoiceDocument> {
+        let organization = self.organization(ctx)?;
+        let preceding_invoice = match &invoice.credited_invoice_id {
+            Some(credited_id) => self.repo.get(credited_id).await?.invoice_number,
+            None => None,
+        };
+        Ok(document_from_invoice(
+            invoice,
+            EInvoiceSource::Ledger,
+            seller_party(organization.as_ref()),
+            &billing_settings_from(organization.as_ref()),
+            preceding_invoice,
+        ))
+    }
+
+    /// Refuse non-compliant documents, render `format` and write it to
+    /// `file_path`. `heading` overrides the printed title on Factur-X pages.
+    #[allow(clippy::too_many_arguments)]
+    async fn write_einvoice(
+        &self,
+        ctx: &RequestContext,
+        invoice: Invoice,
+        document: EInvoiceDocument,
+        format: EInvoiceFormat,
+        heading: Option<String>,
+        title: String,
+        file_path: PathBuf,
+    ) -> AppResult<String> {
+        let violations = validate_en16931(&document);
+        if !violations.is_empty() {
+            return Err(AppError::Validation(format!(
+                "{} is not EN 16931 compliant: {}",
+                title,
+                violations
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join("; ")
+            )));
+        }
+        let organization = self.organization(ctx)?;
+        let settings = billing_settings_from(organization.as_ref());
+        let seller = organization
+            .as_ref()
+            .map(InvoiceSeller::from_organization)
+            .unwrap_or_default();
+
+        let bytes = tokio::task::spawn_blocking(move || match format {
+            EInvoiceFormat::Cii => Ok(render_cii(&document).into_bytes()),
+            EInvoiceFormat::Ubl => Ok(render_ubl(&document).into_bytes()),
+            EInvoiceFormat::FacturX => {
+                let xml = render_cii(&document);
+                render_facturx_pdf(FacturXInput {
+                    pages: page_streams(
+                        &invoice,
+                        &seller,
+                        settings.legal_mentions.as_deref(),
+                        heading.as_deref(),
+                    ),
+                    xml: &xml,
+                    title: &title,
+                    author: &document.seller.name,
+                    created_at: Utc::now(),
+                })
+            }
+        })
+        .await
+        .map_err(|e| {
+            error!("Failed to join e-invoice generation task: {}", e);
+            AppError::Internal("E-invoice generation failed".to_string())
+        })??;
+
+        if let Some(parent) = file_path.parent() {
+            tokio::fs::create_dir_all(parent).await.map_err(|e| {
+                error!("Failed to create invoices directory: {}", e);
+                AppError::Io("Failed to create export directory".to_string())
+            })?;
+        }
+        tokio::fs::write(&file_path, bytes).await.map_err(|e| {
+            error!("Failed to write e-invoice: {}", e);
+            AppError::Io("Failed to write e-invoice".to_string())
+        })?;
+        Ok(file_path.to_string_lossy().to_string())
+    }
+
+    /// Invoice defaults from `organizations.invoice_settings`, or built-in
+    /// defaults before onboarding.
+    pub fn billing_settings(&self, ctx: &RequestContext) -> AppResult<InvoiceBillingSettings> {
+        Ok(billing_settings_from(self.organization(ctx)?.as_ref()))
+    }
+
+    fn organization(&self, ctx: &RequestContext) -> AppResult<Option<Organization>> {
+        match self.settings_service.get_organization(ctx) {
+            Ok(organization) => Ok(Some(organization)),
+            Err(AppError::NotFound(_)) => Ok(None),
+            Err(error) => Err(error),
+        }
+    }
+}
+
+fn billing_settings_from(organization: Option<&Organization>) -> InvoiceBillingSettings {
+    let Some(raw) = organization.and_then(|org| org.invoice_settings.as_deref()) else {
+        return InvoiceBillingSettings::default();
+    };
+    serde_json::from_str(raw).unwrap_or_else(|error| {
+        warn!(%error, "Invalid organizations.invoice_settings JSON; using defaults");
+        InvoiceBillingSettings::default()
+    })
+}
+
+/// Transient invoice mirroring `quote` for a pro-forma export, with the
+/// buyer taken from the current client record.
+fn proforma_invoice(quote: &Quote, client: &Client, now: i64) -> AppResult<Invoice> {
+    let non_blank = |value: &Option<String>| {
+        value
+            .as_deref()
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(str::to_string)
+    };
+    let city_line = [
+        non_blank(&client.address_zip),
+        non_blank(&client.address_city),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>()
+    .join(" ");
+    let address = [
+        non_blank(&client.address_street),
+        Some(city_line).filter(|line| !line.is_empty()),
+        non_blank(&client.address_country),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>()
+    .join("\n");
+
+    let lines = build_lines(&quote.id, &lines_from_quote(quote), 0.0, now)?;
+    let mut invoice = Invoice {
+        id: quote.id.clone(),
+        invoice_number: Some(quote.quote_number.clone()),
+        kind: InvoiceKind::Invoice,
+        status: InvoiceStatus::Draft,
+        client_id: quote.client_id.clone(),
+        client_name: non_blank(&client.company_name).or_else(|| Some(client.name.clone())),
+        client_address: Some(address).filter(|address| !address.is_empty()),
+        client_tax_id: non_blank(&client.tax_id),
+        client_street: non_blank(&client.address_street),
+        client_postcode: non_blank(&client.address_zip),
+        client_city: non_blank(&client.address_city),
+        client_country: non_blank(&client.address_country),
+        task_id: quote.task_id.clone(),
+        quote_id: Some(quote.id.clone()),
+        intervention_id: None,
+        credited_invoice_id: None,
+        issue_date: Some(now),
+        due_date: None,
+        currency: "EUR".to_string(),
+        notes: non_blank(&quote.description),
+        terms: quote.terms.clone(),
+        subtotal: 0,
+        discount_amount: 0,
+        tax_total: 0,
+        total: 0,
+        amount_paid: 0,
+        created_by: None,
+        issued_at: None,
+        created_at: now,
+        updated_at: now,
+        lines,
+        vat_breakdown: Vec::new(),
+    };
+    let discount = validate_discount(quote.discount_amount.filter(|amount| *amount > 0))?;
+    invoice.apply_totals(compute_invoice_totals(&invoice.lines, discount));
+    Ok(invoice)
+}
+
+fn current_year() -> i32 {
+    Utc::now().with_timezone(&chrono_tz::Europe::Paris).year()
+}
+
+fn validate_discount(discount_amount: Option<i64>) -> AppResult<i64> {
+    match discount_amount {
+        Some(amount) if amount < 0 => Err(AppError::Validation(
+            "discount_amount must not be negative".to_string(),
+        )),
+        Some(amount) => Ok(amount),
+        None => Ok(0),
+    }
+}
+
+fn build_lines(
+    invoice_id: &str,
+    inputs: &[InvoiceLineInput],
+    default_tax_rate: f64,
+    now: i64,
+) -> AppResult<Vec<InvoiceLine>> {
+    inputs
+        .iter()
+        .enumerate()
+        .map(|(position, input)| {
+            let label = input.label.trim();
+            if label.is_empty() {
+                return Err(AppError::Validation(format!(
+                    "Line {}: label is required",
+                    position + 1
+                )));
+            }
+            if !input.qty.is_finite() || input.qty <= 0.0 {
+                return Err(AppError::Validation(format!(
+                    "Line {}: quantity must be greater than zero",
+                    position + 1
+                )));
+            }
+            if input.unit_price < 0 && input.kind != InvoiceLineKind::Discount {
+                return Err(AppError::Validation(format!(
+                    "Line {}: only discount lines may have a negative price",
+                    position + 1
+                )));
+            }
+            let tax_rate = input.tax_rate.unwrap_or(default_tax_rate);
+            if !(0.0..=100.0).contains(&tax_rate) {
+                return Err(AppError::Validation(format!(
+                    "Line {}: tax rate must be between 0 and 100",
+                    position + 1
+                )));
+            }
+            Ok(InvoiceLine {
+                id: Uuid::new_v4().to_string(),
+                invoice_id: invoice_id.to_string(),
+                kind: input.kind,
+                label: label.to_string(),
+                description: input.description.clone(),
+                qty: input.qty,
+                unit_price: input.unit_price,
+                tax_rate,
+                line_total: line_total_cents(input.qty, input.unit_price),
+                material_id: input.material_id.clone(),
+                position: position as i32,
+                created_at: now,
+            })
+        })
+        .collect()
+}
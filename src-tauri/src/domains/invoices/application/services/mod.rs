@@ -0,0 +1,8 @@
+pub(crate) mod einvoice;
+pub(crate) mod einvoice_xml;
+pub(crate) mod facturx;
+pub(crate) mod invoice_pdf;
+pub(crate) mod invoice_sources;
+pub(crate) mod invoices_service;
+pub(crate) mod payments_service;
+pub(crate) mod pdf_font;
@@ -0,0 +1,288 @@
+//! Payments ledger: deposits on accepted quotes, settlements on issued
+//! invoices, the deposit gate before a quote becomes a task, and the
+//! aged-receivables report.
+//!
+//! Every write re-derives the affected invoice's `amount_paid` and status
+//! through [`InvoicesService::sync_payment_state`], so the ledger stays the
+//! single source of truth.
+
+use async_trait::async_trait;
+use chrono::Utc;
+use std::sync::Arc;
+use tracing::info;
+use uuid::Uuid;
+
+use crate::db::Database;
+use crate::domains::invoices::application::services::invoices_service::InvoicesService;
+use crate::domains::invoices::domain::models::invoices::InvoiceKind;
+use crate::domains::invoices::domain::models::payments::{
+    build_aged_receivables, AgedReceivablesQuery, AgedReceivablesReport, Payment, PaymentKind,
+    PaymentListResponse, PaymentQuery, QuoteBalance, RecordPaymentRequest,
+};
+use crate::domains::invoices::infrastructure::payments_repository::{
+    PaymentsRepository, SqlitePaymentsRepository,
+};
+use crate::shared::context::RequestContext;
+use crate::shared::contracts::deposit_gate::DepositGate;
+use crate::shared::error::{AppError, AppResult};
+use crate::shared::services::cross_domain::{Quote, QuoteStatus};
+use crate::shared::utils::money::format_eur;
+
+pub struct PaymentsService {
+    repo: Arc<dyn PaymentsRepository>,
+    invoices: Arc<InvoicesService>,
+}
+
+impl std::fmt::Debug for PaymentsService {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PaymentsService")
+            .field("repo", &"Arc<dyn PaymentsRepository>")
+            .finish()
+    }
+}
+
+impl PaymentsService {
+    pub fn new(db: Arc<Database>, invoices: Arc<InvoicesService>) -> Self {
+        Self {
+            repo: Arc::new(SqlitePaymentsRepository::new(db)),
+            invoices,
+        }
+    }
+
+    pub async fn list(
+        &self,
+        _ctx: &RequestContext,
+        query: PaymentQuery,
+    ) -> AppResult<PaymentListResponse> {
+        let (data, total) = self.repo.list(&query).await?;
+        Ok(PaymentListResponse { data, total })
+    }
+
+    /// Record a deposit (acompte) on an accepted quote.
+    ///
+    /// Once the quote has been invoiced, money must be recorded against the
+    /// invoice instead; deposits already taken carry over to it.
+    pub async fn record_deposit(
+        &self,
+        ctx: &RequestContext,
+        quote: &Quote,
+        request: RecordPaymentRequest,
+    ) -> AppResult<Payment> {
+        if !matches!(quote.status, QuoteStatus::Accepted | QuoteStatus::Converted) {
+            return Err(AppError::Validation(format!(
+                "Deposits can only be taken on accepted quotes (quote {} is '{}')",
+                quote.quote_number, quote.status
+            )));
+        }
+        validate_request(&request)?;
+        let invoiced = self
+            .invoices
+            .active_for_quote(ctx, &quote.id)
+            .await?
+            .iter()
+            .any(|invoice| invoice.status.is_open_or_settled());
+        if invoiced {
+            return Err(AppError::Validation(format!(
+                "Quote {} is already invoiced; record the payment on the invoice",
+                quote.quote_number
+            )));
+        }
+        let deposits = self.repo.deposits_total(&quote.id).await?;
+        if deposits + request.amount > quote.total {
+            return Err(AppError::Validation(format!(
+                "Deposits would exceed the quote total ({} left)",
+                format_eur((quote.total - deposits).max(0))
+            )));
+        }
+
+        let payment = new_payment(
+            ctx,
+            &quote.client_id,
+            PaymentKind::Deposit,
+            Some(quote.id.clone()),
+            None,
+            request,
+        );
+        self.repo.create(&payment).await?;
+        info!(
+            payment_id = %payment.id,
+            quote_id = %quote.id,
+            amount = payment.amount,
+            method = %payment.method,
+            "Deposit recorded"
+        );
+        Ok(payment)
+    }
+
+    /// Record a payment against an issued invoice. Overpayment is refused.
+    pub async fn record_settlement(
+        &self,
+        ctx: &RequestContext,
+        invoice_id: &str,
+        request: RecordPaymentRequest,
+    ) -> AppResult<Payment> {
+        validate_request(&request)?;
+        let invoice = self.invoices.get(ctx, invoice_id).await?;
+        if invoice.kind != InvoiceKind::Invoice || !invoice.status.is_open_or_settled() {
+            return Err(AppError::Validation(format!(
+                "Cannot record a payment on an invoice in '{}' status",
+                invoice.status
+            )));
+        }
+        let balance = self.invoices.balance(ctx, invoice_id).await?;
+        if request.amount > balance.outstanding {
+            return Err(AppError::Validation(format!(
+                "Payment exceeds the amount outstanding ({})",
+                format_eur(balance.outstanding)
+            )));
+        }
+
+        let payment = new_payment(
+            ctx,
+            &invoice.client_id,
+            PaymentKind::Settlement,
+            None,
+            Some(invoice.id.clone()),
+            request,
+        );
+        self.repo.create(&payment).await?;
+        self.invoices.sync_payment_state(ctx, invoice_id).await?;
+        info!(
+            payment_id = %payment.id,
+            invoice_id = %invoice_id,
+            amount = payment.amount,
+            method = %payment.method,
+            "Settlement recorded"
+        );
+        Ok(payment)
+    }
+
+    /// Remove a payment recorded by mistake and re-derive the status of the
+    /// invoice it counted towards.
+    pub async fn delete(&self, ctx: &RequestContext, id: &str) -> AppResult<()> {
+        let payment = self.repo.get(id).await?;
+        self.repo.delete(id).await?;
+        let affected = match (&payment.invoice_id, &payment.quote_id) {
+            (Some(invoice_id), _) => vec![invoice_id.clone()],
+            (None, Some(quote_id)) => self
+                .invoices
+                .active_for_quote(ctx, quote_id)
+                .await?
+                .into_iter()
+                .map(|invoice| invoice.id)
+                .collect(),
+            (None, None) => Vec::new(),
+        };
+        for invoice_id in affected {
+            self.invoices.sync_payment_state(ctx, &invoice_id).await?;
+        }
+        info!(payment_id = %id, amount = payment.amount, "Payment deleted");
+        Ok(())
+    }
+
+    pub async fn quote_balance(
+        &self,
+        ctx: &RequestContext,
+        quote: &Quote,
+    ) -> AppResult<QuoteBalance> {
+        self.balance_for(ctx, &quote.id, quote.total).await
+    }
+
+    async fn balance_for(
+        &self,
+        ctx: &RequestContext,
+        quote_id: &str,
+        total: i64,
+    ) -> AppResult<QuoteBalance> {
+        let settings = self.invoices.billing_settings(ctx)?;
+        let deposits = self.repo.deposits_total(quote_id).await?;
+        Ok(QuoteBalance::new(
+            quote_id,
+            total,
+            settings.deposit_percent,
+            deposits,
+        ))
+    }
+
+    /// Refuse to start work on a quote whose required deposit is not fully
+    /// paid. A no-op when `deposit_percent` is not configured.
+    pub async fn ensure_deposit_paid(&self, ctx: &RequestContext, quote: &Quote) -> AppResult<()> {
+        self.check_deposit_paid(ctx, &quote.id, &quote.quote_number, quote.total)
+            .await
+    }
+
+    /// Outstanding invoices per client, bucketed by days overdue.
+    pub async fn aged_receivables(
+        &self,
+        _ctx: &RequestContext,
+        query: AgedReceivablesQuery,
+    ) -> AppResult<AgedReceivablesReport> {
+        let receivables = self
+            .repo
+            .open_receivables(query.client_id.as_deref())
+            .await?;
+        Ok(build_aged_receivables(
+            receivables,
+            query.as_of.unwrap_or_else(|| Utc::now().timestamp_millis()),
+        ))
+    }
+}
+
+#[async_trait]
+impl DepositGate for PaymentsService {
+    async fn check_deposit_paid(
+        &self,
+        ctx: &RequestContext,
+        quote_id: &str,
+        quote_number: &str,
+        total: i64,
+    ) -> AppResult<()> {
+        let balance = self.balance_for(ctx, quote_id, total).await?;
+        if balance.deposit_outstanding > 0 {
+            return Err(AppError::Validation(format!(
+                "A deposit of {} is required before quote {} can become a task ({} received)",
+                format_eur(balance.deposit_required),
+                quote_number,
+                format_eur(balance.deposits_paid)
+            )));
+        }
+        Ok(())
+    }
+}
+
+fn validate_request(request: &RecordPaymentRequest) -> AppResult<()> {
+    if request.amount <= 0 {
+        return Err(AppError::Validation(
+            "Payment amount must be greater than zero".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn new_payment(
+    ctx: &RequestContext,
+    client_id: &str,
+    kind: PaymentKind,
+    quote_id: Option<String>,
+    invoice_id: Option<String>,
+    request: RecordPaymentRequest,
+) -> Payment {
+    let now = Utc::now().timestamp_millis();
+    Payment {
+        id: Uuid::new_v4().to_string(),
+        client_id: client_id.to_string(),
+        kind,
+        method: request.method,
+        amount: request.amount,
+        quote_id,
+        invoice_id,
+        paid_at: request.paid_at.unwrap_or(now),
+        reference: request
+            .reference
+            .map(|reference| reference.trim().to_string())
+            .filter(|reference| !reference.is_empty()),
+        notes: request.notes,
+        recorded_by: Some(ctx.user_id().to_string()).filter(|id| !id.is_empty()),
+        created_at: now,
+    }
+}
@@ -0,0 +1,321 @@
+//! TrueType embedding for PDF/A output.
+//!
+//! PDF/A forbids relying on the standard 14 fonts, so Factur-X files embed
+//! Liberation Sans (bundled in `src-tauri/fonts`, metric-compatible with the
+//! Helvetica used by the plain PDFs). The font is cut down to the glyphs
+//! reachable from WinAnsiEncoding, keeping glyph ids unchanged so `cmap` and
+//! `hmtx` stay valid, which brings ~1 MB per face down to a few dozen KB.
+
+use std::collections::BTreeSet;
+
+/// Tables a PDF viewer needs from an embedded TrueType program.
+const KEPT_TABLES: [&[u8; 4]; 13] = [
+    b"OS/2", b"cmap", b"cvt ", b"fpgm", b"glyf", b"head", b"hhea", b"hmtx", b"loca", b"maxp",
+    b"name", b"post", b"prep",
+];
+
+pub(crate) const FIRST_CHAR: u8 = 32;
+pub(crate) const LAST_CHAR: u8 = 255;
+
+/// A simple TrueType font ready for a `/FontFile2` stream, with the metrics
+/// its font dictionary and descriptor need (glyph space units, 1/1000 em).
+#[derive(Debug, Clone)]
+pub(crate) struct EmbeddedFont {
+    pub program: Vec<u8>,
+    /// Advance widths for codes `FIRST_CHAR..=LAST_CHAR`.
+    pub widths: Vec<i64>,
+    pub bbox: [i64; 4],
+    pub ascent: i64,
+    pub descent: i64,
+    pub cap_height: i64,
+}
+
+/// Unicode character for a WinAnsiEncoding code; `None` for the five codes
+/// the encoding leaves undefined.
+pub(crate) fn win_ansi_char(code: u8) -> Option<char> {
+    let c = match code {
+        0x20..=0x7E | 0xA0..=0xFF => return Some(char::from(code)),
+        0x80 => '\u{20AC}',
+        0x82 => '\u{201A}',
+        0x83 => '\u{0192}',
+        0x84 => '\u{201E}',
+        0x85 => '\u{2026}',
+        0x86 => '\u{2020}',
+        0x87 => '\u{2021}',
+        0x88 => '\u{02C6}',
+        0x89 => '\u{2030}',
+        0x8A => '\u{0160}',
+        0x8B => '\u{2039}',
+        0x8C => '\u{0152}',
+        0x8E => '\u{017D}',
+        0x91 => '\u{2018}',
+        0x92 => '\u{2019}',
+        0x93 => '\u{201C}',
+        0x94 => '\u{201D}',
+        0x95 => '\u{2022}',
+        0x96 => '\u{2013}',
+        0x97 => '\u{2014}',
+        0x98 => '\u{02DC}',
+        0x99 => '\u{2122}',
+        0x9A => '\u{0161}',
+        0x9B => '\u{203A}',
+        0x9C => '\u{0153}',
+        0x9E => '\u{017E}',
+        0x9F => '\u{0178}',
+        _ => return None,
+    };
+    Some(c)
+}
+
+fn u16_at(data: &[u8], offset: usize) -> Option<u16> {
+    data.get(offset..offset + 2)
+        .map(|bytes| u16::from_be_bytes([bytes[0], bytes[1]]))
+}
+
+fn i16_at(data: &[u8], offset: usize) -> Option<i16> {
+    u16_at(data, offset).map(|value| value as i16)
+}
+
+fn u32_at(data: &[u8], offset: usize) -> Option<u32> {
+    data.get(offset..offset + 4)
+        .map(|bytes| u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+fn table<'a>(font: &'a [u8], tag: &[u8; 4]) -> Option<&'a [u8]> {
+    let count = usize::from(u16_at(font, 4)?);
+    (0..count).find_map(|index| {
+        let record = 12 + index * 16;
+        if font.get(record..record + 4)? != tag {
+            return None;
+        }
+        let offset = u32_at(font, record + 8)? as usize;
+        let length = u32_at(font, record + 12)? as usize;
+        font.get(offset..offset + length)
+    })
+}
+
+/// Glyph id for `c` from the Windows Unicode BMP (3, 1) format 4 subtable.
+fn glyph_id(cmap: &[u8], c: char) -> Option<u16> {
+    let code = u16::try_from(u32::from(c)).ok()?;
+    let count = usize::from(u16_at(cmap, 2)?);
+    let subtable = (0..count).find_map(|index| {
+        let record = 4 + index * 8;
+        let platform = u16_at(cmap, record)?;
+        let encoding = u16_at(cmap, record + 2)?;
+        let offset = u32_at(cmap, record + 4)? as usize;
+        (platform == 3 && encoding == 1 && u16_at(cmap, offset)? == 4).then_some(offset)
+    })?;
+    let seg_x2 = usize::from(u16_at(cmap, subtable + 6)?);
+    let ends = subtable + 14;
+    let starts = ends + seg_x2 + 2;
+    let deltas = starts + seg_x2;
+    let range_offsets = deltas + seg_x2;
+    for segment in (0..seg_x2).step_by(2) {
+        if code > u16_at(cmap, ends + segment)? {
+            continue;
+        }
+        let start = u16_at(cmap, starts + segment)?;
+        if code < start {
+            return Some(0);
+        }
+        let delta = u16_at(cmap, deltas + segment)?;
+        let range_offset = usize::from(u16_at(cmap, range_offsets + segment)?);
+        if range_offset == 0 {
+            return Some(code.wrapping_add(delta));
+        }
+        let address = range_offsets + segment + range_offset + 2 * usize::from(code - start);
+        let glyph = u16_at(cmap, address)?;
+        return Some(if glyph == 0 {
+            0
+        } else {
+            glyph.wrapping_add(delta)
+        });
+    }
+    Some(0)
+}
+
+fn advance_width(hhea: &[u8], hmtx: &[u8], glyph: u16) -> Option<u16> {
+    let metrics = usize::from(u16_at(hhea, 34)?).max(1);
+    let index = usize::from(glyph).min(metrics - 1);
+    u16_at(hmtx, index * 4)
+}
+
+fn glyph_offsets(head: &[u8], loca: &[u8], glyph_count: usize) -> Option<Vec<usize>> {
+    let long = i16_at(head, 50)? == 1;
+    (0..=glyph_count)
+        .map(|index| {
+            if long {
+                u32_at(loca, index * 4).map(|offset| offset as usize)
+            } else {
+                u16_at(loca, index * 2).map(|offset| usize::from(offset) * 2)
+            }
+        })
+        .collect()
+}
+
+/// Components referenced by a composite glyph.
+fn components(glyph: &[u8]) -> Vec<u16> {
+    let mut found = Vec::new();
+    if i16_at(glyph, 0).map_or(true, |contours| contours >= 0) {
+        return found;
+    }
+    let mut offset = 10;
+    while let (Some(flags), Some(component)) = (u16_at(glyph, offset), u16_at(glyph, offset + 2))
+    {
+        found.push(component);
+        offset += 4 + if flags & 0x0001 != 0 { 4 } else { 2 };
+        offset += if flags & 0x0008 != 0 {
+            2
+        } else if flags & 0x0040 != 0 {
+            4
+        } else if flags & 0x0080 != 0 {
+            8
+        } else {
+            0
+        };
+        if flags & 0x0020 == 0 {
+            break;
+        }
+    }
+    found
+}
+
+fn checksum(data: &[u8]) -> u32 {
+    data.chunks(4).fold(0u32, |sum, chunk| {
+        let mut word = [0u8; 4];
+        word[..chunk.len()].copy_from_slice(chunk);
+        sum.wrapping_add(u32::from_be_bytes(word))
+    })
+}
+
+/// Rebuild the font with only `kept` glyphs outlined and the tables in
+/// [`KEPT_TABLES`]. `loca` is rewritten in long format.
+fn subset(font: &[u8], kept: &BTreeSet<u16>) -> Option<Vec<u8>> {
+    let head = table(font, b"head")?;
+    let glyph_count = usize::from(u16_at(table(font, b"maxp")?, 4)?);
+    let offsets = glyph_offsets(head, table(font, b"loca")?, glyph_count)?;
+    let glyf = table(font, b"glyf")?;
+
+    let mut new_glyf = Vec::new();
+    let mut new_loca = Vec::with_capacity((glyph_count + 1) * 4);
+    for glyph in 0..glyph_count {
+        new_loca.extend_from_slice(&(new_glyf.len() as u32).to_be_bytes());
+        if kept.contains(&(glyph as u16)) {
+            new_glyf.extend_from_slice(glyf.get(offsets[glyph]..offsets[glyph + 1])?);
+            while new_glyf.len() % 4 != 0 {
+                new_glyf.push(0);
+            }
+        }
+    }
+    new_loca.extend_from_slice(&(new_glyf.len() as u32).to_be_bytes());
+
+    let mut new_head = head.to_vec();
+    new_head.get_mut(8..12)?.copy_from_slice(&[0; 4]);
+    new_head.get_mut(50..52)?.copy_from_slice(&1i16.to_be_bytes());
+
+    let mut tables: Vec<(&[u8; 4], Vec<u8>)> = Vec::new();
+    for tag in KEPT_TABLES {
+        let data = match tag {
+            b"glyf" => new_glyf.clone(),
+            b"loca" => new_loca.clone(),
+            b"head" => new_head.clone(),
+            _ => match table(font, tag) {
+                Some(data) => data.to_vec(),
+                None => continue,
+            },
+        };
+        tables.push((tag, data));
+    }
+
+    let count = tables.len() as u16;
+    let selector = 15 - count.leading_zeros() as u16;
+    let search_range = (1u16 << selector) * 16;
+    let mut out = Vec::new();
+    out.extend_from_slice(&0x0001_0000u32.to_be_bytes());
+    out.extend_from_slice(&count.to_be_bytes());
+    out.extend_from_slice(&search_range.to_be_bytes());
+    out.extend_from_slice(&selector.to_be_bytes());
+    out.extend_from_slice(&(count * 16 - search_range).to_be_bytes());
+    let mut offset = 12 + tables.len() * 16;
+    let mut head_offset = 0;
+    for (tag, data) in &tables {
+        if *tag == b"head" {
+            head_offset = offset;
+        }
+        out.extend_from_slice(*tag);
+        out.extend_from_slice(&checksum(data).to_be_bytes());
+        out.extend_from_slice(&(offset as u32).to_be_bytes());
+        out.extend_from_slice(&(data.len() as u32).to_be_bytes());
+        offset += data.len().div_ceil(4) * 4;
+    }
+    for (_, data) in &tables {
+        out.extend_from_slice(data);
+        while out.len() % 4 != 0 {
+            out.push(0);
+        }
+    }
+    let adjustment = 0xB1B0_AFBAu32.wrapping_sub(checksum(&out));
+    out.get_mut(head_offset + 8..head_offset + 12)?
+        .copy_from_slice(&adjustment.to_be_bytes());
+    Some(out)
+}
+
+/// Parse `ttf` and prepare it for embedding as a WinAnsi simple font.
+/// Returns `None` when a required table is missing or malformed.
+pub(crate) fn embed_win_ansi(ttf: &[u8]) -> Option<EmbeddedFont> {
+    let head = table(ttf, b"head")?;
+    let hhea = table(ttf, b"hhea")?;
+    let hmtx = table(ttf, b"hmtx")?;
+    let cmap = table(ttf, b"cmap")?;
+    let units_per_em = i64::from(u16_at(head, 18)?).max(1);
+    let scale = |value: i64| (value * 1000 + units_per_em / 2).div_euclid(units_per_em);
+
+    let mut kept = BTreeSet::from([0u16]);
+    let mut widths = Vec::with_capacity(usize::from(LAST_CHAR - FIRST_CHAR) + 1);
+    for code in FIRST_CHAR..=LAST_CHAR {
+        let glyph = match win_ansi_char(code) {
+            Some(c) => glyph_id(cmap, c)?,
+            None => 0,
+        };
+        kept.insert(glyph);
+        widths.push(scale(i64::from(advance_width(hhea, hmtx, glyph)?)));
+    }
+
+    // Accented letters are usually composites of a base letter and a mark.
+    let glyph_count = usize::from(u16_at(table(ttf, b"maxp")?, 4)?);
+    let offsets = glyph_offsets(head, table(ttf, b"loca")?, glyph_count)?;
+    let glyf = table(ttf, b"glyf")?;
+    let mut pending: Vec<u16> = kept.iter().copied().collect();
+    while let Some(glyph) = pending.pop() {
+        let index = usize::from(glyph);
+        if index >= glyph_count {
+            continue;
+        }
+        let data = glyf.get(offsets[index]..offsets[index + 1])?;
+        for component in components(data) {
+            if kept.insert(component) {
+                pending.push(component);
+            }
+        }
+    }
+
+    let ascent = scale(i64::from(i16_at(hhea, 4)?));
+    let cap_height = table(ttf, b"OS/2")
+        .filter(|os2| u16_at(os2, 0).is_some_and(|version| version >= 2))
+        .and_then(|os2| i16_at(os2, 88))
+        .map(|value| scale(i64::from(value)))
+        .unwrap_or(ascent * 7 / 10);
+    Some(EmbeddedFont {
+        program: subset(ttf, &kept)?,
+        widths,
+        bbox: [
+            scale(i64::from(i16_at(head, 36)?)),
+            scale(i64::from(i16_at(head, 38)?)),
+            scale(i64::from(i16_at(head, 40)?)),
+            scale(i64::from(i16_at(head, 42)?)),
+        ],
+        ascent,
+        descent: scale(i64::from(i16_at(hhea, 6)?)),
+        cap_height,
+    })
+}
@@ -0,0 +1,486 @@
+//! Structured e-invoice model (EN 16931 semantic model) and the business
+//! rules checked before an invoice is issued.
+//!
+//! Invoices and pro-forma quotes are first mapped to [`EInvoiceDocument`];
+//! the CII, UBL and Factur-X writers only serialise it. Business-term
+//! numbers (`BT-xx`) and rule ids (`BR-xx`) follow the standard so a
+//! violation can be looked up in it. `FR-*` rules are local checks on French
+//! identifiers.
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::shared::utils::money::tax_cents;
+
+/// EN 16931 specification identifier (BT-24).
+pub const EN16931_SPECIFICATION_ID: &str = "urn:cen.eu:en16931:2017";
+
+/// UNTDID 1001 document type codes (BT-3) we produce.
+pub const TYPE_CODE_INVOICE: u16 = 380;
+pub const TYPE_CODE_CREDIT_NOTE: u16 = 381;
+/// Used for quotes: a pro-forma carries no payment obligation.
+pub const TYPE_CODE_PROFORMA: u16 = 325;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum EInvoiceFormat {
+    /// PDF/A-3 with the CII XML embedded as `factur-x.xml`.
+    FacturX,
+    /// UN/CEFACT Cross Industry Invoice XML.
+    Cii,
+    /// OASIS UBL 2.1 Invoice / CreditNote XML.
+    Ubl,
+}
+
+impl EInvoiceFormat {
+    pub fn file_suffix(&self) -> &'static str {
+        match self {
+            Self::FacturX => ".pdf",
+            Self::Cii => ".cii.xml",
+            Self::Ubl => ".ubl.xml",
+        }
+    }
+}
+
+/// VAT category (BT-151 / BT-118), UNTDID 5305.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VatCategory {
+    /// `S`: standard or reduced rate.
+    Standard,
+    /// `E`: exempt, e.g. franchise en base (art. 293 B du CGI).
+    Exempt,
+}
+
+impl VatCategory {
+    pub fn for_rate(rate: f64) -> Self {
+        if rate > 0.0 {
+            Self::Standard
+        } else {
+            Self::Exempt
+        }
+    }
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Standard => "S",
+            Self::Exempt => "E",
+        }
+    }
+}
+
+/// Seller (BG-4) or buyer (BG-7).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EInvoiceParty {
+    pub name: String,
+    /// SIRET, exported as legal registration id with scheme `0009`.
+    pub siret: Option<String>,
+    /// Intra-community VAT number (BT-31 / BT-48).
+    pub vat_id: Option<String>,
+    pub street: Option<String>,
+    pub postcode: Option<String>,
+    pub city: Option<String>,
+    /// ISO 3166-1 alpha-2; `None` when the stored country is not recognised.
+    pub country_code: Option<String>,
+    pub email: Option<String>,
+}
+
+/// Invoice line (BG-25). Amounts are integer cents.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EInvoiceLine {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    /// Negative for discount lines: the net price (BT-146) may not be.
+    pub quantity: f64,
+    /// UN/ECE Rec 20 unit code (BT-130).
+    pub unit_code: String,
+    pub net_price: i64,
+    pub net_amount: i64,
+    pub vat_category: VatCategory,
+    pub vat_rate: f64,
+}
+
+/// Document-level allowance (BG-20), one per VAT rate.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EInvoiceAllowance {
+    pub amount: i64,
+    pub reason: String,
+    pub vat_category: VatCategory,
+    pub vat_rate: f64,
+}
+
+/// VAT breakdown row (BG-23).
+#[derive(Debug, Clone, PartialEq)]
+pub struct EInvoiceVatBreakdown {
+    pub vat_category: VatCategory,
+    pub vat_rate: f64,
+    pub taxable_amount: i64,
+    pub tax_amount: i64,
+    pub exemption_reason: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct EInvoiceDocument {
+    pub type_code: u16,
+    pub number: String,
+    pub issue_date: Option<NaiveDate>,
+    pub due_date: Option<NaiveDate>,
+    pub currency: String,
+    pub note: Option<String>,
+    pub payment_terms: Option<String>,
+    /// Invoice a credit note corrects (BT-25).
+    pub preceding_invoice: Option<String>,
+    pub seller: EInvoiceParty,
+    pub buyer: EInvoiceParty,
+    pub lines: Vec<EInvoiceLine>,
+    pub allowances: Vec<EInvoiceAllowance>,
+    pub vat_breakdown: Vec<EInvoiceVatBreakdown>,
+    /// BT-106: sum of line net amounts.
+    pub line_total: i64,
+    /// BT-107.
+    pub allowance_total: i64,
+    /// BT-109: invoice total without VAT.
+    pub tax_basis_total: i64,
+    /// BT-110.
+    pub tax_total: i64,
+    /// BT-112: total with VAT.
+    pub grand_total: i64,
+    /// BT-113: amount already paid.
+    pub prepaid: i64,
+    /// BT-115.
+    pub due_payable: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+pub struct EInvoiceViolation {
+    pub rule: String,
+    pub message: String,
+}
+
+impl std::fmt::Display for EInvoiceViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{}] {}", self.rule, self.message)
+    }
+}
+
+/// Check a document against the EN 16931 rules that our data can break.
+/// Rules guaranteed by construction (date formats, code lists we never vary)
+/// are left to the XML tests.
+pub fn validate_en16931(document: &EInvoiceDocument) -> Vec<EInvoiceViolation> {
+    let mut violations = Vec::new();
+    let mut fail = |rule: &str, message: String| {
+        violations.push(EInvoiceViolation {
+            rule: rule.to_string(),
+            message,
+        })
+    };
+
+    if document.number.trim().is_empty() {
+        fail("BR-02", "Invoice number is missing".to_string());
+    }
+    if document.issue_date.is_none() {
+        fail("BR-03", "Issue date is missing".to_string());
+    }
+    if ![TYPE_CODE_INVOICE, TYPE_CODE_CREDIT_NOTE, TYPE_CODE_PROFORMA]
+        .contains(&document.type_code)
+    {
+        fail(
+            "BR-04",
+            format!("Unsupported document type code {}", document.type_code),
+        );
+    }
+    if document.currency.len() != 3 || !document.currency.chars().all(|c| c.is_ascii_uppercase())
+    {
+        fail(
+            "BR-05",
+            format!("Invalid currency code '{}'", document.currency),
+        );
+    }
+
+    let seller = &document.seller;
+    if seller.name.trim().is_empty() {
+        fail("BR-06", "Seller name is missing".to_string());
+    }
+    if is_blank(&seller.street) || is_blank(&seller.postcode) || is_blank(&seller.city) {
+        fail(
+            "BR-08",
+            "Seller postal address needs a street, postcode and city".to_string(),
+        );
+    }
+    if seller.country_code.is_none() {
+        fail("BR-09", "Seller country is missing or not recognised".to_string());
+    }
+    if is_blank(&seller.vat_id) && is_blank(&seller.siret) {
+        fail(
+            "BR-CO-26",
+            "Seller needs a VAT number or a SIRET".to_string(),
+        );
+    }
+
+    let buyer = &document.buyer;
+    if buyer.name.trim().is_empty() {
+        fail("BR-07", "Buyer name is missing".to_string());
+    }
+    if buyer.country_code.is_none() {
+        fail("BR-11", "Buyer country is missing or not recognised".to_string());
+    }
+
+    for (party, label) in [(seller, "Seller"), (buyer, "Buyer")] {
+        if let Some(siret) = party.siret.as_deref().filter(|value| !value.trim().is_empty()) {
+            if !is_valid_siret(siret) {
+                fail("FR-SIRET", format!("{} SIRET '{}' is not valid", label, siret));
+            }
+        }
+        if let Some(vat_id) = party.vat_id.as_deref().filter(|value| !value.trim().is_empty()) {
+            let normalized = normalize_identifier(vat_id);
+            if !normalized
+                .get(..2)
+                .is_some_and(|prefix| prefix.chars().all(|c| c.is_ascii_uppercase()))
+            {
+                fail(
+                    "BR-CO-9",
+                    format!("{} VAT number '{}' must start with a country code", label, vat_id),
+                );
+            } else if normalized.starts_with("FR") && !is_valid_fr_vat_number(&normalized) {
+                fail(
+                    "FR-VAT",
+                    format!("{} VAT number '{}' is not a valid French number", label, vat_id),
+                );
+            }
+        }
+    }
+
+    if document.lines.is_empty() {
+        fail("BR-16", "At least one invoice line is required".to_string());
+    }
+    for (index, line) in document.lines.iter().enumerate() {
+        if line.name.trim().is_empty() {
+            fail("BR-25", format!("Line {}: item name is missing", index + 1));
+        }
+        if line.net_price < 0 {
+            fail(
+                "BR-27",
+                format!("Line {}: net price must not be negative", index + 1),
+            );
+        }
+    }
+
+    let line_total: i64 = document.lines.iter().map(|line| line.net_amount).sum();
+    if line_total != document.line_total {
+        fail(
+            "BR-CO-10",
+            "Sum of line net amounts does not match the line total".to_string(),
+        );
+    }
+    let allowance_total: i64 = document
+        .allowances
+        .iter()
+        .map(|allowance| allowance.amount)
+        .sum();
+    if allowance_total != document.allowance_total {
+        fail(
+            "BR-CO-11",
+            "Sum of allowances does not match the allowance total".to_string(),
+        );
+    }
+    if document.tax_basis_total != document.line_total - document.allowance_total {
+        fail(
+            "BR-CO-13",
+            "Total without VAT must equal line total minus allowances".to_string(),
+        );
+    }
+    let tax_total: i64 = document.vat_breakdown.iter().map(|row| row.tax_amount).sum();
+    if tax_total != document.tax_total {
+        fail(
+            "BR-CO-14",
+            "VAT total does not match the VAT breakdown".to_string(),
+        );
+    }
+    if document.grand_total != document.tax_basis_total + document.tax_total {
+        fail(
+            "BR-CO-15",
+            "Total with VAT must equal total without VAT plus VAT".to_string(),
+        );
+    }
+    if document.due_payable != document.grand_total - document.prepaid {
+        fail(
+            "BR-CO-16",
+            "Amount due must equal total with VAT minus prepaid amount".to_string(),
+        );
+    }
+    if document.vat_breakdown.is_empty() {
+        fail("BR-CO-18", "VAT breakdown is missing".to_string());
+    }
+    if document.type_code == TYPE_CODE_INVOICE
+        && document.due_payable > 0
+        && document.due_date.is_none()
+        && is_blank(&document.payment_terms)
+    {
+        fail(
+            "BR-CO-25",
+            "An amount due needs a due date or payment terms".to_string(),
+        );
+    }
+
+    let has_standard = document
+        .lines
+        .iter()
+        .any(|line| line.vat_category == VatCategory::Standard);
+    if has_standard && is_blank(&seller.vat_id) {
+        fail(
+            "BR-S-02",
+            "Seller VAT number is required when VAT is charged".to_string(),
+        );
+    }
+    for row in &document.vat_breakdown {
+        let key = rate_key(row.vat_rate);
+        let lines: i64 = document
+            .lines
+            .iter()
+            .filter(|line| line.vat_category == row.vat_category && rate_key(line.vat_rate) == key)
+            .map(|line| line.net_amount)
+            .sum();
+        let allowances: i64 = document
+            .allowances
+            .iter()
+            .filter(|allowance| {
+                allowance.vat_category == row.vat_category
+                    && rate_key(allowance.vat_rate) == key
+            })
+            .map(|allowance| allowance.amount)
+            .sum();
+        let (basis_rule, tax_rule) = match row.vat_category {
+            VatCategory::Standard => ("BR-S-08", "BR-S-09"),
+            VatCategory::Exempt => ("BR-E-08", "BR-E-09"),
+        };
+        if row.taxable_amount != lines - allowances {
+            fail(
+                basis_rule,
+                format!(
+                    "Taxable amount at {} % does not match its lines and allowances",
+                    row.vat_rate
+                ),
+            );
+        }
+        if row.tax_amount != tax_cents(row.taxable_amount, row.vat_rate) {
+            fail(
+                tax_rule,
+                format!("VAT at {} % is not taxable amount × rate", row.vat_rate),
+            );
+        }
+        if row.vat_category == VatCategory::Exempt && is_blank(&row.exemption_reason) {
+            fail(
+                "BR-E-10",
+                "Exempt VAT needs an exemption reason (set `vat_exemption_reason`)".to_string(),
+            );
+        }
+    }
+    violations
+}
+
+fn is_blank(value: &Option<String>) -> bool {
+    value.as_deref().map_or(true, |value| value.trim().is_empty())
+}
+
+fn rate_key(rate: f64) -> i64 {
+    (rate * 100.0).round() as i64
+}
+
+/// Uppercase with spaces, dots and dashes removed.
+pub fn normalize_identifier(value: &str) -> String {
+    value
+        .chars()
+        .filter(|c| !matches!(c, ' ' | '.' | '-' | '\u{a0}'))
+        .flat_map(char::to_uppercase)
+        .collect()
+}
+
+/// 14 digits passing the Luhn check. La Poste establishments (SIREN
+/// 356 000 000) use a digit-sum rule instead.
+pub fn is_valid_siret(value: &str) -> bool {
+    let digits = normalize_identifier(value);
+    if digits.len() != 14 || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return false;
+    }
+    if digits.starts_with("356000000") {
+        return digits.bytes().map(|b| u32::from(b - b'0')).sum::<u32>() % 5 == 0;
+    }
+    let sum: u32 = digits
+        .bytes()
+        .rev()
+        .enumerate()
+        .map(|(index, b)| {
+            let digit = u32::from(b - b'0');
+            if index % 2 == 1 {
+                let doubled = digit * 2;
+                if doubled > 9 {
+                    doubled - 9
+                } else {
+                    doubled
+                }
+            } else {
+                digit
+            }
+        })
+        .sum();
+    sum % 10 == 0
+}
+
+/// `FR` + 2-digit key + SIREN, the key being `(12 + 3 × (SIREN mod 97)) mod 97`.
+pub fn is_valid_fr_vat_number(value: &str) -> bool {
+    let normalized = normalize_identifier(value);
+    let Some(rest) = normalized.strip_prefix("FR") else {
+        return false;
+    };
+    if rest.len() != 11 || !rest.chars().all(|c| c.is_ascii_digit()) {
+        return false;
+    }
+    let (key, siren) = rest.split_at(2);
+    match (key.parse::<u64>(), siren.parse::<u64>()) {
+        (Ok(key), Ok(siren)) => key == (12 + 3 * (siren % 97)) % 97,
+        _ => false,
+    }
+}
+
+/// ISO 3166-1 alpha-2 code for a stored country, which is free text
+/// (`France` by default). Accepts codes as-is and common French and English
+/// names of the countries our customers are in.
+pub fn country_code(country: &str) -> Option<String> {
+    let trimmed = country.trim();
+    if trimmed.len() == 2 && trimmed.chars().all(|c| c.is_ascii_alphabetic()) {
+        return Some(trimmed.to_ascii_uppercase());
+    }
+    let folded: String = trimmed
+        .to_lowercase()
+        .chars()
+        .map(|c| match c {
+            'à' | 'â' | 'ä' => 'a',
+            'é' | 'è' | 'ê' | 'ë' => 'e',
+            'î' | 'ï' => 'i',
+            'ô' | 'ö' => 'o',
+            'ù' | 'û' | 'ü' => 'u',
+            'ç' => 'c',
+            '-' => ' ',
+            other => other,
+        })
+        .collect();
+    let code = match folded.as_str() {
+        "france" | "republique francaise" => "FR",
+        "belgique" | "belgium" => "BE",
+        "luxembourg" => "LU",
+        "suisse" | "switzerland" => "CH",
+        "monaco" => "MC",
+        "allemagne" | "germany" | "deutschland" => "DE",
+        "espagne" | "spain" | "espana" => "ES",
+        "italie" | "italy" | "italia" => "IT",
+        "portugal" => "PT",
+        "pays bas" | "netherlands" | "nederland" => "NL",
+        "royaume uni" | "united kingdom" => "GB",
+        "irlande" | "ireland" => "IE",
+        "autriche" | "austria" => "AT",
+        "andorre" | "andorra" => "AD",
+        _ => return None,
+    };
+    Some(code.to_string())
+}
@@ -0,0 +1,399 @@
+//! Domain model for the `invoices` bounded context.
+//!
+//! This file is free of infrastructure imports (rusqlite, tauri). Amounts are
+//! integer cents, like quotes; see `shared::utils::money` for the rounding rule.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use ts_rs::TS;
+
+use crate::shared::utils::money::{line_total_cents, prorate_cents, tax_cents};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum InvoiceKind {
+    #[default]
+    Invoice,
+    /// Avoir: cancels all or part of an issued invoice. Amounts are stored
+    /// positive; the kind carries the sign.
+    CreditNote,
+}
+
+impl std::fmt::Display for InvoiceKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Self::Invoice => "invoice",
+            Self::CreditNote => "credit_note",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum InvoiceStatus {
+    #[default]
+    Draft,
+    Issued,
+    PartiallyPaid,
+    Paid,
+    Cancelled,
+}
+
+impl std::fmt::Display for InvoiceStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Self::Draft => "draft",
+            Self::Issued => "issued",
+            Self::PartiallyPaid => "partially_paid",
+            Self::Paid => "paid",
+            Self::Cancelled => "cancelled",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl std::str::FromStr for InvoiceStatus {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "draft" => Ok(Self::Draft),
+            "issued" => Ok(Self::Issued),
+            "partially_paid" => Ok(Self::PartiallyPaid),
+            "paid" => Ok(Self::Paid),
+            "cancelled" => Ok(Self::Cancelled),
+            _ => Err(format!("Invalid invoice status: {}", s)),
+        }
+    }
+}
+
+impl InvoiceStatus {
+    /// Returns `true` when the state machine allows a direct transition from
+    /// `self` to `next`.
+    ///
+    /// | From                  | To                                   |
+    /// |-----------------------|--------------------------------------|
+    /// | Draft                 | Issued                               |
+    /// | Issued                | PartiallyPaid, Paid, Cancelled       |
+    /// | PartiallyPaid         | Issued, Paid, Cancelled              |
+    /// | Paid                  | Issued, PartiallyPaid, Cancelled     |
+    /// | Cancelled             | *(terminal — no outgoing edges)*     |
+    ///
+    /// Paid → Issued/PartiallyPaid covers a reversed payment. Cancelled is
+    /// only reached through a credit note; drafts are deleted instead.
+    pub fn can_transition_to(&self, next: &InvoiceStatus) -> bool {
+        match (self, next) {
+            (Self::Draft, Self::Issued)
+            | (Self::Issued, Self::PartiallyPaid)
+            | (Self::Issued, Self::Paid)
+            | (Self::Issued, Self::Cancelled)
+            | (Self::PartiallyPaid, Self::Issued)
+            | (Self::PartiallyPaid, Self::Paid)
+            | (Self::PartiallyPaid, Self::Cancelled)
+            | (Self::Paid, Self::Issued)
+            | (Self::Paid, Self::PartiallyPaid)
+            | (Self::Paid, Self::Cancelled) => true,
+            _ => false,
+        }
+    }
+
+    /// Only drafts are mutable; an issued invoice is corrected by a credit note.
+    pub fn is_draft(&self) -> bool {
+        matches!(self, Self::Draft)
+    }
+
+    /// Issued and not yet cancelled: counts towards revenue and receivables.
+    pub fn is_open_or_settled(&self) -> bool {
+        matches!(self, Self::Issued | Self::PartiallyPaid | Self::Paid)
+    }
+
+    /// Status implied by a payment total once the invoice is issued.
+    pub fn for_amount_paid(amount_paid: i64, total: i64) -> Self {
+        if amount_paid <= 0 {
+            Self::Issued
+        } else if amount_paid >= total {
+            Self::Paid
+        } else {
+            Self::PartiallyPaid
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum InvoiceLineKind {
+    Labor,
+    Material,
+    #[default]
+    Service,
+    Discount,
+}
+
+impl std::fmt::Display for InvoiceLineKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Self::Labor => "labor",
+            Self::Material => "material",
+            Self::Service => "service",
+            Self::Discount => "discount",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl std::str::FromStr for InvoiceLineKind {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "labor" => Ok(Self::Labor),
+            "material" => Ok(Self::Material),
+            "service" => Ok(Self::Service),
+            "discount" => Ok(Self::Discount),
+            _ => Err(format!("Invalid invoice line kind: {}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct InvoiceLine {
+    pub id: String,
+    pub invoice_id: String,
+    pub kind: InvoiceLineKind,
+    pub label: String,
+    pub description: Option<String>,
+    pub qty: f64,
+    pub unit_price: i64,
+    /// VAT rate in percent; `0.0` for exempt lines.
+    pub tax_rate: f64,
+    pub line_total: i64,
+    pub material_id: Option<String>,
+    pub position: i32,
+    pub created_at: i64,
+}
+
+/// One row of the per-rate VAT summary printed at the bottom of an invoice.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+pub struct VatBreakdownLine {
+    pub tax_rate: f64,
+    /// Net amount at this rate, after its share of the document discount.
+    pub taxable_amount: i64,
+    pub tax_amount: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct Invoice {
+    pub id: String,
+    /// Assigned when the invoice is issued; `None` while in draft.
+    pub invoice_number: Option<String>,
+    pub kind: InvoiceKind,
+    pub status: InvoiceStatus,
+    pub client_id: String,
+    pub client_name: Option<String>,
+    pub client_address: Option<String>,
+    /// Buyer VAT number and structured address, frozen with the name for
+    /// e-invoice exports.
+    pub client_tax_id: Option<String>,
+    pub client_street: Option<String>,
+    pub client_postcode: Option<String>,
+    pub client_city: Option<String>,
+    pub client_country: Option<String>,
+    pub task_id: Option<String>,
+    pub quote_id: Option<String>,
+    pub intervention_id: Option<String>,
+    /// For credit notes: the invoice being credited.
+    pub credited_invoice_id: Option<String>,
+    pub issue_date: Option<i64>,
+    pub due_date: Option<i64>,
+    pub currency: String,
+    pub notes: Option<String>,
+    pub terms: Option<String>,
+    /// Net total, after `discount_amount` (same convention as quotes).
+    pub subtotal: i64,
+    pub discount_amount: i64,
+    pub tax_total: i64,
+    pub total: i64,
+    pub amount_paid: i64,
+    pub created_by: Option<String>,
+    pub issued_at: Option<i64>,
+    pub created_at: i64,
+    pub updated_at: i64,
+    #[serde(default)]
+    pub lines: Vec<InvoiceLine>,
+    #[serde(default)]
+    pub vat_breakdown: Vec<VatBreakdownLine>,
+}
+
+impl Invoice {
+    /// Copy computed totals onto the invoice.
+    pub fn apply_totals(&mut self, totals: InvoiceTotals) {
+        self.subtotal = totals.subtotal;
+        self.discount_amount = totals.discount_amount;
+        self.tax_total = totals.tax_total;
+        self.total = totals.total;
+        self.vat_breakdown = totals.vat_breakdown;
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct InvoiceTotals {
+    pub subtotal: i64,
+    pub discount_amount: i64,
+    pub tax_total: i64,
+    pub total: i64,
+    pub vat_breakdown: Vec<VatBreakdownLine>,
+}
+
+/// Compute invoice totals from its lines and a fixed document discount.
+///
+/// VAT is computed once per rate on the discounted base (EN 16931 BR-CO-17),
+/// not per line as quotes do, so an invoice can differ from its quote by a
+/// cent when several lines share a rate. The discount is spread across rates
+/// pro rata; the last rate absorbs the rounding remainder so the shares always
+/// add up to `discount_amount`.
+pub fn compute_invoice_totals(lines: &[InvoiceLine], discount_amount: i64) -> InvoiceTotals {
+    // Keyed by rate in hundredths of a percent so 5.5 and 5.50 group together.
+    let mut bases: BTreeMap<i64, (f64, i64)> = BTreeMap::new();
+    let mut gross = 0;
+    for line in lines {
+        let line_total = line_total_cents(line.qty, line.unit_price);
+        gross += line_total;
+        let entry = bases
+            .entry((line.tax_rate * 100.0).round() as i64)
+            .or_insert((line.tax_rate, 0));
+        entry.1 += line_total;
+    }
+
+    let discount_amount = discount_amount.clamp(0, gross.max(0));
+    let mut remaining_discount = discount_amount;
+    let mut vat_breakdown = Vec::with_capacity(bases.len());
+    let rate_count = bases.len();
+    for (index, (tax_rate, base)) in bases.into_values().enumerate() {
+        let share = if index + 1 == rate_count {
+            remaining_discount
+        } else {
+            prorate_cents(discount_amount, base, gross)
+        };
+        remaining_discount -= share;
+        let taxable_amount = base - share;
+        vat_breakdown.push(VatBreakdownLine {
+            tax_rate,
+            taxable_amount,
+            tax_amount: tax_cents(taxable_amount, tax_rate),
+        });
+    }
+
+    let subtotal = gross - discount_amount;
+    let tax_total = vat_breakdown
+        .iter()
+        .map(|line| line.tax_amount)
+        .sum::<i64>();
+    InvoiceTotals {
+        subtotal,
+        discount_amount,
+        tax_total,
+        total: subtotal + tax_total,
+        vat_breakdown,
+    }
+}
+
+/// Invoice defaults stored as JSON in `organizations.invoice_settings`.
+/// Numbering prefix and payment terms come from the organization key-value
+/// settings (`invoice_prefix`, `payment_terms`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(default)]
+pub struct InvoiceBillingSettings {
+    pub credit_note_prefix: String,
+    /// VAT rate applied to generated lines and to lines entered without one.
+    pub default_tax_rate: f64,
+    /// Hourly labour rate in cents, used when invoicing an intervention.
+    pub labor_hourly_rate: i64,
+    /// Markup applied to consumed material cost, in percent.
+    pub material_markup_percent: f64,
+    /// Printed under the totals (late-payment penalties, recovery fee…).
+    pub legal_mentions: Option<String>,
+    /// Share of an accepted quote, in percent, to collect as a deposit before
+    /// it can be converted into a task. `0` disables the check.
+    pub deposit_percent: f64,
+    /// Legal reason printed in e-invoices for 0 % VAT lines, e.g.
+    /// `TVA non applicable, art. 293 B du CGI`. Required to issue such lines.
+    pub vat_exemption_reason: Option<String>,
+}
+
+impl Default for InvoiceBillingSettings {
+    fn default() -> Self {
+        Self {
+            credit_note_prefix: "AV-".to_string(),
+            default_tax_rate: 20.0,
+            labor_hourly_rate: 0,
+            material_markup_percent: 0.0,
+            legal_mentions: None,
+            deposit_percent: 0.0,
+            vat_exemption_reason: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+pub struct InvoiceLineInput {
+    #[serde(default)]
+    pub kind: InvoiceLineKind,
+    pub label: String,
+    pub description: Option<String>,
+    pub qty: f64,
+    pub unit_price: i64,
+    /// Falls back to `InvoiceBillingSettings::default_tax_rate` when absent.
+    pub tax_rate: Option<f64>,
+    pub material_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+pub struct CreateInvoiceRequest {
+    pub client_id: String,
+    pub task_id: Option<String>,
+    pub quote_id: Option<String>,
+    pub intervention_id: Option<String>,
+    pub due_date: Option<i64>,
+    pub notes: Option<String>,
+    pub terms: Option<String>,
+    pub discount_amount: Option<i64>,
+    pub lines: Vec<InvoiceLineInput>,
+}
+
+/// Edits to a draft. `lines`, when present, replaces all lines.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+pub struct UpdateInvoiceRequest {
+    pub due_date: Option<i64>,
+    pub notes: Option<String>,
+    pub terms: Option<String>,
+    pub discount_amount: Option<i64>,
+    pub lines: Option<Vec<InvoiceLineInput>>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+pub struct CreateCreditNoteRequest {
+    pub invoice_id: String,
+    pub reason: Option<String>,
+    /// Lines to credit. When absent the whole remaining amount is credited and
+    /// the original invoice is cancelled.
+    pub lines: Option<Vec<InvoiceLineInput>>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+pub struct InvoiceQuery {
+    pub client_id: Option<String>,
+    pub status: Option<InvoiceStatus>,
+    pub kind: Option<InvoiceKind>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct InvoiceListResponse {
+    pub data: Vec<Invoice>,
+    pub total: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct InvoiceExportResponse {
+    pub file_path: String,
+}
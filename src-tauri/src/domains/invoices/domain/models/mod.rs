@@ -0,0 +1,3 @@
+pub mod einvoice;
+pub mod invoices;
+pub mod payments;
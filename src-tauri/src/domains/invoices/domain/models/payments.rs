@@ -0,0 +1,286 @@
+//! Payments ledger: deposits on quotes, settlements on invoices, and the
+//! receivables derived from them. Amounts are integer cents.
+
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::shared::utils::money::prorate_cents;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum PaymentKind {
+    /// Acompte taken on an accepted quote, before any invoice exists.
+    Deposit,
+    /// Money received against an issued invoice.
+    Settlement,
+}
+
+impl std::fmt::Display for PaymentKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Self::Deposit => "deposit",
+            Self::Settlement => "settlement",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl std::str::FromStr for PaymentKind {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "deposit" => Ok(Self::Deposit),
+            "settlement" => Ok(Self::Settlement),
+            _ => Err(format!("Invalid payment kind: {}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum PaymentMethod {
+    Cash,
+    Card,
+    Transfer,
+    Cheque,
+}
+
+impl std::fmt::Display for PaymentMethod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Self::Cash => "cash",
+            Self::Card => "card",
+            Self::Transfer => "transfer",
+            Self::Cheque => "cheque",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl std::str::FromStr for PaymentMethod {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "cash" => Ok(Self::Cash),
+            "card" => Ok(Self::Card),
+            "transfer" => Ok(Self::Transfer),
+            "cheque" => Ok(Self::Cheque),
+            _ => Err(format!("Invalid payment method: {}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct Payment {
+    pub id: String,
+    pub client_id: String,
+    pub kind: PaymentKind,
+    pub method: PaymentMethod,
+    pub amount: i64,
+    /// Set for deposits.
+    pub quote_id: Option<String>,
+    /// Set for settlements.
+    pub invoice_id: Option<String>,
+    pub paid_at: i64,
+    pub reference: Option<String>,
+    pub notes: Option<String>,
+    pub recorded_by: Option<String>,
+    pub created_at: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct RecordPaymentRequest {
+    pub method: PaymentMethod,
+    pub amount: i64,
+    /// Defaults to now.
+    pub paid_at: Option<i64>,
+    pub reference: Option<String>,
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+pub struct PaymentQuery {
+    pub client_id: Option<String>,
+    pub quote_id: Option<String>,
+    pub invoice_id: Option<String>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct PaymentListResponse {
+    pub data: Vec<Payment>,
+    pub total: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+pub struct QuoteBalance {
+    pub quote_id: String,
+    pub total: i64,
+    /// Deposit the organization asks for before work starts; `0` when
+    /// `deposit_percent` is not configured.
+    pub deposit_required: i64,
+    pub deposits_paid: i64,
+    /// Part of `deposit_required` still to collect.
+    pub deposit_outstanding: i64,
+    /// `total - deposits_paid`.
+    pub balance: i64,
+}
+
+impl QuoteBalance {
+    pub fn new(quote_id: &str, total: i64, deposit_percent: f64, deposits_paid: i64) -> Self {
+        let deposit_required = required_deposit_cents(total, deposit_percent);
+        Self {
+            quote_id: quote_id.to_string(),
+            total,
+            deposit_required,
+            deposits_paid,
+            deposit_outstanding: (deposit_required - deposits_paid).max(0),
+            balance: total - deposits_paid,
+        }
+    }
+}
+
+/// `deposit_percent` of `total`, rounded to the cent. Non-positive
+/// percentages mean no deposit is required.
+pub fn required_deposit_cents(total: i64, deposit_percent: f64) -> i64 {
+    if deposit_percent <= 0.0 || total <= 0 {
+        return 0;
+    }
+    prorate_cents(
+        total,
+        (deposit_percent.min(100.0) * 100.0).round() as i64,
+        10_000,
+    )
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+pub struct InvoiceBalance {
+    pub invoice_id: String,
+    pub total: i64,
+    /// Issued credit notes against the invoice.
+    pub credited: i64,
+    /// Settlements on the invoice plus deposits on its quote.
+    pub paid: i64,
+    pub outstanding: i64,
+}
+
+/// Amount still owed on an invoice, never negative.
+pub fn outstanding_cents(total: i64, credited: i64, paid: i64) -> i64 {
+    (total - credited - paid).max(0)
+}
+
+/// Open invoice with money still owed, as read for the receivables report.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+pub struct ReceivableInvoice {
+    pub invoice_id: String,
+    pub invoice_number: Option<String>,
+    pub client_id: String,
+    pub client_name: Option<String>,
+    pub due_date: Option<i64>,
+    pub outstanding: i64,
+    /// Whole days past `due_date` at the report date; `0` when not yet due.
+    #[serde(default)]
+    pub days_overdue: i64,
+}
+
+/// Outstanding amounts split by how long they are overdue.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, TS)]
+pub struct AgingBuckets {
+    /// Not yet due.
+    pub current: i64,
+    pub days_1_30: i64,
+    pub days_31_60: i64,
+    pub days_61_90: i64,
+    pub days_over_90: i64,
+    pub total: i64,
+}
+
+impl AgingBuckets {
+    pub fn add(&mut self, days_overdue: i64, amount: i64) {
+        let bucket = match days_overdue {
+            i64::MIN..=0 => &mut self.current,
+            1..=30 => &mut self.days_1_30,
+            31..=60 => &mut self.days_31_60,
+            61..=90 => &mut self.days_61_90,
+            _ => &mut self.days_over_90,
+        };
+        *bucket += amount;
+        self.total += amount;
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+pub struct AgedReceivablesRow {
+    pub client_id: String,
+    pub client_name: Option<String>,
+    pub buckets: AgingBuckets,
+    pub invoices: Vec<ReceivableInvoice>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+pub struct AgedReceivablesQuery {
+    pub client_id: Option<String>,
+    /// Report date in epoch milliseconds; defaults to now.
+    pub as_of: Option<i64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+pub struct AgedReceivablesReport {
+    pub as_of: i64,
+    /// One row per client, largest balance first.
+    pub rows: Vec<AgedReceivablesRow>,
+    pub totals: AgingBuckets,
+}
+
+/// Group open invoices per client and bucket them by days overdue at `as_of`.
+/// Invoices without a due date are treated as due on issue, i.e. current.
+pub fn build_aged_receivables(
+    receivables: Vec<ReceivableInvoice>,
+    as_of: i64,
+) -> AgedReceivablesReport {
+    const DAY_MS: i64 = 24 * 60 * 60 * 1000;
+
+    let mut rows: Vec<AgedReceivablesRow> = Vec::new();
+    let mut totals = AgingBuckets::default();
+    for mut receivable in receivables {
+        receivable.days_overdue = receivable
+            .due_date
+            .map(|due_date| ((as_of - due_date) / DAY_MS).max(0))
+            .unwrap_or(0);
+        totals.add(receivable.days_overdue, receivable.outstanding);
+        let row = match rows
+            .iter_mut()
+            .position(|row| row.client_id == receivable.client_id)
+        {
+            Some(index) => &mut rows[index],
+            None => {
+                rows.push(AgedReceivablesRow {
+                    client_id: receivable.client_id.clone(),
+                    client_name: receivable.client_name.clone(),
+                    buckets: AgingBuckets::default(),
+                    invoices: Vec::new(),
+                });
+                rows.last_mut().expect("row just pushed")
+            }
+        };
+        row.buckets
+            .add(receivable.days_overdue, receivable.outstanding);
+        row.invoices.push(receivable);
+    }
+    for row in &mut rows {
+        row.invoices
+            .sort_by(|a, b| b.days_overdue.cmp(&a.days_overdue));
+    }
+    rows.sort_by(|a, b| {
+        b.buckets
+            .total
+            .cmp(&a.buckets.total)
+            .then_with(|| a.client_id.cmp(&b.client_id))
+    });
+    AgedReceivablesReport {
+        as_of,
+        rows,
+        totals,
+    }
+}
@@ -0,0 +1,7 @@
+//! Cross-domain facade for the `invoices` domain (ADR-002, ADR-003).
+//!
+//! Keep this surface minimal — only expose what other domains truly need.
+//! Prefer `shared/contracts/` for type-only sharing.
+
+/// Facade for the `Invoices` domain.
+pub struct InvoicesFacade;
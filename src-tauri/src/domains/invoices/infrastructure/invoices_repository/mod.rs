@@ -0,0 +1,654 @@
+use async_trait::async_trait;
+use rusqlite::{params, OptionalExtension, Transaction};
+use std::sync::Arc;
+
+use crate::db::Database;
+use crate::domains::invoices::domain::models::invoices::{
+    Invoice, InvoiceKind, InvoiceLine, InvoiceQuery, InvoiceStatus,
+};
+use crate::shared::error::{AppError, AppResult};
+
+const INVOICE_COLUMNS: &str = "id, invoice_number, kind, status, client_id, client_name, client_address, client_tax_id, client_street, client_postcode, client_city, client_country, task_id, quote_id, intervention_id, credited_invoice_id, issue_date, due_date, currency, notes, terms, subtotal, discount_amount, tax_total, total, amount_paid, created_by, issued_at, created_at, updated_at";
+
+const LINE_COLUMNS: &str = "id, invoice_id, kind, label, description, qty, unit_price, tax_rate, line_total, material_id, position, created_at";
+
+/// The buyer's name, postal address, VAT number and address parts as an
+/// invoice freezes them, read from `clients c`.
+const CLIENT_SNAPSHOT_COLUMNS: &str = "client_name, client_address, client_tax_id, client_street, client_postcode, client_city, client_country";
+const CLIENT_SNAPSHOT_VALUES: &str = "COALESCE(NULLIF(TRIM(c.company_name), ''), c.name),
+    NULLIF(TRIM(
+        COALESCE(c.address_street, '') || char(10) ||
+        TRIM(COALESCE(c.address_zip, '') || ' ' || COALESCE(c.address_city, '')) || char(10) ||
+        COALESCE(c.address_country, ''),
+        char(10) || ' '), ''),
+    NULLIF(TRIM(c.tax_id), ''),
+    NULLIF(TRIM(c.address_street), ''),
+    NULLIF(TRIM(c.address_zip), ''),
+    NULLIF(TRIM(c.address_city), ''),
+    NULLIF(TRIM(c.address_country), '')";
+
+/// Where the next legal number comes from when an invoice leaves draft.
+#[derive(Debug, Clone)]
+pub struct NumberingRequest {
+    /// `invoice` or `credit_note`; each series restarts every year.
+    pub series: String,
+    pub year: i32,
+    /// Printed before the year, e.g. `INV-` gives `INV-2026-00001`.
+    pub prefix: String,
+    /// First value of a series that has never been used; lets a business
+    /// continue the numbering of its previous software.
+    pub first_value: i64,
+}
+
+impl NumberingRequest {
+    fn sequence_key(&self) -> String {
+        format!("{}:{}", self.series, self.year)
+    }
+}
+
+#[async_trait]
+pub trait InvoicesRepository: Send + Sync {
+    async fn list(&self, query: &InvoiceQuery) -> AppResult<(Vec<Invoice>, i64)>;
+    async fn get(&self, id: &str) -> AppResult<Invoice>;
+    async fn create(&self, invoice: &Invoice) -> AppResult<()>;
+    async fn update_draft(&self, invoice: &Invoice) -> AppResult<()>;
+    async fn delete_draft(&self, id: &str) -> AppResult<()>;
+    /// Fill in the client's current identity on `invoice` as issuing would
+    /// freeze it, without writing anything, so a draft can be checked first.
+    async fn preview_client_snapshot(&self, invoice: &mut Invoice) -> AppResult<()>;
+    /// Assign the next number and move a draft to `issued`, atomically.
+    async fn issue(
+        &self,
+        id: &str,
+        numbering: &NumberingRequest,
+        issue_date: i64,
+        due_date: i64,
+    ) -> AppResult<String>;
+    /// Insert an already-issued credit note with its number and, when
+    /// `cancel_original` is set, cancel the credited invoice in the same
+    /// transaction.
+    async fn create_credit_note(
+        &self,
+        credit_note: &Invoice,
+        numbering: &NumberingRequest,
+        cancel_original: bool,
+    ) -> AppResult<String>;
+    async fn update_payment_state(
+        &self,
+        id: &str,
+        amount_paid: i64,
+        status: InvoiceStatus,
+    ) -> AppResult<()>;
+    /// Invoices (not credit notes) for a quote or intervention that have not
+    /// been cancelled.
+    async fn find_active_for_source(
+        &self,
+        quote_id: Option<&str>,
+        intervention_id: Option<&str>,
+    ) -> AppResult<Vec<Invoice>>;
+    /// Sum of issued credit notes against `invoice_id`.
+    async fn credited_total(&self, invoice_id: &str) -> AppResult<i64>;
+}
+
+pub struct SqliteInvoicesRepository {
+    db: Arc<Database>,
+}
+
+impl SqliteInvoicesRepository {
+    pub fn new(db: Arc<Database>) -> Self {
+        Self { db }
+    }
+
+    fn map_invoice(row: &rusqlite::Row<'_>) -> rusqlite::Result<Invoice> {
+        let kind: String = row.get("kind")?;
+        let status: String = row.get("status")?;
+        Ok(Invoice {
+            id: row.get("id")?,
+            invoice_number: row.get("invoice_number")?,
+            kind: serde_json::from_str(&format!("\"{}\"", kind)).map_err(|e| {
+                rusqlite::Error::FromSqlConversionFailure(
+                    0,
+                    rusqlite::types::Type::Text,
+                    Box::new(e),
+                )
+            })?,
+            status: serde_json::from_str(&format!("\"{}\"", status)).map_err(|e| {
+                rusqlite::Error::FromSqlConversionFailure(
+                    0,
+                    rusqlite::types::Type::Text,
+                    Box::new(e),
+                )
+            })?,
+            client_id: row.get("client_id")?,
+            client_name: row.get("client_name")?,
+            client_address: row.get("client_address")?,
+            client_tax_id: row.get("client_tax_id")?,
+            client_street: row.get("client_street")?,
+            client_postcode: row.get("client_postcode")?,
+            client_city: row.get("client_city")?,
+            client_country: row.get("client_country")?,
+            task_id: row.get("task_id")?,
+            quote_id: row.get("quote_id")?,
+            intervention_id: row.get("intervention_id")?,
+            credited_invoice_id: row.get("credited_invoice_id")?,
+            issue_date: row.get("issue_date")?,
+            due_date: row.get("due_date")?,
+            currency: row.get("currency")?,
+            notes: row.get("notes")?,
+            terms: row.get("terms")?,
+            subtotal: row.get("subtotal")?,
+            discount_amount: row.get("discount_amount")?,
+            tax_total: row.get("tax_total")?,
+            total: row.get("total")?,
+            amount_paid: row.get("amount_paid")?,
+            created_by: row.get("created_by")?,
+            issued_at: row.get("issued_at")?,
+            created_at: row.get("created_at")?,
+            updated_at: row.get("updated_at")?,
+            lines: Vec::new(),
+            vat_breakdown: Vec::new(),
+        })
+    }
+
+    fn map_line(row: &rusqlite::Row<'_>) -> rusqlite::Result<InvoiceLine> {
+        let kind: String = row.get("kind")?;
+        Ok(InvoiceLine {
+            id: row.get("id")?,
+            invoice_id: row.get("invoice_id")?,
+            kind: kind.parse().map_err(|e: String| {
+                rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, e.into())
+            })?,
+            label: row.get("label")?,
+            description: row.get("description")?,
+            qty: row.get("qty")?,
+            unit_price: row.get("unit_price")?,
+            tax_rate: row.get("tax_rate")?,
+            line_total: row.get("line_total")?,
+            material_id: row.get("material_id")?,
+            position: row.get("position")?,
+            created_at: row.get("created_at")?,
+        })
+    }
+
+    fn insert_invoice(tx: &Transaction<'_>, invoice: &Invoice) -> rusqlite::Result<()> {
+        tx.execute(
+            &format!(
+                "INSERT INTO invoices ({}) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23, ?24, ?25, ?26, ?27, ?28, ?29, ?30)",
+                INVOICE_COLUMNS
+            ),
+            params![
+                invoice.id,
+                invoice.invoice_number,
+                invoice.kind.to_string(),
+                invoice.status.to_string(),
+                invoice.client_id,
+                invoice.client_name,
+                invoice.client_address,
+                invoice.client_tax_id,
+                invoice.client_street,
+                invoice.client_postcode,
+                invoice.client_city,
+                invoice.client_country,
+                invoice.task_id,
+                invoice.quote_id,
+                invoice.intervention_id,
+                invoice.credited_invoice_id,
+                invoice.issue_date,
+                invoice.due_date,
+                invoice.currency,
+                invoice.notes,
+                invoice.terms,
+                invoice.subtotal,
+                invoice.discount_amount,
+                invoice.tax_total,
+                invoice.total,
+                invoice.amount_paid,
+                invoice.created_by,
+                invoice.issued_at,
+                invoice.created_at,
+                invoice.updated_at,
+            ],
+        )?;
+        Self::insert_lines(tx, &invoice.lines)
+    }
+
+    fn insert_lines(tx: &Transaction<'_>, lines: &[InvoiceLine]) -> rusqlite::Result<()> {
+        let mut stmt = tx.prepare(&format!(
+            "INSERT INTO invoice_lines ({}) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+            LINE_COLUMNS
+        ))?;
+        for line in lines {
+            stmt.execute(params![
+                line.id,
+                line.invoice_id,
+                line.kind.to_string(),
+                line.label,
+                line.description,
+                line.qty,
+                line.unit_price,
+                line.tax_rate,
+                line.line_total,
+                line.material_id,
+                line.position,
+                line.created_at,
+            ])?;
+        }
+        Ok(())
+    }
+
+    /// Take the next value of a numbering series. The `UPDATE` comes first so
+    /// the write lock is held before the value is read: two concurrent issues
+    /// can never observe the same number.
+    fn allocate_number(
+        tx: &Transaction<'_>,
+        numbering: &NumberingRequest,
+        now: i64,
+    ) -> rusqlite::Result<String> {
+        let sequence_key = numbering.sequence_key();
+        tx.execute(
+            "INSERT INTO invoice_sequences (sequence_key, next_value, updated_at)
+             SELECT ?1,
+                    CASE WHEN EXISTS (
+                        SELECT 1 FROM invoice_sequences WHERE sequence_key LIKE ?2 || ':%'
+                    ) THEN 1 ELSE ?3 END,
+                    ?4
+             WHERE NOT EXISTS (SELECT 1 FROM invoice_sequences WHERE sequence_key = ?1)",
+            params![
+                sequence_key,
+                numbering.series,
+                numbering.first_value.max(1),
+                now
+            ],
+        )?;
+        tx.execute(
+            "UPDATE invoice_sequences SET next_value = next_value + 1, updated_at = ?2
+             WHERE sequence_key = ?1",
+            params![sequence_key, now],
+        )?;
+        let value: i64 = tx.query_row(
+            "SELECT next_value - 1 FROM invoice_sequences WHERE sequence_key = ?1",
+            params![sequence_key],
+            |row| row.get(0),
+        )?;
+        Ok(format!(
+            "{}{}-{:05}",
+            numbering.prefix, numbering.year, value
+        ))
+    }
+
+    /// Freeze the buyer's name, VAT number and postal address on the invoice.
+    fn snapshot_client(tx: &Transaction<'_>, invoice_id: &str) -> rusqlite::Result<()> {
+        tx.execute(
+            &format!(
+                "UPDATE invoices SET ({}) = (
+                    SELECT {} FROM clients c WHERE c.id = invoices.client_id
+                 )
+                 WHERE id = ?1",
+                CLIENT_SNAPSHOT_COLUMNS, CLIENT_SNAPSHOT_VALUES
+            ),
+            params![invoice_id],
+        )?;
+        Ok(())
+    }
+
+    fn load_lines(
+        conn: &rusqlite::Connection,
+        invoice_id: &str,
+    ) -> rusqlite::Result<Vec<InvoiceLine>> {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM invoice_lines WHERE invoice_id = ?1 ORDER BY position, created_at",
+            LINE_COLUMNS
+        ))?;
+        let rows = stmt.query_map(params![invoice_id], Self::map_line)?;
+        rows.collect()
+    }
+}
+
+#[async_trait]
+impl InvoicesRepository for SqliteInvoicesRepository {
+    async fn list(&self, query: &InvoiceQuery) -> AppResult<(Vec<Invoice>, i64)> {
+        let conn = self
+            .db
+            .get_connection()
+            .map_err(|error| AppError::db_sanitized("invoices.list.get_connection", error))?;
+        let mut conditions: Vec<String> = Vec::new();
+        let mut values: Vec<rusqlite::types::Value> = Vec::new();
+        if let Some(client_id) = &query.client_id {
+            values.push(client_id.clone().into());
+            conditions.push(format!("client_id = ?{}", values.len()));
+        }
+        if let Some(status) = &query.status {
+            values.push(status.to_string().into());
+            conditions.push(format!("status = ?{}", values.len()));
+        }
+        if let Some(kind) = &query.kind {
+            values.push(kind.to_string().into());
+            conditions.push(format!("kind = ?{}", values.len()));
+        }
+        let where_clause = if conditions.is_empty() {
+            String::new()
+        } else {
+            format!("WHERE {}", conditions.join(" AND "))
+        };
+
+        let total: i64 = conn
+            .query_row(
+                &format!("SELECT COUNT(*) FROM invoices {}", where_clause),
+                rusqlite::params_from_iter(values.iter()),
+                |row| row.get(0),
+            )
+            .map_err(|error| AppError::db_sanitized("invoices.list.count", error))?;
+
+        values.push(query.limit.unwrap_or(50).clamp(1, 500).into());
+        values.push(query.offset.unwrap_or(0).max(0).into());
+        let mut stmt = conn
+            .prepare(&format!(
+                "SELECT {} FROM invoices {} ORDER BY created_at DESC LIMIT ?{} OFFSET ?{}",
+                INVOICE_COLUMNS,
+                where_clause,
+                values.len() - 1,
+                values.len()
+            ))
+            .map_err(|error| AppError::db_sanitized("invoices.list.prepare", error))?;
+        let rows = stmt
+            .query_map(rusqlite::params_from_iter(values.iter()), Self::map_invoice)
+            .map_err(|error| AppError::db_sanitized("invoices.list.query", error))?;
+        let mut invoices = Vec::new();
+        for row in rows {
+            invoices.push(row.map_err(|error| AppError::db_sanitized("invoices.list.row", error))?);
+        }
+        Ok((invoices, total))
+    }
+
+    async fn get(&self, id: &str) -> AppResult<Invoice> {
+        let conn = self
+            .db
+            .get_connection()
+            .map_err(|error| AppError::db_sanitized("invoices.get.get_connection", error))?;
+        let mut invoice = conn
+            .query_row(
+                &format!("SELECT {} FROM invoices WHERE id = ?1", INVOICE_COLUMNS),
+                params![id],
+                Self::map_invoice,
+            )
+            .optional()
+            .map_err(|error| AppError::db_sanitized("invoices.get.query", error))?
+            .ok_or_else(|| AppError::NotFound(format!("Invoice not found: {}", id)))?;
+        invoice.lines = Self::load_lines(&conn, id)
+            .map_err(|error| AppError::db_sanitized("invoices.get.lines", error))?;
+        Ok(invoice)
+    }
+
+    async fn create(&self, invoice: &Invoice) -> AppResult<()> {
+        let mut conn = self
+            .db
+            .get_connection()
+            .map_err(|error| AppError::db_sanitized("invoices.create.get_connection", error))?;
+        let tx = conn
+            .transaction()
+            .map_err(|error| AppError::db_sanitized("invoices.create.begin", error))?;
+        Self::insert_invoice(&tx, invoice)
+            .map_err(|error| AppError::db_sanitized("invoices.create.insert", error))?;
+        tx.commit()
+            .map_err(|error| AppError::db_sanitized("invoices.create.commit", error))
+    }
+
+    async fn update_draft(&self, invoice: &Invoice) -> AppResult<()> {
+        let mut conn = self.db.get_connection().map_err(|error| {
+            AppError::db_sanitized("invoices.update_draft.get_connection", error)
+        })?;
+        let tx = conn
+            .transaction()
+            .map_err(|error| AppError::db_sanitized("invoices.update_draft.begin", error))?;
+        let updated = tx
+            .execute(
+                "UPDATE invoices SET
+                    due_date = ?2, notes = ?3, terms = ?4, subtotal = ?5, discount_amount = ?6,
+                    tax_total = ?7, total = ?8, updated_at = ?9
+                 WHERE id = ?1 AND status = 'draft'",
+                params![
+                    invoice.id,
+                    invoice.due_date,
+                    invoice.notes,
+                    invoice.terms,
+                    invoice.subtotal,
+                    invoice.discount_amount,
+                    invoice.tax_total,
+                    invoice.total,
+                    invoice.updated_at,
+                ],
+            )
+            .map_err(|error| AppError::db_sanitized("invoices.update_draft.update", error))?;
+        if updated == 0 {
+            return Err(AppError::Validation(
+                "Only draft invoices can be edited".to_string(),
+            ));
+        }
+        tx.execute(
+            "DELETE FROM invoice_lines WHERE invoice_id = ?1",
+            params![invoice.id],
+        )
+        .map_err(|error| AppError::db_sanitized("invoices.update_draft.clear_lines", error))?;
+        Self::insert_lines(&tx, &invoice.lines)
+            .map_err(|error| AppError::db_sanitized("invoices.update_draft.insert_lines", error))?;
+        tx.commit()
+            .map_err(|error| AppError::db_sanitized("invoices.update_draft.commit", error))
+    }
+
+    async fn delete_draft(&self, id: &str) -> AppResult<()> {
+        let conn = self.db.get_connection().map_err(|error| {
+            AppError::db_sanitized("invoices.delete_draft.get_connection", error)
+        })?;
+        let deleted = conn
+            .execute(
+                "DELETE FROM invoices WHERE id = ?1 AND status = 'draft'",
+                params![id],
+            )
+            .map_err(|error| AppError::db_sanitized("invoices.delete_draft.delete", error))?;
+        if deleted == 0 {
+            return Err(AppError::Validation(
+                "Only draft invoices can be deleted".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    async fn preview_client_snapshot(&self, invoice: &mut Invoice) -> AppResult<()> {
+        let conn = self.db.get_connection().map_err(|error| {
+            AppError::db_sanitized("invoices.preview_client_snapshot.get_connection", error)
+        })?;
+        let snapshot = conn
+            .query_row(
+                &format!(
+                    "SELECT {} FROM clients c WHERE c.id = ?1",
+                    CLIENT_SNAPSHOT_VALUES
+                ),
+                params![invoice.client_id],
+                |row| {
+                    Ok([
+                        row.get(0)?,
+                        row.get(1)?,
+                        row.get(2)?,
+                        row.get(3)?,
+                        row.get(4)?,
+                        row.get(5)?,
+                        row.get(6)?,
+                    ])
+                },
+            )
+            .optional()
+            .map_err(|error| {
+                AppError::db_sanitized("invoices.preview_client_snapshot.query", error)
+            })?;
+        // A missing client blanks the snapshot, as issuing would.
+        let [name, address, tax_id, street, postcode, city, country]: [Option<String>; 7] =
+            snapshot.unwrap_or_default();
+        invoice.client_name = name;
+        invoice.client_address = address;
+        invoice.client_tax_id = tax_id;
+        invoice.client_street = street;
+        invoice.client_postcode = postcode;
+        invoice.client_city = city;
+        invoice.client_country = country;
+        Ok(())
+    }
+
+    async fn issue(
+        &self,
+        id: &str,
+        numbering: &NumberingRequest,
+        issue_date: i64,
+        due_date: i64,
+    ) -> AppResult<String> {
+        let mut conn = self
+            .db
+            .get_connection()
+            .map_err(|error| AppError::db_sanitized("invoices.issue.get_connection", error))?;
+        let tx = conn
+            .transaction_with_behavior(rusqlite::TransactionBehavior::Immediate)
+            .map_err(|error| AppError::db_sanitized("invoices.issue.begin", error))?;
+        let status: Option<String> = tx
+            .query_row(
+                "SELECT status FROM invoices WHERE id = ?1",
+                params![id],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(|error| AppError::db_sanitized("invoices.issue.status", error))?;
+        match status.as_deref() {
+            None => return Err(AppError::NotFound(format!("Invoice not found: {}", id))),
+            Some("draft") => {}
+            Some(other) => {
+                return Err(AppError::Validation(format!(
+                    "Cannot issue an invoice in '{}' status",
+                    other
+                )))
+            }
+        }
+
+        let number = Self::allocate_number(&tx, numbering, issue_date)
+            .map_err(|error| AppError::db_sanitized("invoices.issue.allocate_number", error))?;
+        tx.execute(
+            "UPDATE invoices SET invoice_number = ?2, status = 'issued', issue_date = ?3,
+                due_date = ?4, issued_at = ?3, updated_at = ?3
+             WHERE id = ?1",
+            params![id, number, issue_date, due_date],
+        )
+        .map_err(|error| AppError::db_sanitized("invoices.issue.update", error))?;
+        Self::snapshot_client(&tx, id)
+            .map_err(|error| AppError::db_sanitized("invoices.issue.snapshot_client", error))?;
+        tx.commit()
+            .map_err(|error| AppError::db_sanitized("invoices.issue.commit", error))?;
+        Ok(number)
+    }
+
+    async fn create_credit_note(
+        &self,
+        credit_note: &Invoice,
+        numbering: &NumberingRequest,
+        cancel_original: bool,
+    ) -> AppResult<String> {
+        let mut conn = self.db.get_connection().map_err(|error| {
+            AppError::db_sanitized("invoices.create_credit_note.get_connection", error)
+        })?;
+        let tx = conn
+            .transaction_with_behavior(rusqlite::TransactionBehavior::Immediate)
+            .map_err(|error| AppError::db_sanitized("invoices.create_credit_note.begin", error))?;
+        let now = credit_note.created_at;
+        let number = Self::allocate_number(&tx, numbering, now).map_err(|error| {
+            AppError::db_sanitized("invoices.create_credit_note.allocate_number", error)
+        })?;
+        let mut credit_note = credit_note.clone();
+        credit_note.invoice_number = Some(number.clone());
+        Self::insert_invoice(&tx, &credit_note)
+            .map_err(|error| AppError::db_sanitized("invoices.create_credit_note.insert", error))?;
+        Self::snapshot_client(&tx, &credit_note.id).map_err(|error| {
+            AppError::db_sanitized("invoices.create_credit_note.snapshot_client", error)
+        })?;
+        if cancel_original {
+            if let Some(original_id) = &credit_note.credited_invoice_id {
+                tx.execute(
+                    "UPDATE invoices SET status = 'cancelled', updated_at = ?2 WHERE id = ?1",
+                    params![original_id, now],
+                )
+                .map_err(|error| {
+                    AppError::db_sanitized("invoices.create_credit_note.cancel_original", error)
+                })?;
+            }
+        }
+        tx.commit()
+            .map_err(|error| AppError::db_sanitized("invoices.create_credit_note.commit", error))?;
+        Ok(number)
+    }
+
+    async fn update_payment_state(
+        &self,
+        id: &str,
+        amount_paid: i64,
+        status: InvoiceStatus,
+    ) -> AppResult<()> {
+        let conn = self.db.get_connection().map_err(|error| {
+            AppError::db_sanitized("invoices.update_payment_state.get_connection", error)
+        })?;
+        conn.execute(
+            "UPDATE invoices SET amount_paid = ?2, status = ?3, updated_at = ?4 WHERE id = ?1",
+            params![
+                id,
+                amount_paid,
+                status.to_string(),
+                chrono::Utc::now().timestamp_millis()
+            ],
+        )
+        .map_err(|error| AppError::db_sanitized("invoices.update_payment_state.update", error))?;
+        Ok(())
+    }
+
+    async fn find_active_for_source(
+        &self,
+        quote_id: Option<&str>,
+        intervention_id: Option<&str>,
+    ) -> AppResult<Vec<Invoice>> {
+        let conn = self.db.get_connection().map_err(|error| {
+            AppError::db_sanitized("invoices.find_active_for_source.get_connection", error)
+        })?;
+        let mut stmt = conn
+            .prepare(&format!(
+                "SELECT {} FROM invoices
+                 WHERE kind = ?1 AND status != 'cancelled'
+                   AND ((?2 IS NOT NULL AND quote_id = ?2) OR (?3 IS NOT NULL AND intervention_id = ?3))
+                 ORDER BY created_at",
+                INVOICE_COLUMNS
+            ))
+            .map_err(|error| {
+                AppError::db_sanitized("invoices.find_active_for_source.prepare", error)
+            })?;
+        let rows = stmt
+            .query_map(
+                params![InvoiceKind::Invoice.to_string(), quote_id, intervention_id],
+                Self::map_invoice,
+            )
+            .map_err(|error| {
+                AppError::db_sanitized("invoices.find_active_for_source.query", error)
+            })?;
+        let mut invoices = Vec::new();
+        for row in rows {
+            invoices.push(row.map_err(|error| {
+                AppError::db_sanitized("invoices.find_active_for_source.row", error)
+            })?);
+        }
+        Ok(invoices)
+    }
+
+    async fn credited_total(&self, invoice_id: &str) -> AppResult<i64> {
+        let conn = self.db.get_connection().map_err(|error| {
+            AppError::db_sanitized("invoices.credited_total.get_connection", error)
+        })?;
+        conn.query_row(
+            "SELECT COALESCE(SUM(total), 0) FROM invoices
+             WHERE kind = 'credit_note' AND credited_invoice_id = ?1",
+            params![invoice_id],
+            |row| row.get(0),
+        )
+        .map_err(|error| AppError::db_sanitized("invoices.credited_total.query", error))
+    }
+}
@@ -0,0 +1,2 @@
+pub(crate) mod invoices_repository;
+pub(crate) mod payments_repository;
@@ -0,0 +1,253 @@
+use async_trait::async_trait;
+use rusqlite::{params, OptionalExtension};
+use std::sync::Arc;
+
+use crate::db::Database;
+use crate::domains::invoices::domain::models::payments::{
+    Payment, PaymentQuery, ReceivableInvoice,
+};
+use crate::shared::error::{AppError, AppResult};
+
+const PAYMENT_COLUMNS: &str = "id, client_id, kind, method, amount, quote_id, invoice_id, paid_at, reference, notes, recorded_by, created_at";
+
+#[async_trait]
+pub trait PaymentsRepository: Send + Sync {
+    async fn list(&self, query: &PaymentQuery) -> AppResult<(Vec<Payment>, i64)>;
+    async fn get(&self, id: &str) -> AppResult<Payment>;
+    async fn create(&self, payment: &Payment) -> AppResult<()>;
+    async fn delete(&self, id: &str) -> AppResult<()>;
+    /// Sum of deposits recorded on a quote.
+    async fn deposits_total(&self, quote_id: &str) -> AppResult<i64>;
+    /// Settlements on the invoice plus deposits on `quote_id`, if any.
+    async fn paid_total_for_invoice(
+        &self,
+        invoice_id: &str,
+        quote_id: Option<&str>,
+    ) -> AppResult<i64>;
+    /// Issued, not fully paid invoices with their outstanding amount net of
+    /// credit notes.
+    async fn open_receivables(&self, client_id: Option<&str>) -> AppResult<Vec<ReceivableInvoice>>;
+}
+
+pub struct SqlitePaymentsRepository {
+    db: Arc<Database>,
+}
+
+impl SqlitePaymentsRepository {
+    pub fn new(db: Arc<Database>) -> Self {
+        Self { db }
+    }
+
+    fn map_payment(row: &rusqlite::Row<'_>) -> rusqlite::Result<Payment> {
+        let kind: String = row.get("kind")?;
+        let method: String = row.get("method")?;
+        Ok(Payment {
+            id: row.get("id")?,
+            client_id: row.get("client_id")?,
+            kind: kind.parse().map_err(|e: String| {
+                rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, e.into())
+            })?,
+            method: method.parse().map_err(|e: String| {
+                rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, e.into())
+            })?,
+            amount: row.get("amount")?,
+            quote_id: row.get("quote_id")?,
+            invoice_id: row.get("invoice_id")?,
+            paid_at: row.get("paid_at")?,
+            reference: row.get("reference")?,
+            notes: row.get("notes")?,
+            recorded_by: row.get("recorded_by")?,
+            created_at: row.get("created_at")?,
+        })
+    }
+}
+
+#[async_trait]
+impl PaymentsRepository for SqlitePaymentsRepository {
+    async fn list(&self, query: &PaymentQuery) -> AppResult<(Vec<Payment>, i64)> {
+        let conn = self
+            .db
+            .get_connection()
+            .map_err(|error| AppError::db_sanitized("payments.list.get_connection", error))?;
+        let mut conditions: Vec<String> = Vec::new();
+        let mut values: Vec<rusqlite::types::Value> = Vec::new();
+        if let Some(client_id) = &query.client_id {
+            values.push(client_id.clone().into());
+            conditions.push(format!("client_id = ?{}", values.len()));
+        }
+        if let Some(quote_id) = &query.quote_id {
+            values.push(quote_id.clone().into());
+            conditions.push(format!("quote_id = ?{}", values.len()));
+        }
+        if let Some(invoice_id) = &query.invoice_id {
+            values.push(invoice_id.clone().into());
+            conditions.push(format!("invoice_id = ?{}", values.len()));
+        }
+        let where_clause = if conditions.is_empty() {
+            String::new()
+        } else {
+            format!("WHERE {}", conditions.join(" AND "))
+        };
+
+        let total: i64 = conn
+            .query_row(
+                &format!("SELECT COUNT(*) FROM payments {}", where_clause),
+                rusqlite::params_from_iter(values.iter()),
+                |row| row.get(0),
+            )
+            .map_err(|error| AppError::db_sanitized("payments.list.count", error))?;
+
+        values.push(query.limit.unwrap_or(50).clamp(1, 500).into());
+        values.push(query.offset.unwrap_or(0).max(0).into());
+        let mut stmt = conn
+            .prepare(&format!(
+                "SELECT {} FROM payments {} ORDER BY paid_at DESC, created_at DESC LIMIT ?{} OFFSET ?{}",
+                PAYMENT_COLUMNS,
+                where_clause,
+                values.len() - 1,
+                values.len()
+            ))
+            .map_err(|error| AppError::db_sanitized("payments.list.prepare", error))?;
+        let rows = stmt
+            .query_map(rusqlite::params_from_iter(values.iter()), Self::map_payment)
+            .map_err(|error| AppError::db_sanitized("payments.list.query", error))?;
+        let mut payments = Vec::new();
+        for row in rows {
+            payments.push(row.map_err(|error| AppError::db_sanitized("payments.list.row", error))?);
+        }
+        Ok((payments, total))
+    }
+
+    async fn get(&self, id: &str) -> AppResult<Payment> {
+        let conn = self
+            .db
+            .get_connection()
+            .map_err(|error| AppError::db_sanitized("payments.get.get_connection", error))?;
+        conn.query_row(
+            &format!("SELECT {} FROM payments WHERE id = ?1", PAYMENT_COLUMNS),
+            params![id],
+            Self::map_payment,
+        )
+        .optional()
+        .map_err(|error| AppError::db_sanitized("payments.get.query", error))?
+        .ok_or_else(|| AppError::NotFound(format!("Payment not found: {}", id)))
+    }
+
+    async fn create(&self, payment: &Payment) -> AppResult<()> {
+        let conn = self
+            .db
+            .get_connection()
+            .map_err(|error| AppError::db_sanitized("payments.create.get_connection", error))?;
+        conn.execute(
+            &format!(
+                "INSERT INTO payments ({}) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+                PAYMENT_COLUMNS
+            ),
+            params![
+                payment.id,
+                payment.client_id,
+                payment.kind.to_string(),
+                payment.method.to_string(),
+                payment.amount,
+                payment.quote_id,
+                payment.invoice_id,
+                payment.paid_at,
+                payment.reference,
+                payment.notes,
+                payment.recorded_by,
+                payment.created_at,
+            ],
+        )
+        .map_err(|error| AppError::db_sanitized("payments.create.insert", error))?;
+        Ok(())
+    }
+
+    async fn delete(&self, id: &str) -> AppResult<()> {
+        let conn = self
+            .db
+            .get_connection()
+            .map_err(|error| AppError::db_sanitized("payments.delete.get_connection", error))?;
+        let deleted = conn
+            .execute("DELETE FROM payments WHERE id = ?1", params![id])
+            .map_err(|error| AppError::db_sanitized("payments.delete.delete", error))?;
+        if deleted == 0 {
+            return Err(AppError::NotFound(format!("Payment not found: {}", id)));
+        }
+        Ok(())
+    }
+
+    async fn deposits_total(&self, quote_id: &str) -> AppResult<i64> {
+        let conn = self.db.get_connection().map_err(|error| {
+            AppError::db_sanitized("payments.deposits_total.get_connection", error)
+        })?;
+        conn.query_row(
+            "SELECT COALESCE(SUM(amount), 0) FROM payments
+             WHERE kind = 'deposit' AND quote_id = ?1",
+            params![quote_id],
+            |row| row.get(0),
+        )
+        .map_err(|error| AppError::db_sanitized("payments.deposits_total.query", error))
+    }
+
+    async fn paid_total_for_invoice(
+        &self,
+        invoice_id: &str,
+        quote_id: Option<&str>,
+    ) -> AppResult<i64> {
+        let conn = self.db.get_connection().map_err(|error| {
+            AppError::db_sanitized("payments.paid_total_for_invoice.get_connection", error)
+        })?;
+        conn.query_row(
+            "SELECT COALESCE(SUM(amount), 0) FROM payments
+             WHERE (kind = 'settlement' AND invoice_id = ?1)
+                OR (kind = 'deposit' AND ?2 IS NOT NULL AND quote_id = ?2)",
+            params![invoice_id, quote_id],
+            |row| row.get(0),
+        )
+        .map_err(|error| AppError::db_sanitized("payments.paid_total_for_invoice.query", error))
+    }
+
+    async fn open_receivables(&self, client_id: Option<&str>) -> AppResult<Vec<ReceivableInvoice>> {
+        let conn = self.db.get_connection().map_err(|error| {
+            AppError::db_sanitized("payments.open_receivables.get_connection", error)
+        })?;
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, invoice_number, client_id, client_name, due_date, outstanding FROM (
+                    SELECT i.id, i.invoice_number, i.client_id, i.client_name, i.due_date,
+                           i.total - i.amount_paid - COALESCE((
+                               SELECT SUM(cn.total) FROM invoices cn
+                               WHERE cn.kind = 'credit_note' AND cn.credited_invoice_id = i.id
+                           ), 0) AS outstanding
+                    FROM invoices i
+                    WHERE i.kind = 'invoice' AND i.status IN ('issued', 'partially_paid')
+                      AND (?1 IS NULL OR i.client_id = ?1)
+                 )
+                 WHERE outstanding > 0
+                 ORDER BY due_date",
+            )
+            .map_err(|error| AppError::db_sanitized("payments.open_receivables.prepare", error))?;
+        let rows = stmt
+            .query_map(params![client_id], |row| {
+                Ok(ReceivableInvoice {
+                    invoice_id: row.get("id")?,
+                    invoice_number: row.get("invoice_number")?,
+                    client_id: row.get("client_id")?,
+                    client_name: row.get("client_name")?,
+                    due_date: row.get("due_date")?,
+                    outstanding: row.get("outstanding")?,
+                    days_overdue: 0,
+                })
+            })
+            .map_err(|error| AppError::db_sanitized("payments.open_receivables.query", error))?;
+        let mut receivables = Vec::new();
+        for row in rows {
+            receivables.push(
+                row.map_err(|error| {
+                    AppError::db_sanitized("payments.open_receivables.row", error)
+                })?,
+            );
+        }
+        Ok(receivables)
+    }
+}
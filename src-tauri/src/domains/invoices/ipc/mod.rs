@@ -0,0 +1,237 @@
+//! IPC handlers for the `invoices` domain (ADR-018: thin IPC layer).
+//!
+//! Handlers MUST:
+//!   - receive `correlation_id: Option<String>`
+//!   - call `resolve_context!` as the first line
+//!   - delegate immediately to the service layer
+//!   - contain no business logic
+//!
+//! `invoice_create_from_*` load their source from the owning domain here
+//! (ADR-003 composition) and hand it to `invoice_sources`;
+//! `quote_export_einvoice` loads the quote and its client the same way.
+
+pub(crate) mod payments;
+
+use crate::commands::{AppError, AppResult, AppState};
+use crate::domains::invoices::application::services::invoice_sources;
+use crate::domains::invoices::domain::models::einvoice::{EInvoiceFormat, EInvoiceViolation};
+use crate::domains::invoices::domain::models::invoices::{
+    CreateCreditNoteRequest, CreateInvoiceRequest, Invoice, InvoiceExportResponse,
+    InvoiceListResponse, InvoiceQuery, UpdateInvoiceRequest,
+};
+use crate::domains::invoices::domain::models::payments::InvoiceBalance;
+use crate::resolve_context;
+use crate::shared::contracts::auth::UserRole;
+
+#[tauri::command]
+pub async fn invoice_list(
+    query: Option<InvoiceQuery>,
+    state: AppState<'_>,
+    correlation_id: Option<String>,
+) -> AppResult<InvoiceListResponse> {
+    let ctx = resolve_context!(&state, &correlation_id);
+    state
+        .invoices_service
+        .list(&ctx, query.unwrap_or_default())
+        .await
+}
+
+#[tauri::command]
+pub async fn invoice_get(
+    id: String,
+    state: AppState<'_>,
+    correlation_id: Option<String>,
+) -> AppResult<Invoice> {
+    let ctx = resolve_context!(&state, &correlation_id);
+    state.invoices_service.get(&ctx, &id).await
+}
+
+#[tauri::command]
+pub async fn invoice_create(
+    request: CreateInvoiceRequest,
+    state: AppState<'_>,
+    correlation_id: Option<String>,
+) -> AppResult<Invoice> {
+    let ctx = resolve_context!(&state, &correlation_id, UserRole::Supervisor);
+    state.invoices_service.create(&ctx, request).await
+}
+
+#[tauri::command]
+pub async fn invoice_create_from_quote(
+    quote_id: String,
+    state: AppState<'_>,
+    correlation_id: Option<String>,
+) -> AppResult<Invoice> {
+    let ctx = resolve_context!(&state, &correlation_id, UserRole::Supervisor);
+    let quote = state
+        .quote_service
+        .get_quote(&quote_id)
+        .map_err(|e| AppError::db_sanitized("invoice_create_from_quote.get_quote", &e))?
+        .ok_or_else(|| AppError::NotFound(format!("Quote not found: {}", quote_id)))?;
+    let request = invoice_sources::request_from_quote(&quote)?;
+    state.invoices_service.create(&ctx, request).await
+}
+
+#[tauri::command]
+pub async fn invoice_create_from_intervention(
+    intervention_id: String,
+    state: AppState<'_>,
+    correlation_id: Option<String>,
+) -> AppResult<Invoice> {
+    let ctx = resolve_context!(&state, &correlation_id, UserRole::Supervisor);
+    let intervention = state
+        .intervention_service
+        .get_intervention(&intervention_id)
+        .map_err(|e| {
+            AppError::db_sanitized("invoice_create_from_intervention.get_intervention", &e)
+        })?
+        .ok_or_else(|| {
+            AppError::NotFound(format!("Intervention not found: {}", intervention_id))
+        })?;
+    let consumptions = state
+        .material_service
+        .get_intervention_consumption(&intervention_id)
+        .map_err(|e| AppError::db_sanitized("invoice_create_from_intervention.consumption", &e))?;
+    let materials = state
+        .material_service
+        .get_materials_by_ids(&invoice_sources::consumed_material_ids(&consumptions))
+        .map_err(|e| AppError::db_sanitized("invoice_create_from_intervention.materials", &e))?;
+    let settings = state.invoices_service.billing_settings(&ctx)?;
+    let request = invoice_sources::request_from_intervention(
+        &intervention,
+        &consumptions,
+        &materials,
+        &settings,
+    )?;
+    state.invoices_service.create(&ctx, request).await
+}
+
+#[tauri::command]
+pub async fn invoice_update_draft(
+    id: String,
+    request: UpdateInvoiceRequest,
+    state: AppState<'_>,
+    correlation_id: Option<String>,
+) -> AppResult<Invoice> {
+    let ctx = resolve_context!(&state, &correlation_id, UserRole::Supervisor);
+    state
+        .invoices_service
+        .update_draft(&ctx, &id, request)
+        .await
+}
+
+#[tauri::command]
+pub async fn invoice_delete_draft(
+    id: String,
+    state: AppState<'_>,
+    correlation_id: Option<String>,
+) -> AppResult<()> {
+    let ctx = resolve_context!(&state, &correlation_id, UserRole::Supervisor);
+    state.invoices_service.delete_draft(&ctx, &id).await
+}
+
+#[tauri::command]
+pub async fn invoice_issue(
+    id: String,
+    state: AppState<'_>,
+    correlation_id: Option<String>,
+) -> AppResult<Invoice> {
+    let ctx = resolve_context!(&state, &correlation_id, UserRole::Supervisor);
+    state.invoices_service.issue(&ctx, &id).await
+}
+
+#[tauri::command]
+pub async fn invoice_create_credit_note(
+    request: CreateCreditNoteRequest,
+    state: AppState<'_>,
+    correlation_id: Option<String>,
+) -> AppResult<Invoice> {
+    let ctx = resolve_context!(&state, &correlation_id, UserRole::Supervisor);
+    state
+        .invoices_service
+        .create_credit_note(&ctx, request)
+        .await
+}
+
+#[tauri::command]
+pub async fn invoice_balance(
+    id: String,
+    state: AppState<'_>,
+    correlation_id: Option<String>,
+) -> AppResult<InvoiceBalance> {
+    let ctx = resolve_context!(&state, &correlation_id);
+    state.invoices_service.balance(&ctx, &id).await
+}
+
+#[tauri::command]
+pub async fn invoice_export_pdf(
+    id: String,
+    state: AppState<'_>,
+    correlation_id: Option<String>,
+) -> AppResult<InvoiceExportResponse> {
+    let ctx = resolve_context!(&state, &correlation_id);
+    state
+        .invoices_service
+        .export_pdf(&ctx, &id, state.app_config.app_data_dir.join("invoices"))
+        .await
+}
+
+#[tauri::command]
+pub async fn invoice_einvoice_check(
+    id: String,
+    state: AppState<'_>,
+    correlation_id: Option<String>,
+) -> AppResult<Vec<EInvoiceViolation>> {
+    let ctx = resolve_context!(&state, &correlation_id);
+    state.invoices_service.einvoice_check(&ctx, &id).await
+}
+
+#[tauri::command]
+pub async fn invoice_export_einvoice(
+    id: String,
+    format: EInvoiceFormat,
+    state: AppState<'_>,
+    correlation_id: Option<String>,
+) -> AppResult<InvoiceExportResponse> {
+    let ctx = resolve_context!(&state, &correlation_id);
+    state
+        .invoices_service
+        .export_einvoice(
+            &ctx,
+            &id,
+            format,
+            state.app_config.app_data_dir.join("invoices"),
+        )
+        .await
+}
+
+#[tauri::command]
+pub async fn quote_export_einvoice(
+    quote_id: String,
+    format: EInvoiceFormat,
+    state: AppState<'_>,
+    correlation_id: Option<String>,
+) -> AppResult<InvoiceExportResponse> {
+    let ctx = resolve_context!(&state, &correlation_id);
+    let quote = state
+        .quote_service
+        .get_quote(&quote_id)
+        .map_err(|e| AppError::db_sanitized("quote_export_einvoice.get_quote", &e))?
+        .ok_or_else(|| AppError::NotFound(format!("Quote not found: {}", quote_id)))?;
+    let client = state
+        .client_service
+        .get_client(&quote.client_id)
+        .await
+        .map_err(|e| AppError::db_sanitized("quote_export_einvoice.get_client", &e))?
+        .ok_or_else(|| AppError::NotFound(format!("Client not found: {}", quote.client_id)))?;
+    state
+        .invoices_service
+        .export_quote_einvoice(
+            &ctx,
+            &quote,
+            &client,
+            format,
+            state.app_config.app_data_dir.join("invoices"),
+        )
+        .await
+}
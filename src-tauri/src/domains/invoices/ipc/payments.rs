@@ -0,0 +1,97 @@
+//! IPC handlers for the payments ledger (ADR-018: thin IPC layer).
+//!
+//! Deposit commands load the quote from the quotes domain here (ADR-003
+//! composition) and pass it to `PaymentsService`.
+
+use crate::commands::{AppError, AppResult, AppState};
+use crate::domains::invoices::domain::models::payments::{
+    AgedReceivablesQuery, AgedReceivablesReport, Payment, PaymentListResponse, PaymentQuery,
+    QuoteBalance, RecordPaymentRequest,
+};
+use crate::resolve_context;
+use crate::shared::contracts::auth::UserRole;
+use crate::shared::services::cross_domain::Quote;
+
+fn load_quote(state: &AppState<'_>, quote_id: &str) -> AppResult<Quote> {
+    state
+        .quote_service
+        .get_quote(quote_id)
+        .map_err(|e| AppError::db_sanitized("payments.load_quote", &e))?
+        .ok_or_else(|| AppError::NotFound(format!("Quote not found: {}", quote_id)))
+}
+
+#[tauri::command]
+pub async fn payment_list(
+    query: Option<PaymentQuery>,
+    state: AppState<'_>,
+    correlation_id: Option<String>,
+) -> AppResult<PaymentListResponse> {
+    let ctx = resolve_context!(&state, &correlation_id);
+    state
+        .payments_service
+        .list(&ctx, query.unwrap_or_default())
+        .await
+}
+
+#[tauri::command]
+pub async fn payment_record_deposit(
+    quote_id: String,
+    request: RecordPaymentRequest,
+    state: AppState<'_>,
+    correlation_id: Option<String>,
+) -> AppResult<Payment> {
+    let ctx = resolve_context!(&state, &correlation_id, UserRole::Supervisor);
+    let quote = load_quote(&state, &quote_id)?;
+    state
+        .payments_service
+        .record_deposit(&ctx, &quote, request)
+        .await
+}
+
+#[tauri::command]
+pub async fn payment_record_settlement(
+    invoice_id: String,
+    request: RecordPaymentRequest,
+    state: AppState<'_>,
+    correlation_id: Option<String>,
+) -> AppResult<Payment> {
+    let ctx = resolve_context!(&state, &correlation_id, UserRole::Supervisor);
+    state
+        .payments_service
+        .record_settlement(&ctx, &invoice_id, request)
+        .await
+}
+
+#[tauri::command]
+pub async fn payment_delete(
+    id: String,
+    state: AppState<'_>,
+    correlation_id: Option<String>,
+) -> AppResult<()> {
+    let ctx = resolve_context!(&state, &correlation_id, UserRole::Admin);
+    state.payments_service.delete(&ctx, &id).await
+}
+
+#[tauri::command]
+pub async fn payment_quote_balance(
+    quote_id: String,
+    state: AppState<'_>,
+    correlation_id: Option<String>,
+) -> AppResult<QuoteBalance> {
+    let ctx = resolve_context!(&state, &correlation_id);
+    let quote = load_quote(&state, &quote_id)?;
+    state.payments_service.quote_balance(&ctx, &quote).await
+}
+
+#[tauri::command]
+pub async fn payment_aged_receivables(
+    query: Option<AgedReceivablesQuery>,
+    state: AppState<'_>,
+    correlation_id: Option<String>,
+) -> AppResult<AgedReceivablesReport> {
+    let ctx = resolve_context!(&state, &correlation_id, UserRole::Supervisor);
+    state
+        .payments_service
+        .aged_receivables(&ctx, query.unwrap_or_default())
+        .await
+}
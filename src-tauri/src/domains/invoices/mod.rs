@@ -0,0 +1,11 @@
+mod facade;
+pub(crate) use facade::InvoicesFacade;
+pub(crate) mod application;
+#[cfg(feature = "export-types")]
+pub mod domain;
+#[cfg(not(feature = "export-types"))]
+pub(crate) mod domain;
+pub(crate) mod infrastructure;
+pub(crate) mod ipc;
+#[cfg(test)]
+pub(crate) mod tests;
@@ -0,0 +1,821 @@
+//! Schema tests for the e-invoice writers.
+//!
+//! The generated XML is parsed into a small element tree and checked
+//! against the rules a validator would apply: child order from the CII
+//! D16B and UBL 2.1 XSDs, the mandatory EN 16931 paths, and the BR-CO
+//! arithmetic between lines, VAT breakdown and document totals.
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use regex::Regex;
+
+    use crate::domains::invoices::application::services::invoices_service::InvoicesService;
+    use crate::domains::invoices::domain::models::einvoice::EInvoiceFormat;
+    use crate::domains::invoices::domain::models::invoices::{
+        CreateCreditNoteRequest, Invoice, InvoiceLineKind,
+    };
+    use crate::domains::invoices::tests::support::{
+        accepted_quote, ctx, line, request, setup, CLIENT_ID, SELLER_SIRET, SELLER_VAT,
+    };
+    use crate::shared::services::cross_domain::{Client, CustomerType, QuoteItem, QuoteItemKind};
+
+    #[derive(Debug, Default)]
+    struct Element {
+        name: String,
+        attributes: Vec<(String, String)>,
+        text: String,
+        children: Vec<Element>,
+    }
+
+    impl Element {
+        fn children_named<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Element> {
+            self.children.iter().filter(move |child| child.name == name)
+        }
+
+        fn child(&self, name: &str) -> Option<&Element> {
+            self.children.iter().find(|child| child.name == name)
+        }
+
+        /// Element at a `/`-separated path of child names (first match).
+        fn at(&self, path: &str) -> &Element {
+            let mut element = self;
+            for name in path.split('/') {
+                element = element.child(name).unwrap_or_else(|| {
+                    panic!("{} has no child {} (path {})", element.name, name, path)
+                });
+            }
+            element
+        }
+
+        fn has(&self, path: &str) -> bool {
+            let mut element = self;
+            for name in path.split('/') {
+                match element.child(name) {
+                    Some(child) => element = child,
+                    None => return false,
+                }
+            }
+            true
+        }
+
+        fn text_at(&self, path: &str) -> &str {
+            &self.at(path).text
+        }
+
+        fn cents_at(&self, path: &str) -> i64 {
+            cents(self.text_at(path))
+        }
+
+        fn attribute(&self, name: &str) -> Option<&str> {
+            self.attributes
+                .iter()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.as_str())
+        }
+
+        fn walk<'a>(&'a self, visit: &mut impl FnMut(&'a Element)) {
+            visit(self);
+            for child in &self.children {
+                child.walk(visit);
+            }
+        }
+    }
+
+    fn unescape(text: &str) -> String {
+        text.replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&quot;", "\"")
+            .replace("&apos;", "'")
+            .replace("&amp;", "&")
+    }
+
+    /// Parse the writers' output: declaration, elements, attributes, text.
+    /// Panics on mismatched tags so malformed XML fails the test.
+    fn parse(xml: &str) -> Element {
+        let attribute = Regex::new(r#"([\w:]+)="([^"]*)""#).unwrap();
+        let mut stack = vec![Element::default()];
+        let mut rest = xml;
+        while let Some(start) = rest.find('<') {
+            let text = &rest[..start];
+            if !text.trim().is_empty() {
+                stack.last_mut().unwrap().text.push_str(&unescape(text));
+            }
+            let end = start + rest[start..].find('>').expect("unterminated tag");
+            let tag = &rest[start + 1..end];
+            rest = &rest[end + 1..];
+            if tag.starts_with('?') {
+                continue;
+            }
+            if let Some(name) = tag.strip_prefix('/') {
+                let element = stack.pop().unwrap();
+                assert_eq!(element.name, name, "mismatched closing tag");
+                stack.last_mut().unwrap().children.push(element);
+                continue;
+            }
+            let self_closing = tag.ends_with('/');
+            let tag = tag.trim_end_matches('/');
+            let name = tag.split_whitespace().next().unwrap().to_string();
+            let element = Element {
+                name,
+                attributes: attribute
+                    .captures_iter(tag)
+                    .map(|captures| (captures[1].to_string(), unescape(&captures[2])))
+                    .collect(),
+                ..Default::default()
+            };
+            if self_closing {
+                stack.last_mut().unwrap().children.push(element);
+            } else {
+                stack.push(element);
+            }
+        }
+        assert!(rest.trim().is_empty(), "trailing content after root");
+        assert_eq!(stack.len(), 1, "unclosed elements");
+        let mut document = stack.pop().unwrap();
+        assert_eq!(document.children.len(), 1, "exactly one root element");
+        document.children.remove(0)
+    }
+
+    fn cents(amount: &str) -> i64 {
+        let pattern = Regex::new(r"^-?\d+\.\d{2}$").unwrap();
+        assert!(
+            pattern.is_match(amount),
+            "amount {amount:?} is not xs:decimal with 2 places"
+        );
+        (amount.parse::<f64>().unwrap() * 100.0).round() as i64
+    }
+
+    /// XSD `xs:sequence` per parent: children must be known and in order.
+    const CII_SEQUENCES: &[(&str, &[&str])] = &[
+        (
+            "rsm:CrossIndustryInvoice",
+            &[
+                "rsm:ExchangedDocumentContext",
+                "rsm:ExchangedDocument",
+                "rsm:SupplyChainTradeTransaction",
+            ],
+        ),
+        (
+            "rsm:ExchangedDocument",
+            &[
+                "ram:ID",
+                "ram:TypeCode",
+                "ram:IssueDateTime",
+                "ram:IncludedNote",
+            ],
+        ),
+        (
+            "rsm:SupplyChainTradeTransaction",
+            &[
+                "ram:IncludedSupplyChainTradeLineItem",
+                "ram:ApplicableHeaderTradeAgreement",
+                "ram:ApplicableHeaderTradeDelivery",
+                "ram:ApplicableHeaderTradeSettlement",
+            ],
+        ),
+        (
+            "ram:IncludedSupplyChainTradeLineItem",
+            &[
+                "ram:AssociatedDocumentLineDocument",
+                "ram:SpecifiedTradeProduct",
+                "ram:SpecifiedLineTradeAgreement",
+                "ram:SpecifiedLineTradeDelivery",
+                "ram:SpecifiedLineTradeSettlement",
+            ],
+        ),
+        (
+            "ram:SpecifiedTradeProduct",
+            &[
+                "ram:GlobalID",
+                "ram:SellerAssignedID",
+                "ram:BuyerAssignedID",
+                "ram:Name",
+                "ram:Description",
+            ],
+        ),
+        (
+            "ram:SpecifiedLineTradeSettlement",
+            &[
+                "ram:ApplicableTradeTax",
+                "ram:BillingSpecifiedPeriod",
+                "ram:SpecifiedTradeAllowanceCharge",
+                "ram:SpecifiedTradeSettlementLineMonetarySummation",
+            ],
+        ),
+        (
+            "ram:ApplicableHeaderTradeAgreement",
+            &[
+                "ram:BuyerReference",
+                "ram:SellerTradeParty",
+                "ram:BuyerTradeParty",
+            ],
+        ),
+        (
+            "ram:SellerTradeParty",
+            &[
+                "ram:ID",
+                "ram:GlobalID",
+                "ram:Name",
+                "ram:SpecifiedLegalOrganization",
+                "ram:PostalTradeAddress",
+                "ram:URIUniversalCommunication",
+                "ram:SpecifiedTaxRegistration",
+            ],
+        ),
+        (
+            "ram:BuyerTradeParty",
+            &[
+                "ram:ID",
+                "ram:GlobalID",
+                "ram:Name",
+                "ram:SpecifiedLegalOrganization",
+                "ram:PostalTradeAddress",
+                "ram:URIUniversalCommunication",
+                "ram:SpecifiedTaxRegistration",
+            ],
+        ),
+        (
+            "ram:PostalTradeAddress",
+            &[
+                "ram:PostcodeCode",
+                "ram:LineOne",
+                "ram:LineTwo",
+                "ram:LineThree",
+                "ram:CityName",
+                "ram:CountryID",
+                "ram:CountrySubDivisionName",
+            ],
+        ),
+        (
+            "ram:ApplicableHeaderTradeSettlement",
+            &[
+                "ram:CreditorReferenceID",
+                "ram:PaymentReference",
+                "ram:TaxCurrencyCode",
+                "ram:InvoiceCurrencyCode",
+                "ram:PayeeTradeParty",
+                "ram:SpecifiedTradeSettlementPaymentMeans",
+                "ram:ApplicableTradeTax",
+                "ram:BillingSpecifiedPeriod",
+                "ram:SpecifiedTradeAllowanceCharge",
+                "ram:SpecifiedLogisticsServiceCharge",
+                "ram:SpecifiedTradePaymentTerms",
+                "ram:SpecifiedTradeSettlementHeaderMonetarySummation",
+                "ram:InvoiceReferencedDocument",
+                "ram:ReceivableSpecifiedTradeAccountingAccount",
+            ],
+        ),
+        (
+            "ram:ApplicableTradeTax",
+            &[
+                "ram:CalculatedAmount",
+                "ram:TypeCode",
+                "ram:ExemptionReason",
+                "ram:BasisAmount",
+                "ram:CategoryCode",
+                "ram:ExemptionReasonCode",
+                "ram:DueDateTypeCode",
+                "ram:RateApplicablePercent",
+            ],
+        ),
+        (
+            "ram:SpecifiedTradeAllowanceCharge",
+            &[
+                "ram:ChargeIndicator",
+                "ram:CalculationPercent",
+                "ram:BasisAmount",
+                "ram:ActualAmount",
+                "ram:ReasonCode",
+                "ram:Reason",
+                "ram:CategoryTradeTax",
+            ],
+        ),
+        (
+            "ram:SpecifiedTradeSettlementHeaderMonetarySummation",
+            &[
+                "ram:LineTotalAmount",
+                "ram:ChargeTotalAmount",
+                "ram:AllowanceTotalAmount",
+                "ram:TaxBasisTotalAmount",
+                "ram:TaxTotalAmount",
+                "ram:RoundingAmount",
+                "ram:GrandTotalAmount",
+                "ram:TotalPrepaidAmount",
+                "ram:DuePayableAmount",
+            ],
+        ),
+    ];
+
+    const UBL_DOCUMENT_SEQUENCE: &[&str] = &[
+        "cbc:UBLVersionID",
+        "cbc:CustomizationID",
+        "cbc:ProfileID",
+        "cbc:ID",
+        "cbc:IssueDate",
+        "cbc:DueDate",
+        "cbc:InvoiceTypeCode",
+        "cbc:CreditNoteTypeCode",
+        "cbc:Note",
+        "cbc:TaxPointDate",
+        "cbc:DocumentCurrencyCode",
+        "cbc:BuyerReference",
+        "cac:InvoicePeriod",
+        "cac:OrderReference",
+        "cac:BillingReference",
+        "cac:AccountingSupplierParty",
+        "cac:AccountingCustomerParty",
+        "cac:Delivery",
+        "cac:PaymentMeans",
+        "cac:PaymentTerms",
+        "cac:AllowanceCharge",
+        "cac:TaxTotal",
+        "cac:LegalMonetaryTotal",
+        "cac:InvoiceLine",
+        "cac:CreditNoteLine",
+    ];
+
+    const UBL_SEQUENCES: &[(&str, &[&str])] = &[
+        ("Invoice", UBL_DOCUMENT_SEQUENCE),
+        ("CreditNote", UBL_DOCUMENT_SEQUENCE),
+        (
+            "cac:Party",
+            &[
+                "cac:PartyIdentification",
+                "cac:PartyName",
+                "cac:PostalAddress",
+                "cac:PartyTaxScheme",
+                "cac:PartyLegalEntity",
+                "cac:Contact",
+            ],
+        ),
+        (
+            "cac:PostalAddress",
+            &[
+                "cbc:StreetName",
+                "cbc:AdditionalStreetName",
+                "cbc:CityName",
+                "cbc:PostalZone",
+                "cbc:CountrySubentity",
+                "cac:AddressLine",
+                "cac:Country",
+            ],
+        ),
+        (
+            "cac:AllowanceCharge",
+            &[
+                "cbc:ChargeIndicator",
+                "cbc:AllowanceChargeReasonCode",
+                "cbc:AllowanceChargeReason",
+                "cbc:MultiplierFactorNumeric",
+                "cbc:Amount",
+                "cbc:BaseAmount",
+                "cac:TaxCategory",
+            ],
+        ),
+        (
+            "cac:TaxCategory",
+            &[
+                "cbc:ID",
+                "cbc:Percent",
+                "cbc:TaxExemptionReasonCode",
+                "cbc:TaxExemptionReason",
+                "cac:TaxScheme",
+            ],
+        ),
+        (
+            "cac:LegalMonetaryTotal",
+            &[
+                "cbc:LineExtensionAmount",
+                "cbc:TaxExclusiveAmount",
+                "cbc:TaxInclusiveAmount",
+                "cbc:AllowanceTotalAmount",
+                "cbc:ChargeTotalAmount",
+                "cbc:PrepaidAmount",
+                "cbc:PayableRoundingAmount",
+                "cbc:PayableAmount",
+            ],
+        ),
+        (
+            "cac:InvoiceLine",
+            &[
+                "cbc:ID",
+                "cbc:Note",
+                "cbc:InvoicedQuantity",
+                "cbc:LineExtensionAmount",
+                "cac:Item",
+                "cac:Price",
+            ],
+        ),
+        (
+            "cac:CreditNoteLine",
+            &[
+                "cbc:ID",
+                "cbc:Note",
+                "cbc:CreditedQuantity",
+                "cbc:LineExtensionAmount",
+                "cac:Item",
+                "cac:Price",
+            ],
+        ),
+        (
+            "cac:Item",
+            &[
+                "cbc:Description",
+                "cbc:Name",
+                "cac:SellersItemIdentification",
+                "cac:ClassifiedTaxCategory",
+            ],
+        ),
+    ];
+
+    fn assert_sequences(root: &Element, sequences: &[(&str, &[&str])]) {
+        root.walk(&mut |element| {
+            let Some((_, sequence)) = sequences.iter().find(|(name, _)| *name == element.name)
+            else {
+                return;
+            };
+            let mut last = 0;
+            for child in &element.children {
+                let position = sequence
+                    .iter()
+                    .position(|name| *name == child.name)
+                    .unwrap_or_else(|| panic!("{} is not allowed in {}", child.name, element.name));
+                assert!(
+                    position >= last,
+                    "{} is out of order in {}",
+                    child.name,
+                    element.name
+                );
+                last = position;
+            }
+        });
+    }
+
+    async fn issued_invoice(service: &InvoicesService) -> Invoice {
+        let ctx = ctx();
+        let mut labour = line(10_000, 1.5, 20.0);
+        labour.kind = InvoiceLineKind::Labor;
+        labour.label = "Pose & dépose <capot>".to_string();
+        let mut film = line(2_000, 2.0, 5.5);
+        film.kind = InvoiceLineKind::Material;
+        let mut discount = line(-500, 1.0, 20.0);
+        discount.kind = InvoiceLineKind::Discount;
+        let mut draft = request(vec![labour, film, discount]);
+        draft.discount_amount = Some(1_000);
+        draft.terms = Some("Paiement à 30 jours".to_string());
+        let draft = service.create(&ctx, draft).await.unwrap();
+        service.issue(&ctx, &draft.id).await.unwrap()
+    }
+
+    async fn export(
+        service: &InvoicesService,
+        id: &str,
+        format: EInvoiceFormat,
+        dir: &Path,
+    ) -> Vec<u8> {
+        let response = service
+            .export_einvoice(&ctx(), id, format, dir.to_path_buf())
+            .await
+            .unwrap();
+        std::fs::read(response.file_path).unwrap()
+    }
+
+    #[tokio::test]
+    async fn cii_follows_the_xsd_and_balances() {
+        let (service, _db) = setup().await;
+        let invoice = issued_invoice(&service).await;
+        let dir = tempfile::tempdir().unwrap();
+        let xml = export(&service, &invoice.id, EInvoiceFormat::Cii, dir.path()).await;
+        let root = parse(std::str::from_utf8(&xml).unwrap());
+
+        assert_eq!(root.name, "rsm:CrossIndustryInvoice");
+        assert_sequences(&root, CII_SEQUENCES);
+        assert_eq!(
+            root.text_at("rsm:ExchangedDocumentContext/ram:GuidelineSpecifiedDocumentContextParameter/ram:ID"),
+            "urn:cen.eu:en16931:2017"
+        );
+        let header = root.at("rsm:ExchangedDocument");
+        assert_eq!(
+            header.text_at("ram:ID"),
+            invoice.invoice_number.as_deref().unwrap()
+        );
+        assert_eq!(header.text_at("ram:TypeCode"), "380");
+        let issue_date = header.at("ram:IssueDateTime/udt:DateTimeString");
+        assert_eq!(issue_date.attribute("format"), Some("102"));
+        assert_eq!(issue_date.text.len(), 8);
+
+        let transaction = root.at("rsm:SupplyChainTradeTransaction");
+        let seller = transaction.at("ram:ApplicableHeaderTradeAgreement/ram:SellerTradeParty");
+        assert_eq!(seller.text_at("ram:Name"), "Atelier PPF SAS");
+        assert_eq!(
+            seller.text_at("ram:SpecifiedLegalOrganization/ram:ID"),
+            SELLER_SIRET
+        );
+        assert_eq!(
+            seller.text_at("ram:SpecifiedTaxRegistration/ram:ID"),
+            SELLER_VAT
+        );
+        assert_eq!(seller.text_at("ram:PostalTradeAddress/ram:CountryID"), "FR");
+        let buyer = transaction.at("ram:ApplicableHeaderTradeAgreement/ram:BuyerTradeParty");
+        assert_eq!(buyer.text_at("ram:Name"), "Garage Martin");
+        assert_eq!(buyer.text_at("ram:PostalTradeAddress/ram:CountryID"), "FR");
+
+        let items: Vec<&Element> = transaction
+            .children_named("ram:IncludedSupplyChainTradeLineItem")
+            .collect();
+        assert_eq!(items.len(), 3);
+        assert_eq!(
+            items[0].text_at("ram:SpecifiedTradeProduct/ram:Name"),
+            "Pose & dépose <capot>"
+        );
+        assert_eq!(
+            items[0]
+                .at("ram:SpecifiedLineTradeDelivery/ram:BilledQuantity")
+                .attribute("unitCode"),
+            Some("HUR")
+        );
+        // BR-27: the discount line keeps a positive price.
+        assert_eq!(
+            items[2].cents_at(
+                "ram:SpecifiedLineTradeAgreement/ram:NetPriceProductTradePrice/ram:ChargeAmount"
+            ),
+            500
+        );
+        let line_sum: i64 = items
+            .iter()
+            .map(|item| {
+                item.cents_at("ram:SpecifiedLineTradeSettlement/ram:SpecifiedTradeSettlementLineMonetarySummation/ram:LineTotalAmount")
+            })
+            .sum();
+
+        let settlement = transaction.at("ram:ApplicableHeaderTradeSettlement");
+        assert_eq!(settlement.text_at("ram:InvoiceCurrencyCode"), "EUR");
+        let totals = settlement.at("ram:SpecifiedTradeSettlementHeaderMonetarySummation");
+        let line_total = totals.cents_at("ram:LineTotalAmount");
+        let allowance_total = totals.cents_at("ram:AllowanceTotalAmount");
+        let basis = totals.cents_at("ram:TaxBasisTotalAmount");
+        let tax = totals.cents_at("ram:TaxTotalAmount");
+        let grand = totals.cents_at("ram:GrandTotalAmount");
+        assert_eq!(
+            totals.at("ram:TaxTotalAmount").attribute("currencyID"),
+            Some("EUR")
+        );
+        // BR-CO-10, 11, 13, 14, 15, 16.
+        assert_eq!(line_sum, line_total);
+        let allowances: i64 = settlement
+            .children_named("ram:SpecifiedTradeAllowanceCharge")
+            .map(|allowance| allowance.cents_at("ram:ActualAmount"))
+            .sum();
+        assert_eq!(allowances, allowance_total);
+        assert_eq!(allowance_total, 1_000);
+        assert_eq!(basis, line_total - allowance_total);
+        let breakdown: Vec<&Element> = settlement
+            .children_named("ram:ApplicableTradeTax")
+            .collect();
+        assert_eq!(breakdown.len(), 2);
+        assert_eq!(
+            breakdown
+                .iter()
+                .map(|row| row.cents_at("ram:CalculatedAmount"))
+                .sum::<i64>(),
+            tax
+        );
+        assert_eq!(
+            breakdown
+                .iter()
+                .map(|row| row.cents_at("ram:BasisAmount"))
+                .sum::<i64>(),
+            basis
+        );
+        assert_eq!(grand, basis + tax);
+        assert_eq!(grand, invoice.total);
+        assert_eq!(
+            totals.cents_at("ram:DuePayableAmount"),
+            grand - totals.cents_at("ram:TotalPrepaidAmount")
+        );
+        assert!(settlement.has("ram:SpecifiedTradePaymentTerms/ram:DueDateDateTime"));
+    }
+
+    #[tokio::test]
+    async fn ubl_invoice_follows_the_xsd_and_balances() {
+        let (service, _db) = setup().await;
+        let invoice = issued_invoice(&service).await;
+        let dir = tempfile::tempdir().unwrap();
+        let xml = export(&service, &invoice.id, EInvoiceFormat::Ubl, dir.path()).await;
+        let root = parse(std::str::from_utf8(&xml).unwrap());
+
+        assert_eq!(root.name, "Invoice");
+        assert_sequences(&root, UBL_SEQUENCES);
+        assert_eq!(
+            root.text_at("cbc:CustomizationID"),
+            "urn:cen.eu:en16931:2017"
+        );
+        assert_eq!(root.text_at("cbc:InvoiceTypeCode"), "380");
+        assert!(root.has("cbc:DueDate"));
+        assert_eq!(
+            root.at("cac:AccountingSupplierParty/cac:Party/cac:PartyLegalEntity/cbc:CompanyID")
+                .attribute("schemeID"),
+            Some("0009")
+        );
+        assert_eq!(
+            root.text_at("cac:AccountingCustomerParty/cac:Party/cac:PostalAddress/cac:Country/cbc:IdentificationCode"),
+            "FR"
+        );
+
+        let totals = root.at("cac:LegalMonetaryTotal");
+        let line_sum: i64 = root
+            .children_named("cac:InvoiceLine")
+            .map(|line| line.cents_at("cbc:LineExtensionAmount"))
+            .sum();
+        assert_eq!(line_sum, totals.cents_at("cbc:LineExtensionAmount"));
+        assert_eq!(
+            totals.cents_at("cbc:TaxExclusiveAmount"),
+            totals.cents_at("cbc:LineExtensionAmount")
+                - totals.cents_at("cbc:AllowanceTotalAmount")
+        );
+        let tax = root.cents_at("cac:TaxTotal/cbc:TaxAmount");
+        assert_eq!(
+            root.at("cac:TaxTotal")
+                .children_named("cac:TaxSubtotal")
+                .map(|row| row.cents_at("cbc:TaxAmount"))
+                .sum::<i64>(),
+            tax
+        );
+        assert_eq!(
+            totals.cents_at("cbc:TaxInclusiveAmount"),
+            totals.cents_at("cbc:TaxExclusiveAmount") + tax
+        );
+        assert_eq!(totals.cents_at("cbc:PayableAmount"), invoice.total);
+        root.walk(&mut |element| {
+            if element.name.ends_with("Amount") {
+                assert_eq!(
+                    element.attribute("currencyID"),
+                    Some("EUR"),
+                    "{}",
+                    element.name
+                );
+            }
+        });
+    }
+
+    #[tokio::test]
+    async fn ubl_credit_note_references_the_credited_invoice() {
+        let (service, _db) = setup().await;
+        let invoice = issued_invoice(&service).await;
+        let credit_note = service
+            .create_credit_note(
+                &ctx(),
+                CreateCreditNoteRequest {
+                    invoice_id: invoice.id.clone(),
+                    reason: Some("Erreur de tarif".to_string()),
+                    lines: None,
+                },
+            )
+            .await
+            .unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let xml = export(&service, &credit_note.id, EInvoiceFormat::Ubl, dir.path()).await;
+        let root = parse(std::str::from_utf8(&xml).unwrap());
+
+        assert_eq!(root.name, "CreditNote");
+        assert_sequences(&root, UBL_SEQUENCES);
+        assert_eq!(root.text_at("cbc:CreditNoteTypeCode"), "381");
+        assert!(!root.has("cbc:DueDate"));
+        assert_eq!(
+            root.text_at("cac:BillingReference/cac:InvoiceDocumentReference/cbc:ID"),
+            invoice.invoice_number.as_deref().unwrap()
+        );
+        assert_eq!(root.children_named("cac:CreditNoteLine").count(), 3);
+        assert!(root.has("cac:CreditNoteLine/cbc:CreditedQuantity"));
+        assert_eq!(
+            root.cents_at("cac:LegalMonetaryTotal/cbc:PayableAmount"),
+            credit_note.total
+        );
+
+        let cii = export(&service, &credit_note.id, EInvoiceFormat::Cii, dir.path()).await;
+        let cii = parse(std::str::from_utf8(&cii).unwrap());
+        assert_sequences(&cii, CII_SEQUENCES);
+        assert_eq!(cii.text_at("rsm:ExchangedDocument/ram:TypeCode"), "381");
+        assert_eq!(
+            cii.text_at("rsm:SupplyChainTradeTransaction/ram:ApplicableHeaderTradeSettlement/ram:InvoiceReferencedDocument/ram:IssuerAssignedID"),
+            invoice.invoice_number.as_deref().unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn facturx_is_pdfa3_with_the_cii_attached() {
+        let (service, _db) = setup().await;
+        let invoice = issued_invoice(&service).await;
+        let dir = tempfile::tempdir().unwrap();
+        let pdf = export(&service, &invoice.id, EInvoiceFormat::FacturX, dir.path()).await;
+        let text = String::from_utf8_lossy(&pdf);
+
+        assert!(pdf.starts_with(b"%PDF-1.7"));
+        assert!(text.trim_end().ends_with("%%EOF"));
+        assert!(text.contains("<pdfaid:part>3</pdfaid:part>"));
+        assert!(text.contains("<pdfaid:conformance>B</pdfaid:conformance>"));
+        assert!(text.contains("<fx:DocumentFileName>factur-x.xml</fx:DocumentFileName>"));
+        assert!(text.contains("<fx:ConformanceLevel>EN 16931</fx:ConformanceLevel>"));
+        assert!(text.contains("/AF[5 0 R]"));
+        assert!(text.contains("/AFRelationship/Data"));
+        assert!(text.contains("/Subtype/text#2Fxml"));
+        assert!(text.contains("/FontFile2"));
+        assert!(text.contains("/ID[<"));
+
+        // The attachment is the CII export, byte for byte.
+        let cii = export(&service, &invoice.id, EInvoiceFormat::Cii, dir.path()).await;
+        let start = text.find("<rsm:CrossIndustryInvoice").unwrap();
+        let end = text.find("</rsm:CrossIndustryInvoice>").unwrap();
+        let cii = String::from_utf8(cii).unwrap();
+        assert!(cii.contains(&text[start..end]));
+    }
+
+    #[tokio::test]
+    async fn quotes_export_as_proforma() {
+        let (service, db) = setup().await;
+        let mut quote = accepted_quote(&db, "q-proforma", 24_000);
+        quote.items = vec![QuoteItem {
+            id: "qi-1".to_string(),
+            quote_id: quote.id.clone(),
+            kind: QuoteItemKind::Service,
+            label: "Film PPF intégral".to_string(),
+            description: None,
+            qty: 1.0,
+            unit_price: 20_000,
+            tax_rate: Some(20.0),
+            material_id: None,
+            position: 0,
+            created_at: 0,
+            updated_at: 0,
+        }];
+        let client = Client {
+            id: CLIENT_ID.to_string(),
+            name: "Garage Martin".to_string(),
+            email: None,
+            phone: None,
+            customer_type: CustomerType::Business,
+            address_street: Some("3 avenue Foch".to_string()),
+            address_city: Some("Paris".to_string()),
+            address_state: None,
+            address_zip: Some("75016".to_string()),
+            address_country: Some("France".to_string()),
+            tax_id: None,
+            company_name: Some("Martin Automobiles".to_string()),
+            contact_person: None,
+            notes: None,
+            tags: None,
+            total_tasks: 0,
+            active_tasks: 0,
+            completed_tasks: 0,
+            last_task_date: None,
+            created_at: 0,
+            updated_at: 0,
+            created_by: None,
+            deleted_at: None,
+            deleted_by: None,
+            synced: false,
+            last_synced_at: None,
+        };
+        let dir = tempfile::tempdir().unwrap();
+        let response = service
+            .export_quote_einvoice(
+                &ctx(),
+                &quote,
+                &client,
+                EInvoiceFormat::Cii,
+                dir.path().to_path_buf(),
+            )
+            .await
+            .unwrap();
+        assert!(response
+            .file_path
+            .ends_with("QT-q-proforma-proforma.cii.xml"));
+        let root = parse(&std::fs::read_to_string(response.file_path).unwrap());
+
+        assert_sequences(&root, CII_SEQUENCES);
+        assert_eq!(root.text_at("rsm:ExchangedDocument/ram:TypeCode"), "325");
+        assert_eq!(
+            root.text_at("rsm:ExchangedDocument/ram:ID"),
+            "QT-q-proforma"
+        );
+        let transaction = root.at("rsm:SupplyChainTradeTransaction");
+        assert_eq!(
+            transaction.text_at("ram:ApplicableHeaderTradeAgreement/ram:BuyerTradeParty/ram:Name"),
+            "Martin Automobiles"
+        );
+        assert_eq!(
+            transaction.text_at("ram:ApplicableHeaderTradeAgreement/ram:BuyerTradeParty/ram:PostalTradeAddress/ram:CityName"),
+            "Paris"
+        );
+        let settlement = transaction.at("ram:ApplicableHeaderTradeSettlement");
+        assert!(!settlement.has("ram:SpecifiedTradePaymentTerms/ram:DueDateDateTime"));
+        assert_eq!(
+            settlement.cents_at(
+                "ram:SpecifiedTradeSettlementHeaderMonetarySummation/ram:GrandTotalAmount"
+            ),
+            24_000
+        );
+    }
+}
@@ -0,0 +1,342 @@
+//! Integration tests for the `invoices` domain.
+//!
+//! Service + SQLite repository against an in-memory database.
+
+#[cfg(test)]
+mod tests {
+    use chrono::Datelike;
+
+    use crate::domains::invoices::domain::models::invoices::{
+        CreateCreditNoteRequest, InvoiceKind, InvoiceQuery, InvoiceStatus, UpdateInvoiceRequest,
+    };
+    use crate::domains::invoices::domain::models::payments::{AgedReceivablesQuery, PaymentMethod};
+    use crate::domains::invoices::tests::support::{
+        accepted_quote, ctx, line, payment, request, set_invoice_settings, setup,
+        setup_with_payments,
+    };
+    use crate::shared::error::AppError;
+
+    fn year() -> i32 {
+        chrono::Utc::now()
+            .with_timezone(&chrono_tz::Europe::Paris)
+            .year()
+    }
+
+    #[tokio::test]
+    async fn draft_has_no_number_until_issued() {
+        let (service, _db) = setup().await;
+        let ctx = ctx();
+        let draft = service
+            .create(&ctx, request(vec![line(10_000, 1.0, 20.0)]))
+            .await
+            .unwrap();
+        assert_eq!(draft.status, InvoiceStatus::Draft);
+        assert!(draft.invoice_number.is_none());
+        assert_eq!(draft.total, 12_000);
+
+        let issued = service.issue(&ctx, &draft.id).await.unwrap();
+        assert_eq!(issued.status, InvoiceStatus::Issued);
+        assert_eq!(
+            issued.invoice_number.as_deref(),
+            Some(format!("INV-{}-00001", year()).as_str())
+        );
+        assert_eq!(issued.client_name.as_deref(), Some("Garage Martin"));
+        assert!(issued.issue_date.is_some());
+        assert!(issued.due_date.unwrap() > issued.issue_date.unwrap());
+    }
+
+    #[tokio::test]
+    async fn numbers_follow_issue_order_not_creation_order() {
+        let (service, _db) = setup().await;
+        let ctx = ctx();
+        let first = service
+            .create(&ctx, request(vec![line(1_000, 1.0, 20.0)]))
+            .await
+            .unwrap();
+        let second = service
+            .create(&ctx, request(vec![line(2_000, 1.0, 20.0)]))
+            .await
+            .unwrap();
+
+        let second = service.issue(&ctx, &second.id).await.unwrap();
+        let first = service.issue(&ctx, &first.id).await.unwrap();
+        assert!(second.invoice_number.unwrap().ends_with("-00001"));
+        assert!(first.invoice_number.unwrap().ends_with("-00002"));
+    }
+
+    #[tokio::test]
+    async fn update_draft_replaces_lines_and_recomputes() {
+        let (service, _db) = setup().await;
+        let ctx = ctx();
+        let draft = service
+            .create(&ctx, request(vec![line(10_000, 1.0, 20.0)]))
+            .await
+            .unwrap();
+        let updated = service
+            .update_draft(
+                &ctx,
+                &draft.id,
+                UpdateInvoiceRequest {
+                    discount_amount: Some(1_000),
+                    lines: Some(vec![line(10_000, 1.0, 20.0), line(2_000, 1.0, 5.5)]),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(updated.lines.len(), 2);
+        assert_eq!(updated.subtotal, 11_000);
+
+        let reloaded = service.get(&ctx, &draft.id).await.unwrap();
+        assert_eq!(reloaded.lines.len(), 2);
+        assert_eq!(reloaded.total, updated.total);
+        assert_eq!(reloaded.vat_breakdown.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn full_credit_note_cancels_the_invoice() {
+        let (service, _db) = setup().await;
+        let ctx = ctx();
+        let draft = service
+            .create(&ctx, request(vec![line(10_000, 1.0, 20.0)]))
+            .await
+            .unwrap();
+        let invoice = service.issue(&ctx, &draft.id).await.unwrap();
+
+        let credit_note = service
+            .create_credit_note(
+                &ctx,
+                CreateCreditNoteRequest {
+                    invoice_id: invoice.id.clone(),
+                    reason: Some("Erreur de client".to_string()),
+                    lines: None,
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(credit_note.kind, InvoiceKind::CreditNote);
+        assert_eq!(credit_note.total, invoice.total);
+        assert_eq!(
+            credit_note.invoice_number.as_deref(),
+            Some(format!("AV-{}-00001", year()).as_str())
+        );
+        assert_eq!(
+            service.get(&ctx, &invoice.id).await.unwrap().status,
+            InvoiceStatus::Cancelled
+        );
+    }
+
+    #[tokio::test]
+    async fn partial_credit_note_reduces_amount_due() {
+        let (invoices, payments, _db) = setup_with_payments().await;
+        let ctx = ctx();
+        let draft = invoices
+            .create(&ctx, request(vec![line(10_000, 1.0, 20.0)]))
+            .await
+            .unwrap();
+        let invoice = invoices.issue(&ctx, &draft.id).await.unwrap();
+        payments
+            .record_settlement(&ctx, &invoice.id, payment(PaymentMethod::Card, 9_600))
+            .await
+            .unwrap();
+
+        invoices
+            .create_credit_note(
+                &ctx,
+                CreateCreditNoteRequest {
+                    invoice_id: invoice.id.clone(),
+                    reason: None,
+                    lines: Some(vec![line(2_000, 1.0, 20.0)]),
+                },
+            )
+            .await
+            .unwrap();
+
+        // 12 000 invoiced − 2 400 credited = 9 600 due, already paid.
+        let invoice = invoices.get(&ctx, &invoice.id).await.unwrap();
+        assert_eq!(invoice.status, InvoiceStatus::Paid);
+    }
+
+    #[tokio::test]
+    async fn settlements_drive_invoice_status() {
+        let (invoices, payments, _db) = setup_with_payments().await;
+        let ctx = ctx();
+        let draft = invoices
+            .create(&ctx, request(vec![line(10_000, 1.0, 20.0)]))
+            .await
+            .unwrap();
+        let invoice = invoices.issue(&ctx, &draft.id).await.unwrap();
+
+        payments
+            .record_settlement(&ctx, &invoice.id, payment(PaymentMethod::Cash, 5_000))
+            .await
+            .unwrap();
+        let partial = invoices.get(&ctx, &invoice.id).await.unwrap();
+        assert_eq!(partial.status, InvoiceStatus::PartiallyPaid);
+        assert_eq!(partial.amount_paid, 5_000);
+
+        let cheque = payments
+            .record_settlement(&ctx, &invoice.id, payment(PaymentMethod::Cheque, 7_000))
+            .await
+            .unwrap();
+        let paid = invoices.get(&ctx, &invoice.id).await.unwrap();
+        assert_eq!(paid.status, InvoiceStatus::Paid);
+        assert_eq!(paid.amount_paid, 12_000);
+
+        payments.delete(&ctx, &cheque.id).await.unwrap();
+        let reopened = invoices.get(&ctx, &invoice.id).await.unwrap();
+        assert_eq!(reopened.status, InvoiceStatus::PartiallyPaid);
+        assert_eq!(
+            invoices
+                .balance(&ctx, &invoice.id)
+                .await
+                .unwrap()
+                .outstanding,
+            7_000
+        );
+    }
+
+    #[tokio::test]
+    async fn quote_deposits_count_towards_its_invoice() {
+        let (invoices, payments, db) = setup_with_payments().await;
+        let ctx = ctx();
+        let quote = accepted_quote(&db, "q-deposit", 12_000);
+        payments
+            .record_deposit(&ctx, &quote, payment(PaymentMethod::Transfer, 3_600))
+            .await
+            .unwrap();
+
+        let mut from_quote = request(vec![line(10_000, 1.0, 20.0)]);
+        from_quote.quote_id = Some(quote.id.clone());
+        let draft = invoices.create(&ctx, from_quote).await.unwrap();
+        let issued = invoices.issue(&ctx, &draft.id).await.unwrap();
+        assert_eq!(issued.status, InvoiceStatus::PartiallyPaid);
+        assert_eq!(issued.amount_paid, 3_600);
+
+        let balance = invoices.balance(&ctx, &issued.id).await.unwrap();
+        assert_eq!(balance.outstanding, 8_400);
+    }
+
+    #[tokio::test]
+    async fn deposit_gate_follows_configured_percentage() {
+        let (_invoices, payments, db) = setup_with_payments().await;
+        let ctx = ctx();
+        let quote = accepted_quote(&db, "q-gate", 150_000);
+
+        // No deposit configured: conversion is never blocked.
+        payments.ensure_deposit_paid(&ctx, &quote).await.unwrap();
+
+        set_invoice_settings(&db, r#"{"deposit_percent": 30}"#);
+        let blocked = payments.ensure_deposit_paid(&ctx, &quote).await;
+        assert!(matches!(blocked, Err(AppError::Validation(_))));
+
+        payments
+            .record_deposit(&ctx, &quote, payment(PaymentMethod::Card, 20_000))
+            .await
+            .unwrap();
+        let balance = payments.quote_balance(&ctx, &quote).await.unwrap();
+        assert_eq!(balance.deposit_required, 45_000);
+        assert_eq!(balance.deposit_outstanding, 25_000);
+        assert!(payments.ensure_deposit_paid(&ctx, &quote).await.is_err());
+
+        payments
+            .record_deposit(&ctx, &quote, payment(PaymentMethod::Cash, 25_000))
+            .await
+            .unwrap();
+        payments.ensure_deposit_paid(&ctx, &quote).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn aged_receivables_bucket_open_invoices_per_client() {
+        let (invoices, payments, _db) = setup_with_payments().await;
+        let ctx = ctx();
+        let day = 24 * 60 * 60 * 1000;
+        let now = chrono::Utc::now().timestamp_millis();
+
+        let mut overdue = request(vec![line(10_000, 1.0, 20.0)]);
+        overdue.due_date = Some(now - 45 * day);
+        let overdue = invoices.create(&ctx, overdue).await.unwrap();
+        let overdue = invoices.issue(&ctx, &overdue.id).await.unwrap();
+        payments
+            .record_settlement(&ctx, &overdue.id, payment(PaymentMethod::Card, 2_000))
+            .await
+            .unwrap();
+
+        let mut upcoming = request(vec![line(5_000, 1.0, 20.0)]);
+        upcoming.due_date = Some(now + 10 * day);
+        let upcoming = invoices.create(&ctx, upcoming).await.unwrap();
+        invoices.issue(&ctx, &upcoming.id).await.unwrap();
+
+        let report = payments
+            .aged_receivables(
+                &ctx,
+                AgedReceivablesQuery {
+                    as_of: Some(now),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(report.rows.len(), 1);
+        let row = &report.rows[0];
+        assert_eq!(row.client_name.as_deref(), Some("Garage Martin"));
+        assert_eq!(row.buckets.days_31_60, 10_000);
+        assert_eq!(row.buckets.current, 6_000);
+        assert_eq!(row.buckets.total, 16_000);
+        assert_eq!(row.invoices[0].invoice_id, overdue.id);
+        assert_eq!(report.totals, row.buckets);
+    }
+
+    #[tokio::test]
+    async fn list_filters_by_status() {
+        let (service, _db) = setup().await;
+        let ctx = ctx();
+        let draft = service
+            .create(&ctx, request(vec![line(1_000, 1.0, 20.0)]))
+            .await
+            .unwrap();
+        service
+            .create(&ctx, request(vec![line(2_000, 1.0, 20.0)]))
+            .await
+            .unwrap();
+        service.issue(&ctx, &draft.id).await.unwrap();
+
+        let issued = service
+            .list(
+                &ctx,
+                InvoiceQuery {
+                    status: Some(InvoiceStatus::Issued),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(issued.total, 1);
+        assert_eq!(issued.data[0].id, draft.id);
+
+        let all = service.list(&ctx, InvoiceQuery::default()).await.unwrap();
+        assert_eq!(all.total, 2);
+    }
+
+    #[tokio::test]
+    async fn export_pdf_writes_a_pdf_file() {
+        let (service, _db) = setup().await;
+        let ctx = ctx();
+        let draft = service
+            .create(&ctx, request(vec![line(10_000, 1.0, 20.0)]))
+            .await
+            .unwrap();
+        let invoice = service.issue(&ctx, &draft.id).await.unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let exported = service
+            .export_pdf(&ctx, &invoice.id, dir.path().to_path_buf())
+            .await
+            .unwrap();
+        let bytes = std::fs::read(&exported.file_path).unwrap();
+        assert!(bytes.starts_with(b"%PDF-"));
+        assert!(exported
+            .file_path
+            .ends_with(&format!("{}.pdf", invoice.invoice_number.unwrap())));
+    }
+}
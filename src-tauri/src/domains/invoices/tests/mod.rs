@@ -0,0 +1,151 @@
+#[cfg(test)]
+pub(crate) mod einvoice_schema;
+#[cfg(test)]
+pub(crate) mod integration;
+#[cfg(test)]
+pub(crate) mod permission;
+#[cfg(test)]
+pub(crate) mod unit;
+#[cfg(test)]
+pub(crate) mod validation;
+
+#[cfg(test)]
+pub(crate) mod support {
+    //! Shared fixtures: an in-memory database with one client, an
+    //! organization complete enough for EN 16931, and an `InvoicesService`
+    //! wired to a real `SettingsService`.
+
+    use std::sync::Arc;
+
+    use crate::db::Database;
+    use crate::domains::invoices::application::services::invoices_service::InvoicesService;
+    use crate::domains::invoices::application::services::payments_service::PaymentsService;
+    use crate::domains::invoices::domain::models::invoices::{
+        CreateInvoiceRequest, InvoiceLineInput, InvoiceLineKind,
+    };
+    use crate::domains::invoices::domain::models::payments::{PaymentMethod, RecordPaymentRequest};
+    use crate::domains::settings::infrastructure::{
+        AppSettingsRepository, OrganizationRepository, SettingsRepository,
+        SqliteSettingsRepository, SqliteUserSettingsRepository, UserSettingsPort,
+    };
+    use crate::shared::context::RequestContext;
+    use crate::shared::services::cross_domain::{Quote, QuoteStatus, SettingsService};
+
+    pub const CLIENT_ID: &str = "client-invoices";
+    pub const SELLER_SIRET: &str = "73282932000074";
+    pub const SELLER_VAT: &str = "FR44732829320";
+
+    pub async fn setup() -> (InvoicesService, Arc<Database>) {
+        let db = Arc::new(Database::new_in_memory().await.expect("in-memory database"));
+        let settings_service = Arc::new(SettingsService::new(
+            Arc::new(SqliteSettingsRepository::new(db.clone())) as Arc<dyn AppSettingsRepository>,
+            Arc::new(SqliteUserSettingsRepository::new(db.clone())) as Arc<dyn UserSettingsPort>,
+            Arc::new(OrganizationRepository::new(db.clone())) as Arc<dyn SettingsRepository>,
+        ));
+
+        let now = chrono::Utc::now().timestamp_millis();
+        db.execute(
+            r#"INSERT INTO clients (id, name, email, customer_type, total_tasks, active_tasks, completed_tasks, created_at, updated_at, synced)
+               VALUES (?, 'Garage Martin', 'martin@example.com', 'individual', 0, 0, 0, ?, ?, 0)"#,
+            rusqlite::params![CLIENT_ID, now, now],
+        )
+        .expect("insert test client");
+        db.execute(
+            r#"INSERT INTO organizations (id, name, legal_name, siret, tax_id, email, address_street, address_zip, address_city, address_country)
+               VALUES ('default', 'Atelier PPF', 'Atelier PPF SAS', ?1, ?2, 'contact@atelier-ppf.fr', '12 rue des Carrossiers', '69007', 'Lyon', 'France')"#,
+            rusqlite::params![SELLER_SIRET, SELLER_VAT],
+        )
+        .expect("insert test organization");
+
+        (InvoicesService::new(db.clone(), settings_service), db)
+    }
+
+    pub async fn setup_with_payments() -> (Arc<InvoicesService>, PaymentsService, Arc<Database>) {
+        let (invoices, db) = setup().await;
+        let invoices = Arc::new(invoices);
+        let payments = PaymentsService::new(db.clone(), invoices.clone());
+        (invoices, payments, db)
+    }
+
+    /// Store `organizations.invoice_settings` so billing settings are read
+    /// from it instead of the built-in defaults.
+    pub fn set_invoice_settings(db: &Database, json: &str) {
+        db.execute(
+            "INSERT INTO organizations (id, name, invoice_settings) VALUES ('default', 'Atelier PPF', ?1)
+             ON CONFLICT(id) DO UPDATE SET invoice_settings = excluded.invoice_settings",
+            rusqlite::params![json],
+        )
+        .expect("store invoice settings");
+    }
+
+    /// Insert an accepted quote row and return the matching model.
+    pub fn accepted_quote(db: &Database, id: &str, total: i64) -> Quote {
+        let now = chrono::Utc::now().timestamp_millis();
+        db.execute(
+            r#"INSERT INTO quotes (id, quote_number, client_id, status, total, created_at, updated_at)
+               VALUES (?1, ?2, ?3, 'accepted', ?4, ?5, ?5)"#,
+            rusqlite::params![id, format!("QT-{}", id), CLIENT_ID, total, now],
+        )
+        .expect("insert test quote");
+        Quote {
+            id: id.to_string(),
+            quote_number: format!("QT-{}", id),
+            client_id: CLIENT_ID.to_string(),
+            task_id: None,
+            status: QuoteStatus::Accepted,
+            valid_until: None,
+            description: None,
+            notes: None,
+            terms: None,
+            subtotal: total,
+            tax_total: 0,
+            total,
+            discount_type: None,
+            discount_value: None,
+            discount_amount: None,
+            vehicle_plate: None,
+            vehicle_make: None,
+            vehicle_model: None,
+            vehicle_year: None,
+            vehicle_vin: None,
+            created_at: now,
+            updated_at: now,
+            created_by: None,
+            items: Vec::new(),
+        }
+    }
+
+    pub fn payment(method: PaymentMethod, amount: i64) -> RecordPaymentRequest {
+        RecordPaymentRequest {
+            method,
+            amount,
+            paid_at: None,
+            reference: None,
+            notes: None,
+        }
+    }
+
+    pub fn ctx() -> RequestContext {
+        RequestContext::unauthenticated("invoices-test".to_string())
+    }
+
+    pub fn line(unit_price: i64, qty: f64, tax_rate: f64) -> InvoiceLineInput {
+        InvoiceLineInput {
+            kind: InvoiceLineKind::Service,
+            label: "Pose film capot".to_string(),
+            description: None,
+            qty,
+            unit_price,
+            tax_rate: Some(tax_rate),
+            material_id: None,
+        }
+    }
+
+    pub fn request(lines: Vec<InvoiceLineInput>) -> CreateInvoiceRequest {
+        CreateInvoiceRequest {
+            client_id: CLIENT_ID.to_string(),
+            lines,
+            ..Default::default()
+        }
+    }
+}
@@ -0,0 +1,95 @@
+//! RBAC / permission tests for the `invoices` domain (ADR-006).
+//!
+//! Reads and PDF export are open to every authenticated role; anything that
+//! creates, changes or issues a fiscal document or records money needs
+//! Supervisor or Admin. Deleting a payment is Admin only.
+
+#[cfg(test)]
+mod tests {
+    use crate::shared::auth_middleware::AuthMiddleware;
+    use crate::shared::contracts::auth::UserRole;
+
+    /// `(command, minimum role passed to resolve_context!)`.
+    const COMMANDS: &[(&str, Option<UserRole>)] = &[
+        ("invoice_list", None),
+        ("invoice_get", None),
+        ("invoice_export_pdf", None),
+        ("invoice_einvoice_check", None),
+        ("invoice_export_einvoice", None),
+        ("quote_export_einvoice", None),
+        ("invoice_balance", None),
+        ("payment_list", None),
+        ("payment_quote_balance", None),
+        ("invoice_create", Some(UserRole::Supervisor)),
+        ("invoice_create_from_quote", Some(UserRole::Supervisor)),
+        (
+            "invoice_create_from_intervention",
+            Some(UserRole::Supervisor),
+        ),
+        ("invoice_update_draft", Some(UserRole::Supervisor)),
+        ("invoice_delete_draft", Some(UserRole::Supervisor)),
+        ("invoice_issue", Some(UserRole::Supervisor)),
+        ("invoice_create_credit_note", Some(UserRole::Supervisor)),
+        ("payment_record_deposit", Some(UserRole::Supervisor)),
+        ("payment_record_settlement", Some(UserRole::Supervisor)),
+        ("payment_aged_receivables", Some(UserRole::Supervisor)),
+        ("payment_delete", Some(UserRole::Admin)),
+    ];
+
+    fn allowed(role: &UserRole, required: &Option<UserRole>) -> bool {
+        required
+            .as_ref()
+            .is_none_or(|required| AuthMiddleware::has_permission(role, required))
+    }
+
+    #[test]
+    fn every_role_can_read_and_export() {
+        for (command, required) in COMMANDS.iter().filter(|(_, required)| required.is_none()) {
+            for role in [
+                UserRole::Admin,
+                UserRole::Supervisor,
+                UserRole::Technician,
+                UserRole::Viewer,
+            ] {
+                assert!(allowed(&role, required), "{command} denied for {role:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn only_supervisors_and_admins_write() {
+        for (command, required) in COMMANDS
+            .iter()
+            .filter(|(_, required)| *required == Some(UserRole::Supervisor))
+        {
+            assert!(
+                allowed(&UserRole::Admin, required),
+                "{command} denied for Admin"
+            );
+            assert!(
+                allowed(&UserRole::Supervisor, required),
+                "{command} denied for Supervisor"
+            );
+            assert!(
+                !allowed(&UserRole::Technician, required),
+                "{command} allowed for Technician"
+            );
+            assert!(
+                !allowed(&UserRole::Viewer, required),
+                "{command} allowed for Viewer"
+            );
+        }
+    }
+
+    #[test]
+    fn only_admins_delete_payments() {
+        let required = Some(UserRole::Admin);
+        assert!(allowed(&UserRole::Admin, &required));
+        for role in [UserRole::Supervisor, UserRole::Technician, UserRole::Viewer] {
+            assert!(
+                !allowed(&role, &required),
+                "payment_delete allowed for {role:?}"
+            );
+        }
+    }
+}
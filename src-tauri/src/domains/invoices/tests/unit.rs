@@ -0,0 +1,232 @@
+//! Unit tests for the `invoices` domain.
+//!
+//! Pure domain logic: status machine, totals and e-invoicing identifiers.
+//! No database.
+
+#[cfg(test)]
+mod tests {
+    use crate::domains::invoices::domain::models::einvoice::{
+        country_code, is_valid_fr_vat_number, is_valid_siret,
+    };
+    use crate::domains::invoices::domain::models::invoices::{
+        compute_invoice_totals, InvoiceBillingSettings, InvoiceLine, InvoiceLineKind, InvoiceStatus,
+    };
+
+    fn line(unit_price: i64, qty: f64, tax_rate: f64) -> InvoiceLine {
+        InvoiceLine {
+            id: "line".to_string(),
+            invoice_id: "inv".to_string(),
+            kind: InvoiceLineKind::Service,
+            label: "Ligne".to_string(),
+            description: None,
+            qty,
+            unit_price,
+            tax_rate,
+            line_total: 0,
+            material_id: None,
+            position: 0,
+            created_at: 0,
+        }
+    }
+
+    #[test]
+    fn draft_can_only_be_issued() {
+        assert!(InvoiceStatus::Draft.can_transition_to(&InvoiceStatus::Issued));
+        assert!(!InvoiceStatus::Draft.can_transition_to(&InvoiceStatus::Paid));
+        assert!(!InvoiceStatus::Draft.can_transition_to(&InvoiceStatus::Cancelled));
+    }
+
+    #[test]
+    fn cancelled_is_terminal() {
+        for next in [
+            InvoiceStatus::Draft,
+            InvoiceStatus::Issued,
+            InvoiceStatus::PartiallyPaid,
+            InvoiceStatus::Paid,
+        ] {
+            assert!(!InvoiceStatus::Cancelled.can_transition_to(&next));
+        }
+    }
+
+    #[test]
+    fn status_for_amount_paid() {
+        assert_eq!(
+            InvoiceStatus::for_amount_paid(0, 1000),
+            InvoiceStatus::Issued
+        );
+        assert_eq!(
+            InvoiceStatus::for_amount_paid(400, 1000),
+            InvoiceStatus::PartiallyPaid
+        );
+        assert_eq!(
+            InvoiceStatus::for_amount_paid(1000, 1000),
+            InvoiceStatus::Paid
+        );
+        assert_eq!(
+            InvoiceStatus::for_amount_paid(1200, 1000),
+            InvoiceStatus::Paid
+        );
+    }
+
+    #[test]
+    fn status_round_trips_through_strings() {
+        for status in [
+            InvoiceStatus::Draft,
+            InvoiceStatus::Issued,
+            InvoiceStatus::PartiallyPaid,
+            InvoiceStatus::Paid,
+            InvoiceStatus::Cancelled,
+        ] {
+            assert_eq!(status.to_string().parse::<InvoiceStatus>(), Ok(status));
+        }
+        assert!("sent".parse::<InvoiceStatus>().is_err());
+    }
+
+    #[test]
+    fn vat_is_computed_per_rate_not_per_line() {
+        // Per line: 1.4 ct rounds to 1 ct twice (2 ct). Per rate: 2.8 ct → 3 ct.
+        let totals = compute_invoice_totals(&[line(7, 1.0, 20.0), line(7, 1.0, 20.0)], 0);
+        assert_eq!(totals.subtotal, 14);
+        assert_eq!(totals.tax_total, 3);
+        assert_eq!(totals.vat_breakdown.len(), 1);
+    }
+
+    #[test]
+    fn breakdown_groups_lines_by_rate() {
+        let totals = compute_invoice_totals(
+            &[
+                line(10_000, 1.0, 20.0),
+                line(5_000, 2.0, 20.0),
+                line(2_000, 1.0, 5.5),
+            ],
+            0,
+        );
+        assert_eq!(totals.vat_breakdown.len(), 2);
+        let reduced = &totals.vat_breakdown[0];
+        assert_eq!(reduced.tax_rate, 5.5);
+        assert_eq!(reduced.taxable_amount, 2_000);
+        assert_eq!(reduced.tax_amount, 110);
+        let standard = &totals.vat_breakdown[1];
+        assert_eq!(standard.taxable_amount, 20_000);
+        assert_eq!(standard.tax_amount, 4_000);
+        assert_eq!(totals.subtotal, 22_000);
+        assert_eq!(totals.total, 26_110);
+    }
+
+    #[test]
+    fn discount_is_split_across_rates_and_adds_up() {
+        let totals =
+            compute_invoice_totals(&[line(10_000, 1.0, 20.0), line(5_000, 1.0, 10.0)], 1_000);
+        let shares: i64 = totals
+            .vat_breakdown
+            .iter()
+            .map(|row| row.taxable_amount)
+            .sum();
+        assert_eq!(shares, 14_000);
+        assert_eq!(totals.subtotal, 14_000);
+        assert_eq!(totals.discount_amount, 1_000);
+        // 10 % base: 5000 - 333 = 4667 → 467; 20 % base: 10000 - 667 = 9333 → 1867
+        assert_eq!(totals.tax_total, 467 + 1_867);
+    }
+
+    #[test]
+    fn discount_never_exceeds_gross() {
+        let totals = compute_invoice_totals(&[line(1_000, 1.0, 20.0)], 5_000);
+        assert_eq!(totals.discount_amount, 1_000);
+        assert_eq!(totals.total, 0);
+    }
+
+    #[test]
+    fn billing_settings_fill_missing_keys_with_defaults() {
+        let settings: InvoiceBillingSettings =
+            serde_json::from_str(r#"{"labor_hourly_rate": 6500}"#).unwrap();
+        assert_eq!(settings.labor_hourly_rate, 6_500);
+        assert_eq!(settings.credit_note_prefix, "AV-");
+        assert_eq!(settings.default_tax_rate, 20.0);
+    }
+
+    #[test]
+    fn required_deposit_is_a_rounded_share_of_the_total() {
+        assert_eq!(required_deposit_cents(150_000, 30.0), 45_000);
+        assert_eq!(required_deposit_cents(99_999, 33.33), 33_330);
+        assert_eq!(required_deposit_cents(150_000, 0.0), 0);
+        assert_eq!(required_deposit_cents(150_000, 150.0), 150_000);
+    }
+
+    #[test]
+    fn quote_balance_never_reports_negative_outstanding_deposit() {
+        let balance = QuoteBalance::new("q", 10_000, 30.0, 5_000);
+        assert_eq!(balance.deposit_required, 3_000);
+        assert_eq!(balance.deposit_outstanding, 0);
+        assert_eq!(balance.balance, 5_000);
+    }
+
+    #[test]
+    fn aging_bucket_boundaries() {
+        let mut buckets = AgingBuckets::default();
+        for (days, amount) in [
+            (0, 1),
+            (1, 10),
+            (30, 10),
+            (31, 100),
+            (60, 100),
+            (61, 1_000),
+            (91, 10_000),
+        ] {
+            buckets.add(days, amount);
+        }
+        assert_eq!(buckets.current, 1);
+        assert_eq!(buckets.days_1_30, 20);
+        assert_eq!(buckets.days_31_60, 200);
+        assert_eq!(buckets.days_61_90, 1_000);
+        assert_eq!(buckets.days_over_90, 10_000);
+        assert_eq!(buckets.total, 11_221);
+    }
+
+    #[test]
+    fn aged_receivables_group_by_client_largest_first() {
+        let day = 24 * 60 * 60 * 1000;
+        let receivable =
+            |id: &str, client: &str, due_in_days: i64, outstanding: i64| ReceivableInvoice {
+                invoice_id: id.to_string(),
+                invoice_number: None,
+                client_id: client.to_string(),
+                client_name: None,
+                due_date: Some(due_in_days * day),
+                outstanding,
+                days_overdue: 0,
+            };
+        let report = build_aged_receivables(
+            vec![
+                receivable("a1", "a", -100, 1_000),
+                receivable("b1", "b", -5, 4_000),
+                receivable("a2", "a", 10, 500),
+            ],
+            0,
+        );
+        assert_eq!(report.rows.len(), 2);
+        assert_eq!(report.rows[0].client_id, "b");
+        assert_eq!(report.rows[1].buckets.days_over_90, 1_000);
+        assert_eq!(report.rows[1].buckets.current, 500);
+        assert_eq!(report.rows[1].invoices[0].days_overdue, 100);
+        assert_eq!(report.totals.total, 5_500);
+    }
+
+    #[test]
+    fn siret_and_vat_numbers_are_checked_by_key() {
+        assert!(is_valid_siret("732 829 320 00074"));
+        assert!(!is_valid_siret("73282932000075"));
+        assert!(!is_valid_siret("7328293200007"));
+        assert!(is_valid_fr_vat_number("FR 44 732829320"));
+        assert!(!is_valid_fr_vat_number("FR45732829320"));
+        assert!(!is_valid_fr_vat_number("DE44732829320"));
+    }
+
+    #[test]
+    fn country_names_map_to_iso_codes() {
+        assert_eq!(country_code("France").as_deref(), Some("FR"));
+        assert_eq!(country_code(" belgique ").as_deref(), Some("BE"));
+        assert_eq!(country_code("de").as_deref(), Some("DE"));
+        assert_eq!(country_code("Atlantide"), None);
+    }
+}
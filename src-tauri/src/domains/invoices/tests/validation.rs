@@ -0,0 +1,308 @@
+//! Input validation tests for the `invoices` domain.
+//!
+//! Every rejected request must surface as `AppError::Validation`.
+
+#[cfg(test)]
+mod tests {
+    use crate::domains::invoices::domain::models::einvoice::EInvoiceFormat;
+    use crate::domains::invoices::domain::models::invoices::{
+        CreateCreditNoteRequest, InvoiceLineKind, UpdateInvoiceRequest,
+    };
+    use crate::domains::invoices::domain::models::payments::PaymentMethod;
+    use crate::domains::invoices::tests::support::{
+        accepted_quote, ctx, line, payment, request, set_invoice_settings, setup,
+        setup_with_payments,
+    };
+    use crate::shared::error::AppError;
+    use crate::shared::services::cross_domain::QuoteStatus;
+
+    #[tokio::test]
+    async fn rejects_invoice_without_lines() {
+        let (service, _db) = setup().await;
+        let result = service.create(&ctx(), request(vec![])).await;
+        assert!(matches!(result, Err(AppError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn rejects_blank_label_and_zero_quantity() {
+        let (service, _db) = setup().await;
+        let mut blank = line(1_000, 1.0, 20.0);
+        blank.label = "  ".to_string();
+        let result = service.create(&ctx(), request(vec![blank])).await;
+        assert!(matches!(result, Err(AppError::Validation(_))));
+
+        let result = service
+            .create(&ctx(), request(vec![line(1_000, 0.0, 20.0)]))
+            .await;
+        assert!(matches!(result, Err(AppError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn negative_price_is_only_allowed_on_discount_lines() {
+        let (service, _db) = setup().await;
+        let result = service
+            .create(&ctx(), request(vec![line(-500, 1.0, 20.0)]))
+            .await;
+        assert!(matches!(result, Err(AppError::Validation(_))));
+
+        let mut discount = line(-500, 1.0, 20.0);
+        discount.kind = InvoiceLineKind::Discount;
+        let invoice = service
+            .create(&ctx(), request(vec![line(10_000, 1.0, 20.0), discount]))
+            .await
+            .unwrap();
+        assert_eq!(invoice.subtotal, 9_500);
+    }
+
+    #[tokio::test]
+    async fn rejects_out_of_range_tax_rate() {
+        let (service, _db) = setup().await;
+        let result = service
+            .create(&ctx(), request(vec![line(1_000, 1.0, 120.0)]))
+            .await;
+        assert!(matches!(result, Err(AppError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn rejects_second_invoice_for_the_same_quote() {
+        let (service, db) = setup().await;
+        let now = chrono::Utc::now().timestamp_millis();
+        db.execute(
+            r#"INSERT INTO quotes (id, quote_number, client_id, status, created_at, updated_at)
+               VALUES ('quote-1', 'QT-0001', 'client-invoices', 'accepted', ?, ?)"#,
+            rusqlite::params![now, now],
+        )
+        .expect("insert test quote");
+
+        let mut first = request(vec![line(1_000, 1.0, 20.0)]);
+        first.quote_id = Some("quote-1".to_string());
+        service.create(&ctx(), first.clone()).await.unwrap();
+
+        let result = service.create(&ctx(), first).await;
+        assert!(matches!(result, Err(AppError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn issued_invoices_are_immutable() {
+        let (service, _db) = setup().await;
+        let ctx = ctx();
+        let draft = service
+            .create(&ctx, request(vec![line(1_000, 1.0, 20.0)]))
+            .await
+            .unwrap();
+        service.issue(&ctx, &draft.id).await.unwrap();
+
+        let result = service
+            .update_draft(&ctx, &draft.id, UpdateInvoiceRequest::default())
+            .await;
+        assert!(matches!(result, Err(AppError::Validation(_))));
+        let result = service.issue(&ctx, &draft.id).await;
+        assert!(matches!(result, Err(AppError::Validation(_))));
+        assert!(service.delete_draft(&ctx, &draft.id).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn credit_note_cannot_exceed_invoice() {
+        let (service, _db) = setup().await;
+        let ctx = ctx();
+        let draft = service
+            .create(&ctx, request(vec![line(1_000, 1.0, 20.0)]))
+            .await
+            .unwrap();
+
+        let result = service
+            .create_credit_note(
+                &ctx,
+                CreateCreditNoteRequest {
+                    invoice_id: draft.id.clone(),
+                    ..Default::default()
+                },
+            )
+            .await;
+        assert!(
+            matches!(result, Err(AppError::Validation(_))),
+            "drafts cannot be credited"
+        );
+
+        service.issue(&ctx, &draft.id).await.unwrap();
+        let result = service
+            .create_credit_note(
+                &ctx,
+                CreateCreditNoteRequest {
+                    invoice_id: draft.id.clone(),
+                    reason: None,
+                    lines: Some(vec![line(5_000, 1.0, 20.0)]),
+                },
+            )
+            .await;
+        assert!(matches!(result, Err(AppError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn rejects_non_positive_payment() {
+        let (_invoices, payments, db) = setup_with_payments().await;
+        let quote = accepted_quote(&db, "q-zero", 10_000);
+        let result = payments
+            .record_deposit(&ctx(), &quote, payment(PaymentMethod::Cash, 0))
+            .await;
+        assert!(matches!(result, Err(AppError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn deposits_need_an_accepted_quote_within_its_total() {
+        let (_invoices, payments, db) = setup_with_payments().await;
+        let mut quote = accepted_quote(&db, "q-deposit-rules", 10_000);
+
+        let result = payments
+            .record_deposit(&ctx(), &quote, payment(PaymentMethod::Card, 12_000))
+            .await;
+        assert!(matches!(result, Err(AppError::Validation(_))));
+
+        quote.status = QuoteStatus::Sent;
+        let result = payments
+            .record_deposit(&ctx(), &quote, payment(PaymentMethod::Card, 1_000))
+            .await;
+        assert!(matches!(result, Err(AppError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn deposits_are_refused_once_the_quote_is_invoiced() {
+        let (invoices, payments, db) = setup_with_payments().await;
+        let ctx = ctx();
+        let quote = accepted_quote(&db, "q-invoiced", 12_000);
+        let mut from_quote = request(vec![line(10_000, 1.0, 20.0)]);
+        from_quote.quote_id = Some(quote.id.clone());
+        let draft = invoices.create(&ctx, from_quote).await.unwrap();
+        invoices.issue(&ctx, &draft.id).await.unwrap();
+
+        let result = payments
+            .record_deposit(&ctx, &quote, payment(PaymentMethod::Cash, 1_000))
+            .await;
+        assert!(matches!(result, Err(AppError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn settlements_cannot_exceed_outstanding_or_target_drafts() {
+        let (invoices, payments, _db) = setup_with_payments().await;
+        let ctx = ctx();
+        let draft = invoices
+            .create(&ctx, request(vec![line(1_000, 1.0, 20.0)]))
+            .await
+            .unwrap();
+
+        let result = payments
+            .record_settlement(&ctx, &draft.id, payment(PaymentMethod::Cash, 100))
+            .await;
+        assert!(matches!(result, Err(AppError::Validation(_))));
+
+        invoices.issue(&ctx, &draft.id).await.unwrap();
+        let result = payments
+            .record_settlement(&ctx, &draft.id, payment(PaymentMethod::Cash, 1_201))
+            .await;
+        assert!(matches!(result, Err(AppError::Validation(_))));
+        payments
+            .record_settlement(&ctx, &draft.id, payment(PaymentMethod::Cash, 1_200))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn issue_is_blocked_until_the_seller_is_identified() {
+        let (service, db) = setup().await;
+        let ctx = ctx();
+        db.execute(
+            "UPDATE organizations SET siret = NULL, tax_id = NULL",
+            rusqlite::params![],
+        )
+        .unwrap();
+        let draft = service
+            .create(&ctx, request(vec![line(10_000, 1.0, 20.0)]))
+            .await
+            .unwrap();
+
+        match service.issue(&ctx, &draft.id).await {
+            Err(AppError::Validation(message)) => assert!(message.contains("BR-CO-26")),
+            other => panic!("expected a validation error, got {other:?}"),
+        }
+        let draft = service.get(&ctx, &draft.id).await.unwrap();
+        assert!(draft.status.is_draft());
+        assert!(draft.invoice_number.is_none());
+    }
+
+    #[tokio::test]
+    async fn zero_rated_lines_need_an_exemption_reason() {
+        let (service, db) = setup().await;
+        let ctx = ctx();
+        let draft = service
+            .create(&ctx, request(vec![line(10_000, 1.0, 0.0)]))
+            .await
+            .unwrap();
+        let violations = service.einvoice_check(&ctx, &draft.id).await.unwrap();
+        assert_eq!(
+            violations
+                .iter()
+                .map(|v| v.rule.as_str())
+                .collect::<Vec<_>>(),
+            vec!["BR-E-10"]
+        );
+        assert!(matches!(
+            service.issue(&ctx, &draft.id).await,
+            Err(AppError::Validation(_))
+        ));
+
+        set_invoice_settings(
+            &db,
+            r#"{"vat_exemption_reason": "TVA non applicable, art. 293 B du CGI"}"#,
+        );
+        assert!(service
+            .einvoice_check(&ctx, &draft.id)
+            .await
+            .unwrap()
+            .is_empty());
+        service.issue(&ctx, &draft.id).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn buyer_country_is_read_from_the_client_at_check_time() {
+        let (service, db) = setup().await;
+        let ctx = ctx();
+        db.execute(
+            "UPDATE clients SET address_country = NULL",
+            rusqlite::params![],
+        )
+        .unwrap();
+        let draft = service
+            .create(&ctx, request(vec![line(10_000, 1.0, 20.0)]))
+            .await
+            .unwrap();
+        let violations = service.einvoice_check(&ctx, &draft.id).await.unwrap();
+        assert!(violations.iter().any(|violation| violation.rule == "BR-11"));
+
+        db.execute(
+            "UPDATE clients SET address_country = 'Belgique'",
+            rusqlite::params![],
+        )
+        .unwrap();
+        assert!(service
+            .einvoice_check(&ctx, &draft.id)
+            .await
+            .unwrap()
+            .is_empty());
+        // Checking does not write the snapshot; issuing does.
+        let draft = service.get(&ctx, &draft.id).await.unwrap();
+        assert!(draft.client_country.is_none());
+    }
+
+    #[tokio::test]
+    async fn drafts_cannot_be_exported_as_einvoices() {
+        let (service, _db) = setup().await;
+        let draft = service
+            .create(&ctx(), request(vec![line(10_000, 1.0, 20.0)]))
+            .await
+            .unwrap();
+        let result = service
+            .export_einvoice(&ctx(), &draft.id, EInvoiceFormat::Ubl, std::env::temp_dir())
+            .await;
+        assert!(matches!(result, Err(AppError::Validation(_))));
+    }
+}
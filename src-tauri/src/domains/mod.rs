@@ -13,6 +13,7 @@
 //! - **clients**: Client management, contact info
 //! - **inventory**: Material and inventory tracking
 //! - **quotes**: Quotes and pricing
+//! - **invoices**: Invoices, credit notes, legal numbering
 //! - **calendar**: Calendar events, scheduling
 //! - **settings**: User and system settings
 //! - **documents**: Document management, report generation, exports
@@ -34,6 +35,7 @@ pub mod documents;
 pub mod integrations;
 pub mod interventions;
 pub mod inventory;
+pub mod invoices;
 pub mod notifications;
 pub mod quotes;
 pub mod rules;
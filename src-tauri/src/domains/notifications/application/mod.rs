@@ -1,4 +1,6 @@
 //! Application layer for the notifications domain (ADR-001).
 
+pub use crate::domains::notifications::notification_handler::message_dispatcher::*;
 pub use crate::domains::notifications::notification_handler::message_service::*;
 pub use crate::domains::notifications::notification_handler::notification_service::*;
+pub use crate::domains::notifications::notification_handler::template_engine::*;
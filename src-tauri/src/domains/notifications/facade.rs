@@ -8,18 +8,22 @@ use std::sync::Arc;
 
 use crate::db::Database;
 use crate::shared::ipc::errors::AppError;
+use crate::shared::repositories::base::RepoError;
 use crate::shared::repositories::cache::Cache;
-use crate::shared::services::cross_domain::{UserSettings, UserSettingsRepository};
+use crate::shared::services::cross_domain::{
+    SettingsRepository, UserSettings, UserSettingsRepository,
+};
 
 use crate::shared::services::event_bus::{event_factory, EventPublisher, InMemoryEventBus};
 
 use super::models::{
-    Message, MessageListResponse, MessageQuery, MessageTemplate, Notification,
-    NotificationPreferences, SendMessageRequest, UpdateNotificationPreferencesRequest,
+    Message, MessageListResponse, MessageQuery, MessageTemplate, MessageTemplatePreview,
+    MessageTemplateRequest, MessageType, Notification, NotificationPreferences,
+    PreviewMessageTemplateRequest, SendMessageRequest, UpdateNotificationPreferencesRequest,
 };
 use super::notification_handler::{
-    GetNotificationsResponse, MessageService, NotificationRepository,
-    NotificationTemplateRepository,
+    validate_template, GetNotificationsResponse, MessageService, NotificationRepository,
+    NotificationTemplateRepository, Template, TemplateContextLoader, TemplateEntityRefs,
 };
 
 /// Facade for the Notifications bounded context.
@@ -126,6 +130,14 @@ impl NotificationsFacade {
         Ok(saved)
     }
 
+    /// Ids of the active admin accounts.
+    pub async fn admin_user_ids(&self) -> Result<Vec<String>, AppError> {
+        self.notification_repo()
+            .find_admin_user_ids()
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to find admins: {}", e)))
+    }
+
     /// Construct a `Notification` from its constituent parts and persist it.
     ///
     /// Use this instead of calling `Notification::new()` in IPC handlers.
@@ -193,6 +205,105 @@ impl NotificationsFacade {
             .map_err(|e| AppError::Database(e.to_string()))
     }
 
+    /// Create (`template_id = None`) or update a message template.
+    ///
+    /// Rejects templates that do not parse or use variables outside
+    /// `TEMPLATE_VARIABLES` and the template's own declared `variables`.
+    pub async fn save_message_template(
+        &self,
+        template_id: Option<&str>,
+        request: &MessageTemplateRequest,
+        user_id: &str,
+    ) -> Result<MessageTemplate, AppError> {
+        if request.name.trim().is_empty() {
+            return Err(AppError::Validation(
+                "Template name is required".to_string(),
+            ));
+        }
+        if MessageType::from_str(&request.message_type).is_none() {
+            return Err(AppError::Validation(format!(
+                "Unknown message type '{}'",
+                request.message_type
+            )));
+        }
+        validate_template(
+            request.subject.as_deref(),
+            &request.body,
+            request.variables.as_deref().unwrap_or_default(),
+        )
+        .map_err(|e| AppError::Validation(e.to_string()))?;
+
+        self.template_repo()
+            .save_message_template(template_id, request, user_id)
+            .await
+            .map_err(|e| match e {
+                RepoError::NotFound(msg) => AppError::NotFound(msg),
+                other => AppError::Database(other.to_string()),
+            })
+    }
+
+    /// Render a stored template, or unsaved editor text, against real entities.
+    pub async fn preview_message_template(
+        &self,
+        request: &PreviewMessageTemplateRequest,
+    ) -> Result<MessageTemplatePreview, AppError> {
+        let stored = match request.template_id.as_deref() {
+            Some(template_id) => Some(
+                self.template_repo()
+                    .find_message_template(template_id)
+                    .await
+                    .map_err(|e| AppError::Database(e.to_string()))?
+                    .ok_or_else(|| {
+                        AppError::NotFound(format!("Message template {} not found", template_id))
+                    })?,
+            ),
+            None => None,
+        };
+        let subject = request
+            .subject
+            .clone()
+            .or_else(|| stored.as_ref().and_then(|t| t.subject.clone()));
+        let body = request
+            .body
+            .clone()
+            .or_else(|| stored.as_ref().map(|t| t.body.clone()))
+            .ok_or_else(|| AppError::Validation("Template body is required".to_string()))?;
+
+        let timezone = SettingsRepository::new(self.db.clone())
+            .get_app_settings_db()?
+            .general
+            .timezone;
+        let context = TemplateContextLoader::new(self.db.clone())
+            .load(&TemplateEntityRefs {
+                task_id: request.task_id.clone(),
+                client_id: request.client_id.clone(),
+                quote_id: request.quote_id.clone(),
+            })?
+            .with_timezone(Some(&timezone));
+
+        let parse =
+            |source: &str| Template::parse(source).map_err(|e| AppError::Validation(e.to_string()));
+        let rendered_body = parse(&body)?.render(&context);
+        let rendered_subject = subject
+            .as_deref()
+            .map(parse)
+            .transpose()?
+            .map(|template| template.render(&context));
+
+        let mut missing_variables = rendered_body.missing;
+        if let Some(subject) = &rendered_subject {
+            missing_variables.extend(subject.missing.iter().cloned());
+        }
+        missing_variables.sort();
+        missing_variables.dedup();
+
+        Ok(MessageTemplatePreview {
+            subject: rendered_subject.map(|subject| subject.text),
+            body: rendered_body.text,
+            missing_variables,
+        })
+    }
+
     // STORAGE NOTE: Preferences are stored in the `user_settings` table via
     // UserSettingsRepository, NOT in `notification_preferences`. The separate
     // NotificationPreferencesRepository (notification_preferences table) is registered
@@ -1,6 +1,8 @@
 //! Infrastructure layer for the notifications domain (ADR-001, ADR-005).
 
+pub use crate::domains::notifications::notification_handler::email_transport::*;
 pub use crate::domains::notifications::notification_handler::message_repository::*;
 pub use crate::domains::notifications::notification_handler::notification_repository::*;
 pub use crate::domains::notifications::notification_handler::preferences_repository::*;
+pub use crate::domains::notifications::notification_handler::sms_transport::*;
 pub use crate::domains::notifications::notification_handler::template_repository::*;
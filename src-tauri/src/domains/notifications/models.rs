@@ -525,6 +525,9 @@ pub struct SendMessageRequest {
     pub client_id: Option<String>,
     pub priority: Option<String>,
     pub scheduled_at: Option<i64>,
+    /// Quote whose values the template may reference (`{{quote.total}}`).
+    #[serde(default)]
+    pub quote_id: Option<String>,
     #[serde(default)]
     pub correlation_id: Option<String>,
 }
@@ -558,6 +561,29 @@ pub struct MessageTemplateRequest {
     pub category: Option<String>,
 }
 
+/// Render a stored template, or unsaved editor text, against real entities.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct PreviewMessageTemplateRequest {
+    pub template_id: Option<String>,
+    pub subject: Option<String>,
+    pub body: Option<String>,
+    pub task_id: Option<String>,
+    pub client_id: Option<String>,
+    pub quote_id: Option<String>,
+    #[serde(default)]
+    pub correlation_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct MessageTemplatePreview {
+    pub subject: Option<String>,
+    pub body: String,
+    /// Variables used by the template that had no value for these entities.
+    pub missing_variables: Vec<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[ts(export)]
 pub struct UpdateNotificationPreferencesRequest {
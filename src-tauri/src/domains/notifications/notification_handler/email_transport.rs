@@ -0,0 +1,153 @@
+//! Outbound email transport for queued `email` messages.
+//!
+//! `EmailTransport` is the seam the dispatcher talks to; `SmtpEmailTransport`
+//! is the production implementation, configured from the SMTP fields of the
+//! global `NotificationSettings`.
+
+use async_trait::async_trait;
+use lettre::message::header::ContentType;
+use lettre::message::Mailbox;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Tokio1Executor};
+use std::time::Duration;
+
+use super::message_dispatcher::DeliveryError;
+use crate::shared::services::cross_domain::NotificationSettings;
+
+const DEFAULT_SMTP_PORT: u16 = 587;
+const SMTP_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A fully rendered email ready to hand to a transport.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutgoingEmail {
+    pub to: String,
+    pub subject: String,
+    pub body: String,
+}
+
+#[async_trait]
+pub trait EmailTransport: Send + Sync {
+    /// Deliver one email.
+    async fn send(&self, email: &OutgoingEmail) -> Result<(), DeliveryError>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SmtpSecurity {
+    /// Plain connection upgraded with STARTTLS (port 587).
+    StartTls,
+    /// Implicit TLS from the first byte (port 465).
+    Tls,
+    /// No encryption — local relays and tests only.
+    None,
+}
+
+#[derive(Debug, Clone)]
+pub struct SmtpConfig {
+    pub host: String,
+    pub port: u16,
+    pub security: SmtpSecurity,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub from_address: String,
+    pub from_name: Option<String>,
+}
+
+impl SmtpConfig {
+    /// Build the SMTP config from app settings.
+    ///
+    /// Returns `None` when email notifications are disabled or the host/sender
+    /// are not configured, in which case email rows stay queued.
+    pub fn from_settings(settings: &NotificationSettings) -> Option<Self> {
+        if !settings.email_notifications {
+            return None;
+        }
+        let host = non_empty(settings.smtp_host.as_deref())?;
+        let from_address = non_empty(settings.smtp_from_address.as_deref())?;
+        let security = match settings.smtp_security.as_deref() {
+            Some("tls") => SmtpSecurity::Tls,
+            Some("none") => SmtpSecurity::None,
+            _ => SmtpSecurity::StartTls,
+        };
+        let port = settings.smtp_port.unwrap_or(match security {
+            SmtpSecurity::Tls => 465,
+            SmtpSecurity::StartTls => DEFAULT_SMTP_PORT,
+            SmtpSecurity::None => 25,
+        });
+        Some(Self {
+            host,
+            port,
+            security,
+            username: non_empty(settings.smtp_username.as_deref()),
+            password: settings.smtp_password.clone(),
+            from_address,
+            from_name: non_empty(settings.smtp_from_name.as_deref()),
+        })
+    }
+}
+
+fn non_empty(value: Option<&str>) -> Option<String> {
+    value
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
+pub struct SmtpEmailTransport {
+    mailer: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+}
+
+impl SmtpEmailTransport {
+    pub fn new(config: &SmtpConfig) -> Result<Self, String> {
+        let from = Mailbox::new(
+            config.from_name.clone(),
+            config
+                .from_address
+                .parse()
+                .map_err(|e| format!("Invalid sender address '{}': {}", config.from_address, e))?,
+        );
+        let builder = match config.security {
+            SmtpSecurity::StartTls => {
+                AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&config.host)
+                    .map_err(|e| format!("Invalid SMTP host '{}': {}", config.host, e))?
+            }
+            SmtpSecurity::Tls => AsyncSmtpTransport::<Tokio1Executor>::relay(&config.host)
+                .map_err(|e| format!("Invalid SMTP host '{}': {}", config.host, e))?,
+            SmtpSecurity::None => {
+                AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&config.host)
+            }
+        };
+        let mut builder = builder.port(config.port).timeout(Some(SMTP_TIMEOUT));
+        if let (Some(username), Some(password)) = (&config.username, &config.password) {
+            builder = builder.credentials(Credentials::new(username.clone(), password.clone()));
+        }
+        Ok(Self {
+            mailer: builder.build(),
+            from,
+        })
+    }
+}
+
+#[async_trait]
+impl EmailTransport for SmtpEmailTransport {
+    async fn send(&self, email: &OutgoingEmail) -> Result<(), DeliveryError> {
+        let to: Mailbox = email.to.parse().map_err(|e| {
+            DeliveryError::Permanent(format!("Invalid recipient address '{}': {}", email.to, e))
+        })?;
+        let message = lettre::Message::builder()
+            .from(self.from.clone())
+            .to(to)
+            .subject(email.subject.clone())
+            .header(ContentType::TEXT_PLAIN)
+            .body(email.body.clone())
+            .map_err(|e| DeliveryError::Permanent(format!("Failed to build email: {}", e)))?;
+        self.mailer.send(message).await.map(|_| ()).map_err(|e| {
+            let reason = format!("SMTP delivery failed: {}", e);
+            if e.is_permanent() {
+                DeliveryError::Permanent(reason)
+            } else {
+                DeliveryError::Transient(reason)
+            }
+        })
+    }
+}
@@ -215,6 +215,48 @@ impl DomainEventHandler for NotificationEventHandler {
                     );
                 }
             }
+            DomainEvent::IntegrationDisabled {
+                integration_id,
+                integration_name,
+                reason,
+                ..
+            } => {
+                let title = format!("Intégration désactivée: {}", integration_name);
+                let admin_ids = self.facade.admin_user_ids().await.unwrap_or_else(|e| {
+                    tracing::error!(
+                        event_type = %event_type,
+                        event_id = %event_id,
+                        correlation_id = %correlation_id,
+                        action = "find_admins_for_integration_disabled",
+                        integration_id = %integration_id,
+                        error = %e,
+                        "Failed to handle notification event"
+                    );
+                    Vec::new()
+                });
+                for admin_id in &admin_ids {
+                    if let Err(e) = NotificationHelper::create_integration_disabled(
+                        &self.facade,
+                        admin_id,
+                        integration_id,
+                        &title,
+                        reason,
+                    )
+                    .await
+                    {
+                        tracing::error!(
+                            event_type = %event_type,
+                            event_id = %event_id,
+                            correlation_id = %correlation_id,
+                            action = "create_integration_disabled_notification",
+                            integration_id = %integration_id,
+                            user_id = %admin_id,
+                            error = %e,
+                            "Failed to handle notification event"
+                        );
+                    }
+                }
+            }
             _ => {}
         }
         Ok(())
@@ -229,6 +271,7 @@ impl DomainEventHandler for NotificationEventHandler {
             DomainEvent::QUOTE_ACCEPTED,
             DomainEvent::CLIENT_CREATED,
             DomainEvent::SYSTEM_ERROR,
+            DomainEvent::INTEGRATION_DISABLED,
         ]
     }
 }
@@ -135,6 +135,28 @@ impl NotificationHelper {
         Ok(())
     }
 
+    pub async fn create_integration_disabled(
+        facade: &NotificationsFacade,
+        user_id: &str,
+        integration_id: &str,
+        title: &str,
+        message: &str,
+    ) -> Result<(), String> {
+        facade
+            .create_notification(Notification::new(
+                user_id.to_string(),
+                DomainEvent::SYSTEM_ALERT_NOTIF.to_string(),
+                title.to_string(),
+                message.to_string(),
+                "integration".to_string(),
+                integration_id.to_string(),
+                "/settings/integrations".to_string(),
+            ))
+            .await
+            .map_err(|e| format!("Failed to create notification: {}", e))?;
+        Ok(())
+    }
+
     pub async fn create_system_alert(
         facade: &NotificationsFacade,
         user_id: &str,
@@ -0,0 +1,450 @@
+//! Background delivery of queued outbound messages.
+//!
+//! `MessageService` only persists `email`/`sms` rows with `status = 'pending'`.
+//! `MessageDispatcher::dispatch_pending` is polled from the worker loop in
+//! `main.rs`: it picks due rows via `MessageRepository::find_unsent`, renders the
+//! linked `message_templates` entry, holds messages back during quiet hours and
+//! hands them to the configured email or SMS transport, recording the outcome
+//! on the row.
+
+use std::sync::Arc;
+use tracing::{debug, info, warn};
+
+use crate::commands::AppError;
+use crate::db::Database;
+use crate::domains::notifications::models::{Message, MessageTemplate, MessageType};
+use crate::shared::policies::phone_policy::normalize_e164_phone_number;
+use crate::shared::repositories::cache::Cache;
+use crate::shared::services::cross_domain::{
+    NotificationSettings, SettingsRepository, UserNotificationSettings, UserSettingsRepository,
+};
+
+use super::email_transport::{EmailTransport, OutgoingEmail, SmtpConfig, SmtpEmailTransport};
+use super::message_repository::MessageRepository;
+use super::message_service::is_quiet_hours_at;
+use super::notification_service::NotificationService;
+use super::sms_transport::{
+    count_sms_segments, OutgoingSms, SmsConfig, SmsTransport, DEFAULT_MAX_SMS_SEGMENTS,
+};
+use super::template_engine::{
+    message_entity_refs, render_message, TemplateContext, TemplateContextLoader,
+};
+use super::template_repository::NotificationTemplateRepository;
+
+/// Attempts after which a failed message is left alone.
+pub const DEFAULT_MAX_DELIVERY_ATTEMPTS: i32 = 5;
+
+const RETRY_BASE_DELAY_MS: i64 = 60_000;
+const RETRY_MAX_DELAY_MS: i64 = 6 * 60 * 60 * 1000;
+/// Country code applied to national phone numbers (`06…`).
+const DEFAULT_SMS_COUNTRY_CODE: &str = "33";
+
+/// Why a transport could not deliver a message.
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum DeliveryError {
+    /// Worth retrying later (network error, 4xx SMTP reply, ...).
+    #[error("{0}")]
+    Transient(String),
+    /// Retrying cannot help (bad address, rejected content, ...).
+    #[error("{0}")]
+    Permanent(String),
+}
+
+/// Counters for one dispatcher pass.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DispatchSummary {
+    pub sent: usize,
+    pub failed: usize,
+    pub deferred: usize,
+}
+
+/// Transport-side details of an accepted message.
+#[derive(Debug, Default)]
+struct DeliveryReceipt {
+    segment_count: Option<i32>,
+    provider_message_id: Option<String>,
+}
+
+/// SMS transport plus the settings that shape what is sent through it.
+struct SmsChannel {
+    transport: Arc<dyn SmsTransport>,
+    sender_id: Option<String>,
+    max_segments: u32,
+}
+
+pub struct MessageDispatcher {
+    repository: Arc<MessageRepository>,
+    db: Arc<Database>,
+    cache: Arc<Cache>,
+    email_transport: Option<Arc<dyn EmailTransport>>,
+    sms_transport: Option<Arc<dyn SmsTransport>>,
+}
+
+impl MessageDispatcher {
+    pub fn new(repository: Arc<MessageRepository>, db: Arc<Database>, cache: Arc<Cache>) -> Self {
+        Self {
+            repository,
+            db,
+            cache,
+            email_transport: None,
+            sms_transport: None,
+        }
+    }
+
+    /// Use a fixed email transport instead of building one from SMTP settings.
+    pub fn with_email_transport(mut self, transport: Arc<dyn EmailTransport>) -> Self {
+        self.email_transport = Some(transport);
+        self
+    }
+
+    /// Use a fixed SMS transport instead of building one from gateway settings.
+    pub fn with_sms_transport(mut self, transport: Arc<dyn SmsTransport>) -> Self {
+        self.sms_transport = Some(transport);
+        self
+    }
+
+    pub async fn dispatch_pending(&self, max_attempts: i32) -> Result<DispatchSummary, AppError> {
+        self.dispatch_pending_at(chrono::Utc::now().timestamp_millis(), max_attempts)
+            .await
+    }
+
+    /// One dispatcher pass as of `now_millis`.
+    pub async fn dispatch_pending_at(
+        &self,
+        now_millis: i64,
+        max_attempts: i32,
+    ) -> Result<DispatchSummary, AppError> {
+        let mut summary = DispatchSummary::default();
+        let messages = self
+            .repository
+            .find_unsent(now_millis, max_attempts)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to load pending messages: {}", e)))?;
+        if messages.is_empty() {
+            return Ok(summary);
+        }
+
+        let app_settings = SettingsRepository::new(self.db.clone()).get_app_settings_db()?;
+        let timezone = app_settings.general.timezone.clone();
+        let email_transport = self.resolve_email_transport(&app_settings.notifications);
+        let sms_channel = self.resolve_sms_channel(&app_settings.notifications);
+        let shop_quiet_hours = shop_quiet_hours().await;
+
+        for message in messages {
+            let message_type = match MessageType::from_str(&message.message_type) {
+                Some(message_type @ (MessageType::Email | MessageType::Sms)) => message_type,
+                _ => continue,
+            };
+            let channel_ready = match message_type {
+                MessageType::Email => email_transport.is_some(),
+                _ => sms_channel.is_some(),
+            };
+            if !channel_ready {
+                debug!(message_id = %message.id, message_type = %message_type, "Transport not configured; message stays queued");
+                summary.deferred += 1;
+                continue;
+            }
+
+            let recipient_settings = match message.recipient_id.as_deref() {
+                Some(user_id) => Some(
+                    UserSettingsRepository::new(self.db.clone())
+                        .get_user_settings(user_id)?
+                        .notifications,
+                ),
+                None => None,
+            };
+            let quiet_hours = recipient_settings.as_ref().unwrap_or(&shop_quiet_hours);
+            if is_quiet_hours_at(quiet_hours, Some(&timezone), now_millis) {
+                summary.deferred += 1;
+                continue;
+            }
+
+            let outcome = match (message_type, &email_transport, &sms_channel) {
+                (MessageType::Email, Some(transport), _) => match recipient_settings {
+                    Some(settings) if !settings.email_enabled => Err(DeliveryError::Permanent(
+                        "Recipient has disabled email notifications".to_string(),
+                    )),
+                    _ => match self.build_email(&message, &timezone).await? {
+                        Ok(email) => transport
+                            .send(&email)
+                            .await
+                            .map(|()| DeliveryReceipt::default()),
+                        Err(error) => Err(error),
+                    },
+                },
+                (MessageType::Sms, _, Some(channel)) => {
+                    self.deliver_sms(&message, channel, &timezone).await?
+                }
+                _ => continue,
+            };
+
+            match outcome {
+                Ok(receipt) => {
+                    self.repository
+                        .mark_sent(
+                            &message.id,
+                            now_millis,
+                            receipt.segment_count,
+                            receipt.provider_message_id.as_deref(),
+                        )
+                        .await
+                        .map_err(|e| AppError::Database(e.to_string()))?;
+                    info!(message_id = %message.id, "Message delivered");
+                    summary.sent += 1;
+                }
+                Err(error) => {
+                    self.record_failure(&message, &error, max_attempts, now_millis)
+                        .await?;
+                    summary.failed += 1;
+                }
+            }
+        }
+
+        Ok(summary)
+    }
+
+    fn resolve_email_transport(
+        &self,
+        settings: &NotificationSettings,
+    ) -> Option<Arc<dyn EmailTransport>> {
+        if let Some(transport) = &self.email_transport {
+            return Some(transport.clone());
+        }
+        let config = SmtpConfig::from_settings(settings)?;
+        match SmtpEmailTransport::new(&config) {
+            Ok(transport) => Some(Arc::new(transport)),
+            Err(error) => {
+                warn!(
+                    "SMTP settings are invalid, email delivery paused: {}",
+                    error
+                );
+                None
+            }
+        }
+    }
+
+    fn resolve_sms_channel(&self, settings: &NotificationSettings) -> Option<SmsChannel> {
+        let config = SmsConfig::from_settings(settings);
+        let transport = match (&self.sms_transport, &config) {
+            (Some(transport), _) => transport.clone(),
+            (None, Some(config)) => match config.build_transport() {
+                Ok(transport) => Arc::from(transport),
+                Err(error) => {
+                    warn!("SMS settings are invalid, SMS delivery paused: {}", error);
+                    return None;
+                }
+            },
+            (None, None) => return None,
+        };
+        Some(SmsChannel {
+            transport,
+            sender_id: config.as_ref().and_then(|config| config.sender_id.clone()),
+            max_segments: config
+                .as_ref()
+                .map_or(DEFAULT_MAX_SMS_SEGMENTS, |config| config.max_segments),
+        })
+    }
+
+    /// Render, size-check and send one SMS. The outer error is a storage
+    /// failure; the inner one is a per-message delivery failure.
+    async fn deliver_sms(
+        &self,
+        message: &Message,
+        channel: &SmsChannel,
+        timezone: &str,
+    ) -> Result<Result<DeliveryReceipt, DeliveryError>, AppError> {
+        let phone = match message
+            .recipient_phone
+            .clone()
+            .filter(|p| !p.trim().is_empty())
+        {
+            Some(phone) => Some(phone),
+            None => {
+                let user_phone = match message.recipient_id.as_deref() {
+                    Some(user_id) => self
+                        .repository
+                        .find_user_phone(user_id)
+                        .await
+                        .map_err(|e| AppError::Database(e.to_string()))?,
+                    None => None,
+                };
+                match (user_phone, message.client_id.as_deref()) {
+                    (Some(phone), _) => Some(phone),
+                    (None, Some(client_id)) => self
+                        .repository
+                        .find_client_phone(client_id)
+                        .await
+                        .map_err(|e| AppError::Database(e.to_string()))?,
+                    (None, None) => None,
+                }
+            }
+        };
+        let Some(phone) = phone else {
+            return Ok(Err(DeliveryError::Permanent(
+                "No recipient phone number".to_string(),
+            )));
+        };
+        let to = match normalize_e164_phone_number(&phone, DEFAULT_SMS_COUNTRY_CODE) {
+            Ok(to) => to,
+            Err(error) => {
+                return Ok(Err(DeliveryError::Permanent(format!(
+                    "Invalid recipient phone number '{}': {}",
+                    phone, error
+                ))))
+            }
+        };
+
+        let body = match self.render(message, timezone).await? {
+            Ok((_, body)) => body,
+            Err(error) => return Ok(Err(error)),
+        };
+        let segments = count_sms_segments(&body);
+        if segments.segments > channel.max_segments {
+            return Ok(Err(DeliveryError::Permanent(format!(
+                "SMS body needs {} segments ({:?}, {} units), limit is {}",
+                segments.segments, segments.encoding, segments.units, channel.max_segments
+            ))));
+        }
+
+        let sms = OutgoingSms {
+            to,
+            from: channel.sender_id.clone(),
+            body,
+        };
+        Ok(channel
+            .transport
+            .send(&sms)
+            .await
+            .map(|receipt| DeliveryReceipt {
+                segment_count: Some(segments.segments as i32),
+                provider_message_id: receipt.provider_message_id,
+            }))
+    }
+
+    async fn find_template(&self, message: &Message) -> Result<Option<MessageTemplate>, AppError> {
+        match message.template_id.as_deref() {
+            Some(template_id) => {
+                NotificationTemplateRepository::new(self.db.clone(), self.cache.clone())
+                    .find_message_template(template_id)
+                    .await
+                    .map_err(|e| AppError::Database(e.to_string()))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Subject and body to send: the linked template rendered against current
+    /// task/client/quote data, or the stored text when there is no template.
+    async fn render(
+        &self,
+        message: &Message,
+        timezone: &str,
+    ) -> Result<Result<(String, String), DeliveryError>, AppError> {
+        let template = self.find_template(message).await?;
+        let context = match template {
+            Some(_) => match TemplateContextLoader::new(self.db.clone())
+                .load(&message_entity_refs(message))
+            {
+                Ok(context) => context.with_timezone(Some(timezone)),
+                // The referenced entity was deleted; retrying will not bring it back.
+                Err(AppError::NotFound(reason)) => {
+                    return Ok(Err(DeliveryError::Permanent(reason)))
+                }
+                Err(error) => return Err(error),
+            },
+            None => TemplateContext::default(),
+        };
+        Ok(render_message(message, template.as_ref(), context)
+            .map_err(|e| DeliveryError::Permanent(format!("Invalid message template: {}", e))))
+    }
+
+    /// Render the message into an email. The outer error is a storage failure;
+    /// the inner one is a per-message delivery failure.
+    async fn build_email(
+        &self,
+        message: &Message,
+        timezone: &str,
+    ) -> Result<Result<OutgoingEmail, DeliveryError>, AppError> {
+        let to = match message.recipient_email.clone() {
+            Some(email) if !email.trim().is_empty() => email,
+            _ => match message.recipient_id.as_deref() {
+                Some(user_id) => self
+                    .repository
+                    .find_user_email(user_id)
+                    .await
+                    .map_err(|e| AppError::Database(e.to_string()))?
+                    .unwrap_or_default(),
+                None => String::new(),
+            },
+        };
+        if to.trim().is_empty() {
+            return Ok(Err(DeliveryError::Permanent(
+                "No recipient email address".to_string(),
+            )));
+        }
+
+        Ok(self
+            .render(message, timezone)
+            .await?
+            .map(|(subject, body)| OutgoingEmail { to, subject, body }))
+    }
+
+    async fn record_failure(
+        &self,
+        message: &Message,
+        error: &DeliveryError,
+        max_attempts: i32,
+        now_millis: i64,
+    ) -> Result<(), AppError> {
+        let attempts = self
+            .repository
+            .delivery_attempts(&message.id)
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?
+            + 1;
+        let (attempts, next_attempt_at) = match error {
+            DeliveryError::Transient(_) if attempts < max_attempts => {
+                (attempts, Some(now_millis + retry_delay_ms(attempts)))
+            }
+            _ => (attempts.max(max_attempts), None),
+        };
+        warn!(
+            message_id = %message.id,
+            attempts,
+            will_retry = next_attempt_at.is_some(),
+            "Message delivery failed: {}",
+            error
+        );
+        self.repository
+            .record_failed_attempt(
+                &message.id,
+                &error.to_string(),
+                attempts,
+                next_attempt_at,
+                now_millis,
+            )
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))
+    }
+}
+
+/// Exponential backoff: 1 min, 2 min, 4 min, ... capped at 6 h.
+pub fn retry_delay_ms(attempts: i32) -> i64 {
+    let exponent = attempts.saturating_sub(1).clamp(0, 20) as u32;
+    RETRY_BASE_DELAY_MS
+        .saturating_mul(1_i64 << exponent)
+        .min(RETRY_MAX_DELAY_MS)
+}
+
+/// Quiet hours applied to client-facing messages (no `recipient_id`).
+async fn shop_quiet_hours() -> UserNotificationSettings {
+    let config = NotificationService::global_config()
+        .await
+        .unwrap_or_default();
+    UserNotificationSettings {
+        quiet_hours_enabled: config.quiet_hours_start.is_some() && config.quiet_hours_end.is_some(),
+        quiet_hours_start: config.quiet_hours_start.unwrap_or_default(),
+        quiet_hours_end: config.quiet_hours_end.unwrap_or_default(),
+        ..Default::default()
+    }
+}
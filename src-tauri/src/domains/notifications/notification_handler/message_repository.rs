@@ -165,19 +165,92 @@ impl MessageRepository {
         Ok(messages)
     }
 
-    pub async fn find_unsent(&self) -> RepoResult<Vec<Message>> {
-        let cache_key = self.cache_key_builder.query(&["unsent"]);
-        if let Some(m) = self.cache.get::<Vec<Message>>(&cache_key) {
-            return Ok(m);
-        }
-        let messages = self.db
+    /// Email/SMS rows due for delivery: pending or failed, scheduled time and
+    /// retry backoff elapsed, and fewer than `max_attempts` tries so far.
+    ///
+    /// Not cached — the dispatcher must see rows it updated on the previous tick.
+    pub async fn find_unsent(&self, now_millis: i64, max_attempts: i32) -> RepoResult<Vec<Message>> {
+        self.db
             .query_as::<Message>(
-                "SELECT id, message_type, sender_id, recipient_id, recipient_email, recipient_phone, subject, body, template_id, task_id, client_id, status, priority, scheduled_at, sent_at, read_at, error_message, metadata, created_at, updated_at FROM messages WHERE status IN ('pending', 'failed') AND (scheduled_at IS NULL OR scheduled_at <= strftime('%s', 'now')) ORDER BY priority DESC, created_at ASC LIMIT 50",
-                [],
+                "SELECT id, message_type, sender_id, recipient_id, recipient_email, recipient_phone, subject, body, template_id, task_id, client_id, status, priority, scheduled_at, sent_at, read_at, error_message, metadata, created_at, updated_at FROM messages WHERE status IN ('pending', 'failed') AND message_type != 'in_app' AND attempts < ? AND (scheduled_at IS NULL OR scheduled_at <= ?) AND (next_attempt_at IS NULL OR next_attempt_at <= ?) ORDER BY CASE priority WHEN 'urgent' THEN 0 WHEN 'high' THEN 1 WHEN 'normal' THEN 2 ELSE 3 END, created_at ASC LIMIT 50",
+                params![max_attempts, now_millis, now_millis],
             )
-            .map_err(|e| RepoError::Database(format!("Failed to find unsent: {}", e)))?;
-        self.cache.set(&cache_key, messages.clone(), ttl::SHORT);
-        Ok(messages)
+            .map_err(|e| RepoError::Database(format!("Failed to find unsent: {}", e)))
+    }
+
+    /// Record a successful hand-off to the transport. `segment_count` and
+    /// `provider_message_id` are only known for SMS.
+    pub async fn mark_sent(
+        &self,
+        message_id: &str,
+        sent_at: i64,
+        segment_count: Option<i32>,
+        provider_message_id: Option<&str>,
+    ) -> RepoResult<()> {
+        self.db
+            .execute(
+                "UPDATE messages SET status='sent', sent_at=?, error_message=NULL, attempts=attempts+1, next_attempt_at=NULL, segment_count=?, provider_message_id=?, updated_at=? WHERE id=?",
+                params![sent_at, segment_count, provider_message_id, sent_at, message_id],
+            )
+            .map_err(|e| RepoError::Database(format!("Failed to mark message sent: {}", e)))?;
+        self.cache.remove(&self.cache_key_builder.id(message_id));
+        Ok(())
+    }
+
+    pub async fn delivery_attempts(&self, message_id: &str) -> RepoResult<i32> {
+        self.db
+            .query_single_value(
+                "SELECT attempts FROM messages WHERE id = ?",
+                params![message_id],
+            )
+            .map_err(|e| RepoError::Database(format!("Failed to read attempts: {}", e)))
+    }
+
+    /// Park a message as failed. `next_attempt_at` of `None` leaves it to the
+    /// `attempts < max` filter in [`Self::find_unsent`] to stop retrying.
+    pub async fn record_failed_attempt(
+        &self,
+        message_id: &str,
+        error_message: &str,
+        attempts: i32,
+        next_attempt_at: Option<i64>,
+        now_millis: i64,
+    ) -> RepoResult<()> {
+        self.db
+            .execute(
+                "UPDATE messages SET status='failed', error_message=?, attempts=?, next_attempt_at=?, updated_at=? WHERE id=?",
+                params![error_message, attempts, next_attempt_at, now_millis, message_id],
+            )
+            .map_err(|e| RepoError::Database(format!("Failed to record delivery failure: {}", e)))?;
+        self.cache.remove(&self.cache_key_builder.id(message_id));
+        Ok(())
+    }
+
+    pub async fn find_user_email(&self, user_id: &str) -> RepoResult<Option<String>> {
+        self.db
+            .query_single_value::<Option<String>>(
+                "SELECT (SELECT email FROM users WHERE id = ?)",
+                params![user_id],
+            )
+            .map_err(|e| RepoError::Database(format!("Failed to find recipient email: {}", e)))
+    }
+
+    pub async fn find_user_phone(&self, user_id: &str) -> RepoResult<Option<String>> {
+        self.db
+            .query_single_value::<Option<String>>(
+                "SELECT (SELECT phone FROM users WHERE id = ?)",
+                params![user_id],
+            )
+            .map_err(|e| RepoError::Database(format!("Failed to find recipient phone: {}", e)))
+    }
+
+    pub async fn find_client_phone(&self, client_id: &str) -> RepoResult<Option<String>> {
+        self.db
+            .query_single_value::<Option<String>>(
+                "SELECT (SELECT phone FROM clients WHERE id = ? AND deleted_at IS NULL)",
+                params![client_id],
+            )
+            .map_err(|e| RepoError::Database(format!("Failed to find client phone: {}", e)))
     }
 
     pub async fn update_status(&self, message_id: &str, status: MessageStatus) -> RepoResult<()> {
@@ -12,12 +12,16 @@ use crate::domains::notifications::models::{
 use crate::shared::contracts::notification::{NotificationSender, SentMessage};
 use crate::shared::repositories::base::Repository;
 use crate::shared::repositories::cache::Cache;
-use crate::shared::services::cross_domain::{UserNotificationSettings, UserSettingsRepository};
+use crate::shared::services::cross_domain::{
+    SettingsRepository, UserNotificationSettings, UserSettingsRepository,
+};
 
 use crate::shared::services::event_bus::{event_factory, EventPublisher, InMemoryEventBus};
 
 use super::message_repository::{MessageRepoQuery, MessageRepository};
 use super::notification_repository::NotificationRepository;
+use super::template_engine::{message_entity_refs, render_message, TemplateContextLoader};
+use super::template_repository::NotificationTemplateRepository;
 
 #[derive(Clone)]
 pub struct MessageService {
@@ -53,7 +57,14 @@ impl MessageService {
     ) -> Result<Message, AppError> {
         let id = format!("{:x}", rand::random::<u128>());
         let now = chrono::Utc::now().timestamp_millis();
-        let message = Message {
+        let mut metadata = serde_json::Map::new();
+        if let Some(kind) = notification_kind {
+            metadata.insert("notification_kind".to_string(), kind.into());
+        }
+        if let Some(quote_id) = request.quote_id.as_deref() {
+            metadata.insert("quote_id".to_string(), quote_id.into());
+        }
+        let mut message = Message {
             id,
             message_type: request.message_type.clone(),
             sender_id: None,
@@ -74,18 +85,24 @@ impl MessageService {
             sent_at: None,
             read_at: None,
             error_message: None,
-            metadata: notification_kind
-                .map(|kind| format!(r#"{{"notification_kind":"{}"}}"#, kind)),
+            metadata: (!metadata.is_empty())
+                .then(|| serde_json::Value::Object(metadata).to_string()),
             created_at: now,
             updated_at: now,
         };
+        self.apply_template(&mut message).await?;
         let saved = self.repository.save(message).await.map_err(|e| {
             error!("Failed to save message: {}", e);
             AppError::Database("Failed to save message".to_string())
         })?;
 
         if request.message_type == "in_app" {
-            self.store_in_app_notification(request, notification_kind)
+            let rendered = SendMessageRequest {
+                subject: saved.subject.clone(),
+                body: saved.body.clone(),
+                ..request.clone()
+            };
+            self.store_in_app_notification(&rendered, notification_kind)
                 .await?;
         }
 
@@ -93,6 +110,50 @@ impl MessageService {
         Ok(saved)
     }
 
+    /// Replace subject/body with the linked template rendered against the
+    /// referenced task/client/quote, so the stored row shows what the
+    /// recipient reads. The dispatcher renders again at delivery time.
+    async fn apply_template(&self, message: &mut Message) -> Result<(), AppError> {
+        let Some(template_id) = message.template_id.as_deref() else {
+            return Ok(());
+        };
+        let template = NotificationTemplateRepository::new(self.db.clone(), self.cache.clone())
+            .find_message_template(template_id)
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?
+            .ok_or_else(|| {
+                AppError::NotFound(format!("Message template {} not found", template_id))
+            })?;
+        // Keep the caller's text for `{{subject}}`/`{{body}}` so re-rendering
+        // at delivery does not nest the template in itself.
+        let mut metadata = message
+            .metadata
+            .as_deref()
+            .and_then(|raw| serde_json::from_str::<serde_json::Value>(raw).ok())
+            .and_then(|value| value.as_object().cloned())
+            .unwrap_or_default();
+        metadata
+            .entry("body")
+            .or_insert_with(|| message.body.clone().into());
+        if let Some(subject) = message.subject.as_deref() {
+            metadata.entry("subject").or_insert_with(|| subject.into());
+        }
+        message.metadata = Some(serde_json::Value::Object(metadata).to_string());
+
+        let timezone = SettingsRepository::new(self.db.clone())
+            .get_app_settings_db()?
+            .general
+            .timezone;
+        let context = TemplateContextLoader::new(self.db.clone())
+            .load(&message_entity_refs(message))?
+            .with_timezone(Some(&timezone));
+        let (subject, body) = render_message(message, Some(&template), context)
+            .map_err(|e| AppError::Validation(format!("Invalid message template: {}", e)))?;
+        message.subject = Some(subject);
+        message.body = body;
+        Ok(())
+    }
+
     async fn store_in_app_notification(
         &self,
         request: &SendMessageRequest,
@@ -188,6 +249,7 @@ impl MessageService {
                 client_id,
                 priority,
                 scheduled_at,
+                quote_id: None,
                 correlation_id,
             },
             notification_kind.as_deref(),
@@ -1,21 +1,29 @@
 //! Unified notifications handler: repositories, services, helpers, and IPC commands.
 
+pub mod email_transport;
 pub mod event_handler;
 pub mod helper;
+pub mod message_dispatcher;
 pub mod message_repository;
 pub mod message_service;
 pub mod notification_repository;
 pub mod notification_service;
 pub mod preferences_repository;
+pub mod sms_transport;
+pub mod template_engine;
 pub mod template_repository;
 
+pub use email_transport::*;
 pub use event_handler::*;
 pub use helper::*;
+pub use message_dispatcher::*;
 pub use message_repository::*;
 pub use message_service::*;
 pub use notification_repository::*;
 pub use notification_service::*;
 pub use preferences_repository::*;
+pub use sms_transport::*;
+pub use template_engine::*;
 pub use template_repository::*;
 
 use serde::{Deserialize, Serialize};
@@ -24,6 +32,7 @@ use ts_rs::TS;
 
 use crate::commands::{ApiResponse, AppError, AppState};
 use crate::resolve_context;
+use crate::shared::contracts::auth::UserRole;
 
 use super::models::*;
 
@@ -133,6 +142,34 @@ pub async fn message_get_templates(
     Ok(ApiResponse::success(templates).with_correlation_id(Some(ctx.correlation_id)))
 }
 
+#[tracing::instrument(skip_all)]
+#[tauri::command]
+pub async fn message_save_template(
+    template_id: Option<String>,
+    request: MessageTemplateRequest,
+    correlation_id: Option<String>,
+    state: AppState<'_>,
+) -> Result<ApiResponse<MessageTemplate>, AppError> {
+    let ctx = resolve_context!(&state, &correlation_id, UserRole::Supervisor);
+    let template = notifications_facade(&state)
+        .save_message_template(template_id.as_deref(), &request, &ctx.auth.user_id)
+        .await?;
+    Ok(ApiResponse::success(template).with_correlation_id(Some(ctx.correlation_id)))
+}
+
+#[tracing::instrument(skip_all)]
+#[tauri::command]
+pub async fn message_preview_template(
+    request: PreviewMessageTemplateRequest,
+    state: AppState<'_>,
+) -> Result<ApiResponse<MessageTemplatePreview>, AppError> {
+    let ctx = resolve_context!(&state, &request.correlation_id);
+    let preview = notifications_facade(&state)
+        .preview_message_template(&request)
+        .await?;
+    Ok(ApiResponse::success(preview).with_correlation_id(Some(ctx.correlation_id)))
+}
+
 #[tracing::instrument(skip_all)]
 #[tauri::command]
 pub async fn message_get_preferences(
@@ -74,6 +74,17 @@ impl NotificationRepository {
         Ok(())
     }
 
+    /// Ids of the active admin accounts, for alerts addressed to admins.
+    pub async fn find_admin_user_ids(&self) -> RepoResult<Vec<String>> {
+        self.db
+            .query_multiple(
+                "SELECT id FROM users WHERE role = 'admin' AND is_active = 1 AND deleted_at IS NULL",
+                [],
+                |row| row.get(0),
+            )
+            .map_err(|e| RepoError::Database(format!("Failed to find admins: {}", e)))
+    }
+
     pub async fn delete(&self, id: &str) -> RepoResult<bool> {
         let result = self
             .db
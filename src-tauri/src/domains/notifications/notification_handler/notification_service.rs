@@ -9,7 +9,8 @@
 //! - `message_service::MessageService` — multi-channel message dispatch
 //!
 //! The `lazy_static` singleton is initialized at startup via the
-//! `initialize_notification_service` IPC command and read by `get_notification_status`.
+//! `initialize_notification_service` IPC command and read by `get_notification_status`
+//! and by the outbound `MessageDispatcher` for client-facing quiet hours.
 
 use lazy_static::lazy_static;
 use std::sync::Arc;
@@ -78,6 +79,15 @@ impl NotificationService {
         *NOTIFICATION_SERVICE.lock().await = Some(service);
     }
 
+    /// Quiet-hours config of the process-wide service, if it has been initialized.
+    pub async fn global_config() -> Option<NotificationConfig> {
+        let service = NOTIFICATION_SERVICE.lock().await.clone();
+        match service {
+            Some(service) => Some(service.get_config().await),
+            None => None,
+        }
+    }
+
     /// Return the status of the process-wide notification service as JSON.
     pub async fn get_global_status() -> serde_json::Value {
         if NOTIFICATION_SERVICE.lock().await.is_some() {
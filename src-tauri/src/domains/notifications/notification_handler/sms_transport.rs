@@ -0,0 +1,322 @@
+//! Outbound SMS transport for queued `sms` messages.
+//!
+//! `SmsTransport` is the seam the dispatcher talks to. Two implementations are
+//! provided, selected by `NotificationSettings::sms_transport`:
+//! - `HttpSmsTransport` — generic REST gateway (URL, auth header and JSON body
+//!   are templates, so most French/EU providers fit without code changes)
+//! - `FileSmsTransport` — appends one JSON line per SMS to a local file, for
+//!   offline testing and demos
+
+use async_trait::async_trait;
+use reqwest::{Client, StatusCode};
+use serde::Serialize;
+use std::path::PathBuf;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+
+use super::message_dispatcher::DeliveryError;
+use crate::shared::services::cross_domain::NotificationSettings;
+
+const HTTP_TIMEOUT: Duration = Duration::from_secs(30);
+/// Reminders longer than this are almost always a template mistake.
+pub const DEFAULT_MAX_SMS_SEGMENTS: u32 = 3;
+const DEFAULT_BODY_TEMPLATE: &str = r#"{"to":"{{to}}","from":"{{from}}","text":"{{body}}"}"#;
+
+/// A rendered SMS ready to hand to a transport. `to` is E.164.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct OutgoingSms {
+    pub to: String,
+    pub from: Option<String>,
+    pub body: String,
+}
+
+/// What the gateway told us about an accepted SMS.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SmsReceipt {
+    pub provider_message_id: Option<String>,
+}
+
+#[async_trait]
+pub trait SmsTransport: Send + Sync {
+    /// Deliver one SMS.
+    async fn send(&self, sms: &OutgoingSms) -> Result<SmsReceipt, DeliveryError>;
+}
+
+// ── Segment accounting ───────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum SmsEncoding {
+    /// GSM 03.38 default alphabet: 160 chars, 153 per part when concatenated.
+    Gsm7,
+    /// Anything outside GSM-7 (e.g. `ê`, `ô`, `œ`, emoji): 70 / 67 per part.
+    Ucs2,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct SmsSegments {
+    pub encoding: SmsEncoding,
+    /// Length in encoding units (septets for GSM-7, UTF-16 code units for UCS-2).
+    pub units: usize,
+    pub segments: u32,
+}
+
+const GSM7_BASIC: &str = "@£$¥èéùìòÇ\nØø\rÅåΔ_ΦΓΛΩΠΨΣΘΞÆæßÉ !\"#¤%&'()*+,-./0123456789:;<=>?\
+¡ABCDEFGHIJKLMNOPQRSTUVWXYZÄÖÑÜ§¿abcdefghijklmnopqrstuvwxyzäöñüà";
+const GSM7_EXTENSION: &str = "^{}\\[~]|€\u{000C}";
+
+/// Count the segments an SMS body will be billed as.
+///
+/// French accents are a trap: `é`, `è`, `à`, `ù` are in GSM-7, but `ê`, `â`,
+/// `ç`, `ô`, `î` are not and switch the whole message to UCS-2.
+pub fn count_sms_segments(body: &str) -> SmsSegments {
+    let gsm_units: Option<usize> = body
+        .chars()
+        .map(|c| {
+            if GSM7_BASIC.contains(c) {
+                Some(1)
+            } else if GSM7_EXTENSION.contains(c) {
+                Some(2)
+            } else {
+                None
+            }
+        })
+        .sum();
+
+    let (encoding, units, single, multi) = match gsm_units {
+        Some(units) => (SmsEncoding::Gsm7, units, 160, 153),
+        None => (SmsEncoding::Ucs2, body.encode_utf16().count(), 70, 67),
+    };
+    let segments = if units <= single {
+        1
+    } else {
+        units.div_ceil(multi) as u32
+    };
+    SmsSegments {
+        encoding,
+        units,
+        segments,
+    }
+}
+
+// ── Configuration ────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone)]
+pub struct HttpSmsConfig {
+    pub url: String,
+    pub auth_header: Option<String>,
+    pub auth_value: Option<String>,
+    pub body_template: String,
+}
+
+#[derive(Debug, Clone)]
+pub enum SmsTransportConfig {
+    Http(HttpSmsConfig),
+    File(PathBuf),
+}
+
+#[derive(Debug, Clone)]
+pub struct SmsConfig {
+    pub transport: SmsTransportConfig,
+    pub sender_id: Option<String>,
+    pub max_segments: u32,
+}
+
+impl SmsConfig {
+    /// Build the SMS config from app settings.
+    ///
+    /// Returns `None` when SMS notifications are disabled or the selected
+    /// transport is missing its URL/path, in which case SMS rows stay queued.
+    pub fn from_settings(settings: &NotificationSettings) -> Option<Self> {
+        if !settings.sms_notifications {
+            return None;
+        }
+        let transport = match settings.sms_transport.as_deref().map(str::trim) {
+            Some("file") => SmsTransportConfig::File(PathBuf::from(non_empty(
+                settings.sms_file_sink_path.as_deref(),
+            )?)),
+            Some("http") => {
+                let auth_value = match (
+                    non_empty(settings.sms_gateway_auth_template.as_deref()),
+                    settings.sms_gateway_api_key.as_deref(),
+                ) {
+                    (Some(template), api_key) => {
+                        Some(template.replace("{{api_key}}", api_key.unwrap_or_default()))
+                    }
+                    (None, Some(api_key)) if !api_key.is_empty() => {
+                        Some(format!("Bearer {}", api_key))
+                    }
+                    _ => None,
+                };
+                SmsTransportConfig::Http(HttpSmsConfig {
+                    url: non_empty(settings.sms_gateway_url.as_deref())?,
+                    auth_header: non_empty(settings.sms_gateway_auth_header.as_deref())
+                        .or_else(|| auth_value.as_ref().map(|_| "Authorization".to_string())),
+                    auth_value,
+                    body_template: non_empty(settings.sms_gateway_body_template.as_deref())
+                        .unwrap_or_else(|| DEFAULT_BODY_TEMPLATE.to_string()),
+                })
+            }
+            _ => return None,
+        };
+        Some(Self {
+            transport,
+            sender_id: non_empty(settings.sms_sender_id.as_deref()),
+            max_segments: settings
+                .sms_max_segments
+                .filter(|max| *max > 0)
+                .unwrap_or(DEFAULT_MAX_SMS_SEGMENTS),
+        })
+    }
+
+    pub fn build_transport(&self) -> Result<Box<dyn SmsTransport>, String> {
+        let transport: Box<dyn SmsTransport> = match &self.transport {
+            SmsTransportConfig::Http(config) => Box::new(HttpSmsTransport::new(config.clone())?),
+            SmsTransportConfig::File(path) => Box::new(FileSmsTransport::new(path.clone())),
+        };
+        Ok(transport)
+    }
+}
+
+fn non_empty(value: Option<&str>) -> Option<String> {
+    value
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
+// ── HTTP gateway ─────────────────────────────────────────────────────────────
+
+pub struct HttpSmsTransport {
+    config: HttpSmsConfig,
+    http_client: Client,
+}
+
+impl HttpSmsTransport {
+    pub fn new(config: HttpSmsConfig) -> Result<Self, String> {
+        reqwest::Url::parse(&config.url)
+            .map_err(|e| format!("Invalid SMS gateway URL '{}': {}", config.url, e))?;
+        let http_client = Client::builder()
+            .timeout(HTTP_TIMEOUT)
+            .build()
+            .map_err(|e| format!("Failed to build SMS HTTP client: {}", e))?;
+        Ok(Self {
+            config,
+            http_client,
+        })
+    }
+
+    /// Fill the body template. Values are JSON-escaped so they can sit inside
+    /// string literals of the template.
+    fn render_body(&self, sms: &OutgoingSms) -> String {
+        self.config
+            .body_template
+            .replace("{{to}}", &json_escape(&sms.to))
+            .replace("{{from}}", &json_escape(sms.from.as_deref().unwrap_or_default()))
+            .replace("{{body}}", &json_escape(&sms.body))
+    }
+}
+
+fn json_escape(value: &str) -> String {
+    let quoted = serde_json::Value::String(value.to_string()).to_string();
+    quoted[1..quoted.len() - 1].to_string()
+}
+
+/// Pull a provider message id out of common gateway response shapes.
+fn provider_message_id(response_body: &str) -> Option<String> {
+    let value: serde_json::Value = serde_json::from_str(response_body).ok()?;
+    let candidates = [
+        value.get("id"),
+        value.get("message_id"),
+        value.get("messageId"),
+        value.get("sid"),
+        value.pointer("/messages/0/id"),
+        value.pointer("/messages/0/message-id"),
+    ];
+    candidates.into_iter().flatten().find_map(|id| match id {
+        serde_json::Value::String(id) => Some(id.clone()),
+        serde_json::Value::Number(id) => Some(id.to_string()),
+        _ => None,
+    })
+}
+
+#[async_trait]
+impl SmsTransport for HttpSmsTransport {
+    async fn send(&self, sms: &OutgoingSms) -> Result<SmsReceipt, DeliveryError> {
+        let mut request = self
+            .http_client
+            .post(&self.config.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(self.render_body(sms));
+        if let (Some(header), Some(value)) = (&self.config.auth_header, &self.config.auth_value) {
+            request = request.header(header.as_str(), value.as_str());
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| DeliveryError::Transient(format!("SMS gateway unreachable: {}", e)))?;
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        if status.is_success() {
+            return Ok(SmsReceipt {
+                provider_message_id: provider_message_id(&body),
+            });
+        }
+
+        let reason = format!("SMS gateway returned {}: {}", status, body.trim());
+        if status.is_client_error()
+            && status != StatusCode::REQUEST_TIMEOUT
+            && status != StatusCode::TOO_MANY_REQUESTS
+        {
+            Err(DeliveryError::Permanent(reason))
+        } else {
+            Err(DeliveryError::Transient(reason))
+        }
+    }
+}
+
+// ── File sink ────────────────────────────────────────────────────────────────
+
+pub struct FileSmsTransport {
+    path: PathBuf,
+}
+
+impl FileSmsTransport {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+}
+
+#[async_trait]
+impl SmsTransport for FileSmsTransport {
+    async fn send(&self, sms: &OutgoingSms) -> Result<SmsReceipt, DeliveryError> {
+        let id = crate::shared::utils::uuid::generate_uuid_string();
+        let line = serde_json::json!({
+            "id": &id,
+            "to": &sms.to,
+            "from": &sms.from,
+            "body": &sms.body,
+            "segments": count_sms_segments(&sms.body),
+            "written_at": chrono::Utc::now().to_rfc3339(),
+        });
+
+        if let Some(parent) = self.path.parent() {
+            tokio::fs::create_dir_all(parent).await.map_err(|e| {
+                DeliveryError::Transient(format!("Failed to create SMS sink directory: {}", e))
+            })?;
+        }
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await
+            .map_err(|e| DeliveryError::Transient(format!("Failed to open SMS sink: {}", e)))?;
+        file.write_all(format!("{}\n", line).as_bytes())
+            .await
+            .map_err(|e| DeliveryError::Transient(format!("Failed to write SMS sink: {}", e)))?;
+
+        Ok(SmsReceipt {
+            provider_message_id: Some(id),
+        })
+    }
+}
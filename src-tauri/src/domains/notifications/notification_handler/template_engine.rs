@@ -0,0 +1,700 @@
+//! Rendering engine for `message_templates`.
+//!
+//! Syntax:
+//! - `{{client.name}}` — variable, formatted by type (French dates/amounts)
+//! - `{{#if quote.total}}…{{else}}…{{/if}}` — conditional on a non-empty value
+//!
+//! Variables are resolved from a [`TemplateContext`], which
+//! [`TemplateContextLoader`] fills from the task/client/quote a message refers
+//! to. Templates are validated against [`TEMPLATE_VARIABLES`] when saved so
+//! typos surface in the editor instead of as blank text in a client's inbox.
+
+use chrono::{Datelike, NaiveDate, TimeZone, Timelike};
+use chrono_tz::Tz;
+use rusqlite::{params, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Arc;
+use ts_rs::TS;
+
+use crate::commands::AppError;
+use crate::db::Database;
+use crate::domains::notifications::models::{Message, MessageTemplate};
+pub use crate::shared::utils::money::format_eur;
+
+/// Variables the context loader can provide, with the flat aliases used by the
+/// templates seeded in migration 023.
+pub const TEMPLATE_VARIABLES: &[&str] = &[
+    "client.name",
+    "client.email",
+    "client.phone",
+    "client.company_name",
+    "client.contact_person",
+    "task.number",
+    "task.title",
+    "task.status",
+    "task.priority",
+    "task.scheduled_date",
+    "task.start_time",
+    "task.end_time",
+    "task.vehicle",
+    "task.vehicle_plate",
+    "task.vehicle_make",
+    "task.vehicle_model",
+    "task.vehicle_year",
+    "task.technician_name",
+    "quote.number",
+    "quote.status",
+    "quote.subtotal",
+    "quote.tax_total",
+    "quote.total",
+    "quote.valid_until",
+    "subject",
+    "body",
+    // Legacy flat names (migration 023 seed templates)
+    "client_name",
+    "task_number",
+    "task_title",
+    "vehicle_plate",
+    "vehicle_model",
+    "scheduled_date",
+    "start_time",
+    "priority",
+    "technician_name",
+];
+
+const DEFAULT_SUBJECT: &str = "Notification";
+
+const FRENCH_MONTHS: [&str; 12] = [
+    "janvier",
+    "février",
+    "mars",
+    "avril",
+    "mai",
+    "juin",
+    "juillet",
+    "août",
+    "septembre",
+    "octobre",
+    "novembre",
+    "décembre",
+];
+
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum TemplateError {
+    #[error("Unclosed '{{{{' at position {0}")]
+    UnclosedTag(usize),
+    #[error("Empty tag at position {0}")]
+    EmptyTag(usize),
+    #[error("Unexpected '{{{{{0}}}}}' at position {1}")]
+    UnexpectedTag(String, usize),
+    #[error("Missing '{{{{/if}}}}' for the '{{{{#if {0}}}}}' block")]
+    UnclosedIf(String),
+    #[error("Unknown template variables: {}", .0.join(", "))]
+    UnknownVariables(Vec<String>),
+}
+
+// ── Values & context ─────────────────────────────────────────────────────────
+
+/// A typed value; the type decides how it is printed.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TemplateValue {
+    Text(String),
+    /// Amount in cents (quote totals are stored as integer cents).
+    Money(i64),
+    Date(NaiveDate),
+    /// Unix timestamp in milliseconds, shown in the context timezone.
+    DateTime(i64),
+    Number(f64),
+    Bool(bool),
+}
+
+impl TemplateValue {
+    fn is_truthy(&self) -> bool {
+        match self {
+            TemplateValue::Text(text) => !text.trim().is_empty(),
+            TemplateValue::Money(cents) => *cents != 0,
+            TemplateValue::Number(number) => *number != 0.0,
+            TemplateValue::Bool(value) => *value,
+            TemplateValue::Date(_) | TemplateValue::DateTime(_) => true,
+        }
+    }
+
+    fn format(&self, timezone: Tz) -> String {
+        match self {
+            TemplateValue::Text(text) => text.clone(),
+            TemplateValue::Money(cents) => format_eur(*cents),
+            TemplateValue::Date(date) => format_french_date(*date),
+            TemplateValue::DateTime(millis) => {
+                match timezone.timestamp_millis_opt(*millis).single() {
+                    Some(local) => format!(
+                        "{} à {}h{:02}",
+                        format_french_date(local.date_naive()),
+                        local.hour(),
+                        local.minute()
+                    ),
+                    None => String::new(),
+                }
+            }
+            TemplateValue::Number(number) => format_french_number(*number),
+            TemplateValue::Bool(true) => "oui".to_string(),
+            TemplateValue::Bool(false) => "non".to_string(),
+        }
+    }
+}
+
+/// `2026-03-18` → `18 mars 2026`.
+pub fn format_french_date(date: NaiveDate) -> String {
+    format!(
+        "{} {} {}",
+        date.day(),
+        FRENCH_MONTHS[date.month0() as usize],
+        date.year()
+    )
+}
+
+fn format_french_number(number: f64) -> String {
+    if number.fract() == 0.0 {
+        format!("{}", number as i64)
+    } else {
+        format!("{}", number).replace('.', ",")
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TemplateContext {
+    values: BTreeMap<String, TemplateValue>,
+    timezone: Tz,
+}
+
+impl Default for TemplateContext {
+    fn default() -> Self {
+        Self {
+            values: BTreeMap::new(),
+            timezone: chrono_tz::Europe::Paris,
+        }
+    }
+}
+
+impl TemplateContext {
+    pub fn with_timezone(mut self, timezone: Option<&str>) -> Self {
+        if let Some(timezone) = timezone.and_then(|value| value.parse::<Tz>().ok()) {
+            self.timezone = timezone;
+        }
+        self
+    }
+
+    pub fn insert(&mut self, name: impl Into<String>, value: TemplateValue) {
+        self.values.insert(name.into(), value);
+    }
+
+    /// Insert a value unless it is `None` or blank text.
+    fn insert_opt(&mut self, name: &str, value: Option<TemplateValue>) {
+        if let Some(value) = value
+            .filter(|value| !matches!(value, TemplateValue::Text(text) if text.trim().is_empty()))
+        {
+            self.values.insert(name.to_string(), value);
+        }
+    }
+
+    /// Add top-level keys of a JSON object (message `metadata`) as variables,
+    /// without overriding values loaded from entities.
+    pub fn extend_from_json(&mut self, object: &serde_json::Map<String, serde_json::Value>) {
+        for (key, value) in object {
+            let value = match value {
+                serde_json::Value::String(text) => TemplateValue::Text(text.clone()),
+                serde_json::Value::Number(number) => match number.as_f64() {
+                    Some(number) => TemplateValue::Number(number),
+                    None => continue,
+                },
+                serde_json::Value::Bool(value) => TemplateValue::Bool(*value),
+                _ => continue,
+            };
+            self.values.entry(key.clone()).or_insert(value);
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&TemplateValue> {
+        self.values.get(name)
+    }
+}
+
+// ── Parsing & rendering ──────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+    Text(String),
+    Variable(String),
+    If {
+        condition: String,
+        then_branch: Vec<Node>,
+        else_branch: Vec<Node>,
+    },
+}
+
+/// A parsed template, reusable across renders.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Template {
+    nodes: Vec<Node>,
+}
+
+/// Result of rendering: the text plus every variable that had no value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RenderedText {
+    pub text: String,
+    pub missing: Vec<String>,
+}
+
+enum Tag {
+    Variable(String),
+    If(String),
+    Else,
+    EndIf,
+}
+
+fn is_valid_variable_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .split('.')
+            .all(|part| !part.is_empty() && part.chars().all(|c| c.is_alphanumeric() || c == '_'))
+}
+
+impl Template {
+    pub fn parse(source: &str) -> Result<Self, TemplateError> {
+        // Each stack frame: (condition, then nodes, else nodes, in_else).
+        let mut stack: Vec<(String, Vec<Node>, Vec<Node>, bool)> = Vec::new();
+        let mut root: Vec<Node> = Vec::new();
+        let mut position = 0;
+
+        fn current<'a>(
+            root: &'a mut Vec<Node>,
+            stack: &'a mut [(String, Vec<Node>, Vec<Node>, bool)],
+        ) -> &'a mut Vec<Node> {
+            match stack.last_mut() {
+                Some((_, _, else_nodes, true)) => else_nodes,
+                Some((_, then_nodes, _, false)) => then_nodes,
+                None => root,
+            }
+        }
+
+        while position < source.len() {
+            let Some(offset) = source[position..].find("{{") else {
+                current(&mut root, &mut stack).push(Node::Text(source[position..].to_string()));
+                break;
+            };
+            let start = position + offset;
+            if start > position {
+                current(&mut root, &mut stack)
+                    .push(Node::Text(source[position..start].to_string()));
+            }
+            let Some(length) = source[start + 2..].find("}}") else {
+                return Err(TemplateError::UnclosedTag(start));
+            };
+            let raw = source[start + 2..start + 2 + length].trim();
+            position = start + 2 + length + 2;
+
+            let tag = if let Some(condition) = raw.strip_prefix("#if") {
+                Tag::If(condition.trim().to_string())
+            } else if raw == "else" {
+                Tag::Else
+            } else if raw == "/if" {
+                Tag::EndIf
+            } else {
+                Tag::Variable(raw.to_string())
+            };
+
+            match tag {
+                Tag::Variable(name) if name.is_empty() => {
+                    return Err(TemplateError::EmptyTag(start))
+                }
+                Tag::Variable(name) if !is_valid_variable_name(&name) => {
+                    return Err(TemplateError::UnexpectedTag(name, start))
+                }
+                Tag::Variable(name) => current(&mut root, &mut stack).push(Node::Variable(name)),
+                Tag::If(condition) if !is_valid_variable_name(&condition) => {
+                    return Err(TemplateError::UnexpectedTag(raw.to_string(), start))
+                }
+                Tag::If(condition) => stack.push((condition, Vec::new(), Vec::new(), false)),
+                Tag::Else => match stack.last_mut() {
+                    Some(frame) if !frame.3 => frame.3 = true,
+                    _ => return Err(TemplateError::UnexpectedTag(raw.to_string(), start)),
+                },
+                Tag::EndIf => {
+                    let Some((condition, then_branch, else_branch, _)) = stack.pop() else {
+                        return Err(TemplateError::UnexpectedTag(raw.to_string(), start));
+                    };
+                    current(&mut root, &mut stack).push(Node::If {
+                        condition,
+                        then_branch,
+                        else_branch,
+                    });
+                }
+            }
+        }
+
+        if let Some((condition, ..)) = stack.pop() {
+            return Err(TemplateError::UnclosedIf(condition));
+        }
+        Ok(Self { nodes: root })
+    }
+
+    /// Every variable referenced, including `#if` conditions.
+    pub fn variables(&self) -> BTreeSet<String> {
+        fn collect(nodes: &[Node], out: &mut BTreeSet<String>) {
+            for node in nodes {
+                match node {
+                    Node::Text(_) => {}
+                    Node::Variable(name) => {
+                        out.insert(name.clone());
+                    }
+                    Node::If {
+                        condition,
+                        then_branch,
+                        else_branch,
+                    } => {
+                        out.insert(condition.clone());
+                        collect(then_branch, out);
+                        collect(else_branch, out);
+                    }
+                }
+            }
+        }
+        let mut variables = BTreeSet::new();
+        collect(&self.nodes, &mut variables);
+        variables
+    }
+
+    pub fn render(&self, context: &TemplateContext) -> RenderedText {
+        fn render_nodes(
+            nodes: &[Node],
+            context: &TemplateContext,
+            text: &mut String,
+            missing: &mut BTreeSet<String>,
+        ) {
+            for node in nodes {
+                match node {
+                    Node::Text(literal) => text.push_str(literal),
+                    Node::Variable(name) => match context.get(name) {
+                        Some(value) => text.push_str(&value.format(context.timezone)),
+                        None => {
+                            missing.insert(name.clone());
+                        }
+                    },
+                    Node::If {
+                        condition,
+                        then_branch,
+                        else_branch,
+                    } => {
+                        let branch = if context.get(condition).is_some_and(TemplateValue::is_truthy)
+                        {
+                            then_branch
+                        } else {
+                            else_branch
+                        };
+                        render_nodes(branch, context, text, missing);
+                    }
+                }
+            }
+        }
+        let mut text = String::new();
+        let mut missing = BTreeSet::new();
+        render_nodes(&self.nodes, context, &mut text, &mut missing);
+        RenderedText {
+            text,
+            missing: missing.into_iter().collect(),
+        }
+    }
+}
+
+/// Check that a template parses and only uses known variables or ones the
+/// template declares itself (filled from message metadata at send time).
+///
+/// Returns the referenced variables on success.
+pub fn validate_template(
+    subject: Option<&str>,
+    body: &str,
+    declared_variables: &[String],
+) -> Result<BTreeSet<String>, TemplateError> {
+    let mut variables = Template::parse(body)?.variables();
+    if let Some(subject) = subject {
+        variables.extend(Template::parse(subject)?.variables());
+    }
+    let unknown: Vec<String> = variables
+        .iter()
+        .filter(|name| {
+            !TEMPLATE_VARIABLES.contains(&name.as_str()) && !declared_variables.contains(name)
+        })
+        .cloned()
+        .collect();
+    if unknown.is_empty() {
+        Ok(variables)
+    } else {
+        Err(TemplateError::UnknownVariables(unknown))
+    }
+}
+
+/// Subject and body for a message, using its template when one is linked.
+///
+/// Besides the entity values already in `context`, templates can use the
+/// top-level keys of the message `metadata` object plus `subject` and `body`
+/// from the row itself. Variables without a value render as empty text.
+pub fn render_message(
+    message: &Message,
+    template: Option<&MessageTemplate>,
+    mut context: TemplateContext,
+) -> Result<(String, String), TemplateError> {
+    let fallback_subject = message
+        .subject
+        .clone()
+        .unwrap_or_else(|| DEFAULT_SUBJECT.to_string());
+    let Some(template) = template else {
+        return Ok((fallback_subject, message.body.clone()));
+    };
+
+    if let Some(metadata) = message
+        .metadata
+        .as_deref()
+        .and_then(|raw| serde_json::from_str::<serde_json::Value>(raw).ok())
+        .and_then(|value| value.as_object().cloned())
+    {
+        context.extend_from_json(&metadata);
+    }
+    if context.get("subject").is_none() {
+        context.insert("subject", TemplateValue::Text(fallback_subject.clone()));
+    }
+    if context.get("body").is_none() {
+        context.insert("body", TemplateValue::Text(message.body.clone()));
+    }
+
+    let body = Template::parse(&template.body)?.render(&context);
+    let subject = match template.subject.as_deref() {
+        Some(subject) => Template::parse(subject)?.render(&context).text,
+        None => fallback_subject,
+    };
+    if !body.missing.is_empty() {
+        tracing::warn!(
+            message_id = %message.id,
+            template_id = %template.id,
+            missing = ?body.missing,
+            "Template variables without value rendered empty"
+        );
+    }
+    Ok((subject, body.text))
+}
+
+/// Task/client/quote a message refers to. The quote id has no column of its
+/// own and travels in `metadata.quote_id`.
+pub fn message_entity_refs(message: &Message) -> TemplateEntityRefs {
+    let quote_id = message
+        .metadata
+        .as_deref()
+        .and_then(|raw| serde_json::from_str::<serde_json::Value>(raw).ok())
+        .and_then(|value| value.get("quote_id")?.as_str().map(str::to_string));
+    TemplateEntityRefs {
+        task_id: message.task_id.clone(),
+        client_id: message.client_id.clone(),
+        quote_id,
+    }
+}
+
+// ── Entity loading ───────────────────────────────────────────────────────────
+
+/// The entities a message refers to.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct TemplateEntityRefs {
+    pub task_id: Option<String>,
+    pub client_id: Option<String>,
+    pub quote_id: Option<String>,
+}
+
+/// Fills a [`TemplateContext`] from the task, client and quote tables.
+///
+/// Missing references are followed: a quote brings its task and client, a task
+/// its client.
+pub struct TemplateContextLoader {
+    db: Arc<Database>,
+}
+
+impl TemplateContextLoader {
+    pub fn new(db: Arc<Database>) -> Self {
+        Self { db }
+    }
+
+    pub fn load(&self, refs: &TemplateEntityRefs) -> Result<TemplateContext, AppError> {
+        let conn = self
+            .db
+            .get_connection()
+            .map_err(|e| AppError::Database(format!("Failed to get DB connection: {}", e)))?;
+        let mut context = TemplateContext::default();
+        let mut task_id = refs.task_id.clone();
+        let mut client_id = refs.client_id.clone();
+
+        if let Some(quote_id) = refs.quote_id.as_deref() {
+            let quote = conn
+                .query_row(
+                    "SELECT quote_number, status, subtotal, tax_total, total, valid_until, client_id, task_id \
+                     FROM quotes WHERE id = ?",
+                    params![quote_id],
+                    |row| {
+                        Ok((
+                            row.get::<_, String>(0)?,
+                            row.get::<_, String>(1)?,
+                            row.get::<_, i64>(2)?,
+                            row.get::<_, i64>(3)?,
+                            row.get::<_, i64>(4)?,
+                            row.get::<_, Option<i64>>(5)?,
+                            row.get::<_, String>(6)?,
+                            row.get::<_, Option<String>>(7)?,
+                        ))
+                    },
+                )
+                .optional()
+                .map_err(|e| AppError::Database(format!("Failed to load quote: {}", e)))?
+                .ok_or_else(|| AppError::NotFound(format!("Quote {} not found", quote_id)))?;
+            let (number, status, subtotal, tax_total, total, valid_until, quote_client, quote_task) =
+                quote;
+            context.insert("quote.number", TemplateValue::Text(number));
+            context.insert("quote.status", TemplateValue::Text(status));
+            context.insert("quote.subtotal", TemplateValue::Money(subtotal));
+            context.insert("quote.tax_total", TemplateValue::Money(tax_total));
+            context.insert("quote.total", TemplateValue::Money(total));
+            context.insert_opt(
+                "quote.valid_until",
+                valid_until.and_then(|millis| {
+                    chrono::DateTime::from_timestamp_millis(millis)
+                        .map(|utc| TemplateValue::Date(utc.date_naive()))
+                }),
+            );
+            if client_id.is_none() {
+                client_id = Some(quote_client);
+            }
+            if task_id.is_none() {
+                task_id = quote_task;
+            }
+        }
+
+        if let Some(task_id) = task_id.as_deref() {
+            let task = conn
+                .query_row(
+                    "SELECT t.task_number, t.title, t.status, t.priority, t.scheduled_date, t.start_time, \
+                     t.end_time, t.vehicle_plate, t.vehicle_make, t.vehicle_model, t.vehicle_year, \
+                     t.client_id, t.customer_name, \
+                     (SELECT u.full_name FROM users u WHERE u.id = t.technician_id) \
+                     FROM tasks t WHERE t.id = ? AND t.deleted_at IS NULL",
+                    params![task_id],
+                    |row| {
+                        Ok([
+                            row.get::<_, Option<String>>(0)?,
+                            row.get::<_, Option<String>>(1)?,
+                            row.get::<_, Option<String>>(2)?,
+                            row.get::<_, Option<String>>(3)?,
+                            row.get::<_, Option<String>>(4)?,
+                            row.get::<_, Option<String>>(5)?,
+                            row.get::<_, Option<String>>(6)?,
+                            row.get::<_, Option<String>>(7)?,
+                            row.get::<_, Option<String>>(8)?,
+                            row.get::<_, Option<String>>(9)?,
+                            row.get::<_, Option<String>>(10)?,
+                            row.get::<_, Option<String>>(11)?,
+                            row.get::<_, Option<String>>(12)?,
+                            row.get::<_, Option<String>>(13)?,
+                        ])
+                    },
+                )
+                .optional()
+                .map_err(|e| AppError::Database(format!("Failed to load task: {}", e)))?
+                .ok_or_else(|| AppError::NotFound(format!("Task {} not found", task_id)))?;
+            let [number, title, status, priority, scheduled_date, start_time, end_time, plate, make, model, year, task_client, customer_name, technician] =
+                task;
+            let text = |value: &Option<String>| value.clone().map(TemplateValue::Text);
+
+            let vehicle = [&make, &model, &plate]
+                .iter()
+                .filter_map(|part| part.as_deref().map(str::trim))
+                .filter(|part| !part.is_empty())
+                .collect::<Vec<_>>()
+                .join(" ");
+            let scheduled = scheduled_date.as_deref().map(|raw| {
+                NaiveDate::parse_from_str(raw.get(..10).unwrap_or(raw), "%Y-%m-%d")
+                    .map(TemplateValue::Date)
+                    .unwrap_or_else(|_| TemplateValue::Text(raw.to_string()))
+            });
+
+            for (name, value) in [
+                ("task.number", text(&number)),
+                ("task.title", text(&title)),
+                ("task.status", text(&status)),
+                ("task.priority", text(&priority)),
+                ("task.scheduled_date", scheduled),
+                ("task.start_time", text(&start_time)),
+                ("task.end_time", text(&end_time)),
+                ("task.vehicle", Some(TemplateValue::Text(vehicle))),
+                ("task.vehicle_plate", text(&plate)),
+                ("task.vehicle_make", text(&make)),
+                ("task.vehicle_model", text(&model)),
+                ("task.vehicle_year", text(&year)),
+                ("task.technician_name", text(&technician)),
+            ] {
+                context.insert_opt(name, value);
+            }
+            if client_id.is_none() {
+                client_id = task_client;
+            }
+            if client_id.is_none() {
+                context.insert_opt("client.name", text(&customer_name));
+            }
+        }
+
+        if let Some(client_id) = client_id.as_deref() {
+            let client = conn
+                .query_row(
+                    "SELECT name, email, phone, company_name, contact_person FROM clients \
+                     WHERE id = ? AND deleted_at IS NULL",
+                    params![client_id],
+                    |row| {
+                        Ok([
+                            row.get::<_, Option<String>>(0)?,
+                            row.get::<_, Option<String>>(1)?,
+                            row.get::<_, Option<String>>(2)?,
+                            row.get::<_, Option<String>>(3)?,
+                            row.get::<_, Option<String>>(4)?,
+                        ])
+                    },
+                )
+                .optional()
+                .map_err(|e| AppError::Database(format!("Failed to load client: {}", e)))?
+                .ok_or_else(|| AppError::NotFound(format!("Client {} not found", client_id)))?;
+            for (name, value) in [
+                "client.name",
+                "client.email",
+                "client.phone",
+                "client.company_name",
+                "client.contact_person",
+            ]
+            .into_iter()
+            .zip(client)
+            {
+                context.insert_opt(name, value.map(TemplateValue::Text));
+            }
+        }
+
+        for (alias, source) in [
+            ("client_name", "client.name"),
+            ("task_number", "task.number"),
+            ("task_title", "task.title"),
+            ("vehicle_plate", "task.vehicle_plate"),
+            ("vehicle_model", "task.vehicle_model"),
+            ("scheduled_date", "task.scheduled_date"),
+            ("start_time", "task.start_time"),
+            ("priority", "task.priority"),
+            ("technician_name", "task.technician_name"),
+        ] {
+            if let Some(value) = context.get(source).cloned() {
+                context.insert(alias, value);
+            }
+        }
+
+        Ok(context)
+    }
+}
@@ -1,5 +1,5 @@
 use async_trait::async_trait;
-use rusqlite::params;
+use rusqlite::{params, OptionalExtension};
 use std::sync::Arc;
 
 use crate::db::Database;
@@ -306,6 +306,98 @@ impl NotificationTemplateRepository {
             .map_err(|e| RepoError::Database(format!("Failed to collect templates: {}", e)))?;
         Ok(templates)
     }
+
+    /// Fetch a single `message_templates` row as the [`MessageTemplate`] DTO.
+    ///
+    /// Unlike `find_by_id`, this tolerates a NULL subject (SMS templates).
+    ///
+    /// [`MessageTemplate`]: crate::domains::notifications::models::MessageTemplate
+    pub async fn find_message_template(
+        &self,
+        template_id: &str,
+    ) -> RepoResult<Option<crate::domains::notifications::models::MessageTemplate>> {
+        let conn = self
+            .db
+            .get_connection()
+            .map_err(|e| RepoError::Database(format!("Failed to get DB connection: {}", e)))?;
+        conn.query_row(
+            "SELECT id, name, description, message_type, subject, body, variables, \
+             category, is_active, created_by, created_at, updated_at \
+             FROM message_templates WHERE id = ?",
+            params![template_id],
+            |row| {
+                Ok(crate::domains::notifications::models::MessageTemplate {
+                    id: row.get(0)?,
+                    name: row.get(1)?,
+                    description: row.get(2)?,
+                    message_type: row.get(3)?,
+                    subject: row.get(4)?,
+                    body: row.get(5)?,
+                    variables: row.get(6)?,
+                    category: row.get(7)?,
+                    is_active: row.get(8)?,
+                    created_by: row.get(9)?,
+                    created_at: row.get(10)?,
+                    updated_at: row.get(11)?,
+                })
+            },
+        )
+        .optional()
+        .map_err(|e| RepoError::Database(format!("Failed to find template: {}", e)))
+    }
+
+    /// Insert (`template_id = None`) or update a `message_templates` row from
+    /// the editor payload. Callers validate the template text beforehand.
+    pub async fn save_message_template(
+        &self,
+        template_id: Option<&str>,
+        request: &crate::domains::notifications::models::MessageTemplateRequest,
+        created_by: &str,
+    ) -> RepoResult<crate::domains::notifications::models::MessageTemplate> {
+        let now = chrono::Utc::now().timestamp_millis();
+        let variables_json = request
+            .variables
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()
+            .map_err(|e| RepoError::Database(format!("Failed to serialize variables: {}", e)))?;
+        let channel = match request.message_type.as_str() {
+            "sms" => "sms",
+            "in_app" => "push",
+            _ => "email",
+        };
+        let category = request.category.as_deref().unwrap_or("general");
+
+        let id = match template_id {
+            Some(id) => {
+                let updated = self
+                    .db
+                    .execute(
+                        "UPDATE message_templates SET name=?, description=?, message_type=?, channel=?, subject=?, body=?, variables=?, category=?, updated_at=? WHERE id=?",
+                        params![request.name, request.description, request.message_type, channel, request.subject, request.body, variables_json, category, now, id],
+                    )
+                    .map_err(|e| RepoError::Database(format!("Failed to update template: {}", e)))?;
+                if updated == 0 {
+                    return Err(RepoError::NotFound(format!("Template {} not found", id)));
+                }
+                id.to_string()
+            }
+            None => {
+                let id = crate::shared::utils::uuid::generate_uuid_string();
+                self.db
+                    .execute(
+                        "INSERT INTO message_templates (id, name, description, message_type, channel, subject, body, variables, category, is_active, created_by, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, 1, ?, ?, ?)",
+                        params![id, request.name, request.description, request.message_type, channel, request.subject, request.body, variables_json, category, created_by, now, now],
+                    )
+                    .map_err(|e| RepoError::Database(format!("Failed to create template: {}", e)))?;
+                id
+            }
+        };
+        self.cache.clear();
+        self.find_message_template(&id)
+            .await?
+            .ok_or_else(|| RepoError::NotFound("Template not found after save".to_string()))
+    }
 }
 
 #[async_trait]
@@ -0,0 +1,492 @@
+//! Outbound email dispatcher tests against an in-process SMTP stand-in.
+
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use chrono::TimeZone;
+use rusqlite::params;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpListener;
+
+use crate::db::Database;
+use crate::domains::notifications::models::{Message, MessageTemplate};
+use crate::domains::notifications::notification_handler::{
+    render_message, DeliveryError, EmailTransport, MessageDispatcher, MessageRepository,
+    OutgoingEmail, OutgoingSms, SmsReceipt, SmsTransport, TemplateContext,
+};
+use crate::shared::repositories::base::Repository;
+use crate::shared::repositories::cache::Cache;
+use crate::shared::services::cross_domain::SettingsRepository;
+
+/// Minimal SMTP server: records envelopes and answers RCPT TO with `rcpt_reply`.
+#[derive(Default)]
+struct FakeSmtpState {
+    recipients: Mutex<Vec<String>>,
+    data: Mutex<Vec<String>>,
+}
+
+async fn start_fake_smtp(rcpt_reply: &'static str) -> (u16, Arc<FakeSmtpState>) {
+    let listener = TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("bind fake smtp");
+    let port = listener.local_addr().expect("local addr").port();
+    let state = Arc::new(FakeSmtpState::default());
+    let server_state = state.clone();
+    tokio::spawn(async move {
+        loop {
+            let Ok((stream, _)) = listener.accept().await else {
+                break;
+            };
+            let state = server_state.clone();
+            tokio::spawn(async move {
+                let (reader, mut writer) = stream.into_split();
+                let mut reader = BufReader::new(reader);
+                let _ = writer.write_all(b"220 fake.smtp ESMTP\r\n").await;
+                let mut line = String::new();
+                loop {
+                    line.clear();
+                    if reader.read_line(&mut line).await.unwrap_or(0) == 0 {
+                        break;
+                    }
+                    let command = line.trim_end().to_uppercase();
+                    let reply = if command.starts_with("EHLO") {
+                        "250-fake.smtp\r\n250 8BITMIME\r\n".to_string()
+                    } else if command.starts_with("RCPT TO") {
+                        state
+                            .recipients
+                            .lock()
+                            .unwrap()
+                            .push(line.trim_end()[8..].to_string());
+                        format!("{}\r\n", rcpt_reply)
+                    } else if command == "DATA" {
+                        let _ = writer.write_all(b"354 go ahead\r\n").await;
+                        let mut body = String::new();
+                        loop {
+                            line.clear();
+                            if reader.read_line(&mut line).await.unwrap_or(0) == 0
+                                || line == ".\r\n"
+                            {
+                                break;
+                            }
+                            body.push_str(&line);
+                        }
+                        state.data.lock().unwrap().push(body);
+                        "250 queued\r\n".to_string()
+                    } else if command == "QUIT" {
+                        let _ = writer.write_all(b"221 bye\r\n").await;
+                        break;
+                    } else {
+                        "250 OK\r\n".to_string()
+                    };
+                    if writer.write_all(reply.as_bytes()).await.is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+    });
+    (port, state)
+}
+
+/// Transport double that records emails and fails with a fixed error if set.
+#[derive(Default)]
+struct RecordingTransport {
+    sent: Mutex<Vec<OutgoingEmail>>,
+    failure: Option<DeliveryError>,
+}
+
+#[async_trait]
+impl EmailTransport for RecordingTransport {
+    async fn send(&self, email: &OutgoingEmail) -> Result<(), DeliveryError> {
+        if let Some(error) = &self.failure {
+            return Err(error.clone());
+        }
+        self.sent.lock().unwrap().push(email.clone());
+        Ok(())
+    }
+}
+
+/// SMS double that records messages and returns a fixed provider id.
+#[derive(Default)]
+struct RecordingSmsTransport {
+    sent: Mutex<Vec<OutgoingSms>>,
+}
+
+#[async_trait]
+impl SmsTransport for RecordingSmsTransport {
+    async fn send(&self, sms: &OutgoingSms) -> Result<SmsReceipt, DeliveryError> {
+        self.sent.lock().unwrap().push(sms.clone());
+        Ok(SmsReceipt {
+            provider_message_id: Some("gw-42".to_string()),
+        })
+    }
+}
+
+struct Fixture {
+    db: Arc<Database>,
+    repository: Arc<MessageRepository>,
+    cache: Arc<Cache>,
+}
+
+async fn fixture(smtp_port: Option<u16>) -> Fixture {
+    let db = Arc::new(Database::new_in_memory().await.expect("in-memory database"));
+    let settings_repository = SettingsRepository::new(db.clone());
+    let mut settings = settings_repository
+        .get_app_settings_db()
+        .expect("default settings");
+    settings.notifications.email_notifications = true;
+    settings.notifications.smtp_host = Some("127.0.0.1".to_string());
+    settings.notifications.smtp_port = smtp_port;
+    settings.notifications.smtp_security = Some("none".to_string());
+    settings.notifications.smtp_from_address = Some("atelier@example.com".to_string());
+    settings.notifications.smtp_from_name = Some("Atelier PPF".to_string());
+    settings_repository
+        .save_app_settings_db(&settings, "test-user")
+        .expect("save smtp settings");
+    let cache = Arc::new(Cache::new(64));
+    Fixture {
+        repository: Arc::new(MessageRepository::new(db.clone(), cache.clone())),
+        db,
+        cache,
+    }
+}
+
+impl Fixture {
+    fn dispatcher(&self) -> MessageDispatcher {
+        MessageDispatcher::new(self.repository.clone(), self.db.clone(), self.cache.clone())
+    }
+
+    async fn queue_email(&self, id: &str, scheduled_at: Option<i64>) {
+        self.repository
+            .save(Message {
+                id: id.to_string(),
+                message_type: "email".to_string(),
+                sender_id: None,
+                recipient_id: None,
+                recipient_email: Some("client@example.com".to_string()),
+                recipient_phone: None,
+                subject: Some("Confirmation de rendez-vous".to_string()),
+                body: "Votre rendez-vous est confirmé.".to_string(),
+                template_id: None,
+                task_id: None,
+                client_id: None,
+                status: "pending".to_string(),
+                priority: "normal".to_string(),
+                scheduled_at,
+                sent_at: None,
+                read_at: None,
+                error_message: None,
+                metadata: None,
+                created_at: 0,
+                updated_at: 0,
+            })
+            .await
+            .expect("queue email");
+    }
+
+    fn enable_sms(&self, sink_path: &std::path::Path) {
+        let settings_repository = SettingsRepository::new(self.db.clone());
+        let mut settings = settings_repository.get_app_settings_db().expect("settings");
+        settings.notifications.sms_notifications = true;
+        settings.notifications.sms_transport = Some("file".to_string());
+        settings.notifications.sms_file_sink_path = Some(sink_path.to_string_lossy().into_owned());
+        settings.notifications.sms_sender_id = Some("ATELIERPPF".to_string());
+        settings_repository
+            .save_app_settings_db(&settings, "test-user")
+            .expect("save sms settings");
+    }
+
+    async fn queue_sms(&self, id: &str, phone: &str, body: &str) {
+        self.queue_email(id, None).await;
+        self.db
+            .execute(
+                "UPDATE messages SET message_type = 'sms', recipient_email = NULL, recipient_phone = ?, subject = NULL, body = ? WHERE id = ?",
+                params![phone, body, id],
+            )
+            .expect("turn message into sms");
+    }
+
+    fn sms_accounting(&self, id: &str) -> (Option<i32>, Option<String>) {
+        self.db
+            .get_connection()
+            .expect("connection")
+            .query_row(
+                "SELECT segment_count, provider_message_id FROM messages WHERE id = ?",
+                params![id],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .expect("message row")
+    }
+
+    fn delivery_state(&self, id: &str) -> (String, i32, Option<i64>, Option<String>) {
+        self.db
+            .get_connection()
+            .expect("connection")
+            .query_row(
+                "SELECT status, attempts, next_attempt_at, error_message FROM messages WHERE id = ?",
+                params![id],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+            )
+            .expect("message row")
+    }
+}
+
+/// 12:00 in Paris, outside the default 22:00–08:00 quiet window.
+fn paris_noon() -> i64 {
+    chrono::Utc
+        .with_ymd_and_hms(2026, 3, 18, 11, 0, 0)
+        .unwrap()
+        .timestamp_millis()
+}
+
+#[tokio::test]
+async fn pending_email_is_sent_over_smtp_and_marked_sent() {
+    let (port, smtp) = start_fake_smtp("250 OK").await;
+    let fixture = fixture(Some(port)).await;
+    fixture.queue_email("msg-smtp", None).await;
+
+    let summary = fixture
+        .dispatcher()
+        .dispatch_pending_at(paris_noon(), 5)
+        .await
+        .expect("dispatch");
+
+    assert_eq!(summary.sent, 1);
+    assert_eq!(
+        smtp.recipients.lock().unwrap().as_slice(),
+        ["<client@example.com>"]
+    );
+    let data = smtp.data.lock().unwrap().join("");
+    assert!(data.contains("From: \"Atelier PPF\" <atelier@example.com>"));
+    assert!(data.contains("Subject: "));
+    let (status, attempts, next_attempt_at, error) = fixture.delivery_state("msg-smtp");
+    assert_eq!(status, "sent");
+    assert_eq!(attempts, 1);
+    assert!(next_attempt_at.is_none());
+    assert!(error.is_none());
+}
+
+#[tokio::test]
+async fn transient_smtp_failure_is_retried_with_backoff_until_max_attempts() {
+    let (port, _smtp) = start_fake_smtp("451 try again later").await;
+    let fixture = fixture(Some(port)).await;
+    fixture.queue_email("msg-retry", None).await;
+    let dispatcher = fixture.dispatcher();
+    let now = paris_noon();
+
+    let first = dispatcher.dispatch_pending_at(now, 2).await.expect("first");
+    let (status, attempts, next_attempt_at, error) = fixture.delivery_state("msg-retry");
+    assert_eq!(first.failed, 1);
+    assert_eq!(status, "failed");
+    assert_eq!(attempts, 1);
+    assert_eq!(next_attempt_at, Some(now + 60_000));
+    assert!(error.is_some_and(|error| error.contains("451")));
+
+    let too_early = dispatcher
+        .dispatch_pending_at(now + 30_000, 2)
+        .await
+        .expect("before backoff");
+    assert_eq!(too_early, Default::default());
+
+    let second = dispatcher
+        .dispatch_pending_at(now + 60_000, 2)
+        .await
+        .expect("second");
+    let (_, attempts, next_attempt_at, _) = fixture.delivery_state("msg-retry");
+    assert_eq!(second.failed, 1);
+    assert_eq!(attempts, 2);
+    assert!(next_attempt_at.is_none());
+
+    let exhausted = dispatcher
+        .dispatch_pending_at(now + 86_400_000, 2)
+        .await
+        .expect("exhausted");
+    assert_eq!(exhausted, Default::default());
+}
+
+#[tokio::test]
+async fn permanent_failure_is_not_retried() {
+    let fixture = fixture(None).await;
+    fixture.queue_email("msg-bounce", None).await;
+    let transport = Arc::new(RecordingTransport {
+        failure: Some(DeliveryError::Permanent(
+            "550 mailbox unavailable".to_string(),
+        )),
+        ..Default::default()
+    });
+
+    fixture
+        .dispatcher()
+        .with_email_transport(transport)
+        .dispatch_pending_at(paris_noon(), 5)
+        .await
+        .expect("dispatch");
+
+    let (status, attempts, next_attempt_at, error) = fixture.delivery_state("msg-bounce");
+    assert_eq!(status, "failed");
+    assert_eq!(attempts, 5);
+    assert!(next_attempt_at.is_none());
+    assert_eq!(error.as_deref(), Some("550 mailbox unavailable"));
+}
+
+#[tokio::test]
+async fn scheduled_and_quiet_hour_messages_are_held_back() {
+    let fixture = fixture(None).await;
+    let noon = paris_noon();
+    fixture
+        .queue_email("msg-later", Some(noon + 3_600_000))
+        .await;
+    let transport = Arc::new(RecordingTransport::default());
+    let dispatcher = fixture.dispatcher().with_email_transport(transport.clone());
+
+    let before_schedule = dispatcher.dispatch_pending_at(noon, 5).await.expect("noon");
+    assert_eq!(before_schedule, Default::default());
+
+    // 23:30 Paris falls in the default shop quiet window.
+    let night = chrono::Utc
+        .with_ymd_and_hms(2026, 3, 18, 22, 30, 0)
+        .unwrap()
+        .timestamp_millis();
+    let at_night = dispatcher
+        .dispatch_pending_at(night, 5)
+        .await
+        .expect("night");
+    assert_eq!(at_night.deferred, 1);
+    assert!(transport.sent.lock().unwrap().is_empty());
+    assert_eq!(fixture.delivery_state("msg-later").0, "pending");
+
+    let next_morning = night + 10 * 3_600_000;
+    let sent = dispatcher
+        .dispatch_pending_at(next_morning, 5)
+        .await
+        .expect("morning");
+    assert_eq!(sent.sent, 1);
+    assert_eq!(transport.sent.lock().unwrap()[0].to, "client@example.com");
+}
+
+#[tokio::test]
+async fn pending_sms_is_normalized_sent_and_accounted() {
+    let fixture = fixture(None).await;
+    let sink = tempfile::tempdir().expect("temp dir");
+    fixture.enable_sms(&sink.path().join("sms.jsonl"));
+    fixture
+        .queue_sms("msg-sms", "06 12 34 56 78", "Rappel : RDV demain à 9h.")
+        .await;
+    let transport = Arc::new(RecordingSmsTransport::default());
+
+    let summary = fixture
+        .dispatcher()
+        .with_sms_transport(transport.clone())
+        .dispatch_pending_at(paris_noon(), 5)
+        .await
+        .expect("dispatch");
+
+    assert_eq!(summary.sent, 1);
+    let sent = transport.sent.lock().unwrap();
+    assert_eq!(sent[0].to, "+33612345678");
+    assert_eq!(sent[0].from.as_deref(), Some("ATELIERPPF"));
+    assert_eq!(fixture.delivery_state("msg-sms").0, "sent");
+    assert_eq!(
+        fixture.sms_accounting("msg-sms"),
+        (Some(1), Some("gw-42".to_string()))
+    );
+}
+
+#[tokio::test]
+async fn sms_file_sink_writes_one_json_line_per_message() {
+    let fixture = fixture(None).await;
+    let sink = tempfile::tempdir().expect("temp dir");
+    let sink_path = sink.path().join("outbox").join("sms.jsonl");
+    fixture.enable_sms(&sink_path);
+    fixture
+        .queue_sms("msg-file", "+33 6 98 76 54 32", "Votre véhicule est prêt.")
+        .await;
+
+    fixture
+        .dispatcher()
+        .dispatch_pending_at(paris_noon(), 5)
+        .await
+        .expect("dispatch");
+
+    let contents = std::fs::read_to_string(&sink_path).expect("sink file");
+    let line: serde_json::Value =
+        serde_json::from_str(contents.lines().next().expect("one line")).expect("json line");
+    assert_eq!(line["to"], "+33698765432");
+    assert_eq!(line["segments"]["encoding"], "Ucs2");
+    let (segments, provider_id) = fixture.sms_accounting("msg-file");
+    assert_eq!(segments, Some(1));
+    assert_eq!(provider_id.as_deref(), line["id"].as_str());
+}
+
+#[tokio::test]
+async fn invalid_phone_and_oversized_sms_fail_permanently() {
+    let fixture = fixture(None).await;
+    let sink = tempfile::tempdir().expect("temp dir");
+    fixture.enable_sms(&sink.path().join("sms.jsonl"));
+    fixture.queue_sms("msg-bad-phone", "612", "Rappel").await;
+    fixture
+        .queue_sms("msg-too-long", "0612345678", &"ê".repeat(250))
+        .await;
+    let transport = Arc::new(RecordingSmsTransport::default());
+
+    let summary = fixture
+        .dispatcher()
+        .with_sms_transport(transport.clone())
+        .dispatch_pending_at(paris_noon(), 5)
+        .await
+        .expect("dispatch");
+
+    assert_eq!(summary.failed, 2);
+    assert!(transport.sent.lock().unwrap().is_empty());
+    let (_, attempts, _, error) = fixture.delivery_state("msg-bad-phone");
+    assert_eq!(attempts, 5);
+    assert!(error.is_some_and(|error| error.contains("Invalid recipient phone number")));
+    let (_, _, _, error) = fixture.delivery_state("msg-too-long");
+    assert!(error.is_some_and(|error| error.contains("4 segments")));
+}
+
+#[test]
+fn template_placeholders_are_filled_from_message_metadata() {
+    let message = Message {
+        id: "msg-template".to_string(),
+        message_type: "email".to_string(),
+        sender_id: None,
+        recipient_id: None,
+        recipient_email: Some("client@example.com".to_string()),
+        recipient_phone: None,
+        subject: None,
+        body: "fallback".to_string(),
+        template_id: Some("tpl-confirmation".to_string()),
+        task_id: None,
+        client_id: None,
+        status: "pending".to_string(),
+        priority: "normal".to_string(),
+        scheduled_at: None,
+        sent_at: None,
+        read_at: None,
+        error_message: None,
+        metadata: Some(r#"{"client_name":"Mme Martin","slot":"18/03 à 14h00"}"#.to_string()),
+        created_at: 0,
+        updated_at: 0,
+    };
+    let template = MessageTemplate {
+        id: "tpl-confirmation".to_string(),
+        name: "confirmation".to_string(),
+        description: None,
+        message_type: "email".to_string(),
+        subject: Some("Rendez-vous du {{ slot }}".to_string()),
+        body: "Bonjour {{client_name}}, rendez-vous le {{slot}}.{{missing}}".to_string(),
+        variables: None,
+        category: "client".to_string(),
+        is_active: true,
+        created_by: None,
+        created_at: 0,
+        updated_at: 0,
+    };
+
+    let (subject, body) = render_message(&message, Some(&template), TemplateContext::default())
+        .expect("template renders");
+
+    assert_eq!(subject, "Rendez-vous du 18/03 à 14h00");
+    assert_eq!(body, "Bonjour Mme Martin, rendez-vous le 18/03 à 14h00.");
+}
@@ -1 +1,4 @@
 // Domain module.
+mod message_dispatcher;
+mod sms_transport;
+mod template_engine;
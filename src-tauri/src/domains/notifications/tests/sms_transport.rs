@@ -0,0 +1,163 @@
+//! SMS segment accounting and HTTP gateway transport tests.
+
+use std::sync::{Arc, Mutex};
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+use crate::domains::notifications::notification_handler::{
+    count_sms_segments, DeliveryError, HttpSmsConfig, HttpSmsTransport, OutgoingSms, SmsConfig,
+    SmsEncoding, SmsTransport, SmsTransportConfig,
+};
+use crate::shared::services::cross_domain::NotificationSettings;
+
+/// One-shot HTTP server: captures the raw request and replies with `response`.
+async fn start_fake_gateway(response: &'static str) -> (String, Arc<Mutex<String>>) {
+    let listener = TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("bind fake gateway");
+    let url = format!("http://{}/sms", listener.local_addr().expect("addr"));
+    let captured = Arc::new(Mutex::new(String::new()));
+    let request = captured.clone();
+    tokio::spawn(async move {
+        let (mut stream, _) = listener.accept().await.expect("accept");
+        let mut buffer = vec![0u8; 16 * 1024];
+        let mut raw = Vec::new();
+        loop {
+            let read = stream.read(&mut buffer).await.unwrap_or(0);
+            if read == 0 {
+                break;
+            }
+            raw.extend_from_slice(&buffer[..read]);
+            let text = String::from_utf8_lossy(&raw);
+            if let Some(header_end) = text.find("\r\n\r\n") {
+                let content_length = text[..header_end]
+                    .lines()
+                    .find_map(|line| {
+                        line.to_ascii_lowercase()
+                            .strip_prefix("content-length:")
+                            .map(|value| value.trim().parse::<usize>().unwrap_or(0))
+                    })
+                    .unwrap_or(0);
+                if raw.len() >= header_end + 4 + content_length {
+                    break;
+                }
+            }
+        }
+        *request.lock().unwrap() = String::from_utf8_lossy(&raw).into_owned();
+        let _ = stream.write_all(response.as_bytes()).await;
+    });
+    (url, captured)
+}
+
+fn gateway_config(url: String) -> HttpSmsConfig {
+    HttpSmsConfig {
+        url,
+        auth_header: Some("X-Api-Key".to_string()),
+        auth_value: Some("key-123".to_string()),
+        body_template: r#"{"recipient":"{{to}}","sender":"{{from}}","message":"{{body}}"}"#
+            .to_string(),
+    }
+}
+
+#[test]
+fn gsm7_and_ucs2_segments_are_counted_like_carriers_bill_them() {
+    let short = count_sms_segments("Bonjour, votre RDV est confirmé à 14h.");
+    assert_eq!(short.encoding, SmsEncoding::Gsm7);
+    assert_eq!(short.segments, 1);
+
+    let exactly_160 = count_sms_segments(&"a".repeat(160));
+    assert_eq!((exactly_160.units, exactly_160.segments), (160, 1));
+    let concatenated = count_sms_segments(&"a".repeat(161));
+    assert_eq!(concatenated.segments, 2);
+
+    // Extension characters cost two septets.
+    let euro = count_sms_segments("Total : 120€");
+    assert_eq!(euro.encoding, SmsEncoding::Gsm7);
+    assert_eq!(euro.units, 13);
+
+    let circumflex = count_sms_segments("Votre véhicule est prêt.");
+    assert_eq!(circumflex.encoding, SmsEncoding::Ucs2);
+    assert_eq!(count_sms_segments(&"ê".repeat(71)).segments, 2);
+}
+
+#[tokio::test]
+async fn http_gateway_posts_templated_json_with_auth_header() {
+    let (url, captured) = start_fake_gateway(
+        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: 22\r\nConnection: close\r\n\r\n{\"message_id\":\"m-987\"}",
+    )
+    .await;
+    let transport = HttpSmsTransport::new(gateway_config(url)).expect("transport");
+
+    let receipt = transport
+        .send(&OutgoingSms {
+            to: "+33612345678".to_string(),
+            from: Some("ATELIERPPF".to_string()),
+            body: "Rappel \"RDV\" demain".to_string(),
+        })
+        .await
+        .expect("accepted");
+
+    assert_eq!(receipt.provider_message_id.as_deref(), Some("m-987"));
+    let request = captured.lock().unwrap().clone();
+    assert!(request.starts_with("POST /sms"));
+    assert!(request.to_ascii_lowercase().contains("x-api-key: key-123"));
+    assert!(request.ends_with(
+        r#"{"recipient":"+33612345678","sender":"ATELIERPPF","message":"Rappel \"RDV\" demain"}"#
+    ));
+}
+
+#[tokio::test]
+async fn http_gateway_client_errors_are_permanent_and_throttling_is_transient() {
+    let (rejected_url, _) = start_fake_gateway(
+        "HTTP/1.1 400 Bad Request\r\nContent-Length: 14\r\nConnection: close\r\n\r\ninvalid number",
+    )
+    .await;
+    let (throttled_url, _) = start_fake_gateway(
+        "HTTP/1.1 429 Too Many Requests\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+    )
+    .await;
+    let sms = OutgoingSms {
+        to: "+33612345678".to_string(),
+        from: None,
+        body: "Rappel".to_string(),
+    };
+
+    let rejected = HttpSmsTransport::new(gateway_config(rejected_url))
+        .expect("transport")
+        .send(&sms)
+        .await;
+    let throttled = HttpSmsTransport::new(gateway_config(throttled_url))
+        .expect("transport")
+        .send(&sms)
+        .await;
+
+    assert!(matches!(rejected, Err(DeliveryError::Permanent(reason)) if reason.contains("invalid number")));
+    assert!(matches!(throttled, Err(DeliveryError::Transient(_))));
+}
+
+#[test]
+fn sms_config_requires_enabled_flag_and_transport_target() {
+    let mut settings = NotificationSettings {
+        sms_transport: Some("http".to_string()),
+        sms_gateway_url: Some("https://sms.example.com/send".to_string()),
+        sms_gateway_auth_template: Some("Bearer {{api_key}}".to_string()),
+        sms_gateway_api_key: Some("secret".to_string()),
+        ..Default::default()
+    };
+    assert!(SmsConfig::from_settings(&settings).is_none());
+
+    settings.sms_notifications = true;
+    let config = SmsConfig::from_settings(&settings).expect("http config");
+    match config.transport {
+        SmsTransportConfig::Http(http) => {
+            assert_eq!(http.auth_header.as_deref(), Some("Authorization"));
+            assert_eq!(http.auth_value.as_deref(), Some("Bearer secret"));
+        }
+        SmsTransportConfig::File(_) => panic!("expected http transport"),
+    }
+    assert_eq!(config.max_segments, 3);
+
+    settings.sms_transport = Some("file".to_string());
+    assert!(SmsConfig::from_settings(&settings).is_none());
+}
@@ -0,0 +1,161 @@
+//! Template parsing, French formatting, validation and entity loading tests.
+
+use std::sync::Arc;
+
+use chrono::NaiveDate;
+use rusqlite::params;
+
+use crate::commands::AppError;
+use crate::db::Database;
+use crate::domains::notifications::notification_handler::{
+    format_eur, format_french_date, validate_template, Template, TemplateContext,
+    TemplateContextLoader, TemplateEntityRefs, TemplateError, TemplateValue,
+};
+
+fn context(values: &[(&str, TemplateValue)]) -> TemplateContext {
+    let mut context = TemplateContext::default();
+    for (name, value) in values {
+        context.insert(*name, value.clone());
+    }
+    context
+}
+
+#[test]
+fn variables_and_conditionals_render_with_missing_values_reported() {
+    let template = Template::parse(
+        "Bonjour {{ client.name }}{{#if quote.total}}, devis de {{quote.total}}{{else}}, sans devis{{/if}}.{{task.title}}",
+    )
+    .expect("parses");
+    let with_quote = context(&[
+        ("client.name", TemplateValue::Text("Mme Martin".to_string())),
+        ("quote.total", TemplateValue::Money(123_456)),
+    ]);
+    let without_quote = context(&[("client.name", TemplateValue::Text("M. Durand".to_string()))]);
+
+    let rendered = template.render(&with_quote);
+    assert_eq!(rendered.text, "Bonjour Mme Martin, devis de 1 234,56 €.");
+    assert_eq!(rendered.missing, vec!["task.title".to_string()]);
+    assert_eq!(
+        template.render(&without_quote).text,
+        "Bonjour M. Durand, sans devis."
+    );
+}
+
+#[test]
+fn amounts_and_dates_use_french_formatting() {
+    assert_eq!(format_eur(5), "0,05 €");
+    assert_eq!(format_eur(99_900), "999,00 €");
+    assert_eq!(format_eur(-1_250_000), "-12 500,00 €");
+    assert_eq!(
+        format_french_date(NaiveDate::from_ymd_opt(2026, 3, 18).unwrap()),
+        "18 mars 2026"
+    );
+
+    // 2026-03-18 13:00 UTC is 14h00 in Paris.
+    let rendered = Template::parse("{{slot}}").unwrap().render(&context(&[(
+        "slot",
+        TemplateValue::DateTime(1_773_838_800_000),
+    )]));
+    assert_eq!(rendered.text, "18 mars 2026 à 14h00");
+}
+
+#[test]
+fn malformed_templates_are_rejected() {
+    assert_eq!(
+        Template::parse("Bonjour {{client.name"),
+        Err(TemplateError::UnclosedTag(8))
+    );
+    assert!(matches!(
+        Template::parse("{{#if quote.total}}oui"),
+        Err(TemplateError::UnclosedIf(condition)) if condition == "quote.total"
+    ));
+    assert!(matches!(
+        Template::parse("{{/if}}"),
+        Err(TemplateError::UnexpectedTag(..))
+    ));
+    assert!(matches!(
+        Template::parse("{{client name}}"),
+        Err(TemplateError::UnexpectedTag(..))
+    ));
+}
+
+#[test]
+fn validation_flags_unknown_variables_unless_declared() {
+    let variables = validate_template(
+        Some("Devis {{quote.number}}"),
+        "{{#if client.name}}{{client.name}}{{/if}} {{slot}}",
+        &["slot".to_string()],
+    )
+    .expect("valid");
+    assert!(variables.contains("quote.number"));
+    assert!(variables.contains("slot"));
+
+    assert_eq!(
+        validate_template(None, "{{client.nom}} {{task.titel}}", &[]),
+        Err(TemplateError::UnknownVariables(vec![
+            "client.nom".to_string(),
+            "task.titel".to_string()
+        ]))
+    );
+}
+
+async fn seeded_db() -> Arc<Database> {
+    let db = Arc::new(Database::new_in_memory().await.expect("in-memory database"));
+    let now = chrono::Utc::now().timestamp_millis();
+    db.execute(
+        r#"INSERT INTO clients (id, name, email, phone, customer_type, total_tasks, active_tasks, completed_tasks, created_at, updated_at, synced)
+           VALUES ('client-1', 'Mme Martin', 'martin@example.com', '0612345678', 'individual', 0, 0, 0, ?, ?, 0)"#,
+        params![now, now],
+    )
+    .expect("seed client");
+    db.execute(
+        r#"INSERT INTO tasks (id, task_number, title, status, priority, client_id, scheduled_date,
+               vehicle_make, vehicle_model, vehicle_plate, created_at, updated_at, synced)
+           VALUES ('task-1', 'T-00042', 'Pose PPF capot', 'scheduled', 'high', 'client-1', '2026-03-18',
+               'Tesla', 'Model 3', 'AB-123-CD', ?, ?, 0)"#,
+        params![now, now],
+    )
+    .expect("seed task");
+    db.execute(
+        r#"INSERT INTO quotes (id, quote_number, client_id, task_id, status, subtotal, tax_total, total, created_at, updated_at)
+           VALUES ('quote-1', 'DEV-2026-0007', 'client-1', 'task-1', 'sent', 100000, 20000, 120000, ?, ?)"#,
+        params![now, now],
+    )
+    .expect("seed quote");
+    db
+}
+
+#[tokio::test]
+async fn loader_follows_quote_to_task_and_client() {
+    let db = seeded_db().await;
+
+    let context = TemplateContextLoader::new(db)
+        .load(&TemplateEntityRefs {
+            quote_id: Some("quote-1".to_string()),
+            ..Default::default()
+        })
+        .expect("context");
+
+    let rendered = Template::parse(
+        "{{client_name}} — {{quote.number}} : {{quote.total}} TTC pour {{task.vehicle}} le {{task.scheduled_date}}",
+    )
+    .unwrap()
+    .render(&context);
+    assert_eq!(
+        rendered.text,
+        "Mme Martin — DEV-2026-0007 : 1 200,00 € TTC pour Tesla Model 3 AB-123-CD le 18 mars 2026"
+    );
+    assert!(rendered.missing.is_empty());
+}
+
+#[tokio::test]
+async fn loader_reports_unknown_entities() {
+    let db = seeded_db().await;
+
+    let result = TemplateContextLoader::new(db).load(&TemplateEntityRefs {
+        task_id: Some("missing-task".to_string()),
+        ..Default::default()
+    });
+
+    assert!(matches!(result, Err(AppError::NotFound(_))));
+}
@@ -1,6 +1,7 @@
 //! Application-layer contracts (DTOs) for the Quotes bounded context.
 
 use crate::domains::quotes::domain::models::quote::*;
+use crate::domains::quotes::domain::models::quote_acceptance::SignQuoteAcceptanceRequest;
 use serde::Deserialize;
 use ts_rs::TS;
 
@@ -100,6 +101,40 @@ pub struct QuoteStatusRequest {
     pub correlation_id: Option<String>,
 }
 
+/// Record the customer's signed acceptance of a sent quote.
+#[derive(Deserialize, Debug, TS)]
+#[serde(deny_unknown_fields)]
+#[ts(export)]
+pub struct QuoteSignAcceptanceRequest {
+    pub id: String,
+    pub data: SignQuoteAcceptanceRequest,
+    #[serde(default)]
+    pub correlation_id: Option<String>,
+}
+
+/// Compare two revisions of a quote.
+#[derive(Deserialize, Debug, TS)]
+#[serde(deny_unknown_fields)]
+#[ts(export)]
+pub struct QuoteRevisionDiffRequest {
+    pub quote_id: String,
+    pub from_revision: i32,
+    pub to_revision: i32,
+    #[serde(default)]
+    pub correlation_id: Option<String>,
+}
+
+/// Export a historical revision of a quote to PDF.
+#[derive(Deserialize, Debug, TS)]
+#[serde(deny_unknown_fields)]
+#[ts(export)]
+pub struct QuoteRevisionExportRequest {
+    pub quote_id: String,
+    pub revision: i32,
+    #[serde(default)]
+    pub correlation_id: Option<String>,
+}
+
 /// TODO: document
 #[derive(Deserialize, Debug, TS)]
 #[serde(deny_unknown_fields)]
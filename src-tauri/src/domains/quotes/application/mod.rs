@@ -4,9 +4,12 @@
 //! contracts for external consumers.
 
 mod contracts;
+mod quote_acceptance;
 mod quote_attachment_service;
 mod quote_events;
 pub(crate) mod quote_export_service;
+mod quote_pdf;
+mod quote_revisions;
 pub(crate) mod quote_service;
 mod quote_status;
 // quote_task_creation module removed: cross-domain SQL (tasks table) violated ADR-001/ADR-004.
@@ -17,5 +20,6 @@ pub use contracts::{
     QuoteAttachmentUpdateRequest, QuoteAttachmentsGetRequest, QuoteConvertToTaskRequest,
     QuoteCreateRequest, QuoteDeleteRequest, QuoteDuplicateRequest, QuoteGetRequest,
     QuoteGetStatsRequest, QuoteItemAddRequest, QuoteItemDeleteRequest, QuoteItemUpdateRequest,
-    QuoteListRequest, QuoteStatusRequest, QuoteUpdateRequest,
+    QuoteListRequest, QuoteRevisionDiffRequest, QuoteRevisionExportRequest,
+    QuoteSignAcceptanceRequest, QuoteStatusRequest, QuoteUpdateRequest,
 };
@@ -0,0 +1,130 @@
+//! Signed customer acceptance ("bon pour accord") for `QuoteService`.
+//!
+//! Unlike `mark_accepted` (staff records the outcome), this captures the
+//! customer's own signature and binds it to the latest sent revision by hash.
+
+use tracing::{info, warn};
+
+use crate::domains::quotes::domain::models::quote::*;
+use crate::domains::quotes::domain::models::quote_acceptance::{
+    QuoteAcceptance, QuoteAcceptanceVerification, QuoteSignedAcceptResponse,
+    SignQuoteAcceptanceRequest,
+};
+use crate::shared::contracts::auth::UserRole;
+
+use super::quote_service::QuoteService;
+
+impl QuoteService {
+    /// Record the customer's signed acceptance (Sent → Accepted).
+    ///
+    /// `req.revision` must be the latest sent revision, so a customer can only
+    /// sign the version they were actually shown.
+    pub fn accept_with_signature(
+        &self,
+        id: &str,
+        req: SignQuoteAcceptanceRequest,
+        recorded_by: &str,
+        role: &UserRole,
+        correlation_id: &str,
+    ) -> Result<QuoteSignedAcceptResponse, String> {
+        Self::check_quote_permission(role, "update")?;
+        let quote = self.fetch_quote(id)?;
+
+        if !quote.status.can_transition_to(&QuoteStatus::Accepted) {
+            return Err(format!(
+                "Cannot accept: quote is in '{}' status (expected 'sent')",
+                quote.status
+            ));
+        }
+        req.validate()?;
+
+        let revision = self
+            .repo
+            .find_revisions(id)
+            .map_err(Self::map_repo_error)?
+            .pop()
+            .ok_or_else(|| {
+                "Ce devis n'a aucune révision envoyée : renvoyez-le avant signature.".to_string()
+            })?;
+        if revision.revision != req.revision {
+            return Err(format!(
+                "Revision {} is not the current sent revision ({})",
+                req.revision, revision.revision
+            ));
+        }
+        self.check_blocking_rules(
+            "quote_accepted",
+            &quote,
+            recorded_by,
+            correlation_id,
+            serde_json::json!({ "signed": true }),
+        )?;
+
+        let signed_at = chrono::Utc::now().timestamp_millis();
+        let acceptance = QuoteAcceptance {
+            id: crate::shared::utils::uuid::generate_uuid_string(),
+            quote_id: quote.id.clone(),
+            revision_id: revision.id.clone(),
+            revision: revision.revision,
+            signer_name: req.signer_name.trim().to_string(),
+            signature: req.signature.trim().to_string(),
+            payload_hash: revision.payload_hash(),
+            signed_at,
+            recorded_by: Some(recorded_by.to_string()),
+        };
+
+        self.repo
+            .record_acceptance(&acceptance)
+            .map_err(Self::map_repo_error)?;
+
+        self.emit_quote_customer_responded(&quote, "accepted", signed_at);
+        if let Err(e) = self.emit_quote_accepted(&quote, recorded_by, None) {
+            warn!(quote_id = %id, error = %e, "Failed to emit QuoteAccepted event");
+        }
+
+        info!(
+            quote_id = %id,
+            revision = acceptance.revision,
+            payload_hash = %acceptance.payload_hash,
+            "Quote accepted with customer signature"
+        );
+
+        Ok(QuoteSignedAcceptResponse {
+            quote: self.fetch_quote(id)?,
+            acceptance,
+        })
+    }
+
+    /// The signed acceptance of a quote, if any, with its hash re-checked
+    /// against the stored revision.
+    pub fn get_acceptance(
+        &self,
+        quote_id: &str,
+    ) -> Result<Option<QuoteAcceptanceVerification>, String> {
+        self.fetch_quote(quote_id)?;
+        let Some(acceptance) = self
+            .repo
+            .find_acceptance(quote_id)
+            .map_err(Self::map_repo_error)?
+        else {
+            return Ok(None);
+        };
+
+        let current_hash = self
+            .repo
+            .find_revision(quote_id, acceptance.revision)
+            .map_err(Self::map_repo_error)?
+            .map(|revision| revision.payload_hash())
+            .unwrap_or_default();
+        let hash_matches = current_hash == acceptance.payload_hash;
+        if !hash_matches {
+            warn!(quote_id = %quote_id, "Accepted quote revision no longer matches its signed hash");
+        }
+
+        Ok(Some(QuoteAcceptanceVerification {
+            acceptance,
+            current_hash,
+            hash_matches,
+        }))
+    }
+}
@@ -37,6 +37,43 @@ impl QuoteService {
             .map_err(|e| format!("Failed to emit QuoteAccepted event: {}", e))
     }
 
+    /// Emit QuoteSent event for the revision frozen by the send.
+    pub(super) fn emit_quote_sent(&self, quote: &Quote, revision: i32, sent_by: &str) {
+        use crate::shared::services::event_bus::event_factory;
+        use crate::shared::services::event_bus::EventPublisher;
+
+        let event = event_factory::quote_sent(
+            quote.id.clone(),
+            quote.quote_number.clone(),
+            quote.client_id.clone(),
+            quote.task_id.clone(),
+            revision,
+            quote.total,
+            sent_by.to_string(),
+        );
+
+        if let Err(e) = self.event_bus.publish(event) {
+            tracing::warn!("Failed to emit QuoteSent event: {}", e);
+        }
+    }
+
+    /// Emit QuoteExpired event.
+    pub(super) fn emit_quote_expired(&self, quote: &Quote, expired_by: &str) {
+        use crate::shared::services::event_bus::event_factory;
+        use crate::shared::services::event_bus::EventPublisher;
+
+        let event = event_factory::quote_expired(
+            quote.id.clone(),
+            quote.quote_number.clone(),
+            quote.client_id.clone(),
+            expired_by.to_string(),
+        );
+
+        if let Err(e) = self.event_bus.publish(event) {
+            tracing::warn!("Failed to emit QuoteExpired event: {}", e);
+        }
+    }
+
     /// Emit QuoteRejected event.
     pub(super) fn emit_quote_rejected(
         &self,
@@ -159,4 +196,28 @@ impl QuoteService {
             .publish(event)
             .map_err(|e| format!("Failed to emit QuoteConverted event: {}", e))
     }
+
+    /// Emit QuoteCustomerResponded event for a response made by the customer
+    /// themselves (e.g. a signed acceptance).
+    pub(super) fn emit_quote_customer_responded(
+        &self,
+        quote: &Quote,
+        action: &str,
+        responded_at_ms: i64,
+    ) {
+        use crate::domains::quotes::domain::events::QuoteCustomerResponded;
+        use crate::shared::services::event_bus::EventPublisher;
+
+        let event = QuoteCustomerResponded {
+            quote_id: quote.id.clone(),
+            quote_number: quote.quote_number.clone(),
+            action: action.to_string(),
+            customer_id: Some(quote.client_id.clone()),
+            responded_at_ms,
+        };
+
+        if let Err(e) = self.event_bus.publish(event.into()) {
+            tracing::warn!("Failed to emit QuoteCustomerResponded event: {}", e);
+        }
+    }
 }
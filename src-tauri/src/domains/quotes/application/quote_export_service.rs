@@ -9,29 +9,38 @@ use tracing::{error, info};
 use crate::commands::AppError;
 use crate::domains::quotes::application::QuoteConvertToTaskRequest;
 use crate::domains::quotes::domain::models::quote::*;
+use crate::domains::quotes::domain::models::quote_acceptance::QuoteAcceptance;
 use crate::domains::quotes::QuotesFacade;
 use crate::shared::context::RequestContext;
 use crate::shared::contracts::auth::UserRole;
+use crate::shared::contracts::deposit_gate::DepositGate;
 use crate::shared::services::cross_domain::CreateTaskRequest;
 
+use super::quote_pdf::generate_quote_pdf;
+
 /// Orchestrates quote export (PDF) and quote→task conversion.
 pub struct QuoteExportService {
     quote_service: Arc<crate::domains::quotes::application::quote_service::QuoteService>,
+    deposit_gate: Arc<dyn DepositGate>,
     app_data_dir: std::path::PathBuf,
 }
 
 impl QuoteExportService {
     pub fn new(
         quote_service: Arc<crate::domains::quotes::application::quote_service::QuoteService>,
+        deposit_gate: Arc<dyn DepositGate>,
         app_data_dir: std::path::PathBuf,
     ) -> Self {
         Self {
             quote_service,
+            deposit_gate,
             app_data_dir,
         }
     }
 
     /// Export a quote to PDF, returning the file path.
+    ///
+    /// A quote signed by the customer gets its "bon pour accord" page appended.
     pub async fn export_to_pdf(
         &self,
         quote_id: &str,
@@ -42,45 +51,109 @@ impl QuoteExportService {
 
         let quote = self.fetch_quote(&facade, &ctx.auth.role, quote_id)?;
 
+        let acceptance = facade
+            .get_acceptance(&ctx.auth.role, quote_id)?
+            .map(|verification| verification.acceptance);
+
+        let title = format!("DEVIS {}", quote.quote_number);
+        let file_name = format!("{}.pdf", quote.quote_number);
+        let file_path = self.write_pdf(quote, title, acceptance, &file_name).await?;
+
+        info!(quote_id = %quote_id, path = %file_path.display(), "Quote PDF exported");
+
+        Ok(QuoteExportResponse {
+            file_path: file_path.to_string_lossy().to_string(),
+        })
+    }
+
+    /// Export a historical revision of a quote to PDF, exactly as it was sent.
+    pub async fn export_revision_to_pdf(
+        &self,
+        quote_id: &str,
+        revision: i32,
+        ctx: &RequestContext,
+    ) -> Result<QuoteExportResponse, AppError> {
+        let facade = QuotesFacade::new(self.quote_service.clone());
+        facade.check_permission(&ctx.auth.role, "export")?;
+
+        let quote = self.fetch_quote(&facade, &ctx.auth.role, quote_id)?;
+        let frozen = facade.get_revision(&ctx.auth.role, quote_id, revision)?;
+        let acceptance = facade
+            .get_acceptance(&ctx.auth.role, quote_id)?
+            .map(|verification| verification.acceptance)
+            .filter(|acceptance| acceptance.revision == frozen.revision);
+
+        let title = format!(
+            "DEVIS {} - révision {}",
+            quote.quote_number, frozen.revision
+        );
+        let file_name = format!("{}-r{}.pdf", quote.quote_number, frozen.revision);
+        let file_path = self
+            .write_pdf(frozen.apply_to(&quote), title, acceptance, &file_name)
+            .await?;
+
+        info!(
+            quote_id = %quote_id,
+            revision,
+            path = %file_path.display(),
+            "Quote revision PDF exported"
+        );
+
+        Ok(QuoteExportResponse {
+            file_path: file_path.to_string_lossy().to_string(),
+        })
+    }
+
+    /// Render `quote` into `app_data_dir/quotes/{file_name}`.
+    async fn write_pdf(
+        &self,
+        quote: Quote,
+        title: String,
+        acceptance: Option<QuoteAcceptance>,
+        file_name: &str,
+    ) -> Result<std::path::PathBuf, AppError> {
         let pdf_dir = self.app_data_dir.join("quotes");
         tokio::fs::create_dir_all(&pdf_dir).await.map_err(|e| {
             error!("Failed to create quotes directory: {}", e);
             AppError::Io("Failed to create export directory".to_string())
         })?;
 
-        let file_name = format!("{}.pdf", quote.quote_number);
-        let file_path = pdf_dir.join(&file_name);
+        let file_path = pdf_dir.join(file_name);
 
         let file_path_for_pdf = file_path.clone();
-        tokio::task::spawn_blocking(move || generate_quote_pdf(&quote, &file_path_for_pdf))
-            .await
-            .map_err(|e| {
-                error!("Failed to join PDF generation task: {}", e);
-                AppError::Internal("PDF generation failed".to_string())
-            })?
-            .map_err(|e| {
-                error!("Failed to generate PDF: {}", e);
-                AppError::Internal("PDF generation failed".to_string())
-            })?;
-
-        info!(quote_id = %quote_id, path = %file_path.display(), "Quote PDF exported");
-
-        Ok(QuoteExportResponse {
-            file_path: file_path.to_string_lossy().to_string(),
+        tokio::task::spawn_blocking(move || {
+            generate_quote_pdf(&quote, &title, acceptance.as_ref(), &file_path_for_pdf)
         })
+        .await
+        .map_err(|e| {
+            error!("Failed to join PDF generation task: {}", e);
+            AppError::Internal("PDF generation failed".to_string())
+        })?
+        .map_err(|e| {
+            error!("Failed to generate PDF: {}", e);
+            AppError::Internal("PDF generation failed".to_string())
+        })?;
+
+        Ok(file_path)
     }
 
     /// Build the task creation request from a quote (step 1 of quote→task).
     ///
-    /// The caller (IPC layer) creates the task via `TaskService`, then calls
-    /// [`Self::record_task_conversion`] with the resulting IDs.
-    pub fn build_task_request(
+    /// Refuses a quote that is not accepted or whose deposit (acompte) is not
+    /// collected, before any task exists. The caller (IPC layer) creates the
+    /// task via `TaskService`, then calls [`Self::record_task_conversion`]
+    /// with the resulting IDs.
+    pub async fn build_task_request(
         &self,
         request: &QuoteConvertToTaskRequest,
         ctx: &RequestContext,
     ) -> Result<CreateTaskRequest, AppError> {
         let facade = QuotesFacade::new(self.quote_service.clone());
         let quote = self.fetch_quote(&facade, &ctx.auth.role, &request.quote_id)?;
+        quote.can_be_converted().map_err(AppError::Validation)?;
+        self.deposit_gate
+            .check_deposit_paid(ctx, &quote.id, &quote.quote_number, quote.total)
+            .await?;
 
         let today = chrono::Utc::now().format("%Y-%m-%d").to_string();
         let ppf_zones = request
@@ -173,93 +246,3 @@ impl QuoteExportService {
         }
     }
 }
-
-/// Generate a minimal PDF for a quote.
-fn generate_quote_pdf(quote: &Quote, path: &std::path::Path) -> std::io::Result<()> {
-    use std::io::Write;
-
-    let mut content = String::new();
-
-    content.push_str(&format!("DEVIS {}\n\n", quote.quote_number));
-    content.push_str(&format!("Client: {}\n", quote.client_id));
-
-    if let Some(ref plate) = quote.vehicle_plate {
-        content.push_str(&format!("Véhicule: {}", plate));
-        if let Some(ref make) = quote.vehicle_make {
-            content.push_str(&format!(" {} ", make));
-        }
-        if let Some(ref model) = quote.vehicle_model {
-            content.push_str(model);
-        }
-        content.push('\n');
-    }
-
-    content.push_str("\n--- Articles ---\n");
-    for item in &quote.items {
-        let line_total = (item.qty * item.unit_price as f64) as i64;
-        content.push_str(&format!(
-            "{}: {} x {:.2}€ = {:.2}€\n",
-            item.label,
-            item.qty,
-            item.unit_price as f64 / 100.0,
-            line_total as f64 / 100.0
-        ));
-    }
-
-    content.push_str(&format!(
-        "\nSous-total: {:.2}€\n",
-        quote.subtotal as f64 / 100.0
-    ));
-    content.push_str(&format!("TVA: {:.2}€\n", quote.tax_total as f64 / 100.0));
-    content.push_str(&format!("Total: {:.2}€\n", quote.total as f64 / 100.0));
-
-    if let Some(ref terms) = quote.terms {
-        content.push_str(&format!("\nConditions:\n{}\n", terms));
-    }
-    if let Some(ref notes) = quote.notes {
-        content.push_str(&format!("\nNotes:\n{}\n", notes));
-    }
-
-    let stream_content = content
-        .replace('\\', "\\\\")
-        .replace('(', "\\(")
-        .replace(')', "\\)");
-
-    let mut lines = Vec::new();
-    let mut y = 750;
-    for line in stream_content.lines() {
-        lines.push(format!("BT /F1 12 Tf 50 {} Td ({}) Tj ET", y, line));
-        y -= 16;
-        if y < 50 {
-            break;
-        }
-    }
-    let stream = lines.join("\n");
-    let stream_len = stream.len();
-
-    let pdf = format!(
-        "%PDF-1.4\n\
-         1 0 obj<</Type/Catalog/Pages 2 0 R>>endobj\n\
-         2 0 obj<</Type/Pages/Kids[3 0 R]/Count 1>>endobj\n\
-         3 0 obj<</Type/Page/Parent 2 0 R/MediaBox[0 0 612 792]/Contents 4 0 R/Resources<</Font<</F1 5 0 R>>>>>>endobj\n\
-         4 0 obj<</Length {}>>stream\n{}\nendstream endobj\n\
-         5 0 obj<</Type/Font/Subtype/Type1/BaseFont/Helvetica>>endobj\n\
-         xref\n0 6\n\
-         0000000000 65535 f \n\
-         0000000009 00000 n \n\
-         0000000058 00000 n \n\
-         0000000115 00000 n \n\
-         0000000266 00000 n \n\
-         0000000{:03} 00000 n \n\
-         trailer<</Size 6/Root 1 0 R>>\n\
-         startxref\n0\n%%EOF",
-        stream_len,
-        stream,
-        300 + stream_len
-    );
-
-    let mut file = std::fs::File::create(path)?;
-    file.write_all(pdf.as_bytes())?;
-
-    Ok(())
-}
@@ -0,0 +1,420 @@
+//! Minimal hand-written PDF for quotes (standard Helvetica, no external
+//! renderer).
+//!
+//! Extracted from `quote_export_service.rs`. A signed quote gets a second
+//! "bon pour accord" page with the signer, date, revision hash and the
+//! captured signature image.
+
+use crate::domains::quotes::domain::models::quote::Quote;
+use crate::domains::quotes::domain::models::quote_acceptance::{decode_signature, QuoteAcceptance};
+
+const PAGE_WIDTH: u32 = 612;
+const PAGE_HEIGHT: u32 = 792;
+const LEFT_MARGIN: u32 = 50;
+const LINE_HEIGHT: u32 = 16;
+const SIGNATURE_MAX_WIDTH: f64 = 250.0;
+const SIGNATURE_MAX_HEIGHT: f64 = 120.0;
+
+/// Generate a quote PDF under `title`, with a "bon pour accord" page when
+/// `acceptance` is given.
+pub(super) fn generate_quote_pdf(
+    quote: &Quote,
+    title: &str,
+    acceptance: Option<&QuoteAcceptance>,
+    path: &std::path::Path,
+) -> std::io::Result<()> {
+    std::fs::write(path, render_quote_pdf(quote, title, acceptance))
+}
+
+/// Render the quote PDF to bytes.
+pub(super) fn render_quote_pdf(
+    quote: &Quote,
+    title: &str,
+    acceptance: Option<&QuoteAcceptance>,
+) -> Vec<u8> {
+    let mut pages = vec![text_stream(&quote_lines(quote, title), PAGE_HEIGHT - 42)];
+    let mut signature_image = None;
+
+    if let Some(acceptance) = acceptance {
+        let lines = acceptance_lines(quote, acceptance);
+        let mut stream = text_stream(&lines, PAGE_HEIGHT - 42);
+        match signature_rgb(&acceptance.signature) {
+            Some((width, height, rgb)) => {
+                let scale = (SIGNATURE_MAX_WIDTH / width as f64)
+                    .min(SIGNATURE_MAX_HEIGHT / height as f64)
+                    .min(1.0);
+                let (w, h) = (width as f64 * scale, height as f64 * scale);
+                let top = PAGE_HEIGHT - 42 - LINE_HEIGHT * (lines.len() as u32 + 1);
+                stream.extend_from_slice(
+                    format!(
+                        "\nq {:.2} 0 0 {:.2} {} {:.2} cm /Sig Do Q",
+                        w,
+                        h,
+                        LEFT_MARGIN,
+                        top as f64 - h
+                    )
+                    .as_bytes(),
+                );
+                signature_image = Some((width, height, rgb));
+            }
+            None => {
+                let top = PAGE_HEIGHT - 42 - LINE_HEIGHT * (lines.len() as u32 + 1);
+                stream.extend_from_slice(b"\n");
+                stream.extend_from_slice(&text_line(
+                    "[image de signature illisible]",
+                    LEFT_MARGIN,
+                    top,
+                ));
+            }
+        }
+        pages.push(stream);
+    }
+
+    // Objects: 1 catalog, 2 page tree, 3 font, then (page, content) pairs,
+    // then the signature image.
+    let page_count = pages.len();
+    let image_object = 4 + 2 * page_count;
+    let kids = (0..page_count)
+        .map(|i| format!("{} 0 R", 4 + 2 * i))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let mut objects: Vec<Vec<u8>> = vec![
+        b"<</Type/Catalog/Pages 2 0 R>>".to_vec(),
+        format!("<</Type/Pages/Kids[{}]/Count {}>>", kids, page_count).into_bytes(),
+        b"<</Type/Font/Subtype/Type1/BaseFont/Helvetica/Encoding/WinAnsiEncoding>>".to_vec(),
+    ];
+    for (index, content) in pages.iter().enumerate() {
+        let xobjects = if index == 1 && signature_image.is_some() {
+            format!("/XObject<</Sig {} 0 R>>", image_object)
+        } else {
+            String::new()
+        };
+        objects.push(
+            format!(
+                "<</Type/Page/Parent 2 0 R/MediaBox[0 0 {} {}]/Contents {} 0 R/Resources<</Font<</F1 3 0 R>>{}>>>>",
+                PAGE_WIDTH,
+                PAGE_HEIGHT,
+                5 + 2 * index,
+                xobjects
+            )
+            .into_bytes(),
+        );
+        objects.push(stream_object("", content));
+    }
+    if let Some((width, height, rgb)) = &signature_image {
+        objects.push(stream_object(
+            &format!(
+                "/Type/XObject/Subtype/Image/Width {}/Height {}/ColorSpace/DeviceRGB/BitsPerComponent 8",
+                width, height
+            ),
+            rgb,
+        ));
+    }
+
+    assemble(&objects)
+}
+
+fn quote_lines(quote: &Quote, title: &str) -> Vec<String> {
+    let mut content = String::new();
+
+    content.push_str(&format!("{}\n\n", title));
+    content.push_str(&format!("Client: {}\n", quote.client_id));
+
+    if let Some(ref plate) = quote.vehicle_plate {
+        content.push_str(&format!("Véhicule: {}", plate));
+        if let Some(ref make) = quote.vehicle_make {
+            content.push_str(&format!(" {} ", make));
+        }
+        if let Some(ref model) = quote.vehicle_model {
+            content.push_str(model);
+        }
+        content.push('\n');
+    }
+
+    content.push_str("\n--- Articles ---\n");
+    for item in &quote.items {
+        let line_total = (item.qty * item.unit_price as f64) as i64;
+        content.push_str(&format!(
+            "{}: {} x {:.2}€ = {:.2}€\n",
+            item.label,
+            item.qty,
+            item.unit_price as f64 / 100.0,
+            line_total as f64 / 100.0
+        ));
+    }
+
+    content.push_str(&format!(
+        "\nSous-total: {:.2}€\n",
+        quote.subtotal as f64 / 100.0
+    ));
+    content.push_str(&format!("TVA: {:.2}€\n", quote.tax_total as f64 / 100.0));
+    content.push_str(&format!("Total: {:.2}€\n", quote.total as f64 / 100.0));
+
+    if let Some(ref terms) = quote.terms {
+        content.push_str(&format!("\nConditions:\n{}\n", terms));
+    }
+    if let Some(ref notes) = quote.notes {
+        content.push_str(&format!("\nNotes:\n{}\n", notes));
+    }
+
+    content.lines().map(str::to_string).collect()
+}
+
+fn acceptance_lines(quote: &Quote, acceptance: &QuoteAcceptance) -> Vec<String> {
+    let signed_at = chrono::DateTime::from_timestamp_millis(acceptance.signed_at)
+        .map(|d| d.format("%d/%m/%Y %H:%M UTC").to_string())
+        .unwrap_or_default();
+    let (hash_head, hash_tail) = acceptance
+        .payload_hash
+        .split_at(acceptance.payload_hash.len().min(32));
+
+    vec![
+        "BON POUR ACCORD".to_string(),
+        String::new(),
+        format!(
+            "Devis {} - révision {}",
+            quote.quote_number, acceptance.revision
+        ),
+        format!("Montant TTC accepté: {:.2}€", quote.total as f64 / 100.0),
+        String::new(),
+        format!(
+            "Je soussigné(e) {} accepte le devis ci-dessus sans réserve.",
+            acceptance.signer_name
+        ),
+        format!("Signé le {}", signed_at),
+        String::new(),
+        "Empreinte SHA-256 de la révision acceptée:".to_string(),
+        hash_head.to_string(),
+        hash_tail.to_string(),
+        String::new(),
+        "Signature:".to_string(),
+    ]
+}
+
+/// Decode the signature to 8-bit RGB, flattening transparency onto white
+/// (signature pads usually export transparent PNGs).
+fn signature_rgb(signature: &str) -> Option<(u32, u32, Vec<u8>)> {
+    let bytes = decode_signature(signature).ok()?;
+    let rgba = image::load_from_memory(&bytes).ok()?.to_rgba8();
+    let (width, height) = rgba.dimensions();
+    let mut rgb = Vec::with_capacity((width * height * 3) as usize);
+    for pixel in rgba.pixels() {
+        let [r, g, b, a] = pixel.0;
+        for channel in [r, g, b] {
+            let blended = (channel as u32 * a as u32 + 255 * (255 - a as u32)) / 255;
+            rgb.push(blended as u8);
+        }
+    }
+    Some((width, height, rgb))
+}
+
+/// One text-show operation per line, top-down from `top`, stopping at the
+/// bottom margin.
+fn text_stream(lines: &[String], top: u32) -> Vec<u8> {
+    let mut stream = Vec::new();
+    let mut y = top;
+    for line in lines {
+        if y < 50 {
+            break;
+        }
+        if !stream.is_empty() {
+            stream.push(b'\n');
+        }
+        stream.extend_from_slice(&text_line(line, LEFT_MARGIN, y));
+        y -= LINE_HEIGHT;
+    }
+    stream
+}
+
+fn text_line(text: &str, x: u32, y: u32) -> Vec<u8> {
+    let mut line = format!("BT /F1 12 Tf {} {} Td (", x, y).into_bytes();
+    line.extend_from_slice(&encode_text(text));
+    line.extend_from_slice(b") Tj ET");
+    line
+}
+
+/// WinAnsi-encode and escape a string for a PDF literal.
+fn encode_text(text: &str) -> Vec<u8> {
+    let mut out = Vec::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '(' | ')' | '\\' => {
+                out.push(b'\\');
+                out.push(c as u8);
+            }
+            '€' => out.push(0x80),
+            '’' => out.push(0x92),
+            '–' => out.push(0x96),
+            '—' => out.push(0x97),
+            c if (c as u32) < 0x80 => out.push(c as u8),
+            c if (0xA0..=0xFF).contains(&(c as u32)) => out.push(c as u32 as u8),
+            _ => out.push(b'?'),
+        }
+    }
+    out
+}
+
+fn stream_object(dictionary_entries: &str, data: &[u8]) -> Vec<u8> {
+    let mut object =
+        format!("<<{}/Length {}>>stream\n", dictionary_entries, data.len()).into_bytes();
+    object.extend_from_slice(data);
+    object.extend_from_slice(b"\nendstream");
+    object
+}
+
+/// Serialise numbered objects (object `n` is `objects[n - 1]`) with a
+/// cross-reference table.
+fn assemble(objects: &[Vec<u8>]) -> Vec<u8> {
+    let mut pdf = b"%PDF-1.4\n".to_vec();
+    let mut offsets = Vec::with_capacity(objects.len());
+    for (index, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        pdf.extend_from_slice(format!("{} 0 obj\n", index + 1).as_bytes());
+        pdf.extend_from_slice(object);
+        pdf.extend_from_slice(b"\nendobj\n");
+    }
+    let xref_offset = pdf.len();
+    pdf.extend_from_slice(
+        format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1).as_bytes(),
+    );
+    for offset in offsets {
+        pdf.extend_from_slice(format!("{:010} 00000 n \n", offset).as_bytes());
+    }
+    pdf.extend_from_slice(
+        format!(
+            "trailer<</Size {}/Root 1 0 R>>\nstartxref\n{}\n%%EOF",
+            objects.len() + 1,
+            xref_offset
+        )
+        .as_bytes(),
+    );
+    pdf
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domains::quotes::domain::models::quote::{QuoteItem, QuoteItemKind, QuoteStatus};
+    use base64::{engine::general_purpose, Engine as _};
+
+    fn quote() -> Quote {
+        Quote {
+            id: "q1".to_string(),
+            quote_number: "DEV-2026-0001".to_string(),
+            client_id: "c1".to_string(),
+            task_id: None,
+            status: QuoteStatus::Accepted,
+            valid_until: None,
+            description: None,
+            notes: None,
+            terms: Some("Paiement à réception".to_string()),
+            subtotal: 50000,
+            tax_total: 10000,
+            total: 60000,
+            discount_type: None,
+            discount_value: None,
+            discount_amount: None,
+            vehicle_plate: None,
+            vehicle_make: None,
+            vehicle_model: None,
+            vehicle_year: None,
+            vehicle_vin: None,
+            created_at: 0,
+            updated_at: 0,
+            created_by: None,
+            items: vec![QuoteItem {
+                id: "i1".to_string(),
+                quote_id: "q1".to_string(),
+                kind: QuoteItemKind::Service,
+                label: "PPF capot".to_string(),
+                description: None,
+                qty: 1.0,
+                unit_price: 50000,
+                tax_rate: Some(20.0),
+                material_id: None,
+                position: 0,
+                created_at: 0,
+                updated_at: 0,
+            }],
+        }
+    }
+
+    fn png_signature() -> String {
+        let mut image = image::RgbaImage::new(4, 2);
+        image.put_pixel(0, 0, image::Rgba([0, 0, 0, 255]));
+        let mut bytes = std::io::Cursor::new(Vec::new());
+        image
+            .write_to(&mut bytes, image::ImageFormat::Png)
+            .expect("encode png");
+        general_purpose::STANDARD.encode(bytes.into_inner())
+    }
+
+    fn acceptance(signature: String) -> QuoteAcceptance {
+        QuoteAcceptance {
+            id: "a1".to_string(),
+            quote_id: "q1".to_string(),
+            revision_id: "r1".to_string(),
+            revision: 2,
+            signer_name: "Jean Dupont".to_string(),
+            signature,
+            payload_hash: "ab".repeat(32),
+            signed_at: 1_767_225_600_000,
+            recorded_by: None,
+        }
+    }
+
+    fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+        haystack.windows(needle.len()).any(|w| w == needle)
+    }
+
+    #[test]
+    fn test_unsigned_quote_is_single_page_with_valid_xref() {
+        let pdf = render_quote_pdf(&quote(), "DEVIS DEV-2026-0001", None);
+
+        assert!(contains(&pdf, b"/Count 1"));
+        assert!(!contains(&pdf, b"BON POUR ACCORD"));
+        // Accents and the euro sign are WinAnsi-encoded.
+        assert!(contains(&pdf, b"Paiement \xE0 r\xE9ception"));
+        assert!(contains(&pdf, b"600.00\x80"));
+
+        let text = String::from_utf8_lossy(&pdf);
+        let startxref: usize = text
+            .rsplit("startxref\n")
+            .next()
+            .and_then(|tail| tail.lines().next())
+            .and_then(|n| n.parse().ok())
+            .expect("startxref offset");
+        assert!(pdf[startxref..].starts_with(b"xref\n"));
+    }
+
+    #[test]
+    fn test_signed_quote_appends_acceptance_page_with_signature_image() {
+        let pdf = render_quote_pdf(
+            &quote(),
+            "DEVIS DEV-2026-0001",
+            Some(&acceptance(png_signature())),
+        );
+
+        assert!(contains(&pdf, b"/Count 2"));
+        assert!(contains(&pdf, b"BON POUR ACCORD"));
+        assert!(contains(&pdf, b"Devis DEV-2026-0001 - r\xE9vision 2"));
+        assert!(contains(&pdf, b"Je soussign\xE9\\(e\\) Jean Dupont"));
+        assert!(contains(&pdf, "ab".repeat(16).as_bytes()));
+        assert!(contains(&pdf, b"/Subtype/Image/Width 4/Height 2"));
+        assert!(contains(&pdf, b"/Sig Do"));
+    }
+
+    #[test]
+    fn test_unreadable_signature_still_renders_acceptance_page() {
+        let pdf = render_quote_pdf(
+            &quote(),
+            "DEVIS DEV-2026-0001",
+            Some(&acceptance("not-base64".to_string())),
+        );
+
+        assert!(contains(&pdf, b"BON POUR ACCORD"));
+        assert!(contains(&pdf, b"[image de signature illisible]"));
+        assert!(!contains(&pdf, b"/Subtype/Image"));
+    }
+}
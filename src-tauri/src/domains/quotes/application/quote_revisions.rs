@@ -0,0 +1,41 @@
+//! Revision history methods for `QuoteService`.
+//!
+//! Revisions are written by `mark_sent` (see `quote_status.rs`); this module
+//! only reads them back and compares them.
+
+use crate::domains::quotes::domain::models::quote_revision::{
+    diff_revisions, QuoteRevision, QuoteRevisionDiff,
+};
+
+use super::quote_service::QuoteService;
+
+impl QuoteService {
+    /// All revisions of a quote, oldest first.
+    pub fn get_revisions(&self, quote_id: &str) -> Result<Vec<QuoteRevision>, String> {
+        self.fetch_quote(quote_id)?;
+        self.repo
+            .find_revisions(quote_id)
+            .map_err(Self::map_repo_error)
+    }
+
+    /// A single revision by number.
+    pub fn get_revision(&self, quote_id: &str, revision: i32) -> Result<QuoteRevision, String> {
+        self.fetch_quote(quote_id)?;
+        self.repo
+            .find_revision(quote_id, revision)
+            .map_err(Self::map_repo_error)?
+            .ok_or_else(|| format!("Quote revision {} not found", revision))
+    }
+
+    /// Lines added, removed and changed between two revisions, with total deltas.
+    pub fn diff_revisions(
+        &self,
+        quote_id: &str,
+        from_revision: i32,
+        to_revision: i32,
+    ) -> Result<QuoteRevisionDiff, String> {
+        let from = self.get_revision(quote_id, from_revision)?;
+        let to = self.get_revision(quote_id, to_revision)?;
+        Ok(diff_revisions(&from, &to))
+    }
+}
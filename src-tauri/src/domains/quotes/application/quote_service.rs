@@ -15,6 +15,9 @@ use crate::domains::quotes::infrastructure::quote_repository::QuoteRepository;
 use crate::domains::quotes::infrastructure::quote_validation;
 use crate::shared::contracts::auth::UserRole;
 use crate::shared::contracts::notification::NotificationSender;
+use crate::shared::contracts::rules_engine::{
+    evaluate_blocking, BlockingRuleEngine, RuleCheckRequest,
+};
 use chrono::Utc;
 use std::sync::Arc;
 use tracing::{info, warn};
@@ -24,6 +27,8 @@ pub struct QuoteService {
     pub(super) repo: Arc<dyn IQuoteRepository>,
     pub(super) event_bus: Arc<crate::shared::services::event_bus::InMemoryEventBus>,
     pub(super) notification_sender: Arc<dyn NotificationSender>,
+    /// Optional blocking rules engine, checked before status transitions.
+    pub(super) rules_engine: Option<Arc<dyn BlockingRuleEngine>>,
 }
 
 impl QuoteService {
@@ -37,9 +42,20 @@ impl QuoteService {
             repo,
             event_bus,
             notification_sender,
+            rules_engine: None,
         }
     }
 
+    /// Attach a blocking rules engine.
+    ///
+    /// When present, `mark_sent`, `mark_accepted`, `accept_with_signature`
+    /// and `mark_expired` evaluate the `quote_sent`, `quote_accepted` and
+    /// `quote_expired` rules before changing the quote's status.
+    pub fn with_rules_engine(mut self, rules_engine: Arc<dyn BlockingRuleEngine>) -> Self {
+        self.rules_engine = Some(rules_engine);
+        self
+    }
+
     // ------------------------------------------------------------------
     // Helpers
     // ------------------------------------------------------------------
@@ -80,6 +96,52 @@ impl QuoteService {
             .ok_or_else(|| "Quote not found".to_string())
     }
 
+    /// Evaluate blocking rules for a status transition of `quote`.
+    ///
+    /// `extra` is merged into the payload (see `QuoteSentPayload` and
+    /// siblings in the rules domain). Returns the blocking rule's message.
+    pub(super) fn check_blocking_rules(
+        &self,
+        trigger: &str,
+        quote: &Quote,
+        user_id: &str,
+        correlation_id: &str,
+        extra: serde_json::Value,
+    ) -> Result<(), String> {
+        let Some(engine) = &self.rules_engine else {
+            return Ok(());
+        };
+        let mut payload = serde_json::json!({
+            "quote_id": quote.id,
+            "quote_number": quote.quote_number,
+            "client_id": quote.client_id,
+            "task_id": quote.task_id,
+            "total": quote.total,
+        });
+        if let (Some(target), serde_json::Value::Object(extra)) = (payload.as_object_mut(), extra)
+        {
+            target.extend(extra);
+        }
+        let outcome = evaluate_blocking(
+            engine.as_ref(),
+            &RuleCheckRequest {
+                trigger: trigger.to_string(),
+                entity_id: Some(quote.id.clone()),
+                payload,
+                user_id: user_id.to_string(),
+                correlation_id: correlation_id.to_string(),
+            },
+        )
+        .map_err(|e| format!("Rule evaluation failed: {}", e))?;
+        if outcome.allowed {
+            Ok(())
+        } else {
+            Err(outcome
+                .message
+                .unwrap_or_else(|| format!("Quote transition blocked by active rule ({})", trigger)))
+        }
+    }
+
     // ------------------------------------------------------------------
     // Quote CRUD
     // ------------------------------------------------------------------
@@ -420,6 +482,7 @@ impl QuoteService {
 }
 // Status transitions (mark_sent, mark_accepted, mark_rejected, mark_expired,
 // mark_changes_requested, reopen) and convert_to_task live in quote_status.rs.
+// Revision history (get_revisions, diff_revisions) lives in quote_revisions.rs.
 
 #[cfg(test)]
 #[path = "../tests/quote_service_tests.rs"]
@@ -11,6 +11,7 @@
 use tracing::{info, warn};
 
 use crate::domains::quotes::domain::models::quote::*;
+use crate::domains::quotes::domain::models::quote_revision::QuoteRevision;
 use crate::shared::contracts::auth::UserRole;
 
 use super::quote_service::QuoteService;
@@ -22,19 +23,40 @@ impl QuoteService {
 
     /// Mark a quote as sent (Draft → Sent).
     /// Requires at least one item and a non-zero total.
-    pub fn mark_sent(&self, id: &str, role: &UserRole) -> Result<Quote, String> {
+    ///
+    /// Freezes the quote as sent into a new numbered revision in the same
+    /// transaction as the status change.
+    pub fn mark_sent(
+        &self,
+        id: &str,
+        sent_by: &str,
+        role: &UserRole,
+        correlation_id: &str,
+    ) -> Result<Quote, String> {
         Self::check_quote_permission(role, "update")?;
         let quote = self.fetch_quote(id)?;
 
         quote.can_be_sent()?;
-
-        self.repo
-            .update_status(id, &QuoteStatus::Sent)
+        self.check_blocking_rules(
+            "quote_sent",
+            &quote,
+            sent_by,
+            correlation_id,
+            serde_json::json!({}),
+        )?;
+
+        let snapshot =
+            QuoteRevision::snapshot(&quote, sent_by, chrono::Utc::now().timestamp_millis());
+        let revision = self
+            .repo
+            .freeze_revision_and_mark_sent(&snapshot)
             .map_err(Self::map_repo_error)?;
 
-        info!(quote_id = %id, "Quote marked as sent");
+        info!(quote_id = %id, revision, "Quote marked as sent");
 
-        self.fetch_quote(id)
+        let updated_quote = self.fetch_quote(id)?;
+        self.emit_quote_sent(&updated_quote, revision, sent_by);
+        Ok(updated_quote)
     }
 
     /// Mark a quote as accepted (Sent → Accepted).
@@ -46,6 +68,7 @@ impl QuoteService {
         id: &str,
         accepted_by: &str,
         role: &UserRole,
+        correlation_id: &str,
     ) -> Result<QuoteAcceptResponse, String> {
         Self::check_quote_permission(role, "update")?;
         let quote = self.fetch_quote(id)?;
@@ -56,6 +79,13 @@ impl QuoteService {
                 quote.status
             ));
         }
+        self.check_blocking_rules(
+            "quote_accepted",
+            &quote,
+            accepted_by,
+            correlation_id,
+            serde_json::json!({ "signed": false }),
+        )?;
 
         self.repo
             .update_status(id, &QuoteStatus::Accepted)
@@ -116,7 +146,13 @@ impl QuoteService {
     ///
     /// Can be triggered manually (Admin) or automatically when `valid_until`
     /// is in the past.
-    pub fn mark_expired(&self, id: &str, role: &UserRole) -> Result<Quote, String> {
+    pub fn mark_expired(
+        &self,
+        id: &str,
+        expired_by: &str,
+        role: &UserRole,
+        correlation_id: &str,
+    ) -> Result<Quote, String> {
         Self::check_quote_permission(role, "delete")?;
         let quote = self.fetch_quote(id)?;
 
@@ -126,6 +162,13 @@ impl QuoteService {
                 quote.status
             ));
         }
+        self.check_blocking_rules(
+            "quote_expired",
+            &quote,
+            expired_by,
+            correlation_id,
+            serde_json::json!({}),
+        )?;
 
         self.repo
             .update_status(id, &QuoteStatus::Expired)
@@ -133,7 +176,9 @@ impl QuoteService {
 
         info!(quote_id = %id, "Quote marked as expired");
 
-        self.fetch_quote(id)
+        let updated_quote = self.fetch_quote(id)?;
+        self.emit_quote_expired(&updated_quote, expired_by);
+        Ok(updated_quote)
     }
 
     /// Mark a quote as changes_requested (Sent → ChangesRequested).
@@ -205,12 +250,7 @@ impl QuoteService {
         Self::check_quote_permission(role, "update")?;
         let quote = self.fetch_quote(quote_id)?;
 
-        if !quote.status.can_transition_to(&QuoteStatus::Converted) {
-            return Err(format!(
-                "Seuls les devis acceptés peuvent être convertis en tâche (statut actuel: '{}')",
-                quote.status
-            ));
-        }
+        quote.can_be_converted()?;
 
         // Atomic: link the task and flip the status in a single transaction so a
         // partial failure never leaves a task linked to a quote whose status was
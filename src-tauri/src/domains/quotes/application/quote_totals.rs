@@ -10,6 +10,7 @@
 //! Fixed `discount_value` is in cents and is capped at the subtotal.
 
 use crate::shared::repositories::base::RepoError;
+use crate::shared::utils::money::{line_total_cents, prorate_cents, tax_cents};
 use tracing::debug;
 
 use super::quote_service::QuoteService;
@@ -26,8 +27,7 @@ impl QuoteService {
 
         for item in &items {
             // ROUND_HALF_UP at each line to avoid accumulation of truncation errors
-            let line_total = (item.qty * item.unit_price as f64).round() as i64;
-            subtotal += line_total;
+            subtotal += line_total_cents(item.qty, item.unit_price);
         }
 
         // Apply discount
@@ -38,17 +38,16 @@ impl QuoteService {
         let mut discounted_tax_total: i64 = 0;
 
         for item in &items {
-            let line_total = (item.qty * item.unit_price as f64).round() as i64;
             if let Some(tax_rate) = item.tax_rate {
-                discounted_tax_total += (line_total as f64 * tax_rate / 100.0).round() as i64;
+                discounted_tax_total +=
+                    tax_cents(line_total_cents(item.qty, item.unit_price), tax_rate);
             }
         }
 
         // Proportionally adjust tax when a discount is applied
         if discount_amount > 0 {
-            discounted_tax_total = (discounted_tax_total as f64 * subtotal_after_discount as f64
-                / subtotal.max(1) as f64)
-                .round() as i64;
+            discounted_tax_total =
+                prorate_cents(discounted_tax_total, subtotal_after_discount, subtotal);
         }
 
         let total = subtotal_after_discount + discounted_tax_total;
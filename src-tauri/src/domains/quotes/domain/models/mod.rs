@@ -1 +1,3 @@
 pub mod quote;
+pub mod quote_acceptance;
+pub mod quote_revision;
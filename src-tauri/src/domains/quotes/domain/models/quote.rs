@@ -12,6 +12,9 @@ use ts_rs::TS;
 
 use crate::shared::contracts::RepoResult;
 
+use super::quote_acceptance::QuoteAcceptance;
+use super::quote_revision::QuoteRevision;
+
 /// Repository trait for quote operations (ADR-005)
 pub trait IQuoteRepository: Send + Sync + std::fmt::Debug {
     fn next_quote_number(&self) -> RepoResult<String>;
@@ -64,6 +67,15 @@ pub trait IQuoteRepository: Send + Sync + std::fmt::Debug {
     ) -> RepoResult<()>;
     fn delete_attachment(&self, id: &str, quote_id: &str) -> RepoResult<bool>;
     fn get_stats(&self) -> RepoResult<QuoteStats>;
+    /// Record `revision` under the next revision number and set the quote to
+    /// `Sent`, atomically. Returns the assigned revision number.
+    fn freeze_revision_and_mark_sent(&self, revision: &QuoteRevision) -> RepoResult<i32>;
+    fn find_revisions(&self, quote_id: &str) -> RepoResult<Vec<QuoteRevision>>;
+    fn find_revision(&self, quote_id: &str, revision: i32) -> RepoResult<Option<QuoteRevision>>;
+    /// Insert `acceptance` and move the quote from `Sent` to `Accepted`,
+    /// atomically.
+    fn record_acceptance(&self, acceptance: &QuoteAcceptance) -> RepoResult<()>;
+    fn find_acceptance(&self, quote_id: &str) -> RepoResult<Option<QuoteAcceptance>>;
 }
 
 /// Quote status enumeration
@@ -262,6 +274,19 @@ impl Quote {
         }
         Ok(())
     }
+
+    /// Returns `Ok(())` when this quote may become a task (`Accepted →
+    /// Converted`). Checked before the task is created and again when the
+    /// link is recorded.
+    pub fn can_be_converted(&self) -> Result<(), String> {
+        if !self.status.can_transition_to(&QuoteStatus::Converted) {
+            return Err(format!(
+                "Seuls les devis acceptés peuvent être convertis en tâche (statut actuel: '{}')",
+                self.status
+            ));
+        }
+        Ok(())
+    }
 }
 
 /// Quote item entity
@@ -0,0 +1,129 @@
+//! Signed customer acceptance of a quote ("bon pour accord").
+//!
+//! The acceptance binds the signer to one sent revision through the SHA-256
+//! of that revision's canonical payload (see
+//! [`QuoteRevision::payload_hash`](super::quote_revision::QuoteRevision::payload_hash)).
+
+use crate::shared::contracts::common::serialize_timestamp;
+use base64::{engine::general_purpose, Engine as _};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use super::quote::Quote;
+
+/// Upper bound on the decoded signature image (a signature pad PNG is a few KB).
+pub const MAX_SIGNATURE_BYTES: usize = 512 * 1024;
+
+/// Recorded acceptance. Immutable once written.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct QuoteAcceptance {
+    pub id: String,
+    pub quote_id: String,
+    pub revision_id: String,
+    pub revision: i32,
+    pub signer_name: String,
+    /// Base64-encoded PNG or JPEG, without data URL prefix.
+    pub signature: String,
+    /// SHA-256 (hex) of the accepted revision's canonical payload.
+    pub payload_hash: String,
+    #[serde(serialize_with = "serialize_timestamp")]
+    #[ts(type = "string")]
+    pub signed_at: i64,
+    /// Staff member whose session captured the signature.
+    pub recorded_by: Option<String>,
+}
+
+/// Customer signature captured for a sent quote.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct SignQuoteAcceptanceRequest {
+    /// Revision number shown to the customer when signing. Must be the
+    /// latest sent revision.
+    pub revision: i32,
+    pub signer_name: String,
+    /// Base64-encoded PNG or JPEG, without data URL prefix.
+    pub signature: String,
+}
+
+impl SignQuoteAcceptanceRequest {
+    /// Validates the signer name and that the signature decodes to a PNG/JPEG image.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.signer_name.trim().is_empty() {
+            return Err("Le nom du signataire est obligatoire.".to_string());
+        }
+        decode_signature(&self.signature)?;
+        Ok(())
+    }
+}
+
+/// Decode a base64 signature and check it is a PNG or JPEG within size limits.
+pub fn decode_signature(signature: &str) -> Result<Vec<u8>, String> {
+    if signature.trim().is_empty() {
+        return Err("La signature est obligatoire.".to_string());
+    }
+    let bytes = general_purpose::STANDARD
+        .decode(signature.trim())
+        .map_err(|e| format!("Invalid signature data: {}", e))?;
+    if bytes.len() > MAX_SIGNATURE_BYTES {
+        return Err(format!(
+            "Signature image too large ({} bytes, max {})",
+            bytes.len(),
+            MAX_SIGNATURE_BYTES
+        ));
+    }
+    let is_png = bytes.starts_with(b"\x89PNG\r\n\x1a\n");
+    let is_jpeg = bytes.starts_with(b"\xFF\xD8\xFF");
+    if !is_png && !is_jpeg {
+        return Err("Signature must be a PNG or JPEG image".to_string());
+    }
+    Ok(bytes)
+}
+
+/// Stored acceptance plus a fresh check of its hash against the revision.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct QuoteAcceptanceVerification {
+    pub acceptance: QuoteAcceptance,
+    /// Hash recomputed from the stored revision now.
+    pub current_hash: String,
+    /// `false` means the stored revision no longer matches what was signed.
+    pub hash_matches: bool,
+}
+
+/// Response for a signed acceptance.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct QuoteSignedAcceptResponse {
+    pub quote: Quote,
+    pub acceptance: QuoteAcceptance,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PNG_HEADER: &[u8] = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR";
+
+    fn request(signer_name: &str, signature: &str) -> SignQuoteAcceptanceRequest {
+        SignQuoteAcceptanceRequest {
+            revision: 1,
+            signer_name: signer_name.to_string(),
+            signature: signature.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_validate_accepts_png_signature() {
+        let signature = general_purpose::STANDARD.encode(PNG_HEADER);
+        assert!(request("Jean Dupont", &signature).validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_rejects_blank_signer_and_non_image() {
+        let png = general_purpose::STANDARD.encode(PNG_HEADER);
+        assert!(request("  ", &png).validate().is_err());
+
+        let text = general_purpose::STANDARD.encode(b"not an image");
+        let err = request("Jean Dupont", &text).validate().unwrap_err();
+        assert!(err.contains("PNG or JPEG"), "got: {}", err);
+
+        assert!(request("Jean Dupont", "%%%").validate().is_err());
+    }
+}
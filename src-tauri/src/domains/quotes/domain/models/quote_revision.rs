@@ -0,0 +1,376 @@
+//! Quote revision model
+//!
+//! A revision is the frozen copy of a quote as it was sent to the customer.
+//! One is recorded on every `Draft → Sent` transition; revisions are never
+//! edited afterwards, so reopening a quote cannot rewrite what the customer saw.
+
+use crate::shared::contracts::common::{serialize_optional_timestamp, serialize_timestamp};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use super::quote::{Quote, QuoteItem};
+
+/// Frozen snapshot of a sent quote.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct QuoteRevision {
+    pub id: String,
+    pub quote_id: String,
+    /// 1-based, assigned in send order.
+    pub revision: i32,
+    #[serde(serialize_with = "serialize_optional_timestamp")]
+    #[ts(type = "string | null")]
+    pub valid_until: Option<i64>,
+    pub description: Option<String>,
+    pub terms: Option<String>,
+    pub discount_type: Option<String>,
+    pub discount_value: Option<i64>,
+    pub discount_amount: Option<i64>,
+    pub subtotal: i64,
+    pub tax_total: i64,
+    pub total: i64,
+    #[serde(serialize_with = "serialize_timestamp")]
+    #[ts(type = "string")]
+    pub created_at: i64,
+    pub created_by: Option<String>,
+    /// Lines as sent. `id` is the id of the originating quote item.
+    #[serde(default)]
+    pub items: Vec<QuoteItem>,
+}
+
+impl QuoteRevision {
+    /// Snapshot the customer-facing part of `quote`.
+    ///
+    /// `revision` is left at 0: the repository assigns the next number inside
+    /// the same transaction that records the snapshot.
+    pub fn snapshot(quote: &Quote, created_by: &str, now: i64) -> Self {
+        Self {
+            id: crate::shared::utils::uuid::generate_uuid_string(),
+            quote_id: quote.id.clone(),
+            revision: 0,
+            valid_until: quote.valid_until,
+            description: quote.description.clone(),
+            terms: quote.terms.clone(),
+            discount_type: quote.discount_type.clone(),
+            discount_value: quote.discount_value,
+            discount_amount: quote.discount_amount,
+            subtotal: quote.subtotal,
+            tax_total: quote.tax_total,
+            total: quote.total,
+            created_at: now,
+            created_by: Some(created_by.to_string()),
+            items: quote.items.clone(),
+        }
+    }
+
+    /// Rebuild the quote as it looked when this revision was sent.
+    ///
+    /// Fields not captured by the revision (vehicle, notes, client) are taken
+    /// from `current`.
+    pub fn apply_to(&self, current: &Quote) -> Quote {
+        Quote {
+            valid_until: self.valid_until,
+            description: self.description.clone(),
+            terms: self.terms.clone(),
+            discount_type: self.discount_type.clone(),
+            discount_value: self.discount_value,
+            discount_amount: self.discount_amount,
+            subtotal: self.subtotal,
+            tax_total: self.tax_total,
+            total: self.total,
+            items: self.items.clone(),
+            ..current.clone()
+        }
+    }
+
+    /// Canonical serialisation of everything the customer agrees to.
+    ///
+    /// Field order is fixed and timestamps are raw milliseconds, so the same
+    /// revision always yields the same bytes.
+    pub fn canonical_payload(&self) -> String {
+        #[derive(Serialize)]
+        struct CanonicalItem<'a> {
+            item_id: &'a str,
+            kind: String,
+            label: &'a str,
+            description: Option<&'a str>,
+            qty: f64,
+            unit_price: i64,
+            tax_rate: Option<f64>,
+            material_id: Option<&'a str>,
+            position: i32,
+        }
+
+        #[derive(Serialize)]
+        struct CanonicalRevision<'a> {
+            quote_id: &'a str,
+            revision: i32,
+            valid_until: Option<i64>,
+            description: Option<&'a str>,
+            terms: Option<&'a str>,
+            discount_type: Option<&'a str>,
+            discount_value: Option<i64>,
+            discount_amount: Option<i64>,
+            subtotal: i64,
+            tax_total: i64,
+            total: i64,
+            created_at: i64,
+            items: Vec<CanonicalItem<'a>>,
+        }
+
+        let canonical = CanonicalRevision {
+            quote_id: &self.quote_id,
+            revision: self.revision,
+            valid_until: self.valid_until,
+            description: self.description.as_deref(),
+            terms: self.terms.as_deref(),
+            discount_type: self.discount_type.as_deref(),
+            discount_value: self.discount_value,
+            discount_amount: self.discount_amount,
+            subtotal: self.subtotal,
+            tax_total: self.tax_total,
+            total: self.total,
+            created_at: self.created_at,
+            items: self
+                .items
+                .iter()
+                .map(|item| CanonicalItem {
+                    item_id: &item.id,
+                    kind: item.kind.to_string(),
+                    label: &item.label,
+                    description: item.description.as_deref(),
+                    qty: item.qty,
+                    unit_price: item.unit_price,
+                    tax_rate: item.tax_rate,
+                    material_id: item.material_id.as_deref(),
+                    position: item.position,
+                })
+                .collect(),
+        };
+
+        serde_json::to_string(&canonical).expect("canonical revision is always serialisable")
+    }
+
+    /// Lower-case hex SHA-256 of [`Self::canonical_payload`].
+    pub fn payload_hash(&self) -> String {
+        use sha2::{Digest, Sha256};
+        format!("{:x}", Sha256::digest(self.canonical_payload().as_bytes()))
+    }
+}
+
+/// A line present in both revisions whose content changed.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct QuoteItemChange {
+    pub item_id: String,
+    pub before: QuoteItem,
+    pub after: QuoteItem,
+    /// Names of the fields that differ (e.g. `"qty"`, `"unit_price"`).
+    pub fields: Vec<String>,
+}
+
+/// Differences between two revisions of the same quote.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct QuoteRevisionDiff {
+    pub quote_id: String,
+    pub from_revision: i32,
+    pub to_revision: i32,
+    pub added: Vec<QuoteItem>,
+    pub removed: Vec<QuoteItem>,
+    pub changed: Vec<QuoteItemChange>,
+    /// Header fields that differ (`"terms"`, `"valid_until"`, `"discount"`, ...).
+    pub changed_fields: Vec<String>,
+    pub subtotal_delta: i64,
+    pub tax_total_delta: i64,
+    pub total_delta: i64,
+    pub discount_amount_delta: i64,
+}
+
+/// Compare two revisions line by line.
+///
+/// Lines are matched on the originating item id. A pure reorder is not
+/// reported as a change.
+pub fn diff_revisions(from: &QuoteRevision, to: &QuoteRevision) -> QuoteRevisionDiff {
+    let mut added = Vec::new();
+    let mut changed = Vec::new();
+
+    for after in &to.items {
+        match from.items.iter().find(|i| i.id == after.id) {
+            None => added.push(after.clone()),
+            Some(before) => {
+                let fields = changed_item_fields(before, after);
+                if !fields.is_empty() {
+                    changed.push(QuoteItemChange {
+                        item_id: after.id.clone(),
+                        before: before.clone(),
+                        after: after.clone(),
+                        fields,
+                    });
+                }
+            }
+        }
+    }
+
+    let removed = from
+        .items
+        .iter()
+        .filter(|before| !to.items.iter().any(|i| i.id == before.id))
+        .cloned()
+        .collect();
+
+    let mut changed_fields = Vec::new();
+    if from.valid_until != to.valid_until {
+        changed_fields.push("valid_until".to_string());
+    }
+    if from.description != to.description {
+        changed_fields.push("description".to_string());
+    }
+    if from.terms != to.terms {
+        changed_fields.push("terms".to_string());
+    }
+    if from.discount_type != to.discount_type || from.discount_value != to.discount_value {
+        changed_fields.push("discount".to_string());
+    }
+
+    QuoteRevisionDiff {
+        quote_id: to.quote_id.clone(),
+        from_revision: from.revision,
+        to_revision: to.revision,
+        added,
+        removed,
+        changed,
+        changed_fields,
+        subtotal_delta: to.subtotal - from.subtotal,
+        tax_total_delta: to.tax_total - from.tax_total,
+        total_delta: to.total - from.total,
+        discount_amount_delta: to.discount_amount.unwrap_or(0) - from.discount_amount.unwrap_or(0),
+    }
+}
+
+fn changed_item_fields(before: &QuoteItem, after: &QuoteItem) -> Vec<String> {
+    let mut fields = Vec::new();
+    if before.kind != after.kind {
+        fields.push("kind");
+    }
+    if before.label != after.label {
+        fields.push("label");
+    }
+    if before.description != after.description {
+        fields.push("description");
+    }
+    if before.qty != after.qty {
+        fields.push("qty");
+    }
+    if before.unit_price != after.unit_price {
+        fields.push("unit_price");
+    }
+    if before.tax_rate != after.tax_rate {
+        fields.push("tax_rate");
+    }
+    if before.material_id != after.material_id {
+        fields.push("material_id");
+    }
+    fields.into_iter().map(String::from).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domains::quotes::domain::models::quote::QuoteItemKind;
+
+    fn item(id: &str, label: &str, qty: f64, unit_price: i64) -> QuoteItem {
+        QuoteItem {
+            id: id.to_string(),
+            quote_id: "q1".to_string(),
+            kind: QuoteItemKind::Service,
+            label: label.to_string(),
+            description: None,
+            qty,
+            unit_price,
+            tax_rate: Some(20.0),
+            material_id: None,
+            position: 0,
+            created_at: 0,
+            updated_at: 0,
+        }
+    }
+
+    fn revision(number: i32, items: Vec<QuoteItem>, subtotal: i64) -> QuoteRevision {
+        QuoteRevision {
+            id: format!("r{}", number),
+            quote_id: "q1".to_string(),
+            revision: number,
+            valid_until: None,
+            description: None,
+            terms: None,
+            discount_type: None,
+            discount_value: None,
+            discount_amount: None,
+            subtotal,
+            tax_total: subtotal / 5,
+            total: subtotal + subtotal / 5,
+            created_at: 0,
+            created_by: None,
+            items,
+        }
+    }
+
+    #[test]
+    fn test_diff_reports_added_removed_and_changed_lines() {
+        let from = revision(
+            1,
+            vec![
+                item("a", "Capot", 1.0, 50000),
+                item("b", "Pare-chocs", 1.0, 30000),
+            ],
+            80000,
+        );
+        let to = revision(
+            2,
+            vec![
+                item("a", "Capot", 2.0, 50000),
+                item("c", "Rétroviseurs", 1.0, 8000),
+            ],
+            108000,
+        );
+
+        let diff = diff_revisions(&from, &to);
+
+        assert_eq!(diff.from_revision, 1);
+        assert_eq!(diff.to_revision, 2);
+        assert_eq!(diff.added.len(), 1);
+        assert_eq!(diff.added[0].id, "c");
+        assert_eq!(diff.removed.len(), 1);
+        assert_eq!(diff.removed[0].id, "b");
+        assert_eq!(diff.changed.len(), 1);
+        assert_eq!(diff.changed[0].fields, vec!["qty".to_string()]);
+        assert_eq!(diff.subtotal_delta, 28000);
+        assert_eq!(diff.tax_total_delta, 5600);
+        assert_eq!(diff.total_delta, 33600);
+    }
+
+    #[test]
+    fn test_payload_hash_is_stable_and_covers_lines() {
+        let a = revision(1, vec![item("a", "Capot", 1.0, 50000)], 50000);
+        let same = a.clone();
+        let mut edited = a.clone();
+        edited.items[0].unit_price = 49999;
+
+        assert_eq!(a.payload_hash(), same.payload_hash());
+        assert_eq!(a.payload_hash().len(), 64);
+        assert_ne!(a.payload_hash(), edited.payload_hash());
+    }
+
+    #[test]
+    fn test_diff_ignores_reordering_and_reports_terms() {
+        let mut moved = item("a", "Capot", 1.0, 50000);
+        let from = revision(1, vec![moved.clone()], 50000);
+        moved.position = 3;
+        let mut to = revision(2, vec![moved], 50000);
+        to.terms = Some("Acompte 30 %".to_string());
+
+        let diff = diff_revisions(&from, &to);
+
+        assert!(diff.added.is_empty() && diff.removed.is_empty() && diff.changed.is_empty());
+        assert_eq!(diff.changed_fields, vec!["terms".to_string()]);
+        assert_eq!(diff.total_delta, 0);
+    }
+}
@@ -2,6 +2,10 @@ use std::sync::Arc;
 
 use crate::domains::quotes::application::quote_service::QuoteService;
 use crate::domains::quotes::domain::models::quote::*;
+use crate::domains::quotes::domain::models::quote_acceptance::{
+    QuoteAcceptanceVerification, QuoteSignedAcceptResponse, SignQuoteAcceptanceRequest,
+};
+use crate::domains::quotes::domain::models::quote_revision::{QuoteRevision, QuoteRevisionDiff};
 use crate::shared::contracts::auth::UserRole;
 use crate::shared::ipc::errors::AppError;
 
@@ -171,11 +175,56 @@ impl QuotesFacade {
             .map_err(|e| self.map_quote_service_error(e))
     }
 
-    /// TODO: document
-    pub fn mark_sent(&self, role: &UserRole, id: &str) -> Result<Quote, AppError> {
+    /// Send a quote, freezing its current content as a new revision.
+    pub fn mark_sent(
+        &self,
+        role: &UserRole,
+        id: &str,
+        user_id: &str,
+        correlation_id: &str,
+    ) -> Result<Quote, AppError> {
         self.check_permission(role, "status")?;
         self.quote_service
-            .mark_sent(id, role)
+            .mark_sent(id, user_id, role, correlation_id)
+            .map_err(|e| self.map_quote_service_error(e))
+    }
+
+    /// List the revisions sent for a quote, oldest first.
+    pub fn get_revisions(
+        &self,
+        role: &UserRole,
+        quote_id: &str,
+    ) -> Result<Vec<QuoteRevision>, AppError> {
+        self.check_permission(role, "read")?;
+        self.quote_service
+            .get_revisions(quote_id)
+            .map_err(|e| self.map_quote_service_error(e))
+    }
+
+    /// Fetch one revision of a quote by number.
+    pub fn get_revision(
+        &self,
+        role: &UserRole,
+        quote_id: &str,
+        revision: i32,
+    ) -> Result<QuoteRevision, AppError> {
+        self.check_permission(role, "read")?;
+        self.quote_service
+            .get_revision(quote_id, revision)
+            .map_err(|e| self.map_quote_service_error(e))
+    }
+
+    /// Compare two revisions of a quote.
+    pub fn diff_revisions(
+        &self,
+        role: &UserRole,
+        quote_id: &str,
+        from_revision: i32,
+        to_revision: i32,
+    ) -> Result<QuoteRevisionDiff, AppError> {
+        self.check_permission(role, "read")?;
+        self.quote_service
+            .diff_revisions(quote_id, from_revision, to_revision)
             .map_err(|e| self.map_quote_service_error(e))
     }
 
@@ -185,10 +234,38 @@ impl QuotesFacade {
         role: &UserRole,
         id: &str,
         user_id: &str,
+        correlation_id: &str,
     ) -> Result<QuoteAcceptResponse, AppError> {
         self.check_permission(role, "status")?;
         self.quote_service
-            .mark_accepted(id, user_id, role)
+            .mark_accepted(id, user_id, role, correlation_id)
+            .map_err(|e| self.map_quote_service_error(e))
+    }
+
+    /// Record the customer's signed acceptance of the latest sent revision.
+    pub fn accept_with_signature(
+        &self,
+        role: &UserRole,
+        id: &str,
+        data: SignQuoteAcceptanceRequest,
+        user_id: &str,
+        correlation_id: &str,
+    ) -> Result<QuoteSignedAcceptResponse, AppError> {
+        self.check_permission(role, "status")?;
+        self.quote_service
+            .accept_with_signature(id, data, user_id, role, correlation_id)
+            .map_err(|e| self.map_quote_service_error(e))
+    }
+
+    /// Signed acceptance of a quote, if any, with its hash re-verified.
+    pub fn get_acceptance(
+        &self,
+        role: &UserRole,
+        id: &str,
+    ) -> Result<Option<QuoteAcceptanceVerification>, AppError> {
+        self.check_permission(role, "read")?;
+        self.quote_service
+            .get_acceptance(id)
             .map_err(|e| self.map_quote_service_error(e))
     }
 
@@ -206,10 +283,16 @@ impl QuotesFacade {
     }
 
     /// TODO: document
-    pub fn mark_expired(&self, role: &UserRole, id: &str) -> Result<Quote, AppError> {
+    pub fn mark_expired(
+        &self,
+        role: &UserRole,
+        id: &str,
+        user_id: &str,
+        correlation_id: &str,
+    ) -> Result<Quote, AppError> {
         self.check_permission(role, "expire")?;
         self.quote_service
-            .mark_expired(id, role)
+            .mark_expired(id, user_id, role, correlation_id)
             .map_err(|e| self.map_quote_service_error(e))
     }
 
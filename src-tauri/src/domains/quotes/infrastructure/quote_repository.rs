@@ -3,11 +3,13 @@
 //! Keeps `QuoteRepository` as the public façade while delegating concrete SQL
 //! operations to focused internal modules.
 
+mod acceptance_ops;
 mod attachment_ops;
 mod helpers;
 mod item_ops;
 mod quote_write_ops;
 mod read_ops;
+mod revision_ops;
 
 use crate::db::Database;
 use crate::domains::quotes::domain::models::quote::{
@@ -15,6 +17,8 @@ use crate::domains::quotes::domain::models::quote::{
     QuoteStats, QuoteStatus, UpdateQuoteAttachmentRequest, UpdateQuoteItemRequest,
     UpdateQuoteRequest,
 };
+use crate::domains::quotes::domain::models::quote_acceptance::QuoteAcceptance;
+use crate::domains::quotes::domain::models::quote_revision::QuoteRevision;
 use crate::shared::repositories::base::RepoResult;
 use crate::shared::repositories::cache::{Cache, CacheKeyBuilder};
 use std::sync::Arc;
@@ -145,6 +149,26 @@ impl IQuoteRepository for QuoteRepository {
     fn get_stats(&self) -> RepoResult<QuoteStats> {
         self.get_stats()
     }
+
+    fn freeze_revision_and_mark_sent(&self, revision: &QuoteRevision) -> RepoResult<i32> {
+        self.freeze_revision_and_mark_sent(revision)
+    }
+
+    fn find_revisions(&self, quote_id: &str) -> RepoResult<Vec<QuoteRevision>> {
+        self.find_revisions(quote_id)
+    }
+
+    fn find_revision(&self, quote_id: &str, revision: i32) -> RepoResult<Option<QuoteRevision>> {
+        self.find_revision(quote_id, revision)
+    }
+
+    fn record_acceptance(&self, acceptance: &QuoteAcceptance) -> RepoResult<()> {
+        self.record_acceptance(acceptance)
+    }
+
+    fn find_acceptance(&self, quote_id: &str) -> RepoResult<Option<QuoteAcceptance>> {
+        self.find_acceptance(quote_id)
+    }
 }
 
 impl QuoteRepository {
@@ -287,6 +311,30 @@ impl QuoteRepository {
     pub fn get_stats(&self) -> RepoResult<QuoteStats> {
         read_ops::get_stats(self)
     }
+
+    pub fn freeze_revision_and_mark_sent(&self, revision: &QuoteRevision) -> RepoResult<i32> {
+        revision_ops::freeze_revision_and_mark_sent(self, revision)
+    }
+
+    pub fn find_revisions(&self, quote_id: &str) -> RepoResult<Vec<QuoteRevision>> {
+        revision_ops::find_revisions(self, quote_id)
+    }
+
+    pub fn find_revision(
+        &self,
+        quote_id: &str,
+        revision: i32,
+    ) -> RepoResult<Option<QuoteRevision>> {
+        revision_ops::find_revision(self, quote_id, revision)
+    }
+
+    pub fn record_acceptance(&self, acceptance: &QuoteAcceptance) -> RepoResult<()> {
+        acceptance_ops::record_acceptance(self, acceptance)
+    }
+
+    pub fn find_acceptance(&self, quote_id: &str) -> RepoResult<Option<QuoteAcceptance>> {
+        acceptance_ops::find_acceptance(self, quote_id)
+    }
 }
 
 // Cross-domain SQL (inserting into the `tasks` table from the quotes repository)
@@ -514,4 +562,43 @@ mod tests {
         assert_eq!(stats.sent, 1);
         assert_eq!(stats.accepted, 0);
     }
+
+    #[tokio::test]
+    async fn test_revisions_are_numbered_and_immutable() {
+        let db = Arc::new(setup_test_db().await);
+        let cache = Arc::new(Cache::new(100));
+        let repo = QuoteRepository::new(db.clone(), cache);
+
+        insert_test_client(&db, "client-rev");
+        let mut quote = make_test_quote("qr1", "client-rev");
+        repo.create(&quote).unwrap();
+        quote.items = vec![make_test_item("ir1", "qr1", 0)];
+
+        let first = QuoteRevision::snapshot(&quote, "test_user", 1);
+        assert_eq!(repo.freeze_revision_and_mark_sent(&first).unwrap(), 1);
+        // Already sent: a concurrent send must not freeze a second revision.
+        let second = QuoteRevision::snapshot(&quote, "test_user", 2);
+        let resend = repo.freeze_revision_and_mark_sent(&second).unwrap_err();
+        assert!(resend.to_string().contains("'draft'"), "got: {}", resend);
+        assert_eq!(repo.find_revisions("qr1").unwrap().len(), 1);
+
+        repo.update_status("qr1", &QuoteStatus::Draft).unwrap();
+        let second = QuoteRevision::snapshot(&quote, "test_user", 2);
+        assert_eq!(repo.freeze_revision_and_mark_sent(&second).unwrap(), 2);
+
+        let found = repo.find_by_id("qr1").unwrap().unwrap();
+        assert_eq!(found.status, QuoteStatus::Sent);
+
+        let revisions = repo.find_revisions("qr1").unwrap();
+        assert_eq!(revisions.len(), 2);
+        assert_eq!(revisions[0].items.len(), 1);
+        assert_eq!(revisions[0].items[0].id, "ir1");
+
+        let tampered = db.execute(
+            "UPDATE quote_revisions SET total = 1 WHERE quote_id = ?",
+            params!["qr1"],
+        );
+        assert!(tampered.is_err());
+        assert!(repo.find_revision("qr1", 3).unwrap().is_none());
+    }
 }
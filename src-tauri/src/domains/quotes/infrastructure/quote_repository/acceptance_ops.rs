@@ -0,0 +1,73 @@
+use crate::domains::quotes::domain::models::quote::QuoteStatus;
+use crate::domains::quotes::domain::models::quote_acceptance::QuoteAcceptance;
+use crate::shared::repositories::base::{RepoError, RepoResult};
+use rusqlite::params;
+
+use super::{helpers, QuoteRepository};
+
+pub(super) fn record_acceptance(
+    repo: &QuoteRepository,
+    acceptance: &QuoteAcceptance,
+) -> RepoResult<()> {
+    repo.db
+        .with_transaction(|tx| {
+            // Guard on the current status so two concurrent signatures cannot
+            // both succeed.
+            let rows = tx
+                .execute(
+                    "UPDATE quotes SET status = ?, updated_at = (unixepoch() * 1000) WHERE id = ? AND status = ? AND deleted_at IS NULL",
+                    params![
+                        QuoteStatus::Accepted.to_string(),
+                        acceptance.quote_id,
+                        QuoteStatus::Sent.to_string()
+                    ],
+                )
+                .map_err(|e| format!("Failed to update quote status: {}", e))?;
+            if rows == 0 {
+                return Err("Failed to record acceptance: quote is no longer in 'sent' status".to_string());
+            }
+
+            tx.execute(
+                r#"
+                INSERT INTO quote_acceptances (
+                    id, quote_id, revision_id, revision, signer_name, signature,
+                    payload_hash, signed_at, recorded_by
+                ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+                "#,
+                params![
+                    acceptance.id,
+                    acceptance.quote_id,
+                    acceptance.revision_id,
+                    acceptance.revision,
+                    acceptance.signer_name,
+                    acceptance.signature,
+                    acceptance.payload_hash,
+                    acceptance.signed_at,
+                    acceptance.recorded_by,
+                ],
+            )
+            .map_err(|e| format!("Failed to insert quote acceptance: {}", e))?;
+            Ok(())
+        })
+        .map_err(RepoError::Database)?;
+
+    helpers::invalidate_cache(repo, &acceptance.quote_id);
+    Ok(())
+}
+
+pub(super) fn find_acceptance(
+    repo: &QuoteRepository,
+    quote_id: &str,
+) -> RepoResult<Option<QuoteAcceptance>> {
+    repo.db
+        .query_single_as::<QuoteAcceptance>(
+            r#"
+            SELECT id, quote_id, revision_id, revision, signer_name, signature,
+                   payload_hash, signed_at, recorded_by
+            FROM quote_acceptances
+            WHERE quote_id = ?
+            "#,
+            params![quote_id],
+        )
+        .map_err(|e| RepoError::Database(format!("Failed to find quote acceptance: {}", e)))
+}
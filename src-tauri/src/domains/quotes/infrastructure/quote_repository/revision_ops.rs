@@ -0,0 +1,168 @@
+use crate::domains::quotes::domain::models::quote::{QuoteItem, QuoteStatus};
+use crate::domains::quotes::domain::models::quote_revision::QuoteRevision;
+use crate::shared::repositories::base::{RepoError, RepoResult};
+use rusqlite::params;
+
+use super::{helpers, QuoteRepository};
+
+const REVISION_COLUMNS: &str = r#"
+    id, quote_id, revision, valid_until, description, terms,
+    discount_type, discount_value, discount_amount,
+    subtotal, tax_total, total, created_at, created_by
+"#;
+
+pub(super) fn freeze_revision_and_mark_sent(
+    repo: &QuoteRepository,
+    revision: &QuoteRevision,
+) -> RepoResult<i32> {
+    let number = repo
+        .db
+        .with_transaction(|tx| {
+            let rows = tx
+                .execute(
+                    "UPDATE quotes SET status = ?, updated_at = (unixepoch() * 1000) WHERE id = ? AND status = ? AND deleted_at IS NULL",
+                    params![
+                        QuoteStatus::Sent.to_string(),
+                        revision.quote_id,
+                        QuoteStatus::Draft.to_string()
+                    ],
+                )
+                .map_err(|e| format!("Failed to update quote status: {}", e))?;
+            if rows == 0 {
+                return Err("Failed to send quote: quote is no longer in 'draft' status".to_string());
+            }
+
+            let number: i32 = tx
+                .query_row(
+                    "SELECT COALESCE(MAX(revision), 0) + 1 FROM quote_revisions WHERE quote_id = ?",
+                    params![revision.quote_id],
+                    |row| row.get(0),
+                )
+                .map_err(|e| format!("Failed to allocate revision number: {}", e))?;
+
+            tx.execute(
+                r#"
+                INSERT INTO quote_revisions (
+                    id, quote_id, revision, valid_until, description, terms,
+                    discount_type, discount_value, discount_amount,
+                    subtotal, tax_total, total, created_at, created_by
+                ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                "#,
+                params![
+                    revision.id,
+                    revision.quote_id,
+                    number,
+                    revision.valid_until,
+                    revision.description,
+                    revision.terms,
+                    revision.discount_type,
+                    revision.discount_value,
+                    revision.discount_amount,
+                    revision.subtotal,
+                    revision.tax_total,
+                    revision.total,
+                    revision.created_at,
+                    revision.created_by,
+                ],
+            )
+            .map_err(|e| format!("Failed to insert quote revision: {}", e))?;
+
+            for item in &revision.items {
+                tx.execute(
+                    r#"
+                    INSERT INTO quote_revision_items (
+                        revision_id, item_id, kind, label, description, qty,
+                        unit_price, tax_rate, material_id, position
+                    ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                    "#,
+                    params![
+                        revision.id,
+                        item.id,
+                        item.kind.to_string(),
+                        item.label,
+                        item.description,
+                        item.qty,
+                        item.unit_price,
+                        item.tax_rate,
+                        item.material_id,
+                        item.position,
+                    ],
+                )
+                .map_err(|e| format!("Failed to insert quote revision item: {}", e))?;
+            }
+            Ok(number)
+        })
+        .map_err(RepoError::Database)?;
+
+    helpers::invalidate_cache(repo, &revision.quote_id);
+    Ok(number)
+}
+
+pub(super) fn find_revisions(
+    repo: &QuoteRepository,
+    quote_id: &str,
+) -> RepoResult<Vec<QuoteRevision>> {
+    let mut revisions = repo
+        .db
+        .query_as::<QuoteRevision>(
+            &format!(
+                "SELECT {} FROM quote_revisions WHERE quote_id = ? ORDER BY revision ASC",
+                REVISION_COLUMNS
+            ),
+            params![quote_id],
+        )
+        .map_err(|e| RepoError::Database(format!("Failed to find quote revisions: {}", e)))?;
+
+    for revision in &mut revisions {
+        revision.items = find_revision_items(repo, revision)?;
+    }
+    Ok(revisions)
+}
+
+pub(super) fn find_revision(
+    repo: &QuoteRepository,
+    quote_id: &str,
+    number: i32,
+) -> RepoResult<Option<QuoteRevision>> {
+    let revision = repo
+        .db
+        .query_single_as::<QuoteRevision>(
+            &format!(
+                "SELECT {} FROM quote_revisions WHERE quote_id = ? AND revision = ?",
+                REVISION_COLUMNS
+            ),
+            params![quote_id, number],
+        )
+        .map_err(|e| RepoError::Database(format!("Failed to find quote revision: {}", e)))?;
+
+    match revision {
+        Some(mut revision) => {
+            revision.items = find_revision_items(repo, &revision)?;
+            Ok(Some(revision))
+        }
+        None => Ok(None),
+    }
+}
+
+/// Revision lines are mapped through `QuoteItem`'s row mapping: the frozen
+/// item id stands in for `id`, and the revision timestamp for both
+/// `created_at` and `updated_at`.
+fn find_revision_items(
+    repo: &QuoteRepository,
+    revision: &QuoteRevision,
+) -> RepoResult<Vec<QuoteItem>> {
+    repo.db
+        .query_as::<QuoteItem>(
+            r#"
+            SELECT ri.item_id AS id, r.quote_id, ri.kind, ri.label, ri.description,
+                   ri.qty, ri.unit_price, ri.tax_rate, ri.material_id, ri.position,
+                   r.created_at, r.created_at AS updated_at
+            FROM quote_revision_items ri
+            JOIN quote_revisions r ON r.id = ri.revision_id
+            WHERE ri.revision_id = ?
+            ORDER BY ri.position ASC
+            "#,
+            params![revision.id],
+        )
+        .map_err(|e| RepoError::Database(format!("Failed to find quote revision items: {}", e)))
+}
@@ -4,6 +4,8 @@ use crate::db::FromSqlRow;
 use crate::domains::quotes::domain::models::quote::{
     AttachmentType, Quote, QuoteAttachment, QuoteItem, QuoteItemKind, QuoteStatus,
 };
+use crate::domains::quotes::domain::models::quote_acceptance::QuoteAcceptance;
+use crate::domains::quotes::domain::models::quote_revision::QuoteRevision;
 use rusqlite::Row;
 
 fn get_i64_from_row(row: &Row, column: &str) -> rusqlite::Result<i64> {
@@ -124,3 +126,41 @@ impl FromSqlRow for QuoteAttachment {
         })
     }
 }
+
+impl FromSqlRow for QuoteRevision {
+    fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        Ok(Self {
+            id: row.get("id")?,
+            quote_id: row.get("quote_id")?,
+            revision: row.get("revision")?,
+            valid_until: get_optional_i64_from_row(row, "valid_until")?,
+            description: row.get("description")?,
+            terms: row.get("terms")?,
+            discount_type: row.get("discount_type")?,
+            discount_value: get_optional_i64_from_row(row, "discount_value")?,
+            discount_amount: get_optional_i64_from_row(row, "discount_amount")?,
+            subtotal: get_i64_from_row(row, "subtotal")?,
+            tax_total: get_i64_from_row(row, "tax_total")?,
+            total: get_i64_from_row(row, "total")?,
+            created_at: get_i64_from_row(row, "created_at")?,
+            created_by: row.get("created_by")?,
+            items: Vec::new(),
+        })
+    }
+}
+
+impl FromSqlRow for QuoteAcceptance {
+    fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        Ok(Self {
+            id: row.get("id")?,
+            quote_id: row.get("quote_id")?,
+            revision_id: row.get("revision_id")?,
+            revision: row.get("revision")?,
+            signer_name: row.get("signer_name")?,
+            signature: row.get("signature")?,
+            payload_hash: row.get("payload_hash")?,
+            signed_at: get_i64_from_row(row, "signed_at")?,
+            recorded_by: row.get("recorded_by")?,
+        })
+    }
+}
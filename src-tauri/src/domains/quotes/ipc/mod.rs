@@ -1,7 +1,9 @@
+pub(crate) mod quote_acceptance;
 pub(crate) mod quote_attachments;
 pub(crate) mod quote_crud;
 pub(crate) mod quote_export;
 pub(crate) mod quote_items;
+pub(crate) mod quote_revisions;
 pub(crate) mod quote_status;
 
 /// Re-export all handlers so existing `domains::quotes::ipc::quote::*` paths
@@ -6,9 +6,13 @@
 //! - `quote_items`       — line-item add/update/delete
 //! - `quote_attachments` — attachment CRUD + open
 //! - `quote_export`      — PDF export + convert-to-task
+//! - `quote_revisions`   — sent-revision history + diff
+//! - `quote_acceptance`  — signed customer acceptance
 
+pub use super::quote_acceptance::*;
 pub use super::quote_attachments::*;
 pub use super::quote_crud::*;
 pub use super::quote_export::*;
 pub use super::quote_items::*;
+pub use super::quote_revisions::*;
 pub use super::quote_status::*;
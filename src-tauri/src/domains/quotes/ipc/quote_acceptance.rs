@@ -0,0 +1,66 @@
+//! Signed customer acceptance commands — accept_with_signature, get_acceptance
+
+use crate::commands::{ApiResponse, AppError, AppState};
+use crate::domains::quotes::domain::models::quote_acceptance::{
+    QuoteAcceptanceVerification, QuoteSignedAcceptResponse,
+};
+use crate::domains::quotes::QuotesFacade;
+use tracing::{debug, error, info, instrument};
+
+use crate::domains::quotes::application::{QuoteGetRequest, QuoteSignAcceptanceRequest};
+use crate::resolve_context;
+
+/// Record the customer's signature on the latest sent revision (Sent → Accepted).
+/// ADR-018: Thin IPC layer
+#[tauri::command]
+#[instrument(skip(state, request), fields(quote_id = %request.id))]
+pub async fn quote_accept_with_signature(
+    request: QuoteSignAcceptanceRequest,
+    state: AppState<'_>,
+) -> Result<ApiResponse<QuoteSignedAcceptResponse>, AppError> {
+    debug!("quote_accept_with_signature command received");
+    let ctx = resolve_context!(&state, &request.correlation_id);
+    let correlation_id = ctx.correlation_id.clone();
+    let facade = QuotesFacade::new(state.quote_service.clone());
+
+    match facade.accept_with_signature(
+        &ctx.auth.role,
+        &request.id,
+        request.data,
+        ctx.user_id(),
+        &correlation_id,
+    ) {
+        Ok(response) => {
+            info!(quote_id = %request.id, "Quote accepted with customer signature");
+            Ok(ApiResponse::success(response).with_correlation_id(Some(correlation_id.clone())))
+        }
+        Err(e) => {
+            error!("Failed to record signed quote acceptance: {}", e);
+            Ok(ApiResponse::error(e).with_correlation_id(Some(correlation_id.clone())))
+        }
+    }
+}
+
+/// Get the signed acceptance of a quote (if any) with its hash re-verified.
+/// ADR-018: Thin IPC layer
+#[tauri::command]
+#[instrument(skip(state))]
+pub async fn quote_get_acceptance(
+    request: QuoteGetRequest,
+    state: AppState<'_>,
+) -> Result<ApiResponse<Option<QuoteAcceptanceVerification>>, AppError> {
+    debug!(quote_id = %request.id, "quote_get_acceptance command received");
+    let ctx = resolve_context!(&state, &request.correlation_id);
+    let correlation_id = ctx.correlation_id.clone();
+    let facade = QuotesFacade::new(state.quote_service.clone());
+
+    match facade.get_acceptance(&ctx.auth.role, &request.id) {
+        Ok(acceptance) => {
+            Ok(ApiResponse::success(acceptance).with_correlation_id(Some(correlation_id.clone())))
+        }
+        Err(e) => {
+            error!("Failed to get quote acceptance: {}", e);
+            Ok(ApiResponse::error(e).with_correlation_id(Some(correlation_id.clone())))
+        }
+    }
+}
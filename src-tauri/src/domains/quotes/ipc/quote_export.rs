@@ -1,4 +1,5 @@
-//! Quote export and conversion commands — export_pdf, convert_to_task
+//! Quote export and conversion commands — export_pdf, export_revision_pdf,
+//! convert_to_task
 //!
 //! ADR-018: Thin IPC layer — all business logic delegated to
 //! [`QuoteExportService`](crate::domains::quotes::application::quote_export_service::QuoteExportService).
@@ -6,16 +7,21 @@
 use crate::commands::{ApiResponse, AppError, AppState};
 use crate::domains::quotes::application::quote_export_service::QuoteExportService;
 use crate::domains::quotes::domain::models::quote::*;
+use crate::shared::contracts::deposit_gate::DepositGate;
 use crate::shared::services::event_bus::EventPublisher;
+use std::sync::Arc;
 use tracing::{debug, error, instrument, Span};
 
-use crate::domains::quotes::application::{QuoteConvertToTaskRequest, QuoteGetRequest};
+use crate::domains::quotes::application::{
+    QuoteConvertToTaskRequest, QuoteGetRequest, QuoteRevisionExportRequest,
+};
 use crate::resolve_context;
 
 /// Construct a per-request [`QuoteExportService`] from shared application state.
 fn export_service(state: &AppState<'_>) -> QuoteExportService {
     QuoteExportService::new(
         state.quote_service.clone(),
+        state.payments_service.clone() as Arc<dyn DepositGate>,
         state.app_config.app_data_dir.clone(),
     )
 }
@@ -38,6 +44,28 @@ pub async fn quote_export_pdf(
     Ok(ApiResponse::success(result).with_correlation_id(Some(ctx.correlation_id.clone())))
 }
 
+/// Export a previously sent revision of a quote to PDF.
+/// ADR-018: Thin IPC layer
+#[tauri::command]
+#[instrument(skip(state))]
+pub async fn quote_export_revision_pdf(
+    request: QuoteRevisionExportRequest,
+    state: AppState<'_>,
+) -> Result<ApiResponse<QuoteExportResponse>, AppError> {
+    debug!(
+        quote_id = %request.quote_id,
+        revision = request.revision,
+        "quote_export_revision_pdf command received"
+    );
+    let ctx = resolve_context!(&state, &request.correlation_id);
+
+    let result = export_service(&state)
+        .export_revision_to_pdf(&request.quote_id, request.revision, &ctx)
+        .await?;
+
+    Ok(ApiResponse::success(result).with_correlation_id(Some(ctx.correlation_id.clone())))
+}
+
 /// Convert an accepted quote to a task.
 ///
 /// Cross-domain orchestration delegated to [`QuoteExportService::convert_to_task`].
@@ -59,8 +87,9 @@ pub async fn quote_convert_to_task(
 
     let svc = export_service(&state);
 
-    // Step 1: build task request inside quotes domain.
-    let create_req = svc.build_task_request(&request, &ctx)?;
+    // Step 1: build task request inside quotes domain; refuses unaccepted or
+    // unpaid-deposit quotes before anything is created.
+    let create_req = svc.build_task_request(&request, &ctx).await?;
 
     // Step 2: create the task — cross-domain call at the IPC/composition layer.
     let task = state
@@ -0,0 +1,64 @@
+//! Quote revision commands — get_revisions, diff_revisions
+//!
+//! Revisions are frozen on every send; these commands are read-only.
+
+use crate::commands::{ApiResponse, AppError, AppState};
+use crate::domains::quotes::domain::models::quote_revision::{QuoteRevision, QuoteRevisionDiff};
+use crate::domains::quotes::QuotesFacade;
+use tracing::{debug, error, instrument};
+
+use crate::domains::quotes::application::{QuoteGetRequest, QuoteRevisionDiffRequest};
+use crate::resolve_context;
+
+/// List every revision sent for a quote, oldest first.
+/// ADR-018: Thin IPC layer
+#[tauri::command]
+#[instrument(skip(state))]
+pub async fn quote_get_revisions(
+    request: QuoteGetRequest,
+    state: AppState<'_>,
+) -> Result<ApiResponse<Vec<QuoteRevision>>, AppError> {
+    debug!(quote_id = %request.id, "quote_get_revisions command received");
+    let ctx = resolve_context!(&state, &request.correlation_id);
+    let correlation_id = ctx.correlation_id.clone();
+    let facade = QuotesFacade::new(state.quote_service.clone());
+
+    match facade.get_revisions(&ctx.auth.role, &request.id) {
+        Ok(revisions) => {
+            Ok(ApiResponse::success(revisions).with_correlation_id(Some(correlation_id.clone())))
+        }
+        Err(e) => {
+            error!("Failed to get quote revisions: {}", e);
+            Ok(ApiResponse::error(e).with_correlation_id(Some(correlation_id.clone())))
+        }
+    }
+}
+
+/// Compare two revisions of a quote: added/removed/changed lines and total deltas.
+/// ADR-018: Thin IPC layer
+#[tauri::command]
+#[instrument(skip(state))]
+pub async fn quote_diff_revisions(
+    request: QuoteRevisionDiffRequest,
+    state: AppState<'_>,
+) -> Result<ApiResponse<QuoteRevisionDiff>, AppError> {
+    debug!(quote_id = %request.quote_id, "quote_diff_revisions command received");
+    let ctx = resolve_context!(&state, &request.correlation_id);
+    let correlation_id = ctx.correlation_id.clone();
+    let facade = QuotesFacade::new(state.quote_service.clone());
+
+    match facade.diff_revisions(
+        &ctx.auth.role,
+        &request.quote_id,
+        request.from_revision,
+        request.to_revision,
+    ) {
+        Ok(diff) => {
+            Ok(ApiResponse::success(diff).with_correlation_id(Some(correlation_id.clone())))
+        }
+        Err(e) => {
+            error!("Failed to diff quote revisions: {}", e);
+            Ok(ApiResponse::error(e).with_correlation_id(Some(correlation_id.clone())))
+        }
+    }
+}
@@ -22,7 +22,7 @@ pub async fn quote_mark_sent(
     let correlation_id = ctx.correlation_id.clone();
     let facade = QuotesFacade::new(state.quote_service.clone());
 
-    match facade.mark_sent(&ctx.auth.role, &request.id) {
+    match facade.mark_sent(&ctx.auth.role, &request.id, ctx.user_id(), &correlation_id) {
         Ok(quote) => {
             info!(quote_id = %request.id, "Quote marked as sent");
             Ok(ApiResponse::success(quote).with_correlation_id(Some(correlation_id.clone())))
@@ -46,7 +46,7 @@ pub async fn quote_mark_accepted(
     let correlation_id = ctx.correlation_id.clone();
     let facade = QuotesFacade::new(state.quote_service.clone());
 
-    match facade.mark_accepted(&ctx.auth.role, &request.id, ctx.user_id()) {
+    match facade.mark_accepted(&ctx.auth.role, &request.id, ctx.user_id(), &correlation_id) {
         Ok(response) => {
             info!(quote_id = %request.id, "Quote accepted");
             Ok(ApiResponse::success(response).with_correlation_id(Some(correlation_id.clone())))
@@ -94,7 +94,7 @@ pub async fn quote_mark_expired(
     let correlation_id = ctx.correlation_id.clone();
     let facade = QuotesFacade::new(state.quote_service.clone());
 
-    match facade.mark_expired(&ctx.auth.role, &request.id) {
+    match facade.mark_expired(&ctx.auth.role, &request.id, ctx.user_id(), &correlation_id) {
         Ok(quote) => {
             info!(quote_id = %request.id, "Quote marked as expired");
             Ok(ApiResponse::success(quote).with_correlation_id(Some(correlation_id.clone())))
@@ -2,8 +2,13 @@
 
 use super::*;
 use crate::db::Database;
+use crate::domains::quotes::domain::models::quote_acceptance::SignQuoteAcceptanceRequest;
 use crate::domains::quotes::infrastructure::quote_repository::QuoteRepository;
 use crate::shared::contracts::auth::UserRole;
+use crate::shared::contracts::rules_engine::{
+    BlockingRuleEngine, RuleCheckOutcome, RuleCheckRequest,
+};
+use crate::shared::error::AppError;
 use crate::shared::repositories::cache::Cache;
 
 async fn setup_service_async() -> (QuoteService, Arc<Database>) {
@@ -174,7 +179,9 @@ async fn test_update_forbidden_when_not_draft() {
         .unwrap();
 
     // Mark as sent
-    service.mark_sent(&quote.id, &UserRole::Admin).unwrap();
+    service
+        .mark_sent(&quote.id, "test-user", &UserRole::Admin, "test-correlation")
+        .unwrap();
 
     // Try to update - should fail
     let result = service.update_quote(
@@ -216,10 +223,17 @@ async fn test_mark_accepted_from_sent_succeeds() {
             &UserRole::Admin,
         )
         .unwrap();
-    service.mark_sent(&quote.id, &UserRole::Admin).unwrap();
+    service
+        .mark_sent(&quote.id, "test-user", &UserRole::Admin, "test-correlation")
+        .unwrap();
 
     let result = service
-        .mark_accepted(&quote.id, "accepting-user", &UserRole::Admin)
+        .mark_accepted(
+            &quote.id,
+            "accepting-user",
+            &UserRole::Admin,
+            "test-correlation",
+        )
         .unwrap();
     assert_eq!(result.quote.status, QuoteStatus::Accepted);
 }
@@ -235,7 +249,8 @@ async fn test_status_transitions() {
     assert_eq!(quote.status, QuoteStatus::Draft);
 
     // Cannot accept a draft directly
-    let result = service.mark_accepted(&quote.id, "test-user", &UserRole::Admin);
+    let result =
+        service.mark_accepted(&quote.id, "test-user", &UserRole::Admin, "test-correlation");
     assert!(result.is_err());
 
     // Cannot reject a draft (changed behavior: only from Sent now)
@@ -266,7 +281,9 @@ async fn test_mark_rejected_from_sent_succeeds() {
             &UserRole::Admin,
         )
         .unwrap();
-    service.mark_sent(&quote.id, &UserRole::Admin).unwrap();
+    service
+        .mark_sent(&quote.id, "test-user", &UserRole::Admin, "test-correlation")
+        .unwrap();
 
     let rejected = service
         .mark_rejected(&quote.id, "test-user", &UserRole::Admin)
@@ -284,7 +301,7 @@ async fn test_mark_sent_with_no_items_fails() {
         .unwrap();
 
     // Try to send empty quote
-    let result = service.mark_sent(&quote.id, &UserRole::Admin);
+    let result = service.mark_sent(&quote.id, "test-user", &UserRole::Admin, "test-correlation");
     assert!(result.is_err());
     assert!(result.unwrap_err().contains("sans lignes"));
 }
@@ -322,7 +339,7 @@ async fn test_mark_sent_with_zero_total_fails() {
     let quote = service
         .create_quote(req, "test-user", &UserRole::Admin)
         .unwrap();
-    let result = service.mark_sent(&quote.id, &UserRole::Admin);
+    let result = service.mark_sent(&quote.id, "test-user", &UserRole::Admin, "test-correlation");
     assert!(result.is_err());
     assert!(result.unwrap_err().contains("total nul"));
 }
@@ -336,7 +353,9 @@ async fn test_mark_expired_from_draft_succeeds() {
         .create_quote(req, "test-user", &UserRole::Admin)
         .unwrap();
 
-    let expired = service.mark_expired(&quote.id, &UserRole::Admin).unwrap();
+    let expired = service
+        .mark_expired(&quote.id, "test-user", &UserRole::Admin, "test-correlation")
+        .unwrap();
     assert_eq!(expired.status, QuoteStatus::Expired);
 }
 
@@ -355,12 +374,14 @@ async fn test_mark_expired_from_accepted_fails() {
             &UserRole::Admin,
         )
         .unwrap();
-    service.mark_sent(&quote.id, &UserRole::Admin).unwrap();
     service
-        .mark_accepted(&quote.id, "user", &UserRole::Admin)
+        .mark_sent(&quote.id, "test-user", &UserRole::Admin, "test-correlation")
+        .unwrap();
+    service
+        .mark_accepted(&quote.id, "user", &UserRole::Admin, "test-correlation")
         .unwrap();
 
-    let result = service.mark_expired(&quote.id, &UserRole::Admin);
+    let result = service.mark_expired(&quote.id, "test-user", &UserRole::Admin, "test-correlation");
     assert!(result.is_err());
 }
 
@@ -811,9 +832,11 @@ async fn test_convert_to_task_is_atomic() {
             &UserRole::Admin,
         )
         .unwrap();
-    service.mark_sent(&quote.id, &UserRole::Admin).unwrap();
     service
-        .mark_accepted(&quote.id, "test-user", &UserRole::Admin)
+        .mark_sent(&quote.id, "test-user", &UserRole::Admin, "test-correlation")
+        .unwrap();
+    service
+        .mark_accepted(&quote.id, "test-user", &UserRole::Admin, "test-correlation")
         .unwrap();
 
     let task_id = "task-uuid-001";
@@ -934,10 +957,15 @@ async fn test_convert_to_task_emits_event_with_current_actor() {
         )
         .expect("add item");
     service
-        .mark_sent(&quote.id, &UserRole::Admin)
+        .mark_sent(&quote.id, "test-user", &UserRole::Admin, "test-correlation")
         .expect("mark sent");
     service
-        .mark_accepted(&quote.id, "acceptor-user", &UserRole::Admin)
+        .mark_accepted(
+            &quote.id,
+            "acceptor-user",
+            &UserRole::Admin,
+            "test-correlation",
+        )
         .expect("mark accepted");
 
     let task_id = "task-uuid-actor-001";
@@ -968,3 +996,349 @@ async fn test_convert_to_task_emits_event_with_current_actor() {
         .expect("quote converted event actor");
     assert_eq!(actor, "conversion-actor-user");
 }
+
+#[tokio::test]
+async fn test_resend_after_changes_requested_keeps_first_revision_intact() {
+    let (service, _db) = setup_service_async().await;
+
+    let quote = service
+        .create_quote(make_quote_req("test-client"), "test-user", &UserRole::Admin)
+        .unwrap();
+    let quote = service
+        .add_item(
+            &quote.id,
+            make_item("PPF Hood", 50000, 1.0, 20.0),
+            &UserRole::Admin,
+        )
+        .unwrap();
+    service
+        .mark_sent(&quote.id, "sender-1", &UserRole::Admin, "test-correlation")
+        .unwrap();
+
+    // Customer asks for changes; staff edit the same quote and send it again.
+    service
+        .mark_changes_requested(&quote.id, &UserRole::Admin)
+        .unwrap();
+    service.reopen(&quote.id, &UserRole::Admin).unwrap();
+    let hood_id = quote.items[0].id.clone();
+    service
+        .update_item(
+            &quote.id,
+            &hood_id,
+            UpdateQuoteItemRequest {
+                kind: None,
+                label: None,
+                description: None,
+                qty: Some(2.0),
+                unit_price: None,
+                tax_rate: None,
+                material_id: None,
+                position: None,
+            },
+            &UserRole::Admin,
+        )
+        .unwrap();
+    service
+        .add_item(
+            &quote.id,
+            make_item("Rétroviseurs", 8000, 1.0, 20.0),
+            &UserRole::Admin,
+        )
+        .unwrap();
+    service
+        .mark_sent(&quote.id, "sender-2", &UserRole::Admin, "test-correlation")
+        .unwrap();
+
+    let revisions = service.get_revisions(&quote.id).unwrap();
+    assert_eq!(revisions.len(), 2);
+    assert_eq!(revisions[0].revision, 1);
+    assert_eq!(revisions[0].created_by.as_deref(), Some("sender-1"));
+    assert_eq!(revisions[0].items.len(), 1);
+    assert_eq!(revisions[0].items[0].qty, 1.0);
+    assert_eq!(revisions[0].total, 60000);
+    assert_eq!(revisions[1].revision, 2);
+    assert_eq!(revisions[1].items.len(), 2);
+
+    let diff = service.diff_revisions(&quote.id, 1, 2).unwrap();
+    assert_eq!(diff.added.len(), 1);
+    assert_eq!(diff.added[0].label, "Rétroviseurs");
+    assert!(diff.removed.is_empty());
+    assert_eq!(diff.changed.len(), 1);
+    assert_eq!(diff.changed[0].item_id, hood_id);
+    assert_eq!(diff.changed[0].fields, vec!["qty".to_string()]);
+    // +1 hood (500.00) + mirrors (80.00) = +580.00 HT, +696.00 TTC
+    assert_eq!(diff.subtotal_delta, 58000);
+    assert_eq!(diff.total_delta, 69600);
+}
+
+#[tokio::test]
+async fn test_diff_revisions_unknown_revision_is_not_found() {
+    let (service, _db) = setup_service_async().await;
+
+    let quote = service
+        .create_quote(make_quote_req("test-client"), "test-user", &UserRole::Admin)
+        .unwrap();
+    service
+        .add_item(
+            &quote.id,
+            make_item("PPF", 10000, 1.0, 20.0),
+            &UserRole::Admin,
+        )
+        .unwrap();
+    service
+        .mark_sent(&quote.id, "test-user", &UserRole::Admin, "test-correlation")
+        .unwrap();
+
+    let err = service.diff_revisions(&quote.id, 1, 2).unwrap_err();
+    assert!(err.contains("not found"), "got: {}", err);
+}
+
+fn signature_png() -> String {
+    use base64::{engine::general_purpose, Engine as _};
+
+    let mut bytes = std::io::Cursor::new(Vec::new());
+    image::RgbaImage::new(8, 4)
+        .write_to(&mut bytes, image::ImageFormat::Png)
+        .expect("encode png");
+    general_purpose::STANDARD.encode(bytes.into_inner())
+}
+
+fn sign_req(revision: i32) -> SignQuoteAcceptanceRequest {
+    SignQuoteAcceptanceRequest {
+        revision,
+        signer_name: "Jean Dupont".to_string(),
+        signature: signature_png(),
+    }
+}
+
+fn sent_quote(service: &QuoteService) -> Quote {
+    let quote = service
+        .create_quote(make_quote_req("test-client"), "test-user", &UserRole::Admin)
+        .unwrap();
+    service
+        .add_item(
+            &quote.id,
+            make_item("PPF Hood", 50000, 1.0, 20.0),
+            &UserRole::Admin,
+        )
+        .unwrap();
+    service
+        .mark_sent(&quote.id, "test-user", &UserRole::Admin, "test-correlation")
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_accept_with_signature_binds_latest_revision_hash() {
+    let (service, _db) = setup_service_async().await;
+    let quote = sent_quote(&service);
+
+    let response = service
+        .accept_with_signature(
+            &quote.id,
+            sign_req(1),
+            "counter-user",
+            &UserRole::Admin,
+            "test-correlation",
+        )
+        .unwrap();
+
+    assert_eq!(response.quote.status, QuoteStatus::Accepted);
+    assert_eq!(response.acceptance.revision, 1);
+    assert_eq!(response.acceptance.signer_name, "Jean Dupont");
+    assert_eq!(
+        response.acceptance.recorded_by.as_deref(),
+        Some("counter-user")
+    );
+    let revision = service.get_revision(&quote.id, 1).unwrap();
+    assert_eq!(response.acceptance.payload_hash, revision.payload_hash());
+
+    let verification = service.get_acceptance(&quote.id).unwrap().unwrap();
+    assert!(verification.hash_matches);
+    assert_eq!(verification.current_hash, response.acceptance.payload_hash);
+}
+
+#[tokio::test]
+async fn test_accept_with_signature_rejects_stale_revision_and_double_signing() {
+    let (service, _db) = setup_service_async().await;
+    let quote = sent_quote(&service);
+
+    let err = service
+        .accept_with_signature(
+            &quote.id,
+            sign_req(2),
+            "counter-user",
+            &UserRole::Admin,
+            "test-correlation",
+        )
+        .unwrap_err();
+    assert!(
+        err.contains("not the current sent revision"),
+        "got: {}",
+        err
+    );
+
+    service
+        .accept_with_signature(
+            &quote.id,
+            sign_req(1),
+            "counter-user",
+            &UserRole::Admin,
+            "test-correlation",
+        )
+        .unwrap();
+    let err = service
+        .accept_with_signature(
+            &quote.id,
+            sign_req(1),
+            "counter-user",
+            &UserRole::Admin,
+            "test-correlation",
+        )
+        .unwrap_err();
+    assert!(err.contains("Cannot accept"), "got: {}", err);
+}
+
+#[tokio::test]
+async fn test_get_acceptance_is_none_for_unsigned_quote() {
+    let (service, _db) = setup_service_async().await;
+    let quote = sent_quote(&service);
+
+    assert!(service.get_acceptance(&quote.id).unwrap().is_none());
+}
+
+struct DenyTrigger(&'static str);
+
+#[async_trait::async_trait]
+impl BlockingRuleEngine for DenyTrigger {
+    async fn evaluate(&self, request: &RuleCheckRequest) -> Result<RuleCheckOutcome, AppError> {
+        let denied = request.trigger == self.0;
+        Ok(RuleCheckOutcome {
+            allowed: !denied,
+            matched_rule_ids: Vec::new(),
+            message: denied.then(|| {
+                format!(
+                    "{} over {} ({})",
+                    self.0, request.payload["total"], request.correlation_id
+                )
+            }),
+        })
+    }
+}
+
+#[tokio::test]
+async fn test_blocking_rule_stops_quote_send_but_not_other_transitions() {
+    let (service, _db) = setup_service_async().await;
+    let service = service.with_rules_engine(Arc::new(DenyTrigger("quote_sent")));
+    let quote = service
+        .create_quote(make_quote_req("test-client"), "test-user", &UserRole::Admin)
+        .unwrap();
+    service
+        .add_item(
+            &quote.id,
+            make_item("PPF", 10000, 1.0, 20.0),
+            &UserRole::Admin,
+        )
+        .unwrap();
+
+    let err = service
+        .mark_sent(&quote.id, "test-user", &UserRole::Admin, "corr-send")
+        .unwrap_err();
+    assert_eq!(err, "quote_sent over 12000 (corr-send)");
+    let unchanged = service.get_quote(&quote.id).unwrap().unwrap();
+    assert_eq!(unchanged.status, QuoteStatus::Draft);
+
+    service
+        .mark_expired(&quote.id, "test-user", &UserRole::Admin, "test-correlation")
+        .unwrap();
+}
+
+struct DepositGateStub {
+    outstanding: bool,
+}
+
+#[async_trait::async_trait]
+impl crate::shared::contracts::deposit_gate::DepositGate for DepositGateStub {
+    async fn check_deposit_paid(
+        &self,
+        _ctx: &crate::shared::context::RequestContext,
+        _quote_id: &str,
+        quote_number: &str,
+        _total: i64,
+    ) -> Result<(), AppError> {
+        if self.outstanding {
+            return Err(AppError::Validation(format!(
+                "deposit outstanding on {}",
+                quote_number
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn test_build_task_request_refuses_unaccepted_or_unpaid_quotes() {
+    use crate::domains::quotes::application::quote_export_service::QuoteExportService;
+    use crate::domains::quotes::application::QuoteConvertToTaskRequest;
+    use crate::shared::context::{AuthContext, RequestContext};
+
+    let (service, _db) = setup_service_async().await;
+    let service = Arc::new(service);
+    let ctx = RequestContext::new(
+        AuthContext {
+            user_id: "test-user".to_string(),
+            role: UserRole::Admin,
+            session_id: String::new(),
+            username: "admin".to_string(),
+            email: String::new(),
+        },
+        "corr-convert".to_string(),
+    );
+    let export = |outstanding: bool| {
+        QuoteExportService::new(
+            service.clone(),
+            Arc::new(DepositGateStub { outstanding }),
+            std::env::temp_dir(),
+        )
+    };
+    let quote = sent_quote(&service);
+    let request = QuoteConvertToTaskRequest {
+        quote_id: quote.id.clone(),
+        vehicle_plate: "AB-123-CD".to_string(),
+        vehicle_model: "Model 3".to_string(),
+        vehicle_make: None,
+        vehicle_year: None,
+        vehicle_vin: None,
+        scheduled_date: None,
+        ppf_zones: None,
+        correlation_id: None,
+    };
+
+    let not_accepted = export(false)
+        .build_task_request(&request, &ctx)
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(not_accepted, AppError::Validation(ref msg) if msg.contains("acceptés")),
+        "got: {:?}",
+        not_accepted
+    );
+
+    service
+        .mark_accepted(&quote.id, "test-user", &UserRole::Admin, "test-correlation")
+        .unwrap();
+    let unpaid = export(true)
+        .build_task_request(&request, &ctx)
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(unpaid, AppError::Validation(ref msg) if msg.contains(&quote.quote_number)),
+        "got: {:?}",
+        unpaid
+    );
+
+    let task_request = export(false)
+        .build_task_request(&request, &ctx)
+        .await
+        .expect("accepted quote with its deposit paid");
+    assert_eq!(task_request.client_id.as_deref(), Some("test-client"));
+}
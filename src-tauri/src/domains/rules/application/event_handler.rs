@@ -0,0 +1,282 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde_json::{json, Value};
+
+use crate::domains::rules::application::services::reactive_rules::RuleActionPorts;
+use crate::domains::rules::application::services::rules_service::RulesService;
+use crate::domains::rules::domain::models::rules::RuleTrigger;
+use crate::shared::contracts::rules_engine::RuleCheckRequest;
+use crate::shared::event_bus::{DomainEvent, DomainEventHandler};
+
+/// Runs active reactive rules for the domain events behind each
+/// `RuleTrigger`.
+pub struct RulesEventHandler {
+    rules: Arc<RulesService>,
+    ports: RuleActionPorts,
+}
+
+impl RulesEventHandler {
+    pub fn new(rules: Arc<RulesService>, ports: RuleActionPorts) -> Self {
+        Self { rules, ports }
+    }
+}
+
+#[async_trait]
+impl DomainEventHandler for RulesEventHandler {
+    async fn handle(&self, event: &DomainEvent) -> Result<(), String> {
+        let Some(request) = rule_check_request(event) else {
+            return Ok(());
+        };
+        self.rules
+            .execute_reactive(&request, Some(event.id()), &self.ports)
+            .await
+            .map(|_| ())
+            .map_err(|error| error.to_string())
+    }
+
+    fn interested_events(&self) -> Vec<&'static str> {
+        vec![
+            DomainEvent::TASK_CREATED,
+            DomainEvent::TASK_STATUS_CHANGED,
+            DomainEvent::INTERVENTION_STARTED,
+            DomainEvent::INTERVENTION_FINALIZED,
+            DomainEvent::QUOTE_SENT,
+            DomainEvent::QUOTE_ACCEPTED,
+            DomainEvent::QUOTE_EXPIRED,
+            DomainEvent::MATERIAL_LOW_STOCK,
+            DomainEvent::MATERIAL_CONSUMED,
+            DomainEvent::CLIENT_CREATED,
+            DomainEvent::INTERVENTION_STEP_COMPLETED,
+            DomainEvent::PHOTO_UPLOADED,
+        ]
+    }
+}
+
+/// `user_id` of rule runs for events without an acting user.
+const SYSTEM_USER: &str = "system";
+
+/// Translate a domain event into the rule check for its trigger.
+///
+/// Payload keys match what the blocking checks send for the same trigger,
+/// plus the event's metadata fields.
+pub(crate) fn rule_check_request(event: &DomainEvent) -> Option<RuleCheckRequest> {
+    // Stock levels change as a side effect; nobody in particular caused them.
+    let system_user = SYSTEM_USER.to_string();
+    let (trigger, entity_id, user_id, payload) = match event {
+        DomainEvent::TaskCreated {
+            task_id,
+            task_number,
+            title,
+            user_id,
+            ..
+        } => (
+            RuleTrigger::TaskCreated,
+            task_id,
+            user_id,
+            json!({ "task_id": task_id, "task_number": task_number, "title": title }),
+        ),
+        DomainEvent::TaskStatusChanged {
+            task_id,
+            old_status,
+            new_status,
+            user_id,
+            ..
+        } => (
+            RuleTrigger::TaskStatusChanged,
+            task_id,
+            user_id,
+            json!({ "task_id": task_id, "old_status": old_status, "new_status": new_status }),
+        ),
+        DomainEvent::InterventionStarted {
+            intervention_id,
+            task_id,
+            started_by,
+            ..
+        } => (
+            RuleTrigger::InterventionStarted,
+            task_id,
+            started_by,
+            json!({ "intervention_id": intervention_id, "task_id": task_id }),
+        ),
+        DomainEvent::InterventionFinalized {
+            intervention_id,
+            task_id,
+            technician_id,
+            completed_at_ms,
+            ..
+        } => (
+            RuleTrigger::InterventionFinalized,
+            intervention_id,
+            technician_id,
+            json!({
+                "intervention_id": intervention_id,
+                "task_id": task_id,
+                "technician_id": technician_id,
+                "completed_at_ms": completed_at_ms,
+            }),
+        ),
+        DomainEvent::QuoteSent {
+            quote_id,
+            quote_number,
+            client_id,
+            task_id,
+            revision,
+            total,
+            sent_by,
+            ..
+        } => (
+            RuleTrigger::QuoteSent,
+            quote_id,
+            sent_by,
+            json!({
+                "quote_id": quote_id,
+                "quote_number": quote_number,
+                "client_id": client_id,
+                "task_id": task_id,
+                "total": total,
+                "revision": revision,
+            }),
+        ),
+        DomainEvent::QuoteAccepted {
+            quote_id,
+            quote_number,
+            client_id,
+            accepted_by,
+            task_id,
+            ..
+        } => (
+            RuleTrigger::QuoteAccepted,
+            quote_id,
+            accepted_by,
+            json!({
+                "quote_id": quote_id,
+                "quote_number": quote_number,
+                "client_id": client_id,
+                "task_id": task_id,
+            }),
+        ),
+        DomainEvent::QuoteExpired {
+            quote_id,
+            quote_number,
+            client_id,
+            expired_by,
+            ..
+        } => (
+            RuleTrigger::QuoteExpired,
+            quote_id,
+            expired_by,
+            json!({ "quote_id": quote_id, "quote_number": quote_number, "client_id": client_id }),
+        ),
+        DomainEvent::MaterialLowStock {
+            material_id,
+            material_name,
+            current_stock,
+            minimum_stock,
+            unit,
+            ..
+        } => (
+            RuleTrigger::MaterialLowStock,
+            material_id,
+            &system_user,
+            json!({
+                "material_id": material_id,
+                "material_name": material_name,
+                "current_stock": current_stock,
+                "minimum_stock": minimum_stock,
+                "unit": unit,
+            }),
+        ),
+        DomainEvent::MaterialConsumed {
+            material_id,
+            intervention_id,
+            quantity,
+            unit,
+            consumed_by,
+            ..
+        } => (
+            RuleTrigger::MaterialConsumed,
+            material_id,
+            consumed_by,
+            json!({
+                "material_id": material_id,
+                "intervention_id": intervention_id,
+                "quantity": quantity,
+                "unit": unit,
+            }),
+        ),
+        DomainEvent::ClientCreated {
+            client_id,
+            name,
+            user_id,
+            ..
+        } => (
+            RuleTrigger::ClientCreated,
+            client_id,
+            user_id,
+            json!({ "client_id": client_id, "name": name }),
+        ),
+        DomainEvent::InterventionStepCompleted {
+            intervention_id,
+            step_id,
+            step_number,
+            completed_by,
+            photos_taken,
+            actual_duration,
+            ..
+        } => (
+            RuleTrigger::StepCompleted,
+            step_id,
+            completed_by,
+            json!({
+                "intervention_id": intervention_id,
+                "step_id": step_id,
+                "step_number": step_number,
+                "photos_taken": photos_taken,
+                "duration_seconds": actual_duration,
+            }),
+        ),
+        DomainEvent::PhotoUploaded {
+            photo_id,
+            intervention_id,
+            step_id,
+            step_number,
+            uploaded_by,
+            ..
+        } => (
+            RuleTrigger::PhotoUploaded,
+            photo_id,
+            uploaded_by,
+            json!({
+                "photo_id": photo_id,
+                "intervention_id": intervention_id,
+                "step_id": step_id,
+                "step_number": step_number,
+            }),
+        ),
+        _ => return None,
+    };
+
+    Some(RuleCheckRequest {
+        trigger: trigger.as_str().to_string(),
+        entity_id: Some(entity_id.clone()),
+        payload: with_metadata(payload, event.metadata()),
+        user_id: user_id.clone(),
+        correlation_id: event
+            .correlation_id()
+            .unwrap_or_else(|| event.id())
+            .to_string(),
+    })
+}
+
+/// Add metadata fields that the payload does not already define.
+fn with_metadata(mut payload: Value, metadata: Option<&Value>) -> Value {
+    if let (Some(target), Some(Value::Object(extra))) = (payload.as_object_mut(), metadata) {
+        for (key, value) in extra {
+            if key != "correlation_id" {
+                target.entry(key.clone()).or_insert_with(|| value.clone());
+            }
+        }
+    }
+    payload
+}
@@ -1 +1,2 @@
+pub(crate) mod event_handler;
 pub(crate) mod services;
@@ -1 +1,3 @@
+pub(crate) mod reactive_rules;
+pub(crate) mod rule_backtest;
 pub(crate) mod rules_service;
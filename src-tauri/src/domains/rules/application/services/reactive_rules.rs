@@ -0,0 +1,240 @@
+//! Reactive rule execution for `RulesService`.
+//!
+//! Blocking rules are checked inline by the operation they guard (see
+//! `BlockingRuleEngine`). Reactive rules run afterwards, from domain events
+//! (see `RulesEventHandler`), and execute side-effect actions through shared
+//! contracts. Every run is logged in `rule_execution_logs`.
+
+use std::sync::Arc;
+
+use chrono::Utc;
+use serde_json::json;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::domains::rules::domain::models::rules::{
+    RuleAction, RuleActionResult, RuleDefinition, RuleExecutionRecord, RuleMode, RuleStatus,
+};
+use crate::shared::contracts::integration_sink::{
+    IntegrationDispatchRequest, IntegrationEventSink,
+};
+use crate::shared::contracts::notification::NotificationSender;
+use crate::shared::contracts::rules_engine::RuleCheckRequest;
+use crate::shared::contracts::task_tagging::TaskTagger;
+use crate::shared::error::AppResult;
+
+use super::rules_service::RulesService;
+
+/// `notification_kind` of messages sent by rules.
+const RULE_NOTIFICATION_KIND: &str = "rule";
+
+/// Other domains' services used by reactive actions.
+#[derive(Clone)]
+pub struct RuleActionPorts {
+    pub integration_sink: Arc<dyn IntegrationEventSink>,
+    pub notification_sender: Arc<dyn NotificationSender>,
+    pub task_tagger: Arc<dyn TaskTagger>,
+}
+
+impl RulesService {
+    /// Active reactive rules for `request.trigger` whose conditions match.
+    pub(crate) async fn matching_reactive_rules(
+        &self,
+        request: &RuleCheckRequest,
+    ) -> AppResult<Vec<RuleDefinition>> {
+        Ok(self
+            .repo
+            .list_active_by_trigger(&request.trigger)
+            .await?
+            .into_iter()
+            .filter(|rule| rule.mode == RuleMode::Reactive && rule.status == RuleStatus::Active)
+            .filter(|rule| self.matches_conditions(rule, request))
+            .collect())
+    }
+
+    /// Run every matching reactive rule and log each run.
+    ///
+    /// A failing action is recorded in the run's `action_results` and does
+    /// not stop the remaining actions or rules.
+    pub async fn execute_reactive(
+        &self,
+        request: &RuleCheckRequest,
+        event_id: Option<&str>,
+        ports: &RuleActionPorts,
+    ) -> AppResult<Vec<RuleExecutionRecord>> {
+        let rules = self.matching_reactive_rules(request).await?;
+        let mut records = Vec::with_capacity(rules.len());
+        for rule in rules {
+            let mut action_results = Vec::with_capacity(rule.actions.len());
+            for action in &rule.actions {
+                let (success, detail) = match run_action(&rule, action, request, ports).await {
+                    Ok(detail) => (true, Some(detail)),
+                    Err(error) => {
+                        warn!(
+                            rule_id = %rule.id,
+                            action = action.kind(),
+                            correlation_id = %request.correlation_id,
+                            error = %error,
+                            "Reactive rule action failed"
+                        );
+                        (false, Some(error))
+                    }
+                };
+                action_results.push(RuleActionResult {
+                    action: action.kind().to_string(),
+                    success,
+                    detail,
+                });
+            }
+
+            let failed = action_results
+                .iter()
+                .filter(|result| !result.success)
+                .count();
+            let record = RuleExecutionRecord {
+                id: Uuid::new_v4().to_string(),
+                rule_id: rule.id.clone(),
+                trigger: request.trigger.clone(),
+                entity_id: request.entity_id.clone(),
+                correlation_id: request.correlation_id.clone(),
+                allowed: true,
+                message: (failed > 0)
+                    .then(|| format!("{} of {} actions failed", failed, action_results.len())),
+                mode: RuleMode::Reactive,
+                event_id: event_id.map(str::to_string),
+                action_results,
+                created_at: Utc::now().timestamp_millis(),
+            };
+            self.repo.log_execution(&record).await?;
+            info!(
+                rule_id = %rule.id,
+                trigger = %request.trigger,
+                correlation_id = %request.correlation_id,
+                failed_actions = failed,
+                "Reactive rule executed"
+            );
+            records.push(record);
+        }
+        Ok(records)
+    }
+}
+
+async fn run_action(
+    rule: &RuleDefinition,
+    action: &RuleAction,
+    request: &RuleCheckRequest,
+    ports: &RuleActionPorts,
+) -> Result<String, String> {
+    match action {
+        RuleAction::Block { .. } => Err("block actions only apply to blocking rules".to_string()),
+        RuleAction::QueueIntegration {
+            event_name,
+            integration_ids,
+        } => {
+            let queued = ports
+                .integration_sink
+                .enqueue(IntegrationDispatchRequest {
+                    event_name: event_name.clone(),
+                    payload: json!({
+                        "rule_id": rule.id,
+                        "trigger": request.trigger,
+                        "entity_id": request.entity_id,
+                        "payload": request.payload,
+                    }),
+                    correlation_id: request.correlation_id.clone(),
+                    requested_integration_ids: integration_ids.clone(),
+                })
+                .await
+                .map_err(|error| error.to_string())?;
+            Ok(format!("{} deliveries queued", queued))
+        }
+        RuleAction::SendMessage {
+            channel,
+            recipient_id,
+            recipient_email,
+            recipient_phone,
+            subject,
+            body,
+        } => {
+            let sent = ports
+                .notification_sender
+                .send_message_raw(
+                    channel.clone(),
+                    Some(RULE_NOTIFICATION_KIND.to_string()),
+                    recipient_id.clone(),
+                    recipient_email.clone(),
+                    recipient_phone.clone(),
+                    subject.clone(),
+                    body.clone(),
+                    task_id(request),
+                    payload_str(request, "client_id"),
+                    None,
+                    None,
+                    Some(request.correlation_id.clone()),
+                )
+                .await
+                .map_err(|error| error.to_string())?;
+            Ok(format!("message {} queued", sent.id))
+        }
+        RuleAction::CreateNotification {
+            user_id,
+            title,
+            message,
+        } => {
+            let recipient = user_id.clone().unwrap_or_else(|| request.user_id.clone());
+            let sent = ports
+                .notification_sender
+                .send_message_raw(
+                    "in_app".to_string(),
+                    Some(RULE_NOTIFICATION_KIND.to_string()),
+                    Some(recipient.clone()),
+                    None,
+                    None,
+                    Some(title.clone()),
+                    message.clone(),
+                    task_id(request),
+                    payload_str(request, "client_id"),
+                    None,
+                    None,
+                    Some(request.correlation_id.clone()),
+                )
+                .await
+                .map_err(|error| error.to_string())?;
+            Ok(format!("notification {} sent to {}", sent.id, recipient))
+        }
+        RuleAction::SetTaskTag { tag } => {
+            let task_id = task_id(request)
+                .ok_or_else(|| format!("'{}' events carry no task", request.trigger))?;
+            let added = ports
+                .task_tagger
+                .add_task_tag(&task_id, tag)
+                .await
+                .map_err(|error| error.to_string())?;
+            Ok(if added {
+                format!("tag '{}' added to task {}", tag, task_id)
+            } else {
+                format!("task {} already tagged '{}'", task_id, tag)
+            })
+        }
+    }
+}
+
+fn payload_str(request: &RuleCheckRequest, key: &str) -> Option<String> {
+    request
+        .payload
+        .get(key)
+        .and_then(|value| value.as_str())
+        .map(str::to_string)
+}
+
+/// The task an event is about: `payload.task_id`, or the entity of task
+/// triggers.
+fn task_id(request: &RuleCheckRequest) -> Option<String> {
+    payload_str(request, "task_id").or_else(|| {
+        request
+            .trigger
+            .starts_with("task_")
+            .then(|| request.entity_id.clone())
+            .flatten()
+    })
+}
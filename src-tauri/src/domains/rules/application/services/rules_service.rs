@@ -2,6 +2,7 @@ use async_trait::async_trait;
 use std::sync::Arc;
 
 use crate::db::Database;
+use crate::domains::rules::domain::conditions::{parse_conditions, ConditionContext};
 use crate::domains::rules::domain::models::rules::{
     CreateRuleDefinitionRequest, RuleAction, RuleDefinition, RuleEvaluationResult, RuleMode,
     RuleStatus, RuleTrigger, TestRuleRequest, UpdateRuleDefinitionRequest,
@@ -19,7 +20,7 @@ use tracing::warn;
 use uuid::Uuid;
 
 pub struct RulesService {
-    repo: Arc<dyn RulesRepository>,
+    pub(super) repo: Arc<dyn RulesRepository>,
 }
 
 impl std::fmt::Debug for RulesService {
@@ -50,7 +51,14 @@ impl RulesService {
         _ctx: &RequestContext,
         request: CreateRuleDefinitionRequest,
     ) -> AppResult<RuleDefinition> {
-        self.validate_request(&request.name, &request.template_key, &request.actions)?;
+        self.validate_request(
+            &request.name,
+            &request.template_key,
+            &request.trigger,
+            &request.mode,
+            &request.conditions,
+            &request.actions,
+        )?;
         let now = Utc::now().timestamp_millis();
         let rule = RuleDefinition {
             id: Uuid::new_v4().to_string(),
@@ -102,7 +110,14 @@ impl RulesService {
             existing.status = status;
         }
         existing.updated_at = Utc::now().timestamp_millis();
-        self.validate_request(&existing.name, &existing.template_key, &existing.actions)?;
+        self.validate_request(
+            &existing.name,
+            &existing.template_key,
+            &existing.trigger,
+            &existing.mode,
+            &existing.conditions,
+            &existing.actions,
+        )?;
         self.repo.update(&existing).await?;
         Ok(existing)
     }
@@ -138,27 +153,47 @@ impl RulesService {
         ctx: &RequestContext,
         request: TestRuleRequest,
     ) -> AppResult<RuleEvaluationResult> {
-        let outcome = self
-            .evaluate(&RuleCheckRequest {
-                trigger: request.trigger.as_str().to_string(),
-                entity_id: request.entity_id,
-                payload: request.payload,
-                user_id: ctx.auth.user_id.clone(),
-                correlation_id: ctx.correlation_id.clone(),
-            })
-            .await?;
+        let check = RuleCheckRequest {
+            trigger: request.trigger.as_str().to_string(),
+            entity_id: request.entity_id,
+            payload: request.payload,
+            user_id: ctx.auth.user_id.clone(),
+            correlation_id: ctx.correlation_id.clone(),
+        };
+        let outcome = self.evaluate(&check).await?;
+        // Reactive rules are only reported here; they run from domain events.
+        let queued_actions = self
+            .matching_reactive_rules(&check)
+            .await?
+            .into_iter()
+            .flat_map(|rule| rule.actions)
+            .collect();
         Ok(RuleEvaluationResult {
             allowed: outcome.allowed,
             matched_rule_ids: outcome.matched_rule_ids,
             message: outcome.message,
-            queued_actions: Vec::new(),
+            queued_actions,
         })
     }
 
+    pub async fn list_executions(
+        &self,
+        _ctx: &RequestContext,
+        rule_id: &str,
+        limit: Option<usize>,
+    ) -> AppResult<Vec<RuleExecutionRecord>> {
+        self.repo
+            .list_executions(rule_id, limit.unwrap_or(50).min(500))
+            .await
+    }
+
     fn validate_request(
         &self,
         name: &str,
         template_key: &str,
+        trigger: &RuleTrigger,
+        mode: &RuleMode,
+        conditions: &serde_json::Value,
         actions: &[RuleAction],
     ) -> AppResult<()> {
         if name.trim().is_empty() {
@@ -169,77 +204,123 @@ impl RulesService {
                 "Rule template_key is required".to_string(),
             ));
         }
+        if *mode == RuleMode::Blocking && !trigger.has_blocking_check() {
+            return Err(AppError::Validation(format!(
+                "Trigger {} has no blocking check; use a reactive rule",
+                trigger.as_str()
+            )));
+        }
         if actions.is_empty() {
             return Err(AppError::Validation(
                 "At least one rule action is required".to_string(),
             ));
         }
+        for (index, action) in actions.iter().enumerate() {
+            if let Err(message) = Self::validate_action(mode, action) {
+                return Err(AppError::Validation(format!(
+                    "Invalid rule action #{} ({}): {}",
+                    index + 1,
+                    action.kind(),
+                    message
+                )));
+            }
+        }
+        if let Err(error) = parse_conditions(conditions) {
+            return Err(AppError::Validation(format!(
+                "Invalid rule condition at {}",
+                error
+            )));
+        }
         Ok(())
     }
 
-    fn matches_conditions(&self, rule: &RuleDefinition, request: &RuleCheckRequest) -> bool {
-        let conditions = match rule.conditions.as_object() {
-            Some(value) => value,
-            None => return true,
-        };
-
-        if let Some(task_ids) = conditions
-            .get("task_id_in")
-            .and_then(|value| value.as_array())
+    fn validate_action(mode: &RuleMode, action: &RuleAction) -> Result<(), &'static str> {
+        // Blocking rules only decide; side effects belong to reactive rules.
+        if *mode == RuleMode::Blocking
+            && !matches!(
+                action,
+                RuleAction::Block { .. } | RuleAction::QueueIntegration { .. }
+            )
         {
-            let entity_id = match request.entity_id.as_deref() {
-                Some(value) => value,
-                None => return false,
-            };
-            if !task_ids
-                .iter()
-                .any(|value| value.as_str() == Some(entity_id))
-            {
-                return false;
-            }
+            return Err("blocking rules only support block and queue_integration actions");
         }
-
-        if let Some(statuses) = conditions
-            .get("status_in")
-            .and_then(|value| value.as_array())
-        {
-            let current_status = request
-                .payload
-                .get("new_status")
-                .and_then(|value| value.as_str())
-                .or_else(|| {
-                    request
-                        .payload
-                        .get("status")
-                        .and_then(|value| value.as_str())
-                });
-            match current_status {
-                Some(status) if statuses.iter().any(|value| value.as_str() == Some(status)) => {}
-                _ => return false,
+        let blank = |value: &str| value.trim().is_empty();
+        match action {
+            RuleAction::Block { message } => {
+                if *mode == RuleMode::Reactive {
+                    return Err("reactive rules run after the fact and cannot block");
+                }
+                if blank(message) {
+                    return Err("message is required");
+                }
             }
-        }
-
-        if let Some(priorities) = conditions
-            .get("priority_in")
-            .and_then(|value| value.as_array())
-        {
-            let current_priority = request
-                .payload
-                .get("priority")
-                .and_then(|value| value.as_str());
-            match current_priority {
-                Some(priority)
-                    if priorities
-                        .iter()
-                        .any(|value| value.as_str() == Some(priority)) => {}
-                _ => return false,
+            RuleAction::QueueIntegration { event_name, .. } => {
+                if blank(event_name) {
+                    return Err("event_name is required");
+                }
+            }
+            RuleAction::SendMessage {
+                channel,
+                recipient_id,
+                recipient_email,
+                recipient_phone,
+                body,
+                ..
+            } => {
+                let has = |value: &Option<String>| value.as_deref().is_some_and(|v| !blank(v));
+                match channel.as_str() {
+                    "email" if !has(recipient_email) => {
+                        return Err("email messages need recipient_email")
+                    }
+                    "sms" if !has(recipient_phone) => {
+                        return Err("sms messages need recipient_phone")
+                    }
+                    "in_app" if !has(recipient_id) => {
+                        return Err("in_app messages need recipient_id")
+                    }
+                    "email" | "sms" | "in_app" => {}
+                    _ => return Err("channel must be email, sms or in_app"),
+                }
+                if blank(body) {
+                    return Err("body is required");
+                }
+            }
+            RuleAction::CreateNotification { title, message, .. } => {
+                if blank(title) || blank(message) {
+                    return Err("title and message are required");
+                }
+            }
+            RuleAction::SetTaskTag { tag } => {
+                if blank(tag) || tag.contains(',') {
+                    return Err("tag must be non-empty and must not contain ','");
+                }
             }
         }
+        Ok(())
+    }
 
-        true
+    pub(super) fn matches_conditions(
+        &self,
+        rule: &RuleDefinition,
+        request: &RuleCheckRequest,
+    ) -> bool {
+        // Conditions are validated on save; a rule stored before that (or
+        // edited directly in the database) is skipped rather than applied.
+        let condition = match parse_conditions(&rule.conditions) {
+            Ok(condition) => condition,
+            Err(error) => {
+                warn!(rule_id = %rule.id, %error, "Skipping rule with invalid conditions");
+                return false;
+            }
+        };
+        condition.evaluate(&ConditionContext {
+            payload: &request.payload,
+            entity_id: request.entity_id.as_deref(),
+            now_ms: Utc::now().timestamp_millis(),
+        })
     }
 
-    fn blocking_message(&self, rule: &RuleDefinition) -> Option<String> {
+    pub(super) fn blocking_message(&self, rule: &RuleDefinition) -> Option<String> {
         rule.actions.iter().find_map(|action| match action {
             RuleAction::Block { message } => Some(message.clone()),
             _ => None,
@@ -283,6 +364,9 @@ impl BlockingRuleEngine for RulesService {
                     correlation_id: request.correlation_id.clone(),
                     allowed,
                     message: message.clone(),
+                    mode: RuleMode::Blocking,
+                    event_id: None,
+                    action_results: Vec::new(),
                     created_at: Utc::now().timestamp_millis(),
                 })
                 .await?;
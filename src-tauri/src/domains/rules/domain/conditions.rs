@@ -0,0 +1,1179 @@
+//! Condition language for `RuleDefinition::conditions`.
+//!
+//! A condition is a JSON value:
+//! - `"vehicle.year < 2015 and client.customer_type == \"business\""` — an
+//!   expression string (see below)
+//! - `{"all": [..]}`, `{"any": [..]}`, `{"not": ..}` — groups, nestable; a bare
+//!   array is the same as `all`
+//! - `{"path": "quote.total", "op": ">", "value": 200000}` — one comparison, for
+//!   editors that build conditions field by field
+//! - `{}`, `null` or `true` — always matches
+//! - `{"task_id_in": [..], "status_in": [..], "priority_in": [..]}` — the
+//!   original hard-coded keys, still accepted
+//!
+//! Expressions combine predicates with `and`/`or`/`not` (or `&&`/`||`/`!`) and
+//! parentheses. A predicate is `path op value`:
+//! - `==`, `!=`, `<`, `<=`, `>`, `>=` on numbers and strings
+//! - `in [..]`, `contains`, `starts_with`, `ends_with`
+//! - `matches "regex"`
+//! - `before`/`after` a date: `"2025-01-31"`, an RFC 3339 timestamp, `now`,
+//!   or `now-30d` (units `m`, `h`, `d`, `w`)
+//! - `path exists` (present and not null)
+//!
+//! Paths are dot-separated into the trigger payload (`items.0.label`);
+//! `$entity_id` is the entity the rule is checked for. A predicate on a
+//! missing path is false. Payload dates may be millisecond timestamps or date
+//! strings.
+//!
+//! Parse errors carry the JSON pointer of the offending node and, inside an
+//! expression string, the 1-based column, so the editor can point at them.
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime};
+use regex::Regex;
+use serde::Serialize;
+use serde_json::Value;
+use std::cmp::Ordering;
+use std::fmt;
+use ts_rs::TS;
+
+/// Keys of the original `conditions` format.
+const LEGACY_KEYS: &[&str] = &["task_id_in", "status_in", "priority_in"];
+
+/// Where and why a condition failed to parse.
+#[derive(Debug, Clone, PartialEq, Serialize, TS)]
+pub struct ConditionError {
+    /// JSON pointer into `conditions` (`""` for the root, `/all/1`, ...).
+    pub pointer: String,
+    /// 1-based character column inside an expression string.
+    pub column: Option<usize>,
+    pub message: String,
+}
+
+impl ConditionError {
+    fn at(pointer: &str, message: impl Into<String>) -> Self {
+        Self {
+            pointer: pointer.to_string(),
+            column: None,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ConditionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "conditions{}", self.pointer)?;
+        if let Some(column) = self.column {
+            write!(f, ", column {}", column)?;
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+impl std::error::Error for ConditionError {}
+
+// ── AST ──────────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone)]
+pub enum Condition {
+    Literal(bool),
+    All(Vec<Condition>),
+    Any(Vec<Condition>),
+    Not(Box<Condition>),
+    Predicate(Predicate),
+}
+
+#[derive(Debug, Clone)]
+pub struct Predicate {
+    pub path: String,
+    pub op: Operator,
+    pub operand: Operand,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    In,
+    Contains,
+    StartsWith,
+    EndsWith,
+    Matches,
+    Before,
+    After,
+    Exists,
+}
+
+impl Operator {
+    fn parse(word: &str) -> Option<Self> {
+        Some(match word {
+            "==" | "eq" => Self::Eq,
+            "!=" | "ne" => Self::Ne,
+            "<" | "lt" => Self::Lt,
+            "<=" | "lte" => Self::Le,
+            ">" | "gt" => Self::Gt,
+            ">=" | "gte" => Self::Ge,
+            "in" => Self::In,
+            "contains" => Self::Contains,
+            "starts_with" => Self::StartsWith,
+            "ends_with" => Self::EndsWith,
+            "matches" => Self::Matches,
+            "before" => Self::Before,
+            "after" => Self::After,
+            "exists" => Self::Exists,
+            _ => return None,
+        })
+    }
+
+    fn is_word(word: &str) -> bool {
+        word.chars().all(|c| c.is_ascii_alphabetic() || c == '_') && Self::parse(word).is_some()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Operand {
+    None,
+    Value(Value),
+    Regex(Regex),
+    Date(DateRef),
+}
+
+/// A date operand, resolved against the evaluation time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateRef {
+    /// Unix timestamp in milliseconds.
+    Absolute(i64),
+    /// `now` plus an offset in milliseconds.
+    Now(i64),
+}
+
+/// What a condition is evaluated against.
+#[derive(Debug, Clone, Copy)]
+pub struct ConditionContext<'a> {
+    pub payload: &'a Value,
+    pub entity_id: Option<&'a str>,
+    /// Evaluation time in Unix milliseconds (for `now` dates).
+    pub now_ms: i64,
+}
+
+// ── JSON parsing ─────────────────────────────────────────────────────────────
+
+/// Parse and validate a `conditions` value.
+pub fn parse_conditions(value: &Value) -> Result<Condition, ConditionError> {
+    parse_node(value, "")
+}
+
+fn parse_node(value: &Value, pointer: &str) -> Result<Condition, ConditionError> {
+    match value {
+        Value::Null => Ok(Condition::Literal(true)),
+        Value::Bool(value) => Ok(Condition::Literal(*value)),
+        Value::String(expression) => {
+            parse_expression(expression).map_err(|(column, message)| ConditionError {
+                pointer: pointer.to_string(),
+                column: Some(column),
+                message,
+            })
+        }
+        Value::Array(items) => parse_group(items, pointer).map(Condition::All),
+        Value::Object(map) => {
+            if map.is_empty() {
+                return Ok(Condition::Literal(true));
+            }
+            if map.keys().all(|key| LEGACY_KEYS.contains(&key.as_str())) {
+                return parse_legacy(map, pointer);
+            }
+            if map.contains_key("path") {
+                return parse_structured(map, pointer);
+            }
+            if map.len() != 1 {
+                return Err(ConditionError::at(
+                    pointer,
+                    "a group must have exactly one of 'all', 'any' or 'not'",
+                ));
+            }
+            let (key, inner) = map.iter().next().expect("one entry");
+            let inner_pointer = format!("{}/{}", pointer, key);
+            match key.as_str() {
+                "all" | "any" => {
+                    let items = inner.as_array().ok_or_else(|| {
+                        ConditionError::at(&inner_pointer, format!("'{}' expects an array", key))
+                    })?;
+                    let conditions = parse_group(items, &inner_pointer)?;
+                    Ok(if key == "all" {
+                        Condition::All(conditions)
+                    } else {
+                        Condition::Any(conditions)
+                    })
+                }
+                "not" => Ok(Condition::Not(Box::new(parse_node(inner, &inner_pointer)?))),
+                other => Err(ConditionError::at(
+                    pointer,
+                    format!(
+                        "unknown key '{}' (expected 'all', 'any', 'not' or 'path')",
+                        other
+                    ),
+                )),
+            }
+        }
+        Value::Number(_) => Err(ConditionError::at(
+            pointer,
+            "expected an expression string, a group or a comparison object",
+        )),
+    }
+}
+
+fn parse_group(items: &[Value], pointer: &str) -> Result<Vec<Condition>, ConditionError> {
+    items
+        .iter()
+        .enumerate()
+        .map(|(index, item)| parse_node(item, &format!("{}/{}", pointer, index)))
+        .collect()
+}
+
+fn parse_structured(
+    map: &serde_json::Map<String, Value>,
+    pointer: &str,
+) -> Result<Condition, ConditionError> {
+    if let Some(key) = map
+        .keys()
+        .find(|key| !matches!(key.as_str(), "path" | "op" | "value"))
+    {
+        return Err(ConditionError::at(
+            &format!("{}/{}", pointer, key),
+            format!("unknown key '{}' in comparison", key),
+        ));
+    }
+    let path = map
+        .get("path")
+        .and_then(Value::as_str)
+        .filter(|path| is_valid_path(path))
+        .ok_or_else(|| {
+            ConditionError::at(
+                &format!("{}/path", pointer),
+                "expected a payload path string",
+            )
+        })?;
+    let op_pointer = format!("{}/op", pointer);
+    let op = map
+        .get("op")
+        .and_then(Value::as_str)
+        .ok_or_else(|| ConditionError::at(&op_pointer, "expected an operator string"))?;
+    let op = Operator::parse(op)
+        .ok_or_else(|| ConditionError::at(&op_pointer, format!("unknown operator '{}'", op)))?;
+
+    let value_pointer = format!("{}/value", pointer);
+    let operand = match (op, map.get("value")) {
+        (Operator::Exists, None) => Operand::None,
+        (Operator::Exists, Some(_)) => {
+            return Err(ConditionError::at(
+                &value_pointer,
+                "'exists' takes no value",
+            ))
+        }
+        (_, None) => return Err(ConditionError::at(&value_pointer, "missing value")),
+        (op, Some(value)) => build_operand(op, value.clone())
+            .map_err(|message| ConditionError::at(&value_pointer, message))?,
+    };
+
+    Ok(Condition::Predicate(Predicate {
+        path: path.to_string(),
+        op,
+        operand,
+    }))
+}
+
+fn parse_legacy(
+    map: &serde_json::Map<String, Value>,
+    pointer: &str,
+) -> Result<Condition, ConditionError> {
+    let list = |key: &str| -> Result<Option<Value>, ConditionError> {
+        match map.get(key) {
+            None => Ok(None),
+            Some(Value::Array(items)) => Ok(Some(Value::Array(items.clone()))),
+            Some(_) => Err(ConditionError::at(
+                &format!("{}/{}", pointer, key),
+                format!("'{}' expects an array", key),
+            )),
+        }
+    };
+    let predicate = |path: &str, values: Value| {
+        Condition::Predicate(Predicate {
+            path: path.to_string(),
+            op: Operator::In,
+            operand: Operand::Value(values),
+        })
+    };
+    let exists = |path: &str| {
+        Condition::Predicate(Predicate {
+            path: path.to_string(),
+            op: Operator::Exists,
+            operand: Operand::None,
+        })
+    };
+
+    let mut all = Vec::new();
+    if let Some(ids) = list("task_id_in")? {
+        all.push(predicate("$entity_id", ids));
+    }
+    if let Some(statuses) = list("status_in")? {
+        // `new_status` (status change payloads) wins over `status`.
+        all.push(Condition::Any(vec![
+            predicate("new_status", statuses.clone()),
+            Condition::All(vec![
+                Condition::Not(Box::new(exists("new_status"))),
+                predicate("status", statuses),
+            ]),
+        ]));
+    }
+    if let Some(priorities) = list("priority_in")? {
+        all.push(predicate("priority", priorities));
+    }
+    Ok(Condition::All(all))
+}
+
+/// Check a value against what its operator accepts; compile regexes and dates.
+fn build_operand(op: Operator, value: Value) -> Result<Operand, String> {
+    match op {
+        Operator::Exists => Ok(Operand::None),
+        Operator::In => match value {
+            Value::Array(_) => Ok(Operand::Value(value)),
+            _ => Err("'in' expects a list".to_string()),
+        },
+        Operator::Matches => match value {
+            Value::String(pattern) => Regex::new(&pattern)
+                .map(Operand::Regex)
+                .map_err(|e| format!("invalid regex: {}", e)),
+            _ => Err("'matches' expects a regex string".to_string()),
+        },
+        Operator::Before | Operator::After => match value {
+            Value::String(date) => parse_date_literal(&date).map(Operand::Date),
+            _ => Err("expected a date string (YYYY-MM-DD, RFC 3339, now, now-30d)".to_string()),
+        },
+        Operator::Lt | Operator::Le | Operator::Gt | Operator::Ge => match value {
+            Value::Number(_) | Value::String(_) => Ok(Operand::Value(value)),
+            _ => Err("ordering comparisons expect a number or a string".to_string()),
+        },
+        Operator::StartsWith | Operator::EndsWith => match value {
+            Value::String(_) => Ok(Operand::Value(value)),
+            _ => Err("expected a string".to_string()),
+        },
+        Operator::Eq | Operator::Ne | Operator::Contains => match value {
+            Value::Array(_) | Value::Object(_) => Err("expected a single value".to_string()),
+            _ => Ok(Operand::Value(value)),
+        },
+    }
+}
+
+fn is_valid_path(path: &str) -> bool {
+    !path.is_empty()
+        && path
+            .split('.')
+            .all(|segment| !segment.is_empty() && segment.chars().all(is_path_char))
+}
+
+fn is_path_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == '$'
+}
+
+/// Furthest a relative date may reach from now: a century.
+const MAX_RELATIVE_MS: i64 = 100 * 366 * 86_400_000;
+
+fn parse_date_literal(text: &str) -> Result<DateRef, String> {
+    let text = text.trim();
+    if let Some(offset) = text.strip_prefix("now") {
+        if offset.is_empty() {
+            return Ok(DateRef::Now(0));
+        }
+        let (sign, rest) = if let Some(rest) = offset.strip_prefix('+') {
+            (1, rest)
+        } else if let Some(rest) = offset.strip_prefix('-') {
+            (-1, rest)
+        } else {
+            return Err(format!("invalid relative date '{}'", text));
+        };
+        let unit_ms = match rest.chars().last() {
+            Some('m') => 60_000,
+            Some('h') => 3_600_000,
+            Some('d') => 86_400_000,
+            Some('w') => 7 * 86_400_000,
+            _ => {
+                return Err(format!(
+                    "invalid relative date '{}' (units: m, h, d, w)",
+                    text
+                ))
+            }
+        };
+        let amount: i64 = rest[..rest.len() - 1]
+            .parse()
+            .map_err(|_| format!("invalid relative date '{}'", text))?;
+        return amount
+            .checked_mul(sign * unit_ms)
+            .filter(|offset| offset.abs() <= MAX_RELATIVE_MS)
+            .map(DateRef::Now)
+            .ok_or_else(|| format!("relative date '{}' is out of range", text));
+    }
+    parse_date_ms(text)
+        .map(DateRef::Absolute)
+        .ok_or_else(|| format!("invalid date '{}'", text))
+}
+
+/// RFC 3339, `YYYY-MM-DD HH:MM:SS` (UTC) or `YYYY-MM-DD` (midnight UTC).
+fn parse_date_ms(text: &str) -> Option<i64> {
+    if let Ok(datetime) = DateTime::parse_from_rfc3339(text) {
+        return Some(datetime.timestamp_millis());
+    }
+    if let Ok(datetime) = NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M:%S") {
+        return Some(datetime.and_utc().timestamp_millis());
+    }
+    NaiveDate::parse_from_str(text, "%Y-%m-%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .map(|datetime| datetime.and_utc().timestamp_millis())
+}
+
+// ── Expression parsing ───────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Str(String),
+    Num(serde_json::Number),
+    Symbol(&'static str),
+    LParen,
+    RParen,
+    LBracket,
+    RBracket,
+    Comma,
+    End,
+}
+
+/// A token and the 1-based column it starts at.
+type Spanned = (Token, usize);
+
+/// Parse error inside an expression: column and message.
+type ExprError = (usize, String);
+
+fn tokenize(input: &str) -> Result<Vec<Spanned>, ExprError> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let column = i + 1;
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+        let two: String = chars[i..(i + 2).min(chars.len())].iter().collect();
+        if let Some(symbol) = ["==", "!=", "<=", ">=", "&&", "||"]
+            .into_iter()
+            .find(|symbol| *symbol == two)
+        {
+            tokens.push((Token::Symbol(symbol), column));
+            i += 2;
+            continue;
+        }
+        match c {
+            '(' => tokens.push((Token::LParen, column)),
+            ')' => tokens.push((Token::RParen, column)),
+            '[' => tokens.push((Token::LBracket, column)),
+            ']' => tokens.push((Token::RBracket, column)),
+            ',' => tokens.push((Token::Comma, column)),
+            '<' => tokens.push((Token::Symbol("<"), column)),
+            '>' => tokens.push((Token::Symbol(">"), column)),
+            '!' => tokens.push((Token::Symbol("!"), column)),
+            '=' => return Err((column, "unexpected '=' (use '==' to compare)".to_string())),
+            '"' | '\'' => {
+                let quote = c;
+                let mut value = String::new();
+                i += 1;
+                loop {
+                    match chars.get(i) {
+                        None => return Err((column, "unterminated string".to_string())),
+                        Some('\\') => {
+                            match chars.get(i + 1) {
+                                Some('n') => value.push('\n'),
+                                Some(&escaped) => value.push(escaped),
+                                None => return Err((column, "unterminated string".to_string())),
+                            }
+                            i += 2;
+                        }
+                        Some(&end) if end == quote => break,
+                        Some(&other) => {
+                            value.push(other);
+                            i += 1;
+                        }
+                    }
+                }
+                tokens.push((Token::Str(value), column));
+            }
+            c if c.is_ascii_digit()
+                || (c == '-' && chars.get(i + 1).is_some_and(|n| n.is_ascii_digit())) =>
+            {
+                let start = i;
+                i += 1;
+                while chars
+                    .get(i)
+                    .is_some_and(|n| n.is_ascii_digit() || *n == '.')
+                {
+                    i += 1;
+                }
+                let text: String = chars[start..i].iter().collect();
+                let number = text
+                    .parse::<i64>()
+                    .map(serde_json::Number::from)
+                    .ok()
+                    .or_else(|| {
+                        text.parse::<f64>()
+                            .ok()
+                            .and_then(serde_json::Number::from_f64)
+                    })
+                    .ok_or_else(|| (column, format!("invalid number '{}'", text)))?;
+                tokens.push((Token::Num(number), column));
+                continue;
+            }
+            c if is_path_char(c) => {
+                let start = i;
+                while chars.get(i).is_some_and(|n| is_path_char(*n) || *n == '.') {
+                    i += 1;
+                }
+                let mut word: String = chars[start..i].iter().collect();
+                // Relative dates: `now-30d`, `now+2h`
+                if word == "now"
+                    && matches!(chars.get(i), Some('+') | Some('-'))
+                    && chars.get(i + 1).is_some_and(|n| n.is_ascii_digit())
+                {
+                    let offset_start = i;
+                    i += 1;
+                    while chars.get(i).is_some_and(|n| n.is_ascii_alphanumeric()) {
+                        i += 1;
+                    }
+                    word.extend(&chars[offset_start..i]);
+                }
+                tokens.push((Token::Ident(word), column));
+                continue;
+            }
+            other => return Err((column, format!("unexpected character '{}'", other))),
+        }
+        i += 1;
+    }
+    tokens.push((Token::End, chars.len() + 1));
+    Ok(tokens)
+}
+
+/// Recursive-descent parser over the token list.
+///
+/// ```text
+/// or        := and (("or" | "||") and)*
+/// and       := unary (("and" | "&&") unary)*
+/// unary     := ("not" | "!") unary | "(" or ")" | predicate
+/// predicate := path "exists" | path op value
+/// value     := string | number | true | false | null | now[±Nu] | "[" value, .. "]"
+/// ```
+struct ExprParser {
+    tokens: Vec<Spanned>,
+    position: usize,
+}
+
+fn parse_expression(input: &str) -> Result<Condition, ExprError> {
+    let mut parser = ExprParser {
+        tokens: tokenize(input)?,
+        position: 0,
+    };
+    if parser.peek() == &Token::End {
+        return Err((1, "empty expression".to_string()));
+    }
+    let condition = parser.parse_or()?;
+    match parser.peek() {
+        Token::End => Ok(condition),
+        _ => Err(parser.error("expected 'and', 'or' or end of expression")),
+    }
+}
+
+impl ExprParser {
+    fn peek(&self) -> &Token {
+        &self.tokens[self.position].0
+    }
+
+    fn column(&self) -> usize {
+        self.tokens[self.position].1
+    }
+
+    fn advance(&mut self) -> Spanned {
+        let token = self.tokens[self.position].clone();
+        if self.position + 1 < self.tokens.len() {
+            self.position += 1;
+        }
+        token
+    }
+
+    fn error(&self, message: &str) -> ExprError {
+        let found = match self.peek() {
+            Token::End => "end of expression".to_string(),
+            Token::Ident(word) => format!("'{}'", word),
+            Token::Str(text) => format!("\"{}\"", text),
+            Token::Num(number) => number.to_string(),
+            Token::Symbol(symbol) => format!("'{}'", symbol),
+            Token::LParen => "'('".to_string(),
+            Token::RParen => "')'".to_string(),
+            Token::LBracket => "'['".to_string(),
+            Token::RBracket => "']'".to_string(),
+            Token::Comma => "','".to_string(),
+        };
+        (self.column(), format!("{}, found {}", message, found))
+    }
+
+    fn is_keyword(&self, keyword: &str, symbol: &str) -> bool {
+        match self.peek() {
+            Token::Ident(word) => word == keyword,
+            Token::Symbol(found) => *found == symbol,
+            _ => false,
+        }
+    }
+
+    fn parse_or(&mut self) -> Result<Condition, ExprError> {
+        let mut items = vec![self.parse_and()?];
+        while self.is_keyword("or", "||") {
+            self.advance();
+            items.push(self.parse_and()?);
+        }
+        Ok(if items.len() == 1 {
+            items.remove(0)
+        } else {
+            Condition::Any(items)
+        })
+    }
+
+    fn parse_and(&mut self) -> Result<Condition, ExprError> {
+        let mut items = vec![self.parse_unary()?];
+        while self.is_keyword("and", "&&") {
+            self.advance();
+            items.push(self.parse_unary()?);
+        }
+        Ok(if items.len() == 1 {
+            items.remove(0)
+        } else {
+            Condition::All(items)
+        })
+    }
+
+    fn parse_unary(&mut self) -> Result<Condition, ExprError> {
+        if self.is_keyword("not", "!") {
+            self.advance();
+            return Ok(Condition::Not(Box::new(self.parse_unary()?)));
+        }
+        if self.peek() == &Token::LParen {
+            let (_, open_column) = self.advance();
+            let inner = self.parse_or()?;
+            if self.peek() != &Token::RParen {
+                let (column, message) = self.error("expected ')'");
+                return Err((
+                    column,
+                    format!("{} to close '(' at column {}", message, open_column),
+                ));
+            }
+            self.advance();
+            return Ok(inner);
+        }
+        self.parse_predicate()
+    }
+
+    fn parse_predicate(&mut self) -> Result<Condition, ExprError> {
+        let path = match self.peek() {
+            Token::Ident(word)
+                if !matches!(
+                    word.as_str(),
+                    "and" | "or" | "not" | "true" | "false" | "null"
+                ) && !Operator::is_word(word) =>
+            {
+                word.clone()
+            }
+            _ => return Err(self.error("expected a payload path")),
+        };
+        let path_column = self.column();
+        if !is_valid_path(&path) {
+            return Err((path_column, format!("invalid path '{}'", path)));
+        }
+        self.advance();
+
+        let op_column = self.column();
+        let op = match self.peek() {
+            Token::Symbol(symbol) if !matches!(*symbol, "&&" | "||" | "!") => {
+                Operator::parse(symbol)
+            }
+            Token::Ident(word) => Operator::parse(word),
+            _ => None,
+        }
+        .ok_or_else(|| self.error(&format!("expected an operator after '{}'", path)))?;
+        self.advance();
+
+        if op == Operator::Exists {
+            return Ok(Condition::Predicate(Predicate {
+                path,
+                op,
+                operand: Operand::None,
+            }));
+        }
+
+        let value_column = self.column();
+        let value = self.parse_value().map_err(|(column, message)| {
+            if column == value_column && self.tokens[self.position].0 == Token::End {
+                (
+                    op_column,
+                    format!("missing value after operator: {}", message),
+                )
+            } else {
+                (column, message)
+            }
+        })?;
+        let operand = build_operand(op, value).map_err(|message| (value_column, message))?;
+        Ok(Condition::Predicate(Predicate { path, op, operand }))
+    }
+
+    fn parse_value(&mut self) -> Result<Value, ExprError> {
+        match self.peek().clone() {
+            Token::Str(text) => {
+                self.advance();
+                Ok(Value::String(text))
+            }
+            Token::Num(number) => {
+                self.advance();
+                Ok(Value::Number(number))
+            }
+            Token::Ident(word) => {
+                let value = match word.as_str() {
+                    "true" => Value::Bool(true),
+                    "false" => Value::Bool(false),
+                    "null" => Value::Null,
+                    _ if word.starts_with("now") => Value::String(word),
+                    _ => {
+                        return Err(
+                            self.error("expected a value (quote strings, e.g. \"business\")")
+                        )
+                    }
+                };
+                self.advance();
+                Ok(value)
+            }
+            Token::LBracket => {
+                self.advance();
+                let mut items = Vec::new();
+                if self.peek() != &Token::RBracket {
+                    loop {
+                        items.push(self.parse_value()?);
+                        match self.peek() {
+                            Token::Comma => {
+                                self.advance();
+                            }
+                            Token::RBracket => break,
+                            _ => return Err(self.error("expected ',' or ']'")),
+                        }
+                    }
+                }
+                self.advance();
+                Ok(Value::Array(items))
+            }
+            _ => Err(self.error("expected a value")),
+        }
+    }
+}
+
+// ── Evaluation ───────────────────────────────────────────────────────────────
+
+impl Condition {
+    pub fn evaluate(&self, ctx: &ConditionContext<'_>) -> bool {
+        match self {
+            Condition::Literal(value) => *value,
+            Condition::All(items) => items.iter().all(|item| item.evaluate(ctx)),
+            Condition::Any(items) => items.iter().any(|item| item.evaluate(ctx)),
+            Condition::Not(inner) => !inner.evaluate(ctx),
+            Condition::Predicate(predicate) => predicate.evaluate(ctx),
+        }
+    }
+}
+
+impl Condition {
+    /// The predicates that hold for `ctx`, written as expressions. A
+    /// predicate under `not` is listed (as `not ...`) when it is false.
+    pub fn matched_predicates(&self, ctx: &ConditionContext<'_>) -> Vec<String> {
+        let mut matched = Vec::new();
+        self.collect_matched(ctx, false, &mut matched);
+        matched
+    }
+
+    fn collect_matched(&self, ctx: &ConditionContext<'_>, negated: bool, out: &mut Vec<String>) {
+        match self {
+            Condition::Literal(_) => {}
+            Condition::All(items) | Condition::Any(items) => {
+                for item in items {
+                    item.collect_matched(ctx, negated, out);
+                }
+            }
+            Condition::Not(inner) => inner.collect_matched(ctx, !negated, out),
+            Condition::Predicate(predicate) => {
+                if predicate.evaluate(ctx) != negated {
+                    out.push(if negated {
+                        format!("not {}", predicate)
+                    } else {
+                        predicate.to_string()
+                    });
+                }
+            }
+        }
+    }
+}
+
+impl Predicate {
+    pub fn evaluate(&self, ctx: &ConditionContext<'_>) -> bool {
+        let entity_id;
+        let actual = if self.path == "$entity_id" {
+            entity_id = ctx.entity_id.map(|id| Value::String(id.to_string()));
+            entity_id.as_ref()
+        } else {
+            resolve_path(ctx.payload, &self.path)
+        };
+        let Some(actual) = actual.filter(|value| !value.is_null()) else {
+            // Missing values only satisfy `== null`.
+            return matches!(
+                (&self.op, &self.operand),
+                (Operator::Eq, Operand::Value(Value::Null))
+            );
+        };
+
+        match (&self.op, &self.operand) {
+            (Operator::Exists, _) => true,
+            (Operator::Eq, Operand::Value(expected)) => values_equal(actual, expected),
+            (Operator::Ne, Operand::Value(expected)) => !values_equal(actual, expected),
+            (Operator::Lt, Operand::Value(expected)) => {
+                compare(actual, expected) == Some(Ordering::Less)
+            }
+            (Operator::Le, Operand::Value(expected)) => matches!(
+                compare(actual, expected),
+                Some(Ordering::Less | Ordering::Equal)
+            ),
+            (Operator::Gt, Operand::Value(expected)) => {
+                compare(actual, expected) == Some(Ordering::Greater)
+            }
+            (Operator::Ge, Operand::Value(expected)) => matches!(
+                compare(actual, expected),
+                Some(Ordering::Greater | Ordering::Equal)
+            ),
+            (Operator::In, Operand::Value(Value::Array(options))) => {
+                options.iter().any(|option| values_equal(actual, option))
+            }
+            (Operator::Contains, Operand::Value(expected)) => match actual {
+                Value::Array(items) => items.iter().any(|item| values_equal(item, expected)),
+                Value::String(text) => expected.as_str().is_some_and(|part| text.contains(part)),
+                _ => false,
+            },
+            (Operator::StartsWith, Operand::Value(Value::String(prefix))) => actual
+                .as_str()
+                .is_some_and(|text| text.starts_with(prefix.as_str())),
+            (Operator::EndsWith, Operand::Value(Value::String(suffix))) => actual
+                .as_str()
+                .is_some_and(|text| text.ends_with(suffix.as_str())),
+            (Operator::Matches, Operand::Regex(regex)) => {
+                actual.as_str().is_some_and(|text| regex.is_match(text))
+            }
+            (Operator::Before | Operator::After, Operand::Date(date)) => {
+                let Some(actual_ms) = value_to_ms(actual) else {
+                    return false;
+                };
+                let reference_ms = match date {
+                    DateRef::Absolute(ms) => *ms,
+                    DateRef::Now(offset) => match ctx.now_ms.checked_add(*offset) {
+                        Some(ms) => ms,
+                        None => return false,
+                    },
+                };
+                if self.op == Operator::Before {
+                    actual_ms < reference_ms
+                } else {
+                    actual_ms > reference_ms
+                }
+            }
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for Operator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Operator::Eq => "==",
+            Operator::Ne => "!=",
+            Operator::Lt => "<",
+            Operator::Le => "<=",
+            Operator::Gt => ">",
+            Operator::Ge => ">=",
+            Operator::In => "in",
+            Operator::Contains => "contains",
+            Operator::StartsWith => "starts_with",
+            Operator::EndsWith => "ends_with",
+            Operator::Matches => "matches",
+            Operator::Before => "before",
+            Operator::After => "after",
+            Operator::Exists => "exists",
+        })
+    }
+}
+
+/// Written in expression syntax, so it parses back to the same predicate.
+impl fmt::Display for Predicate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.path, self.op)?;
+        match &self.operand {
+            Operand::None => Ok(()),
+            Operand::Value(value) => write!(f, " {}", value),
+            Operand::Regex(regex) => write!(f, " {}", Value::from(regex.as_str())),
+            Operand::Date(DateRef::Absolute(ms)) => match DateTime::from_timestamp_millis(*ms) {
+                Some(date) => write!(f, " \"{}\"", date.to_rfc3339()),
+                None => write!(f, " {}", ms),
+            },
+            Operand::Date(DateRef::Now(0)) => write!(f, " \"now\""),
+            Operand::Date(DateRef::Now(offset)) => {
+                let (amount, unit) = [
+                    (7 * 86_400_000, 'w'),
+                    (86_400_000, 'd'),
+                    (3_600_000, 'h'),
+                    (60_000, 'm'),
+                ]
+                .into_iter()
+                .find(|(unit_ms, _)| offset % unit_ms == 0)
+                .map(|(unit_ms, unit)| (offset / unit_ms, unit))
+                .unwrap_or((offset / 60_000, 'm'));
+                write!(f, " \"now{:+}{}\"", amount, unit)
+            }
+        }
+    }
+}
+
+/// Follow a dot-separated path; numeric segments index arrays.
+pub fn resolve_path<'a>(payload: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.')
+        .try_fold(payload, |value, segment| match value {
+            Value::Object(map) => map.get(segment),
+            Value::Array(items) => segment.parse::<usize>().ok().and_then(|i| items.get(i)),
+            _ => None,
+        })
+}
+
+fn values_equal(actual: &Value, expected: &Value) -> bool {
+    match (actual.as_f64(), expected.as_f64()) {
+        (Some(a), Some(b)) => a == b,
+        _ => actual == expected,
+    }
+}
+
+fn compare(actual: &Value, expected: &Value) -> Option<Ordering> {
+    match (actual, expected) {
+        (Value::Number(a), Value::Number(b)) => a.as_f64()?.partial_cmp(&b.as_f64()?),
+        (Value::String(a), Value::String(b)) => Some(a.cmp(b)),
+        _ => None,
+    }
+}
+
+fn value_to_ms(value: &Value) -> Option<i64> {
+    match value {
+        Value::Number(number) => number.as_i64(),
+        Value::String(text) => parse_date_ms(text),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const NOW: i64 = 1_735_689_600_000; // 2025-01-01T00:00:00Z
+
+    fn eval(conditions: Value, payload: Value) -> bool {
+        let condition = parse_conditions(&conditions).expect("valid condition");
+        condition.evaluate(&ConditionContext {
+            payload: &payload,
+            entity_id: Some("task-1"),
+            now_ms: NOW,
+        })
+    }
+
+    fn error(conditions: Value) -> ConditionError {
+        parse_conditions(&conditions).expect_err("invalid condition")
+    }
+
+    #[test]
+    fn test_expression_comparisons_on_nested_paths() {
+        let payload = json!({
+            "vehicle": {"year": 2012},
+            "client": {"customer_type": "business", "name": "Atelier Nord"},
+            "quote": {"total": 250000},
+        });
+        assert!(eval(json!("vehicle.year < 2015"), payload.clone()));
+        assert!(eval(
+            json!("client.customer_type == \"business\" and quote.total > 200000"),
+            payload.clone()
+        ));
+        assert!(!eval(json!("quote.total >= 300000"), payload.clone()));
+        assert!(eval(
+            json!("not (vehicle.year >= 2015) || client.name matches '^X'"),
+            payload.clone()
+        ));
+        assert!(eval(
+            json!("client.name starts_with 'Atelier'"),
+            payload.clone()
+        ));
+        assert!(!eval(json!("vehicle.vin exists"), payload));
+    }
+
+    #[test]
+    fn test_groups_structured_leaves_and_lists() {
+        let conditions = json!({
+            "all": [
+                {"path": "priority", "op": "in", "value": ["high", "urgent"]},
+                {"any": ["tags contains 'vip'", {"not": "zones.0 == 'capot'"}]},
+            ]
+        });
+        assert!(eval(
+            conditions.clone(),
+            json!({"priority": "urgent", "tags": ["vip"], "zones": ["capot"]})
+        ));
+        assert!(!eval(
+            conditions.clone(),
+            json!({"priority": "urgent", "tags": [], "zones": ["capot"]})
+        ));
+        assert!(!eval(
+            conditions,
+            json!({"priority": "low", "tags": ["vip"]})
+        ));
+        assert!(eval(json!("$entity_id in ['task-1', 'task-2']"), json!({})));
+    }
+
+    #[test]
+    fn test_date_predicates() {
+        let payload = json!({
+            "scheduled_date": "2024-12-15",
+            "created_at": NOW - 40 * 86_400_000_i64,
+        });
+        assert!(eval(
+            json!("scheduled_date before '2025-01-01'"),
+            payload.clone()
+        ));
+        assert!(eval(json!("scheduled_date after now-30d"), payload.clone()));
+        assert!(eval(json!("created_at before now-30d"), payload.clone()));
+        assert!(!eval(
+            json!({"path": "created_at", "op": "after", "value": "2024-12-01T00:00:00Z"}),
+            payload
+        ));
+    }
+
+    #[test]
+    fn test_bad_relative_dates_are_rejected() {
+        for value in ["nowé", "now+99999999999999d", "now-1000000000000w"] {
+            let err = error(json!({"path": "created_at", "op": "before", "value": value}));
+            assert_eq!(err.pointer, "/value", "{}", value);
+        }
+    }
+
+    #[test]
+    fn test_missing_paths_are_false_and_empty_conditions_match() {
+        assert!(!eval(
+            json!("client.customer_type != 'business'"),
+            json!({})
+        ));
+        assert!(eval(json!("client.customer_type == null"), json!({})));
+        assert!(eval(json!({}), json!({})));
+        assert!(eval(Value::Null, json!({})));
+    }
+
+    #[test]
+    fn test_matched_predicates_are_listed_as_expressions() {
+        let condition = parse_conditions(&json!(
+            "(priority in ['high', 'urgent'] or total > 1000) and not status == 'draft' \
+             and created_at after now-30d"
+        ))
+        .unwrap();
+        let payload =
+            json!({"priority": "urgent", "total": 500, "status": "sent", "created_at": NOW});
+        let matched = condition.matched_predicates(&ConditionContext {
+            payload: &payload,
+            entity_id: None,
+            now_ms: NOW,
+        });
+        assert_eq!(
+            matched,
+            vec![
+                r#"priority in ["high","urgent"]"#,
+                r#"not status == "draft""#,
+                r#"created_at after "now-30d""#,
+            ]
+        );
+        for expression in matched {
+            parse_conditions(&Value::String(expression)).expect("listed predicate parses");
+        }
+    }
+
+    #[test]
+    fn test_legacy_keys_keep_their_semantics() {
+        let conditions = json!({"status_in": ["completed"], "task_id_in": ["task-1"]});
+        assert!(eval(conditions.clone(), json!({"new_status": "completed"})));
+        assert!(eval(conditions.clone(), json!({"status": "completed"})));
+        assert!(!eval(
+            conditions,
+            json!({"new_status": "in_progress", "status": "completed"})
+        ));
+        assert!(!eval(
+            json!({"priority_in": ["urgent"]}),
+            json!({"priority": "low"})
+        ));
+    }
+
+    #[test]
+    fn test_errors_report_pointer_and_column() {
+        let err = error(json!({"all": ["vehicle.year < 2015", "quote.total >"]}));
+        assert_eq!(err.pointer, "/all/1");
+        assert_eq!(err.column, Some(13));
+        assert!(
+            err.message.contains("missing value"),
+            "got: {}",
+            err.message
+        );
+        assert_eq!(
+            err.to_string(),
+            format!("conditions/all/1, column 13: {}", err.message)
+        );
+
+        let err = error(json!("client.name matches '(unclosed'"));
+        assert_eq!(err.column, Some(21));
+        assert!(err.message.starts_with("invalid regex"));
+
+        let err = error(json!("client.customer_type = 'business'"));
+        assert_eq!(err.column, Some(22));
+
+        let err = error(json!("(vehicle.year < 2015"));
+        assert!(
+            err.message.contains("to close '(' at column 1"),
+            "got: {}",
+            err.message
+        );
+
+        let err = error(json!("client.customer_type == business"));
+        assert_eq!(err.column, Some(25));
+
+        let err = error(json!({"any": [{"path": "quote.total", "op": "between", "value": 1}]}));
+        assert_eq!(err.pointer, "/any/0/op");
+        assert_eq!(err.column, None);
+
+        let err = error(json!({"every": []}));
+        assert_eq!(err.pointer, "");
+        assert!(err.message.contains("unknown key 'every'"));
+
+        assert_eq!(error(json!({"status_in": "done"})).pointer, "/status_in");
+        assert_eq!(
+            error(json!("created_at before '31/12/2024'")).column,
+            Some(19)
+        );
+    }
+}
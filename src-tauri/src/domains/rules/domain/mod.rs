@@ -1 +1,2 @@
-pub(crate) mod models;
+pub(crate) mod conditions;
+pub mod models;
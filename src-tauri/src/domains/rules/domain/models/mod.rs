@@ -1 +1,2 @@
-pub(crate) mod rules;
+pub mod rules;
+pub mod trigger_payloads;
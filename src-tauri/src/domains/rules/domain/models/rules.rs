@@ -23,17 +23,61 @@ pub enum RuleTrigger {
     TaskStatusChanged,
     InterventionStarted,
     InterventionFinalized,
+    QuoteSent,
+    QuoteAccepted,
+    QuoteExpired,
+    MaterialLowStock,
+    MaterialConsumed,
+    ClientCreated,
+    StepCompleted,
+    PhotoUploaded,
 }
 
 impl RuleTrigger {
+    pub const ALL: [RuleTrigger; 12] = [
+        Self::TaskCreated,
+        Self::TaskStatusChanged,
+        Self::InterventionStarted,
+        Self::InterventionFinalized,
+        Self::QuoteSent,
+        Self::QuoteAccepted,
+        Self::QuoteExpired,
+        Self::MaterialLowStock,
+        Self::MaterialConsumed,
+        Self::ClientCreated,
+        Self::StepCompleted,
+        Self::PhotoUploaded,
+    ];
+
     pub fn as_str(&self) -> &'static str {
         match self {
             Self::TaskCreated => "task_created",
             Self::TaskStatusChanged => "task_status_changed",
             Self::InterventionStarted => "intervention_started",
             Self::InterventionFinalized => "intervention_finalized",
+            Self::QuoteSent => "quote_sent",
+            Self::QuoteAccepted => "quote_accepted",
+            Self::QuoteExpired => "quote_expired",
+            Self::MaterialLowStock => "material_low_stock",
+            Self::MaterialConsumed => "material_consumed",
+            Self::ClientCreated => "client_created",
+            Self::StepCompleted => "step_completed",
+            Self::PhotoUploaded => "photo_uploaded",
         }
     }
+
+    /// Whether an operation asks blocking rules before this trigger fires.
+    /// The others are only raised as domain events, so only reactive rules
+    /// can run on them.
+    pub fn has_blocking_check(&self) -> bool {
+        !matches!(
+            self,
+            Self::MaterialLowStock
+                | Self::ClientCreated
+                | Self::StepCompleted
+                | Self::PhotoUploaded
+        )
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
@@ -46,6 +90,45 @@ pub enum RuleAction {
         event_name: String,
         integration_ids: Option<Vec<String>>,
     },
+    /// Send a message through the notifications outbox (`email`, `sms` or
+    /// `in_app`).
+    SendMessage {
+        channel: String,
+        recipient_id: Option<String>,
+        recipient_email: Option<String>,
+        recipient_phone: Option<String>,
+        subject: Option<String>,
+        body: String,
+    },
+    /// In-app notification; `user_id` defaults to the user behind the event.
+    CreateNotification {
+        user_id: Option<String>,
+        title: String,
+        message: String,
+    },
+    SetTaskTag {
+        tag: String,
+    },
+}
+
+impl RuleAction {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Block { .. } => "block",
+            Self::QueueIntegration { .. } => "queue_integration",
+            Self::SendMessage { .. } => "send_message",
+            Self::CreateNotification { .. } => "create_notification",
+            Self::SetTaskTag { .. } => "set_task_tag",
+        }
+    }
+}
+
+/// Outcome of one action of a reactive rule run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+pub struct RuleActionResult {
+    pub action: String,
+    pub success: bool,
+    pub detail: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
@@ -105,3 +188,70 @@ pub struct RuleEvaluationResult {
     pub message: Option<String>,
     pub queued_actions: Vec<RuleAction>,
 }
+
+/// One evaluation of a rule, as stored in `rule_execution_logs`.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct RuleExecutionRecord {
+    pub id: String,
+    pub rule_id: String,
+    pub trigger: String,
+    pub entity_id: Option<String>,
+    pub correlation_id: String,
+    pub allowed: bool,
+    pub message: Option<String>,
+    pub mode: RuleMode,
+    /// Domain event that triggered a reactive run.
+    pub event_id: Option<String>,
+    pub action_results: Vec<RuleActionResult>,
+    pub created_at: i64,
+}
+
+/// Replay a rule against the last `days` days of recorded operations.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct BacktestRuleRequest {
+    pub rule_id: String,
+    pub days: u32,
+}
+
+/// A recorded operation, rebuilt as the rule check it would have made.
+#[derive(Debug, Clone)]
+pub struct RuleHistorySample {
+    /// The recorded row: task, `task_history` entry or intervention.
+    pub source_id: String,
+    pub entity_id: Option<String>,
+    pub user_id: Option<String>,
+    /// When the operation happened, in Unix milliseconds.
+    pub occurred_at: i64,
+    pub payload: serde_json::Value,
+}
+
+/// A recorded operation the rule's conditions matched.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct RuleBacktestMatch {
+    /// The recorded row: task, `task_history` entry or intervention.
+    pub source_id: String,
+    /// Entity id of the rule check, as the live check sends it.
+    pub entity_id: Option<String>,
+    pub occurred_at: i64,
+    /// `true` for blocking rules with a block action.
+    pub would_block: bool,
+    /// Predicates that held, as condition expressions.
+    pub matched_conditions: Vec<String>,
+    #[ts(type = "JsonValue")]
+    pub payload: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct RuleBacktestReport {
+    pub rule_id: String,
+    pub trigger: RuleTrigger,
+    pub mode: RuleMode,
+    /// Start of the replayed window, in Unix milliseconds.
+    pub since: i64,
+    pub evaluated: u32,
+    pub matched: u32,
+    pub would_block: u32,
+    /// `true` when the window held more operations than were replayed.
+    pub truncated: bool,
+    pub matches: Vec<RuleBacktestMatch>,
+}
@@ -0,0 +1,193 @@
+//! Payload schema of each `RuleTrigger`.
+//!
+//! One struct per trigger lists the keys rule conditions can reference. The
+//! blocking check and the domain event behind a trigger do not always know the
+//! same facts (a check runs before the entity is saved), so keys only one of
+//! them sends are optional. Event payloads also carry their metadata fields.
+
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use super::rules::RuleTrigger;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+pub struct TaskCreatedPayload {
+    /// Absent in blocking checks: the task does not exist yet.
+    pub task_id: Option<String>,
+    pub task_number: Option<String>,
+    pub title: String,
+    pub priority: Option<String>,
+    pub status: Option<String>,
+    pub technician_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+pub struct TaskStatusChangedPayload {
+    pub task_id: Option<String>,
+    pub old_status: String,
+    pub new_status: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+pub struct InterventionStartedPayload {
+    /// Absent in blocking checks: the intervention does not exist yet.
+    pub intervention_id: Option<String>,
+    pub task_id: String,
+    pub priority: Option<String>,
+    pub estimated_duration_minutes: Option<u32>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+pub struct InterventionFinalizedPayload {
+    pub intervention_id: String,
+    pub task_id: Option<String>,
+    pub technician_id: Option<String>,
+    pub completed_at_ms: Option<i64>,
+    pub quality_score: Option<i32>,
+    pub customer_satisfaction: Option<i32>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+pub struct QuoteSentPayload {
+    pub quote_id: String,
+    pub quote_number: String,
+    pub client_id: String,
+    pub task_id: Option<String>,
+    /// Total including tax, in cents.
+    pub total: i64,
+    /// Revision frozen by the send; absent in blocking checks.
+    pub revision: Option<i32>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+pub struct QuoteAcceptedPayload {
+    pub quote_id: String,
+    pub quote_number: String,
+    pub client_id: String,
+    pub task_id: Option<String>,
+    /// Total including tax, in cents; absent in events.
+    pub total: Option<i64>,
+    /// `true` when the customer signed the acceptance; absent in events.
+    pub signed: Option<bool>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+pub struct QuoteExpiredPayload {
+    pub quote_id: String,
+    pub quote_number: String,
+    pub client_id: String,
+    /// Total including tax, in cents; absent in events.
+    pub total: Option<i64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+pub struct MaterialLowStockPayload {
+    pub material_id: String,
+    pub material_name: String,
+    pub current_stock: f64,
+    pub minimum_stock: f64,
+    pub unit: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+pub struct MaterialConsumedPayload {
+    pub material_id: String,
+    pub intervention_id: String,
+    pub quantity: f64,
+    pub unit: Option<String>,
+    pub step_id: Option<String>,
+    /// Absent in events.
+    pub waste_quantity: Option<f64>,
+    /// Stock before consumption; absent in events.
+    pub current_stock: Option<f64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+pub struct ClientCreatedPayload {
+    pub client_id: String,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+pub struct StepCompletedPayload {
+    pub intervention_id: String,
+    pub step_id: String,
+    pub step_number: i32,
+    pub photos_taken: i32,
+    pub duration_seconds: Option<i32>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+pub struct PhotoUploadedPayload {
+    pub photo_id: String,
+    pub intervention_id: String,
+    pub step_id: Option<String>,
+    pub step_number: Option<i32>,
+}
+
+/// A trigger with its payload, as the rule editor sees it.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(tag = "trigger", content = "payload", rename_all = "snake_case")]
+pub enum RuleTriggerPayload {
+    TaskCreated(TaskCreatedPayload),
+    TaskStatusChanged(TaskStatusChangedPayload),
+    InterventionStarted(InterventionStartedPayload),
+    InterventionFinalized(InterventionFinalizedPayload),
+    QuoteSent(QuoteSentPayload),
+    QuoteAccepted(QuoteAcceptedPayload),
+    QuoteExpired(QuoteExpiredPayload),
+    MaterialLowStock(MaterialLowStockPayload),
+    MaterialConsumed(MaterialConsumedPayload),
+    ClientCreated(ClientCreatedPayload),
+    StepCompleted(StepCompletedPayload),
+    PhotoUploaded(PhotoUploadedPayload),
+}
+
+/// Payload keys of one trigger, for condition autocomplete.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct RuleTriggerSchema {
+    pub trigger: RuleTrigger,
+    pub fields: Vec<String>,
+}
+
+impl RuleTriggerPayload {
+    /// An empty payload of `trigger`'s shape.
+    pub fn empty(trigger: &RuleTrigger) -> Self {
+        match trigger {
+            RuleTrigger::TaskCreated => Self::TaskCreated(Default::default()),
+            RuleTrigger::TaskStatusChanged => Self::TaskStatusChanged(Default::default()),
+            RuleTrigger::InterventionStarted => Self::InterventionStarted(Default::default()),
+            RuleTrigger::InterventionFinalized => Self::InterventionFinalized(Default::default()),
+            RuleTrigger::QuoteSent => Self::QuoteSent(Default::default()),
+            RuleTrigger::QuoteAccepted => Self::QuoteAccepted(Default::default()),
+            RuleTrigger::QuoteExpired => Self::QuoteExpired(Default::default()),
+            RuleTrigger::MaterialLowStock => Self::MaterialLowStock(Default::default()),
+            RuleTrigger::MaterialConsumed => Self::MaterialConsumed(Default::default()),
+            RuleTrigger::ClientCreated => Self::ClientCreated(Default::default()),
+            RuleTrigger::StepCompleted => Self::StepCompleted(Default::default()),
+            RuleTrigger::PhotoUploaded => Self::PhotoUploaded(Default::default()),
+        }
+    }
+}
+
+impl RuleTriggerSchema {
+    pub fn for_trigger(trigger: RuleTrigger) -> Self {
+        let fields = match serde_json::to_value(RuleTriggerPayload::empty(&trigger)) {
+            Ok(serde_json::Value::Object(mut tagged)) => match tagged.remove("payload") {
+                Some(serde_json::Value::Object(payload)) => payload.keys().cloned().collect(),
+                _ => Vec::new(),
+            },
+            _ => Vec::new(),
+        };
+        Self { trigger, fields }
+    }
+
+    /// Schemas of every trigger, in `RuleTrigger::ALL` order.
+    pub fn all() -> Vec<Self> {
+        RuleTrigger::ALL
+            .iter()
+            .cloned()
+            .map(Self::for_trigger)
+            .collect()
+    }
+}
@@ -3,23 +3,14 @@ use rusqlite::{params, OptionalExtension};
 use std::sync::Arc;
 
 use crate::db::Database;
+pub use crate::domains::rules::domain::models::rules::RuleExecutionRecord;
 use crate::domains::rules::domain::models::rules::{
-    RuleAction, RuleDefinition, RuleMode, RuleStatus, RuleTrigger,
+    RuleAction, RuleActionResult, RuleDefinition, RuleHistorySample, RuleMode, RuleStatus,
+    RuleTrigger,
 };
+use crate::shared::contracts::rules_engine::payloads;
 use crate::shared::error::{AppError, AppResult};
 
-#[derive(Debug, Clone)]
-pub struct RuleExecutionRecord {
-    pub id: String,
-    pub rule_id: String,
-    pub trigger: String,
-    pub entity_id: Option<String>,
-    pub correlation_id: String,
-    pub allowed: bool,
-    pub message: Option<String>,
-    pub created_at: i64,
-}
-
 #[async_trait]
 pub trait RulesRepository: Send + Sync {
     async fn list(&self) -> AppResult<Vec<RuleDefinition>>;
@@ -28,6 +19,19 @@ pub trait RulesRepository: Send + Sync {
     async fn update(&self, rule: &RuleDefinition) -> AppResult<()>;
     async fn list_active_by_trigger(&self, trigger: &str) -> AppResult<Vec<RuleDefinition>>;
     async fn log_execution(&self, record: &RuleExecutionRecord) -> AppResult<()>;
+    async fn list_executions(
+        &self,
+        rule_id: &str,
+        limit: usize,
+    ) -> AppResult<Vec<RuleExecutionRecord>>;
+    /// Recorded `trigger` operations since `since` (Unix ms), oldest first.
+    /// Empty for triggers without a history source.
+    async fn list_history(
+        &self,
+        trigger: &RuleTrigger,
+        since: i64,
+        limit: usize,
+    ) -> AppResult<Vec<RuleHistorySample>>;
 }
 
 pub struct SqliteRulesRepository {
@@ -39,6 +43,104 @@ impl SqliteRulesRepository {
         Self { db }
     }
 
+    /// History query of `trigger`, binding `?1` = since and `?2` = limit.
+    fn history_sql(trigger: &RuleTrigger) -> Option<&'static str> {
+        match trigger {
+            RuleTrigger::TaskCreated => Some(
+                "SELECT id, title, priority, status, technician_id, created_by, created_at
+                 FROM tasks
+                 WHERE created_at >= ?1 AND deleted_at IS NULL
+                 ORDER BY created_at ASC
+                 LIMIT ?2",
+            ),
+            RuleTrigger::TaskStatusChanged => Some(
+                "SELECT id, task_id, old_status, new_status, changed_by, changed_at
+                 FROM task_history
+                 WHERE changed_at >= ?1
+                 ORDER BY changed_at ASC
+                 LIMIT ?2",
+            ),
+            RuleTrigger::InterventionStarted => Some(
+                "SELECT i.id, i.task_id, i.technician_id, i.started_at, i.estimated_duration, t.priority
+                 FROM interventions i
+                 LEFT JOIN tasks t ON t.id = i.task_id
+                 WHERE i.started_at >= ?1
+                 ORDER BY i.started_at ASC
+                 LIMIT ?2",
+            ),
+            RuleTrigger::InterventionFinalized => Some(
+                "SELECT id, technician_id, completed_at, quality_score, customer_satisfaction
+                 FROM interventions
+                 WHERE status = 'completed' AND completed_at >= ?1
+                 ORDER BY completed_at ASC
+                 LIMIT ?2",
+            ),
+            _ => None,
+        }
+    }
+
+    /// Rebuild a history row as the rule check `trigger`'s blocking check
+    /// makes, with the payload from [`payloads`].
+    fn map_history_row(
+        trigger: &RuleTrigger,
+        row: &rusqlite::Row<'_>,
+    ) -> rusqlite::Result<RuleHistorySample> {
+        match trigger {
+            RuleTrigger::TaskCreated => Ok(RuleHistorySample {
+                source_id: row.get("id")?,
+                entity_id: None,
+                user_id: row.get("created_by")?,
+                occurred_at: row.get("created_at")?,
+                payload: payloads::task_created(
+                    &row.get::<_, String>("title")?,
+                    row.get::<_, Option<String>>("priority")?.as_deref(),
+                    row.get::<_, Option<String>>("status")?.as_deref(),
+                    row.get::<_, Option<String>>("technician_id")?.as_deref(),
+                ),
+            }),
+            RuleTrigger::TaskStatusChanged => Ok(RuleHistorySample {
+                source_id: row.get("id")?,
+                entity_id: Some(row.get("task_id")?),
+                user_id: row.get("changed_by")?,
+                occurred_at: row.get("changed_at")?,
+                payload: payloads::task_status_changed(
+                    row.get::<_, Option<String>>("old_status")?.as_deref(),
+                    &row.get::<_, String>("new_status")?,
+                ),
+            }),
+            RuleTrigger::InterventionStarted => {
+                let task_id: String = row.get("task_id")?;
+                Ok(RuleHistorySample {
+                    source_id: row.get("id")?,
+                    // The start check runs before the intervention exists.
+                    entity_id: Some(task_id.clone()),
+                    user_id: row.get("technician_id")?,
+                    occurred_at: row.get("started_at")?,
+                    payload: payloads::intervention_started(
+                        &task_id,
+                        row.get::<_, Option<i64>>("estimated_duration")?,
+                        row.get::<_, Option<String>>("priority")?.as_deref(),
+                    ),
+                })
+            }
+            RuleTrigger::InterventionFinalized => {
+                let intervention_id: String = row.get("id")?;
+                Ok(RuleHistorySample {
+                    source_id: intervention_id.clone(),
+                    entity_id: Some(intervention_id.clone()),
+                    user_id: row.get("technician_id")?,
+                    occurred_at: row.get("completed_at")?,
+                    payload: payloads::intervention_finalized(
+                        &intervention_id,
+                        row.get::<_, Option<i32>>("quality_score")?,
+                        row.get::<_, Option<i32>>("customer_satisfaction")?,
+                    ),
+                })
+            }
+            _ => Err(rusqlite::Error::InvalidQuery),
+        }
+    }
+
     fn map_rule(row: &rusqlite::Row<'_>) -> rusqlite::Result<RuleDefinition> {
         let trigger: String = row.get("trigger")?;
         let mode: String = row.get("mode")?;
@@ -233,8 +335,9 @@ impl RulesRepository for SqliteRulesRepository {
             .map_err(|error| AppError::db_sanitized("rules.log.get_connection", error))?;
         conn.execute(
             "INSERT INTO rule_execution_logs (
-                id, rule_id, trigger, entity_id, correlation_id, allowed, message, created_at
-             ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                id, rule_id, trigger, entity_id, correlation_id, allowed, message, created_at,
+                mode, event_id, action_results_json
+             ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
             params![
                 record.id,
                 record.rule_id,
@@ -244,9 +347,98 @@ impl RulesRepository for SqliteRulesRepository {
                 if record.allowed { 1 } else { 0 },
                 record.message,
                 record.created_at,
+                match record.mode {
+                    RuleMode::Blocking => "blocking",
+                    RuleMode::Reactive => "reactive",
+                },
+                record.event_id,
+                (!record.action_results.is_empty())
+                    .then(|| serde_json::to_string(&record.action_results).ok())
+                    .flatten(),
             ],
         )
         .map_err(|error| AppError::db_sanitized("rules.log.execute", error))?;
         Ok(())
     }
+
+    async fn list_executions(
+        &self,
+        rule_id: &str,
+        limit: usize,
+    ) -> AppResult<Vec<RuleExecutionRecord>> {
+        let conn = self
+            .db
+            .get_connection()
+            .map_err(|error| AppError::db_sanitized("rules.executions.get_connection", error))?;
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, rule_id, trigger, entity_id, correlation_id, allowed, message, created_at, mode, event_id, action_results_json
+                 FROM rule_execution_logs
+                 WHERE rule_id = ?1
+                 ORDER BY created_at DESC
+                 LIMIT ?2",
+            )
+            .map_err(|error| AppError::db_sanitized("rules.executions.prepare", error))?;
+        let rows = stmt
+            .query_map(params![rule_id, limit as i64], |row| {
+                let mode: String = row.get("mode")?;
+                let action_results: Option<String> = row.get("action_results_json")?;
+                Ok(RuleExecutionRecord {
+                    id: row.get("id")?,
+                    rule_id: row.get("rule_id")?,
+                    trigger: row.get("trigger")?,
+                    entity_id: row.get("entity_id")?,
+                    correlation_id: row.get("correlation_id")?,
+                    allowed: row.get::<_, i64>("allowed")? != 0,
+                    message: row.get("message")?,
+                    mode: if mode == "reactive" {
+                        RuleMode::Reactive
+                    } else {
+                        RuleMode::Blocking
+                    },
+                    event_id: row.get("event_id")?,
+                    action_results: action_results
+                        .and_then(|value| {
+                            serde_json::from_str::<Vec<RuleActionResult>>(&value).ok()
+                        })
+                        .unwrap_or_default(),
+                    created_at: row.get("created_at")?,
+                })
+            })
+            .map_err(|error| AppError::db_sanitized("rules.executions.query", error))?;
+        let mut records = Vec::new();
+        for row in rows {
+            records
+                .push(row.map_err(|error| AppError::db_sanitized("rules.executions.row", error))?);
+        }
+        Ok(records)
+    }
+
+    async fn list_history(
+        &self,
+        trigger: &RuleTrigger,
+        since: i64,
+        limit: usize,
+    ) -> AppResult<Vec<RuleHistorySample>> {
+        let Some(sql) = Self::history_sql(trigger) else {
+            return Ok(Vec::new());
+        };
+        let conn = self
+            .db
+            .get_connection()
+            .map_err(|error| AppError::db_sanitized("rules.history.get_connection", error))?;
+        let mut stmt = conn
+            .prepare(sql)
+            .map_err(|error| AppError::db_sanitized("rules.history.prepare", error))?;
+        let rows = stmt
+            .query_map(params![since, limit as i64], |row| {
+                Self::map_history_row(trigger, row)
+            })
+            .map_err(|error| AppError::db_sanitized("rules.history.query", error))?;
+        let mut samples = Vec::new();
+        for row in rows {
+            samples.push(row.map_err(|error| AppError::db_sanitized("rules.history.row", error))?);
+        }
+        Ok(samples)
+    }
 }
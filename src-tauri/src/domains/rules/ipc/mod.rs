@@ -1,8 +1,9 @@
 use crate::commands::{AppResult, AppState};
 use crate::domains::rules::domain::models::rules::{
-    CreateRuleDefinitionRequest, RuleDefinition, RuleEvaluationResult, TestRuleRequest,
-    UpdateRuleDefinitionRequest,
+    BacktestRuleRequest, CreateRuleDefinitionRequest, RuleBacktestReport, RuleDefinition,
+    RuleEvaluationResult, RuleExecutionRecord, TestRuleRequest, UpdateRuleDefinitionRequest,
 };
+use crate::domains::rules::domain::models::trigger_payloads::RuleTriggerSchema;
 use crate::resolve_context;
 use crate::shared::contracts::auth::UserRole;
 
@@ -85,3 +86,35 @@ pub async fn test_rule(
     let ctx = resolve_context!(&state, &correlation_id, UserRole::Admin);
     state.rules_service.test(&ctx, request).await
 }
+
+/// Replay a rule against recent history without applying or logging it.
+#[tauri::command]
+pub async fn backtest_rule(
+    request: BacktestRuleRequest,
+    state: AppState<'_>,
+    correlation_id: Option<String>,
+) -> AppResult<RuleBacktestReport> {
+    let ctx = resolve_context!(&state, &correlation_id, UserRole::Admin);
+    state.rules_service.backtest(&ctx, request).await
+}
+
+#[tauri::command]
+pub async fn list_rule_executions(
+    id: String,
+    limit: Option<usize>,
+    state: AppState<'_>,
+    correlation_id: Option<String>,
+) -> AppResult<Vec<RuleExecutionRecord>> {
+    let ctx = resolve_context!(&state, &correlation_id, UserRole::Admin);
+    state.rules_service.list_executions(&ctx, &id, limit).await
+}
+
+/// Payload keys of every trigger, for the rule editor's condition autocomplete.
+#[tauri::command]
+pub async fn list_rule_trigger_schemas(
+    state: AppState<'_>,
+    correlation_id: Option<String>,
+) -> AppResult<Vec<RuleTriggerSchema>> {
+    let _ctx = resolve_context!(&state, &correlation_id, UserRole::Admin);
+    Ok(RuleTriggerSchema::all())
+}
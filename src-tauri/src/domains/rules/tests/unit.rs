@@ -3,14 +3,28 @@
 #[cfg(test)]
 mod tests {
     use crate::db::Database;
+    use crate::domains::rules::application::event_handler::{
+        rule_check_request, RulesEventHandler,
+    };
+    use crate::domains::rules::application::services::reactive_rules::RuleActionPorts;
     use crate::domains::rules::application::services::rules_service::RulesService;
     use crate::domains::rules::domain::models::rules::{
-        CreateRuleDefinitionRequest, RuleAction, RuleMode, RuleStatus, RuleTrigger,
-        TestRuleRequest, UpdateRuleDefinitionRequest,
+        BacktestRuleRequest, CreateRuleDefinitionRequest, RuleAction, RuleMode, RuleStatus,
+        RuleTrigger, TestRuleRequest, UpdateRuleDefinitionRequest,
     };
+    use crate::domains::rules::domain::models::trigger_payloads::RuleTriggerSchema;
     use crate::shared::context::RequestContext;
+    use crate::shared::contracts::integration_sink::{
+        IntegrationDispatchRequest, IntegrationEventSink,
+    };
+    use crate::shared::contracts::task_tagging::TaskTagger;
+    use crate::shared::error::AppError;
+    use crate::shared::event_bus::{DomainEvent, DomainEventHandler};
+    use crate::shared::services::event_bus::event_factory;
+    use crate::test_utils::DummyNotificationSender;
+    use async_trait::async_trait;
     use serde_json::json;
-    use std::sync::Arc;
+    use std::sync::{Arc, Mutex};
 
     fn ctx() -> RequestContext {
         RequestContext::unauthenticated("rules-test-correlation".to_string())
@@ -21,6 +35,47 @@ mod tests {
         RulesService::new(db)
     }
 
+    struct FailingSink;
+
+    #[async_trait]
+    impl IntegrationEventSink for FailingSink {
+        async fn enqueue(&self, _request: IntegrationDispatchRequest) -> Result<usize, AppError> {
+            Err(AppError::Internal(
+                "integration outbox unavailable".to_string(),
+            ))
+        }
+
+        async fn process_pending(&self, _limit: usize) -> Result<usize, AppError> {
+            Ok(0)
+        }
+    }
+
+    #[derive(Default)]
+    struct RecordingTagger(Mutex<Vec<(String, String)>>);
+
+    #[async_trait]
+    impl TaskTagger for RecordingTagger {
+        async fn add_task_tag(&self, task_id: &str, tag: &str) -> Result<bool, AppError> {
+            self.0
+                .lock()
+                .unwrap()
+                .push((task_id.to_string(), tag.to_string()));
+            Ok(true)
+        }
+    }
+
+    fn status_changed(new_status: &str) -> DomainEvent {
+        DomainEvent::TaskStatusChanged {
+            id: format!("evt-{}", new_status),
+            task_id: "task-9".to_string(),
+            old_status: "in_progress".to_string(),
+            new_status: new_status.to_string(),
+            user_id: "user-1".to_string(),
+            timestamp: chrono::Utc::now(),
+            metadata: Some(json!({ "correlation_id": "corr-reactive" })),
+        }
+    }
+
     #[tokio::test]
     async fn test_rules_service_blocks_matching_status_rule() {
         let service = service().await;
@@ -107,4 +162,529 @@ mod tests {
         assert!(result.matched_rule_ids.is_empty());
         assert!(result.message.is_none());
     }
+
+    #[tokio::test]
+    async fn test_rules_service_evaluates_expression_conditions() {
+        let service = service().await;
+        let request = CreateRuleDefinitionRequest {
+            name: "Old vehicles for business clients".to_string(),
+            description: None,
+            template_key: "task-vehicle-policy".to_string(),
+            trigger: RuleTrigger::TaskCreated,
+            mode: RuleMode::Blocking,
+            conditions: json!({
+                "all": [
+                    "vehicle.year < 2015",
+                    { "any": ["client.customer_type == \"business\"", "quote.total > 200000"] }
+                ]
+            }),
+            actions: vec![RuleAction::Block {
+                message: "Vehicles before 2015 need a workshop inspection".to_string(),
+            }],
+        };
+
+        let created = service.create(&ctx(), request).await.expect("create rule");
+        service
+            .activate(&ctx(), &created.id)
+            .await
+            .expect("activate rule");
+
+        let check = |payload: serde_json::Value| TestRuleRequest {
+            trigger: RuleTrigger::TaskCreated,
+            entity_id: Some("task-3".to_string()),
+            payload,
+        };
+        let blocked = service
+            .test(
+                &ctx(),
+                check(json!({
+                    "vehicle": { "year": 2012 },
+                    "client": { "customer_type": "individual" },
+                    "quote": { "total": 250000 }
+                })),
+            )
+            .await
+            .expect("evaluate rule");
+        let allowed = service
+            .test(
+                &ctx(),
+                check(json!({
+                    "vehicle": { "year": 2019 },
+                    "client": { "customer_type": "business" }
+                })),
+            )
+            .await
+            .expect("evaluate rule");
+
+        assert!(!blocked.allowed);
+        assert_eq!(blocked.matched_rule_ids, vec![created.id]);
+        assert!(allowed.allowed);
+        assert!(allowed.matched_rule_ids.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_reactive_rule_runs_actions_from_domain_event_and_logs_run() {
+        let service = Arc::new(service().await);
+        let created = service
+            .create(
+                &ctx(),
+                CreateRuleDefinitionRequest {
+                    name: "Tag completed tasks".to_string(),
+                    description: None,
+                    template_key: "task-completed-followup".to_string(),
+                    trigger: RuleTrigger::TaskStatusChanged,
+                    mode: RuleMode::Reactive,
+                    conditions: json!("new_status == 'completed'"),
+                    actions: vec![
+                        RuleAction::SetTaskTag {
+                            tag: "to-invoice".to_string(),
+                        },
+                        RuleAction::QueueIntegration {
+                            event_name: "task_completed".to_string(),
+                            integration_ids: None,
+                        },
+                        RuleAction::CreateNotification {
+                            user_id: None,
+                            title: "Task completed".to_string(),
+                            message: "Ready for invoicing".to_string(),
+                        },
+                    ],
+                },
+            )
+            .await
+            .expect("create rule");
+        service
+            .activate(&ctx(), &created.id)
+            .await
+            .expect("activate rule");
+
+        let tagger = Arc::new(RecordingTagger::default());
+        let handler = RulesEventHandler::new(
+            service.clone(),
+            RuleActionPorts {
+                integration_sink: Arc::new(FailingSink),
+                notification_sender: Arc::new(DummyNotificationSender),
+                task_tagger: tagger.clone(),
+            },
+        );
+        handler
+            .handle(&status_changed("on_hold"))
+            .await
+            .expect("non matching event");
+        handler
+            .handle(&status_changed("completed"))
+            .await
+            .expect("matching event");
+
+        let runs = service
+            .list_executions(&ctx(), &created.id, None)
+            .await
+            .expect("list executions");
+        assert_eq!(runs.len(), 1);
+        let run = &runs[0];
+        assert_eq!(run.mode, RuleMode::Reactive);
+        assert_eq!(run.correlation_id, "corr-reactive");
+        assert_eq!(run.event_id.as_deref(), Some("evt-completed"));
+        assert_eq!(run.entity_id.as_deref(), Some("task-9"));
+        assert_eq!(run.message.as_deref(), Some("1 of 3 actions failed"));
+        let outcomes: Vec<(&str, bool)> = run
+            .action_results
+            .iter()
+            .map(|result| (result.action.as_str(), result.success))
+            .collect();
+        assert_eq!(
+            outcomes,
+            vec![
+                ("set_task_tag", true),
+                ("queue_integration", false),
+                ("create_notification", true),
+            ]
+        );
+        assert_eq!(
+            *tagger.0.lock().unwrap(),
+            vec![("task-9".to_string(), "to-invoice".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_trigger_schemas_cover_every_trigger() {
+        let schemas = RuleTriggerSchema::all();
+        assert_eq!(schemas.len(), RuleTrigger::ALL.len());
+        let quote_sent = schemas
+            .iter()
+            .find(|schema| schema.trigger == RuleTrigger::QuoteSent)
+            .expect("quote_sent schema");
+        for key in ["quote_id", "client_id", "total", "revision"] {
+            assert!(
+                quote_sent.fields.iter().any(|field| field == key),
+                "{}",
+                key
+            );
+        }
+    }
+
+    #[test]
+    fn test_extended_events_map_to_triggers_with_schema_keys() {
+        let events = vec![
+            event_factory::quote_sent(
+                "quote-1".to_string(),
+                "Q-0001".to_string(),
+                "client-1".to_string(),
+                None,
+                1,
+                12000,
+                "user-1".to_string(),
+            ),
+            event_factory::material_low_stock(
+                "mat-1".to_string(),
+                "Gloss film".to_string(),
+                2.0,
+                5.0,
+                "m".to_string(),
+            ),
+            event_factory::material_consumed_by(
+                "mat-1".to_string(),
+                "int-1".to_string(),
+                1.5,
+                "m".to_string(),
+                "user-1".to_string(),
+            ),
+            event_factory::photo_uploaded_with_ctx(
+                "photo-1".to_string(),
+                "int-1".to_string(),
+                Some("step-1".to_string()),
+                Some(2),
+                "user-1".to_string(),
+                "corr-photo".to_string(),
+            ),
+        ];
+        let expected = [
+            (RuleTrigger::QuoteSent, "quote-1", "user-1"),
+            (RuleTrigger::MaterialLowStock, "mat-1", "system"),
+            (RuleTrigger::MaterialConsumed, "mat-1", "user-1"),
+            (RuleTrigger::PhotoUploaded, "photo-1", "user-1"),
+        ];
+
+        for (event, (trigger, entity_id, user_id)) in events.iter().zip(expected) {
+            let request = rule_check_request(event).expect("mapped event");
+            assert_eq!(request.trigger, trigger.as_str());
+            assert_eq!(request.entity_id.as_deref(), Some(entity_id));
+            assert_eq!(request.user_id, user_id);
+            let schema = RuleTriggerSchema::for_trigger(trigger);
+            for key in request.payload.as_object().expect("object payload").keys() {
+                assert!(
+                    schema.fields.contains(key),
+                    "{} payload key '{}' missing from schema",
+                    request.trigger,
+                    key
+                );
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_backtest_replays_task_history_without_logging_runs() {
+        let db = Arc::new(Database::new_in_memory().await.expect("rules test db"));
+        let service = RulesService::new(db.clone());
+        let now = chrono::Utc::now().timestamp_millis();
+        let day = 86_400_000_i64;
+        for (task_id, number) in [("task-a", "T-001"), ("task-b", "T-002")] {
+            db.execute(
+                "INSERT INTO tasks (id, task_number, title, status, priority, created_at, updated_at)
+                 VALUES (?1, ?2, 'Backtest task', 'in_progress', 'medium', ?3, ?3)",
+                rusqlite::params![task_id, number, now - 40 * day],
+            )
+            .expect("seed task");
+        }
+        for (task_id, old_status, new_status, changed_at) in [
+            ("task-a", "in_progress", "completed", now - 2 * day),
+            ("task-b", "in_progress", "on_hold", now - day),
+            ("task-b", "on_hold", "completed", now - 40 * day),
+        ] {
+            db.execute(
+                "INSERT INTO task_history (task_id, old_status, new_status, changed_at)
+                 VALUES (?1, ?2, ?3, ?4)",
+                rusqlite::params![task_id, old_status, new_status, changed_at],
+            )
+            .expect("seed task history");
+        }
+
+        let created = service
+            .create(
+                &ctx(),
+                CreateRuleDefinitionRequest {
+                    name: "Hold completions".to_string(),
+                    description: None,
+                    template_key: "task-status-policy".to_string(),
+                    trigger: RuleTrigger::TaskStatusChanged,
+                    mode: RuleMode::Blocking,
+                    conditions: json!("new_status == 'completed' and not old_status == 'on_hold'"),
+                    actions: vec![RuleAction::Block {
+                        message: "Completion needs a quality check".to_string(),
+                    }],
+                },
+            )
+            .await
+            .expect("create draft rule");
+
+        let report = service
+            .backtest(
+                &ctx(),
+                BacktestRuleRequest {
+                    rule_id: created.id.clone(),
+                    days: 30,
+                },
+            )
+            .await
+            .expect("backtest rule");
+
+        assert_eq!(report.evaluated, 2);
+        assert_eq!(report.matched, 1);
+        assert_eq!(report.would_block, 1);
+        assert!(!report.truncated);
+        let hit = &report.matches[0];
+        assert_eq!(hit.entity_id.as_deref(), Some("task-a"));
+        assert!(hit.would_block);
+        assert_eq!(
+            hit.matched_conditions,
+            vec![
+                r#"new_status == "completed""#.to_string(),
+                r#"not old_status == "on_hold""#.to_string(),
+            ]
+        );
+        assert!(service
+            .list_executions(&ctx(), &created.id, None)
+            .await
+            .expect("list executions")
+            .is_empty());
+
+        let err = service
+            .backtest(
+                &ctx(),
+                BacktestRuleRequest {
+                    rule_id: created.id,
+                    days: 0,
+                },
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AppError::Validation(_)));
+    }
+
+    /// Backtest samples carry the entity id and payload the live blocking
+    /// checks send for the same operations.
+    #[tokio::test]
+    async fn test_backtest_samples_match_live_check_payloads() {
+        use crate::domains::rules::infrastructure::rules_repository::{
+            RulesRepository, SqliteRulesRepository,
+        };
+        use crate::shared::contracts::rules_engine::payloads;
+
+        let db = Arc::new(Database::new_in_memory().await.expect("rules test db"));
+        let repo = SqliteRulesRepository::new(db.clone());
+        let now = chrono::Utc::now().timestamp_millis();
+        db.execute(
+            "INSERT INTO tasks (id, task_number, title, status, priority, created_at, updated_at)
+             VALUES ('task-a', 'T-001', 'Wrap hood', 'pending', 'high', ?1, ?1)",
+            rusqlite::params![now],
+        )
+        .expect("seed task");
+        db.execute(
+            "INSERT INTO task_history (id, task_id, old_status, new_status, changed_at)
+             VALUES ('history-a', 'task-a', 'pending', 'in_progress', ?1)",
+            rusqlite::params![now],
+        )
+        .expect("seed task history");
+        db.execute(
+            "INSERT INTO interventions
+                 (id, task_id, vehicle_plate, status, started_at, estimated_duration,
+                  completed_at, quality_score, customer_satisfaction)
+             VALUES ('int-a', 'task-a', 'AB-123-CD', 'completed', ?1, 90, ?1, 4, 5)",
+            rusqlite::params![now],
+        )
+        .expect("seed intervention");
+
+        // What each live check sends for these operations.
+        let live = [
+            (
+                RuleTrigger::TaskCreated,
+                "task-a",
+                None,
+                payloads::task_created("Wrap hood", Some("high"), Some("pending"), None),
+            ),
+            (
+                RuleTrigger::TaskStatusChanged,
+                "history-a",
+                Some("task-a"),
+                payloads::task_status_changed(Some("pending"), "in_progress"),
+            ),
+            (
+                RuleTrigger::InterventionStarted,
+                "int-a",
+                Some("task-a"),
+                payloads::intervention_started("task-a", Some(90), Some("high")),
+            ),
+            (
+                RuleTrigger::InterventionFinalized,
+                "int-a",
+                Some("int-a"),
+                payloads::intervention_finalized("int-a", Some(4), Some(5)),
+            ),
+        ];
+        for (trigger, source_id, entity_id, payload) in live {
+            let samples = repo
+                .list_history(&trigger, now - 1, 10)
+                .await
+                .expect("list history");
+            assert_eq!(samples.len(), 1, "{:?}", trigger);
+            assert_eq!(samples[0].source_id, source_id);
+            assert_eq!(samples[0].entity_id.as_deref(), entity_id, "{:?}", trigger);
+            assert_eq!(samples[0].payload, payload, "{:?}", trigger);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_blocking_mode_is_rejected_for_event_only_triggers() {
+        let service = service().await;
+        let blocking = |trigger: RuleTrigger| CreateRuleDefinitionRequest {
+            name: "Block new clients".to_string(),
+            description: None,
+            template_key: "client-policy".to_string(),
+            trigger,
+            mode: RuleMode::Blocking,
+            conditions: json!({}),
+            actions: vec![RuleAction::Block {
+                message: "Blocked".to_string(),
+            }],
+        };
+
+        for trigger in RuleTrigger::ALL {
+            let result = service.create(&ctx(), blocking(trigger.clone())).await;
+            if trigger.has_blocking_check() {
+                assert!(result.is_ok(), "{:?}", trigger);
+            } else {
+                assert!(
+                    matches!(result, Err(AppError::Validation(_))),
+                    "{:?}",
+                    trigger
+                );
+            }
+        }
+
+        let reactive = service
+            .create(
+                &ctx(),
+                CreateRuleDefinitionRequest {
+                    mode: RuleMode::Reactive,
+                    actions: vec![RuleAction::CreateNotification {
+                        user_id: None,
+                        title: "Nouveau client".to_string(),
+                        message: "Un client a été créé".to_string(),
+                    }],
+                    ..blocking(RuleTrigger::ClientCreated)
+                },
+            )
+            .await
+            .expect("reactive rule on an event-only trigger");
+        let switched = service
+            .update(
+                &ctx(),
+                &reactive.id,
+                UpdateRuleDefinitionRequest {
+                    mode: Some(RuleMode::Blocking),
+                    actions: Some(vec![RuleAction::Block {
+                        message: "Blocked".to_string(),
+                    }]),
+                    ..Default::default()
+                },
+            )
+            .await;
+        assert!(matches!(switched, Err(AppError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn test_evaluate_blocking_completes_inside_the_runtime() {
+        use crate::shared::contracts::rules_engine::{evaluate_blocking, RuleCheckRequest};
+
+        let service = service().await;
+        let created = service
+            .create(
+                &ctx(),
+                CreateRuleDefinitionRequest {
+                    name: "No quotes over 5000".to_string(),
+                    description: None,
+                    template_key: "quote-policy".to_string(),
+                    trigger: RuleTrigger::QuoteSent,
+                    mode: RuleMode::Blocking,
+                    conditions: json!("total > 500000"),
+                    actions: vec![RuleAction::Block {
+                        message: "Quote needs approval".to_string(),
+                    }],
+                },
+            )
+            .await
+            .expect("create rule");
+        service
+            .activate(&ctx(), &created.id)
+            .await
+            .expect("activate rule");
+
+        // Same call the synchronous quote service makes from a worker thread.
+        let outcome = evaluate_blocking(
+            &service,
+            &RuleCheckRequest {
+                trigger: RuleTrigger::QuoteSent.as_str().to_string(),
+                entity_id: Some("quote-1".to_string()),
+                payload: json!({ "quote_id": "quote-1", "total": 750000 }),
+                user_id: "user-1".to_string(),
+                correlation_id: "corr-quote".to_string(),
+            },
+        )
+        .expect("evaluate without yielding");
+
+        assert!(!outcome.allowed);
+        assert_eq!(outcome.message.as_deref(), Some("Quote needs approval"));
+        let runs = service
+            .list_executions(&ctx(), &created.id, None)
+            .await
+            .expect("list runs");
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].correlation_id, "corr-quote");
+    }
+
+    #[tokio::test]
+    async fn test_evaluate_blocking_fails_when_the_engine_yields() {
+        use crate::shared::contracts::rules_engine::{
+            evaluate_blocking, BlockingRuleEngine, RuleCheckOutcome, RuleCheckRequest,
+        };
+
+        struct YieldingEngine;
+
+        #[async_trait]
+        impl BlockingRuleEngine for YieldingEngine {
+            async fn evaluate(
+                &self,
+                _request: &RuleCheckRequest,
+            ) -> Result<RuleCheckOutcome, AppError> {
+                tokio::task::yield_now().await;
+                Ok(RuleCheckOutcome {
+                    allowed: true,
+                    ..Default::default()
+                })
+            }
+        }
+
+        let result = evaluate_blocking(
+            &YieldingEngine,
+            &RuleCheckRequest {
+                trigger: RuleTrigger::MaterialConsumed.as_str().to_string(),
+                entity_id: None,
+                payload: json!({}),
+                user_id: "user-1".to_string(),
+                correlation_id: "corr-yield".to_string(),
+            },
+        );
+
+        assert!(matches!(result, Err(AppError::Internal(_))));
+    }
 }
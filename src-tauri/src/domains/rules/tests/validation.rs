@@ -47,4 +47,70 @@ mod tests {
             if message.contains("Rule name is required")
         ));
     }
+
+    #[tokio::test]
+    async fn test_create_rule_reports_condition_error_location() {
+        let db = Arc::new(
+            Database::new_in_memory()
+                .await
+                .expect("rules validation db"),
+        );
+        let service = RulesService::new(db);
+
+        let result = service
+            .create(
+                &ctx(),
+                CreateRuleDefinitionRequest {
+                    name: "Old vehicles".to_string(),
+                    description: None,
+                    template_key: "task-policy".to_string(),
+                    trigger: RuleTrigger::TaskCreated,
+                    mode: RuleMode::Blocking,
+                    conditions: json!({ "all": ["vehicle.year < 2015", "quote.total >"] }),
+                    actions: vec![RuleAction::Block {
+                        message: "blocked".to_string(),
+                    }],
+                },
+            )
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(crate::shared::error::AppError::Validation(message))
+            if message.starts_with("Invalid rule condition at conditions/all/1, column 13:")
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_create_reactive_rule_rejects_block_action() {
+        let db = Arc::new(
+            Database::new_in_memory()
+                .await
+                .expect("rules validation db"),
+        );
+        let service = RulesService::new(db);
+
+        let result = service
+            .create(
+                &ctx(),
+                CreateRuleDefinitionRequest {
+                    name: "Reactive block".to_string(),
+                    description: None,
+                    template_key: "task-policy".to_string(),
+                    trigger: RuleTrigger::TaskCreated,
+                    mode: RuleMode::Reactive,
+                    conditions: json!({}),
+                    actions: vec![RuleAction::Block {
+                        message: "blocked".to_string(),
+                    }],
+                },
+            )
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(crate::shared::error::AppError::Validation(message))
+            if message.contains("cannot block")
+        ));
+    }
 }
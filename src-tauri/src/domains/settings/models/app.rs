@@ -54,6 +54,47 @@ pub struct NotificationSettings {
     pub task_completions: bool,
     pub system_alerts: bool,
     pub daily_digest: bool,
+    #[serde(default)]
+    pub smtp_host: Option<String>,
+    #[serde(default)]
+    pub smtp_port: Option<u16>,
+    /// `starttls` (default), `tls` or `none`.
+    #[serde(default)]
+    pub smtp_security: Option<String>,
+    #[serde(default)]
+    pub smtp_username: Option<String>,
+    #[serde(default, skip_serializing)]
+    pub smtp_password: Option<String>,
+    #[serde(default)]
+    pub smtp_from_address: Option<String>,
+    #[serde(default)]
+    pub smtp_from_name: Option<String>,
+    /// `http` (REST gateway) or `file` (JSON-lines sink for offline testing).
+    #[serde(default)]
+    pub sms_transport: Option<String>,
+    #[serde(default)]
+    pub sms_gateway_url: Option<String>,
+    /// Header carrying the credential, e.g. `Authorization` or `X-Api-Key`.
+    #[serde(default)]
+    pub sms_gateway_auth_header: Option<String>,
+    /// Header value with `{{api_key}}` placeholder, e.g. `Bearer {{api_key}}`.
+    #[serde(default)]
+    pub sms_gateway_auth_template: Option<String>,
+    #[serde(default, skip_serializing)]
+    pub sms_gateway_api_key: Option<String>,
+    /// JSON request body with `{{to}}`, `{{from}}` and `{{body}}` placeholders.
+    #[serde(default)]
+    pub sms_gateway_body_template: Option<String>,
+    #[serde(default)]
+    pub sms_sender_id: Option<String>,
+    #[serde(default)]
+    pub sms_file_sink_path: Option<String>,
+    #[serde(default)]
+    pub sms_max_segments: Option<u32>,
+    /// Email / text clients ahead of their appointments, on the channels
+    /// enabled above.
+    #[serde(default)]
+    pub customer_appointment_reminders: bool,
 }
 
 impl Default for NotificationSettings {
@@ -66,6 +107,23 @@ impl Default for NotificationSettings {
             task_completions: true,
             system_alerts: true,
             daily_digest: false,
+            smtp_host: None,
+            smtp_port: None,
+            smtp_security: None,
+            smtp_username: None,
+            smtp_password: None,
+            smtp_from_address: None,
+            smtp_from_name: None,
+            sms_transport: None,
+            sms_gateway_url: None,
+            sms_gateway_auth_header: None,
+            sms_gateway_auth_template: None,
+            sms_gateway_api_key: None,
+            sms_gateway_body_template: None,
+            sms_sender_id: None,
+            sms_file_sink_path: None,
+            sms_max_segments: None,
+            customer_appointment_reminders: false,
         }
     }
 }
@@ -117,6 +175,8 @@ pub struct StorageSettings {
     pub cloud_provider: Option<String>,
     pub cloud_bucket: Option<String>,
     pub cloud_region: Option<String>,
+    #[serde(default)]
+    pub cloud_endpoint: Option<String>,
     #[serde(skip_serializing)]
     pub cloud_access_key: Option<String>,
     #[serde(skip_serializing)]
@@ -135,6 +195,7 @@ impl Default for StorageSettings {
             cloud_provider: None,
             cloud_bucket: None,
             cloud_region: None,
+            cloud_endpoint: None,
             cloud_access_key: None,
             cloud_secret_key: None,
             auto_sync_photos: false,
@@ -324,6 +324,9 @@ pub struct BusinessSettings {
     pub business_hours_start: String,
     pub business_hours_end: String,
     pub business_days: Vec<String>,
+    /// What scheduling does with a booking outside a technician's working
+    /// time or over their capacity: `warn` or `reject`.
+    pub business_capacity_policy: String,
 }
 
 impl Default for BusinessSettings {
@@ -338,6 +341,7 @@ impl Default for BusinessSettings {
                 "4".to_string(),
                 "5".to_string(),
             ],
+            business_capacity_policy: "warn".to_string(),
         }
     }
 }
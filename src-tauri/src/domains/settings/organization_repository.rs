@@ -396,6 +396,10 @@ impl OrganizationRepository {
                             "5".to_string(),
                         ]
                     }),
+                business_capacity_policy: settings_map
+                    .get("business_capacity_policy")
+                    .cloned()
+                    .unwrap_or_else(|| "warn".to_string()),
             },
         })
     }
@@ -6,6 +6,7 @@ use crate::domains::settings::models::{
 };
 /// ADR-005: Repository Pattern
 use crate::shared::ipc::errors::AppError;
+use crate::shared::services::secrets_vault::{secrets_vault, SecretsVault};
 use rusqlite::params;
 use std::sync::Arc;
 use tracing::{error, info};
@@ -37,8 +38,38 @@ impl SettingsRepository {
         })
     }
 
+    /// Seal a credential for storage with the secrets vault.
+    fn seal_credential(value: &Option<String>) -> Result<Option<String>, AppError> {
+        value
+            .as_deref()
+            .filter(|value| !value.is_empty())
+            .map(|value| secrets_vault().seal(value))
+            .transpose()
+    }
+
+    /// Open a sealed credential in place. Values written before the vault are
+    /// plain text and kept as they are. A value that no longer opens fails
+    /// the load: returning it empty would erase it on the next save.
+    fn open_credential(value: &mut Option<String>, field: &str) -> Result<(), AppError> {
+        let Some(stored) = value
+            .as_deref()
+            .filter(|stored| SecretsVault::is_sealed(stored))
+        else {
+            return Ok(());
+        };
+        let plaintext = secrets_vault().open(stored).map_err(|e| {
+            error!("Failed to decrypt app_settings credential {}: {}", field, e);
+            AppError::Internal(format!(
+                "Stored credential '{}' cannot be decrypted with the current secrets key",
+                field
+            ))
+        })?;
+        *value = Some(plaintext);
+        Ok(())
+    }
+
     /// Persists storage credentials for DB round-trips while frontend-facing
-    /// serde serialization skips those fields.
+    /// serde serialization skips those fields. The secret key is sealed.
     fn storage_settings_to_json_str(value: &StorageSettings) -> Result<String, AppError> {
         serde_json::to_string(&serde_json::json!({
             "photo_storage_type": &value.photo_storage_type,
@@ -46,8 +77,9 @@ impl SettingsRepository {
             "cloud_provider": &value.cloud_provider,
             "cloud_bucket": &value.cloud_bucket,
             "cloud_region": &value.cloud_region,
+            "cloud_endpoint": &value.cloud_endpoint,
             "cloud_access_key": &value.cloud_access_key,
-            "cloud_secret_key": &value.cloud_secret_key,
+            "cloud_secret_key": Self::seal_credential(&value.cloud_secret_key)?,
             "auto_sync_photos": value.auto_sync_photos,
             "sync_on_wifi_only": value.sync_on_wifi_only,
             "max_photo_size_mb": value.max_photo_size_mb,
@@ -59,6 +91,40 @@ impl SettingsRepository {
         })
     }
 
+    /// Same as [`Self::storage_settings_to_json_str`] for the SMTP password and
+    /// SMS gateway API key, both sealed.
+    fn notification_settings_to_json_str(value: &NotificationSettings) -> Result<String, AppError> {
+        serde_json::to_string(&serde_json::json!({
+            "push_notifications": value.push_notifications,
+            "email_notifications": value.email_notifications,
+            "sms_notifications": value.sms_notifications,
+            "task_assignments": value.task_assignments,
+            "task_completions": value.task_completions,
+            "system_alerts": value.system_alerts,
+            "daily_digest": value.daily_digest,
+            "smtp_host": &value.smtp_host,
+            "smtp_port": value.smtp_port,
+            "smtp_security": &value.smtp_security,
+            "smtp_username": &value.smtp_username,
+            "smtp_password": Self::seal_credential(&value.smtp_password)?,
+            "smtp_from_address": &value.smtp_from_address,
+            "smtp_from_name": &value.smtp_from_name,
+            "sms_transport": &value.sms_transport,
+            "sms_gateway_url": &value.sms_gateway_url,
+            "sms_gateway_auth_header": &value.sms_gateway_auth_header,
+            "sms_gateway_auth_template": &value.sms_gateway_auth_template,
+            "sms_gateway_api_key": Self::seal_credential(&value.sms_gateway_api_key)?,
+            "sms_gateway_body_template": &value.sms_gateway_body_template,
+            "sms_sender_id": &value.sms_sender_id,
+            "sms_file_sink_path": &value.sms_file_sink_path,
+            "sms_max_segments": value.sms_max_segments,
+        }))
+        .map_err(|e| {
+            error!("Failed to serialize app_settings.notifications_settings: {}", e);
+            AppError::Database("Serialization error for 'notifications_settings'".to_string())
+        })
+    }
+
     // ── read ─────────────────────────────────────────────────────────────────
 
     /// Load the global `AppSettings` from the database.
@@ -113,11 +179,17 @@ impl SettingsRepository {
                     .unwrap_or_default(),
                 security: Self::json_col_to::<SecuritySettings>(&sec, "security_settings")
                     .unwrap_or_default(),
-                notifications: Self::json_col_to::<NotificationSettings>(
-                    &notif,
-                    "notifications_settings",
-                )
-                .unwrap_or_default(),
+                notifications: {
+                    let mut notifications =
+                        Self::json_col_to::<NotificationSettings>(&notif, "notifications_settings")
+                            .unwrap_or_default();
+                    Self::open_credential(&mut notifications.smtp_password, "smtp_password")?;
+                    Self::open_credential(
+                        &mut notifications.sms_gateway_api_key,
+                        "sms_gateway_api_key",
+                    )?;
+                    notifications
+                },
                 appearance: Self::json_col_to::<AppearanceSettings>(&appear, "appearance_settings")
                     .unwrap_or_default(),
                 data_management: Self::json_col_to::<DataManagementSettings>(
@@ -125,8 +197,13 @@ impl SettingsRepository {
                     "data_management_settings",
                 )
                 .unwrap_or_default(),
-                storage: Self::json_col_to::<StorageSettings>(&stor, "storage_settings")
-                    .unwrap_or_default(),
+                storage: {
+                    let mut storage =
+                        Self::json_col_to::<StorageSettings>(&stor, "storage_settings")
+                            .unwrap_or_default();
+                    Self::open_credential(&mut storage.cloud_secret_key, "cloud_secret_key")?;
+                    storage
+                },
                 business_rules: Self::json_col_to::<Vec<serde_json::Value>>(&br, "business_rules")
                     .unwrap_or_default(),
                 security_policies: Self::json_col_to::<Vec<serde_json::Value>>(
@@ -178,7 +255,7 @@ impl SettingsRepository {
 
         let general = Self::to_json_str(&settings.general, "general_settings")?;
         let security = Self::to_json_str(&settings.security, "security_settings")?;
-        let notif = Self::to_json_str(&settings.notifications, "notifications_settings")?;
+        let notif = Self::notification_settings_to_json_str(&settings.notifications)?;
         let appear = Self::to_json_str(&settings.appearance, "appearance_settings")?;
         let dm = Self::to_json_str(&settings.data_management, "data_management_settings")?;
         let stor = Self::storage_settings_to_json_str(&settings.storage)?;
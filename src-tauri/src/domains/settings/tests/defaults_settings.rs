@@ -153,6 +153,7 @@ fn storage_settings_serialization_omits_cloud_credentials() {
         cloud_provider: Some("s3".to_string()),
         cloud_bucket: Some("bucket".to_string()),
         cloud_region: Some("eu-west-1".to_string()),
+        cloud_endpoint: None,
         cloud_access_key: Some("access-key".to_string()),
         cloud_secret_key: Some("secret-key".to_string()),
         auto_sync_photos: true,
@@ -180,6 +181,7 @@ async fn storage_settings_repository_round_trip_preserves_cloud_credentials() {
         cloud_provider: Some("s3".to_string()),
         cloud_bucket: Some("bucket".to_string()),
         cloud_region: Some("eu-west-1".to_string()),
+        cloud_endpoint: None,
         cloud_access_key: Some("access-key".to_string()),
         cloud_secret_key: Some("secret-key".to_string()),
         auto_sync_photos: true,
@@ -209,7 +211,7 @@ async fn storage_settings_repository_round_trip_preserves_cloud_credentials() {
 }
 
 #[tokio::test]
-async fn storage_settings_repository_persists_cloud_credentials_in_raw_json() {
+async fn storage_settings_repository_seals_cloud_secret_key_in_raw_json() {
     let state = build_test_app_state().await;
     let mut settings = AppSettings::default();
     settings.storage = StorageSettings {
@@ -218,6 +220,7 @@ async fn storage_settings_repository_persists_cloud_credentials_in_raw_json() {
         cloud_provider: Some("s3".to_string()),
         cloud_bucket: Some("bucket".to_string()),
         cloud_region: Some("eu-west-1".to_string()),
+        cloud_endpoint: None,
         cloud_access_key: Some("access-key".to_string()),
         cloud_secret_key: Some("secret-key".to_string()),
         auto_sync_photos: true,
@@ -255,10 +258,80 @@ async fn storage_settings_repository_persists_cloud_credentials_in_raw_json() {
             .and_then(serde_json::Value::as_str),
         Some("access-key")
     );
+    // The secret key is sealed by the secrets vault, never stored as is.
+    let sealed = raw_object
+        .get("cloud_secret_key")
+        .and_then(serde_json::Value::as_str)
+        .expect("cloud_secret_key should be stored");
+    assert!(sealed.starts_with("vault:v"));
     assert_eq!(
-        raw_object
-            .get("cloud_secret_key")
-            .and_then(serde_json::Value::as_str),
-        Some("secret-key")
+        crate::shared::services::secrets_vault::secrets_vault()
+            .open(sealed)
+            .expect("sealed key should open"),
+        "secret-key"
+    );
+}
+
+#[tokio::test]
+async fn notification_settings_keep_smtp_password_out_of_ipc_but_in_storage() {
+    let state = build_test_app_state().await;
+    let mut settings = AppSettings::default();
+    settings.notifications.smtp_host = Some("smtp.example.com".to_string());
+    settings.notifications.smtp_username = Some("atelier".to_string());
+    settings.notifications.smtp_password = Some("smtp-secret".to_string());
+
+    let value =
+        serde_json::to_value(&settings.notifications).expect("notification settings serialize");
+    assert!(!value
+        .as_object()
+        .expect("notification settings object")
+        .contains_key("smtp_password"));
+
+    state
+        .settings_repository
+        .save_app_settings_db(&settings, "test-user")
+        .expect("app settings should save");
+    let loaded = state
+        .settings_repository
+        .get_app_settings_db()
+        .expect("app settings should load");
+
+    assert_eq!(
+        loaded.notifications.smtp_password.as_deref(),
+        Some("smtp-secret")
     );
+    assert_eq!(
+        loaded.notifications.smtp_host.as_deref(),
+        Some("smtp.example.com")
+    );
+}
+
+#[tokio::test]
+async fn undecryptable_credential_fails_the_load_and_is_kept() {
+    let state = build_test_app_state().await;
+    state
+        .settings_repository
+        .save_app_settings_db(&AppSettings::default(), "test-user")
+        .expect("app settings should save");
+    // Sealed with a key this vault does not hold.
+    let stored = r#"{"photo_storage_type":"cloud","cloud_secret_key":"vault:v999:AAAA"}"#;
+    let conn = state
+        .db
+        .get_connection()
+        .expect("database connection should be available");
+    conn.execute(
+        "UPDATE app_settings SET storage_settings = ?1 WHERE id = 'global'",
+        [stored],
+    )
+    .expect("storage_settings should update");
+
+    assert!(state.settings_repository.get_app_settings_db().is_err());
+    let raw_storage: String = conn
+        .query_row(
+            "SELECT storage_settings FROM app_settings WHERE id = 'global'",
+            [],
+            |row| row.get(0),
+        )
+        .expect("storage_settings JSON should be readable");
+    assert_eq!(raw_storage, stored);
 }
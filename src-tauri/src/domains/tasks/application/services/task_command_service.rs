@@ -21,7 +21,7 @@ use crate::domains::tasks::TasksFacade;
 use crate::shared::context::RequestContext;
 use crate::shared::contracts::integration_sink::{IntegrationDispatchRequest, IntegrationEventSink};
 use crate::shared::contracts::notification::NotificationSender;
-use crate::shared::contracts::rules_engine::{BlockingRuleEngine, RuleCheckRequest};
+use crate::shared::contracts::rules_engine::{payloads, BlockingRuleEngine, RuleCheckRequest};
 use crate::shared::contracts::task_scheduler::TaskScheduler;
 use crate::shared::services::event_bus::{event_factory, EventPublisher, InMemoryEventBus};
 use crate::shared::services::validation::ValidationService;
@@ -523,10 +523,7 @@ impl TaskCommandService {
             .evaluate(&RuleCheckRequest {
                 trigger: "task_status_changed".to_string(),
                 entity_id: Some(task_id.to_string()),
-                payload: serde_json::json!({
-                    "old_status": old_status,
-                    "new_status": new_status,
-                }),
+                payload: payloads::task_status_changed(Some(&old_status), new_status),
                 user_id: ctx.auth.user_id.clone(),
                 correlation_id: ctx.correlation_id.clone(),
             })
@@ -11,7 +11,7 @@ use crate::domains::tasks::domain::models::task::{CreateTaskRequest, Task, Updat
 use crate::shared::auth_middleware::AuthMiddleware;
 use crate::shared::context::RequestContext;
 use crate::shared::contracts::integration_sink::IntegrationDispatchRequest;
-use crate::shared::contracts::rules_engine::RuleCheckRequest;
+use crate::shared::contracts::rules_engine::{payloads, RuleCheckRequest};
 use crate::shared::services::event_bus::{event_factory, EventPublisher};
 use crate::shared::services::validation::ValidationService;
 
@@ -56,12 +56,20 @@ impl TaskCommandService {
             .evaluate(&RuleCheckRequest {
                 trigger: "task_created".to_string(),
                 entity_id: None,
-                payload: serde_json::json!({
-                    "title": validated_data.title.clone(),
-                    "priority": validated_data.priority.as_ref().map(|value| value.to_string()),
-                    "status": validated_data.status.as_ref().map(|value| value.to_string()),
-                    "technician_id": validated_data.technician_id.clone(),
-                }),
+                payload: payloads::task_created(
+                    &validated_data.title,
+                    validated_data
+                        .priority
+                        .as_ref()
+                        .map(|value| value.to_string())
+                        .as_deref(),
+                    validated_data
+                        .status
+                        .as_ref()
+                        .map(|value| value.to_string())
+                        .as_deref(),
+                    validated_data.technician_id.as_deref(),
+                ),
                 user_id: ctx.auth.user_id.clone(),
                 correlation_id: ctx.correlation_id.clone(),
             })
@@ -665,6 +665,7 @@ impl TaskService {
 // -- Shared contract implementation --
 
 use crate::shared::contracts::task_assignment::{TaskAssignmentChecker, TaskAssignmentInfo};
+use crate::shared::contracts::task_tagging::TaskTagger;
 use async_trait::async_trait;
 
 #[async_trait]
@@ -693,3 +694,89 @@ impl TaskAssignmentChecker for TaskService {
         }))
     }
 }
+
+#[async_trait]
+impl TaskTagger for TaskService {
+    async fn add_task_tag(
+        &self,
+        task_id: &str,
+        tag: &str,
+    ) -> Result<bool, crate::shared::contracts::AppError> {
+        let tag = tag.trim().to_string();
+        let task_id = task_id.to_string();
+        self.crud
+            .db
+            .with_transaction(|tx| {
+                let existing: Option<String> = tx
+                    .query_row(
+                        "SELECT tags FROM tasks WHERE id = ? AND deleted_at IS NULL",
+                        rusqlite::params![task_id],
+                        |row| row.get(0),
+                    )
+                    .map_err(|e| format!("Task not found: {} ({})", task_id, e))?;
+                let Some(tags) = merge_task_tag(existing.as_deref(), &tag)
+                    .map_err(|e| format!("Task {}: {}", task_id, e))?
+                else {
+                    return Ok(false);
+                };
+                tx.execute(
+                    "UPDATE tasks SET tags = ?, updated_at = ? WHERE id = ?",
+                    rusqlite::params![tags, chrono::Utc::now().timestamp_millis(), task_id],
+                )
+                .map_err(|e| format!("Failed to update task tags: {}", e))?;
+                Ok(true)
+            })
+            .map_err(convert_to_app_error)
+    }
+}
+
+/// Add `tag` to a stored tag list, keeping its format: a JSON array when the
+/// column holds one, comma-separated otherwise. `None` when already present.
+/// A malformed JSON list is an error rather than overwritten.
+fn merge_task_tag(existing: Option<&str>, tag: &str) -> Result<Option<String>, String> {
+    let existing = existing.map(str::trim).unwrap_or_default();
+    let as_json = existing.starts_with('[');
+    let mut tags: Vec<String> = if as_json {
+        serde_json::from_str(existing)
+            .map_err(|e| format!("stored tags are not a valid JSON list ({})", e))?
+    } else {
+        existing
+            .split(',')
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+            .collect()
+    };
+    if tags.iter().any(|value| value.eq_ignore_ascii_case(tag)) {
+        return Ok(None);
+    }
+    tags.push(tag.to_string());
+    Ok(Some(if as_json {
+        serde_json::to_string(&tags).unwrap_or_default()
+    } else {
+        tags.join(",")
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::merge_task_tag;
+
+    #[test]
+    fn test_merge_task_tag_keeps_storage_format() {
+        assert_eq!(merge_task_tag(None, "vip"), Ok(Some("vip".to_string())));
+        assert_eq!(
+            merge_task_tag(Some("tag1,tag2"), "vip"),
+            Ok(Some("tag1,tag2,vip".to_string()))
+        );
+        assert_eq!(
+            merge_task_tag(Some(r#"["tag1"]"#), "vip"),
+            Ok(Some(r#"["tag1","vip"]"#.to_string()))
+        );
+        assert_eq!(merge_task_tag(Some("tag1, VIP"), "vip"), Ok(None));
+    }
+
+    #[test]
+    fn test_merge_task_tag_rejects_malformed_json() {
+        assert!(merge_task_tag(Some(r#"["tag1","#), "vip").is_err());
+    }
+}
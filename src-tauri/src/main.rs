@@ -83,6 +83,7 @@ fn main() {
             commands::system::force_wal_checkpoint,
             commands::system::export_data_backup,
             commands::system::restore_data_backup,
+            commands::system::rotate_secrets_key,
             // ── Auth ─────────────────────────────────────────────────────
             domains::auth::ipc::auth::auth_login,
             domains::auth::ipc::auth::auth_create_account,
@@ -183,6 +184,21 @@ fn main() {
             domains::calendar::calendar_handler::calendar_get_tasks,
             domains::calendar::calendar_handler::calendar_check_conflicts,
             domains::calendar::calendar_handler::calendar_schedule_task,
+            domains::calendar::calendar_handler::calendar_export_ics,
+            domains::calendar::calendar_handler::calendar_export_event_invite,
+            domains::calendar::calendar_handler::calendar_import_ics,
+            domains::calendar::calendar_handler::calendar_get_feed_path,
+            domains::calendar::calendar_handler::calendar_get_technician_availability,
+            domains::calendar::calendar_handler::calendar_set_technician_availability,
+            domains::calendar::calendar_handler::calendar_list_absences,
+            domains::calendar::calendar_handler::calendar_add_absence,
+            domains::calendar::calendar_handler::calendar_delete_absence,
+            domains::calendar::calendar_handler::calendar_list_public_holidays,
+            domains::calendar::calendar_handler::calendar_set_public_holiday,
+            domains::calendar::calendar_handler::calendar_delete_public_holiday,
+            domains::calendar::calendar_handler::calendar_get_capacity_heatmap,
+            domains::calendar::calendar_handler::calendar_suggest_slots,
+            domains::calendar::calendar_handler::calendar_propose_schedule,
             // ── Quotes ───────────────────────────────────────────────────
             domains::quotes::ipc::quote::quote_create,
             domains::quotes::ipc::quote::quote_get,
@@ -195,10 +211,15 @@ fn main() {
             domains::quotes::ipc::quote::quote_item_delete,
             domains::quotes::ipc::quote::quote_mark_sent,
             domains::quotes::ipc::quote::quote_mark_accepted,
+            domains::quotes::ipc::quote::quote_accept_with_signature,
+            domains::quotes::ipc::quote::quote_get_acceptance,
             domains::quotes::ipc::quote::quote_mark_rejected,
             domains::quotes::ipc::quote::quote_mark_expired,
             domains::quotes::ipc::quote::quote_duplicate,
             domains::quotes::ipc::quote::quote_export_pdf,
+            domains::quotes::ipc::quote::quote_export_revision_pdf,
+            domains::quotes::ipc::quote::quote_get_revisions,
+            domains::quotes::ipc::quote::quote_diff_revisions,
             domains::quotes::ipc::quote::quote_attachments_get,
             domains::quotes::ipc::quote::quote_attachment_create,
             domains::quotes::ipc::quote::quote_attachment_update,
@@ -262,6 +283,8 @@ fn main() {
             domains::notifications::notification_handler::message_get_list,
             domains::notifications::notification_handler::message_mark_read,
             domains::notifications::notification_handler::message_get_templates,
+            domains::notifications::notification_handler::message_save_template,
+            domains::notifications::notification_handler::message_preview_template,
             domains::notifications::notification_handler::message_get_preferences,
             domains::notifications::notification_handler::message_update_preferences,
             // ── Auth / Session ───────────────────────────────────────────
@@ -316,6 +339,9 @@ fn main() {
             domains::rules::ipc::disable_rule,
             domains::rules::ipc::delete_rule,
             domains::rules::ipc::test_rule,
+            domains::rules::ipc::backtest_rule,
+            domains::rules::ipc::list_rule_executions,
+            domains::rules::ipc::list_rule_trigger_schemas,
             // ── Integrations ──────────────────────────────────────────────────────────────
             domains::integrations::ipc::list_integrations,
             domains::integrations::ipc::get_integrations,
@@ -324,6 +350,35 @@ fn main() {
             domains::integrations::ipc::test_integration,
             domains::integrations::ipc::delete_integration,
             domains::integrations::ipc::retry_dead_letter_integrations,
+            domains::integrations::ipc::reveal_integration_signing_secret,
+            domains::integrations::ipc::rotate_integration_signing_secret,
+            domains::integrations::ipc::list_integration_deliveries,
+            domains::integrations::ipc::list_integration_delivery_attempts,
+            domains::integrations::ipc::replay_integration_delivery,
+            domains::integrations::ipc::preview_integration_payload,
+            domains::integrations::ipc::list_integration_inbound_requests,
+            // ── Invoices ──────────────────────────────────────────────────────────────────
+            domains::invoices::ipc::invoice_list,
+            domains::invoices::ipc::invoice_get,
+            domains::invoices::ipc::invoice_create,
+            domains::invoices::ipc::invoice_create_from_quote,
+            domains::invoices::ipc::invoice_create_from_intervention,
+            domains::invoices::ipc::invoice_update_draft,
+            domains::invoices::ipc::invoice_delete_draft,
+            domains::invoices::ipc::invoice_issue,
+            domains::invoices::ipc::invoice_create_credit_note,
+            domains::invoices::ipc::invoice_balance,
+            domains::invoices::ipc::invoice_export_pdf,
+            domains::invoices::ipc::invoice_einvoice_check,
+            domains::invoices::ipc::invoice_export_einvoice,
+            domains::invoices::ipc::quote_export_einvoice,
+            // ── Payments ──────────────────────────────────────────────────────────────────
+            domains::invoices::ipc::payments::payment_list,
+            domains::invoices::ipc::payments::payment_record_deposit,
+            domains::invoices::ipc::payments::payment_record_settlement,
+            domains::invoices::ipc::payments::payment_delete,
+            domains::invoices::ipc::payments::payment_quote_balance,
+            domains::invoices::ipc::payments::payment_aged_receivables,
         ])
         .on_window_event(|window, event| {
             if let tauri::WindowEvent::CloseRequested { .. } = event {
@@ -401,14 +456,33 @@ fn main() {
 
             // Apply staged restore if one was prepared by a previous restore_data_backup call.
             // Must happen before the connection pool is opened so the file is not locked.
+            // The backup's keyring goes with it, before the vault is loaded below.
             let restore_path = app_dir.join("rpma.restore.db");
+            let restore_keyring_path =
+                app_dir.join(shared::services::secrets_vault::RESTORE_KEYRING_FILE_NAME);
             if restore_path.exists() {
                 info!("Staged restore file detected — applying before opening database pool");
+                if restore_keyring_path.exists() {
+                    std::fs::rename(
+                        &restore_keyring_path,
+                        app_dir.join(shared::services::secrets_vault::KEYRING_FILE_NAME),
+                    )
+                    .map_err(|e| format!("Failed to apply staged secrets keyring: {e}"))?;
+                }
                 std::fs::rename(&restore_path, &db_path)
                     .map_err(|e| format!("Failed to apply staged restore: {e}"))?;
                 info!("Staged restore applied successfully: {:?}", db_path);
             }
 
+            // The secrets vault must be ready before migrations: migration 080
+            // seals stored credentials with it.
+            let secrets_vault = shared::services::secrets_vault::SecretsVault::load_or_create(
+                app_dir.join(shared::services::secrets_vault::KEYRING_FILE_NAME),
+            )
+            .map_err(|e| format!("Failed to open secrets keyring: {e}"))?;
+            shared::services::secrets_vault::set_secrets_vault(Arc::new(secrets_vault));
+            info!("Secrets vault ready");
+
             // Initialize database
             let encryption_key = std::env::var("RPMA_DB_KEY").unwrap_or_else(|_| "".to_string());
             let db_instance = db::Database::new(&db_path, &encryption_key)
@@ -510,6 +584,108 @@ fn main() {
                 }
             });
 
+            let calendar_feed = app
+                .state::<shared::app_state::AppStateType>()
+                .calendar_feed
+                .clone();
+            async_runtime::spawn(calendar_feed.run());
+
+            let reminder_scheduler = app
+                .state::<shared::app_state::AppStateType>()
+                .reminder_scheduler
+                .clone();
+            async_runtime::spawn(async move {
+                let mut interval = time::interval(Duration::from_secs(60));
+                loop {
+                    interval.tick().await;
+                    if let Err(error) = reminder_scheduler.run_due().await {
+                        warn!("Calendar reminder worker failed: {}", error);
+                    }
+                }
+            });
+
+            if let Some(retention) = shared::db::event_store::SqliteEventStore::retention() {
+                let event_store = app
+                    .state::<shared::app_state::AppStateType>()
+                    .event_store
+                    .clone();
+                async_runtime::spawn(async move {
+                    let mut interval = time::interval(Duration::from_secs(6 * 60 * 60));
+                    loop {
+                        interval.tick().await;
+                        match event_store.prune_before(chrono::Utc::now() - retention) {
+                            Ok(0) => {}
+                            Ok(pruned) => info!("Pruned {} stored domain events", pruned),
+                            Err(error) => warn!("Domain event retention failed: {}", error),
+                        }
+                    }
+                });
+            }
+
+            if let Ok(address) = std::env::var(
+                domains::integrations::infrastructure::inbound_listener::INBOUND_ADDR_ENV,
+            ) {
+                let state = app.state::<shared::app_state::AppStateType>();
+                let inbound = Arc::new(
+                    domains::integrations::application::services::inbound_webhook_service::InboundWebhookService::new(
+                        state.integrations_service.clone(),
+                        Arc::new(domains::integrations::ipc::inbound_commands::AppInboundCommands::new(
+                            app.handle().clone(),
+                        )),
+                        state.audit_service.clone(),
+                    ),
+                );
+                async_runtime::spawn(async move {
+                    match tokio::net::TcpListener::bind(&address).await {
+                        Ok(listener) => {
+                            info!("Inbound webhook listener started on {}", address);
+                            domains::integrations::infrastructure::inbound_listener::serve(
+                                listener,
+                                move |request| {
+                                    let inbound = inbound.clone();
+                                    async move { inbound.handle(request).await }
+                                },
+                            )
+                            .await;
+                        }
+                        Err(error) => {
+                            warn!("Inbound webhook listener failed to bind {}: {}", address, error)
+                        }
+                    }
+                });
+            }
+
+            let message_dispatcher = app
+                .state::<shared::app_state::AppStateType>()
+                .message_dispatcher
+                .clone();
+            async_runtime::spawn(async move {
+                let mut interval = time::interval(Duration::from_secs(60));
+                loop {
+                    interval.tick().await;
+                    if let Err(error) = message_dispatcher
+                        .dispatch_pending(domains::notifications::DEFAULT_MAX_DELIVERY_ATTEMPTS)
+                        .await
+                    {
+                        warn!("Message delivery worker failed: {}", error);
+                    }
+                }
+            });
+
+            let photo_service = app
+                .state::<shared::app_state::AppStateType>()
+                .photo_service
+                .clone();
+            async_runtime::spawn(async move {
+                let mut interval = time::interval(Duration::from_secs(120));
+                loop {
+                    interval.tick().await;
+                    if let Err(error) = photo_service.sync_pending_uploads(10, 5).await {
+                        warn!("Photo upload sync worker failed: {}", error);
+                    }
+                }
+            });
+
             #[cfg(not(debug_assertions))]
             {
                 let resource_dir = app
@@ -33,11 +33,12 @@
 //! 11. CacheService                   <- self-contained
 //! 12. SessionService                 <- Database
 //! 13. SessionStore                   <- self-contained
-//! 14. PhotoService                   <- Database + AppDataDir-derived storage settings
+//! 14. PhotoService                   <- Database + AppDataDir + persisted StorageSettings
 //! 15. MaterialService                <- Database
 //! 16. InventoryFacade                <- Database + MaterialService
 //! 17. QuoteService                   <- Repositories.quote + Database + QuoteEventBus
 //! 18. MessageService                 <- Repositories.message + Database
+//! 18b. MessageDispatcher             <- Repositories.message + Database + SettingsRepository
 //! 19. AsyncDatabase                  <- Database
 //! 20. AuditService                   <- Database (plus init)
 //! 21. AuditLogHandler                <- AuditService + EventBus registration
@@ -47,6 +48,8 @@
 // TODO(scaffold): ("RulesService", &["Database"]),   // ← wire real deps
 // TODO(scaffold): ("IntegrationsService", &["Database"]),   // ← wire real deps
 //! 24. QuoteConvertedHandler          <- InterventionWorkflowService + global EventBus registration
+//! 25. InvoicesService                <- Database + SettingsService
+//! 26. PaymentsService                <- Database + InvoicesService
 //!
 //! The graph is intentionally acyclic: every edge points from a root resource or an
 //! earlier-initialized service to a later-initialized one. If a future change needs a
@@ -58,11 +61,12 @@ use crate::db::Database;
 use crate::domains::users::infrastructure::user::UserService;
 use crate::infrastructure::auth::session_store::SessionStore;
 use crate::shared::app_state::{AppConfig, AppStateType};
+use crate::shared::db::event_store::SqliteEventStore;
 use crate::shared::event_bus::{register_handler, set_global_event_bus};
 use crate::shared::logging::audit_log_handler::AuditLogHandler;
 use crate::shared::logging::audit_service::AuditService;
 use crate::shared::repositories::Repositories;
-use crate::shared::services::domain_event::DomainEvent;
+use crate::shared::services::domain_event::{DomainEvent, EventStore};
 use crate::shared::services::event_bus::InMemoryEventBus;
 #[cfg(test)]
 use std::collections::{HashMap, HashSet};
@@ -72,6 +76,7 @@ use std::sync::Arc;
 const DOCUMENTED_SERVICE_INIT_ORDER: &[&str] = &[
     "TaskService",
     "QuoteEventBus",
+    "EventStore",
     "EventBus",
     "ClientService",
     "InterventionStepService",
@@ -92,6 +97,7 @@ const DOCUMENTED_SERVICE_INIT_ORDER: &[&str] = &[
     "InventoryFacade",
     "QuoteService",
     "MessageService",
+    "MessageDispatcher",
     "AsyncDatabase",
     "AuditService",
     "AuditLogHandler",
@@ -102,13 +108,16 @@ const DOCUMENTED_SERVICE_INIT_ORDER: &[&str] = &[
     "QuoteConvertedHandler",
     "TrashService",
     "GlobalSearchService",
+    "InvoicesService",
+    "PaymentsService",
 ];
 
 #[cfg(test)]
 const DOCUMENTED_SERVICE_DEPENDENCIES: &[(&str, &[&str])] = &[
     ("TaskService", &["Database"]),
     ("QuoteEventBus", &[]),
-    ("EventBus", &[]),
+    ("EventStore", &["Database"]),
+    ("EventBus", &["EventStore"]),
     ("ClientService", &["Repositories.client", "EventBus"]),
     ("InterventionStepService", &["Database"]),
     ("PhotoValidationService", &["Database"]),
@@ -139,7 +148,10 @@ const DOCUMENTED_SERVICE_DEPENDENCIES: &[(&str, &[&str])] = &[
     ("CacheService", &[]),
     ("SessionService", &["Database"]),
     ("SessionStore", &[]),
-    ("PhotoService", &["Database", "AppDataDir"]),
+    (
+        "PhotoService",
+        &["Database", "AppDataDir", "SettingsRepository"],
+    ),
     ("MaterialService", &["Database"]),
     ("InventoryFacade", &["Database", "MaterialService"]),
     (
@@ -147,6 +159,10 @@ const DOCUMENTED_SERVICE_DEPENDENCIES: &[(&str, &[&str])] = &[
         &["Repositories.quote", "Database", "QuoteEventBus"],
     ),
     ("MessageService", &["Repositories.message", "Database"]),
+    (
+        "MessageDispatcher",
+        &["Repositories.message", "Database", "SettingsRepository"],
+    ),
     ("AsyncDatabase", &["Database"]),
     ("AuditService", &["Database"]),
     ("AuditLogHandler", &["AuditService", "EventBus"]),
@@ -164,6 +180,8 @@ const DOCUMENTED_SERVICE_DEPENDENCIES: &[(&str, &[&str])] = &[
     ),
     ("TrashService", &["Database"]),
     ("GlobalSearchService", &["Repositories"]),
+    ("InvoicesService", &["Database", "SettingsService"]),
+    ("PaymentsService", &["Database", "InvoicesService"]),
 ];
 
 /// Service Builder
@@ -231,12 +249,13 @@ impl ServiceBuilder {
             crate::domains::tasks::infrastructure::task::TaskService::new(self.db.clone()),
         );
 
-        // Quote service currently depends on the event_bus implementation.
-        let quote_event_bus = Arc::new(crate::shared::services::event_bus::InMemoryEventBus::new());
-
         // Initialize Event Bus early (self-contained, thread-safe) so it can be
         // injected into services that publish domain events at startup.
-        let event_bus = Arc::new(InMemoryEventBus::new());
+        // Every dispatched event is also appended to the durable event store.
+        let event_store = Arc::new(SqliteEventStore::new(self.db.clone()));
+        let event_bus = Arc::new(
+            InMemoryEventBus::new().with_store(event_store.clone() as Arc<dyn EventStore>),
+        );
         set_global_event_bus(event_bus.clone());
 
         let client_service = Arc::new(
@@ -312,6 +331,18 @@ impl ServiceBuilder {
                 self.db.clone(),
             ),
         );
+        let invoices_service = Arc::new(
+            crate::domains::invoices::application::services::invoices_service::InvoicesService::new(
+                self.db.clone(),
+                settings_service.clone(),
+            ),
+        );
+        let payments_service = Arc::new(
+            crate::domains::invoices::application::services::payments_service::PaymentsService::new(
+                self.db.clone(),
+                invoices_service.clone(),
+            ),
+        );
         let task_import_service = Arc::new(
             crate::domains::tasks::infrastructure::task_import::TaskImportService::new(
                 self.db.clone(),
@@ -320,6 +351,19 @@ impl ServiceBuilder {
         let calendar_service = Arc::new(
             crate::domains::calendar::calendar_handler::CalendarService::new(self.db.clone()),
         );
+        let calendar_event_repository = Arc::new(
+            crate::domains::calendar::calendar_handler::CalendarEventRepository::new(
+                self.db.clone(),
+            ),
+        );
+        let calendar_feed = Arc::new(
+            crate::domains::calendar::calendar_handler::CalendarFeed::new(
+                calendar_event_repository.clone()
+                    as Arc<dyn crate::shared::repositories::CalendarEventRepositoryContract>,
+                calendar_service.clone(),
+                &self.app_data_dir,
+            ),
+        );
 
         // Initialize Auth Service (needs initialization)
         let auth_service =
@@ -352,28 +396,58 @@ impl ServiceBuilder {
         // REMOVED — 2FA has been replaced by plain UUID sessions
 
         // Initialize Photo Service (depends on DB and StorageSettings).
-        // Force local photo storage under the app data directory to avoid writing
+        // Local files always live under the app data directory to avoid writing
         // uploads into the repo root during `tauri dev` (which can trigger backend rebuilds).
-        let mut default_storage_settings =
-            crate::domains::documents::PhotoStorageSettings::default();
+        // Cloud/hybrid providers come from the persisted storage settings.
+        let stored_storage_settings = settings_repository
+            .get_app_settings_db()
+            .map(|settings| settings.storage)
+            .unwrap_or_default();
         let photo_storage_path = self.app_data_dir.join("photos");
-        default_storage_settings.photo_storage_type = "local".to_string();
-        default_storage_settings.local_storage_path =
-            Some(photo_storage_path.to_string_lossy().to_string());
+        let photo_storage_settings = crate::domains::documents::PhotoStorageSettings {
+            photo_storage_type: stored_storage_settings.photo_storage_type,
+            local_storage_path: Some(photo_storage_path.to_string_lossy().to_string()),
+            cloud_provider: stored_storage_settings.cloud_provider,
+            cloud_bucket: stored_storage_settings.cloud_bucket,
+            cloud_region: stored_storage_settings.cloud_region,
+            cloud_endpoint: stored_storage_settings.cloud_endpoint,
+            cloud_access_key: stored_storage_settings.cloud_access_key,
+            cloud_secret_key: stored_storage_settings.cloud_secret_key,
+        };
         tracing::info!(
-            photo_storage_provider = %default_storage_settings.photo_storage_type,
+            photo_storage_provider = %photo_storage_settings.photo_storage_type,
             photo_storage_path = %photo_storage_path.display(),
             "Configured photo storage path"
         );
-        let photo_service = Arc::new(crate::domains::documents::PhotoService::new(
+        let photo_service = match crate::domains::documents::PhotoService::new(
             db_instance.clone(),
-            &default_storage_settings,
-        )?);
+            &photo_storage_settings,
+        ) {
+            Ok(service) => service,
+            Err(e) => {
+                tracing::warn!(
+                    "Photo cloud storage misconfigured, falling back to local storage: {}",
+                    e
+                );
+                crate::domains::documents::PhotoService::new(
+                    db_instance.clone(),
+                    &crate::domains::documents::PhotoStorageSettings {
+                        local_storage_path: photo_storage_settings.local_storage_path.clone(),
+                        ..Default::default()
+                    },
+                )?
+            }
+        };
+        let photo_service = Arc::new(photo_service);
 
-        // Initialize Material Service (depends on DB)
+        // Initialize Material Service (depends on DB; consumption is checked against blocking rules)
         let material_service = Arc::new(
             crate::domains::inventory::infrastructure::material::MaterialService::new(
                 db_instance.clone(),
+            )
+            .with_rules_engine(
+                rules_service.clone()
+                    as Arc<dyn crate::shared::contracts::rules_engine::BlockingRuleEngine>,
             ),
         );
 
@@ -391,13 +465,26 @@ impl ServiceBuilder {
             event_bus.clone(),
         ));
 
-        // Initialize Quote Service (depends on QuoteRepository)
+        // Initialize Message Dispatcher (delivers queued email/SMS rows; transport settings are read per pass)
+        let message_dispatcher = Arc::new(crate::domains::notifications::MessageDispatcher::new(
+            self.repositories.message.clone(),
+            self.db.clone(),
+            self.repositories.cache.clone(),
+        ));
+
+        // Initialize Quote Service (depends on QuoteRepository; status transitions are
+        // checked against blocking rules). Quote events go to the shared bus so that
+        // registered handlers, reactive rules included, receive them.
         let quote_service = Arc::new(
             crate::domains::quotes::application::quote_service::QuoteService::new(
                 self.repositories.quote.clone()
                     as Arc<dyn crate::domains::quotes::domain::models::quote::IQuoteRepository>,
-                quote_event_bus,
+                event_bus.clone(),
                 message_service.clone(),
+            )
+            .with_rules_engine(
+                rules_service.clone()
+                    as Arc<dyn crate::shared::contracts::rules_engine::BlockingRuleEngine>,
             ),
         );
 
@@ -421,6 +508,17 @@ impl ServiceBuilder {
                 message_service.clone(),
                 event_bus.clone(),
             ));
+        let reminder_scheduler = Arc::new(
+            crate::domains::calendar::calendar_handler::ReminderScheduler::new(
+                calendar_event_repository.clone()
+                    as Arc<dyn crate::shared::repositories::CalendarEventRepositoryContract>,
+                calendar_service.clone(),
+                notification_facade.clone(),
+                message_service.clone()
+                    as Arc<dyn crate::shared::contracts::notification::NotificationSender>,
+                self.db.clone(),
+            ),
+        );
         let notification_event_handler =
             crate::domains::notifications::NotificationEventHandler::new(notification_facade);
         register_handler(Arc::new(notification_event_handler));
@@ -436,6 +534,28 @@ impl ServiceBuilder {
         );
         register_handler(Arc::new(quote_converted_handler));
 
+        // Register Reactive Rules Handler (runs active reactive rules on domain events)
+        let rules_event_handler =
+            crate::domains::rules::application::event_handler::RulesEventHandler::new(
+                rules_service.clone(),
+                crate::domains::rules::application::services::reactive_rules::RuleActionPorts {
+                    integration_sink: integrations_service.clone()
+                        as Arc<dyn crate::shared::contracts::integration_sink::IntegrationEventSink>,
+                    notification_sender: message_service.clone()
+                        as Arc<dyn crate::shared::contracts::notification::NotificationSender>,
+                    task_tagger: task_service.clone()
+                        as Arc<dyn crate::shared::contracts::task_tagging::TaskTagger>,
+                },
+            );
+        register_handler(Arc::new(rules_event_handler));
+
+        // Keep the local ICS feed current when tasks are (re)scheduled
+        register_handler(Arc::new(
+            crate::domains::calendar::calendar_handler::CalendarFeedHandler::new(
+                calendar_feed.clone(),
+            ),
+        ));
+
         // Register Tauri event emitter when an AppHandle is available (production only).
         // Excluded from test builds to avoid linking WebView2 native DLLs.
         #[cfg(not(test))]
@@ -472,17 +592,16 @@ impl ServiceBuilder {
             client_service,
             task_import_service,
             calendar_service: calendar_service.clone(),
-            calendar_event_repository: Arc::new(
-                crate::domains::calendar::calendar_handler::CalendarEventRepository::new(
-                    self.db.clone(),
-                ),
-            ),
+            calendar_event_repository,
+            calendar_feed,
+            reminder_scheduler,
             intervention_service,
             intervention_creator: intervention_workflow_service
                 as Arc<dyn crate::domains::interventions::application::InterventionCreator>,
             material_service,
             inventory_service,
             message_service,
+            message_dispatcher,
             photo_service,
             quote_service,
             auth_service,
@@ -493,9 +612,12 @@ impl ServiceBuilder {
             settings_service,
             rules_service,
             integrations_service,
+            invoices_service,
+            payments_service,
             user_service,
             cache_service,
             event_bus,
+            event_store,
             app_config: Arc::new(AppConfig {
                 app_data_dir: self.app_data_dir,
             }),
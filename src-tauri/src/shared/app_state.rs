@@ -4,6 +4,8 @@ use crate::db::Database;
 use crate::domains::clients::application::client_service::ClientService;
 use crate::domains::integrations::application::services::integrations_service::IntegrationsService;
 use crate::domains::inventory::InventoryFacade;
+use crate::domains::invoices::application::services::invoices_service::InvoicesService;
+use crate::domains::invoices::application::services::payments_service::PaymentsService;
 use crate::domains::rules::application::services::rules_service::RulesService;
 use crate::domains::settings::application::settings_service::SettingsService;
 use crate::domains::tasks::infrastructure::task::TaskService;
@@ -35,6 +37,10 @@ pub struct AppStateType {
     pub calendar_service: Arc<crate::domains::calendar::calendar_handler::CalendarService>,
     pub calendar_event_repository:
         Arc<crate::domains::calendar::calendar_handler::CalendarEventRepository>,
+    /// Read-only `.ics` feed regenerated after calendar changes.
+    pub calendar_feed: Arc<crate::domains::calendar::calendar_handler::CalendarFeed>,
+    /// Fires event and task reminders; run every minute.
+    pub reminder_scheduler: Arc<crate::domains::calendar::calendar_handler::ReminderScheduler>,
     pub intervention_service:
         Arc<crate::domains::interventions::infrastructure::intervention::InterventionService>,
     /// ADR-016: Exposed for saga-style orchestration at the IPC layer
@@ -45,6 +51,7 @@ pub struct AppStateType {
     pub material_service: Arc<crate::domains::inventory::infrastructure::material::MaterialService>,
     pub inventory_service: Arc<InventoryFacade>,
     pub message_service: Arc<crate::domains::notifications::MessageService>,
+    pub message_dispatcher: Arc<crate::domains::notifications::MessageDispatcher>,
     pub photo_service: Arc<crate::domains::documents::PhotoService>,
     pub quote_service: Arc<crate::domains::quotes::application::quote_service::QuoteService>,
     pub auth_service: Arc<crate::domains::auth::infrastructure::auth::AuthService>,
@@ -55,9 +62,12 @@ pub struct AppStateType {
     pub settings_service: Arc<SettingsService>,
     pub rules_service: Arc<RulesService>,
     pub integrations_service: Arc<IntegrationsService>,
+    pub invoices_service: Arc<InvoicesService>,
+    pub payments_service: Arc<PaymentsService>,
     pub user_service: Arc<UserService>,
     pub cache_service: Arc<crate::shared::services::cache::CacheService>,
     pub event_bus: Arc<crate::shared::services::event_bus::InMemoryEventBus>,
+    pub event_store: Arc<crate::shared::db::event_store::SqliteEventStore>,
     pub app_config: Arc<AppConfig>,
     pub trash_service:
         Arc<crate::domains::trash::application::services::trash_service::TrashService>,
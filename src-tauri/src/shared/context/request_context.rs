@@ -47,6 +47,29 @@ impl RequestContext {
         }
     }
 
+    /// Create the context an inbound integration acts under.
+    ///
+    /// The actor is `integration:{id}`, so audit entries and `created_by`
+    /// columns name the integration rather than a user. The `Supervisor` role
+    /// lets it create tasks and clients and record quote responses, and goes
+    /// through the same permission checks as a signed-in supervisor.
+    pub fn for_integration(
+        integration_id: &str,
+        integration_name: &str,
+        correlation_id: String,
+    ) -> Self {
+        Self {
+            auth: AuthContext {
+                user_id: format!("integration:{}", integration_id),
+                role: UserRole::Supervisor,
+                session_id: String::new(),
+                username: integration_name.to_string(),
+                email: String::new(),
+            },
+            correlation_id,
+        }
+    }
+
     // ── convenience accessors ────────────────────────────────────────
 
     /// Shorthand for `self.auth.user_id`.
@@ -0,0 +1,24 @@
+//! Shared contract for the deposit gate on quote→task conversion.
+//!
+//! Lets the quotes domain refuse to schedule work on an unpaid quote without
+//! depending on the payments ledger in the invoices domain.
+
+use async_trait::async_trait;
+
+use crate::shared::context::RequestContext;
+use crate::shared::contracts::AppError;
+
+/// Port for checking that a quote's required deposit has been collected.
+#[async_trait]
+pub trait DepositGate: Send + Sync {
+    /// Fail with a validation error while the deposit required on the quote
+    /// (`total` in cents) is not fully paid. A no-op when no deposit is
+    /// configured.
+    async fn check_deposit_paid(
+        &self,
+        ctx: &RequestContext,
+        quote_id: &str,
+        quote_number: &str,
+        total: i64,
+    ) -> Result<(), AppError>;
+}
@@ -0,0 +1,43 @@
+//! Shared contract for commands arriving through inbound integrations.
+//!
+//! The integrations domain authenticates and logs inbound webhook requests;
+//! the work itself belongs to other domains, reached through this trait so
+//! that it runs the same validation and blocking rules as the IPC commands.
+
+use async_trait::async_trait;
+
+use crate::shared::context::RequestContext;
+use crate::shared::error::AppError;
+
+/// What an inbound command did, returned to the sender as the response body.
+#[derive(Debug, Clone)]
+pub struct InboundCommandOutcome {
+    pub resource_type: String,
+    pub resource_id: String,
+    /// `true` when a new resource was created (answered with 201).
+    pub created: bool,
+    pub body: serde_json::Value,
+}
+
+#[async_trait]
+pub trait InboundCommandHandler: Send + Sync {
+    async fn create_task(
+        &self,
+        ctx: &RequestContext,
+        payload: serde_json::Value,
+    ) -> Result<InboundCommandOutcome, AppError>;
+
+    /// Create a client, or update the one with the same email.
+    async fn upsert_client(
+        &self,
+        ctx: &RequestContext,
+        payload: serde_json::Value,
+    ) -> Result<InboundCommandOutcome, AppError>;
+
+    async fn respond_to_quote(
+        &self,
+        ctx: &RequestContext,
+        quote_id: &str,
+        payload: serde_json::Value,
+    ) -> Result<InboundCommandOutcome, AppError>;
+}
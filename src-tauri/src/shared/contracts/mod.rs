@@ -1,7 +1,9 @@
 pub mod auth;
 pub mod client_ops;
 pub mod common;
+pub mod deposit_gate;
 pub mod events;
+pub mod inbound_commands;
 pub mod integration_sink;
 pub mod intervention_enums;
 pub mod location;
@@ -15,6 +17,7 @@ pub mod sync;
 pub mod task_assignment;
 pub mod task_scheduler;
 pub mod task_status;
+pub mod task_tagging;
 pub mod timestamp;
 pub mod user_account;
 
@@ -1,4 +1,5 @@
 use async_trait::async_trait;
+use futures::FutureExt;
 use serde::{Deserialize, Serialize};
 
 use crate::shared::error::AppError;
@@ -23,3 +24,80 @@ pub struct RuleCheckOutcome {
 pub trait BlockingRuleEngine: Send + Sync {
     async fn evaluate(&self, request: &RuleCheckRequest) -> Result<RuleCheckOutcome, AppError>;
 }
+
+/// Evaluate `request` from synchronous service code.
+///
+/// The quote and material services are synchronous but run on tokio worker
+/// threads, where parking on a future the runtime must drive would deadlock.
+/// The future is therefore polled exactly once: `RulesService` only makes
+/// blocking SQLite calls and never yields, so it is always ready. An engine
+/// that does yield fails the check with an error instead of hanging.
+pub fn evaluate_blocking(
+    engine: &dyn BlockingRuleEngine,
+    request: &RuleCheckRequest,
+) -> Result<RuleCheckOutcome, AppError> {
+    engine.evaluate(request).now_or_never().unwrap_or_else(|| {
+        Err(AppError::Internal(format!(
+            "Rule engine yielded while checking {} from synchronous code",
+            request.trigger
+        )))
+    })
+}
+
+/// Payloads the blocking checks send, one function per trigger.
+///
+/// The rules backtest rebuilds recorded operations with the same functions,
+/// so conditions match history exactly as they would match the live check.
+pub mod payloads {
+    use serde_json::{json, Value};
+
+    /// `task_created`, checked before the task exists: no entity id.
+    pub fn task_created(
+        title: &str,
+        priority: Option<&str>,
+        status: Option<&str>,
+        technician_id: Option<&str>,
+    ) -> Value {
+        json!({
+            "title": title,
+            "priority": priority,
+            "status": status,
+            "technician_id": technician_id,
+        })
+    }
+
+    /// `task_status_changed`; the entity id is the task.
+    pub fn task_status_changed(old_status: Option<&str>, new_status: &str) -> Value {
+        json!({
+            "old_status": old_status,
+            "new_status": new_status,
+        })
+    }
+
+    /// `intervention_started`, checked before the intervention exists: the
+    /// entity id is the task.
+    pub fn intervention_started(
+        task_id: &str,
+        estimated_duration_minutes: Option<i64>,
+        priority: Option<&str>,
+    ) -> Value {
+        json!({
+            "task_id": task_id,
+            "estimated_duration_minutes": estimated_duration_minutes,
+            "priority": priority,
+        })
+    }
+
+    /// `intervention_finalized`; the entity id is the intervention.
+    pub fn intervention_finalized(
+        intervention_id: &str,
+        quality_score: Option<i32>,
+        customer_satisfaction: Option<i32>,
+    ) -> Value {
+        json!({
+            "intervention_id": intervention_id,
+            "quality_score": quality_score,
+            "customer_satisfaction": customer_satisfaction,
+        })
+    }
+}
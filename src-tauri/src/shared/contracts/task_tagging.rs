@@ -0,0 +1,15 @@
+//! Shared contract for tagging tasks across bounded contexts.
+//!
+//! Lets domains (e.g. reactive rules) label a task without depending on the
+//! tasks infrastructure.
+
+use async_trait::async_trait;
+
+use crate::shared::contracts::AppError;
+
+/// Port for adding tags to a task.
+#[async_trait]
+pub trait TaskTagger: Send + Sync {
+    /// Add `tag` to the task. Returns `false` when the task already had it.
+    async fn add_task_tag(&self, task_id: &str, tag: &str) -> Result<bool, AppError>;
+}
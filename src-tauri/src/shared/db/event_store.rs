@@ -0,0 +1,193 @@
+//! SQLite-backed `EventStore`.
+//!
+//! Appends every published domain event to `domain_events` so it survives a
+//! restart and can be queried or replayed. Versions are assigned per
+//! aggregate at insert time; the version on the incoming envelope is ignored.
+
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use std::sync::Arc;
+
+use crate::db::{Database, QueryBuilder};
+use crate::shared::services::domain_event::{
+    DomainEvent, EventEnvelope, EventFilter, EventMetadata, EventStore,
+};
+
+/// Days of events to keep; `0` keeps everything.
+pub const RETENTION_DAYS_ENV: &str = "RPMA_EVENT_RETENTION_DAYS";
+pub const DEFAULT_RETENTION_DAYS: i64 = 365;
+
+const SELECT_COLUMNS: &str = "SELECT payload, metadata, version FROM domain_events";
+
+/// Event store persisting envelopes in the application database.
+pub struct SqliteEventStore {
+    db: Arc<Database>,
+}
+
+impl SqliteEventStore {
+    pub fn new(db: Arc<Database>) -> Self {
+        Self { db }
+    }
+
+    /// Retention window from `RPMA_EVENT_RETENTION_DAYS`, or `None` when
+    /// pruning is turned off.
+    pub fn retention() -> Option<chrono::Duration> {
+        let days = std::env::var(RETENTION_DAYS_ENV)
+            .ok()
+            .and_then(|value| value.trim().parse::<i64>().ok())
+            .unwrap_or(DEFAULT_RETENTION_DAYS);
+        (days > 0).then(|| chrono::Duration::days(days))
+    }
+
+    /// Delete events that occurred before `cutoff`. Returns the number removed.
+    pub fn prune_before(&self, cutoff: DateTime<Utc>) -> Result<usize, String> {
+        let conn = self.db.get_connection()?;
+        conn.execute(
+            "DELETE FROM domain_events WHERE occurred_at < ?1",
+            params![cutoff.timestamp_millis()],
+        )
+        .map_err(|e| format!("Failed to prune domain events: {}", e))
+    }
+
+    /// Number of stored events.
+    pub fn count(&self) -> Result<i64, String> {
+        let conn = self.db.get_connection()?;
+        conn.query_row("SELECT COUNT(*) FROM domain_events", [], |row| row.get(0))
+            .map_err(|e| format!("Failed to count domain events: {}", e))
+    }
+
+    fn insert(conn: &Connection, envelope: &EventEnvelope) -> Result<(), String> {
+        let event = &envelope.event;
+        let (aggregate_type, aggregate_id) = event.subject();
+        let already_stored = conn
+            .query_row(
+                "SELECT 1 FROM domain_events WHERE id = ?1",
+                params![event.id()],
+                |_| Ok(()),
+            )
+            .optional()
+            .map_err(|e| format!("Failed to look up domain event: {}", e))?
+            .is_some();
+        if already_stored {
+            return Ok(());
+        }
+
+        let payload = serde_json::to_string(event)
+            .map_err(|e| format!("Failed to serialize domain event: {}", e))?;
+        let metadata = serde_json::to_string(&envelope.metadata)
+            .map_err(|e| format!("Failed to serialize event metadata: {}", e))?;
+        conn.execute(
+            "INSERT INTO domain_events (
+                id, event_type, aggregate_type, aggregate_id, version, correlation_id,
+                user_id, source, payload, metadata, occurred_at, stored_at
+            )
+            SELECT ?1, ?2, ?3, ?4, COALESCE(MAX(version), 0) + 1, ?5, ?6, ?7, ?8, ?9, ?10, ?11
+            FROM domain_events WHERE aggregate_id = ?4",
+            params![
+                event.id(),
+                event.event_type(),
+                aggregate_type,
+                aggregate_id,
+                envelope.metadata.correlation_id,
+                envelope.metadata.user_id,
+                envelope.metadata.source,
+                payload,
+                metadata,
+                event.timestamp().timestamp_millis(),
+                Utc::now().timestamp_millis(),
+            ],
+        )
+        .map_err(|e| format!("Failed to store domain event: {}", e))?;
+        Ok(())
+    }
+
+    fn envelope_from_row(row: &Row) -> rusqlite::Result<EventEnvelope> {
+        let payload: String = row.get(0)?;
+        let metadata: String = row.get(1)?;
+        let event: DomainEvent = serde_json::from_str(&payload).map_err(|e| {
+            rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, Box::new(e))
+        })?;
+        let metadata: EventMetadata = serde_json::from_str(&metadata).map_err(|e| {
+            rusqlite::Error::FromSqlConversionFailure(1, rusqlite::types::Type::Text, Box::new(e))
+        })?;
+        Ok(EventEnvelope {
+            event,
+            metadata,
+            version: row.get(2)?,
+        })
+    }
+
+    fn select(&self, query: QueryBuilder) -> Result<Vec<EventEnvelope>, String> {
+        let (sql, values) = query.build();
+        let conn = self.db.get_connection()?;
+        let mut stmt = conn
+            .prepare(&sql)
+            .map_err(|e| format!("Failed to prepare domain event query: {}", e))?;
+        let rows = stmt
+            .query_map(
+                rusqlite::params_from_iter(values.iter()),
+                Self::envelope_from_row,
+            )
+            .map_err(|e| format!("Failed to query domain events: {}", e))?;
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Failed to read domain event: {}", e))
+    }
+}
+
+impl EventStore for SqliteEventStore {
+    fn store(&self, envelope: &EventEnvelope) -> Result<(), String> {
+        let conn = self.db.get_connection()?;
+        Self::insert(&conn, envelope)
+    }
+
+    fn store_batch(&self, envelopes: &[EventEnvelope]) -> Result<(), String> {
+        self.db.with_transaction(|tx| {
+            for envelope in envelopes {
+                Self::insert(tx, envelope)?;
+            }
+            Ok(())
+        })
+    }
+
+    fn query(&self, filter: EventFilter) -> Result<Vec<EventEnvelope>, String> {
+        let mut query = QueryBuilder::new(SELECT_COLUMNS).where_clause("1 = 1");
+        if let Some(types) = filter.event_types {
+            let placeholders = vec!["?"; types.len()].join(", ");
+            query = query.and(&format!("event_type IN ({})", placeholders));
+            for event_type in types {
+                query = query.param(event_type);
+            }
+        }
+        if let Some(aggregate_id) = filter.aggregate_id {
+            query = query.and("aggregate_id = ?").param(aggregate_id);
+        }
+        if let Some(user_id) = filter.user_id {
+            query = query.and("user_id = ?").param(user_id);
+        }
+        if let Some(from) = filter.from_timestamp {
+            query = query.and("occurred_at >= ?").param(from.timestamp_millis());
+        }
+        if let Some(to) = filter.to_timestamp {
+            query = query.and("occurred_at <= ?").param(to.timestamp_millis());
+        }
+        query = query.order_by("occurred_at", "ASC");
+        if let Some(limit) = filter.limit {
+            query = query.limit(limit as i64);
+        }
+        self.select(query)
+    }
+
+    fn get_aggregate_events(
+        &self,
+        aggregate_id: &str,
+        from_version: Option<i64>,
+    ) -> Result<Vec<EventEnvelope>, String> {
+        let query = QueryBuilder::new(SELECT_COLUMNS)
+            .where_clause("aggregate_id = ?")
+            .param(aggregate_id.to_string())
+            .and("version >= ?")
+            .param(from_version.unwrap_or(1))
+            .order_by("version", "ASC");
+        self.select(query)
+    }
+}
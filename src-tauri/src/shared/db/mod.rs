@@ -5,6 +5,8 @@
 //! isolate SQL from shared services.
 
 pub(crate) use crate::db::Database;
+pub mod event_store;
 pub mod performance_repository;
+pub mod secrets_repository;
 pub mod system_repository;
 pub mod tx;
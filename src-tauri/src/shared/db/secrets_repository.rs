@@ -0,0 +1,160 @@
+//! Repository for credentials sealed with the secrets vault.
+//!
+//! Knows every place a sealed value is stored, so that a key rotation or the
+//! upgrade from the pre-vault formats can rewrite them all in one pass.
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use rusqlite::{params, Connection, OptionalExtension};
+use sha2::{Digest, Sha256};
+
+use crate::shared::services::secrets_vault::SecretsVault;
+
+/// `integration_configs` columns holding sealed values.
+const INTEGRATION_SECRET_COLUMNS: [&str; 3] = [
+    "encrypted_secret",
+    "encrypted_signing_secret",
+    "encrypted_previous_signing_secret",
+];
+
+/// `app_settings` JSON columns and the credential fields sealed inside them.
+const SETTINGS_SECRET_FIELDS: [(&str, &str); 3] = [
+    ("storage_settings", "cloud_secret_key"),
+    ("notifications_settings", "smtp_password"),
+    ("notifications_settings", "sms_gateway_api_key"),
+];
+
+/// What [`SecretsRepository::reseal_all`] did.
+#[derive(Debug, Default)]
+pub struct ResealReport {
+    /// Values rewritten with the current key.
+    pub resealed: usize,
+    /// Pre-vault values that could not be decoded, as `integration_id.column`.
+    /// They are left as stored for an operator to re-enter.
+    pub undecodable: Vec<String>,
+}
+
+/// ADR-005: Repository Pattern
+pub struct SecretsRepository;
+
+impl SecretsRepository {
+    /// Reseal every stored credential that is not sealed with the vault's
+    /// current key. Values sealed with an older key are reopened; integration
+    /// secrets still in the pre-vault XOR format are decoded with
+    /// `RPMA_DB_KEY`, and settings credentials stored in plain text are
+    /// sealed as they are.
+    ///
+    /// A pre-vault value that cannot be decoded (possibly written under
+    /// another `RPMA_DB_KEY`) is skipped and reported: no key in the vault
+    /// opens it either way. A sealed value that fails to open fails the whole
+    /// pass, rewriting nothing, so a rotation never retires the key it needs.
+    pub fn reseal_all(conn: &Connection, vault: &SecretsVault) -> Result<ResealReport, String> {
+        let tx = conn
+            .unchecked_transaction()
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
+        let mut report = ResealReport::default();
+        for column in INTEGRATION_SECRET_COLUMNS {
+            Self::reseal_integration_column(&tx, vault, column, &mut report)?;
+        }
+        for (column, field) in SETTINGS_SECRET_FIELDS {
+            report.resealed += Self::reseal_settings_field(&tx, vault, column, field)?;
+        }
+        tx.commit()
+            .map_err(|e| format!("Failed to commit resealed secrets: {}", e))?;
+        Ok(report)
+    }
+
+    fn reseal_integration_column(
+        conn: &Connection,
+        vault: &SecretsVault,
+        column: &str,
+        report: &mut ResealReport,
+    ) -> Result<(), String> {
+        let mut stmt = conn
+            .prepare(&format!(
+                "SELECT id, {column} FROM integration_configs WHERE {column} IS NOT NULL"
+            ))
+            .map_err(|e| format!("Failed to read integration_configs.{}: {}", column, e))?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+            })
+            .and_then(Iterator::collect::<Result<Vec<_>, _>>)
+            .map_err(|e| format!("Failed to read integration_configs.{}: {}", column, e))?;
+
+        for (id, value) in rows {
+            if !vault.needs_reseal(&value) {
+                continue;
+            }
+            let plaintext = if SecretsVault::is_sealed(&value) {
+                vault.open(&value).map_err(|e| e.to_string())?
+            } else if let Some(plaintext) = open_legacy_integration_secret(&value) {
+                plaintext
+            } else {
+                report.undecodable.push(format!("{}.{}", id, column));
+                continue;
+            };
+            let sealed = vault.seal(&plaintext).map_err(|e| e.to_string())?;
+            conn.execute(
+                &format!("UPDATE integration_configs SET {column} = ?1 WHERE id = ?2"),
+                params![sealed, id],
+            )
+            .map_err(|e| format!("Failed to reseal integration_configs.{}: {}", column, e))?;
+            report.resealed += 1;
+        }
+        Ok(())
+    }
+
+    fn reseal_settings_field(
+        conn: &Connection,
+        vault: &SecretsVault,
+        column: &str,
+        field: &str,
+    ) -> Result<usize, String> {
+        let raw: Option<String> = conn
+            .query_row(
+                &format!("SELECT {column} FROM app_settings WHERE id = 'global'"),
+                [],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(|e| format!("Failed to read app_settings.{}: {}", column, e))?;
+        let Some(mut settings) = raw
+            .and_then(|raw| serde_json::from_str::<serde_json::Value>(&raw).ok())
+            .filter(serde_json::Value::is_object)
+        else {
+            return Ok(0);
+        };
+        let Some(value) = settings[field].as_str().filter(|value| !value.is_empty()) else {
+            return Ok(0);
+        };
+        if !vault.needs_reseal(value) {
+            return Ok(0);
+        }
+        let plaintext = if SecretsVault::is_sealed(value) {
+            vault.open(value).map_err(|e| e.to_string())?
+        } else {
+            value.to_string()
+        };
+        settings[field] = vault.seal(&plaintext).map_err(|e| e.to_string())?.into();
+        conn.execute(
+            &format!("UPDATE app_settings SET {column} = ?1 WHERE id = 'global'"),
+            params![settings.to_string()],
+        )
+        .map_err(|e| format!("Failed to reseal app_settings.{}: {}", column, e))?;
+        Ok(1)
+    }
+}
+
+/// Decode an integration secret written before the vault: base64 of the
+/// secret XORed with SHA-256 of `RPMA_DB_KEY` (default `rpma-dev-key`).
+fn open_legacy_integration_secret(value: &str) -> Option<String> {
+    let key = std::env::var("RPMA_DB_KEY").unwrap_or_else(|_| "rpma-dev-key".to_string());
+    let digest = Sha256::digest(key.as_bytes());
+    let decoded = BASE64.decode(value).ok()?;
+    let decrypted: Vec<u8> = decoded
+        .iter()
+        .enumerate()
+        .map(|(index, byte)| byte ^ digest[index % digest.len()])
+        .collect();
+    String::from_utf8(decrypted).ok()
+}
@@ -7,6 +7,8 @@
 /// ADR-005: Repository Pattern
 use serde_json::json;
 
+use crate::shared::services::secrets_vault::{backup_keyring_path, validate_keyring_file};
+
 /// Repository responsible for system-level database diagnostics.
 pub struct SystemRepository;
 
@@ -206,13 +208,16 @@ impl SystemRepository {
     }
 
     /// Perform a FULL WAL checkpoint on the live database then copy the
-    /// database file to `dest_path`.
+    /// database file to `dest_path`, and the secrets keyring next to it.
     ///
     /// A FULL checkpoint flushes all WAL frames into the main DB file so the
-    /// copy is a consistent, self-contained snapshot.
+    /// copy is a consistent, self-contained snapshot. The keyring is what
+    /// opens the credentials sealed in that snapshot: without it they are
+    /// lost once the keys are rotated, or on another machine.
     pub fn export_data_backup(
         pool: &r2d2::Pool<r2d2_sqlite::SqliteConnectionManager>,
         db_path: &std::path::Path,
+        keyring_path: &std::path::Path,
         dest_path: &str,
     ) -> Result<serde_json::Value, String> {
         if dest_path.trim().is_empty() {
@@ -240,6 +245,11 @@ impl SystemRepository {
         let bytes_copied = std::fs::copy(db_path, dest)
             .map_err(|e| format!("Failed to copy database: {}", e))?;
 
+        // Copied after the database: a rotation in between only adds a key.
+        let dest_keyring = backup_keyring_path(dest);
+        std::fs::copy(keyring_path, &dest_keyring)
+            .map_err(|e| format!("Failed to copy secrets keyring: {}", e))?;
+
         let file_name = dest
             .file_name()
             .and_then(|n| n.to_str())
@@ -256,18 +266,24 @@ impl SystemRepository {
             "success": true,
             "path": dest.display().to_string(),
             "filename": file_name,
-            "size_bytes": bytes_copied
+            "size_bytes": bytes_copied,
+            "keyring_path": dest_keyring.display().to_string()
         }))
     }
 
-    /// Validate `source_path` as a SQLite file then copy it to `staged_path`.
+    /// Validate `source_path` as a SQLite file then copy it to `staged_path`,
+    /// and the keyring saved next to it to `staged_keyring_path`.
     ///
     /// The staged file is named `rpma.restore.db` and will be renamed over the
-    /// live `rpma.db` on the next application startup before the pool is opened.
+    /// live `rpma.db` on the next application startup before the pool is opened;
+    /// a staged keyring replaces `secrets.keyring` at the same time. Returns
+    /// whether the backup had a keyring: backups exported before keyrings
+    /// were saved keep the current one, which may not open their credentials.
     pub fn stage_restore_backup(
         source_path: &str,
         staged_path: &std::path::Path,
-    ) -> Result<(), String> {
+        staged_keyring_path: &std::path::Path,
+    ) -> Result<bool, String> {
         if source_path.trim().is_empty() {
             return Err("Source path must not be empty".to_string());
         }
@@ -275,16 +291,30 @@ impl SystemRepository {
         let src = std::path::Path::new(source_path);
         Self::validate_sqlite_file(src)?;
 
+        // Drop a keyring left by an earlier restore that was never applied.
+        if staged_keyring_path.exists() {
+            std::fs::remove_file(staged_keyring_path)
+                .map_err(|e| format!("Failed to clear staged keyring: {}", e))?;
+        }
+        let src_keyring = backup_keyring_path(src);
+        let has_keyring = src_keyring.exists();
+        if has_keyring {
+            validate_keyring_file(&src_keyring).map_err(|e| e.to_string())?;
+            std::fs::copy(&src_keyring, staged_keyring_path)
+                .map_err(|e| format!("Failed to stage secrets keyring: {}", e))?;
+        }
+
         std::fs::copy(src, staged_path)
             .map_err(|e| format!("Failed to stage restore file: {}", e))?;
 
         tracing::info!(
             source = %src.display(),
             staged = %staged_path.display(),
+            has_keyring,
             "Restore backup staged — will be applied on next startup"
         );
 
-        Ok(())
+        Ok(has_keyring)
     }
 }
 
@@ -347,6 +377,7 @@ mod backup_tests {
         let result = SystemRepository::export_data_backup(
             &pool,
             std::path::Path::new("rpma.db"),
+            std::path::Path::new("secrets.keyring"),
             "   ",
         );
         assert!(result.is_err());
@@ -356,7 +387,8 @@ mod backup_tests {
     #[test]
     fn stage_restore_backup_rejects_empty_source_path() {
         let staged = std::path::Path::new("/tmp/rpma.restore.db");
-        let result = SystemRepository::stage_restore_backup("", staged);
+        let staged_keyring = std::path::Path::new("/tmp/secrets.restore.keyring");
+        let result = SystemRepository::stage_restore_backup("", staged, staged_keyring);
         assert!(result.is_err());
         assert!(result.unwrap_err().contains("must not be empty"));
     }
@@ -372,8 +404,71 @@ mod backup_tests {
         let result = SystemRepository::stage_restore_backup(
             src.to_str().unwrap(),
             &staged,
+            &dir.join("secrets.restore.keyring"),
         );
         let _ = std::fs::remove_dir_all(&dir);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn backup_carries_the_keyring_through_export_and_restore() {
+        use crate::shared::services::secrets_vault::SecretsVault;
+        use r2d2_sqlite::SqliteConnectionManager;
+
+        let dir = temp_dir();
+        std::fs::create_dir_all(&dir).unwrap();
+        let db_path = dir.join("rpma.db");
+        let keyring_path = dir.join("secrets.keyring");
+        let vault = SecretsVault::load_or_create(&keyring_path).unwrap();
+        let sealed = vault.seal("smtp-secret").unwrap();
+        let pool = r2d2::Pool::builder()
+            .max_size(1)
+            .build(SqliteConnectionManager::file(&db_path))
+            .unwrap();
+        pool.get()
+            .unwrap()
+            .execute_batch("CREATE TABLE t (x INTEGER);")
+            .unwrap();
+
+        let backup = dir.join("backup").join("rpma-backup.db");
+        let exported = SystemRepository::export_data_backup(
+            &pool,
+            &db_path,
+            &keyring_path,
+            backup.to_str().unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            exported["keyring_path"],
+            dir.join("backup/rpma-backup.db.keyring")
+                .display()
+                .to_string()
+        );
+
+        let staged = dir.join("rpma.restore.db");
+        let staged_keyring = dir.join("secrets.restore.keyring");
+        let has_keyring = SystemRepository::stage_restore_backup(
+            backup.to_str().unwrap(),
+            &staged,
+            &staged_keyring,
+        );
+        let restored = SecretsVault::load_or_create(&staged_keyring)
+            .ok()
+            .and_then(|vault| vault.open(&sealed).ok());
+
+        // A backup without a keyring stages none, and clears a stale one.
+        std::fs::remove_file(dir.join("backup/rpma-backup.db.keyring")).unwrap();
+        let without_keyring = SystemRepository::stage_restore_backup(
+            backup.to_str().unwrap(),
+            &staged,
+            &staged_keyring,
+        );
+        let stale_keyring_left = staged_keyring.exists();
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert_eq!(has_keyring, Ok(true));
+        assert_eq!(restored.as_deref(), Some("smtp-secret"));
+        assert_eq!(without_keyring, Ok(false));
+        assert!(!stale_keyring_left);
+    }
 }
@@ -18,9 +18,42 @@ pub fn normalize_dialable_phone_number(phone_number: &str) -> Result<String, Str
     Ok(clean_number)
 }
 
+/// Normalizes a phone number to E.164 (`+33612345678`) for SMS gateways.
+///
+/// National numbers (leading `0`) are prefixed with `default_country_code`,
+/// `00` international prefixes become `+`, and the French `(0)` trunk marker
+/// written after the country code is dropped.
+pub fn normalize_e164_phone_number(
+    phone_number: &str,
+    default_country_code: &str,
+) -> Result<String, String> {
+    let dialable = normalize_dialable_phone_number(phone_number.trim())?;
+    let without_trunk = dialable.replace("(0)", "");
+    let digits: String = without_trunk
+        .chars()
+        .filter(|c| c.is_ascii_digit())
+        .collect();
+
+    let international = if without_trunk.starts_with('+') {
+        digits
+    } else if let Some(rest) = digits.strip_prefix("00") {
+        rest.to_string()
+    } else if let Some(national) = digits.strip_prefix('0') {
+        format!("{}{}", default_country_code.trim_start_matches('+'), national)
+    } else {
+        return Err("Phone number must be international or start with 0".to_string());
+    };
+
+    if !(8..=15).contains(&international.len()) {
+        return Err("Phone number has an invalid length".to_string());
+    }
+
+    Ok(format!("+{}", international))
+}
+
 #[cfg(test)]
 mod tests {
-    use super::normalize_dialable_phone_number;
+    use super::{normalize_dialable_phone_number, normalize_e164_phone_number};
 
     #[test]
     fn rejects_empty_phone() {
@@ -45,4 +78,26 @@ mod tests {
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), "+33(0)612345678");
     }
+
+    #[test]
+    fn normalizes_french_numbers_to_e164() {
+        assert_eq!(
+            normalize_e164_phone_number("06 12 34 56 78", "33").unwrap(),
+            "+33612345678"
+        );
+        assert_eq!(
+            normalize_e164_phone_number(" +33 (0)6 12 34 56 78 ", "33").unwrap(),
+            "+33612345678"
+        );
+        assert_eq!(
+            normalize_e164_phone_number("0033 6 12 34 56 78", "+33").unwrap(),
+            "+33612345678"
+        );
+    }
+
+    #[test]
+    fn rejects_numbers_without_country_context() {
+        assert!(normalize_e164_phone_number("612345678", "33").is_err());
+        assert!(normalize_e164_phone_number("+33 6", "33").is_err());
+    }
 }
@@ -59,6 +59,20 @@ pub trait CalendarEventRepositoryContract: Send + Sync {
         Vec<crate::domains::calendar::models::CalendarEvent>,
     >;
 
+    async fn find_by_parent(
+        &self,
+        parent_id: &str,
+    ) -> crate::shared::repositories::base::RepoResult<
+        Vec<crate::domains::calendar::models::CalendarEvent>,
+    >;
+
+    async fn find_by_ical_uid(
+        &self,
+        uid: &str,
+    ) -> crate::shared::repositories::base::RepoResult<
+        Option<crate::domains::calendar::models::CalendarEvent>,
+    >;
+
     async fn find_events_in_range(
         &self,
         from: i64,
@@ -126,6 +140,24 @@ impl CalendarEventRepositoryContract for CalendarEventRepository {
         CalendarEventRepository::find_by_task(self, task_id).await
     }
 
+    async fn find_by_parent(
+        &self,
+        parent_id: &str,
+    ) -> crate::shared::repositories::base::RepoResult<
+        Vec<crate::domains::calendar::models::CalendarEvent>,
+    > {
+        CalendarEventRepository::find_by_parent(self, parent_id).await
+    }
+
+    async fn find_by_ical_uid(
+        &self,
+        uid: &str,
+    ) -> crate::shared::repositories::base::RepoResult<
+        Option<crate::domains::calendar::models::CalendarEvent>,
+    > {
+        CalendarEventRepository::find_by_ical_uid(self, uid).await
+    }
+
     async fn find_events_in_range(
         &self,
         from: i64,
@@ -12,22 +12,38 @@
 // --- Services ---
 // Intervention domain
 pub use crate::domains::interventions::infrastructure::intervention::InterventionService;
+pub use crate::domains::interventions::infrastructure::workflow_strategy::WorkflowStrategyFactory;
 
 // Client domain
-pub use crate::domains::clients::application::client_service::ClientService;
+pub use crate::domains::clients::application::client_service::{ClientService, ClientUpsert};
 pub use crate::domains::clients::client_handler::ClientStat;
 pub use crate::domains::clients::client_handler::{CreateClientRequest, CustomerType};
 pub use crate::domains::clients::ClientsFacade;
+// Used by inbound integrations to run client commands like the IPC handlers.
+pub(crate) use crate::domains::clients::application::client_input_validator::sanitize_create_request;
+pub use crate::domains::clients::ipc::error_mapping::{
+    check_client_access, map_service_error as map_client_service_error,
+};
 
 // Settings domain
-pub use crate::domains::settings::models::{UserNotificationSettings, UserSettings};
+pub use crate::domains::settings::models::{
+    BusinessSettings, NotificationSettings, Organization, UserNotificationSettings, UserSettings,
+};
+pub use crate::domains::settings::organization_repository::OrganizationRepository;
 pub use crate::domains::settings::settings_repository::SettingsRepository;
+pub use crate::domains::settings::SettingsService;
 pub use crate::domains::settings::UserSettingsRepository;
 
 // Calendar domain
 pub use crate::domains::calendar::calendar_handler::CalendarService;
 
+// Notifications domain
+// Used by the calendar reminder scheduler, which honours users' quiet hours.
+pub use crate::domains::notifications::notification_handler::is_quiet_hours_at;
+pub use crate::domains::notifications::NotificationsFacade;
+
 // Tasks domain
+pub use crate::domains::tasks::application::services::task_command_service::TaskCommandService;
 pub use crate::domains::tasks::infrastructure::task::TaskService;
 
 // Auth domain
@@ -51,7 +67,7 @@ pub use crate::domains::interventions::domain::models::step::{
 };
 
 // Inventory types
-pub use crate::domains::inventory::domain::models::material::MaterialConsumption;
+pub use crate::domains::inventory::domain::models::material::{Material, MaterialConsumption};
 pub use crate::domains::inventory::domain::models::material::{MaterialType, UnitOfMeasure};
 pub use crate::domains::inventory::infrastructure::material::{
     CreateMaterialRequest, MaterialService, RecordConsumptionRequest, UpdateStockRequest,
@@ -69,8 +85,9 @@ pub use crate::domains::interventions::infrastructure::intervention::{
 // Quote services and models
 pub use crate::domains::quotes::application::quote_service::QuoteService;
 pub use crate::domains::quotes::domain::models::quote::{
-    ConvertQuoteToTaskResponse, CreateQuoteItemRequest, CreateQuoteRequest, QuoteAcceptResponse,
-    QuoteItemKind, QuoteStatus, UpdateQuoteItemRequest, UpdateQuoteRequest,
+    ConvertQuoteToTaskResponse, CreateQuoteItemRequest, CreateQuoteRequest, Quote,
+    QuoteAcceptResponse, QuoteItem, QuoteItemKind, QuoteStatus, UpdateQuoteItemRequest,
+    UpdateQuoteRequest,
 };
 pub use crate::domains::quotes::QuotesFacade;
 
@@ -165,6 +165,26 @@ pub enum DomainEvent {
         timestamp: DateTime<Utc>,
         metadata: Option<serde_json::Value>,
     },
+    MaterialLowStock {
+        id: String,
+        material_id: String,
+        material_name: String,
+        current_stock: f64,
+        minimum_stock: f64,
+        unit: String,
+        timestamp: DateTime<Utc>,
+        metadata: Option<serde_json::Value>,
+    },
+    PhotoUploaded {
+        id: String,
+        photo_id: String,
+        intervention_id: String,
+        step_id: Option<String>,
+        step_number: Option<i32>,
+        uploaded_by: String,
+        timestamp: DateTime<Utc>,
+        metadata: Option<serde_json::Value>,
+    },
     QuoteCreated {
         id: String,
         quote_id: String,
@@ -203,6 +223,27 @@ pub enum DomainEvent {
         timestamp: DateTime<Utc>,
         metadata: Option<serde_json::Value>,
     },
+    QuoteSent {
+        id: String,
+        quote_id: String,
+        quote_number: String,
+        client_id: String,
+        task_id: Option<String>,
+        revision: i32,
+        total: i64,
+        sent_by: String,
+        timestamp: DateTime<Utc>,
+        metadata: Option<serde_json::Value>,
+    },
+    QuoteExpired {
+        id: String,
+        quote_id: String,
+        quote_number: String,
+        client_id: String,
+        expired_by: String,
+        timestamp: DateTime<Utc>,
+        metadata: Option<serde_json::Value>,
+    },
     QuoteShared {
         id: String,
         quote_id: String,
@@ -277,6 +318,17 @@ pub enum DomainEvent {
         timestamp: DateTime<Utc>,
         metadata: Option<serde_json::Value>,
     },
+    /// An integration was switched off automatically after too many failed
+    /// deliveries in a row.
+    IntegrationDisabled {
+        id: String,
+        integration_id: String,
+        integration_name: String,
+        consecutive_failures: i64,
+        reason: String,
+        timestamp: DateTime<Utc>,
+        metadata: Option<serde_json::Value>,
+    },
     SystemMaintenance {
         id: String,
         maintenance_type: String,
@@ -387,6 +439,8 @@ impl DomainEvent {
     pub const INTERVENTION_FINALIZED: &'static str = "InterventionFinalized";
     pub const INTERVENTION_CANCELLED: &'static str = "InterventionCancelled";
     pub const MATERIAL_CONSUMED: &'static str = "MaterialConsumed";
+    pub const MATERIAL_LOW_STOCK: &'static str = "MaterialLowStock";
+    pub const PHOTO_UPLOADED: &'static str = "PhotoUploaded";
     pub const USER_CREATED: &'static str = "UserCreated";
     pub const USER_UPDATED: &'static str = "UserUpdated";
     pub const USER_LOGGED_IN: &'static str = "UserLoggedIn";
@@ -394,6 +448,7 @@ impl DomainEvent {
     pub const AUTHENTICATION_FAILED: &'static str = "AuthenticationFailed";
     pub const AUTHENTICATION_SUCCESS: &'static str = "AuthenticationSuccess";
     pub const SYSTEM_ERROR: &'static str = "SystemError";
+    pub const INTEGRATION_DISABLED: &'static str = "IntegrationDisabled";
     pub const SYSTEM_MAINTENANCE: &'static str = "SystemMaintenance";
     pub const PERFORMANCE_ALERT: &'static str = "PerformanceAlert";
     pub const NOTIFICATION_RECEIVED: &'static str = "NotificationReceived";
@@ -404,6 +459,8 @@ impl DomainEvent {
     pub const QUOTE_ACCEPTED: &'static str = "QuoteAccepted";
     pub const QUOTE_REJECTED: &'static str = "QuoteRejected";
     pub const QUOTE_CONVERTED: &'static str = "QuoteConverted";
+    pub const QUOTE_SENT: &'static str = "QuoteSent";
+    pub const QUOTE_EXPIRED: &'static str = "QuoteExpired";
     pub const QUOTE_SHARED: &'static str = "QuoteShared";
     pub const QUOTE_CUSTOMER_RESPONDED: &'static str = "QuoteCustomerResponded";
     pub const ENTITY_RESTORED: &'static str = "EntityRestored";
@@ -414,6 +471,7 @@ impl DomainEvent {
     pub const QUOTE_CREATED_NOTIF: &'static str = "QuoteCreated";
     pub const QUOTE_APPROVED_NOTIF: &'static str = "QuoteApproved";
     pub const SYSTEM_ALERT_NOTIF: &'static str = "SystemAlert";
+    pub const DEADLINE_REMINDER_NOTIF: &'static str = "DeadlineReminder";
 
     pub fn id(&self) -> &str {
         match self {
@@ -434,10 +492,14 @@ impl DomainEvent {
             | DomainEvent::InterventionFinalized { id, .. }
             | DomainEvent::InterventionCancelled { id, .. }
             | DomainEvent::MaterialConsumed { id, .. }
+            | DomainEvent::MaterialLowStock { id, .. }
+            | DomainEvent::PhotoUploaded { id, .. }
             | DomainEvent::QuoteCreated { id, .. }
             | DomainEvent::QuoteUpdated { id, .. }
             | DomainEvent::QuoteDeleted { id, .. }
             | DomainEvent::QuoteDuplicated { id, .. }
+            | DomainEvent::QuoteSent { id, .. }
+            | DomainEvent::QuoteExpired { id, .. }
             | DomainEvent::QuoteShared { id, .. }
             | DomainEvent::QuoteCustomerResponded { id, .. }
             | DomainEvent::UserCreated { id, .. }
@@ -447,6 +509,7 @@ impl DomainEvent {
             | DomainEvent::AuthenticationFailed { id, .. }
             | DomainEvent::AuthenticationSuccess { id, .. }
             | DomainEvent::SystemError { id, .. }
+            | DomainEvent::IntegrationDisabled { id, .. }
             | DomainEvent::SystemMaintenance { id, .. }
             | DomainEvent::PerformanceAlert { id, .. }
             | DomainEvent::NotificationReceived { id, .. }
@@ -477,10 +540,14 @@ impl DomainEvent {
             | DomainEvent::InterventionFinalized { metadata, .. }
             | DomainEvent::InterventionCancelled { metadata, .. }
             | DomainEvent::MaterialConsumed { metadata, .. }
+            | DomainEvent::MaterialLowStock { metadata, .. }
+            | DomainEvent::PhotoUploaded { metadata, .. }
             | DomainEvent::QuoteCreated { metadata, .. }
             | DomainEvent::QuoteUpdated { metadata, .. }
             | DomainEvent::QuoteDeleted { metadata, .. }
             | DomainEvent::QuoteDuplicated { metadata, .. }
+            | DomainEvent::QuoteSent { metadata, .. }
+            | DomainEvent::QuoteExpired { metadata, .. }
             | DomainEvent::QuoteShared { metadata, .. }
             | DomainEvent::QuoteCustomerResponded { metadata, .. }
             | DomainEvent::UserCreated { metadata, .. }
@@ -490,6 +557,7 @@ impl DomainEvent {
             | DomainEvent::AuthenticationFailed { metadata, .. }
             | DomainEvent::AuthenticationSuccess { metadata, .. }
             | DomainEvent::SystemError { metadata, .. }
+            | DomainEvent::IntegrationDisabled { metadata, .. }
             | DomainEvent::SystemMaintenance { metadata, .. }
             | DomainEvent::PerformanceAlert { metadata, .. }
             | DomainEvent::NotificationReceived { metadata, .. }
@@ -539,13 +607,17 @@ impl DomainEvent {
             | DomainEvent::InterventionCancelled {
                 intervention_id, ..
             } => ("intervention", intervention_id),
-            DomainEvent::MaterialConsumed { material_id, .. } => ("material", material_id),
+            DomainEvent::MaterialConsumed { material_id, .. }
+            | DomainEvent::MaterialLowStock { material_id, .. } => ("material", material_id),
+            DomainEvent::PhotoUploaded { photo_id, .. } => ("photo", photo_id),
             DomainEvent::QuoteCreated { quote_id, .. }
             | DomainEvent::QuoteUpdated { quote_id, .. }
             | DomainEvent::QuoteDeleted { quote_id, .. }
             | DomainEvent::QuoteAccepted { quote_id, .. }
             | DomainEvent::QuoteRejected { quote_id, .. }
             | DomainEvent::QuoteConverted { quote_id, .. }
+            | DomainEvent::QuoteSent { quote_id, .. }
+            | DomainEvent::QuoteExpired { quote_id, .. }
             | DomainEvent::QuoteShared { quote_id, .. }
             | DomainEvent::QuoteCustomerResponded { quote_id, .. } => ("quote", quote_id),
             DomainEvent::QuoteDuplicated { new_quote_id, .. } => ("quote", new_quote_id),
@@ -558,6 +630,9 @@ impl DomainEvent {
                 ("user", user_id.as_deref().unwrap_or("unknown"))
             }
             DomainEvent::SystemError { component, .. } => ("component", component),
+            DomainEvent::IntegrationDisabled { integration_id, .. } => {
+                ("integration", integration_id)
+            }
             DomainEvent::SystemMaintenance {
                 maintenance_type, ..
             } => ("maintenance", maintenance_type),
@@ -589,6 +664,8 @@ impl DomainEvent {
             DomainEvent::InterventionFinalized { .. } => Self::INTERVENTION_FINALIZED,
             DomainEvent::InterventionCancelled { .. } => Self::INTERVENTION_CANCELLED,
             DomainEvent::MaterialConsumed { .. } => Self::MATERIAL_CONSUMED,
+            DomainEvent::MaterialLowStock { .. } => Self::MATERIAL_LOW_STOCK,
+            DomainEvent::PhotoUploaded { .. } => Self::PHOTO_UPLOADED,
             DomainEvent::UserCreated { .. } => Self::USER_CREATED,
             DomainEvent::UserUpdated { .. } => Self::USER_UPDATED,
             DomainEvent::UserLoggedIn { .. } => Self::USER_LOGGED_IN,
@@ -596,6 +673,7 @@ impl DomainEvent {
             DomainEvent::AuthenticationFailed { .. } => Self::AUTHENTICATION_FAILED,
             DomainEvent::AuthenticationSuccess { .. } => Self::AUTHENTICATION_SUCCESS,
             DomainEvent::SystemError { .. } => Self::SYSTEM_ERROR,
+            DomainEvent::IntegrationDisabled { .. } => Self::INTEGRATION_DISABLED,
             DomainEvent::SystemMaintenance { .. } => Self::SYSTEM_MAINTENANCE,
             DomainEvent::PerformanceAlert { .. } => Self::PERFORMANCE_ALERT,
             DomainEvent::NotificationReceived { .. } => Self::NOTIFICATION_RECEIVED,
@@ -606,6 +684,8 @@ impl DomainEvent {
             DomainEvent::QuoteAccepted { .. } => Self::QUOTE_ACCEPTED,
             DomainEvent::QuoteRejected { .. } => Self::QUOTE_REJECTED,
             DomainEvent::QuoteConverted { .. } => Self::QUOTE_CONVERTED,
+            DomainEvent::QuoteSent { .. } => Self::QUOTE_SENT,
+            DomainEvent::QuoteExpired { .. } => Self::QUOTE_EXPIRED,
             DomainEvent::QuoteShared { .. } => Self::QUOTE_SHARED,
             DomainEvent::QuoteCustomerResponded { .. } => Self::QUOTE_CUSTOMER_RESPONDED,
             DomainEvent::EntityRestored { .. } => Self::ENTITY_RESTORED,
@@ -632,10 +712,14 @@ impl DomainEvent {
             | DomainEvent::InterventionFinalized { timestamp, .. }
             | DomainEvent::InterventionCancelled { timestamp, .. }
             | DomainEvent::MaterialConsumed { timestamp, .. }
+            | DomainEvent::MaterialLowStock { timestamp, .. }
+            | DomainEvent::PhotoUploaded { timestamp, .. }
             | DomainEvent::QuoteCreated { timestamp, .. }
             | DomainEvent::QuoteUpdated { timestamp, .. }
             | DomainEvent::QuoteDeleted { timestamp, .. }
             | DomainEvent::QuoteDuplicated { timestamp, .. }
+            | DomainEvent::QuoteSent { timestamp, .. }
+            | DomainEvent::QuoteExpired { timestamp, .. }
             | DomainEvent::QuoteShared { timestamp, .. }
             | DomainEvent::QuoteCustomerResponded { timestamp, .. }
             | DomainEvent::UserCreated { timestamp, .. }
@@ -645,6 +729,7 @@ impl DomainEvent {
             | DomainEvent::AuthenticationFailed { timestamp, .. }
             | DomainEvent::AuthenticationSuccess { timestamp, .. }
             | DomainEvent::SystemError { timestamp, .. }
+            | DomainEvent::IntegrationDisabled { timestamp, .. }
             | DomainEvent::SystemMaintenance { timestamp, .. }
             | DomainEvent::PerformanceAlert { timestamp, .. }
             | DomainEvent::NotificationReceived { timestamp, .. }
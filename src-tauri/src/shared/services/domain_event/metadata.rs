@@ -69,4 +69,35 @@ impl EventEnvelope {
     pub fn with_user(event: DomainEvent, source: String, user_id: String) -> Self {
         Self::new(event, EventMetadata::with_user(source, user_id))
     }
+
+    /// Wrap an event published on the bus for storage.
+    ///
+    /// The correlation id and custom data come from the event's own metadata.
+    /// The acting user is the event's `user_id` field, or else its first
+    /// `*_by` field (`assigned_by`, `completed_by`, ...).
+    pub fn from_published(event: DomainEvent, source: &str) -> Self {
+        let mut metadata = EventMetadata::new(source.to_string());
+        if let Some(correlation_id) = event.correlation_id() {
+            metadata.correlation_id = correlation_id.to_string();
+        }
+        metadata.custom = event.metadata().cloned();
+        metadata.user_id = serde_json::to_value(&event)
+            .ok()
+            .and_then(|value| actor_of(&value));
+        Self::new(event, metadata)
+    }
+}
+
+fn actor_of(event: &serde_json::Value) -> Option<String> {
+    let fields = event.as_object()?;
+    fields
+        .get("user_id")
+        .or_else(|| {
+            fields
+                .iter()
+                .find(|(name, _)| name.ends_with("_by"))
+                .map(|(_, value)| value)
+        })
+        .and_then(|value| value.as_str())
+        .map(str::to_string)
 }
@@ -63,6 +63,16 @@ impl EventStore for InMemoryEventStore {
                         return false;
                     }
                 }
+                if let Some(ref aggregate_id) = filter.aggregate_id {
+                    if envelope.event.subject().1 != aggregate_id.as_str() {
+                        return false;
+                    }
+                }
+                if let Some(ref user_id) = filter.user_id {
+                    if envelope.metadata.user_id.as_ref() != Some(user_id) {
+                        return false;
+                    }
+                }
                 if let Some(from) = filter.from_timestamp {
                     if envelope.event.timestamp() < from {
                         return false;
@@ -87,9 +97,15 @@ impl EventStore for InMemoryEventStore {
 
     fn get_aggregate_events(
         &self,
-        _aggregate_id: &str,
-        _from_version: Option<i64>,
+        aggregate_id: &str,
+        from_version: Option<i64>,
     ) -> Result<Vec<EventEnvelope>, String> {
-        Ok(Vec::new())
+        let events = self.events.lock().unwrap_or_else(|e| e.into_inner());
+        Ok(events
+            .iter()
+            .filter(|envelope| envelope.event.subject().1 == aggregate_id)
+            .filter(|envelope| i64::from(envelope.version) >= from_version.unwrap_or(1))
+            .cloned()
+            .collect())
     }
 }
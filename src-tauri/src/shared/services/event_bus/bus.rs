@@ -2,14 +2,25 @@ use futures_util::FutureExt;
 use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
 
-use crate::shared::services::domain_event::DomainEvent;
+use crate::shared::services::domain_event::{DomainEvent, EventEnvelope, EventFilter, EventStore};
 
 use super::{EventHandler, EventPublisher};
 
+/// Source recorded on envelopes stored by the bus.
+const STORE_SOURCE: &str = "event_bus";
+
+/// Outcome of [`InMemoryEventBus::replay`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReplaySummary {
+    pub replayed: usize,
+    pub failed: usize,
+}
+
 /// In-memory event bus with publish/subscribe pattern
 pub struct InMemoryEventBus {
     // RwLock: read-heavy, written only on handler registration at startup.
     handlers: Arc<RwLock<HashMap<String, Vec<Arc<dyn EventHandler>>>>>,
+    store: Option<Arc<dyn EventStore>>,
 }
 
 impl InMemoryEventBus {
@@ -17,9 +28,16 @@ impl InMemoryEventBus {
     pub fn new() -> Self {
         Self {
             handlers: Arc::new(RwLock::new(HashMap::new())),
+            store: None,
         }
     }
 
+    /// Append every dispatched event to `store` before handlers run.
+    pub fn with_store(mut self, store: Arc<dyn EventStore>) -> Self {
+        self.store = Some(store);
+        self
+    }
+
     /// Register an event handler for specific event types
     pub fn register_handler<H>(&self, handler: H)
     where
@@ -47,6 +65,21 @@ impl InMemoryEventBus {
         let (subject_type, subject_id) = event.subject();
         let subject_type = subject_type.to_string();
         let subject_id = subject_id.to_string();
+
+        // A store failure is logged; handlers still get the event.
+        if let Some(store) = &self.store {
+            let envelope = EventEnvelope::from_published(event.clone(), STORE_SOURCE);
+            if let Err(e) = store.store(&envelope) {
+                tracing::error!(
+                    event_type = %event_type,
+                    event_id = %event_id,
+                    correlation_id = %correlation_id,
+                    error = %e,
+                    "Failed to store event"
+                );
+            }
+        }
+
         let handlers = {
             let handlers = self.handlers.read().unwrap_or_else(|e| e.into_inner());
             handlers.get(event_type).cloned().unwrap_or_default()
@@ -104,6 +137,66 @@ impl InMemoryEventBus {
         Ok(())
     }
 
+    /// Re-deliver stored events matching `filter` to `handler`, oldest first.
+    ///
+    /// Only the event types the handler is interested in are read. Replayed
+    /// events are not stored again and no other handler sees them, so this
+    /// can rebuild a projection or feed history to a new integration.
+    pub async fn replay(
+        &self,
+        filter: EventFilter,
+        handler: &dyn EventHandler,
+    ) -> Result<ReplaySummary, String> {
+        let store = self
+            .store
+            .as_ref()
+            .ok_or_else(|| "Event bus has no event store to replay from".to_string())?;
+        let interested: Vec<String> = handler
+            .interested_events()
+            .into_iter()
+            .map(str::to_string)
+            .collect();
+        let event_types = match filter.event_types.clone() {
+            Some(types) => types
+                .into_iter()
+                .filter(|event_type| interested.contains(event_type))
+                .collect(),
+            None => interested,
+        };
+        if event_types.is_empty() {
+            return Ok(ReplaySummary::default());
+        }
+
+        let mut summary = ReplaySummary::default();
+        for envelope in store.query(filter.with_event_types(event_types))? {
+            let event = &envelope.event;
+            match std::panic::AssertUnwindSafe(handler.handle(event))
+                .catch_unwind()
+                .await
+            {
+                Ok(Ok(())) => summary.replayed += 1,
+                Ok(Err(e)) => {
+                    summary.failed += 1;
+                    tracing::warn!(
+                        event_type = %event.event_type(),
+                        event_id = %event.id(),
+                        error = %e,
+                        "Replayed event handler failed"
+                    );
+                }
+                Err(_) => {
+                    summary.failed += 1;
+                    tracing::error!(
+                        event_type = %event.event_type(),
+                        event_id = %event.id(),
+                        "Replayed event handler panicked"
+                    );
+                }
+            }
+        }
+        Ok(summary)
+    }
+
     /// Get the number of registered handlers for an event type
     pub fn handler_count(&self, event_type: &str) -> usize {
         let handlers = self.handlers.read().unwrap_or_else(|e| e.into_inner());
@@ -126,6 +219,7 @@ impl Clone for InMemoryEventBus {
     fn clone(&self) -> Self {
         Self {
             handlers: Arc::clone(&self.handlers),
+            store: self.store.clone(),
         }
     }
 }
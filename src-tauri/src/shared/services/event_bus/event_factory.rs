@@ -225,6 +225,93 @@ pub fn material_consumed(
     }
 }
 
+/// Creates a `MaterialConsumed` event attributed to the recording user.
+pub fn material_consumed_by(
+    material_id: String,
+    intervention_id: String,
+    quantity: f64,
+    unit: String,
+    consumed_by: String,
+) -> DomainEvent {
+    DomainEvent::MaterialConsumed {
+        id: Uuid::new_v4().to_string(),
+        material_id,
+        intervention_id,
+        quantity,
+        unit,
+        consumed_by,
+        timestamp: Utc::now(),
+        metadata: None,
+    }
+}
+
+/// Creates a `MaterialLowStock` event once stock reaches the minimum level.
+pub fn material_low_stock(
+    material_id: String,
+    material_name: String,
+    current_stock: f64,
+    minimum_stock: f64,
+    unit: String,
+) -> DomainEvent {
+    DomainEvent::MaterialLowStock {
+        id: Uuid::new_v4().to_string(),
+        material_id,
+        material_name,
+        current_stock,
+        minimum_stock,
+        unit,
+        timestamp: Utc::now(),
+        metadata: None,
+    }
+}
+
+/// Create an `InterventionStepCompleted` event carrying the request
+/// correlation ID. `actual_duration` is the step's recorded duration in
+/// seconds.
+pub fn intervention_step_completed_with_ctx(
+    intervention_id: String,
+    step_id: String,
+    step_number: i32,
+    completed_by: String,
+    photos_taken: i32,
+    actual_duration: Option<i32>,
+    correlation_id: String,
+) -> DomainEvent {
+    DomainEvent::InterventionStepCompleted {
+        id: Uuid::new_v4().to_string(),
+        intervention_id,
+        step_id,
+        step_number,
+        completed_by,
+        photos_taken,
+        actual_duration,
+        quality_score: None,
+        timestamp: Utc::now(),
+        metadata: Some(serde_json::json!({ "correlation_id": correlation_id })),
+    }
+}
+
+/// Create a `PhotoUploaded` event carrying the request correlation ID.
+pub fn photo_uploaded_with_ctx(
+    photo_id: String,
+    intervention_id: String,
+    step_id: Option<String>,
+    step_number: Option<i32>,
+    uploaded_by: String,
+    correlation_id: String,
+) -> DomainEvent {
+    DomainEvent::PhotoUploaded {
+        id: Uuid::new_v4().to_string(),
+        photo_id,
+        intervention_id,
+        step_id,
+        step_number,
+        uploaded_by,
+        timestamp: Utc::now(),
+        metadata: Some(serde_json::json!({ "correlation_id": correlation_id })),
+    }
+}
+
 /// Create a QuoteCreated event.
 pub fn quote_created(
     quote_id: String,
@@ -529,3 +616,64 @@ pub fn quote_customer_responded(
         metadata: None,
     }
 }
+
+/// Create a `QuoteSent` domain event for the revision frozen on send.
+pub fn quote_sent(
+    quote_id: String,
+    quote_number: String,
+    client_id: String,
+    task_id: Option<String>,
+    revision: i32,
+    total: i64,
+    sent_by: String,
+) -> DomainEvent {
+    DomainEvent::QuoteSent {
+        id: Uuid::new_v4().to_string(),
+        quote_id,
+        quote_number,
+        client_id,
+        task_id,
+        revision,
+        total,
+        sent_by,
+        timestamp: Utc::now(),
+        metadata: None,
+    }
+}
+
+/// Create a `QuoteExpired` domain event.
+pub fn quote_expired(
+    quote_id: String,
+    quote_number: String,
+    client_id: String,
+    expired_by: String,
+) -> DomainEvent {
+    DomainEvent::QuoteExpired {
+        id: Uuid::new_v4().to_string(),
+        quote_id,
+        quote_number,
+        client_id,
+        expired_by,
+        timestamp: Utc::now(),
+        metadata: None,
+    }
+}
+
+/// Create an `IntegrationDisabled` event once sustained delivery failures
+/// switch an integration off.
+pub fn integration_disabled(
+    integration_id: String,
+    integration_name: String,
+    consecutive_failures: i64,
+    reason: String,
+) -> DomainEvent {
+    DomainEvent::IntegrationDisabled {
+        id: Uuid::new_v4().to_string(),
+        integration_id,
+        integration_name,
+        consecutive_failures,
+        reason,
+        timestamp: Utc::now(),
+        metadata: None,
+    }
+}
@@ -8,7 +8,7 @@ pub mod event_factory;
 mod tauri_emitter;
 mod traits;
 
-pub use bus::InMemoryEventBus;
+pub use bus::{InMemoryEventBus, ReplaySummary};
 pub use tauri_emitter::TauriEmitter;
 pub use traits::{EventHandler, EventPublisher};
 
@@ -3,7 +3,9 @@ use chrono::Utc;
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 
-use crate::shared::services::domain_event::DomainEvent;
+use crate::shared::db::event_store::SqliteEventStore;
+use crate::shared::db::Database;
+use crate::shared::services::domain_event::{DomainEvent, EventFilter, EventStore};
 
 use super::{event_factory, EventHandler, InMemoryEventBus};
 
@@ -275,3 +277,159 @@ async fn test_event_bus_dispatch_releases_rwlock_before_await() {
 
     assert_eq!(counter.load(Ordering::SeqCst), 4);
 }
+
+async fn bus_with_sqlite_store() -> (InMemoryEventBus, Arc<SqliteEventStore>) {
+    let db = Arc::new(Database::new_in_memory().await.unwrap());
+    let store = Arc::new(SqliteEventStore::new(db));
+    let bus = InMemoryEventBus::new().with_store(store.clone() as Arc<dyn EventStore>);
+    (bus, store)
+}
+
+#[tokio::test]
+async fn test_event_bus_stores_dispatched_events_with_aggregate_versions() {
+    let (event_bus, store) = bus_with_sqlite_store().await;
+
+    let created = event_factory::task_created_with_ctx(
+        "task-1".to_string(),
+        "T-001".to_string(),
+        "Test Task".to_string(),
+        "user-1".to_string(),
+        "corr-1".to_string(),
+    );
+    event_bus.dispatch(created.clone()).await.unwrap();
+    event_bus
+        .dispatch(event_factory::task_assigned(
+            "task-1".to_string(),
+            "tech-1".to_string(),
+        ))
+        .await
+        .unwrap();
+    event_bus
+        .dispatch(event_factory::client_created(
+            "client-1".to_string(),
+            "Acme".to_string(),
+            "user-1".to_string(),
+        ))
+        .await
+        .unwrap();
+    // Re-dispatching the same event does not store it twice.
+    event_bus.dispatch(created).await.unwrap();
+
+    assert_eq!(store.count().unwrap(), 3);
+
+    let task_events = store.get_aggregate_events("task-1", None).unwrap();
+    let versions: Vec<i32> = task_events.iter().map(|e| e.version).collect();
+    assert_eq!(versions, vec![1, 2]);
+    assert_eq!(task_events[0].metadata.correlation_id, "corr-1");
+    assert_eq!(task_events[0].metadata.user_id.as_deref(), Some("user-1"));
+    assert_eq!(task_events[1].metadata.user_id.as_deref(), Some("system"));
+    assert_eq!(
+        store.get_aggregate_events("task-1", Some(2)).unwrap().len(),
+        1
+    );
+
+    let by_type = store
+        .query(EventFilter::new().with_event_type(DomainEvent::CLIENT_CREATED.to_string()))
+        .unwrap();
+    assert_eq!(by_type.len(), 1);
+    assert_eq!(by_type[0].event.subject(), ("client", "client-1"));
+
+    let by_user = store
+        .query(EventFilter::new().with_user_id("user-1".to_string()))
+        .unwrap();
+    assert_eq!(by_user.len(), 2);
+}
+
+#[tokio::test]
+async fn test_event_bus_replay_feeds_only_the_chosen_handler() {
+    let (event_bus, store) = bus_with_sqlite_store().await;
+    let live_counter = Arc::new(AtomicUsize::new(0));
+    event_bus.register_handler(TestHandler {
+        counter: live_counter.clone(),
+        event_types: vec![DomainEvent::TASK_CREATED],
+    });
+
+    for i in 0..3 {
+        event_bus
+            .dispatch(event_factory::task_created(
+                format!("task-{}", i),
+                format!("Task {}", i),
+                None,
+            ))
+            .await
+            .unwrap();
+    }
+    event_bus
+        .dispatch(event_factory::task_assigned(
+            "task-0".to_string(),
+            "tech-1".to_string(),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(live_counter.load(Ordering::SeqCst), 3);
+
+    let replay_counter = Arc::new(AtomicUsize::new(0));
+    let replay_handler = TestHandler {
+        counter: replay_counter.clone(),
+        event_types: vec![DomainEvent::TASK_CREATED],
+    };
+    let summary = event_bus
+        .replay(EventFilter::new(), &replay_handler)
+        .await
+        .unwrap();
+
+    assert_eq!(summary.replayed, 3);
+    assert_eq!(summary.failed, 0);
+    assert_eq!(replay_counter.load(Ordering::SeqCst), 3);
+    // Registered handlers are not called again and nothing is re-stored.
+    assert_eq!(live_counter.load(Ordering::SeqCst), 3);
+    assert_eq!(store.count().unwrap(), 4);
+
+    let limited = event_bus
+        .replay(EventFilter::new().with_limit(1), &replay_handler)
+        .await
+        .unwrap();
+    assert_eq!(limited.replayed, 1);
+}
+
+#[tokio::test]
+async fn test_event_bus_replay_requires_a_store() {
+    let event_bus = InMemoryEventBus::new();
+    let handler = TestHandler {
+        counter: Arc::new(AtomicUsize::new(0)),
+        event_types: vec![DomainEvent::TASK_CREATED],
+    };
+
+    assert!(event_bus
+        .replay(EventFilter::new(), &handler)
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn test_sqlite_event_store_prunes_events_older_than_cutoff() {
+    let (event_bus, store) = bus_with_sqlite_store().await;
+
+    let mut old = event_factory::task_created("task-old".to_string(), "Old".to_string(), None);
+    if let DomainEvent::TaskCreated { timestamp, .. } = &mut old {
+        *timestamp = Utc::now() - chrono::Duration::days(400);
+    }
+    event_bus.dispatch(old).await.unwrap();
+    event_bus
+        .dispatch(event_factory::task_created(
+            "task-new".to_string(),
+            "New".to_string(),
+            None,
+        ))
+        .await
+        .unwrap();
+
+    let pruned = store
+        .prune_before(Utc::now() - chrono::Duration::days(365))
+        .unwrap();
+
+    assert_eq!(pruned, 1);
+    let remaining = store.query(EventFilter::new()).unwrap();
+    assert_eq!(remaining.len(), 1);
+    assert_eq!(remaining[0].event.subject().1, "task-new");
+}
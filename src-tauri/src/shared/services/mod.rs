@@ -10,5 +10,6 @@ pub mod document_storage;
 pub mod domain_event;
 pub mod event_bus;
 pub mod global_search;
+pub mod secrets_vault;
 pub mod system;
 pub mod validation;
@@ -0,0 +1,402 @@
+//! Secrets vault: authenticated encryption for credentials stored in the database.
+//!
+//! Values are sealed with XChaCha20-Poly1305 under a data key derived from a
+//! per-installation master key, and tagged with that key's version:
+//!
+//! ```text
+//! vault:v2:<base64(nonce || ciphertext || tag)>
+//! ```
+//!
+//! The keyring (every master key still needed to open stored values, plus
+//! which one seals new ones) lives in `secrets.keyring` in the app data
+//! directory, outside the database, so a copied database file does not carry
+//! its own key. A data backup saves the keyring in a file next to the
+//! database copy (see [`backup_keyring_path`]), and restoring the backup
+//! restores that keyring with it.
+//!
+//! Rotation adds a key and makes it current; stored values are then resealed
+//! (see `SecretsRepository::reseal_all`) and the older keys retired.
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{Key, XChaCha20Poly1305, XNonce};
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock, RwLock};
+
+use crate::shared::error::{AppError, AppResult};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// File name of the keyring in the app data directory.
+pub const KEYRING_FILE_NAME: &str = "secrets.keyring";
+
+/// File name of a keyring staged by a restore, applied with the database on
+/// the next startup.
+pub const RESTORE_KEYRING_FILE_NAME: &str = "secrets.restore.keyring";
+
+const SEALED_PREFIX: &str = "vault:v";
+const NONCE_LEN: usize = 24;
+
+/// Master keys by version, and the version that seals new values.
+struct Keyring {
+    current: u32,
+    keys: BTreeMap<u32, [u8; 32]>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct KeyringFile {
+    current: u32,
+    keys: BTreeMap<u32, String>,
+}
+
+pub struct SecretsVault {
+    keyring: RwLock<Keyring>,
+    /// Where the keyring is persisted; `None` for an in-memory vault.
+    path: Option<PathBuf>,
+}
+
+impl SecretsVault {
+    /// Open the keyring at `path`, creating it with a fresh master key on
+    /// first start.
+    pub fn load_or_create(path: impl Into<PathBuf>) -> AppResult<Self> {
+        let path = path.into();
+        if path.exists() {
+            let keyring = read_keyring(&path)?;
+            return Ok(Self {
+                keyring: RwLock::new(keyring),
+                path: Some(path),
+            });
+        }
+        let vault = Self {
+            keyring: RwLock::new(Keyring {
+                current: 1,
+                keys: BTreeMap::from([(1, random_key())]),
+            }),
+            path: Some(path),
+        };
+        vault.persist(&vault.keyring.read().expect("keyring lock"))?;
+        tracing::info!("Created secrets keyring");
+        Ok(vault)
+    }
+
+    /// A vault whose key lives only in this process, for tests and for code
+    /// running before startup installs the real one.
+    pub fn ephemeral() -> Self {
+        Self {
+            keyring: RwLock::new(Keyring {
+                current: 1,
+                keys: BTreeMap::from([(1, random_key())]),
+            }),
+            path: None,
+        }
+    }
+
+    /// Version of the key that seals new values.
+    pub fn current_version(&self) -> u32 {
+        self.keyring.read().expect("keyring lock").current
+    }
+
+    /// Seal `plaintext` with the current key.
+    pub fn seal(&self, plaintext: &str) -> AppResult<String> {
+        let keyring = self.keyring.read().expect("keyring lock");
+        let version = keyring.current;
+        let cipher = cipher_for(&keyring, version)?;
+        let tag = format!("{}{}", SEALED_PREFIX, version);
+        let mut nonce = [0u8; NONCE_LEN];
+        rand::thread_rng().fill_bytes(&mut nonce);
+        let ciphertext = cipher
+            .encrypt(
+                XNonce::from_slice(&nonce),
+                Payload {
+                    msg: plaintext.as_bytes(),
+                    aad: tag.as_bytes(),
+                },
+            )
+            .map_err(|_| AppError::Internal("Failed to seal secret".to_string()))?;
+        let mut sealed = nonce.to_vec();
+        sealed.extend_from_slice(&ciphertext);
+        Ok(format!("{}:{}", tag, BASE64.encode(sealed)))
+    }
+
+    /// Open a value produced by [`Self::seal`] with any key still in the
+    /// keyring. Fails if the value was tampered with or its key is retired.
+    pub fn open(&self, sealed: &str) -> AppResult<String> {
+        let (version, payload) = parse_sealed(sealed)
+            .ok_or_else(|| AppError::Internal("Stored secret is not sealed".to_string()))?;
+        let keyring = self.keyring.read().expect("keyring lock");
+        let cipher = cipher_for(&keyring, version)?;
+        let bytes = BASE64
+            .decode(payload)
+            .map_err(|_| AppError::Internal("Stored secret is corrupted".to_string()))?;
+        if bytes.len() < NONCE_LEN {
+            return Err(AppError::Internal("Stored secret is corrupted".to_string()));
+        }
+        let (nonce, ciphertext) = bytes.split_at(NONCE_LEN);
+        let tag = format!("{}{}", SEALED_PREFIX, version);
+        let plaintext = cipher
+            .decrypt(
+                XNonce::from_slice(nonce),
+                Payload {
+                    msg: ciphertext,
+                    aad: tag.as_bytes(),
+                },
+            )
+            .map_err(|_| AppError::Internal("Stored secret failed authentication".to_string()))?;
+        String::from_utf8(plaintext)
+            .map_err(|_| AppError::Internal("Stored secret is corrupted".to_string()))
+    }
+
+    /// Whether `value` is in the vault format (of any key version).
+    pub fn is_sealed(value: &str) -> bool {
+        parse_sealed(value).is_some()
+    }
+
+    /// Whether `value` should be rewritten: it is not sealed yet, or was
+    /// sealed with a key other than the current one.
+    pub fn needs_reseal(&self, value: &str) -> bool {
+        parse_sealed(value).map(|(version, _)| version) != Some(self.current_version())
+    }
+
+    /// Add a new master key and make it current. Older keys stay available
+    /// to [`Self::open`] until [`Self::retire_old_keys`].
+    pub fn rotate(&self) -> AppResult<u32> {
+        let mut keyring = self.keyring.write().expect("keyring lock");
+        let previous = keyring.current;
+        let version = keyring.keys.keys().max().copied().unwrap_or(0) + 1;
+        keyring.keys.insert(version, random_key());
+        keyring.current = version;
+        if let Err(error) = self.persist(&keyring) {
+            keyring.keys.remove(&version);
+            keyring.current = previous;
+            return Err(error);
+        }
+        tracing::info!(key_version = version, "Rotated secrets master key");
+        Ok(version)
+    }
+
+    /// Drop every key but the current one. Only call once all stored values
+    /// have been resealed, or they can no longer be opened.
+    pub fn retire_old_keys(&self) -> AppResult<usize> {
+        let mut keyring = self.keyring.write().expect("keyring lock");
+        let current = keyring.current;
+        let retired: Vec<(u32, [u8; 32])> = keyring
+            .keys
+            .iter()
+            .filter(|(version, _)| **version != current)
+            .map(|(version, key)| (*version, *key))
+            .collect();
+        keyring.keys.retain(|version, _| *version == current);
+        if let Err(error) = self.persist(&keyring) {
+            keyring.keys.extend(retired);
+            return Err(error);
+        }
+        Ok(retired.len())
+    }
+
+    fn persist(&self, keyring: &Keyring) -> AppResult<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let file = KeyringFile {
+            current: keyring.current,
+            keys: keyring
+                .keys
+                .iter()
+                .map(|(version, key)| (*version, BASE64.encode(key)))
+                .collect(),
+        };
+        let json = serde_json::to_vec_pretty(&file)
+            .map_err(|error| AppError::Internal(error.to_string()))?;
+        // Write then rename so a crash never leaves a truncated keyring.
+        let staging = path.with_extension("keyring.tmp");
+        write_private(&staging, &json)?;
+        std::fs::rename(&staging, path)
+            .map_err(|error| AppError::Io(format!("Failed to save secrets keyring: {}", error)))
+    }
+}
+
+static SECRETS_VAULT: OnceLock<Arc<SecretsVault>> = OnceLock::new();
+
+/// Register the process-global vault.
+///
+/// Called once at startup, before migrations run (see `main`). Subsequent
+/// calls are ignored, like `set_global_event_bus`.
+pub fn set_secrets_vault(vault: Arc<SecretsVault>) {
+    if SECRETS_VAULT.set(vault).is_err() {
+        tracing::warn!("set_secrets_vault called twice — keeping the first vault");
+    }
+}
+
+/// Install an ephemeral vault unless one is installed already. For throwaway
+/// databases only: nothing it seals survives a restart.
+pub fn install_ephemeral_secrets_vault() {
+    SECRETS_VAULT.get_or_init(|| Arc::new(SecretsVault::ephemeral()));
+}
+
+/// The process-global vault.
+///
+/// # Panics
+///
+/// If startup has not installed it yet: falling back to a made-up key would
+/// seal credentials that can never be opened after a restart.
+#[cfg(not(test))]
+pub fn secrets_vault() -> Arc<SecretsVault> {
+    SECRETS_VAULT.get().cloned().expect(
+        "Secrets vault used before set_secrets_vault: it must be installed at startup, \
+         before migrations run",
+    )
+}
+
+/// The process-global vault; unit tests get an ephemeral one.
+#[cfg(test)]
+pub fn secrets_vault() -> Arc<SecretsVault> {
+    SECRETS_VAULT
+        .get_or_init(|| Arc::new(SecretsVault::ephemeral()))
+        .clone()
+}
+
+/// Where a data backup at `backup_path` keeps its keyring:
+/// `rpma-backup.db` → `rpma-backup.db.keyring`.
+pub fn backup_keyring_path(backup_path: &Path) -> PathBuf {
+    let mut path = backup_path.as_os_str().to_owned();
+    path.push(".keyring");
+    PathBuf::from(path)
+}
+
+/// Check that `path` holds a well-formed keyring, without installing it.
+pub fn validate_keyring_file(path: &Path) -> AppResult<()> {
+    read_keyring(path).map(|_| ())
+}
+
+fn random_key() -> [u8; 32] {
+    let mut key = [0u8; 32];
+    rand::rngs::OsRng.fill_bytes(&mut key);
+    key
+}
+
+/// The data key for `version`: HMAC-SHA256 of a fixed label under the master
+/// key, so the master key itself never keys the cipher.
+fn cipher_for(keyring: &Keyring, version: u32) -> AppResult<XChaCha20Poly1305> {
+    let master = keyring.keys.get(&version).ok_or_else(|| {
+        AppError::Configuration(format!(
+            "Secrets key version {} is not in the keyring",
+            version
+        ))
+    })?;
+    let mut mac =
+        <HmacSha256 as Mac>::new_from_slice(master).expect("HMAC accepts keys of any length");
+    mac.update(b"rpma secrets vault data key");
+    let data_key = mac.finalize().into_bytes();
+    Ok(XChaCha20Poly1305::new(Key::from_slice(&data_key)))
+}
+
+fn parse_sealed(value: &str) -> Option<(u32, &str)> {
+    let (version, payload) = value.strip_prefix(SEALED_PREFIX)?.split_once(':')?;
+    Some((version.parse().ok()?, payload))
+}
+
+fn read_keyring(path: &Path) -> AppResult<Keyring> {
+    let raw = std::fs::read(path)
+        .map_err(|error| AppError::Io(format!("Failed to read secrets keyring: {}", error)))?;
+    let file: KeyringFile = serde_json::from_slice(&raw).map_err(|error| {
+        AppError::Configuration(format!("Secrets keyring is corrupted: {}", error))
+    })?;
+    let mut keys = BTreeMap::new();
+    for (version, encoded) in file.keys {
+        let key: [u8; 32] = BASE64
+            .decode(encoded)
+            .ok()
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or_else(|| {
+                AppError::Configuration(format!(
+                    "Secrets keyring key version {} is corrupted",
+                    version
+                ))
+            })?;
+        keys.insert(version, key);
+    }
+    if !keys.contains_key(&file.current) {
+        return Err(AppError::Configuration(
+            "Secrets keyring has no current key".to_string(),
+        ));
+    }
+    Ok(Keyring {
+        current: file.current,
+        keys,
+    })
+}
+
+fn write_private(path: &Path, contents: &[u8]) -> AppResult<()> {
+    use std::io::Write;
+
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options
+        .open(path)
+        .and_then(|mut file| {
+            file.write_all(contents)?;
+            file.sync_all()
+        })
+        .map_err(|error| AppError::Io(format!("Failed to save secrets keyring: {}", error)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sealed_values_open_and_reject_tampering() {
+        let vault = SecretsVault::ephemeral();
+        let sealed = vault.seal("smtp-secret").expect("seal");
+        assert!(sealed.starts_with("vault:v1:"));
+        assert_ne!(sealed, vault.seal("smtp-secret").expect("seal again"));
+        assert_eq!(vault.open(&sealed).expect("open"), "smtp-secret");
+
+        // Relabelling the key version breaks the associated data.
+        let relabelled = sealed.replacen("vault:v1:", "vault:v2:", 1);
+        assert!(vault.open(&relabelled).is_err());
+        let mut bytes = BASE64.decode(&sealed["vault:v1:".len()..]).unwrap();
+        let last = bytes.len() - 1;
+        bytes[last] ^= 1;
+        let tampered = format!("vault:v1:{}", BASE64.encode(bytes));
+        assert!(vault.open(&tampered).is_err());
+        assert!(vault.open("plain-text").is_err());
+        assert!(SecretsVault::ephemeral().open(&sealed).is_err());
+    }
+
+    #[test]
+    fn test_rotation_keeps_old_values_readable_until_retired() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join(KEYRING_FILE_NAME);
+        let vault = SecretsVault::load_or_create(&path).expect("create keyring");
+        let old = vault.seal("api-key").expect("seal");
+
+        assert_eq!(vault.rotate().expect("rotate"), 2);
+        assert!(vault.needs_reseal(&old));
+        assert!(vault.needs_reseal("plain-text"));
+        assert_eq!(vault.open(&old).expect("old key still opens"), "api-key");
+        let resealed = vault.seal("api-key").expect("reseal");
+        assert!(!vault.needs_reseal(&resealed));
+
+        // The keyring on disk matches: a restart opens both values.
+        let reloaded = SecretsVault::load_or_create(&path).expect("reload keyring");
+        assert_eq!(reloaded.current_version(), 2);
+        assert_eq!(reloaded.open(&old).expect("open old"), "api-key");
+
+        assert_eq!(vault.retire_old_keys().expect("retire"), 1);
+        assert!(vault.open(&old).is_err());
+        assert_eq!(vault.open(&resealed).expect("open new"), "api-key");
+        let reloaded = SecretsVault::load_or_create(&path).expect("reload keyring");
+        assert!(reloaded.open(&old).is_err());
+    }
+}
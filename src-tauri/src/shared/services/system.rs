@@ -1,6 +1,8 @@
 //! System service - Business logic for system diagnostics and database operations
 
+use crate::shared::db::secrets_repository::SecretsRepository;
 use crate::shared::db::system_repository::SystemRepository;
+use crate::shared::services::secrets_vault::secrets_vault;
 
 /// System service providing business logic for database health monitoring
 /// and diagnostics operations such as integrity checks, WAL checkpoint
@@ -43,21 +45,48 @@ impl SystemService {
         SystemRepository::get_database_stats(pool)
     }
 
-    /// Export the live database to `dest_path` after a FULL WAL checkpoint.
+    /// Export the live database to `dest_path` after a FULL WAL checkpoint,
+    /// with the secrets keyring saved next to it.
     pub fn export_data_backup(
         pool: &r2d2::Pool<r2d2_sqlite::SqliteConnectionManager>,
         db_path: &std::path::Path,
+        keyring_path: &std::path::Path,
         dest_path: &str,
     ) -> Result<serde_json::Value, String> {
-        SystemRepository::export_data_backup(pool, db_path, dest_path)
+        SystemRepository::export_data_backup(pool, db_path, keyring_path, dest_path)
     }
 
-    /// Stage a restore: validate `source_path` as SQLite then copy to `staged_path`.
+    /// Stage a restore: validate `source_path` as SQLite then copy to
+    /// `staged_path`, staging its keyring too. Returns whether it had one.
     pub fn stage_restore_backup(
         source_path: &str,
         staged_path: &std::path::Path,
-    ) -> Result<(), String> {
-        SystemRepository::stage_restore_backup(source_path, staged_path)
+        staged_keyring_path: &std::path::Path,
+    ) -> Result<bool, String> {
+        SystemRepository::stage_restore_backup(source_path, staged_path, staged_keyring_path)
+    }
+
+    /// Rotate the secrets vault key: add a new key, reseal every stored
+    /// credential with it, then retire the old keys. If resealing fails the
+    /// old keys are kept, so nothing becomes unreadable. Pre-vault secrets
+    /// that cannot be decoded are listed under `undecodable`.
+    pub fn rotate_secrets_key(
+        pool: &r2d2::Pool<r2d2_sqlite::SqliteConnectionManager>,
+    ) -> Result<serde_json::Value, String> {
+        let vault = secrets_vault();
+        let key_version = vault.rotate().map_err(|e| e.to_string())?;
+        let conn = pool
+            .get()
+            .map_err(|e| format!("Failed to get connection: {}", e))?;
+        let report = SecretsRepository::reseal_all(&conn, &vault)?;
+        let retired = vault.retire_old_keys().map_err(|e| e.to_string())?;
+
+        Ok(serde_json::json!({
+            "key_version": key_version,
+            "resealed": report.resealed,
+            "undecodable": report.undecodable,
+            "retired_keys": retired
+        }))
     }
 
     /// Get database status (initialisation, tables, version) via the Database
@@ -1,2 +1,3 @@
+pub mod money;
 pub mod sql;
 pub mod uuid;
@@ -0,0 +1,70 @@
+//! Integer-cent money arithmetic shared by quotes and invoices.
+//!
+//! All amounts are **integer cents** (EUR × 100). ROUND_HALF_UP (`f64::round`)
+//! is applied once per computed amount — never on running totals — so a
+//! document recomputed from its lines always yields the same figures.
+
+/// Line total for `qty` units at `unit_price` cents.
+pub fn line_total_cents(qty: f64, unit_price: i64) -> i64 {
+    (qty * unit_price as f64).round() as i64
+}
+
+/// Tax on `amount` cents at `tax_rate` percent (e.g. `20.0`).
+pub fn tax_cents(amount: i64, tax_rate: f64) -> i64 {
+    (amount as f64 * tax_rate / 100.0).round() as i64
+}
+
+/// `amount × part / whole`, rounded. A non-positive `whole` is treated as 1.
+pub fn prorate_cents(amount: i64, part: i64, whole: i64) -> i64 {
+    (amount as f64 * part as f64 / whole.max(1) as f64).round() as i64
+}
+
+/// `123456` cents → `1 234,56 €`. Plain spaces keep SMS bodies in GSM-7.
+pub fn format_eur(cents: i64) -> String {
+    let sign = if cents < 0 { "-" } else { "" };
+    let abs = cents.unsigned_abs();
+    let euros = (abs / 100).to_string();
+    let mut grouped = String::with_capacity(euros.len() + euros.len() / 3);
+    for (index, digit) in euros.chars().enumerate() {
+        if index > 0 && (euros.len() - index) % 3 == 0 {
+            grouped.push(' ');
+        }
+        grouped.push(digit);
+    }
+    format!("{}{},{:02} €", sign, grouped, abs % 100)
+}
+
+/// `123456` cents → `1234.56`: the plain decimal form structured exports
+/// (e-invoice XML) expect.
+pub fn format_decimal(cents: i64) -> String {
+    let sign = if cents < 0 { "-" } else { "" };
+    let abs = cents.unsigned_abs();
+    format!("{}{}.{:02}", sign, abs / 100, abs % 100)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_decimal, line_total_cents, prorate_cents, tax_cents};
+
+    #[test]
+    fn rounds_half_up_per_computation() {
+        assert_eq!(line_total_cents(1.5, 333), 500);
+        assert_eq!(line_total_cents(3.0, 1999), 5997);
+        assert_eq!(tax_cents(5997, 20.0), 1199);
+        assert_eq!(tax_cents(1005, 5.5), 55);
+    }
+
+    #[test]
+    fn prorates_against_whole_and_guards_zero() {
+        assert_eq!(prorate_cents(1000, 900, 1000), 900);
+        assert_eq!(prorate_cents(333, 1, 3), 111);
+        assert_eq!(prorate_cents(500, 0, 0), 0);
+    }
+
+    #[test]
+    fn formats_plain_decimals() {
+        assert_eq!(format_decimal(123456), "1234.56");
+        assert_eq!(format_decimal(5), "0.05");
+        assert_eq!(format_decimal(-1050), "-10.50");
+    }
+}
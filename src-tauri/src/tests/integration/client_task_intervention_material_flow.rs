@@ -292,7 +292,7 @@ impl ClientTaskInterventionMaterialFlowTestFixture {
                 };
 
                 self.material_service
-                    .record_consumption(consumption_request)?;
+                    .record_consumption(consumption_request, "test-correlation")?;
             }
 
             // Complete the step
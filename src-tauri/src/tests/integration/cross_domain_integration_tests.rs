@@ -314,7 +314,7 @@ impl CrossDomainTestFixture {
                 };
 
                 self.material_service
-                    .record_consumption(consumption_request)?;
+                    .record_consumption(consumption_request, "test-correlation")?;
             }
 
             // Complete the step
@@ -448,7 +448,7 @@ impl CrossDomainTestFixture {
             };
 
             self.material_service
-                .record_consumption(consumption_request)?;
+                .record_consumption(consumption_request, "test-correlation")?;
         }
 
         // Check remaining stock
@@ -465,20 +465,21 @@ impl CrossDomainTestFixture {
 
             // This might still succeed (intervention creation), but material consumption should fail
             if let Ok(intervention) = intervention_result {
-                let consumption_result =
-                    self.material_service
-                        .record_consumption(RecordConsumptionRequest {
-                            intervention_id: intervention.id.clone(),
-                            material_id: film.id.clone().unwrap(),
-                            step_id: Some(intervention.steps[0].id.clone()),
-                            step_number: Some(1),
-                            quantity_used: 5.0, // More than remaining stock
-                            waste_quantity: Some(0.5),
-                            waste_reason: Some("Should fail".to_string()),
-                            batch_used: Some("BATCH-SHOULD-FAIL".to_string()),
-                            quality_notes: Some("Should fail".to_string()),
-                            recorded_by: Some("should_fail_technician".to_string()),
-                        });
+                let consumption_result = self.material_service.record_consumption(
+                    RecordConsumptionRequest {
+                        intervention_id: intervention.id.clone(),
+                        material_id: film.id.clone().unwrap(),
+                        step_id: Some(intervention.steps[0].id.clone()),
+                        step_number: Some(1),
+                        quantity_used: 5.0, // More than remaining stock
+                        waste_quantity: Some(0.5),
+                        waste_reason: Some("Should fail".to_string()),
+                        batch_used: Some("BATCH-SHOULD-FAIL".to_string()),
+                        quality_notes: Some("Should fail".to_string()),
+                        recorded_by: Some("should_fail_technician".to_string()),
+                    },
+                    "test-correlation",
+                );
 
                 assert!(
                     consumption_result.is_err(),
@@ -885,7 +886,7 @@ mod tests {
 
         let consumption_result = fixture
             .material_service
-            .record_consumption(consumption_request);
+            .record_consumption(consumption_request, "test-correlation");
         assert!(
             consumption_result.is_err(),
             "Should fail with insufficient stock"
@@ -913,7 +914,7 @@ mod tests {
 
         let valid_consumption_result = fixture
             .material_service
-            .record_consumption(valid_consumption_request);
+            .record_consumption(valid_consumption_request, "test-correlation");
         assert!(
             valid_consumption_result.is_ok(),
             "Should succeed with valid amount"
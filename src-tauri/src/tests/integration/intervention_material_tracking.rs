@@ -269,7 +269,7 @@ impl InterventionMaterialTrackingTestFixture {
         };
 
         self.material_service
-            .record_consumption(cleaner_consumption)?;
+            .record_consumption(cleaner_consumption, "test-correlation")?;
 
         // Complete the step with photos
         let complete_prep_request = AdvanceStepRequest {
@@ -324,7 +324,8 @@ impl InterventionMaterialTrackingTestFixture {
             recorded_by: Some("test_technician".to_string()),
         };
 
-        self.material_service.record_consumption(film_consumption)?;
+        self.material_service
+            .record_consumption(film_consumption, "test-correlation")?;
 
         // Complete the step with photos
         let complete_film_request = AdvanceStepRequest {
@@ -381,7 +382,7 @@ impl InterventionMaterialTrackingTestFixture {
         };
 
         self.material_service
-            .record_consumption(adhesive_consumption)?;
+            .record_consumption(adhesive_consumption, "test-correlation")?;
 
         // Complete the step with photos
         let complete_seal_request = AdvanceStepRequest {
@@ -529,7 +530,7 @@ mod tests {
 
         let consumption = fixture
             .material_service
-            .record_consumption(consumption_request)?;
+            .record_consumption(consumption_request, "test-correlation")?;
 
         // Verify waste details are properly recorded
         assert_eq!(consumption.waste_quantity, 1.2);
@@ -692,7 +693,7 @@ mod tests {
 
         fixture
             .material_service
-            .record_consumption(consumption_request)?;
+            .record_consumption(consumption_request, "test-correlation")?;
 
         // Complete step with photos
         let photo_urls = vec![
@@ -792,7 +793,7 @@ mod tests {
 
         let film_consumption = fixture
             .material_service
-            .record_consumption(film_consumption_request)?;
+            .record_consumption(film_consumption_request, "test-correlation")?;
 
         // Record adhesive consumption with expiry tracking
         let adhesive_consumption_request = RecordConsumptionRequest {
@@ -810,7 +811,7 @@ mod tests {
 
         let adhesive_consumption = fixture
             .material_service
-            .record_consumption(adhesive_consumption_request)?;
+            .record_consumption(adhesive_consumption_request, "test-correlation")?;
 
         // Verify batch tracking for film
         assert_eq!(
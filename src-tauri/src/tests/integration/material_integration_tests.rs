@@ -247,7 +247,7 @@ mod tests {
         };
 
         let consumption1 = material_service
-            .record_consumption(consumption_request1)
+            .record_consumption(consumption_request1, "test-correlation")
             .unwrap();
         assert_eq!(consumption1.material_id, ppf_film.id.unwrap());
         assert_eq!(consumption1.quantity_used, 15.0);
@@ -267,7 +267,7 @@ mod tests {
         };
 
         let consumption2 = material_service
-            .record_consumption(consumption_request2)
+            .record_consumption(consumption_request2, "test-correlation")
             .unwrap();
         assert_eq!(consumption2.material_id, adhesive.id.unwrap());
         assert_eq!(consumption2.quantity_used, 5.0);
@@ -360,7 +360,7 @@ mod tests {
         };
 
         material_service
-            .record_consumption(hood_consumption)
+            .record_consumption(hood_consumption, "test-correlation")
             .unwrap();
 
         // 6. Record material consumption for fender installation
@@ -378,7 +378,7 @@ mod tests {
         };
 
         material_service
-            .record_consumption(fender_consumption)
+            .record_consumption(fender_consumption, "test-correlation")
             .unwrap();
 
         // 7. Record adhesive consumption
@@ -396,7 +396,7 @@ mod tests {
         };
 
         material_service
-            .record_consumption(adhesive_consumption)
+            .record_consumption(adhesive_consumption, "test-correlation")
             .unwrap();
 
         // 8. Verify stock levels
@@ -415,7 +415,7 @@ impl NetworkResilienceTestFixture {
 
         match self
             .material_service
-            .record_consumption(over_consumption_request)
+            .record_consumption(over_consumption_request, "test-correlation")
         {
             Err(_) => {
                 // Expected to fail
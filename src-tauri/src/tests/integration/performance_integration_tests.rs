@@ -338,7 +338,7 @@ impl PerformanceTestFixture {
 
                 match self
                     .material_service
-                    .record_consumption(consumption_request)
+                    .record_consumption(consumption_request, "test-correlation")
                 {
                     Ok(_) => {
                         total_consumed += 2.0;
@@ -855,7 +855,7 @@ mod tests {
 
                 if fixture
                     .material_service
-                    .record_consumption(consumption_request)
+                    .record_consumption(consumption_request, "test-correlation")
                     .is_ok()
                 {
                     // Finalize intervention
@@ -223,7 +223,7 @@ impl TaskMaterialTestFixture {
                 };
 
                 self.material_service
-                    .record_consumption(consumption_request)?;
+                    .record_consumption(consumption_request, "test-correlation")?;
             }
 
             // Complete the step
@@ -375,7 +375,7 @@ mod tests {
 
         fixture
             .material_service
-            .record_consumption(film_consumption)?;
+            .record_consumption(film_consumption, "test-correlation")?;
 
         let adhesive_consumption = RecordConsumptionRequest {
             intervention_id: intervention.id.clone(),
@@ -392,7 +392,7 @@ mod tests {
 
         fixture
             .material_service
-            .record_consumption(adhesive_consumption)?;
+            .record_consumption(adhesive_consumption, "test-correlation")?;
 
         // Verify stock levels were properly updated
         let updated_film = fixture
@@ -452,7 +452,7 @@ mod tests {
 
         let consumption = fixture
             .material_service
-            .record_consumption(consumption_request)?;
+            .record_consumption(consumption_request, "test-correlation")?;
 
         // Verify consumption record details
         assert_eq!(consumption.intervention_id, intervention.id);
@@ -519,7 +519,7 @@ mod tests {
         // Attempt to record consumption should fail
         let result = fixture
             .material_service
-            .record_consumption(consumption_request);
+            .record_consumption(consumption_request, "test-correlation");
         assert!(result.is_err());
         assert!(result
             .unwrap_err()
@@ -615,8 +615,12 @@ mod tests {
         };
 
         // Record both consumptions
-        fixture.material_service.record_consumption(consumption1)?;
-        fixture.material_service.record_consumption(consumption2)?;
+        fixture
+            .material_service
+            .record_consumption(consumption1, "test-correlation")?;
+        fixture
+            .material_service
+            .record_consumption(consumption2, "test-correlation")?;
 
         // Verify stock levels were updated correctly for both materials
         let updated_material1 = fixture
@@ -510,7 +510,7 @@ mod tests {
             recorded_by: Some("test_user".to_string()),
         };
 
-        let result = service.record_consumption(request);
+        let result = service.record_consumption(request, "test-correlation");
         assert!(result.is_ok());
 
         let consumption = result.unwrap();
@@ -522,6 +522,64 @@ mod tests {
         assert_eq!(consumption.recorded_by, Some("test_user".to_string()));
     }
 
+    #[test]
+    fn test_record_consumption_blocking_rule_sees_request_correlation_id() {
+        use crate::shared::contracts::rules_engine::{
+            BlockingRuleEngine, RuleCheckOutcome, RuleCheckRequest,
+        };
+        use crate::shared::error::AppError;
+        use std::sync::Arc;
+
+        struct DenyConsumption;
+
+        #[async_trait::async_trait]
+        impl BlockingRuleEngine for DenyConsumption {
+            async fn evaluate(
+                &self,
+                request: &RuleCheckRequest,
+            ) -> Result<RuleCheckOutcome, AppError> {
+                Ok(RuleCheckOutcome {
+                    allowed: false,
+                    matched_rule_ids: Vec::new(),
+                    message: Some(format!(
+                        "{} blocked ({})",
+                        request.trigger, request.correlation_id
+                    )),
+                })
+            }
+        }
+
+        let test_db = TestDatabase::new().expect("Failed to create test database");
+        let service =
+            MaterialService::new(test_db.db()).with_rules_engine(Arc::new(DenyConsumption));
+        let material = create_test_material_with_stock(&service, "CONSUME-RULE", 100.0);
+
+        let request = RecordConsumptionRequest {
+            intervention_id: "INT-001".to_string(),
+            material_id: material.id.clone(),
+            step_id: None,
+            step_number: None,
+            quantity_used: 10.0,
+            waste_quantity: None,
+            waste_reason: None,
+            batch_used: None,
+            quality_notes: None,
+            recorded_by: Some("test_user".to_string()),
+        };
+
+        match service
+            .record_consumption(request, "corr-consume")
+            .unwrap_err()
+        {
+            MaterialError::Validation(msg) => {
+                assert_eq!(msg, "material_consumed blocked (corr-consume)");
+            }
+            other => panic!("Expected Validation error, got {:?}", other),
+        }
+        let unchanged = service.get_material(&material.id).unwrap().unwrap();
+        assert_eq!(unchanged.current_stock, 100.0);
+    }
+
     #[test]
     fn test_record_consumption_material_not_found() {
         let test_db = TestDatabase::new().expect("Failed to create test database");
@@ -540,7 +598,7 @@ mod tests {
             recorded_by: Some("test_user".to_string()),
         };
 
-        let result = service.record_consumption(request);
+        let result = service.record_consumption(request, "test-correlation");
         assert!(result.is_err());
 
         match result.unwrap_err() {
@@ -581,7 +639,7 @@ mod tests {
             recorded_by: Some("test_user".to_string()),
         };
 
-        let result = service.record_consumption(consume_request);
+        let result = service.record_consumption(consume_request, "test-correlation");
         assert!(result.is_err());
 
         match result.unwrap_err() {
@@ -612,7 +670,7 @@ mod tests {
             recorded_by: Some("test_user".to_string()),
         };
 
-        let result = service.record_consumption(request);
+        let result = service.record_consumption(request, "test-correlation");
         assert!(result.is_err());
 
         match result.unwrap_err() {
@@ -650,7 +708,9 @@ mod tests {
             quality_notes: Some("Good quality".to_string()),
             recorded_by: Some("test_user".to_string()),
         };
-        service.record_consumption(request1).unwrap();
+        service
+            .record_consumption(request1, "test-correlation")
+            .unwrap();
 
         let request2 = RecordConsumptionRequest {
             intervention_id: "INT-001".to_string(),
@@ -664,7 +724,9 @@ mod tests {
             quality_notes: None,
             recorded_by: Some("test_user".to_string()),
         };
-        service.record_consumption(request2).unwrap();
+        service
+            .record_consumption(request2, "test-correlation")
+            .unwrap();
 
         // Get consumption for intervention
         let result = service.get_intervention_consumption("INT-001");
@@ -695,7 +757,9 @@ mod tests {
             quality_notes: Some("Good quality".to_string()),
             recorded_by: Some("test_user".to_string()),
         };
-        service.record_consumption(request1).unwrap();
+        service
+            .record_consumption(request1, "test-correlation")
+            .unwrap();
 
         let request2 = RecordConsumptionRequest {
             intervention_id: "INT-001".to_string(),
@@ -709,7 +773,9 @@ mod tests {
             quality_notes: None,
             recorded_by: Some("test_user".to_string()),
         };
-        service.record_consumption(request2).unwrap();
+        service
+            .record_consumption(request2, "test-correlation")
+            .unwrap();
 
         // Get material summary for intervention
         let result = service.get_intervention_material_summary("INT-001");
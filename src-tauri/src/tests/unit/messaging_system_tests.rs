@@ -56,6 +56,8 @@ async fn create_test_db() -> Database {
             metadata TEXT,
             created_at INTEGER NOT NULL DEFAULT (strftime('%s', 'now')),
             updated_at INTEGER NOT NULL DEFAULT (strftime('%s', 'now')),
+            attempts INTEGER NOT NULL DEFAULT 0,
+            next_attempt_at INTEGER,
             FOREIGN KEY (sender_id) REFERENCES users(id) ON DELETE SET NULL,
             FOREIGN KEY (recipient_id) REFERENCES users(id) ON DELETE CASCADE,
             FOREIGN KEY (template_id) REFERENCES message_templates(id) ON DELETE SET NULL
@@ -653,7 +655,10 @@ async fn test_unsent_messages_filter() {
     }
 
     // Find unsent messages (pending or failed)
-    let unsent_messages = repo.find_unsent().await.unwrap();
+    let unsent_messages = repo
+        .find_unsent(chrono::Utc::now().timestamp_millis(), 5)
+        .await
+        .unwrap();
 
     // Should include pending and failed messages
     assert_eq!(unsent_messages.len(), 2);
@@ -629,10 +629,12 @@ mod quote_invariants {
             .add_item(&quote.id, labour_item(), &UserRole::Admin)
             .expect("add item");
 
-        let result = app
-            .state
-            .quote_service
-            .mark_sent(&quote.id, &UserRole::Admin);
+        let result = app.state.quote_service.mark_sent(
+            &quote.id,
+            "test-user",
+            &UserRole::Admin,
+            "test-correlation",
+        );
 
         assert!(result.is_ok(), "expected Ok, got: {:?}", result);
         assert_eq!(result.unwrap().status, QuoteStatus::Sent);
@@ -652,7 +654,7 @@ mod quote_invariants {
         let err = app
             .state
             .quote_service
-            .mark_sent(&quote.id, &UserRole::Admin)
+            .mark_sent(&quote.id, "test-user", &UserRole::Admin, "test-correlation")
             .unwrap_err();
 
         // Must complain about missing items or zero total
@@ -681,13 +683,13 @@ mod quote_invariants {
             .expect("add item");
         app.state
             .quote_service
-            .mark_sent(&quote.id, &UserRole::Admin)
+            .mark_sent(&quote.id, "test-user", &UserRole::Admin, "test-correlation")
             .expect("first mark_sent");
 
         let err = app
             .state
             .quote_service
-            .mark_sent(&quote.id, &UserRole::Admin)
+            .mark_sent(&quote.id, "test-user", &UserRole::Admin, "test-correlation")
             .unwrap_err();
 
         assert!(
@@ -715,13 +717,15 @@ mod quote_invariants {
             .expect("add item");
         app.state
             .quote_service
-            .mark_sent(&quote.id, &UserRole::Admin)
+            .mark_sent(&quote.id, "test-user", &UserRole::Admin, "test-correlation")
             .expect("mark_sent");
 
-        let result =
-            app.state
-                .quote_service
-                .mark_accepted(&quote.id, "test-user", &UserRole::Admin);
+        let result = app.state.quote_service.mark_accepted(
+            &quote.id,
+            "test-user",
+            &UserRole::Admin,
+            "test-correlation",
+        );
 
         assert!(result.is_ok(), "expected Ok, got: {:?}", result);
         assert_eq!(result.unwrap().quote.status, QuoteStatus::Accepted);
@@ -741,7 +745,7 @@ mod quote_invariants {
         let err = app
             .state
             .quote_service
-            .mark_accepted(&quote.id, "test-user", &UserRole::Admin)
+            .mark_accepted(&quote.id, "test-user", &UserRole::Admin, "test-correlation")
             .unwrap_err();
 
         assert!(
@@ -767,7 +771,7 @@ mod quote_invariants {
             .expect("add item");
         app.state
             .quote_service
-            .mark_sent(&quote.id, &UserRole::Admin)
+            .mark_sent(&quote.id, "test-user", &UserRole::Admin, "test-correlation")
             .expect("mark_sent");
 
         let result =
@@ -853,7 +857,7 @@ mod quote_invariants {
             .expect("add item");
         app.state
             .quote_service
-            .mark_sent(&quote.id, &UserRole::Admin)
+            .mark_sent(&quote.id, "test-user", &UserRole::Admin, "test-correlation")
             .expect("mark_sent");
 
         let update = UpdateQuoteRequest {
@@ -917,7 +921,7 @@ mod quote_invariants {
             .expect("add item");
         app.state
             .quote_service
-            .mark_sent(&quote.id, &UserRole::Admin)
+            .mark_sent(&quote.id, "test-user", &UserRole::Admin, "test-correlation")
             .expect("mark_sent");
 
         let err = app
@@ -993,11 +997,11 @@ mod quote_invariants {
             .expect("add item");
         app.state
             .quote_service
-            .mark_sent(&quote.id, &UserRole::Admin)
+            .mark_sent(&quote.id, "test-user", &UserRole::Admin, "test-correlation")
             .expect("mark_sent");
         app.state
             .quote_service
-            .mark_accepted(&quote.id, "test-user", &UserRole::Admin)
+            .mark_accepted(&quote.id, "test-user", &UserRole::Admin, "test-correlation")
             .expect("mark_accepted");
 
         let result = app.state.quote_service.convert_to_task(
@@ -1032,7 +1036,7 @@ mod quote_invariants {
             .expect("add item");
         app.state
             .quote_service
-            .mark_sent(&quote.id, &UserRole::Admin)
+            .mark_sent(&quote.id, "test-user", &UserRole::Admin, "test-correlation")
             .expect("mark_sent");
 
         let count_before: i64 = app
@@ -119,18 +119,21 @@ async fn finalize_intervention_emits_event_and_inventory_updates() {
         .expect("seed stock");
 
     material_service
-        .record_consumption(RecordConsumptionRequest {
-            intervention_id: start_response.intervention.id.clone(),
-            material_id: material.id.clone().unwrap(),
-            step_id: start_response.steps.first().map(|step| step.id.clone()),
-            step_number: start_response.steps.first().map(|step| step.step_number),
-            quantity_used: 2.0,
-            waste_quantity: Some(0.5),
-            waste_reason: Some("Cutoff".to_string()),
-            batch_used: None,
-            quality_notes: None,
-            recorded_by: Some("test_user".to_string()),
-        })
+        .record_consumption(
+            RecordConsumptionRequest {
+                intervention_id: start_response.intervention.id.clone(),
+                material_id: material.id.clone().unwrap(),
+                step_id: start_response.steps.first().map(|step| step.id.clone()),
+                step_number: start_response.steps.first().map(|step| step.step_number),
+                quantity_used: 2.0,
+                waste_quantity: Some(0.5),
+                waste_reason: Some("Cutoff".to_string()),
+                batch_used: None,
+                quality_notes: None,
+                recorded_by: Some("test_user".to_string()),
+            },
+            "test-correlation",
+        )
         .expect("record consumption");
 
     for step in &start_response.steps {
@@ -293,18 +296,21 @@ async fn finalize_intervention_rollback_does_not_update_inventory() {
         .expect("seed stock");
 
     let consumption = material_service
-        .record_consumption(RecordConsumptionRequest {
-            intervention_id: start_response.intervention.id.clone(),
-            material_id: material.id.clone().unwrap(),
-            step_id: start_response.steps.first().map(|step| step.id.clone()),
-            step_number: start_response.steps.first().map(|step| step.step_number),
-            quantity_used: 1.0,
-            waste_quantity: Some(0.0),
-            waste_reason: None,
-            batch_used: None,
-            quality_notes: None,
-            recorded_by: Some("test_user".to_string()),
-        })
+        .record_consumption(
+            RecordConsumptionRequest {
+                intervention_id: start_response.intervention.id.clone(),
+                material_id: material.id.clone().unwrap(),
+                step_id: start_response.steps.first().map(|step| step.id.clone()),
+                step_number: start_response.steps.first().map(|step| step.step_number),
+                quantity_used: 1.0,
+                waste_quantity: Some(0.0),
+                waste_reason: None,
+                batch_used: None,
+                quality_notes: None,
+                recorded_by: Some("test_user".to_string()),
+            },
+            "test-correlation",
+        )
         .expect("record consumption");
 
     for step in &start_response.steps {
@@ -224,10 +224,20 @@ async fn quote_to_task_conversion_updates_inventory_and_audit() {
         .expect("create task");
 
     quote_service
-        .mark_sent(&quote.id, &admin_role)
+        .mark_sent(
+            &quote.id,
+            tester_id.as_str(),
+            &admin_role,
+            "test-correlation",
+        )
         .expect("mark quote sent");
     quote_service
-        .mark_accepted(&quote.id, tester_id.as_str(), &admin_role)
+        .mark_accepted(
+            &quote.id,
+            tester_id.as_str(),
+            &admin_role,
+            "test-correlation",
+        )
         .expect("mark quote accepted");
     let conversion = quote_service
         .convert_to_task(
@@ -272,18 +282,21 @@ async fn quote_to_task_conversion_updates_inventory_and_audit() {
         .expect("start intervention");
 
     material_service
-        .record_consumption(RecordConsumptionRequest {
-            intervention_id: started.intervention.id.clone(),
-            material_id: material.id.clone(),
-            step_id: started.steps.first().map(|s| s.id.clone()),
-            step_number: started.steps.first().map(|s| s.step_number),
-            quantity_used: 2.0,
-            waste_quantity: Some(0.0),
-            waste_reason: None,
-            batch_used: None,
-            quality_notes: None,
-            recorded_by: Some(tester_id.clone()),
-        })
+        .record_consumption(
+            RecordConsumptionRequest {
+                intervention_id: started.intervention.id.clone(),
+                material_id: material.id.clone(),
+                step_id: started.steps.first().map(|s| s.id.clone()),
+                step_number: started.steps.first().map(|s| s.step_number),
+                quantity_used: 2.0,
+                waste_quantity: Some(0.0),
+                waste_reason: None,
+                batch_used: None,
+                quality_notes: None,
+                recorded_by: Some(tester_id.clone()),
+            },
+            "test-correlation",
+        )
         .expect("record consumption");
 
     for step in &started.steps {